@@ -0,0 +1,179 @@
+// Esplora REST chain source (mempool.space, blockstream.info or a self-hosted electrs)
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use reqwest::{Client, ClientBuilder};
+use serde::Deserialize;
+
+use crate::api::{
+    errors::{ApiError, ApiResult},
+    traits::onchain::{ChainOutput, ChainSource},
+};
+
+// Esplora endpoints
+const TIP_HEIGHT: &str = "/blocks/tip/height";
+const ADDRESS_TXS: &str = "/address";
+
+#[derive(Debug, Deserialize)]
+struct EsploraTx {
+    txid: String,
+    vout: Vec<EsploraVout>,
+    status: EsploraStatus,
+}
+
+#[derive(Debug, Deserialize)]
+struct EsploraVout {
+    scriptpubkey_address: Option<String>,
+    value: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct EsploraStatus {
+    confirmed: bool,
+    block_height: Option<u32>,
+}
+
+#[derive(Debug, Clone)]
+pub struct EsploraChainSource {
+    client: Client,
+    base_url: String,
+}
+
+impl EsploraChainSource {
+    pub fn new(base_url: &str) -> ApiResult<Self> {
+        let client = ClientBuilder::new()
+            .timeout(Duration::from_secs(30))
+            .build()
+            .map_err(|e| ApiError::Network {
+                message: e.to_string(),
+            })?;
+        Ok(Self {
+            client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+        })
+    }
+
+    async fn get(&self, path: &str) -> ApiResult<reqwest::Response> {
+        let response = self
+            .client
+            .get(format!("{}{}", self.base_url, path))
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(ApiError::Server {
+                message: format!("Esplora returned {} for {}", response.status(), path),
+            });
+        }
+        Ok(response)
+    }
+
+    async fn tip_height(&self) -> ApiResult<u32> {
+        let body = self.get(TIP_HEIGHT).await?.text().await?;
+        body.trim().parse().map_err(|_| ApiError::Serialization {
+            message: format!("Unexpected Esplora tip height '{}'", body.trim()),
+        })
+    }
+}
+
+#[async_trait]
+impl ChainSource for EsploraChainSource {
+    async fn outputs_to(&self, addresses: &[String]) -> ApiResult<Vec<ChainOutput>> {
+        if addresses.is_empty() {
+            return Ok(Vec::new());
+        }
+        let tip = self.tip_height().await?;
+
+        let mut outputs = Vec::new();
+        for address in addresses {
+            let txs: Vec<EsploraTx> = self
+                .get(&format!("{}/{}/txs", ADDRESS_TXS, address))
+                .await?
+                .json()
+                .await
+                .map_err(|e| ApiError::Serialization {
+                    message: format!("Unexpected Esplora response: {}", e),
+                })?;
+
+            for tx in txs {
+                let confirmations = match (tx.status.confirmed, tx.status.block_height) {
+                    (true, Some(height)) => tip.saturating_sub(height) + 1,
+                    _ => 0,
+                };
+                for (vout, output) in tx.vout.iter().enumerate() {
+                    if output.scriptpubkey_address.as_deref() == Some(address.as_str()) {
+                        outputs.push(ChainOutput {
+                            txid: tx.txid.clone(),
+                            vout: vout as u32,
+                            address: address.clone(),
+                            amount_sats: output.value,
+                            confirmations,
+                        });
+                    }
+                }
+            }
+        }
+        Ok(outputs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{routing::get, Json, Router};
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_outputs_and_confirmations_from_esplora() {
+        let router = Router::new()
+            .route(TIP_HEIGHT, get(|| async { "110" }))
+            .route(
+                "/address/{address}/txs",
+                get(|| async {
+                    Json(json!([
+                        {
+                            "txid": "aa",
+                            "vout": [
+                                { "scriptpubkey_address": "bcrt1qchange", "value": 900 },
+                                { "scriptpubkey_address": "bcrt1qours", "value": 25000 }
+                            ],
+                            "status": { "confirmed": true, "block_height": 108 }
+                        },
+                        {
+                            "txid": "bb",
+                            "vout": [{ "scriptpubkey_address": "bcrt1qours", "value": 1000 }],
+                            "status": { "confirmed": false }
+                        }
+                    ]))
+                }),
+            );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+        let source = EsploraChainSource::new(&format!("http://{}/", addr)).unwrap();
+        let outputs = source
+            .outputs_to(&["bcrt1qours".to_string()])
+            .await
+            .unwrap();
+        assert_eq!(
+            outputs,
+            vec![
+                ChainOutput {
+                    txid: "aa".to_string(),
+                    vout: 1,
+                    address: "bcrt1qours".to_string(),
+                    amount_sats: 25_000,
+                    confirmations: 3,
+                },
+                ChainOutput {
+                    txid: "bb".to_string(),
+                    vout: 0,
+                    address: "bcrt1qours".to_string(),
+                    amount_sats: 1_000,
+                    confirmations: 0,
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,118 @@
+// Chain sources for on-chain deposit tracking
+// The Rust backend asks a ChainSource which outputs pay the addresses it handed
+// out; Esplora is used when ESPLORA_URL is set, otherwise the in-memory chain
+
+pub mod esplora;
+
+pub use esplora::EsploraChainSource;
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use crate::api::{
+    errors::{ApiError, ApiResult},
+    traits::onchain::{ChainOutput, ChainSource},
+};
+
+struct MemoryOutput {
+    txid: String,
+    vout: u32,
+    address: String,
+    amount_sats: u64,
+    /// Height of the block that mined it, `None` while in the mempool
+    mined_at: Option<u32>,
+}
+
+#[derive(Default)]
+struct MemoryChain {
+    height: u32,
+    outputs: Vec<MemoryOutput>,
+}
+
+/// A chain kept in memory, for tests and for running without a block explorer.
+/// Payments are "broadcast" into a mempool and confirmed by mining blocks.
+#[derive(Clone, Default)]
+pub struct InMemoryChainSource {
+    chain: Arc<RwLock<MemoryChain>>,
+}
+
+impl InMemoryChainSource {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an unconfirmed payment to `address`, returning its txid
+    pub async fn broadcast(&self, address: &str, amount_sats: u64) -> String {
+        let txid = format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple());
+        self.chain.write().await.outputs.push(MemoryOutput {
+            txid: txid.clone(),
+            vout: 0,
+            address: address.to_string(),
+            amount_sats,
+            mined_at: None,
+        });
+        txid
+    }
+
+    /// Mine `blocks` blocks; the first one includes everything in the mempool
+    pub async fn mine(&self, blocks: u32) {
+        if blocks == 0 {
+            return;
+        }
+        let mut chain = self.chain.write().await;
+        let next = chain.height + 1;
+        for output in chain.outputs.iter_mut().filter(|o| o.mined_at.is_none()) {
+            output.mined_at = Some(next);
+        }
+        chain.height += blocks;
+    }
+
+    /// Remove a transaction, as when it is replaced or evicted from the mempool
+    pub async fn drop_transaction(&self, txid: &str) -> ApiResult<()> {
+        let mut chain = self.chain.write().await;
+        let before = chain.outputs.len();
+        chain.outputs.retain(|output| output.txid != txid);
+        if chain.outputs.len() == before {
+            return Err(ApiError::NotFound {
+                resource: format!("Transaction {}", txid),
+            });
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ChainSource for InMemoryChainSource {
+    async fn outputs_to(&self, addresses: &[String]) -> ApiResult<Vec<ChainOutput>> {
+        let chain = self.chain.read().await;
+        Ok(chain
+            .outputs
+            .iter()
+            .filter(|output| addresses.contains(&output.address))
+            .map(|output| ChainOutput {
+                txid: output.txid.clone(),
+                vout: output.vout,
+                address: output.address.clone(),
+                amount_sats: output.amount_sats,
+                confirmations: output
+                    .mined_at
+                    .map(|height| chain.height - height + 1)
+                    .unwrap_or(0),
+            })
+            .collect())
+    }
+}
+
+/// The chain source configured by the environment
+pub fn chain_source_from_env() -> ApiResult<Arc<dyn ChainSource>> {
+    match std::env::var("ESPLORA_URL")
+        .ok()
+        .filter(|url| !url.is_empty())
+    {
+        Some(url) => Ok(Arc::new(EsploraChainSource::new(&url)?)),
+        None => Ok(Arc::new(InMemoryChainSource::new())),
+    }
+}
@@ -0,0 +1,313 @@
+// Local stand-in for fmcd used by tests
+// Serves the Lightning and admin endpoints the adapter calls from an ephemeral port;
+// tests decide when invoices are paid and which invoices cannot be paid, and the
+// mock keeps a balance and operation log the way a federation client would
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::{
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use serde_json::{json, Value};
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+use super::{
+    FmcdClient, FmcdConfig, InvoiceState, ADMIN_INFO, ADMIN_OPERATIONS, LN_AWAIT_INVOICE,
+    LN_INVOICE, LN_PAY,
+};
+use crate::api::traits::lightning::bolt11_amount_msat;
+
+const PASSWORD: &str = "mock-password";
+
+/// Routing fee the mock charges on every payment, in millisatoshis
+pub const MOCK_FEE_MSAT: u64 = 2_000;
+
+#[derive(Default)]
+struct MockState {
+    invoices: HashMap<String, InvoiceState>,
+    invoice_amounts: HashMap<String, u64>,
+    unpayable: HashSet<String>,
+    /// Paid, but the reply is lost on the way back
+    lost_replies: HashSet<String>,
+    /// Never reach fmcd, and the reply is lost
+    lost_requests: HashSet<String>,
+    paid: Vec<String>,
+    balance_msat: u64,
+    operations: Vec<Value>,
+}
+
+impl MockState {
+    fn record_operation(
+        &mut self,
+        operation_id: &str,
+        kind: &str,
+        amount_msat: u64,
+        fee_msat: u64,
+    ) {
+        self.operations.push(json!({
+            "operationId": operation_id,
+            "creationTime": chrono::Utc::now(),
+            "operationKind": kind,
+            "amountMsat": amount_msat,
+            "feeMsat": fee_msat,
+            "outcome": "success",
+        }));
+    }
+}
+
+pub struct MockFmcd {
+    pub base_url: String,
+    state: Arc<Mutex<MockState>>,
+}
+
+impl MockFmcd {
+    pub async fn start() -> Self {
+        let state = Arc::new(Mutex::new(MockState::default()));
+        let router = Router::new()
+            .route(LN_INVOICE, post(create_invoice))
+            .route(LN_AWAIT_INVOICE, post(await_invoice))
+            .route(LN_PAY, post(pay))
+            .route(ADMIN_INFO, get(info))
+            .route(ADMIN_OPERATIONS, post(operations))
+            .with_state(state.clone());
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, router).await.unwrap();
+        });
+
+        Self {
+            base_url: format!("http://{}", addr),
+            state,
+        }
+    }
+
+    /// A client configured for this server, with a short settlement wait
+    pub fn client(&self) -> FmcdClient {
+        FmcdClient::new(FmcdConfig {
+            base_url: self.base_url.clone(),
+            password: Some(PASSWORD.to_string()),
+            await_timeout: Duration::from_millis(100),
+            ..FmcdConfig::default()
+        })
+        .unwrap()
+    }
+
+    pub async fn mark_paid(&self, operation_id: &str) {
+        self.set_state(operation_id, InvoiceState::Paid).await;
+    }
+
+    pub async fn mark_failed(&self, operation_id: &str) {
+        self.set_state(operation_id, InvoiceState::Failed("cancelled".to_string()))
+            .await;
+    }
+
+    /// Make payments of `bolt11` fail
+    pub async fn refuse(&self, bolt11: &str) {
+        self.state.lock().await.unpayable.insert(bolt11.to_string());
+    }
+
+    /// Pay `bolt11` but answer with a reply the client can't read
+    pub async fn lose_reply(&self, bolt11: &str) {
+        self.state
+            .lock()
+            .await
+            .lost_replies
+            .insert(bolt11.to_string());
+    }
+
+    /// Answer payments of `bolt11` with an unreadable reply without paying
+    pub async fn lose_request(&self, bolt11: &str) {
+        self.state
+            .lock()
+            .await
+            .lost_requests
+            .insert(bolt11.to_string());
+    }
+
+    /// Invoices paid through the mock, in order
+    pub async fn paid_invoices(&self) -> Vec<String> {
+        self.state.lock().await.paid.clone()
+    }
+
+    /// Receive a payment the dashboard never asked for, returning its operation ID
+    pub async fn receive_external(&self, amount_msat: u64) -> String {
+        let operation_id = Uuid::new_v4().simple().to_string();
+        let mut state = self.state.lock().await;
+        state.balance_msat += amount_msat;
+        state.record_operation(&operation_id, "ln_receive", amount_msat, 0);
+        operation_id
+    }
+
+    async fn set_state(&self, operation_id: &str, invoice_state: InvoiceState) {
+        let mut state = self.state.lock().await;
+        let newly_paid = invoice_state == InvoiceState::Paid
+            && state.invoices.get(operation_id) != Some(&InvoiceState::Paid);
+        if newly_paid {
+            let amount_msat = state
+                .invoice_amounts
+                .get(operation_id)
+                .copied()
+                .unwrap_or_default();
+            state.balance_msat += amount_msat;
+            state.record_operation(operation_id, "ln_receive", amount_msat, 0);
+        }
+        state
+            .invoices
+            .insert(operation_id.to_string(), invoice_state);
+    }
+}
+
+fn authorized(headers: &HeaderMap) -> bool {
+    // Build the header the way clients do rather than encoding it by hand
+    let expected = reqwest::Client::new()
+        .post("http://fmcd")
+        .basic_auth("fmcd", Some(PASSWORD))
+        .build()
+        .ok()
+        .and_then(|request| {
+            let value = request.headers().get("authorization")?;
+            Some(value.as_bytes().to_vec())
+        });
+    expected.is_some()
+        && headers
+            .get("authorization")
+            .map(|value| value.as_bytes().to_vec())
+            == expected
+}
+
+fn error(status: StatusCode, message: &str) -> Response {
+    (status, Json(json!({ "error": message }))).into_response()
+}
+
+async fn create_invoice(
+    State(state): State<Arc<Mutex<MockState>>>,
+    headers: HeaderMap,
+    Json(body): Json<Value>,
+) -> Response {
+    if !authorized(&headers) {
+        return error(StatusCode::UNAUTHORIZED, "invalid password");
+    }
+    let Some(amount_msat) = body["amountMsat"].as_u64() else {
+        return error(StatusCode::BAD_REQUEST, "amountMsat is required");
+    };
+
+    let operation_id = Uuid::new_v4().simple().to_string();
+    // Nano-bitcoin amounts are hundreds of millisatoshis. The data part uses the
+    // bech32 alphabet, which has no '1', so the separator stays unambiguous.
+    const BECH32: &[u8] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+    let data: String = operation_id
+        .bytes()
+        .map(|b| BECH32[b as usize % BECH32.len()] as char)
+        .collect();
+    let invoice = format!("lnbcrt{}n1p{}", amount_msat / 100, data);
+    let mut state = state.lock().await;
+    state
+        .invoices
+        .insert(operation_id.clone(), InvoiceState::Pending);
+    state
+        .invoice_amounts
+        .insert(operation_id.clone(), amount_msat);
+    Json(json!({ "operationId": operation_id, "invoice": invoice })).into_response()
+}
+
+async fn await_invoice(
+    State(state): State<Arc<Mutex<MockState>>>,
+    headers: HeaderMap,
+    Json(body): Json<Value>,
+) -> Response {
+    if !authorized(&headers) {
+        return error(StatusCode::UNAUTHORIZED, "invalid password");
+    }
+    let operation_id = body["operationId"].as_str().unwrap_or_default();
+    let invoice_state = state.lock().await.invoices.get(operation_id).cloned();
+    match invoice_state {
+        Some(InvoiceState::Paid) => Json(json!({ "status": "claimed" })).into_response(),
+        Some(InvoiceState::Failed(reason)) => error(StatusCode::INTERNAL_SERVER_ERROR, &reason),
+        // Like fmcd, hold the request open until the invoice settles
+        Some(InvoiceState::Pending) => {
+            tokio::time::sleep(Duration::from_secs(30)).await;
+            error(StatusCode::REQUEST_TIMEOUT, "still pending")
+        }
+        None => error(StatusCode::NOT_FOUND, "unknown operation"),
+    }
+}
+
+async fn pay(
+    State(state): State<Arc<Mutex<MockState>>>,
+    headers: HeaderMap,
+    Json(body): Json<Value>,
+) -> Response {
+    if !authorized(&headers) {
+        return error(StatusCode::UNAUTHORIZED, "invalid password");
+    }
+    let bolt11 = body["paymentInfo"].as_str().unwrap_or_default().to_string();
+
+    let mut state = state.lock().await;
+    if state.unpayable.contains(&bolt11) {
+        return error(StatusCode::INTERNAL_SERVER_ERROR, "no route found");
+    }
+    if state.lost_requests.contains(&bolt11) {
+        return (StatusCode::OK, "upstream connection reset").into_response();
+    }
+    let amount_msat = bolt11_amount_msat(&bolt11)
+        .ok()
+        .flatten()
+        .unwrap_or_default();
+    let operation_id = Uuid::new_v4().simple().to_string();
+    state.balance_msat = state
+        .balance_msat
+        .saturating_sub(amount_msat + MOCK_FEE_MSAT);
+    state.record_operation(&operation_id, "ln_pay", amount_msat, MOCK_FEE_MSAT);
+    if state.lost_replies.contains(&bolt11) {
+        state.paid.push(bolt11);
+        return (StatusCode::OK, "upstream connection reset").into_response();
+    }
+    state.paid.push(bolt11);
+    Json(json!({
+        "operationId": operation_id,
+        "contractId": Uuid::new_v4().simple().to_string(),
+        "fee": MOCK_FEE_MSAT,
+    }))
+    .into_response()
+}
+
+async fn info(State(state): State<Arc<Mutex<MockState>>>, headers: HeaderMap) -> Response {
+    if !authorized(&headers) {
+        return error(StatusCode::UNAUTHORIZED, "invalid password");
+    }
+    let balance_msat = state.lock().await.balance_msat;
+    Json(json!({
+        "mock-federation": { "network": "regtest", "totalAmountMsat": balance_msat }
+    }))
+    .into_response()
+}
+
+async fn operations(
+    State(state): State<Arc<Mutex<MockState>>>,
+    headers: HeaderMap,
+    Json(body): Json<Value>,
+) -> Response {
+    if !authorized(&headers) {
+        return error(StatusCode::UNAUTHORIZED, "invalid password");
+    }
+    let limit = body["limit"].as_u64().unwrap_or(u64::MAX) as usize;
+    let operations: Vec<Value> = state
+        .lock()
+        .await
+        .operations
+        .iter()
+        .rev()
+        .take(limit)
+        .cloned()
+        .collect();
+    Json(json!({ "operations": operations })).into_response()
+}
@@ -0,0 +1,429 @@
+// Fedimint client daemon (fmcd) adapter
+// fmcd runs alongside the dashboard (see compose.yml) and exposes the federation's
+// Lightning gateway over REST; amounts on the wire are in millisatoshis
+
+#[cfg(test)]
+pub mod mock;
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use reqwest::{Client, ClientBuilder, StatusCode};
+use serde::{Deserialize, Serialize};
+
+use crate::api::{
+    errors::{ApiError, ApiResult},
+    traits::reconciliation::{CustodyProvider, ReconciliationItem},
+};
+
+// fmcd endpoints
+pub const LN_INVOICE: &str = "/v2/ln/invoice";
+pub const LN_AWAIT_INVOICE: &str = "/v2/ln/await-invoice";
+pub const LN_PAY: &str = "/v2/ln/pay";
+pub const ADMIN_INFO: &str = "/v2/admin/info";
+pub const ADMIN_OPERATIONS: &str = "/v2/admin/operations";
+
+/// Most operations a reconciliation asks fmcd for
+const OPERATIONS_LIMIT: usize = 10_000;
+
+/// fmcd authenticates with HTTP basic auth under a fixed user name
+const AUTH_USER: &str = "fmcd";
+
+#[derive(Debug, Clone)]
+pub struct FmcdConfig {
+    pub base_url: String,
+    /// `None` when fmcd runs with `FMCD_NO_AUTH`
+    pub password: Option<String>,
+    pub federation_id: Option<String>,
+    pub gateway_id: Option<String>,
+    pub timeout_seconds: u64,
+    /// How long a settlement check waits on fmcd before treating the invoice as still pending
+    pub await_timeout: Duration,
+}
+
+impl Default for FmcdConfig {
+    fn default() -> Self {
+        Self {
+            base_url: "http://localhost:7070".to_string(),
+            password: None,
+            federation_id: None,
+            gateway_id: None,
+            timeout_seconds: 30,
+            await_timeout: Duration::from_secs(2),
+        }
+    }
+}
+
+impl FmcdConfig {
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let non_empty = |key: &str| std::env::var(key).ok().filter(|value| !value.is_empty());
+
+        Self {
+            base_url: non_empty("FMCD_URL").unwrap_or(defaults.base_url),
+            password: non_empty("FMCD_PASSWORD"),
+            federation_id: non_empty("FMCD_FEDERATION_ID"),
+            gateway_id: non_empty("FMCD_GATEWAY_ID"),
+            timeout_seconds: non_empty("FMCD_TIMEOUT_SECONDS")
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(defaults.timeout_seconds),
+            await_timeout: non_empty("FMCD_AWAIT_TIMEOUT_MS")
+                .and_then(|value| value.parse().ok())
+                .map(Duration::from_millis)
+                .unwrap_or(defaults.await_timeout),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct InvoiceRequest<'a> {
+    amount_msat: u64,
+    description: &'a str,
+    expiry_time: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    gateway_id: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    federation_id: Option<&'a str>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FmcdInvoice {
+    pub operation_id: String,
+    pub invoice: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AwaitInvoiceRequest<'a> {
+    operation_id: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    federation_id: Option<&'a str>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PayRequest<'a> {
+    payment_info: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    gateway_id: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    federation_id: Option<&'a str>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FmcdPayment {
+    pub operation_id: String,
+    /// Routing fee in millisatoshis
+    #[serde(default)]
+    pub fee: u64,
+}
+
+/// One federation's entry in `/v2/admin/info`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FederationInfo {
+    total_amount_msat: u64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct OperationsRequest<'a> {
+    limit: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    federation_id: Option<&'a str>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct OperationsResponse {
+    operations: Vec<FmcdOperation>,
+}
+
+/// A Lightning operation from fmcd's operation log
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FmcdOperation {
+    pub operation_id: String,
+    pub creation_time: DateTime<Utc>,
+    /// `ln_receive` or `ln_pay`
+    pub operation_kind: String,
+    /// Amount received or paid, excluding fees
+    pub amount_msat: u64,
+    #[serde(default)]
+    pub fee_msat: u64,
+    /// `success`, `pending` or `failed`
+    pub outcome: String,
+}
+
+impl FmcdOperation {
+    pub fn is_outgoing(&self) -> bool {
+        self.operation_kind == "ln_pay"
+    }
+
+    pub fn succeeded(&self) -> bool {
+        self.outcome == "success"
+    }
+}
+
+/// Where an invoice stands according to fmcd
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InvoiceState {
+    Pending,
+    Paid,
+    Failed(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct FmcdClient {
+    client: Client,
+    config: FmcdConfig,
+}
+
+impl FmcdClient {
+    pub fn new(config: FmcdConfig) -> ApiResult<Self> {
+        let client = ClientBuilder::new()
+            .timeout(Duration::from_secs(config.timeout_seconds))
+            .build()
+            .map_err(|e| ApiError::Network {
+                message: e.to_string(),
+            })?;
+        Ok(Self { client, config })
+    }
+
+    pub fn config(&self) -> &FmcdConfig {
+        &self.config
+    }
+
+    /// Create a BOLT11 invoice through the federation's gateway
+    pub async fn create_invoice(
+        &self,
+        amount_msat: u64,
+        description: &str,
+        expiry_secs: u32,
+    ) -> ApiResult<FmcdInvoice> {
+        let body = InvoiceRequest {
+            amount_msat,
+            description,
+            expiry_time: expiry_secs,
+            gateway_id: self.config.gateway_id.as_deref(),
+            federation_id: self.config.federation_id.as_deref(),
+        };
+        let response = self.post(LN_INVOICE, &body).send().await?;
+        Self::parse(response).await
+    }
+
+    /// Pay a BOLT11 invoice; fmcd returns once the payment has succeeded or failed
+    pub async fn pay_invoice(&self, bolt11: &str) -> ApiResult<FmcdPayment> {
+        let body = PayRequest {
+            payment_info: bolt11,
+            gateway_id: self.config.gateway_id.as_deref(),
+            federation_id: self.config.federation_id.as_deref(),
+        };
+        let response = self.post(LN_PAY, &body).send().await?;
+        Self::parse(response).await
+    }
+
+    /// Check whether an invoice has been paid. fmcd only answers once the invoice
+    /// settles, so an answer that takes longer than `await_timeout` means pending.
+    pub async fn invoice_state(&self, operation_id: &str) -> ApiResult<InvoiceState> {
+        let body = AwaitInvoiceRequest {
+            operation_id,
+            federation_id: self.config.federation_id.as_deref(),
+        };
+        let response = match self
+            .post(LN_AWAIT_INVOICE, &body)
+            .timeout(self.config.await_timeout)
+            .send()
+            .await
+        {
+            Ok(response) => response,
+            Err(e) if e.is_timeout() => return Ok(InvoiceState::Pending),
+            Err(e) => return Err(e.into()),
+        };
+
+        let status = response.status();
+        if status.is_success() {
+            return Ok(InvoiceState::Paid);
+        }
+        match status {
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN | StatusCode::NOT_FOUND => {
+                Err(Self::error(status, response).await)
+            }
+            _ => Ok(InvoiceState::Failed(Self::error_message(response).await)),
+        }
+    }
+
+    /// Ecash held for the configured federation, or across every federation
+    /// fmcd has joined when none is configured
+    pub async fn balance_msat(&self) -> ApiResult<u64> {
+        let response = self
+            .authorized(self.client.get(self.url(ADMIN_INFO)))
+            .send()
+            .await?;
+        let federations: HashMap<String, FederationInfo> = Self::parse(response).await?;
+        match &self.config.federation_id {
+            Some(federation_id) => federations
+                .get(federation_id)
+                .map(|info| info.total_amount_msat)
+                .ok_or_else(|| ApiError::NotFound {
+                    resource: format!("fmcd federation {}", federation_id),
+                }),
+            None => Ok(federations
+                .values()
+                .map(|info| info.total_amount_msat)
+                .sum()),
+        }
+    }
+
+    /// Lightning operations fmcd has recorded, newest first
+    pub async fn operations(&self) -> ApiResult<Vec<FmcdOperation>> {
+        let body = OperationsRequest {
+            limit: OPERATIONS_LIMIT,
+            federation_id: self.config.federation_id.as_deref(),
+        };
+        let response = self.post(ADMIN_OPERATIONS, &body).send().await?;
+        let listed: OperationsResponse = Self::parse(response).await?;
+        Ok(listed.operations)
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.config.base_url.trim_end_matches('/'), path)
+    }
+
+    fn post<T: Serialize>(&self, path: &str, body: &T) -> reqwest::RequestBuilder {
+        self.authorized(self.client.post(self.url(path)).json(body))
+    }
+
+    fn authorized(&self, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        match &self.config.password {
+            Some(password) => request.basic_auth(AUTH_USER, Some(password)),
+            None => request,
+        }
+    }
+
+    async fn parse<R: serde::de::DeserializeOwned>(response: reqwest::Response) -> ApiResult<R> {
+        let status = response.status();
+        if !status.is_success() {
+            return Err(Self::error(status, response).await);
+        }
+        response.json().await.map_err(|e| ApiError::Serialization {
+            message: format!("Unexpected fmcd response: {}", e),
+        })
+    }
+
+    async fn error(status: StatusCode, response: reqwest::Response) -> ApiError {
+        let message = Self::error_message(response).await;
+        match status {
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => ApiError::Authentication {
+                message: format!("fmcd rejected the credentials: {}", message),
+            },
+            StatusCode::NOT_FOUND => ApiError::NotFound {
+                resource: format!("fmcd: {}", message),
+            },
+            _ => ApiError::Server {
+                message: format!("fmcd: {}", message),
+            },
+        }
+    }
+
+    /// fmcd reports failures as `{"error": "..."}`
+    async fn error_message(response: reqwest::Response) -> String {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        serde_json::from_str::<serde_json::Value>(&body)
+            .ok()
+            .and_then(|value| value.get("error")?.as_str().map(str::to_string))
+            .unwrap_or_else(|| {
+                if body.trim().is_empty() {
+                    status.to_string()
+                } else {
+                    body
+                }
+            })
+    }
+}
+
+/// The federation holds the ecash backing Lightning wallets
+#[async_trait]
+impl CustodyProvider for FmcdClient {
+    fn name(&self) -> String {
+        match &self.config.federation_id {
+            Some(federation_id) => format!("Federation {}", federation_id),
+            None => "Federation".to_string(),
+        }
+    }
+
+    async fn balance_sats(&self) -> ApiResult<u64> {
+        Ok(self.balance_msat().await? / 1_000)
+    }
+
+    async fn movements(&self) -> ApiResult<Vec<ReconciliationItem>> {
+        Ok(self
+            .operations()
+            .await?
+            .into_iter()
+            .filter(FmcdOperation::succeeded)
+            .map(|operation| {
+                // Rounded the way wallets are charged, so amounts compare equal
+                let sats = operation.amount_msat.div_ceil(1_000) as i64;
+                let (amount_sats, description) = if operation.is_outgoing() {
+                    (-sats, "Lightning payment")
+                } else {
+                    (sats, "Lightning deposit")
+                };
+                ReconciliationItem {
+                    reference: operation.operation_id,
+                    amount_sats,
+                    description: description.to_string(),
+                    occurred_at: operation.creation_time,
+                }
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::traits::lightning::bolt11_amount_msat;
+
+    #[tokio::test]
+    async fn test_invoice_lifecycle_against_mock() {
+        let fmcd = mock::MockFmcd::start().await;
+        let client = fmcd.client();
+
+        let invoice = client
+            .create_invoice(21_000_000, "Deposit", 3_600)
+            .await
+            .unwrap();
+        assert_eq!(
+            bolt11_amount_msat(&invoice.invoice).unwrap(),
+            Some(21_000_000)
+        );
+        assert_eq!(
+            client.invoice_state(&invoice.operation_id).await.unwrap(),
+            InvoiceState::Pending
+        );
+
+        fmcd.mark_paid(&invoice.operation_id).await;
+        assert_eq!(
+            client.invoice_state(&invoice.operation_id).await.unwrap(),
+            InvoiceState::Paid
+        );
+
+        let unknown = client.invoice_state("missing").await;
+        assert!(matches!(unknown, Err(ApiError::NotFound { .. })));
+
+        let wrong_password = FmcdClient::new(FmcdConfig {
+            password: Some("wrong".to_string()),
+            ..client.config().clone()
+        })
+        .unwrap();
+        let rejected = wrong_password.create_invoice(1_000, "Deposit", 60).await;
+        assert!(matches!(rejected, Err(ApiError::Authentication { .. })));
+    }
+}
@@ -0,0 +1,153 @@
+// Exchange rate sources for the FX subsystem
+// FX_RATE_SOURCES lists the sources to try, in order, e.g. `coingecko,fixed`
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use reqwest::{Client, ClientBuilder};
+use rust_decimal::Decimal;
+
+use crate::api::{
+    errors::{ApiError, ApiResult},
+    traits::fx::RateSource,
+};
+
+/// A rate that never changes, for development and for pinning a rate by configuration
+#[derive(Debug, Clone)]
+pub struct FixedRateSource {
+    kes_per_btc: Decimal,
+}
+
+impl FixedRateSource {
+    pub fn new(kes_per_btc: Decimal) -> Self {
+        Self { kes_per_btc }
+    }
+}
+
+#[async_trait]
+impl RateSource for FixedRateSource {
+    fn name(&self) -> &str {
+        "fixed"
+    }
+
+    async fn fetch_kes_per_btc(&self) -> ApiResult<Decimal> {
+        Ok(self.kes_per_btc)
+    }
+}
+
+/// CoinGecko's simple price API
+#[derive(Debug, Clone)]
+pub struct CoinGeckoRateSource {
+    client: Client,
+    base_url: String,
+}
+
+impl CoinGeckoRateSource {
+    pub const DEFAULT_URL: &'static str = "https://api.coingecko.com/api/v3";
+
+    pub fn new(base_url: &str) -> ApiResult<Self> {
+        let client = ClientBuilder::new()
+            .timeout(Duration::from_secs(15))
+            .build()
+            .map_err(|e| ApiError::Network {
+                message: e.to_string(),
+            })?;
+        Ok(Self {
+            client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+        })
+    }
+}
+
+#[async_trait]
+impl RateSource for CoinGeckoRateSource {
+    fn name(&self) -> &str {
+        "coingecko"
+    }
+
+    async fn fetch_kes_per_btc(&self) -> ApiResult<Decimal> {
+        let response = self
+            .client
+            .get(format!(
+                "{}/simple/price?ids=bitcoin&vs_currencies=kes",
+                self.base_url
+            ))
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(ApiError::Server {
+                message: format!("CoinGecko returned {}", response.status()),
+            });
+        }
+
+        let body: serde_json::Value =
+            response.json().await.map_err(|e| ApiError::Serialization {
+                message: format!("Unexpected CoinGecko response: {}", e),
+            })?;
+        // Read back from the number's shortest text form so no float noise creeps in
+        match &body["bitcoin"]["kes"] {
+            serde_json::Value::Number(price) => price.to_string().parse::<Decimal>().ok(),
+            _ => None,
+        }
+        .ok_or_else(|| ApiError::Serialization {
+            message: "CoinGecko response has no bitcoin/KES price".to_string(),
+        })
+    }
+}
+
+/// The rate sources listed in FX_RATE_SOURCES
+pub fn rate_sources_from_env() -> ApiResult<Vec<Arc<dyn RateSource>>> {
+    let names = std::env::var("FX_RATE_SOURCES").unwrap_or_default();
+    names
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(|name| -> ApiResult<Arc<dyn RateSource>> {
+            match name {
+                "coingecko" => {
+                    let url = std::env::var("FX_COINGECKO_URL")
+                        .unwrap_or_else(|_| CoinGeckoRateSource::DEFAULT_URL.to_string());
+                    Ok(Arc::new(CoinGeckoRateSource::new(&url)?))
+                }
+                "fixed" => {
+                    let rate = std::env::var("FX_FIXED_KES_PER_BTC")
+                        .ok()
+                        .and_then(|value| value.trim().parse::<Decimal>().ok())
+                        .filter(|rate| *rate > Decimal::ZERO)
+                        .ok_or_else(|| ApiError::Validation {
+                            message: "The fixed rate source needs FX_FIXED_KES_PER_BTC".to_string(),
+                        })?;
+                    Ok(Arc::new(FixedRateSource::new(rate)))
+                }
+                other => Err(ApiError::Validation {
+                    message: format!("Unknown FX rate source '{}'", other),
+                }),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{routing::get, Json, Router};
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_coingecko_price() {
+        let router = Router::new().route(
+            "/simple/price",
+            get(|| async { Json(json!({ "bitcoin": { "kes": 12_345_678.9 } })) }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+        let source = CoinGeckoRateSource::new(&format!("http://{}", addr)).unwrap();
+        assert_eq!(
+            source.fetch_kes_per_btc().await.unwrap(),
+            Decimal::new(123_456_789, 1)
+        );
+    }
+}
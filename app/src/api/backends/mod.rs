@@ -1,5 +1,219 @@
+pub mod chain;
+pub mod fmcd;
+pub mod fx;
+pub mod mpesa;
 pub mod nestjs;
 pub mod rust;
 
+use std::sync::{Arc, OnceLock};
+
+use crate::api::{
+    config::{ApiConfig, Backend},
+    errors::{ApiError, ApiResult},
+    traits::{
+        AlertsApi, ContributionsApi, DividendsApi, FxApi, GroupsApi, LedgerApi, LightningApi,
+        LoansApi, OnChainApi, ReconciliationApi, SavedReportsApi, SharesApi, StatementsApi,
+        SwapsApi, WalletsApi, WithdrawalPolicyApi,
+    },
+};
+
 pub use nestjs::NestJsBackend;
 pub use rust::RustBackend;
+
+/// Shared Rust backend instance so in-process state survives across requests
+static RUST_BACKEND: OnceLock<RustBackend> = OnceLock::new();
+
+/// Shared alert acknowledgements, kept locally whichever backend is configured
+static ALERTS: OnceLock<rust::RustAlertsApi> = OnceLock::new();
+
+/// Get the shared Rust backend, creating it on first use
+pub fn rust_backend(config: &ApiConfig) -> ApiResult<&'static RustBackend> {
+    if let Some(backend) = RUST_BACKEND.get() {
+        return Ok(backend);
+    }
+    let backend = RustBackend::new(config)?;
+    Ok(RUST_BACKEND.get_or_init(|| backend))
+}
+
+/// Resolve the shares API for the configured backend
+pub fn shares_api(config: &ApiConfig) -> ApiResult<Arc<dyn SharesApi>> {
+    match config.backend {
+        Backend::NestJs => {
+            let client = nestjs::NestJsClient::new(config)?;
+            Ok(Arc::new(nestjs::NestJsSharesApi::new(client)))
+        }
+        Backend::Rust => Ok(Arc::new(rust_backend(config)?.shares.clone())),
+    }
+}
+
+/// Resolve the wallets API for the configured backend
+pub fn wallets_api(config: &ApiConfig) -> ApiResult<Arc<dyn WalletsApi>> {
+    match config.backend {
+        Backend::NestJs => {
+            let client = nestjs::NestJsClient::new(config)?;
+            Ok(Arc::new(nestjs::NestJsWalletsApi::new(client)))
+        }
+        Backend::Rust => Ok(Arc::new(rust_backend(config)?.wallets.clone())),
+    }
+}
+
+/// Resolve the loans API; loans are only implemented by the Rust backend
+pub fn loans_api(config: &ApiConfig) -> ApiResult<Arc<dyn LoansApi>> {
+    match config.backend {
+        Backend::NestJs => Err(ApiError::NotFound {
+            resource: "Loans are not supported by the NestJS backend; set API_BACKEND=rust"
+                .to_string(),
+        }),
+        Backend::Rust => Ok(Arc::new(rust_backend(config)?.loans.clone())),
+    }
+}
+
+/// Resolve the groups API for the configured backend
+pub fn groups_api(config: &ApiConfig) -> ApiResult<Arc<dyn GroupsApi>> {
+    match config.backend {
+        Backend::NestJs => {
+            let client = nestjs::NestJsClient::new(config)?;
+            Ok(Arc::new(nestjs::NestJsGroupsApi::new(client)))
+        }
+        // Groups are not served in-process yet, so surface the backend's friendly error
+        Backend::Rust => Err(rust_backend(config)?.get_friendly_error()),
+    }
+}
+
+/// Resolve the chama contributions API; contribution plans are only implemented by the Rust backend
+pub fn contributions_api(config: &ApiConfig) -> ApiResult<Arc<dyn ContributionsApi>> {
+    match config.backend {
+        Backend::NestJs => Err(ApiError::NotFound {
+            resource:
+                "Contribution plans are not supported by the NestJS backend; set API_BACKEND=rust"
+                    .to_string(),
+        }),
+        Backend::Rust => Ok(Arc::new(rust_backend(config)?.contributions.clone())),
+    }
+}
+
+/// Resolve the dividends API; dividend runs are only implemented by the Rust backend
+pub fn dividends_api(config: &ApiConfig) -> ApiResult<Arc<dyn DividendsApi>> {
+    match config.backend {
+        Backend::NestJs => Err(ApiError::NotFound {
+            resource: "Dividend runs are not supported by the NestJS backend; set API_BACKEND=rust"
+                .to_string(),
+        }),
+        Backend::Rust => Ok(Arc::new(rust_backend(config)?.dividends.clone())),
+    }
+}
+
+/// Resolve the member statements API; statements are built from the Rust backend's ledger
+pub fn statements_api(config: &ApiConfig) -> ApiResult<Arc<dyn StatementsApi>> {
+    match config.backend {
+        Backend::NestJs => Err(ApiError::NotFound {
+            resource:
+                "Member statements are not supported by the NestJS backend; set API_BACKEND=rust"
+                    .to_string(),
+        }),
+        Backend::Rust => Ok(Arc::new(rust_backend(config)?.statements.clone())),
+    }
+}
+
+/// Resolve the Lightning API; invoices and payments go through fmcd from the Rust backend
+pub fn lightning_api(config: &ApiConfig) -> ApiResult<Arc<dyn LightningApi>> {
+    match config.backend {
+        Backend::NestJs => Err(ApiError::NotFound {
+            resource:
+                "Lightning payments are not supported by the NestJS backend; set API_BACKEND=rust"
+                    .to_string(),
+        }),
+        Backend::Rust => Ok(Arc::new(rust_backend(config)?.lightning.clone())),
+    }
+}
+
+/// Resolve the on-chain deposits API; addresses are derived and tracked by the Rust backend
+pub fn onchain_api(config: &ApiConfig) -> ApiResult<Arc<dyn OnChainApi>> {
+    match config.backend {
+        Backend::NestJs => Err(ApiError::NotFound {
+            resource:
+                "On-chain deposits are not supported by the NestJS backend; set API_BACKEND=rust"
+                    .to_string(),
+        }),
+        Backend::Rust => Ok(Arc::new(rust_backend(config)?.onchain.clone())),
+    }
+}
+
+/// Resolve the exchange rate API; rate history is kept by the Rust backend
+pub fn fx_api(config: &ApiConfig) -> ApiResult<Arc<dyn FxApi>> {
+    match config.backend {
+        Backend::NestJs => Err(ApiError::NotFound {
+            resource:
+                "Exchange rates are not supported by the NestJS backend; set API_BACKEND=rust"
+                    .to_string(),
+        }),
+        Backend::Rust => Ok(Arc::new(rust_backend(config)?.fx.clone())),
+    }
+}
+
+/// Resolve the M-Pesa swaps API; swaps are only run by the Rust backend
+pub fn swaps_api(config: &ApiConfig) -> ApiResult<Arc<dyn SwapsApi>> {
+    match config.backend {
+        Backend::NestJs => Err(ApiError::NotFound {
+            resource: "Swaps are not supported by the NestJS backend; set API_BACKEND=rust"
+                .to_string(),
+        }),
+        Backend::Rust => Ok(Arc::new(rust_backend(config)?.swaps.clone())),
+    }
+}
+
+/// Resolve reconciliation runs; only the Rust backend keeps a ledger to reconcile
+pub fn reconciliation_api(config: &ApiConfig) -> ApiResult<Arc<dyn ReconciliationApi>> {
+    match config.backend {
+        Backend::NestJs => Err(ApiError::NotFound {
+            resource: "Reconciliation is not supported by the NestJS backend; set API_BACKEND=rust"
+                .to_string(),
+        }),
+        Backend::Rust => Ok(Arc::new(rust_backend(config)?.reconciliation.clone())),
+    }
+}
+
+/// Resolve system alert acknowledgements. The alerts come from the dashboard on
+/// either backend, so acknowledgements are stored locally under RUST_DATA_DIR.
+pub fn alerts_api(_config: &ApiConfig) -> ApiResult<Arc<dyn AlertsApi>> {
+    if let Some(alerts) = ALERTS.get() {
+        return Ok(Arc::new(alerts.clone()));
+    }
+    let alerts =
+        rust::RustAlertsApi::new().with_store(rust::JsonStore::from_env("alert-acks.json"))?;
+    Ok(Arc::new(ALERTS.get_or_init(|| alerts).clone()))
+}
+
+/// Resolve saved analytics reports; only the Rust backend stores them
+pub fn saved_reports_api(config: &ApiConfig) -> ApiResult<Arc<dyn SavedReportsApi>> {
+    match config.backend {
+        Backend::NestJs => Err(ApiError::NotFound {
+            resource: "Saved reports are not supported by the NestJS backend; set API_BACKEND=rust"
+                .to_string(),
+        }),
+        Backend::Rust => Ok(Arc::new(rust_backend(config)?.reports.clone())),
+    }
+}
+
+/// Resolve the withdrawal policy; limits are enforced by the Rust backend's wallet store
+pub fn withdrawal_policy_api(config: &ApiConfig) -> ApiResult<Arc<dyn WithdrawalPolicyApi>> {
+    match config.backend {
+        Backend::NestJs => Err(ApiError::NotFound {
+            resource:
+                "Withdrawal limits are not supported by the NestJS backend; set API_BACKEND=rust"
+                    .to_string(),
+        }),
+        Backend::Rust => Ok(Arc::new(rust_backend(config)?.withdrawal_policy.clone())),
+    }
+}
+
+/// Resolve the double-entry ledger; the ledger is only kept by the Rust backend
+pub fn ledger_api(config: &ApiConfig) -> ApiResult<Arc<dyn LedgerApi>> {
+    match config.backend {
+        Backend::NestJs => Err(ApiError::NotFound {
+            resource: "The ledger is not supported by the NestJS backend; set API_BACKEND=rust"
+                .to_string(),
+        }),
+        Backend::Rust => Ok(Arc::new(rust_backend(config)?.ledger.clone())),
+    }
+}
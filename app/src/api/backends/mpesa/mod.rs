@@ -0,0 +1,235 @@
+// M-Pesa gateways for swaps
+// Only the mock gateway exists so far: it stands in for Daraja's STK push and B2C
+// APIs, accepting requests and leaving them pending until they are completed or
+// failed by hand from the swaps page or a test
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use crate::api::{
+    errors::{ApiError, ApiResult},
+    traits::swaps::{MpesaGateway, MpesaState},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MockRequestKind {
+    StkPush,
+    B2cPayout,
+}
+
+/// A request the mock gateway has accepted
+#[derive(Debug, Clone)]
+pub struct MockMpesaRequest {
+    pub request_id: String,
+    pub kind: MockRequestKind,
+    pub phone: String,
+    pub amount_kes: Decimal,
+    pub reference: String,
+    /// Idempotency key, for payouts
+    pub payout_key: Option<String>,
+    pub state: MpesaState,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Default)]
+struct MockState {
+    requests: HashMap<String, MockMpesaRequest>,
+    /// Refuse new requests, as Daraja does during maintenance windows
+    unavailable: bool,
+    /// Accept new requests but fail to answer
+    losing_replies: bool,
+    /// Drop new requests before they reach M-Pesa
+    losing_requests: bool,
+}
+
+#[derive(Clone, Default)]
+pub struct MockMpesaGateway {
+    state: Arc<RwLock<MockState>>,
+}
+
+impl MockMpesaGateway {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Complete a pending request as if the customer had entered their PIN or the
+    /// payout had landed, returning the receipt number
+    pub async fn complete(&self, request_id: &str) -> ApiResult<String> {
+        let receipt = Uuid::new_v4().simple().to_string()[..10].to_uppercase();
+        self.finish(
+            request_id,
+            MpesaState::Completed {
+                receipt: receipt.clone(),
+            },
+        )
+        .await?;
+        Ok(receipt)
+    }
+
+    /// Fail a pending request, e.g. with "Request cancelled by user"
+    pub async fn fail(&self, request_id: &str, reason: &str) -> ApiResult<()> {
+        self.finish(
+            request_id,
+            MpesaState::Failed {
+                reason: reason.to_string(),
+            },
+        )
+        .await
+    }
+
+    pub async fn set_unavailable(&self, unavailable: bool) {
+        self.state.write().await.unavailable = unavailable;
+    }
+
+    pub async fn set_losing_replies(&self, losing: bool) {
+        self.state.write().await.losing_replies = losing;
+    }
+
+    pub async fn set_losing_requests(&self, losing: bool) {
+        self.state.write().await.losing_requests = losing;
+    }
+
+    pub async fn requests(&self) -> Vec<MockMpesaRequest> {
+        let mut requests: Vec<MockMpesaRequest> =
+            self.state.read().await.requests.values().cloned().collect();
+        requests.sort_by_key(|request| request.created_at);
+        requests
+    }
+
+    async fn finish(&self, request_id: &str, outcome: MpesaState) -> ApiResult<()> {
+        let mut state = self.state.write().await;
+        let request = state
+            .requests
+            .get_mut(request_id)
+            .ok_or_else(|| ApiError::NotFound {
+                resource: format!("M-Pesa request {}", request_id),
+            })?;
+        if request.state != MpesaState::Pending {
+            return Err(ApiError::Conflict {
+                message: format!("M-Pesa request {} has already finished", request_id),
+            });
+        }
+        request.state = outcome;
+        Ok(())
+    }
+
+    async fn accept(
+        &self,
+        kind: MockRequestKind,
+        phone: &str,
+        amount_kes: Decimal,
+        reference: &str,
+        payout_key: Option<&str>,
+    ) -> ApiResult<String> {
+        let mut state = self.state.write().await;
+        if state.unavailable {
+            return Err(ApiError::Server {
+                message: "M-Pesa is unavailable".to_string(),
+            });
+        }
+        let lost = || ApiError::Network {
+            message: "No reply from M-Pesa".to_string(),
+        };
+        if state.losing_requests {
+            return Err(lost());
+        }
+        if let Some(existing) = payout_key.and_then(|key| find_by_key(&state, key)) {
+            return Ok(existing);
+        }
+        let prefix = match kind {
+            MockRequestKind::StkPush => "ws_CO_",
+            MockRequestKind::B2cPayout => "AG_",
+        };
+        let request_id = format!("{}{}", prefix, Uuid::new_v4().simple());
+        state.requests.insert(
+            request_id.clone(),
+            MockMpesaRequest {
+                request_id: request_id.clone(),
+                kind,
+                phone: phone.to_string(),
+                amount_kes,
+                reference: reference.to_string(),
+                payout_key: payout_key.map(str::to_string),
+                state: MpesaState::Pending,
+                created_at: Utc::now(),
+            },
+        );
+        if state.losing_replies {
+            return Err(lost());
+        }
+        Ok(request_id)
+    }
+}
+
+fn find_by_key(state: &MockState, payout_key: &str) -> Option<String> {
+    state
+        .requests
+        .values()
+        .find(|request| request.payout_key.as_deref() == Some(payout_key))
+        .map(|request| request.request_id.clone())
+}
+
+#[async_trait]
+impl MpesaGateway for MockMpesaGateway {
+    async fn stk_push(
+        &self,
+        phone: &str,
+        amount_kes: Decimal,
+        reference: &str,
+    ) -> ApiResult<String> {
+        self.accept(MockRequestKind::StkPush, phone, amount_kes, reference, None)
+            .await
+    }
+
+    async fn b2c_payout(
+        &self,
+        phone: &str,
+        amount_kes: Decimal,
+        reference: &str,
+        payout_key: &str,
+    ) -> ApiResult<String> {
+        self.accept(
+            MockRequestKind::B2cPayout,
+            phone,
+            amount_kes,
+            reference,
+            Some(payout_key),
+        )
+        .await
+    }
+
+    async fn request_state(&self, request_id: &str) -> ApiResult<MpesaState> {
+        self.state
+            .read()
+            .await
+            .requests
+            .get(request_id)
+            .map(|request| request.state.clone())
+            .ok_or_else(|| ApiError::NotFound {
+                resource: format!("M-Pesa request {}", request_id),
+            })
+    }
+
+    async fn find_payout(&self, payout_key: &str) -> ApiResult<Option<String>> {
+        Ok(find_by_key(&*self.state.read().await, payout_key))
+    }
+}
+
+/// The M-Pesa gateway named by MPESA_GATEWAY; only `mock` is available
+pub fn mpesa_gateway_from_env() -> ApiResult<MockMpesaGateway> {
+    match std::env::var("MPESA_GATEWAY").unwrap_or_default().trim() {
+        "" | "mock" => Ok(MockMpesaGateway::new()),
+        other => Err(ApiError::Validation {
+            message: format!(
+                "Unknown M-Pesa gateway '{}'; only the mock gateway is available",
+                other
+            ),
+        }),
+    }
+}
@@ -3,6 +3,7 @@ pub const AUTH_BASE: &str = "/auth";
 pub const USERS_BASE: &str = "/users";
 pub const GROUPS_BASE: &str = "/groups";
 pub const WALLETS_BASE: &str = "/wallets";
+pub const SHARES_BASE: &str = "/shares";
 
 // Auth endpoints
 pub const LOGIN: &str = "/auth/login";
@@ -31,3 +32,9 @@ pub const CREATE_WALLET: &str = "/wallets";
 pub const DELETE_WALLET: &str = "/wallets";
 pub const GET_WALLET_TRANSACTIONS: &str = "/wallets";
 pub const GET_WALLET_BALANCE: &str = "/wallets";
+
+// Share endpoints
+pub const GET_SHARE_OFFERS: &str = "/shares/offers";
+pub const OFFER_SHARES: &str = "/shares/offer";
+pub const SUBSCRIBE_SHARES: &str = "/shares/subscribe";
+pub const SHARES_TRANSACTIONS: &str = "/shares/transactions";
@@ -251,6 +251,7 @@ impl NestJsGroupsApi {
 }
 
 #[cfg(test)]
+#[allow(unused_variables, clippy::assertions_on_constants, clippy::useless_vec)]
 mod tests {
     use super::*;
     use crate::api::config::ApiConfig;
@@ -83,6 +83,23 @@ impl JwtValidator {
         }
     }
 
+    /// Verify a session token's signature and expiry and decode its payload.
+    /// Session tokens carry the user in a nested `user` claim rather than `sub`.
+    #[cfg(feature = "ssr")]
+    pub fn validate_session_token<T: serde::de::DeserializeOwned>(
+        &self,
+        token: &str,
+    ) -> ApiResult<T> {
+        let mut validation = self.validation.clone();
+        validation.required_spec_claims.remove("sub");
+        validation.validate_aud = false;
+        decode::<T>(token, &self.decoding_key, &validation)
+            .map(|token_data| token_data.claims)
+            .map_err(|e| ApiError::Authentication {
+                message: format!("Invalid JWT token: {}", e),
+            })
+    }
+
     pub fn is_token_expired(&self, claims: &Claims) -> bool {
         let now = chrono::Utc::now().timestamp() as usize;
         claims.exp < now
@@ -98,6 +115,7 @@ impl JwtValidator {
 }
 
 #[cfg(test)]
+#[allow(unused_variables, clippy::assertions_on_constants)]
 mod tests {
     use super::*;
 
@@ -161,4 +179,40 @@ mod tests {
         assert!(validator.has_any_role(&claims, &["admin", "superadmin"]));
         assert!(!validator.has_any_role(&claims, &["superadmin", "moderator"]));
     }
+
+    #[test]
+    #[cfg(feature = "ssr")]
+    fn test_session_tokens_need_a_valid_signature_and_expiry() {
+        use jsonwebtoken::{encode, EncodingKey, Header};
+
+        let now = chrono::Utc::now().timestamp();
+        let sign = |secret: &str, exp: i64| {
+            let claims = serde_json::json!({
+                "user": { "id": "5b3f2a1c-0000-4000-8000-000000000001" },
+                "aud": "bitsacco",
+                "iat": now,
+                "nbf": now,
+                "exp": exp,
+            });
+            encode(
+                &Header::new(Algorithm::HS256),
+                &claims,
+                &EncodingKey::from_secret(secret.as_ref()),
+            )
+            .unwrap()
+        };
+        let validator = JwtValidator::new("test_secret");
+
+        let claims: serde_json::Value = validator
+            .validate_session_token(&sign("test_secret", now + 3600))
+            .unwrap();
+        assert_eq!(claims["user"]["id"], "5b3f2a1c-0000-4000-8000-000000000001");
+
+        assert!(validator
+            .validate_session_token::<serde_json::Value>(&sign("forged", now + 3600))
+            .is_err());
+        assert!(validator
+            .validate_session_token::<serde_json::Value>(&sign("test_secret", now - 3600))
+            .is_err());
+    }
 }
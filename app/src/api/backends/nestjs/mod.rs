@@ -4,6 +4,7 @@ pub mod endpoints;
 pub mod groups;
 pub mod jwt_validator;
 pub mod middleware;
+pub mod shares;
 pub mod users;
 pub mod wallets;
 
@@ -14,6 +15,7 @@ pub use client::NestJsClient;
 pub use groups::NestJsGroupsApi;
 pub use jwt_validator::JwtValidator;
 pub use middleware::MiddlewareChain;
+pub use shares::NestJsSharesApi;
 pub use users::NestJsUsersApi;
 pub use wallets::NestJsWalletsApi;
 
@@ -21,6 +23,7 @@ pub struct NestJsBackend {
     pub auth: NestJsAuthApi,
     pub users: NestJsUsersApi,
     pub groups: NestJsGroupsApi,
+    pub shares: NestJsSharesApi,
     pub wallets: NestJsWalletsApi,
 }
 
@@ -30,12 +33,14 @@ impl NestJsBackend {
         let auth = NestJsAuthApi::new(client.clone());
         let users = NestJsUsersApi::new(client.clone());
         let groups = NestJsGroupsApi::new(client.clone());
+        let shares = NestJsSharesApi::new(client.clone());
         let wallets = NestJsWalletsApi::new(client.clone());
 
         Ok(Self {
             auth,
             users,
             groups,
+            shares,
             wallets,
         })
     }
@@ -0,0 +1,636 @@
+use std::collections::HashSet;
+
+use async_trait::async_trait;
+use chrono::SubsecRound;
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+use crate::api::{
+    errors::{ApiError, ApiResult},
+    traits::shares::{
+        CreateShareOfferRequest, PaySharesRequest, ShareLedgerEntry, ShareLedgerEntryKind,
+        ShareOffer, ShareOfferStatus, ShareSubscription, ShareTransfer, ShareTransferFilter,
+        SharesApi, SubscribeSharesRequest, TransferSharesRequest,
+    },
+    types::{PaginatedResponse, PaginationQuery, User},
+};
+
+use super::client::NestJsClient;
+use super::endpoints;
+
+#[derive(Clone)]
+pub struct NestJsSharesApi {
+    client: NestJsClient,
+}
+
+impl NestJsSharesApi {
+    pub fn new(client: NestJsClient) -> Self {
+        Self { client }
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct OfferSharesRequest {
+    quantity: u64,
+    #[serde(rename = "availableFrom")]
+    available_from: String,
+    #[serde(rename = "availableTo")]
+    available_to: String,
+    // Not part of the core NestJS DTO yet, forwarded for backends that store them
+    title: String,
+    description: Option<String>,
+    #[serde(rename = "pricePerShare")]
+    price_per_share: String,
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct NestJsSubscribeSharesRequest {
+    #[serde(rename = "userId")]
+    user_id: String,
+    #[serde(rename = "offerId")]
+    offer_id: String,
+    quantity: u64,
+    // Not part of the core NestJS DTO yet; backends that store it echo it back on
+    // the transaction so the subscription can be told apart from identical ones
+    #[serde(rename = "idempotencyKey")]
+    idempotency_key: String,
+}
+
+#[async_trait]
+impl SharesApi for NestJsSharesApi {
+    async fn get_share_offer(&self, offer_id: Uuid) -> ApiResult<ShareOffer> {
+        // The NestJS backend only exposes the full offers listing
+        self.fetch_offers()
+            .await?
+            .into_iter()
+            .find(|offer| offer.id == offer_id)
+            .ok_or_else(|| ApiError::NotFound {
+                resource: format!("Share offer {}", offer_id),
+            })
+    }
+
+    async fn get_share_offers(
+        &self,
+        pagination: PaginationQuery,
+    ) -> ApiResult<PaginatedResponse<ShareOffer>> {
+        let offers = self.fetch_offers().await?;
+        Ok(PaginatedResponse::paginate(offers, &pagination))
+    }
+
+    async fn create_share_offer(&self, request: CreateShareOfferRequest) -> ApiResult<ShareOffer> {
+        // Whole seconds, so the dates can be matched against what the backend stores
+        let available_from = request
+            .available_from
+            .unwrap_or_else(chrono::Utc::now)
+            .trunc_subsecs(0);
+        let available_to = request.expires_at.trunc_subsecs(0);
+        let offer_request = OfferSharesRequest {
+            quantity: request.total_quantity,
+            available_from: available_from.to_rfc3339(),
+            available_to: available_to.to_rfc3339(),
+            title: request.title.clone(),
+            description: request.description.clone(),
+            price_per_share: request.price_per_share.to_string(),
+        };
+
+        let known: HashSet<Uuid> = self
+            .fetch_offers()
+            .await?
+            .into_iter()
+            .map(|offer| offer.id)
+            .collect();
+
+        let req = self.client.post(endpoints::OFFER_SHARES);
+        let response: serde_json::Value = self.client.send_json(req, &offer_request).await?;
+
+        // The backend responds with the refreshed offers list; ours is the one new
+        // offer with the quantity and dates we sent
+        let mut created: Vec<ShareOffer> = self
+            .convert_offers(&response)?
+            .into_iter()
+            .filter(|offer| {
+                !known.contains(&offer.id)
+                    && offer.total_quantity == request.total_quantity
+                    && offer.available_from == available_from
+                    && offer.expires_at == available_to
+            })
+            .collect();
+
+        match created.len() {
+            1 => Ok(created.remove(0)),
+            0 => Err(ApiError::Server {
+                message: format!(
+                    "The backend did not record an offer of {} shares",
+                    request.total_quantity
+                ),
+            }),
+            _ => Err(ApiError::Server {
+                message: format!(
+                    "The backend recorded several offers of {} shares at once; check the \
+                     offers list before creating another",
+                    request.total_quantity
+                ),
+            }),
+        }
+    }
+
+    async fn subscribe_shares(
+        &self,
+        request: SubscribeSharesRequest,
+    ) -> ApiResult<ShareSubscription> {
+        if request.quantity == 0 {
+            return Err(ApiError::Validation {
+                message: "Subscription quantity must be greater than zero".to_string(),
+            });
+        }
+
+        // The member's transactions before subscribing, so a backend that doesn't
+        // echo the key still leaves exactly one new transaction to find
+        let known: HashSet<Uuid> = self
+            .fetch_subscriptions()
+            .await?
+            .into_iter()
+            .filter(|sub| sub.user_id == request.user_id)
+            .map(|sub| sub.id)
+            .collect();
+
+        let idempotency_key = Uuid::new_v4().to_string();
+        let subscribe_request = NestJsSubscribeSharesRequest {
+            user_id: request.user_id.to_string(),
+            offer_id: request.offer_id.to_string(),
+            quantity: request.quantity,
+            idempotency_key: idempotency_key.clone(),
+        };
+
+        let req = self.client.post(endpoints::SUBSCRIBE_SHARES);
+        let response: serde_json::Value = self.client.send_json(req, &subscribe_request).await?;
+
+        self.find_subscription(&response, &request, &idempotency_key, &known)
+    }
+
+    async fn pay_for_shares(
+        &self,
+        request: PaySharesRequest,
+        _payer: &User,
+    ) -> ApiResult<ShareSubscription> {
+        // The NestJS backend settles subscriptions itself when they are made
+        Err(ApiError::NotFound {
+            resource: format!(
+                "Share payment operation not supported by backend for subscription {}",
+                request.subscription_id
+            ),
+        })
+    }
+
+    async fn get_member_subscriptions(&self, user_id: Uuid) -> ApiResult<Vec<ShareSubscription>> {
+        let mut subscriptions: Vec<ShareSubscription> = self
+            .fetch_subscriptions()
+            .await?
+            .into_iter()
+            .filter(|sub| sub.user_id == user_id)
+            .collect();
+        subscriptions.sort_by_key(|sub| std::cmp::Reverse(sub.created_at));
+        Ok(subscriptions)
+    }
+
+    async fn get_offer_subscriptions(
+        &self,
+        offer_id: Uuid,
+        pagination: PaginationQuery,
+    ) -> ApiResult<PaginatedResponse<ShareSubscription>> {
+        // No per-offer endpoint exists, so filter the full transaction list client-side
+        let subscriptions: Vec<ShareSubscription> = self
+            .fetch_subscriptions()
+            .await?
+            .into_iter()
+            .filter(|sub| sub.offer_id == offer_id)
+            .collect();
+
+        Ok(PaginatedResponse::paginate(subscriptions, &pagination))
+    }
+
+    async fn close_share_offer(&self, offer_id: Uuid) -> ApiResult<ShareOffer> {
+        // The NestJS backend doesn't have an endpoint for closing offers early;
+        // offers close when their availableTo date passes
+        Err(ApiError::NotFound {
+            resource: format!(
+                "Close share offer operation not supported by backend for offer {}",
+                offer_id
+            ),
+        })
+    }
+
+    async fn request_share_transfer(
+        &self,
+        request: TransferSharesRequest,
+        _maker: &User,
+    ) -> ApiResult<ShareTransfer> {
+        // The NestJS backend applies transfers immediately with no review step,
+        // so routing them there would bypass admin approval
+        Err(ApiError::NotFound {
+            resource: format!(
+                "Share transfer approval workflow not supported by backend for member {}",
+                request.from_user_id
+            ),
+        })
+    }
+
+    async fn approve_share_transfer(
+        &self,
+        transfer_id: Uuid,
+        _reviewer: &User,
+    ) -> ApiResult<ShareTransfer> {
+        Err(ApiError::NotFound {
+            resource: format!(
+                "Approve share transfer operation not supported by backend for transfer {}",
+                transfer_id
+            ),
+        })
+    }
+
+    async fn reject_share_transfer(
+        &self,
+        transfer_id: Uuid,
+        _reviewer: &User,
+        _reason: Option<String>,
+    ) -> ApiResult<ShareTransfer> {
+        Err(ApiError::NotFound {
+            resource: format!(
+                "Reject share transfer operation not supported by backend for transfer {}",
+                transfer_id
+            ),
+        })
+    }
+
+    async fn get_share_transfers(
+        &self,
+        _filter: ShareTransferFilter,
+        pagination: PaginationQuery,
+    ) -> ApiResult<PaginatedResponse<ShareTransfer>> {
+        // No transfer requests can exist without the approval workflow
+        Ok(PaginatedResponse::paginate(Vec::new(), &pagination))
+    }
+
+    async fn get_member_share_ledger(&self, user_id: Uuid) -> ApiResult<Vec<ShareLedgerEntry>> {
+        let mut subscriptions: Vec<ShareSubscription> = self
+            .fetch_subscriptions()
+            .await?
+            .into_iter()
+            .filter(|sub| sub.user_id == user_id)
+            .collect();
+        subscriptions.sort_by_key(|sub| sub.created_at);
+
+        Ok(build_subscription_ledger(subscriptions))
+    }
+}
+
+impl NestJsSharesApi {
+    async fn fetch_offers(&self) -> ApiResult<Vec<ShareOffer>> {
+        let req = self.client.get(endpoints::GET_SHARE_OFFERS);
+        let response: serde_json::Value = self.client.send(req).await?;
+        self.convert_offers(&response)
+    }
+
+    async fn fetch_subscriptions(&self) -> ApiResult<Vec<ShareSubscription>> {
+        let req = self.client.get(endpoints::SHARES_TRANSACTIONS);
+        let response: serde_json::Value = self.client.send(req).await?;
+
+        let transactions = response
+            .get("shares")
+            .and_then(|v| v.get("transactions"))
+            .and_then(|v| v.as_array())
+            .ok_or_else(|| ApiError::Serialization {
+                message: "Invalid response format: missing 'shares.transactions' array".to_string(),
+            })?;
+
+        Ok(transactions
+            .iter()
+            .filter_map(|tx| self.convert_subscription(tx.clone()).ok())
+            .collect())
+    }
+
+    /// Pick our subscription out of the member's refreshed share history: the
+    /// transaction carrying our idempotency key or, if the backend doesn't store
+    /// keys, the only new transaction for the same offer and quantity. Reporting
+    /// success for a subscription we can't single out would hide a failed one.
+    fn find_subscription(
+        &self,
+        response: &serde_json::Value,
+        request: &SubscribeSharesRequest,
+        idempotency_key: &str,
+        known: &HashSet<Uuid>,
+    ) -> ApiResult<ShareSubscription> {
+        let transactions = response
+            .get("shares")
+            .and_then(|v| v.get("transactions"))
+            .and_then(|v| v.as_array())
+            .cloned()
+            .unwrap_or_default();
+
+        let keyed = transactions
+            .iter()
+            .find(|tx| tx.get("idempotencyKey").and_then(|v| v.as_str()) == Some(idempotency_key))
+            .and_then(|tx| self.convert_subscription(tx.clone()).ok())
+            .filter(|sub| sub.user_id == request.user_id);
+        if let Some(subscription) = keyed {
+            return Ok(subscription);
+        }
+
+        let mut new: Vec<ShareSubscription> = transactions
+            .into_iter()
+            .filter(|tx| tx.get("idempotencyKey").is_none())
+            .filter_map(|tx| self.convert_subscription(tx).ok())
+            .filter(|sub| {
+                sub.user_id == request.user_id
+                    && sub.offer_id == request.offer_id
+                    && sub.quantity == request.quantity
+                    && !known.contains(&sub.id)
+            })
+            .collect();
+
+        match new.len() {
+            1 => Ok(new.remove(0)),
+            0 => Err(ApiError::Server {
+                message: format!(
+                    "The backend did not record a subscription of {} shares in offer {}",
+                    request.quantity, request.offer_id
+                ),
+            }),
+            _ => Err(ApiError::Server {
+                message: format!(
+                    "The backend recorded several subscriptions of {} shares in offer {} at once; \
+                     check the member's share history before subscribing again",
+                    request.quantity, request.offer_id
+                ),
+            }),
+        }
+    }
+
+    /// Convert the `{ offers: [...] }` payload returned by the NestJS backend
+    fn convert_offers(&self, response: &serde_json::Value) -> ApiResult<Vec<ShareOffer>> {
+        let offers = response
+            .get("offers")
+            .and_then(|v| v.as_array())
+            .ok_or_else(|| ApiError::Serialization {
+                message: "Invalid response format: missing 'offers' array".to_string(),
+            })?;
+
+        offers
+            .iter()
+            .map(|offer| self.convert_offer(offer.clone()))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| ApiError::Serialization {
+                message: format!("Failed to convert share offers: {}", e),
+            })
+    }
+
+    /// Convert a share offer from the NestJS backend format to our ShareOffer structure
+    fn convert_offer(&self, offer: serde_json::Value) -> Result<ShareOffer, String> {
+        let id = offer
+            .get("id")
+            .and_then(|v| v.as_str())
+            .and_then(|s| Uuid::parse_str(s).ok())
+            .ok_or_else(|| "Missing or invalid offer ID".to_string())?;
+
+        let total_quantity = offer.get("quantity").and_then(|v| v.as_u64()).unwrap_or(0);
+        let subscribed_quantity = offer
+            .get("subscribedQuantity")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0);
+
+        let price_per_share = offer.get("pricePerShare").and_then(|v| match v {
+            serde_json::Value::String(s) => s.parse::<Decimal>().ok(),
+            serde_json::Value::Number(n) => n.to_string().parse::<Decimal>().ok(),
+            _ => None,
+        });
+
+        let now = chrono::Utc::now();
+        let created_at = parse_timestamp(&offer, "createdAt").unwrap_or(now);
+        let updated_at = parse_timestamp(&offer, "updatedAt").unwrap_or(created_at);
+        let available_from = parse_timestamp(&offer, "availableFrom").unwrap_or(created_at);
+        let expires_at = parse_timestamp(&offer, "availableTo")
+            .ok_or_else(|| format!("Missing or invalid availableTo for offer {}", id))?;
+
+        let mut share_offer = ShareOffer {
+            id,
+            title: offer
+                .get("title")
+                .and_then(|v| v.as_str())
+                .map(str::to_string)
+                .unwrap_or_else(|| format!("Share offer {}", available_from.format("%Y-%m-%d"))),
+            description: offer
+                .get("description")
+                .and_then(|v| v.as_str())
+                .map(str::to_string),
+            total_quantity,
+            subscribed_quantity,
+            price_per_share,
+            status: ShareOfferStatus::Open,
+            available_from,
+            expires_at,
+            created_at,
+            updated_at,
+        };
+        share_offer.status = share_offer.effective_status(now);
+
+        Ok(share_offer)
+    }
+
+    /// Convert a share transaction from the NestJS backend into a subscription
+    fn convert_subscription(&self, tx: serde_json::Value) -> Result<ShareSubscription, String> {
+        let id = tx
+            .get("id")
+            .and_then(|v| v.as_str())
+            .and_then(|s| Uuid::parse_str(s).ok())
+            .ok_or_else(|| "Missing or invalid share transaction ID".to_string())?;
+
+        let offer_id = tx
+            .get("offerId")
+            .and_then(|v| v.as_str())
+            .and_then(|s| Uuid::parse_str(s).ok())
+            .ok_or_else(|| "Missing or invalid offer ID".to_string())?;
+
+        let user_id = tx
+            .get("userId")
+            .and_then(|v| v.as_str())
+            .and_then(|s| Uuid::parse_str(s).ok())
+            .ok_or_else(|| "Missing or invalid user ID".to_string())?;
+
+        Ok(ShareSubscription {
+            id,
+            offer_id,
+            user_id,
+            quantity: tx.get("quantity").and_then(|v| v.as_u64()).unwrap_or(0),
+            price_per_share: None,
+            paid: Decimal::ZERO,
+            created_at: parse_timestamp(&tx, "createdAt").unwrap_or_else(chrono::Utc::now),
+        })
+    }
+}
+
+/// Build ledger entries with running balances from a member's subscriptions,
+/// which must already be sorted oldest first
+fn build_subscription_ledger(subscriptions: Vec<ShareSubscription>) -> Vec<ShareLedgerEntry> {
+    let mut balance = 0u64;
+    subscriptions
+        .into_iter()
+        .map(|sub| {
+            balance += sub.quantity;
+            ShareLedgerEntry {
+                id: Uuid::new_v4(),
+                user_id: sub.user_id,
+                kind: ShareLedgerEntryKind::Subscription,
+                quantity: sub.quantity as i64,
+                balance_after: balance,
+                reference_id: sub.id,
+                counterparty_id: None,
+                price_per_share: sub.price_per_share,
+                created_at: sub.created_at,
+            }
+        })
+        .collect()
+}
+
+fn parse_timestamp(value: &serde_json::Value, key: &str) -> Option<chrono::DateTime<chrono::Utc>> {
+    value
+        .get(key)
+        .and_then(|v| v.as_str())
+        .and_then(|s| chrono::DateTime::parse_from_rfc3339(s).ok())
+        .map(|dt| dt.with_timezone(&chrono::Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::config::ApiConfig;
+
+    fn create_test_client() -> NestJsClient {
+        let config = ApiConfig::default();
+        NestJsClient::new(&config).expect("Failed to create test client")
+    }
+
+    #[test]
+    fn test_offer_conversion() {
+        let shares_api = NestJsSharesApi::new(create_test_client());
+        let offer_id = Uuid::new_v4();
+
+        let offer_json = serde_json::json!({
+            "id": offer_id.to_string(),
+            "quantity": 1000,
+            "subscribedQuantity": 250,
+            "availableFrom": "2099-01-01T00:00:00Z",
+            "availableTo": "2099-12-31T00:00:00Z",
+            "createdAt": "2099-01-01T00:00:00Z",
+            "updatedAt": "2099-01-01T00:00:00Z"
+        });
+
+        let offer = shares_api.convert_offer(offer_json).unwrap();
+        assert_eq!(offer.id, offer_id);
+        assert_eq!(offer.available_quantity(), 750);
+        assert_eq!(offer.progress(), 0.25);
+        assert_eq!(offer.status, ShareOfferStatus::Open);
+        assert!(offer.price_per_share.is_none());
+    }
+
+    #[test]
+    fn test_expired_offer_conversion() {
+        let shares_api = NestJsSharesApi::new(create_test_client());
+
+        let offer_json = serde_json::json!({
+            "id": Uuid::new_v4().to_string(),
+            "quantity": 100,
+            "subscribedQuantity": 10,
+            "availableFrom": "2020-01-01T00:00:00Z",
+            "availableTo": "2020-06-30T00:00:00Z",
+            "pricePerShare": "1000"
+        });
+
+        let offer = shares_api.convert_offer(offer_json).unwrap();
+        assert_eq!(offer.status, ShareOfferStatus::Expired);
+        assert_eq!(offer.total_value(), Some(Decimal::from(100_000)));
+    }
+
+    #[test]
+    fn test_subscription_conversion_requires_offer() {
+        let shares_api = NestJsSharesApi::new(create_test_client());
+
+        let tx_json = serde_json::json!({
+            "id": Uuid::new_v4().to_string(),
+            "userId": Uuid::new_v4().to_string(),
+            "quantity": 5
+        });
+
+        assert!(shares_api.convert_subscription(tx_json).is_err());
+    }
+
+    #[test]
+    fn test_subscription_ledger_running_balance() {
+        let user_id = Uuid::new_v4();
+        let subscription = |quantity| ShareSubscription {
+            id: Uuid::new_v4(),
+            offer_id: Uuid::new_v4(),
+            user_id,
+            quantity,
+            price_per_share: None,
+            paid: Decimal::ZERO,
+            created_at: chrono::Utc::now(),
+        };
+
+        let ledger = build_subscription_ledger(vec![subscription(10), subscription(5)]);
+        assert_eq!(ledger.len(), 2);
+        assert_eq!(ledger[0].balance_after, 10);
+        assert_eq!(ledger[1].balance_after, 15);
+        assert_eq!(ledger[1].kind, ShareLedgerEntryKind::Subscription);
+    }
+
+    #[test]
+    fn test_subscription_is_found_by_key_or_as_the_only_new_one() {
+        let shares_api = NestJsSharesApi::new(create_test_client());
+        let (user_id, offer_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let request = SubscribeSharesRequest {
+            offer_id,
+            user_id,
+            quantity: 10,
+        };
+        let tx = |id: Uuid, key: Option<&str>| {
+            let mut tx = serde_json::json!({
+                "id": id.to_string(),
+                "offerId": offer_id.to_string(),
+                "userId": user_id.to_string(),
+                "quantity": 10,
+                "createdAt": "2099-01-01T00:00:00Z"
+            });
+            if let Some(key) = key {
+                tx["idempotencyKey"] = serde_json::json!(key);
+            }
+            tx
+        };
+        let history =
+            |txs: Vec<serde_json::Value>| serde_json::json!({ "shares": { "transactions": txs } });
+        let (earlier, ours, other) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let known = HashSet::from([earlier]);
+
+        // An identical earlier subscription isn't mistaken for ours
+        let response = history(vec![tx(earlier, None), tx(ours, None)]);
+        let found = shares_api
+            .find_subscription(&response, &request, "key", &known)
+            .unwrap();
+        assert_eq!(found.id, ours);
+
+        let response = history(vec![tx(other, Some("other")), tx(ours, Some("key"))]);
+        let found = shares_api
+            .find_subscription(&response, &request, "key", &known)
+            .unwrap();
+        assert_eq!(found.id, ours);
+
+        // Nothing new, or two identical new ones, can't be told apart
+        let response = history(vec![tx(earlier, None)]);
+        assert!(shares_api
+            .find_subscription(&response, &request, "key", &known)
+            .is_err());
+        let response = history(vec![tx(ours, None), tx(other, None)]);
+        assert!(shares_api
+            .find_subscription(&response, &request, "key", &known)
+            .is_err());
+    }
+}
@@ -178,6 +178,7 @@ impl UsersApi for NestJsUsersApi {
 }
 
 #[cfg(test)]
+#[allow(unused_variables, clippy::assertions_on_constants, clippy::erasing_op)]
 mod tests {
     use super::*;
     use crate::api::config::ApiConfig;
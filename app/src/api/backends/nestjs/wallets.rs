@@ -4,8 +4,8 @@ use uuid::Uuid;
 use crate::api::{
     errors::ApiResult,
     traits::wallets::{
-        CreateWalletRequest, TransactionStatus, TransactionType, Wallet, WalletTransaction,
-        WalletType, WalletsApi,
+        CreateWalletRequest, TransactionFilter, TransactionStatus, TransactionType, Wallet,
+        WalletTransaction, WalletType, WalletsApi,
     },
     types::{PaginatedResponse, PaginationQuery},
 };
@@ -55,6 +55,7 @@ struct CreateSoloWalletRequest {
     // Additional fields as needed based on the NestJS backend
 }
 
+#[allow(dead_code)]
 #[derive(serde::Serialize, serde::Deserialize)]
 struct CreateChamaWalletRequest {
     #[serde(rename = "chamaId")]
@@ -104,8 +105,9 @@ impl WalletsApi for NestJsWalletsApi {
 
     async fn create_wallet(&self, request: CreateWalletRequest) -> ApiResult<Wallet> {
         match request.wallet_type {
-            WalletType::Fedimint => {
-                // Create a solo wallet
+            WalletType::Fedimint | WalletType::Lightning => {
+                // Create a solo wallet; solo wallets hold federation ecash and take
+                // deposits over Lightning, so both wallet types map onto them
                 let solo_request = CreateSoloWalletRequest {
                     user_id: request.user_id.to_string(),
                 };
@@ -121,13 +123,13 @@ impl WalletsApi for NestJsWalletsApi {
                     user_id: request.user_id,
                     name: request.name,
                     balance: 0, // Initial balance
-                    wallet_type: WalletType::Fedimint,
+                    wallet_type: request.wallet_type,
                     created_at: chrono::Utc::now(),
                     updated_at: chrono::Utc::now(),
                 })
             }
-            WalletType::Lightning | WalletType::OnChain => {
-                // These wallet types are not directly supported by the current backend
+            WalletType::OnChain => {
+                // On-chain wallets are not directly supported by the current backend
                 Err(crate::api::errors::ApiError::NotFound {
                     resource: format!(
                         "Wallet type {:?} not supported by backend",
@@ -185,6 +187,19 @@ impl WalletsApi for NestJsWalletsApi {
         })
     }
 
+    async fn get_transactions(
+        &self,
+        _filter: TransactionFilter,
+        _pagination: PaginationQuery,
+    ) -> ApiResult<PaginatedResponse<WalletTransaction>> {
+        // The NestJS backend only lists transactions per solo wallet
+        Err(crate::api::errors::ApiError::NotFound {
+            resource:
+                "Transaction search is not supported by the NestJS backend; set API_BACKEND=rust"
+                    .to_string(),
+        })
+    }
+
     async fn get_wallet_balance(&self, _wallet_id: Uuid) -> ApiResult<u64> {
         // The NestJS backend doesn't have a direct balance endpoint
         // We would need to either:
@@ -259,6 +274,7 @@ impl NestJsWalletsApi {
 }
 
 #[cfg(test)]
+#[allow(unused_variables, clippy::assertions_on_constants)]
 mod tests {
     use super::*;
     use crate::api::config::ApiConfig;
@@ -0,0 +1,194 @@
+// System alert acknowledgements for the Rust backend
+// Acknowledging or snoozing an alert replaces whatever response it had before,
+// so an admin can acknowledge an alert someone else snoozed. Snoozes are dropped
+// once they run out. Alerts come from the dashboard whichever backend is
+// configured, so acknowledgements are always kept here, on disk.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use super::JsonStore;
+use crate::api::{
+    errors::{ApiError, ApiResult},
+    traits::alerts::{AlertAcknowledgement, AlertsApi, MAX_SNOOZE_MINUTES},
+};
+
+#[derive(Clone, Default)]
+pub struct RustAlertsApi {
+    acknowledgements: Arc<RwLock<HashMap<String, AlertAcknowledgement>>>,
+    store: JsonStore,
+}
+
+impl RustAlertsApi {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keep acknowledgements in `store`, starting from the ones it already holds
+    pub fn with_store(mut self, store: JsonStore) -> ApiResult<Self> {
+        let acknowledgements: HashMap<String, AlertAcknowledgement> = store.load()?;
+        self.acknowledgements = Arc::new(RwLock::new(acknowledgements));
+        self.store = store;
+        Ok(self)
+    }
+
+    async fn record(
+        &self,
+        alert_id: &str,
+        acknowledged_by: Uuid,
+        snooze: Option<Duration>,
+    ) -> ApiResult<AlertAcknowledgement> {
+        let alert_id = alert_id.trim();
+        if alert_id.is_empty() {
+            return Err(ApiError::Validation {
+                message: "An alert id is required".to_string(),
+            });
+        }
+        let now = Utc::now();
+        let acknowledgement = AlertAcknowledgement {
+            alert_id: alert_id.to_string(),
+            acknowledged_by,
+            acknowledged_at: now,
+            snoozed_until: snooze.map(|snooze| now + snooze),
+        };
+        let mut acknowledgements = self.acknowledgements.write().await;
+        let previous = acknowledgements.insert(alert_id.to_string(), acknowledgement.clone());
+        if let Err(e) = self.store.save(&*acknowledgements) {
+            match previous {
+                Some(previous) => acknowledgements.insert(alert_id.to_string(), previous),
+                None => acknowledgements.remove(alert_id),
+            };
+            return Err(e);
+        }
+        Ok(acknowledgement)
+    }
+}
+
+#[async_trait]
+impl AlertsApi for RustAlertsApi {
+    async fn list_acknowledgements(&self) -> ApiResult<Vec<AlertAcknowledgement>> {
+        let now = Utc::now();
+        let mut acknowledgements = self.acknowledgements.write().await;
+        let before = acknowledgements.len();
+        acknowledgements.retain(|_, acknowledgement| acknowledgement.in_force(now));
+        if acknowledgements.len() != before {
+            // Expired snoozes are gone from memory either way; a failed save only
+            // leaves them on disk until the next change
+            if let Err(e) = self.store.save(&*acknowledgements) {
+                tracing::warn!("Could not save alert acknowledgements: {}", e);
+            }
+        }
+        let mut listed: Vec<AlertAcknowledgement> = acknowledgements.values().cloned().collect();
+        listed.sort_by_key(|acknowledgement| std::cmp::Reverse(acknowledgement.acknowledged_at));
+        Ok(listed)
+    }
+
+    async fn acknowledge_alert(
+        &self,
+        alert_id: &str,
+        acknowledged_by: Uuid,
+    ) -> ApiResult<AlertAcknowledgement> {
+        self.record(alert_id, acknowledged_by, None).await
+    }
+
+    async fn snooze_alert(
+        &self,
+        alert_id: &str,
+        acknowledged_by: Uuid,
+        minutes: u32,
+    ) -> ApiResult<AlertAcknowledgement> {
+        if minutes == 0 || minutes > MAX_SNOOZE_MINUTES {
+            return Err(ApiError::Validation {
+                message: format!(
+                    "Alerts can be snoozed for 1 to {} minutes",
+                    MAX_SNOOZE_MINUTES
+                ),
+            });
+        }
+        self.record(
+            alert_id,
+            acknowledged_by,
+            Some(Duration::minutes(i64::from(minutes))),
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_acknowledgements_are_shared_and_snoozes_expire() {
+        let api = RustAlertsApi::new();
+        let (alice, bob) = (Uuid::new_v4(), Uuid::new_v4());
+
+        let snoozed = api.snooze_alert("alert-1", alice, 60).await.unwrap();
+        assert!(snoozed.in_force(Utc::now()));
+        assert!(!snoozed.in_force(Utc::now() + Duration::minutes(61)));
+
+        // Anyone can acknowledge an alert another admin snoozed
+        let acknowledged = api.acknowledge_alert("alert-1", bob).await.unwrap();
+        assert_eq!(acknowledged.snoozed_until, None);
+        api.snooze_alert("alert-2", alice, 30).await.unwrap();
+        let listed = api.list_acknowledgements().await.unwrap();
+        assert_eq!(listed.len(), 2);
+        assert!(listed
+            .iter()
+            .any(|ack| ack.alert_id == "alert-1" && ack.acknowledged_by == bob));
+
+        // An expired snooze is dropped
+        api.acknowledgements
+            .write()
+            .await
+            .get_mut("alert-2")
+            .unwrap()
+            .snoozed_until = Some(Utc::now() - Duration::minutes(1));
+        let listed = api.list_acknowledgements().await.unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].alert_id, "alert-1");
+
+        assert!(matches!(
+            api.snooze_alert("alert-3", alice, 0).await,
+            Err(ApiError::Validation { .. })
+        ));
+        assert!(matches!(
+            api.snooze_alert("alert-3", alice, MAX_SNOOZE_MINUTES + 1)
+                .await,
+            Err(ApiError::Validation { .. })
+        ));
+        assert!(matches!(
+            api.acknowledge_alert(" ", alice).await,
+            Err(ApiError::Validation { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_acknowledgements_survive_a_restart() {
+        let dir = std::env::temp_dir().join(format!("bitsacco-alerts-{}", Uuid::new_v4()));
+        let path = dir.join("alert-acks.json");
+        let admin = Uuid::new_v4();
+
+        let api = RustAlertsApi::new()
+            .with_store(JsonStore::at(&path))
+            .unwrap();
+        api.acknowledge_alert("alert-1", admin).await.unwrap();
+        api.snooze_alert("alert-2", admin, 30).await.unwrap();
+
+        let restarted = RustAlertsApi::new()
+            .with_store(JsonStore::at(&path))
+            .unwrap();
+        let listed = restarted.list_acknowledgements().await.unwrap();
+        assert_eq!(listed.len(), 2);
+        assert!(listed
+            .iter()
+            .all(|ack| ack.acknowledged_by == admin && ack.in_force(Utc::now())));
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,532 @@
+// In-process chama contribution plans for the Rust backend
+// Members contribute by paying from their plan wallet into the chama's
+// contributions pool; reconciliation reads those contribution transactions only.
+// A wallet pays into one plan at most, so its contributions belong to that plan,
+// and plan members are checked against the chama's own member list.
+// Plans are saved to a JSON store when they are created.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use super::{wallets::signed_sats, JsonStore, RustWalletsApi};
+use crate::api::{
+    errors::{ApiError, ApiResult},
+    traits::{
+        contributions::{
+            reconcile_contributions, ContributionPlan, ContributionReconciliation,
+            ContributionsApi, CreateContributionPlanRequest,
+        },
+        groups::GroupsApi,
+        ledger::{AccountKey, AccountPurpose, LedgerCurrency, PostingRequest},
+        wallets::{TransactionType, WalletTransaction, WalletsApi},
+    },
+    types::{PaginationQuery, User},
+};
+
+#[derive(Default, serde::Serialize, serde::Deserialize)]
+struct ContributionsState {
+    plans: HashMap<Uuid, ContributionPlan>,
+}
+
+#[derive(Clone)]
+pub struct RustContributionsApi {
+    state: Arc<RwLock<ContributionsState>>,
+    wallets: RustWalletsApi,
+    /// Where chamas and their members are kept
+    groups: Arc<dyn GroupsApi>,
+    store: JsonStore,
+}
+
+impl RustContributionsApi {
+    pub fn new(wallets: RustWalletsApi, groups: Arc<dyn GroupsApi>) -> Self {
+        Self {
+            state: Arc::default(),
+            wallets,
+            groups,
+            store: JsonStore::in_memory(),
+        }
+    }
+
+    /// Keep plans in `store`, starting from what it already holds
+    pub fn with_store(mut self, store: JsonStore) -> ApiResult<Self> {
+        let state: ContributionsState = store.load()?;
+        self.state = Arc::new(RwLock::new(state));
+        self.store = store;
+        Ok(self)
+    }
+}
+
+#[async_trait]
+impl ContributionsApi for RustContributionsApi {
+    async fn get_contribution_plans(&self, group_id: Uuid) -> ApiResult<Vec<ContributionPlan>> {
+        let state = self.state.read().await;
+        let mut plans: Vec<ContributionPlan> = state
+            .plans
+            .values()
+            .filter(|plan| plan.group_id == group_id)
+            .cloned()
+            .collect();
+        plans.sort_by_key(|plan| plan.created_at);
+        Ok(plans)
+    }
+
+    async fn get_contribution_plan(&self, plan_id: Uuid) -> ApiResult<ContributionPlan> {
+        self.state
+            .read()
+            .await
+            .plans
+            .get(&plan_id)
+            .cloned()
+            .ok_or_else(|| ApiError::NotFound {
+                resource: format!("Contribution plan {}", plan_id),
+            })
+    }
+
+    async fn create_contribution_plan(
+        &self,
+        request: CreateContributionPlanRequest,
+    ) -> ApiResult<ContributionPlan> {
+        if request.name.trim().is_empty() {
+            return Err(ApiError::Validation {
+                message: "Contribution plan name is required".to_string(),
+            });
+        }
+        if request.amount == 0 {
+            return Err(ApiError::Validation {
+                message: "Contribution amount must be greater than zero".to_string(),
+            });
+        }
+        if request.members.is_empty() {
+            return Err(ApiError::Validation {
+                message: "A contribution plan needs at least one member".to_string(),
+            });
+        }
+
+        let chama = self.groups.get_group(request.group_id).await?;
+        let mut seen = HashSet::new();
+        for member in &request.members {
+            if !seen.insert(member.user_id) {
+                return Err(ApiError::Validation {
+                    message: format!("Member {} is listed more than once", member.user_id),
+                });
+            }
+            if !chama.has_member(member.user_id) {
+                return Err(ApiError::Validation {
+                    message: format!("{} is not a member of {}", member.user_id, chama.name),
+                });
+            }
+            let wallet = self.wallets.get_wallet(member.wallet_id).await?;
+            if wallet.user_id != member.user_id {
+                return Err(ApiError::Validation {
+                    message: format!(
+                        "Wallet {} does not belong to member {}",
+                        member.wallet_id, member.user_id
+                    ),
+                });
+            }
+        }
+
+        let plan = ContributionPlan {
+            id: Uuid::new_v4(),
+            group_id: request.group_id,
+            name: request.name.trim().to_string(),
+            amount: request.amount,
+            frequency: request.frequency,
+            start_date: request.start_date,
+            members: request.members,
+            created_at: Utc::now(),
+        };
+
+        // Contributions aren't tagged with a plan, so a wallet that paid into two
+        // plans would clear arrears in both with one payment
+        let mut state = self.state.write().await;
+        if let Some((wallet_id, other)) = plan.members.iter().find_map(|member| {
+            state
+                .plans
+                .values()
+                .find(|other| {
+                    other
+                        .members
+                        .iter()
+                        .any(|m| m.wallet_id == member.wallet_id)
+                })
+                .map(|other| (member.wallet_id, other))
+        }) {
+            return Err(ApiError::Conflict {
+                message: format!(
+                    "Wallet {} already pays into {}; members need a separate wallet for each plan",
+                    wallet_id, other.name
+                ),
+            });
+        }
+        state.plans.insert(plan.id, plan.clone());
+        self.store.save_or_undo(&mut *state, |state| {
+            state.plans.remove(&plan.id);
+        })?;
+        drop(state);
+
+        // Plan members are the chama's members as far as withdrawal limits go
+        self.wallets
+            .policy()
+            .add_group_members(plan.group_id, plan.members.iter().map(|m| m.user_id))
+            .await;
+        Ok(plan)
+    }
+
+    async fn pay_contribution(
+        &self,
+        plan_id: Uuid,
+        payer: &User,
+        amount: u64,
+    ) -> ApiResult<WalletTransaction> {
+        if amount == 0 {
+            return Err(ApiError::Validation {
+                message: "Contribution amount must be greater than zero".to_string(),
+            });
+        }
+        let plan = self.get_contribution_plan(plan_id).await?;
+        let member = plan
+            .members
+            .iter()
+            .find(|member| member.user_id == payer.id)
+            .ok_or_else(|| ApiError::Authorization {
+                message: "Only members of the plan can contribute to it".to_string(),
+            })?;
+
+        self.wallets
+            .post(
+                member.wallet_id,
+                -signed_sats(amount)?,
+                TransactionType::Contribution,
+                vec![PostingRequest::credit(
+                    AccountKey::chama(
+                        plan.group_id,
+                        AccountPurpose::ContributionsPool,
+                        LedgerCurrency::Sats,
+                    ),
+                    amount,
+                )],
+            )
+            .await
+    }
+
+    async fn reconcile_contribution_plan(
+        &self,
+        plan_id: Uuid,
+        as_of: DateTime<Utc>,
+    ) -> ApiResult<ContributionReconciliation> {
+        let plan = self.get_contribution_plan(plan_id).await?;
+
+        let mut transactions = HashMap::new();
+        for member in &plan.members {
+            let history = self
+                .wallets
+                .get_wallet_transactions(
+                    member.wallet_id,
+                    PaginationQuery {
+                        page: Some(1),
+                        limit: Some(u32::MAX),
+                    },
+                )
+                .await?;
+            transactions.insert(member.wallet_id, history.data);
+        }
+
+        Ok(reconcile_contributions(&plan, &transactions, as_of))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::backends::rust::RustLedgerApi;
+    use crate::api::traits::{
+        contributions::{ContributionFrequency, ContributionMember},
+        groups::{CreateGroupRequest, Group, GroupMember, UpdateGroupRequest},
+        wallets::{CreateWalletRequest, TransactionType, WalletType},
+    };
+    use crate::api::{
+        traits::ledger::LedgerApi,
+        types::{PaginatedResponse, Role, SearchQuery},
+    };
+    use chrono::{Duration, TimeZone};
+
+    /// A single chama standing in for the groups service
+    struct Chama(Group);
+
+    impl Chama {
+        fn with_members(members: &[&ContributionMember]) -> Arc<Self> {
+            Arc::new(Self(Group {
+                id: Uuid::new_v4(),
+                name: "Umoja".to_string(),
+                description: None,
+                members: members
+                    .iter()
+                    .map(|member| GroupMember {
+                        user_id: member.user_id,
+                    })
+                    .collect(),
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+            }))
+        }
+    }
+
+    fn unsupported<T>(what: &str) -> ApiResult<T> {
+        Err(ApiError::Server {
+            message: format!("{} is not supported by the test chama", what),
+        })
+    }
+
+    #[async_trait]
+    impl GroupsApi for Chama {
+        async fn get_group(&self, group_id: Uuid) -> ApiResult<Group> {
+            if group_id == self.0.id {
+                Ok(self.0.clone())
+            } else {
+                Err(ApiError::NotFound {
+                    resource: format!("Chama {}", group_id),
+                })
+            }
+        }
+
+        async fn get_groups(&self, _: PaginationQuery) -> ApiResult<PaginatedResponse<Group>> {
+            unsupported("Listing chamas")
+        }
+
+        async fn search_groups(
+            &self,
+            _: SearchQuery,
+            _: PaginationQuery,
+        ) -> ApiResult<PaginatedResponse<Group>> {
+            unsupported("Searching chamas")
+        }
+
+        async fn create_group(&self, _: CreateGroupRequest) -> ApiResult<Group> {
+            unsupported("Creating chamas")
+        }
+
+        async fn update_group(&self, _: Uuid, _: UpdateGroupRequest) -> ApiResult<Group> {
+            unsupported("Updating chamas")
+        }
+
+        async fn delete_group(&self, _: Uuid) -> ApiResult<()> {
+            unsupported("Deleting chamas")
+        }
+    }
+
+    fn user(id: Uuid) -> User {
+        User {
+            id,
+            phone: None,
+            nostr: None,
+            profile: None,
+            roles: vec![Role::Member],
+            verified: true,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    async fn member(wallets: &RustWalletsApi) -> ContributionMember {
+        let user_id = Uuid::new_v4();
+        let wallet = wallets
+            .create_wallet(CreateWalletRequest {
+                user_id,
+                name: "Chama savings".to_string(),
+                wallet_type: WalletType::Fedimint,
+            })
+            .await
+            .unwrap();
+        ContributionMember {
+            user_id,
+            wallet_id: wallet.id,
+        }
+    }
+
+    fn plan_request(
+        chama: &Chama,
+        members: Vec<ContributionMember>,
+    ) -> CreateContributionPlanRequest {
+        CreateContributionPlanRequest {
+            group_id: chama.0.id,
+            name: "Weekly merry-go-round".to_string(),
+            amount: 5_000,
+            frequency: ContributionFrequency::Weekly,
+            start_date: Utc::now() - Duration::days(15),
+            members,
+        }
+    }
+
+    #[test]
+    fn test_monthly_due_dates_and_rotation() {
+        let members: Vec<ContributionMember> = (0..3)
+            .map(|_| ContributionMember {
+                user_id: Uuid::new_v4(),
+                wallet_id: Uuid::new_v4(),
+            })
+            .collect();
+        let plan = ContributionPlan {
+            id: Uuid::new_v4(),
+            group_id: Uuid::new_v4(),
+            name: "Monthly".to_string(),
+            amount: 10_000,
+            frequency: ContributionFrequency::Monthly,
+            start_date: Utc.with_ymd_and_hms(2024, 1, 31, 0, 0, 0).unwrap(),
+            members: members.clone(),
+            created_at: Utc::now(),
+        };
+
+        assert_eq!(
+            plan.due_date(1),
+            Utc.with_ymd_and_hms(2024, 2, 29, 0, 0, 0).unwrap()
+        );
+        assert_eq!(
+            plan.periods_due(Utc.with_ymd_and_hms(2024, 4, 15, 0, 0, 0).unwrap()),
+            3
+        );
+
+        // Fourth period (April) starts the second round with the first member again
+        let round = plan.current_round(Utc.with_ymd_and_hms(2024, 5, 1, 0, 0, 0).unwrap());
+        assert_eq!(round.len(), 3);
+        assert_eq!(round[0].period, 3);
+        assert_eq!(round[0].recipient_id, members[0].user_id);
+        assert_eq!(round[2].recipient_id, members[2].user_id);
+        assert_eq!(round[0].amount, 30_000);
+    }
+
+    #[tokio::test]
+    async fn test_plan_requires_member_owned_wallets() {
+        let wallets = RustWalletsApi::new(RustLedgerApi::new());
+        let mut stranger = member(&wallets).await;
+        stranger.user_id = Uuid::new_v4();
+        let chama = Chama::with_members(&[&stranger]);
+        let contributions = RustContributionsApi::new(wallets.clone(), chama.clone());
+
+        let result = contributions
+            .create_contribution_plan(plan_request(&chama, vec![stranger]))
+            .await;
+        assert!(matches!(result, Err(ApiError::Validation { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_plan_members_and_wallets() {
+        let wallets = RustWalletsApi::new(RustLedgerApi::new());
+        let insider = member(&wallets).await;
+        let outsider = member(&wallets).await;
+        let chama = Chama::with_members(&[&insider]);
+        let contributions = RustContributionsApi::new(wallets.clone(), chama.clone());
+
+        // Only the chama's own members can be enrolled
+        let with_outsider = contributions
+            .create_contribution_plan(plan_request(&chama, vec![insider.clone(), outsider]))
+            .await;
+        assert!(matches!(with_outsider, Err(ApiError::Validation { .. })));
+        let mut unknown_chama = plan_request(&chama, vec![insider.clone()]);
+        unknown_chama.group_id = Uuid::new_v4();
+        assert!(matches!(
+            contributions.create_contribution_plan(unknown_chama).await,
+            Err(ApiError::NotFound { .. })
+        ));
+
+        // A wallet's contributions can only count towards one plan
+        contributions
+            .create_contribution_plan(plan_request(&chama, vec![insider.clone()]))
+            .await
+            .unwrap();
+        let second_plan = contributions
+            .create_contribution_plan(plan_request(&chama, vec![insider]))
+            .await;
+        assert!(matches!(second_plan, Err(ApiError::Conflict { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_reconciliation_tracks_member_arrears() {
+        let ledger = RustLedgerApi::new();
+        let wallets = RustWalletsApi::new(ledger.clone());
+        let up_to_date = member(&wallets).await;
+        let behind = member(&wallets).await;
+        let chama = Chama::with_members(&[&up_to_date, &behind]);
+        let contributions = RustContributionsApi::new(wallets.clone(), chama.clone());
+        let plan = contributions
+            .create_contribution_plan(plan_request(
+                &chama,
+                vec![up_to_date.clone(), behind.clone()],
+            ))
+            .await
+            .unwrap();
+
+        // Deposits are savings, not contributions, until they're paid into the pool
+        wallets
+            .credit(up_to_date.wallet_id, 20_000, TransactionType::Deposit)
+            .await
+            .unwrap();
+        wallets
+            .credit(behind.wallet_id, 20_000, TransactionType::Deposit)
+            .await
+            .unwrap();
+        contributions
+            .pay_contribution(plan.id, &user(up_to_date.user_id), 15_000)
+            .await
+            .unwrap();
+        contributions
+            .pay_contribution(plan.id, &user(behind.user_id), 5_000)
+            .await
+            .unwrap();
+        // Withdrawals are not contributions either
+        wallets
+            .debit(
+                behind.wallet_id,
+                1_000,
+                TransactionType::Withdrawal,
+                &user(behind.user_id),
+            )
+            .await
+            .unwrap();
+        // Nor can anyone outside the plan pay into it
+        assert!(matches!(
+            contributions
+                .pay_contribution(plan.id, &user(Uuid::new_v4()), 5_000)
+                .await,
+            Err(ApiError::Authorization { .. })
+        ));
+
+        let report = contributions
+            .reconcile_contribution_plan(plan.id, Utc::now())
+            .await
+            .unwrap();
+
+        assert_eq!(report.periods_due, 3);
+        assert_eq!(report.expected_total, 30_000);
+        assert_eq!(report.paid_total, 20_000);
+        assert_eq!(report.arrears_total, 10_000);
+        assert_eq!(report.members_in_arrears(), 1);
+
+        let behind_status = report
+            .members
+            .iter()
+            .find(|m| m.user_id == behind.user_id)
+            .unwrap();
+        assert_eq!(behind_status.arrears, 10_000);
+        assert_eq!(behind_status.periods_missed, 2);
+        assert!(behind_status.last_contribution_at.is_some());
+
+        // What members paid in is held in the chama's pool
+        assert_eq!(
+            ledger
+                .get_account_balance(AccountKey::chama(
+                    plan.group_id,
+                    AccountPurpose::ContributionsPool,
+                    LedgerCurrency::Sats
+                ))
+                .await
+                .unwrap(),
+            rust_decimal::Decimal::from(20_000)
+        );
+    }
+}
@@ -0,0 +1,667 @@
+// In-process dividends engine for the Rust backend
+// Entitlements are computed from time-weighted share capital and wallet balances
+// in the ledger, approved under maker-checker and paid into member wallets. Dividends
+// are charged to SACCO equity and savings interest to SACCO interest expense.
+// Runs are saved to a JSON store after each change.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use rust_decimal::Decimal;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use super::{wallets::signed_sats, JsonStore, RustLedgerApi, RustWalletsApi};
+use crate::api::{
+    errors::{ApiError, ApiResult},
+    traits::{
+        dividends::{
+            pro_rated_return, time_weighted_average, whole_sats, DividendRun, DividendRunRequest,
+            DividendRunStatus, DividendsApi, MemberEntitlement,
+        },
+        ledger::{
+            AccountKey, AccountOwner, AccountPurpose, LedgerApi, LedgerCurrency, PostingRequest,
+        },
+        wallets::{TransactionType, WalletsApi},
+    },
+    types::{PaginatedResponse, PaginationQuery, User},
+};
+
+#[derive(Default, serde::Serialize, serde::Deserialize)]
+struct DividendsState {
+    runs: HashMap<Uuid, DividendRun>,
+}
+
+#[derive(Clone)]
+pub struct RustDividendsApi {
+    state: Arc<RwLock<DividendsState>>,
+    ledger: RustLedgerApi,
+    wallets: RustWalletsApi,
+    store: JsonStore,
+}
+
+impl RustDividendsApi {
+    pub fn new(ledger: RustLedgerApi, wallets: RustWalletsApi) -> Self {
+        Self {
+            state: Arc::default(),
+            ledger,
+            wallets,
+            store: JsonStore::in_memory(),
+        }
+    }
+
+    /// Keep dividend runs in `store`, starting from what it already holds
+    pub fn with_store(mut self, store: JsonStore) -> ApiResult<Self> {
+        let state: DividendsState = store.load()?;
+        self.state = Arc::new(RwLock::new(state));
+        self.store = store;
+        Ok(self)
+    }
+
+    /// Save a reviewed run, putting `previous` back if it can't be saved
+    fn save_run(
+        &self,
+        state: &mut DividendsState,
+        previous: DividendRun,
+    ) -> ApiResult<DividendRun> {
+        let updated = state
+            .runs
+            .get(&previous.id)
+            .cloned()
+            .ok_or_else(|| run_not_found(previous.id))?;
+        self.store.save_or_undo(state, |state| {
+            state.runs.insert(previous.id, previous);
+        })?;
+        Ok(updated)
+    }
+
+    /// Work out every member's entitlement from their ledger accounts
+    async fn compute_entitlements(
+        &self,
+        request: &DividendRunRequest,
+    ) -> ApiResult<Vec<MemberEntitlement>> {
+        let (start, end) = (request.period_start, request.period_end);
+
+        // Members with share capital or a wallet, with the wallet accounts they hold
+        let mut members: BTreeMap<Uuid, Vec<AccountKey>> = BTreeMap::new();
+        for balance in self.ledger.get_accounts(None).await? {
+            let key = balance.account.key;
+            let AccountOwner::Member(user_id) = key.owner else {
+                continue;
+            };
+            match key.purpose {
+                AccountPurpose::ShareCapital if key.currency == LedgerCurrency::Kes => {
+                    members.entry(user_id).or_default();
+                }
+                AccountPurpose::Wallet(_) => members.entry(user_id).or_default().push(key),
+                _ => {}
+            }
+        }
+
+        let mut entitlements = Vec::with_capacity(members.len());
+        for (user_id, wallet_accounts) in members {
+            let share_capital = self
+                .ledger
+                .get_account_movements(AccountKey::member(
+                    user_id,
+                    AccountPurpose::ShareCapital,
+                    LedgerCurrency::Kes,
+                ))
+                .await?;
+            let average_share_capital = time_weighted_average(&share_capital, start, end);
+            let dividend_kes =
+                pro_rated_return(average_share_capital, request.dividend_rate, start, end)
+                    .round_dp(2);
+
+            let mut average_savings = Decimal::ZERO;
+            for account in wallet_accounts {
+                let movements = self.ledger.get_account_movements(account).await?;
+                average_savings += time_weighted_average(&movements, start, end);
+            }
+            let interest_sats = whole_sats(pro_rated_return(
+                average_savings,
+                request.savings_interest_rate,
+                start,
+                end,
+            ));
+
+            let mut wallets = self.wallets.get_user_wallets(user_id).await?;
+            wallets.sort_by_key(|wallet| wallet.created_at);
+
+            entitlements.push(MemberEntitlement {
+                user_id,
+                average_share_capital,
+                dividend_kes,
+                dividend_sats: whole_sats(dividend_kes * request.sats_per_kes),
+                average_savings,
+                interest_sats,
+                payout_wallet_id: wallets.first().map(|wallet| wallet.id),
+                paid_at: None,
+                payout_error: None,
+            });
+        }
+
+        Ok(entitlements)
+    }
+}
+
+#[async_trait]
+impl DividendsApi for RustDividendsApi {
+    async fn create_dividend_run(&self, request: DividendRunRequest) -> ApiResult<DividendRun> {
+        if request.period_start >= request.period_end {
+            return Err(ApiError::Validation {
+                message: "Dividend period must end after it starts".to_string(),
+            });
+        }
+        if request.period_end > Utc::now() {
+            return Err(ApiError::Validation {
+                message: "Dividends can only be declared for a period that has ended".to_string(),
+            });
+        }
+        if request.dividend_rate < Decimal::ZERO || request.savings_interest_rate < Decimal::ZERO {
+            return Err(ApiError::Validation {
+                message: "Rates cannot be negative".to_string(),
+            });
+        }
+        if request.sats_per_kes <= Decimal::ZERO {
+            return Err(ApiError::Validation {
+                message: "Sats per KES must be greater than zero".to_string(),
+            });
+        }
+
+        let overlaps = self.state.read().await.runs.values().any(|run| {
+            run.status.is_committed()
+                && run.period_start < request.period_end
+                && request.period_start < run.period_end
+        });
+        if overlaps {
+            return Err(ApiError::Conflict {
+                message: "Period overlaps a dividend run that is already approved or paid"
+                    .to_string(),
+            });
+        }
+
+        let entitlements = self.compute_entitlements(&request).await?;
+        let run = DividendRun {
+            id: Uuid::new_v4(),
+            period_start: request.period_start,
+            period_end: request.period_end,
+            dividend_rate: request.dividend_rate,
+            savings_interest_rate: request.savings_interest_rate,
+            sats_per_kes: request.sats_per_kes,
+            status: DividendRunStatus::Preview,
+            entitlements,
+            created_by: request.created_by,
+            created_at: Utc::now(),
+            reviewed_by: None,
+            reviewed_at: None,
+            rejection_reason: None,
+            paid_at: None,
+        };
+
+        let mut state = self.state.write().await;
+        state.runs.insert(run.id, run.clone());
+        self.store.save_or_undo(&mut *state, |state| {
+            state.runs.remove(&run.id);
+        })?;
+        Ok(run)
+    }
+
+    async fn get_dividend_run(&self, run_id: Uuid) -> ApiResult<DividendRun> {
+        self.state
+            .read()
+            .await
+            .runs
+            .get(&run_id)
+            .cloned()
+            .ok_or_else(|| run_not_found(run_id))
+    }
+
+    async fn get_dividend_runs(
+        &self,
+        pagination: PaginationQuery,
+    ) -> ApiResult<PaginatedResponse<DividendRun>> {
+        let mut runs: Vec<DividendRun> = self.state.read().await.runs.values().cloned().collect();
+        runs.sort_by_key(|run| std::cmp::Reverse(run.created_at));
+        Ok(PaginatedResponse::paginate(runs, &pagination))
+    }
+
+    async fn approve_dividend_run(&self, run_id: Uuid, checker: &User) -> ApiResult<DividendRun> {
+        let mut state = self.state.write().await;
+        let overlaps_approved = {
+            let run = state
+                .runs
+                .get(&run_id)
+                .ok_or_else(|| run_not_found(run_id))?;
+            state.runs.values().any(|other| {
+                other.id != run.id
+                    && other.status.is_committed()
+                    && other.period_start < run.period_end
+                    && run.period_start < other.period_end
+            })
+        };
+
+        let run = state
+            .runs
+            .get_mut(&run_id)
+            .ok_or_else(|| run_not_found(run_id))?;
+        check_reviewer(run, checker)?;
+        if overlaps_approved {
+            return Err(ApiError::Conflict {
+                message: "Another dividend run covering this period has already been approved"
+                    .to_string(),
+            });
+        }
+
+        let previous = run.clone();
+        run.status = DividendRunStatus::Approved;
+        run.reviewed_by = Some(checker.id);
+        run.reviewed_at = Some(Utc::now());
+        self.save_run(&mut state, previous)
+    }
+
+    async fn reject_dividend_run(
+        &self,
+        run_id: Uuid,
+        checker: &User,
+        reason: Option<String>,
+    ) -> ApiResult<DividendRun> {
+        let mut state = self.state.write().await;
+        let run = state
+            .runs
+            .get_mut(&run_id)
+            .ok_or_else(|| run_not_found(run_id))?;
+        check_reviewer(run, checker)?;
+
+        let previous = run.clone();
+        run.status = DividendRunStatus::Rejected;
+        run.reviewed_by = Some(checker.id);
+        run.reviewed_at = Some(Utc::now());
+        run.rejection_reason = reason.filter(|r| !r.trim().is_empty());
+        self.save_run(&mut state, previous)
+    }
+
+    async fn pay_dividend_run(&self, run_id: Uuid, operator: &User) -> ApiResult<DividendRun> {
+        if !operator.is_admin() {
+            return Err(ApiError::Authorization {
+                message: "Only admins can pay out dividends".to_string(),
+            });
+        }
+
+        // Hold the run lock across the wallet credits so a run can't be paid twice
+        let mut state = self.state.write().await;
+        let run = state
+            .runs
+            .get_mut(&run_id)
+            .ok_or_else(|| run_not_found(run_id))?;
+        if !matches!(
+            run.status,
+            DividendRunStatus::Approved | DividendRunStatus::PartiallyPaid
+        ) {
+            return Err(ApiError::Conflict {
+                message: format!(
+                    "Only approved dividend runs can be paid; this run is {}",
+                    run.status.as_str()
+                ),
+            });
+        }
+
+        // Entitlements are marked paid as they go; the rest stay outstanding with
+        // the reason, for the next attempt
+        for entitlement in run.entitlements.iter_mut() {
+            if !entitlement.is_outstanding() {
+                continue;
+            }
+            // Members without a wallet when the run was computed may have opened one
+            if entitlement.payout_wallet_id.is_none() {
+                match self.wallets.get_user_wallets(entitlement.user_id).await {
+                    Ok(mut wallets) => {
+                        wallets.sort_by_key(|wallet| wallet.created_at);
+                        entitlement.payout_wallet_id = wallets.first().map(|wallet| wallet.id);
+                    }
+                    Err(e) => {
+                        entitlement.payout_error = Some(e.to_string());
+                        continue;
+                    }
+                }
+            }
+            let Some(wallet_id) = entitlement.payout_wallet_id else {
+                entitlement.payout_error = Some("Member has no wallet".to_string());
+                continue;
+            };
+
+            let mut counterparts = Vec::new();
+            if entitlement.dividend_sats > 0 {
+                counterparts.push(PostingRequest::debit(
+                    AccountKey::sacco(AccountPurpose::Dividends, LedgerCurrency::Sats),
+                    entitlement.dividend_sats,
+                ));
+            }
+            if entitlement.interest_sats > 0 {
+                counterparts.push(PostingRequest::debit(
+                    AccountKey::sacco(AccountPurpose::InterestExpense, LedgerCurrency::Sats),
+                    entitlement.interest_sats,
+                ));
+            }
+            let posted = match signed_sats(entitlement.total_sats()) {
+                Ok(amount) => {
+                    self.wallets
+                        .post(wallet_id, amount, TransactionType::Deposit, counterparts)
+                        .await
+                }
+                Err(e) => Err(e),
+            };
+            match posted {
+                Ok(_) => {
+                    entitlement.paid_at = Some(Utc::now());
+                    entitlement.payout_error = None;
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        "Could not pay dividend run {} to member {}: {}",
+                        run_id,
+                        entitlement.user_id,
+                        e
+                    );
+                    entitlement.payout_error = Some(e.to_string());
+                }
+            }
+        }
+
+        if run.entitlements.iter().any(|e| e.is_outstanding()) {
+            run.status = DividendRunStatus::PartiallyPaid;
+        } else {
+            run.status = DividendRunStatus::Paid;
+            run.paid_at = Some(Utc::now());
+        }
+        let paid = run.clone();
+        self.store.save_or_log(&*state, "dividend runs");
+        Ok(paid)
+    }
+}
+
+/// Enforce maker-checker: reviewers must be admins other than whoever prepared the run
+fn check_reviewer(run: &DividendRun, checker: &User) -> ApiResult<()> {
+    if !checker.is_admin() {
+        return Err(ApiError::Authorization {
+            message: "Only admins can review dividend runs".to_string(),
+        });
+    }
+    if checker.id == run.created_by {
+        return Err(ApiError::Authorization {
+            message: "Dividend runs must be reviewed by someone other than their maker".to_string(),
+        });
+    }
+    if run.status != DividendRunStatus::Preview {
+        return Err(ApiError::Conflict {
+            message: format!("Dividend run is already {}", run.status.as_str()),
+        });
+    }
+    Ok(())
+}
+
+fn run_not_found(run_id: Uuid) -> ApiError {
+    ApiError::NotFound {
+        resource: format!("Dividend run {}", run_id),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::traits::ledger::AccountMovement;
+    use crate::api::traits::wallets::{CreateWalletRequest, WalletType};
+    use crate::api::types::Role;
+    use chrono::{DateTime, Duration};
+
+    fn user(roles: Vec<Role>) -> User {
+        User {
+            id: Uuid::new_v4(),
+            phone: None,
+            nostr: None,
+            profile: None,
+            roles,
+            verified: true,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn movement(amount: i64, at: DateTime<Utc>) -> AccountMovement {
+        AccountMovement {
+            entry_id: Uuid::new_v4(),
+            description: "test".to_string(),
+            reference_id: None,
+            amount: Decimal::from(amount),
+            balance_after: Decimal::ZERO,
+            created_at: at,
+        }
+    }
+
+    /// Store a preview run directly, since ledger postings are always timestamped now
+    async fn preview_run(
+        dividends: &RustDividendsApi,
+        maker: Uuid,
+        entitlements: Vec<MemberEntitlement>,
+    ) -> DividendRun {
+        let end = Utc::now() - Duration::days(1);
+        let run = DividendRun {
+            id: Uuid::new_v4(),
+            period_start: end - Duration::days(365),
+            period_end: end,
+            dividend_rate: Decimal::from(10),
+            savings_interest_rate: Decimal::from(5),
+            sats_per_kes: Decimal::from(10),
+            status: DividendRunStatus::Preview,
+            entitlements,
+            created_by: maker,
+            created_at: Utc::now(),
+            reviewed_by: None,
+            reviewed_at: None,
+            rejection_reason: None,
+            paid_at: None,
+        };
+        dividends
+            .state
+            .write()
+            .await
+            .runs
+            .insert(run.id, run.clone());
+        run
+    }
+
+    #[test]
+    fn test_time_weighted_average_and_pro_rating() {
+        let start = Utc::now() - Duration::days(365);
+        let end = start + Duration::days(365);
+
+        // 1,000 held all year, another 1,000 added half way through
+        let movements = vec![
+            movement(1_000, start - Duration::days(10)),
+            movement(
+                1_000,
+                start + Duration::seconds((end - start).num_seconds() / 2),
+            ),
+        ];
+        let average = time_weighted_average(&movements, start, end);
+        assert_eq!(average.round_dp(6), Decimal::from(1_500));
+
+        // Movements after the period don't count
+        let late = vec![movement(5_000, end + Duration::days(1))];
+        assert_eq!(time_weighted_average(&late, start, end), Decimal::ZERO);
+
+        // 10% a year on 1,500 for a full year, and half that for half a year
+        assert_eq!(
+            pro_rated_return(average, Decimal::from(10), start, end).round_dp(6),
+            Decimal::from(150)
+        );
+        let mid = start + Duration::seconds((end - start).num_seconds() / 2);
+        assert_eq!(
+            pro_rated_return(Decimal::from(1_500), Decimal::from(10), start, mid).round_dp(6),
+            Decimal::from(75)
+        );
+        assert_eq!(whole_sats(Decimal::new(12_99, 2)), 12);
+    }
+
+    #[tokio::test]
+    async fn test_run_is_computed_from_ledger_members() {
+        let ledger = RustLedgerApi::new();
+        let wallets = RustWalletsApi::new(ledger.clone());
+        let dividends = RustDividendsApi::new(ledger, wallets.clone());
+
+        let member = Uuid::new_v4();
+        let wallet = wallets
+            .create_wallet(CreateWalletRequest {
+                user_id: member,
+                name: "Savings".to_string(),
+                wallet_type: WalletType::Fedimint,
+            })
+            .await
+            .unwrap();
+        wallets
+            .credit(wallet.id, 50_000, TransactionType::Deposit)
+            .await
+            .unwrap();
+
+        let end = Utc::now();
+        let request = DividendRunRequest {
+            period_start: end - Duration::days(365),
+            period_end: end,
+            dividend_rate: Decimal::from(10),
+            savings_interest_rate: Decimal::from(5),
+            sats_per_kes: Decimal::from(10),
+            created_by: Uuid::new_v4(),
+        };
+        let run = dividends
+            .create_dividend_run(request.clone())
+            .await
+            .unwrap();
+        assert_eq!(run.status, DividendRunStatus::Preview);
+        assert_eq!(run.entitlements.len(), 1);
+        assert_eq!(run.entitlements[0].user_id, member);
+        assert_eq!(run.entitlements[0].payout_wallet_id, Some(wallet.id));
+
+        let future = DividendRunRequest {
+            period_end: Utc::now() + Duration::days(1),
+            ..request
+        };
+        assert!(matches!(
+            dividends.create_dividend_run(future).await,
+            Err(ApiError::Validation { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_approval_and_payout_post_to_wallets() {
+        let ledger = RustLedgerApi::new();
+        let wallets = RustWalletsApi::new(ledger.clone());
+        let dividends = RustDividendsApi::new(ledger.clone(), wallets.clone());
+
+        let member = Uuid::new_v4();
+        let wallet = wallets
+            .create_wallet(CreateWalletRequest {
+                user_id: member,
+                name: "Savings".to_string(),
+                wallet_type: WalletType::Fedimint,
+            })
+            .await
+            .unwrap();
+        let no_wallet = Uuid::new_v4();
+
+        let maker = user(vec![Role::Admin]);
+        let entitlement = |user_id, payout_wallet_id| MemberEntitlement {
+            user_id,
+            average_share_capital: Decimal::from(10_000),
+            dividend_kes: Decimal::from(1_000),
+            dividend_sats: 10_000,
+            average_savings: Decimal::from(40_000),
+            interest_sats: 2_000,
+            payout_wallet_id,
+            paid_at: None,
+            payout_error: None,
+        };
+        let run = preview_run(
+            &dividends,
+            maker.id,
+            vec![
+                entitlement(member, Some(wallet.id)),
+                entitlement(no_wallet, None),
+            ],
+        )
+        .await;
+
+        // Maker-checker and approval before payout
+        let self_approval = dividends.approve_dividend_run(run.id, &maker).await;
+        assert!(matches!(self_approval, Err(ApiError::Authorization { .. })));
+        let early = dividends.pay_dividend_run(run.id, &maker).await;
+        assert!(matches!(early, Err(ApiError::Conflict { .. })));
+
+        let checker = user(vec![Role::Admin]);
+        let approved = dividends
+            .approve_dividend_run(run.id, &checker)
+            .await
+            .unwrap();
+        assert_eq!(approved.status, DividendRunStatus::Approved);
+
+        // A second run over the same period can't be approved
+        let overlapping = preview_run(&dividends, maker.id, Vec::new()).await;
+        let second = dividends
+            .approve_dividend_run(overlapping.id, &checker)
+            .await;
+        assert!(matches!(second, Err(ApiError::Conflict { .. })));
+
+        // The member without a wallet stays owed, and so does the run
+        let partial = dividends.pay_dividend_run(run.id, &maker).await.unwrap();
+        assert_eq!(partial.status, DividendRunStatus::PartiallyPaid);
+        assert!(partial.paid_at.is_none());
+        assert!(partial.entitlements[0].paid_at.is_some());
+        assert!(partial.entitlements[1].paid_at.is_none());
+        assert_eq!(
+            partial.entitlements[1].payout_error.as_deref(),
+            Some("Member has no wallet")
+        );
+        assert_eq!(wallets.get_wallet_balance(wallet.id).await.unwrap(), 12_000);
+
+        // Once they open a wallet, paying again reaches just them
+        let late_wallet = wallets
+            .create_wallet(CreateWalletRequest {
+                user_id: no_wallet,
+                name: "Savings".to_string(),
+                wallet_type: WalletType::Fedimint,
+            })
+            .await
+            .unwrap();
+        let paid = dividends.pay_dividend_run(run.id, &maker).await.unwrap();
+        assert_eq!(paid.status, DividendRunStatus::Paid);
+        assert!(paid.paid_at.is_some());
+        assert_eq!(paid.entitlements[1].payout_wallet_id, Some(late_wallet.id));
+        assert!(paid.entitlements[1].payout_error.is_none());
+        assert_eq!(wallets.get_wallet_balance(wallet.id).await.unwrap(), 12_000);
+        assert_eq!(
+            wallets.get_wallet_balance(late_wallet.id).await.unwrap(),
+            12_000
+        );
+
+        assert_eq!(
+            ledger
+                .get_account_balance(AccountKey::sacco(
+                    AccountPurpose::InterestExpense,
+                    LedgerCurrency::Sats
+                ))
+                .await
+                .unwrap(),
+            Decimal::from(4_000)
+        );
+        assert!(ledger
+            .get_trial_balance()
+            .await
+            .unwrap()
+            .iter()
+            .all(|line| line.is_balanced()));
+
+        let again = dividends.pay_dividend_run(run.id, &maker).await;
+        assert!(matches!(again, Err(ApiError::Conflict { .. })));
+    }
+}
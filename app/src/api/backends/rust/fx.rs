@@ -0,0 +1,298 @@
+// Exchange rate history for the Rust backend
+// Rates are kept in observation order so the rate in force at any past moment can
+// be looked up when valuing historical balances. Each rate is appended to a log on
+// disk, so the history outlives a restart.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use tokio::sync::RwLock;
+
+use super::JsonLog;
+use crate::api::{
+    errors::{ApiError, ApiResult},
+    traits::fx::{FxApi, FxRate, RateSource},
+};
+
+#[derive(Clone)]
+pub struct RustFxApi {
+    /// Sorted by `observed_at`
+    history: Arc<RwLock<Vec<FxRate>>>,
+    log: JsonLog,
+    sources: Vec<Arc<dyn RateSource>>,
+}
+
+impl RustFxApi {
+    pub fn new(sources: Vec<Arc<dyn RateSource>>) -> Self {
+        Self {
+            history: Arc::default(),
+            log: JsonLog::in_memory(),
+            sources,
+        }
+    }
+
+    /// Keep the rate history in `log`, starting from the rates it already holds
+    pub fn with_log(mut self, log: JsonLog) -> ApiResult<Self> {
+        let mut history: Vec<FxRate> = log.load()?;
+        history.sort_by_key(|rate| rate.observed_at);
+        self.history = Arc::new(RwLock::new(history));
+        self.log = log;
+        Ok(self)
+    }
+
+    pub fn has_sources(&self) -> bool {
+        !self.sources.is_empty()
+    }
+
+    /// Fetch a fresh rate every `every` until the process exits
+    pub fn spawn_rate_poller(&self, every: Duration) -> tokio::task::JoinHandle<()> {
+        let api = self.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(every);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                ticker.tick().await;
+                if let Err(e) = api.refresh_rate().await {
+                    tracing::warn!("Exchange rate refresh failed: {}", e);
+                }
+            }
+        })
+    }
+
+    async fn insert(&self, rate: FxRate) -> ApiResult<FxRate> {
+        let mut history = self.history.write().await;
+        self.log.append(std::slice::from_ref(&rate))?;
+        let position = history.partition_point(|r| r.observed_at <= rate.observed_at);
+        history.insert(position, rate.clone());
+        Ok(rate)
+    }
+}
+
+fn no_rate(context: String) -> ApiError {
+    ApiError::NotFound {
+        resource: format!("Exchange rate {}", context),
+    }
+}
+
+#[async_trait]
+impl FxApi for RustFxApi {
+    async fn current_rate(&self) -> ApiResult<FxRate> {
+        self.history
+            .read()
+            .await
+            .last()
+            .cloned()
+            .ok_or_else(|| no_rate("(none recorded yet)".to_string()))
+    }
+
+    async fn rate_at(&self, at: DateTime<Utc>) -> ApiResult<FxRate> {
+        let history = self.history.read().await;
+        let position = history.partition_point(|r| r.observed_at <= at);
+        position
+            .checked_sub(1)
+            .map(|i| history[i].clone())
+            .ok_or_else(|| no_rate(format!("as of {}", at.format("%Y-%m-%d %H:%M UTC"))))
+    }
+
+    async fn get_rate_history(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> ApiResult<Vec<FxRate>> {
+        Ok(self
+            .history
+            .read()
+            .await
+            .iter()
+            .filter(|rate| rate.observed_at >= from && rate.observed_at < to)
+            .cloned()
+            .collect())
+    }
+
+    async fn record_rate(&self, kes_per_btc: Decimal, source: String) -> ApiResult<FxRate> {
+        if kes_per_btc <= Decimal::ZERO {
+            return Err(ApiError::Validation {
+                message: "The bitcoin price must be greater than zero".to_string(),
+            });
+        }
+        self.insert(FxRate {
+            kes_per_btc,
+            source,
+            observed_at: Utc::now(),
+        })
+        .await
+    }
+
+    async fn refresh_rate(&self) -> ApiResult<FxRate> {
+        if self.sources.is_empty() {
+            return Err(ApiError::Validation {
+                message: "No exchange rate sources are configured; set FX_RATE_SOURCES".to_string(),
+            });
+        }
+
+        let mut failures = Vec::new();
+        for source in &self.sources {
+            match source.fetch_kes_per_btc().await {
+                Ok(kes_per_btc) => {
+                    return self
+                        .record_rate(kes_per_btc, source.name().to_string())
+                        .await
+                }
+                Err(e) => failures.push(format!("{}: {}", source.name(), e)),
+            }
+        }
+        Err(ApiError::Network {
+            message: format!("Every rate source failed ({})", failures.join("; ")),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::{
+        backends::fx::FixedRateSource,
+        traits::{
+            fx::MemberBalances,
+            ledger::LedgerCurrency,
+            statements::{MemberStatement, StatementSection},
+        },
+    };
+    use uuid::Uuid;
+
+    struct DownSource;
+
+    #[async_trait]
+    impl RateSource for DownSource {
+        fn name(&self) -> &str {
+            "down"
+        }
+
+        async fn fetch_kes_per_btc(&self) -> ApiResult<Decimal> {
+            Err(ApiError::Network {
+                message: "unreachable".to_string(),
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sources_history_and_lookup() {
+        let api = RustFxApi::new(vec![
+            Arc::new(DownSource),
+            Arc::new(FixedRateSource::new(Decimal::from(13_000_000))),
+        ]);
+        let before = Utc::now();
+        assert!(matches!(
+            api.rate_at(before).await,
+            Err(ApiError::NotFound { .. })
+        ));
+
+        // The first source that answers wins
+        let fetched = api.refresh_rate().await.unwrap();
+        assert_eq!(fetched.source, "fixed");
+
+        let manual = api
+            .record_rate(Decimal::from(12_000_000), "manual".to_string())
+            .await
+            .unwrap();
+        assert_eq!(api.current_rate().await.unwrap(), manual);
+        assert_eq!(api.rate_at(fetched.observed_at).await.unwrap(), fetched);
+        assert_eq!(
+            api.get_rate_history(before, Utc::now() + chrono::Duration::seconds(1))
+                .await
+                .unwrap()
+                .len(),
+            2
+        );
+        assert!(api
+            .record_rate(Decimal::ZERO, "manual".to_string())
+            .await
+            .is_err());
+        assert!(RustFxApi::new(Vec::new()).refresh_rate().await.is_err());
+    }
+
+    #[test]
+    fn test_conversion_and_member_balances() {
+        let rate = FxRate {
+            kes_per_btc: Decimal::from(13_000_000),
+            source: "manual".to_string(),
+            observed_at: Utc::now(),
+        };
+        // 100,000 sats is a thousandth of a bitcoin
+        assert_eq!(
+            rate.sats_to_kes(Decimal::from(100_000)),
+            Decimal::from(13_000)
+        );
+        assert_eq!(rate.kes_to_sats(Decimal::from(1)), Decimal::from(8));
+        assert_eq!(
+            rate.kes_to_sats(Decimal::from(13_000)),
+            Decimal::from(100_000)
+        );
+        assert_eq!(rate.sats_per_kes().round_dp(4), Decimal::new(76_923, 4));
+
+        let section = |title: &str, currency, balance: i64| StatementSection {
+            title: title.to_string(),
+            currency,
+            opening_balance: Decimal::ZERO,
+            lines: Vec::new(),
+            closing_balance: Decimal::from(balance),
+        };
+        let statement = MemberStatement {
+            user_id: Uuid::new_v4(),
+            from: DateTime::UNIX_EPOCH,
+            to: Utc::now(),
+            sections: vec![
+                section("Wallet: Savings", LedgerCurrency::Sats, 200_000),
+                section("Share capital", LedgerCurrency::Kes, 5_200),
+            ],
+            generated_at: Utc::now(),
+        };
+
+        let valued = MemberBalances::from_statement(&statement, Some(rate));
+        assert_eq!(valued.total_sats(), Some(Decimal::from(240_000)));
+        assert_eq!(valued.total_kes(), Some(Decimal::from(31_200)));
+
+        let unvalued = MemberBalances::from_statement(&statement, None);
+        assert_eq!(unvalued.balances[0].sats, Some(Decimal::from(200_000)));
+        assert_eq!(unvalued.balances[0].kes, None);
+        assert_eq!(unvalued.total_kes(), None);
+    }
+
+    #[tokio::test]
+    async fn test_history_survives_a_restart() {
+        let dir = std::env::temp_dir().join(format!("bitsacco-fx-{}", Uuid::new_v4()));
+        let path = dir.join("fx-rates.jsonl");
+
+        let api = RustFxApi::new(Vec::new())
+            .with_log(JsonLog::at(&path))
+            .unwrap();
+        let first = api
+            .record_rate(Decimal::from(12_000_000), "manual".to_string())
+            .await
+            .unwrap();
+        let second = api
+            .record_rate(Decimal::from(13_000_000), "manual".to_string())
+            .await
+            .unwrap();
+
+        let restarted = RustFxApi::new(Vec::new())
+            .with_log(JsonLog::at(&path))
+            .unwrap();
+        assert_eq!(restarted.current_rate().await.unwrap(), second.clone());
+        assert_eq!(
+            restarted
+                .get_rate_history(
+                    first.observed_at,
+                    second.observed_at + chrono::Duration::seconds(1)
+                )
+                .await
+                .unwrap(),
+            vec![first, second]
+        );
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,584 @@
+// In-process double-entry ledger for the Rust backend
+// Wallets, share subscriptions and loans post here; balances are always computed
+// from the journal, which is append-only. Accounts and entries are appended to a
+// JSON-lines log as they are made and replayed from it on start.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use rust_decimal::Decimal;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use super::JsonLog;
+use crate::api::{
+    errors::{ApiError, ApiResult},
+    traits::ledger::{
+        validate_postings, AccountBalance, AccountKey, AccountMovement, AccountOwner, EntrySide,
+        JournalEntry, JournalEntryRequest, JournalFilter, LedgerAccount, LedgerApi, Posting,
+        PostingRequest, TrialBalanceLine,
+    },
+    types::{PaginatedResponse, PaginationQuery, User},
+};
+
+/// A line of the journal log
+#[derive(serde::Serialize, serde::Deserialize)]
+enum JournalRecord {
+    Account(LedgerAccount),
+    Entry(JournalEntry),
+}
+
+#[derive(Default)]
+struct LedgerState {
+    accounts: HashMap<Uuid, LedgerAccount>,
+    index: HashMap<AccountKey, Uuid>,
+    entries: Vec<JournalEntry>,
+    log: JsonLog,
+}
+
+impl LedgerState {
+    fn apply(&mut self, record: JournalRecord) {
+        match record {
+            JournalRecord::Account(account) => {
+                self.index.insert(account.key, account.id);
+                self.accounts.insert(account.id, account);
+            }
+            JournalRecord::Entry(entry) => self.entries.push(entry),
+        }
+    }
+
+    /// The account for `key`, or a new one added to `opened` if it doesn't exist yet
+    fn account_id(&self, key: AccountKey, opened: &mut Vec<LedgerAccount>) -> Uuid {
+        if let Some(id) = self.index.get(&key) {
+            return *id;
+        }
+        if let Some(account) = opened.iter().find(|account| account.key == key) {
+            return account.id;
+        }
+
+        let owner = match key.owner {
+            AccountOwner::Member(id) => format!("Member {}", id),
+            AccountOwner::Chama(id) => format!("Chama {}", id),
+            AccountOwner::Sacco => "SACCO".to_string(),
+        };
+        let account = LedgerAccount {
+            id: Uuid::new_v4(),
+            key,
+            name: format!(
+                "{} · {} ({})",
+                owner,
+                key.purpose.label(),
+                key.currency.as_str()
+            ),
+            account_type: key.purpose.account_type(),
+            created_at: Utc::now(),
+        };
+        let id = account.id;
+        opened.push(account);
+        id
+    }
+
+    fn totals(&self, account_id: Uuid) -> (Decimal, Decimal) {
+        self.entries
+            .iter()
+            .flat_map(|entry| entry.postings.iter())
+            .filter(|posting| posting.account_id == account_id)
+            .fold(
+                (Decimal::ZERO, Decimal::ZERO),
+                |(debits, credits), posting| match posting.side {
+                    EntrySide::Debit => (debits + posting.amount, credits),
+                    EntrySide::Credit => (debits, credits + posting.amount),
+                },
+            )
+    }
+
+    fn balance(&self, key: &AccountKey) -> Decimal {
+        match self.index.get(key).and_then(|id| self.accounts.get(id)) {
+            Some(account) => {
+                let (debits, credits) = self.totals(account.id);
+                AccountBalance::new(account.clone(), debits, credits).balance
+            }
+            None => Decimal::ZERO,
+        }
+    }
+
+    fn post(
+        &mut self,
+        request: JournalEntryRequest,
+        reverses: Option<Uuid>,
+    ) -> ApiResult<JournalEntry> {
+        if request.description.trim().is_empty() {
+            return Err(ApiError::Validation {
+                message: "Journal entries need a description".to_string(),
+            });
+        }
+        validate_postings(&request.postings)?;
+
+        let mut opened = Vec::new();
+        let postings = request
+            .postings
+            .into_iter()
+            .map(|posting| Posting {
+                account_id: self.account_id(posting.account, &mut opened),
+                side: posting.side,
+                amount: posting.amount,
+                currency: posting.account.currency,
+            })
+            .collect();
+
+        let entry = JournalEntry {
+            id: Uuid::new_v4(),
+            description: request.description.trim().to_string(),
+            reference_id: request.reference_id,
+            postings,
+            reverses,
+            created_by: request.created_by,
+            created_at: Utc::now(),
+        };
+
+        // Nothing is posted unless it's on disk first
+        let records: Vec<JournalRecord> = opened
+            .into_iter()
+            .map(JournalRecord::Account)
+            .chain([JournalRecord::Entry(entry.clone())])
+            .collect();
+        self.log.append(&records)?;
+        for record in records {
+            self.apply(record);
+        }
+        Ok(entry)
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct RustLedgerApi {
+    state: Arc<RwLock<LedgerState>>,
+}
+
+impl RustLedgerApi {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keep the journal in `log`, starting from the accounts and entries it already holds
+    pub fn with_log(self, log: JsonLog) -> ApiResult<Self> {
+        let mut state = LedgerState::default();
+        for record in log.load()? {
+            state.apply(record);
+        }
+        state.log = log;
+        Ok(Self {
+            state: Arc::new(RwLock::new(state)),
+        })
+    }
+
+    /// Post an entry only if `account` keeps a non-negative balance afterwards.
+    /// The check and the posting happen under one lock.
+    pub async fn post_without_overdraft(
+        &self,
+        request: JournalEntryRequest,
+        account: AccountKey,
+    ) -> ApiResult<JournalEntry> {
+        let mut state = self.state.write().await;
+
+        let debit_normal = account.purpose.account_type().is_debit_normal();
+        let change: Decimal = request
+            .postings
+            .iter()
+            .filter(|posting| posting.account == account)
+            .map(
+                |posting| match (posting.side == EntrySide::Debit, debit_normal) {
+                    (true, true) | (false, false) => posting.amount,
+                    _ => -posting.amount,
+                },
+            )
+            .sum();
+
+        let available = state.balance(&account);
+        if available + change < Decimal::ZERO {
+            return Err(ApiError::Validation {
+                message: format!(
+                    "Insufficient balance: {} holds {} {} but {} were requested",
+                    account.purpose.label(),
+                    available,
+                    account.currency.as_str(),
+                    -change
+                ),
+            });
+        }
+
+        state.post(request, None)
+    }
+}
+
+#[async_trait]
+impl LedgerApi for RustLedgerApi {
+    async fn get_accounts(&self, owner: Option<AccountOwner>) -> ApiResult<Vec<AccountBalance>> {
+        let state = self.state.read().await;
+        let mut balances: Vec<AccountBalance> = state
+            .accounts
+            .values()
+            .filter(|account| owner.is_none_or(|owner| account.key.owner == owner))
+            .map(|account| {
+                let (debits, credits) = state.totals(account.id);
+                AccountBalance::new(account.clone(), debits, credits)
+            })
+            .collect();
+        balances.sort_by(|a, b| a.account.name.cmp(&b.account.name));
+        Ok(balances)
+    }
+
+    async fn get_account_balance(&self, key: AccountKey) -> ApiResult<Decimal> {
+        Ok(self.state.read().await.balance(&key))
+    }
+
+    async fn get_account_movements(&self, key: AccountKey) -> ApiResult<Vec<AccountMovement>> {
+        let state = self.state.read().await;
+        let Some(account_id) = state.index.get(&key).copied() else {
+            return Ok(Vec::new());
+        };
+        let debit_normal = key.purpose.account_type().is_debit_normal();
+
+        let mut balance = Decimal::ZERO;
+        let mut movements = Vec::new();
+        for entry in &state.entries {
+            for posting in entry.postings.iter().filter(|p| p.account_id == account_id) {
+                let amount = if (posting.side == EntrySide::Debit) == debit_normal {
+                    posting.amount
+                } else {
+                    -posting.amount
+                };
+                balance += amount;
+                movements.push(AccountMovement {
+                    entry_id: entry.id,
+                    description: entry.description.clone(),
+                    reference_id: entry.reference_id,
+                    amount,
+                    balance_after: balance,
+                    created_at: entry.created_at,
+                });
+            }
+        }
+        Ok(movements)
+    }
+
+    async fn post_journal_entry(&self, request: JournalEntryRequest) -> ApiResult<JournalEntry> {
+        self.state.write().await.post(request, None)
+    }
+
+    async fn reverse_journal_entry(
+        &self,
+        entry_id: Uuid,
+        reviewer: &User,
+        reason: String,
+    ) -> ApiResult<JournalEntry> {
+        if !reviewer.is_admin() {
+            return Err(ApiError::Authorization {
+                message: "Only admins can reverse journal entries".to_string(),
+            });
+        }
+
+        let mut state = self.state.write().await;
+        let original = state
+            .entries
+            .iter()
+            .find(|entry| entry.id == entry_id)
+            .cloned()
+            .ok_or_else(|| ApiError::NotFound {
+                resource: format!("Journal entry {}", entry_id),
+            })?;
+        if original.reverses.is_some() {
+            return Err(ApiError::Conflict {
+                message: "Reversals cannot themselves be reversed".to_string(),
+            });
+        }
+        if state.entries.iter().any(|e| e.reverses == Some(entry_id)) {
+            return Err(ApiError::Conflict {
+                message: "Journal entry has already been reversed".to_string(),
+            });
+        }
+
+        let postings = original
+            .postings
+            .iter()
+            .map(|posting| PostingRequest {
+                account: state.accounts[&posting.account_id].key,
+                side: posting.side.opposite(),
+                amount: posting.amount,
+            })
+            .collect();
+        let description = match reason.trim() {
+            "" => format!("Reversal of {}", original.description),
+            reason => format!("Reversal of {}: {}", original.description, reason),
+        };
+
+        state.post(
+            JournalEntryRequest {
+                description,
+                reference_id: original.reference_id,
+                postings,
+                created_by: Some(reviewer.id),
+            },
+            Some(entry_id),
+        )
+    }
+
+    async fn get_journal_entries(
+        &self,
+        filter: JournalFilter,
+        pagination: PaginationQuery,
+    ) -> ApiResult<PaginatedResponse<JournalEntry>> {
+        let state = self.state.read().await;
+        let entries: Vec<JournalEntry> = state
+            .entries
+            .iter()
+            .rev()
+            .filter(|entry| {
+                filter.account_id.is_none_or(|id| {
+                    entry
+                        .postings
+                        .iter()
+                        .any(|posting| posting.account_id == id)
+                })
+            })
+            .filter(|entry| {
+                filter
+                    .reference_id
+                    .is_none_or(|id| entry.reference_id == Some(id))
+            })
+            .cloned()
+            .collect();
+        Ok(PaginatedResponse::paginate(entries, &pagination))
+    }
+
+    async fn get_trial_balance(&self) -> ApiResult<Vec<TrialBalanceLine>> {
+        let state = self.state.read().await;
+        let mut totals = BTreeMap::new();
+        for posting in state.entries.iter().flat_map(|entry| entry.postings.iter()) {
+            let (debits, credits) = totals
+                .entry(posting.currency)
+                .or_insert((Decimal::ZERO, Decimal::ZERO));
+            match posting.side {
+                EntrySide::Debit => *debits += posting.amount,
+                EntrySide::Credit => *credits += posting.amount,
+            }
+        }
+
+        Ok(totals
+            .into_iter()
+            .map(|(currency, (debits, credits))| TrialBalanceLine {
+                currency,
+                debits,
+                credits,
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::traits::ledger::{AccountPurpose, LedgerCurrency};
+    use crate::api::types::Role;
+
+    fn admin() -> User {
+        User {
+            id: Uuid::new_v4(),
+            phone: None,
+            nostr: None,
+            profile: None,
+            roles: vec![Role::Admin],
+            verified: true,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn deposit(member: Uuid, wallet: Uuid, sats: u64) -> JournalEntryRequest {
+        JournalEntryRequest {
+            description: "Deposit".to_string(),
+            reference_id: None,
+            postings: vec![
+                PostingRequest::debit(
+                    AccountKey::sacco(AccountPurpose::Treasury, LedgerCurrency::Sats),
+                    sats,
+                ),
+                PostingRequest::credit(AccountKey::member_wallet(member, wallet), sats),
+            ],
+            created_by: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unbalanced_entries_are_rejected() {
+        let ledger = RustLedgerApi::new();
+        let member = Uuid::new_v4();
+
+        let mut unbalanced = deposit(member, Uuid::new_v4(), 1_000);
+        unbalanced.postings[1].amount = Decimal::from(999);
+        assert!(matches!(
+            ledger.post_journal_entry(unbalanced).await,
+            Err(ApiError::Validation { .. })
+        ));
+
+        // Balanced in total but not per currency
+        let mixed = JournalEntryRequest {
+            description: "Mixed".to_string(),
+            reference_id: None,
+            postings: vec![
+                PostingRequest::debit(
+                    AccountKey::sacco(AccountPurpose::Treasury, LedgerCurrency::Kes),
+                    100,
+                ),
+                PostingRequest::credit(
+                    AccountKey::member(member, AccountPurpose::ShareCapital, LedgerCurrency::Sats),
+                    100,
+                ),
+            ],
+            created_by: None,
+        };
+        assert!(ledger.post_journal_entry(mixed).await.is_err());
+
+        let mut fractional = deposit(member, Uuid::new_v4(), 0);
+        for posting in fractional.postings.iter_mut() {
+            posting.amount = Decimal::new(15, 1);
+        }
+        assert!(ledger.post_journal_entry(fractional).await.is_err());
+
+        assert!(ledger.get_trial_balance().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_balances_and_reversals_are_computed_from_journal() {
+        let ledger = RustLedgerApi::new();
+        let member = Uuid::new_v4();
+        let wallet = Uuid::new_v4();
+        let wallet_account = AccountKey::member_wallet(member, wallet);
+
+        let entry = ledger
+            .post_journal_entry(deposit(member, wallet, 5_000))
+            .await
+            .unwrap();
+        ledger
+            .post_journal_entry(deposit(member, wallet, 2_000))
+            .await
+            .unwrap();
+        assert_eq!(
+            ledger.get_account_balance(wallet_account).await.unwrap(),
+            Decimal::from(7_000)
+        );
+
+        let reversal = ledger
+            .reverse_journal_entry(entry.id, &admin(), "Duplicate".to_string())
+            .await
+            .unwrap();
+        assert_eq!(reversal.reverses, Some(entry.id));
+        assert_eq!(
+            ledger.get_account_balance(wallet_account).await.unwrap(),
+            Decimal::from(2_000)
+        );
+        assert!(ledger
+            .reverse_journal_entry(entry.id, &admin(), String::new())
+            .await
+            .is_err());
+
+        let trial = ledger.get_trial_balance().await.unwrap();
+        assert_eq!(trial.len(), 1);
+        assert!(trial[0].is_balanced());
+        assert_eq!(trial[0].debits, Decimal::from(12_000));
+
+        let member_accounts = ledger
+            .get_accounts(Some(AccountOwner::Member(member)))
+            .await
+            .unwrap();
+        assert_eq!(member_accounts.len(), 1);
+        assert_eq!(member_accounts[0].balance, Decimal::from(2_000));
+    }
+
+    #[tokio::test]
+    async fn test_overdraft_guard() {
+        let ledger = RustLedgerApi::new();
+        let member = Uuid::new_v4();
+        let wallet = Uuid::new_v4();
+        let account = AccountKey::member_wallet(member, wallet);
+        ledger
+            .post_journal_entry(deposit(member, wallet, 1_000))
+            .await
+            .unwrap();
+
+        let withdrawal = |sats: u64| JournalEntryRequest {
+            description: "Withdrawal".to_string(),
+            reference_id: None,
+            postings: vec![
+                PostingRequest::debit(account, sats),
+                PostingRequest::credit(
+                    AccountKey::sacco(AccountPurpose::Treasury, LedgerCurrency::Sats),
+                    sats,
+                ),
+            ],
+            created_by: None,
+        };
+
+        assert!(ledger
+            .post_without_overdraft(withdrawal(1_001), account)
+            .await
+            .is_err());
+        ledger
+            .post_without_overdraft(withdrawal(1_000), account)
+            .await
+            .unwrap();
+        assert!(ledger.get_account_balance(account).await.unwrap().is_zero());
+    }
+
+    #[tokio::test]
+    async fn test_journal_survives_restart() {
+        let dir = std::env::temp_dir().join(format!("bitsacco-ledger-{}", Uuid::new_v4()));
+        let path = dir.join("ledger.jsonl");
+        let member = Uuid::new_v4();
+        let wallet = Uuid::new_v4();
+        let account = AccountKey::member_wallet(member, wallet);
+
+        let ledger = RustLedgerApi::new().with_log(JsonLog::at(&path)).unwrap();
+        let entry = ledger
+            .post_journal_entry(deposit(member, wallet, 5_000))
+            .await
+            .unwrap();
+        ledger
+            .reverse_journal_entry(entry.id, &admin(), String::new())
+            .await
+            .unwrap();
+        ledger
+            .post_journal_entry(deposit(member, wallet, 2_000))
+            .await
+            .unwrap();
+
+        let restarted = RustLedgerApi::new().with_log(JsonLog::at(&path)).unwrap();
+        assert_eq!(
+            restarted.get_account_balance(account).await.unwrap(),
+            Decimal::from(2_000)
+        );
+        assert_eq!(
+            restarted.get_accounts(None).await.unwrap().len(),
+            ledger.get_accounts(None).await.unwrap().len()
+        );
+        assert!(restarted
+            .reverse_journal_entry(entry.id, &admin(), String::new())
+            .await
+            .is_err());
+
+        // An entry that can't be written isn't posted
+        std::fs::remove_dir_all(&dir).unwrap();
+        std::fs::write(&dir, b"").unwrap();
+        assert!(restarted
+            .post_journal_entry(deposit(member, wallet, 1_000))
+            .await
+            .is_err());
+        assert_eq!(
+            restarted.get_account_balance(account).await.unwrap(),
+            Decimal::from(2_000)
+        );
+        std::fs::remove_file(&dir).unwrap();
+    }
+}
@@ -0,0 +1,738 @@
+// Lightning deposits and withdrawals for the Rust backend
+// Invoices are created and paid through fmcd; deposits stay pending on the wallet
+// until fmcd reports the invoice paid, which the settlement poller checks for.
+// A payment fmcd never answered for keeps its funds held until the poller finds
+// its outcome in fmcd's operation log. Invoices and payments are saved to a JSON
+// store whenever they change.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use super::{JsonStore, RustLedgerApi, RustWalletsApi};
+use crate::api::{
+    backends::fmcd::{FmcdClient, FmcdOperation, InvoiceState},
+    errors::{ApiError, ApiResult},
+    traits::{
+        ledger::{
+            AccountKey, AccountPurpose, JournalEntryRequest, LedgerApi, LedgerCurrency,
+            PostingRequest,
+        },
+        lightning::{
+            bolt11_amount_msat, CreateInvoiceRequest, LightningApi, LightningInvoice,
+            LightningPayment, PayInvoiceRequest,
+        },
+        reconciliation::ReconciliationItem,
+        wallets::{TransactionStatus, TransactionType, WalletType, WalletsApi},
+    },
+    types::User,
+};
+
+/// Allowance for clock differences when matching payments to fmcd operations
+const OPERATION_CLOCK_SKEW_SECS: i64 = 60;
+
+/// Whether a failed `pay` call may still have sent the payment: fmcd never
+/// answered, or answered in a way we couldn't read
+fn payment_may_be_in_flight(error: &ApiError) -> bool {
+    matches!(
+        error,
+        ApiError::Network { .. } | ApiError::Serialization { .. } | ApiError::Unknown { .. }
+    )
+}
+
+#[derive(Default, serde::Serialize, serde::Deserialize)]
+struct LightningState {
+    invoices: Vec<LightningInvoice>,
+    payments: Vec<LightningPayment>,
+}
+
+#[derive(Clone)]
+pub struct RustLightningApi {
+    state: Arc<RwLock<LightningState>>,
+    ledger: RustLedgerApi,
+    wallets: RustWalletsApi,
+    fmcd: FmcdClient,
+    store: JsonStore,
+}
+
+impl RustLightningApi {
+    pub fn new(ledger: RustLedgerApi, wallets: RustWalletsApi, fmcd: FmcdClient) -> Self {
+        Self {
+            state: Arc::default(),
+            ledger,
+            wallets,
+            fmcd,
+            store: JsonStore::in_memory(),
+        }
+    }
+
+    /// Keep invoices and payments in `store`, starting from what it already holds
+    pub fn with_store(mut self, store: JsonStore) -> ApiResult<Self> {
+        let state: LightningState = store.load()?;
+        self.state = Arc::new(RwLock::new(state));
+        self.store = store;
+        Ok(self)
+    }
+
+    /// Check pending invoices every `every` until the process exits
+    pub fn spawn_settlement_poller(&self, every: Duration) -> tokio::task::JoinHandle<()> {
+        let api = self.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(every);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                ticker.tick().await;
+                match api.sync_pending_invoices().await {
+                    Ok(0) => {}
+                    Ok(settled) => tracing::info!("Settled {} Lightning invoice(s)", settled),
+                    Err(e) => tracing::warn!("Lightning settlement check failed: {}", e),
+                }
+            }
+        })
+    }
+
+    /// Confirmed deposits and payments, keyed by the fmcd operation that moved the funds
+    pub async fn settled_movements(&self) -> Vec<ReconciliationItem> {
+        let state = self.state.read().await;
+        let deposits = state
+            .invoices
+            .iter()
+            .filter(|invoice| invoice.status == TransactionStatus::Confirmed)
+            .map(|invoice| ReconciliationItem {
+                reference: invoice.operation_id.clone(),
+                amount_sats: invoice.amount_sats as i64,
+                description: format!("Lightning deposit to wallet {}", invoice.wallet_id),
+                occurred_at: invoice.settled_at.unwrap_or(invoice.created_at),
+            });
+        let payments = state.payments.iter().filter_map(|payment| {
+            let operation_id = payment.operation_id.clone()?;
+            (payment.status == TransactionStatus::Confirmed).then(|| ReconciliationItem {
+                reference: operation_id,
+                amount_sats: -(payment.amount_sats as i64),
+                description: format!("Lightning payment from wallet {}", payment.wallet_id),
+                occurred_at: payment.settled_at.unwrap_or(payment.created_at),
+            })
+        });
+        deposits.chain(payments).collect()
+    }
+
+    /// Only wallets backed by the federation can send and receive over Lightning
+    async fn lightning_wallet(&self, wallet_id: Uuid) -> ApiResult<()> {
+        let wallet = self.wallets.get_wallet(wallet_id).await?;
+        match wallet.wallet_type {
+            WalletType::Lightning | WalletType::Fedimint => Ok(()),
+            WalletType::OnChain => Err(ApiError::Validation {
+                message: format!("Wallet {} is an on-chain wallet", wallet.name),
+            }),
+        }
+    }
+
+    /// Settle a payment's held funds; a confirmed payment's routing fee is
+    /// charged to the SACCO
+    async fn finish_payment(
+        &self,
+        payment: &mut LightningPayment,
+        status: TransactionStatus,
+    ) -> ApiResult<()> {
+        self.wallets
+            .settle(payment.transaction_id, status.clone())
+            .await?;
+        if status == TransactionStatus::Confirmed && payment.fee_sats > 0 {
+            let treasury = AccountKey::sacco(AccountPurpose::Treasury, LedgerCurrency::Sats);
+            let fees = AccountKey::sacco(AccountPurpose::RoutingFees, LedgerCurrency::Sats);
+            self.ledger
+                .post_journal_entry(JournalEntryRequest {
+                    description: format!("Lightning routing fee for payment {}", payment.id),
+                    reference_id: Some(payment.id),
+                    postings: vec![
+                        PostingRequest::debit(fees, payment.fee_sats),
+                        PostingRequest::credit(treasury, payment.fee_sats),
+                    ],
+                    created_by: None,
+                })
+                .await?;
+        }
+        payment.status = status;
+        payment.settled_at = Some(Utc::now());
+        Ok(())
+    }
+
+    /// Resolve payments fmcd never answered for from its operation log. fmcd
+    /// doesn't log the invoice, so each payment takes the first unclaimed
+    /// outgoing operation for the same amount started after it. A payment fmcd
+    /// has no record of once the request has surely timed out never left.
+    async fn sync_pending_payments(&self) -> ApiResult<usize> {
+        let pending: Vec<LightningPayment> = self
+            .state
+            .read()
+            .await
+            .payments
+            .iter()
+            .filter(|payment| payment.status == TransactionStatus::Pending)
+            .cloned()
+            .collect();
+        if pending.is_empty() {
+            return Ok(0);
+        }
+
+        let mut operations: Vec<FmcdOperation> = self
+            .fmcd
+            .operations()
+            .await?
+            .into_iter()
+            .filter(FmcdOperation::is_outgoing)
+            .collect();
+        operations.sort_by_key(|operation| operation.creation_time);
+        let mut claimed: Vec<String> = self
+            .state
+            .read()
+            .await
+            .payments
+            .iter()
+            .filter_map(|payment| payment.operation_id.clone())
+            .collect();
+        let request_timeout =
+            chrono::Duration::seconds(self.fmcd.config().timeout_seconds as i64 * 2);
+
+        let mut settled = 0;
+        for mut payment in pending {
+            // Checked when the payment was made
+            let Ok(Some(amount_msat)) = bolt11_amount_msat(&payment.bolt11) else {
+                continue;
+            };
+            let earliest =
+                payment.created_at - chrono::Duration::seconds(OPERATION_CLOCK_SKEW_SECS);
+            let operation = operations.iter().find(|operation| {
+                operation.amount_msat == amount_msat
+                    && operation.creation_time >= earliest
+                    && !claimed.contains(&operation.operation_id)
+            });
+
+            let status = match operation {
+                Some(operation) => {
+                    claimed.push(operation.operation_id.clone());
+                    payment.operation_id = Some(operation.operation_id.clone());
+                    match operation.outcome.as_str() {
+                        "success" => {
+                            payment.fee_sats = operation.fee_msat.div_ceil(1_000);
+                            TransactionStatus::Confirmed
+                        }
+                        "failed" => {
+                            payment.failure_reason =
+                                Some("fmcd reported the payment failed".to_string());
+                            TransactionStatus::Failed
+                        }
+                        _ => {
+                            self.store_payment(&payment).await;
+                            continue;
+                        }
+                    }
+                }
+                None if Utc::now() - payment.created_at > request_timeout => {
+                    payment.failure_reason = Some("fmcd has no record of the payment".to_string());
+                    TransactionStatus::Failed
+                }
+                None => continue,
+            };
+            if let Err(e) = self.finish_payment(&mut payment, status).await {
+                tracing::warn!("Could not settle Lightning payment {}: {}", payment.id, e);
+                continue;
+            }
+            self.store_payment(&payment).await;
+            settled += 1;
+        }
+        Ok(settled)
+    }
+
+    async fn store_payment(&self, payment: &LightningPayment) {
+        let mut state = self.state.write().await;
+        match state.payments.iter_mut().find(|p| p.id == payment.id) {
+            Some(stored) => *stored = payment.clone(),
+            None => state.payments.push(payment.clone()),
+        }
+        self.store.save_or_log(&*state, "Lightning payments");
+    }
+
+    /// Record the final status of an invoice and its wallet transaction
+    async fn settle_invoice(
+        &self,
+        invoice_id: Uuid,
+        transaction_id: Uuid,
+        status: TransactionStatus,
+    ) -> ApiResult<()> {
+        self.wallets.settle(transaction_id, status.clone()).await?;
+
+        let mut state = self.state.write().await;
+        if let Some(invoice) = state.invoices.iter_mut().find(|i| i.id == invoice_id) {
+            invoice.status = status;
+            invoice.settled_at = Some(Utc::now());
+        }
+        self.store.save_or_log(&*state, "Lightning invoices");
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl LightningApi for RustLightningApi {
+    async fn create_deposit_invoice(
+        &self,
+        request: CreateInvoiceRequest,
+    ) -> ApiResult<LightningInvoice> {
+        if request.amount_sats == 0 {
+            return Err(ApiError::Validation {
+                message: "Invoice amount must be greater than zero".to_string(),
+            });
+        }
+        if request.expiry_secs == 0 {
+            return Err(ApiError::Validation {
+                message: "Invoice expiry must be greater than zero".to_string(),
+            });
+        }
+        self.lightning_wallet(request.wallet_id).await?;
+
+        let created = self
+            .fmcd
+            .create_invoice(
+                request.amount_sats * 1_000,
+                &request.description,
+                request.expiry_secs,
+            )
+            .await?;
+        let transaction = self
+            .wallets
+            .record_pending_credit(
+                request.wallet_id,
+                request.amount_sats,
+                TransactionType::Deposit,
+            )
+            .await?;
+
+        let now = Utc::now();
+        let invoice = LightningInvoice {
+            id: Uuid::new_v4(),
+            wallet_id: request.wallet_id,
+            transaction_id: transaction.id,
+            bolt11: created.invoice,
+            amount_sats: request.amount_sats,
+            description: request.description,
+            operation_id: created.operation_id,
+            status: TransactionStatus::Pending,
+            created_at: now,
+            expires_at: now + chrono::Duration::seconds(request.expiry_secs.into()),
+            settled_at: None,
+        };
+        let mut state = self.state.write().await;
+        state.invoices.push(invoice.clone());
+        self.store.save_or_log(&*state, "Lightning invoices");
+        Ok(invoice)
+    }
+
+    async fn pay_invoice(
+        &self,
+        request: PayInvoiceRequest,
+        payer: &User,
+    ) -> ApiResult<LightningPayment> {
+        let bolt11 = request.bolt11.trim().to_string();
+        let amount_msat = bolt11_amount_msat(&bolt11)?.ok_or_else(|| ApiError::Validation {
+            message: "Invoices without an amount cannot be paid from a wallet".to_string(),
+        })?;
+        let amount_sats = amount_msat.div_ceil(1_000);
+        self.lightning_wallet(request.wallet_id).await?;
+
+        // Hold the funds before paying so concurrent withdrawals cannot overdraw
+        let transaction = self
+            .wallets
+            .hold_debit(
+                request.wallet_id,
+                amount_sats,
+                TransactionType::Withdrawal,
+                payer,
+            )
+            .await?;
+        let mut payment = LightningPayment {
+            id: Uuid::new_v4(),
+            wallet_id: request.wallet_id,
+            transaction_id: transaction.id,
+            bolt11,
+            amount_sats,
+            fee_sats: 0,
+            operation_id: None,
+            status: TransactionStatus::Pending,
+            failure_reason: None,
+            created_at: Utc::now(),
+            settled_at: None,
+        };
+
+        match self.fmcd.pay_invoice(&payment.bolt11).await {
+            Ok(paid) => {
+                payment.operation_id = Some(paid.operation_id);
+                // Routing fees are borne by the SACCO
+                payment.fee_sats = paid.fee.div_ceil(1_000);
+                let settled = self
+                    .finish_payment(&mut payment, TransactionStatus::Confirmed)
+                    .await;
+                self.store_payment(&payment).await;
+                settled.map(|_| payment)
+            }
+            Err(e) if payment_may_be_in_flight(&e) => {
+                // Releasing the hold now could let the same sats be spent twice
+                tracing::warn!(
+                    "Lightning payment {} outcome unknown, leaving it pending: {}",
+                    payment.id,
+                    e
+                );
+                self.store_payment(&payment).await;
+                Ok(payment)
+            }
+            Err(e) => {
+                payment.failure_reason = Some(e.to_string());
+                self.finish_payment(&mut payment, TransactionStatus::Failed)
+                    .await?;
+                self.store_payment(&payment).await;
+                Err(e)
+            }
+        }
+    }
+
+    async fn get_wallet_invoices(&self, wallet_id: Uuid) -> ApiResult<Vec<LightningInvoice>> {
+        let mut invoices: Vec<LightningInvoice> = self
+            .state
+            .read()
+            .await
+            .invoices
+            .iter()
+            .filter(|invoice| invoice.wallet_id == wallet_id)
+            .cloned()
+            .collect();
+        invoices.sort_by_key(|invoice| std::cmp::Reverse(invoice.created_at));
+        Ok(invoices)
+    }
+
+    async fn get_wallet_payments(&self, wallet_id: Uuid) -> ApiResult<Vec<LightningPayment>> {
+        let mut payments: Vec<LightningPayment> = self
+            .state
+            .read()
+            .await
+            .payments
+            .iter()
+            .filter(|payment| payment.wallet_id == wallet_id)
+            .cloned()
+            .collect();
+        payments.sort_by_key(|payment| std::cmp::Reverse(payment.created_at));
+        Ok(payments)
+    }
+
+    async fn sync_pending_invoices(&self) -> ApiResult<usize> {
+        let pending: Vec<LightningInvoice> = self
+            .state
+            .read()
+            .await
+            .invoices
+            .iter()
+            .filter(|invoice| invoice.status == TransactionStatus::Pending)
+            .cloned()
+            .collect();
+
+        // One invoice fmcd can't answer for doesn't hold up the rest
+        let mut settled = 0;
+        for invoice in pending {
+            let state = match self.fmcd.invoice_state(&invoice.operation_id).await {
+                Ok(state) => state,
+                Err(e) => {
+                    tracing::warn!("Could not check Lightning invoice {}: {}", invoice.id, e);
+                    continue;
+                }
+            };
+            let status = match state {
+                InvoiceState::Paid => TransactionStatus::Confirmed,
+                InvoiceState::Failed(reason) => {
+                    tracing::info!("Lightning invoice {} failed: {}", invoice.id, reason);
+                    TransactionStatus::Failed
+                }
+                InvoiceState::Pending if Utc::now() >= invoice.expires_at => {
+                    TransactionStatus::Failed
+                }
+                InvoiceState::Pending => continue,
+            };
+            if let Err(e) = self
+                .settle_invoice(invoice.id, invoice.transaction_id, status)
+                .await
+            {
+                tracing::warn!("Could not settle Lightning invoice {}: {}", invoice.id, e);
+                continue;
+            }
+            settled += 1;
+        }
+
+        match self.sync_pending_payments().await {
+            Ok(payments) => settled += payments,
+            Err(e) => tracing::warn!("Could not check pending Lightning payments: {}", e),
+        }
+        Ok(settled)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::{
+        backends::{fmcd::mock::MockFmcd, rust::RustLedgerApi},
+        traits::wallets::CreateWalletRequest,
+        types::{common::Role, PaginationQuery},
+    };
+
+    fn member(id: Uuid) -> User {
+        User {
+            id,
+            phone: None,
+            nostr: None,
+            profile: None,
+            roles: vec![Role::Member],
+            verified: true,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    async fn setup(fmcd: &MockFmcd) -> (RustLightningApi, RustWalletsApi, Uuid) {
+        let ledger = RustLedgerApi::new();
+        let wallets = RustWalletsApi::new(ledger.clone());
+        let wallet = wallets
+            .create_wallet(CreateWalletRequest {
+                user_id: Uuid::new_v4(),
+                name: "Spending".to_string(),
+                wallet_type: WalletType::Lightning,
+            })
+            .await
+            .unwrap();
+        let api = RustLightningApi::new(ledger.clone(), wallets.clone(), fmcd.client());
+        (api, wallets, wallet.id)
+    }
+
+    async fn deposit(
+        api: &RustLightningApi,
+        wallet_id: Uuid,
+        amount_sats: u64,
+    ) -> LightningInvoice {
+        api.create_deposit_invoice(CreateInvoiceRequest {
+            wallet_id,
+            amount_sats,
+            description: "Deposit".to_string(),
+            expiry_secs: 3_600,
+        })
+        .await
+        .unwrap()
+    }
+
+    #[test]
+    fn test_bolt11_amounts() {
+        assert_eq!(
+            bolt11_amount_msat("lnbc2500u1pvjluez").unwrap(),
+            Some(250_000_000)
+        );
+        assert_eq!(
+            bolt11_amount_msat("LNTB20M1pvjluez").unwrap(),
+            Some(2_000_000_000)
+        );
+        assert_eq!(bolt11_amount_msat("lnbcrt10n1pabc").unwrap(), Some(1_000));
+        assert_eq!(bolt11_amount_msat("lnbc1pvjluez").unwrap(), None);
+        assert_eq!(bolt11_amount_msat("lnbc10p1pvjluez").unwrap(), Some(1));
+        assert!(bolt11_amount_msat("lnbc15p1pvjluez").is_err());
+        assert!(bolt11_amount_msat("bc1qxyz").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_deposit_invoice_settles_into_wallet() {
+        let fmcd = MockFmcd::start().await;
+        let (api, wallets, wallet_id) = setup(&fmcd).await;
+
+        let paid = deposit(&api, wallet_id, 5_000).await;
+        let expired = deposit(&api, wallet_id, 7_000).await;
+        let cancelled = deposit(&api, wallet_id, 9_000).await;
+        assert_eq!(bolt11_amount_msat(&paid.bolt11).unwrap(), Some(5_000_000));
+
+        // Nothing is credited while invoices are pending
+        assert_eq!(api.sync_pending_invoices().await.unwrap(), 0);
+        assert_eq!(wallets.get_wallet_balance(wallet_id).await.unwrap(), 0);
+
+        fmcd.mark_paid(&paid.operation_id).await;
+        fmcd.mark_failed(&cancelled.operation_id).await;
+        api.state
+            .write()
+            .await
+            .invoices
+            .iter_mut()
+            .find(|invoice| invoice.id == expired.id)
+            .unwrap()
+            .expires_at = Utc::now() - chrono::Duration::seconds(1);
+
+        assert_eq!(api.sync_pending_invoices().await.unwrap(), 3);
+        assert_eq!(wallets.get_wallet_balance(wallet_id).await.unwrap(), 5_000);
+
+        let status_of = |invoices: &[LightningInvoice], id: Uuid| {
+            invoices.iter().find(|i| i.id == id).unwrap().status.clone()
+        };
+        let invoices = api.get_wallet_invoices(wallet_id).await.unwrap();
+        assert_eq!(status_of(&invoices, paid.id), TransactionStatus::Confirmed);
+        assert_eq!(status_of(&invoices, expired.id), TransactionStatus::Failed);
+        assert_eq!(
+            status_of(&invoices, cancelled.id),
+            TransactionStatus::Failed
+        );
+
+        let history = wallets
+            .get_wallet_transactions(wallet_id, PaginationQuery::default())
+            .await
+            .unwrap();
+        let tx = history
+            .data
+            .iter()
+            .find(|tx| tx.id == paid.transaction_id)
+            .unwrap();
+        assert_eq!(tx.status, TransactionStatus::Confirmed);
+        assert!(history
+            .data
+            .iter()
+            .all(|tx| tx.status != TransactionStatus::Pending));
+
+        // Settled invoices are not checked again
+        assert_eq!(api.sync_pending_invoices().await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_payments_hold_and_release_funds() {
+        let fmcd = MockFmcd::start().await;
+        let (api, wallets, wallet_id) = setup(&fmcd).await;
+        let payer = member(wallets.get_wallet(wallet_id).await.unwrap().user_id);
+        wallets
+            .credit(wallet_id, 10_000, TransactionType::Deposit)
+            .await
+            .unwrap();
+
+        let payment = api
+            .pay_invoice(
+                PayInvoiceRequest {
+                    wallet_id,
+                    bolt11: "lnbcrt40u1pexample".to_string(),
+                },
+                &payer,
+            )
+            .await
+            .unwrap();
+        assert_eq!(payment.amount_sats, 4_000);
+        assert_eq!(payment.fee_sats, 2);
+        assert_eq!(payment.status, TransactionStatus::Confirmed);
+        assert_eq!(fmcd.paid_invoices().await, vec!["lnbcrt40u1pexample"]);
+        assert_eq!(wallets.get_wallet_balance(wallet_id).await.unwrap(), 6_000);
+
+        // A failed payment returns the held funds
+        fmcd.refuse("lnbcrt50u1prefused").await;
+        let refused = api
+            .pay_invoice(
+                PayInvoiceRequest {
+                    wallet_id,
+                    bolt11: "lnbcrt50u1prefused".to_string(),
+                },
+                &payer,
+            )
+            .await;
+        assert!(matches!(refused, Err(ApiError::Server { .. })));
+        assert_eq!(wallets.get_wallet_balance(wallet_id).await.unwrap(), 6_000);
+        let payments = api.get_wallet_payments(wallet_id).await.unwrap();
+        assert_eq!(payments[0].status, TransactionStatus::Failed);
+        assert!(payments[0].failure_reason.is_some());
+
+        // Payments larger than the balance never reach fmcd
+        let overdraft = api
+            .pay_invoice(
+                PayInvoiceRequest {
+                    wallet_id,
+                    bolt11: "lnbcrt70u1ptoolarge".to_string(),
+                },
+                &payer,
+            )
+            .await;
+        assert!(matches!(overdraft, Err(ApiError::Validation { .. })));
+        assert_eq!(fmcd.paid_invoices().await.len(), 1);
+
+        let amountless = api
+            .pay_invoice(
+                PayInvoiceRequest {
+                    wallet_id,
+                    bolt11: "lnbcrt1pnoamount".to_string(),
+                },
+                &payer,
+            )
+            .await;
+        assert!(matches!(amountless, Err(ApiError::Validation { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_unanswered_payments_stay_held_until_fmcd_reports_them() {
+        let fmcd = MockFmcd::start().await;
+        let (api, wallets, wallet_id) = setup(&fmcd).await;
+        let payer = member(wallets.get_wallet(wallet_id).await.unwrap().user_id);
+        wallets
+            .credit(wallet_id, 10_000, TransactionType::Deposit)
+            .await
+            .unwrap();
+
+        // fmcd paid, but its reply never arrived: the funds stay held
+        fmcd.lose_reply("lnbcrt30u1plostreply").await;
+        let sent = api
+            .pay_invoice(
+                PayInvoiceRequest {
+                    wallet_id,
+                    bolt11: "lnbcrt30u1plostreply".to_string(),
+                },
+                &payer,
+            )
+            .await
+            .unwrap();
+        assert_eq!(sent.status, TransactionStatus::Pending);
+        assert_eq!(wallets.get_wallet_balance(wallet_id).await.unwrap(), 7_000);
+
+        // The request never reached fmcd either, as far as anyone can tell yet
+        fmcd.lose_request("lnbcrt20u1plostrequest").await;
+        let lost = api
+            .pay_invoice(
+                PayInvoiceRequest {
+                    wallet_id,
+                    bolt11: "lnbcrt20u1plostrequest".to_string(),
+                },
+                &payer,
+            )
+            .await
+            .unwrap();
+        assert_eq!(lost.status, TransactionStatus::Pending);
+        assert_eq!(wallets.get_wallet_balance(wallet_id).await.unwrap(), 5_000);
+
+        // The sent payment is found in fmcd's log; the lost one waits out the timeout
+        assert_eq!(api.sync_pending_invoices().await.unwrap(), 1);
+        let payments = api.get_wallet_payments(wallet_id).await.unwrap();
+        let status_of = |id: Uuid| payments.iter().find(|p| p.id == id).unwrap().clone();
+        assert_eq!(status_of(sent.id).status, TransactionStatus::Confirmed);
+        assert_eq!(status_of(sent.id).fee_sats, 2);
+        assert!(status_of(sent.id).operation_id.is_some());
+        assert_eq!(status_of(lost.id).status, TransactionStatus::Pending);
+
+        api.state
+            .write()
+            .await
+            .payments
+            .iter_mut()
+            .find(|payment| payment.id == lost.id)
+            .unwrap()
+            .created_at = Utc::now() - chrono::Duration::hours(1);
+        assert_eq!(api.sync_pending_invoices().await.unwrap(), 1);
+        let payments = api.get_wallet_payments(wallet_id).await.unwrap();
+        let lost = payments.iter().find(|p| p.id == lost.id).unwrap();
+        assert_eq!(lost.status, TransactionStatus::Failed);
+        assert_eq!(wallets.get_wallet_balance(wallet_id).await.unwrap(), 7_000);
+        assert_eq!(fmcd.paid_invoices().await, vec!["lnbcrt30u1plostreply"]);
+    }
+}
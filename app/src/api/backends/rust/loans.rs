@@ -0,0 +1,1335 @@
+// In-process loans engine for the Rust backend
+// Applications go through maker-checker approval, are disbursed into a member
+// wallet and repaid against an amortised monthly schedule. Principal is carried
+// on the member's loans-receivable account and interest is booked as SACCO income.
+// Products and loans are saved to a JSON store after each change.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Months, Utc};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use super::{wallets::signed_sats, JsonStore, RustLedgerApi, RustSharesApi, RustWalletsApi};
+use crate::api::{
+    errors::{ApiError, ApiResult},
+    traits::{
+        ledger::{
+            AccountKey, AccountPurpose, JournalEntryRequest, LedgerApi, LedgerCurrency,
+            PostingRequest,
+        },
+        loans::{
+            CreateLoanProductRequest, GuaranteeStatus, Guarantor, Loan, LoanApplicationRequest,
+            LoanFilter, LoanProduct, LoanRepaymentRequest, LoanStatus, LoansApi,
+            RepaymentInstallment, MAX_ANNUAL_INTEREST_RATE, MAX_TERM_MONTHS,
+        },
+        wallets::{TransactionType, WalletsApi},
+    },
+    types::{PaginatedResponse, PaginationQuery, User},
+};
+
+#[derive(Default, serde::Serialize, serde::Deserialize)]
+struct LoansState {
+    products: HashMap<Uuid, LoanProduct>,
+    loans: HashMap<Uuid, Loan>,
+}
+
+impl LoansState {
+    /// Sats a member has accepted to guarantee on loans not yet rejected or repaid
+    fn pledged(&self, user_id: Uuid) -> u64 {
+        self.loans
+            .values()
+            .filter(|loan| !matches!(loan.status, LoanStatus::Rejected | LoanStatus::Repaid))
+            .flat_map(|loan| &loan.guarantors)
+            .filter(|pledge| {
+                pledge.user_id == user_id && pledge.status == GuaranteeStatus::Accepted
+            })
+            .fold(0, |total: u64, pledge| total.saturating_add(pledge.amount))
+    }
+}
+
+#[derive(Clone)]
+pub struct RustLoansApi {
+    state: Arc<RwLock<LoansState>>,
+    ledger: RustLedgerApi,
+    wallets: RustWalletsApi,
+    shares: RustSharesApi,
+    store: JsonStore,
+}
+
+impl RustLoansApi {
+    pub fn new(ledger: RustLedgerApi, wallets: RustWalletsApi, shares: RustSharesApi) -> Self {
+        Self {
+            state: Arc::default(),
+            ledger,
+            wallets,
+            shares,
+            store: JsonStore::in_memory(),
+        }
+    }
+
+    /// Keep products and loans in `store`, starting from what it already holds
+    pub fn with_store(mut self, store: JsonStore) -> ApiResult<Self> {
+        let state: LoansState = store.load()?;
+        self.state = Arc::new(RwLock::new(state));
+        self.store = store;
+        Ok(self)
+    }
+
+    /// Save a change to a loan, putting `previous` back if it can't be saved
+    fn save_loan(&self, state: &mut LoansState, previous: Loan) -> ApiResult<Loan> {
+        let updated = state
+            .loans
+            .get(&previous.id)
+            .cloned()
+            .ok_or_else(|| loan_not_found(previous.id))?;
+        self.store.save_or_undo(state, |state| {
+            state.loans.insert(previous.id, previous);
+        })?;
+        Ok(updated)
+    }
+
+    /// Total balance across a member's wallets, in sats
+    async fn member_savings(&self, user_id: Uuid) -> ApiResult<u64> {
+        Ok(self
+            .wallets
+            .get_user_wallets(user_id)
+            .await?
+            .iter()
+            .map(|wallet| wallet.balance)
+            .sum())
+    }
+
+    /// Savings a guarantor has not already pledged to other loans, in sats
+    async fn unpledged_savings(&self, user_id: Uuid, state: &LoansState) -> ApiResult<u64> {
+        let savings = self.member_savings(user_id).await?;
+        Ok(savings.saturating_sub(state.pledged(user_id)))
+    }
+
+    /// Shares the member has paid for; shares still being paid for don't back loans
+    async fn member_shares(&self, user_id: Uuid) -> u64 {
+        self.shares.paid_up_shares(user_id).await
+    }
+
+    /// Largest loan the product allows given the member's savings and shares
+    async fn borrowing_limit(&self, product: &LoanProduct, user_id: Uuid) -> ApiResult<u64> {
+        if product.max_savings_multiple.is_none() && product.max_sats_per_share.is_none() {
+            return Ok(product.max_amount);
+        }
+
+        let from_savings = match product.max_savings_multiple {
+            Some(multiple) => (Decimal::from(self.member_savings(user_id).await?) * multiple)
+                .floor()
+                .to_u64()
+                .unwrap_or(u64::MAX),
+            None => 0,
+        };
+        let from_shares = match product.max_sats_per_share {
+            Some(per_share) => self.member_shares(user_id).await.saturating_mul(per_share),
+            None => 0,
+        };
+
+        Ok(from_savings
+            .saturating_add(from_shares)
+            .min(product.max_amount))
+    }
+
+    async fn validate_guarantors(
+        &self,
+        product: &LoanProduct,
+        request: &LoanApplicationRequest,
+    ) -> ApiResult<()> {
+        if (request.guarantors.len() as u32) < product.required_guarantors {
+            return Err(ApiError::Validation {
+                message: format!(
+                    "{} requires {} guarantors but {} were provided",
+                    product.name,
+                    product.required_guarantors,
+                    request.guarantors.len()
+                ),
+            });
+        }
+
+        let mut seen = std::collections::HashSet::new();
+        for guarantor in &request.guarantors {
+            if guarantor.user_id == request.user_id {
+                return Err(ApiError::Validation {
+                    message: "Members cannot guarantee their own loan".to_string(),
+                });
+            }
+            if !seen.insert(guarantor.user_id) {
+                return Err(ApiError::Validation {
+                    message: format!("Guarantor {} is listed more than once", guarantor.user_id),
+                });
+            }
+            if guarantor.amount == 0 {
+                return Err(ApiError::Validation {
+                    message: "Guaranteed amounts must be greater than zero".to_string(),
+                });
+            }
+
+            let state = self.state.read().await;
+            let available = self.unpledged_savings(guarantor.user_id, &state).await?;
+            if available < guarantor.amount {
+                return Err(ApiError::Validation {
+                    message: format!(
+                        "Guarantor {} has {} sats of savings not already pledged, less than the {} sats pledged",
+                        guarantor.user_id, available, guarantor.amount
+                    ),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl LoansApi for RustLoansApi {
+    async fn get_loan_products(&self) -> ApiResult<Vec<LoanProduct>> {
+        let state = self.state.read().await;
+        let mut products: Vec<LoanProduct> = state.products.values().cloned().collect();
+        products.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(products)
+    }
+
+    async fn create_loan_product(
+        &self,
+        request: CreateLoanProductRequest,
+        operator: &User,
+    ) -> ApiResult<LoanProduct> {
+        if !operator.is_admin() {
+            return Err(ApiError::Authorization {
+                message: "Only admins can create loan products".to_string(),
+            });
+        }
+        if request.name.trim().is_empty() {
+            return Err(ApiError::Validation {
+                message: "Loan product name is required".to_string(),
+            });
+        }
+        if request.annual_interest_rate < Decimal::ZERO
+            || request.annual_interest_rate > Decimal::from(MAX_ANNUAL_INTEREST_RATE)
+        {
+            return Err(ApiError::Validation {
+                message: format!(
+                    "Interest rate must be between 0% and {}% a year",
+                    MAX_ANNUAL_INTEREST_RATE
+                ),
+            });
+        }
+        if request.term_months == 0 || request.term_months > MAX_TERM_MONTHS {
+            return Err(ApiError::Validation {
+                message: format!("Loan term must be between 1 and {} months", MAX_TERM_MONTHS),
+            });
+        }
+        if request.min_amount == 0 || request.min_amount > request.max_amount {
+            return Err(ApiError::Validation {
+                message: "Loan amount range must be positive with min not above max".to_string(),
+            });
+        }
+        if request
+            .max_savings_multiple
+            .is_some_and(|multiple| multiple <= Decimal::ZERO)
+        {
+            return Err(ApiError::Validation {
+                message: "Savings multiple must be greater than zero".to_string(),
+            });
+        }
+
+        let product = LoanProduct {
+            id: Uuid::new_v4(),
+            name: request.name.trim().to_string(),
+            annual_interest_rate: request.annual_interest_rate,
+            term_months: request.term_months,
+            min_amount: request.min_amount,
+            max_amount: request.max_amount,
+            max_savings_multiple: request.max_savings_multiple,
+            max_sats_per_share: request.max_sats_per_share.filter(|sats| *sats > 0),
+            required_guarantors: request.required_guarantors,
+            active: true,
+            created_at: Utc::now(),
+        };
+
+        let mut state = self.state.write().await;
+        state.products.insert(product.id, product.clone());
+        self.store.save_or_undo(&mut *state, |state| {
+            state.products.remove(&product.id);
+        })?;
+        Ok(product)
+    }
+
+    async fn apply_for_loan(
+        &self,
+        request: LoanApplicationRequest,
+        maker: &User,
+    ) -> ApiResult<Loan> {
+        if request.user_id != maker.id && !maker.is_admin() {
+            return Err(ApiError::Authorization {
+                message: "Only admins can apply for a loan on another member's behalf".to_string(),
+            });
+        }
+
+        let product = self
+            .state
+            .read()
+            .await
+            .products
+            .get(&request.product_id)
+            .cloned()
+            .ok_or_else(|| ApiError::NotFound {
+                resource: format!("Loan product {}", request.product_id),
+            })?;
+
+        if !product.active {
+            return Err(ApiError::Conflict {
+                message: format!("{} is no longer offered", product.name),
+            });
+        }
+        if request.amount < product.min_amount || request.amount > product.max_amount {
+            return Err(ApiError::Validation {
+                message: format!(
+                    "{} lends between {} and {} sats",
+                    product.name, product.min_amount, product.max_amount
+                ),
+            });
+        }
+
+        let limit = self.borrowing_limit(&product, request.user_id).await?;
+        if request.amount > limit {
+            return Err(ApiError::Validation {
+                message: format!(
+                    "Requested {} sats exceeds the member's borrowing limit of {} sats",
+                    request.amount, limit
+                ),
+            });
+        }
+
+        self.validate_guarantors(&product, &request).await?;
+
+        let now = Utc::now();
+        let mut state = self.state.write().await;
+        let in_arrears = state.loans.values().any(|loan| {
+            loan.user_id == request.user_id && loan.arrears(now).overdue_installments > 0
+        });
+        if in_arrears {
+            return Err(ApiError::Conflict {
+                message: "Member has a loan in arrears and cannot borrow again until it is cleared"
+                    .to_string(),
+            });
+        }
+
+        // Pledges only count once each guarantor has accepted them
+        let guarantors = request
+            .guarantors
+            .into_iter()
+            .map(|guarantor| Guarantor {
+                status: GuaranteeStatus::Pending,
+                ..guarantor
+            })
+            .collect();
+        let loan = Loan {
+            id: Uuid::new_v4(),
+            product_id: product.id,
+            user_id: request.user_id,
+            amount: request.amount,
+            annual_interest_rate: product.annual_interest_rate,
+            term_months: product.term_months,
+            purpose: request.purpose.filter(|p| !p.trim().is_empty()),
+            guarantors,
+            status: LoanStatus::PendingApproval,
+            submitted_by: maker.id,
+            submitted_at: now,
+            reviewed_by: None,
+            reviewed_at: None,
+            rejection_reason: None,
+            disbursed_at: None,
+            disbursement_wallet_id: None,
+            schedule: Vec::new(),
+        };
+        state.loans.insert(loan.id, loan.clone());
+        self.store.save_or_undo(&mut *state, |state| {
+            state.loans.remove(&loan.id);
+        })?;
+
+        Ok(loan)
+    }
+
+    async fn respond_to_guarantee(
+        &self,
+        loan_id: Uuid,
+        guarantor: &User,
+        accept: bool,
+    ) -> ApiResult<Loan> {
+        let mut state = self.state.write().await;
+        let previous = state
+            .loans
+            .get(&loan_id)
+            .cloned()
+            .ok_or_else(|| loan_not_found(loan_id))?;
+        if previous.status != LoanStatus::PendingApproval {
+            return Err(ApiError::Conflict {
+                message: format!(
+                    "Loan is already {}",
+                    previous.status.as_str().replace('_', " ")
+                ),
+            });
+        }
+        let pledge = previous
+            .guarantors
+            .iter()
+            .find(|pledge| pledge.user_id == guarantor.id)
+            .ok_or_else(|| ApiError::Authorization {
+                message: "You are not a guarantor on this loan".to_string(),
+            })?;
+        if pledge.status != GuaranteeStatus::Pending {
+            return Err(ApiError::Conflict {
+                message: "You have already responded to this guarantee".to_string(),
+            });
+        }
+        // Other applications may have been guaranteed since this one was made
+        if accept {
+            let available = self.unpledged_savings(guarantor.id, &state).await?;
+            if available < pledge.amount {
+                return Err(ApiError::Validation {
+                    message: format!(
+                        "You have {} sats of savings not already pledged, less than the {} sats pledged",
+                        available, pledge.amount
+                    ),
+                });
+            }
+        }
+
+        let mut loan = previous.clone();
+        for pledge in loan
+            .guarantors
+            .iter_mut()
+            .filter(|pledge| pledge.user_id == guarantor.id)
+        {
+            pledge.status = if accept {
+                GuaranteeStatus::Accepted
+            } else {
+                GuaranteeStatus::Declined
+            };
+        }
+        state.loans.insert(loan_id, loan);
+        self.save_loan(&mut state, previous)
+    }
+
+    async fn get_loan(&self, loan_id: Uuid) -> ApiResult<Loan> {
+        self.state
+            .read()
+            .await
+            .loans
+            .get(&loan_id)
+            .cloned()
+            .ok_or_else(|| loan_not_found(loan_id))
+    }
+
+    async fn get_loans(
+        &self,
+        filter: LoanFilter,
+        pagination: PaginationQuery,
+    ) -> ApiResult<PaginatedResponse<Loan>> {
+        let state = self.state.read().await;
+        let mut loans: Vec<Loan> = state
+            .loans
+            .values()
+            .filter(|loan| filter.user_id.is_none_or(|id| loan.user_id == id))
+            .filter(|loan| {
+                filter
+                    .guarantor_id
+                    .is_none_or(|id| loan.guarantors.iter().any(|g| g.user_id == id))
+            })
+            .filter(|loan| filter.status.as_ref().is_none_or(|s| &loan.status == s))
+            .cloned()
+            .collect();
+        loans.sort_by_key(|loan| std::cmp::Reverse(loan.submitted_at));
+        Ok(PaginatedResponse::paginate(loans, &pagination))
+    }
+
+    async fn approve_loan(&self, loan_id: Uuid, checker: &User) -> ApiResult<Loan> {
+        let mut state = self.state.write().await;
+        let loan = state
+            .loans
+            .get_mut(&loan_id)
+            .ok_or_else(|| loan_not_found(loan_id))?;
+        check_reviewer(loan, checker)?;
+        if loan
+            .guarantors
+            .iter()
+            .any(|guarantor| guarantor.status != GuaranteeStatus::Accepted)
+        {
+            return Err(ApiError::Conflict {
+                message: "Every guarantor must accept their pledge before the loan is approved"
+                    .to_string(),
+            });
+        }
+
+        let previous = loan.clone();
+        loan.status = LoanStatus::Approved;
+        loan.reviewed_by = Some(checker.id);
+        loan.reviewed_at = Some(Utc::now());
+        self.save_loan(&mut state, previous)
+    }
+
+    async fn reject_loan(
+        &self,
+        loan_id: Uuid,
+        checker: &User,
+        reason: Option<String>,
+    ) -> ApiResult<Loan> {
+        let mut state = self.state.write().await;
+        let loan = state
+            .loans
+            .get_mut(&loan_id)
+            .ok_or_else(|| loan_not_found(loan_id))?;
+        check_reviewer(loan, checker)?;
+
+        let previous = loan.clone();
+        loan.status = LoanStatus::Rejected;
+        loan.reviewed_by = Some(checker.id);
+        loan.reviewed_at = Some(Utc::now());
+        loan.rejection_reason = reason.filter(|r| !r.trim().is_empty());
+        self.save_loan(&mut state, previous)
+    }
+
+    async fn disburse_loan(
+        &self,
+        loan_id: Uuid,
+        wallet_id: Uuid,
+        operator: &User,
+    ) -> ApiResult<Loan> {
+        if !operator.is_admin() {
+            return Err(ApiError::Authorization {
+                message: "Only admins can disburse loans".to_string(),
+            });
+        }
+
+        // Hold the loan lock across the wallet credit so a loan can't be paid out twice
+        let mut state = self.state.write().await;
+        let loan = state
+            .loans
+            .get_mut(&loan_id)
+            .ok_or_else(|| loan_not_found(loan_id))?;
+        if loan.status != LoanStatus::Approved {
+            return Err(ApiError::Conflict {
+                message: format!(
+                    "Only approved loans can be disbursed; this loan is {}",
+                    loan.status.as_str().replace('_', " ")
+                ),
+            });
+        }
+
+        let wallet = self.wallets.get_wallet(wallet_id).await?;
+        if wallet.user_id != loan.user_id {
+            return Err(ApiError::Validation {
+                message: "Loans can only be disbursed to the borrower's own wallet".to_string(),
+            });
+        }
+        // Worked out first so a loan too large to schedule is never paid out
+        let now = Utc::now();
+        let schedule = amortization_schedule(
+            loan.amount,
+            loan.annual_interest_rate,
+            loan.term_months,
+            now,
+        )?;
+
+        self.wallets
+            .post(
+                wallet_id,
+                signed_sats(loan.amount)?,
+                TransactionType::Deposit,
+                vec![PostingRequest::debit(
+                    loans_receivable(loan.user_id),
+                    loan.amount,
+                )],
+            )
+            .await?;
+
+        loan.status = LoanStatus::Disbursed;
+        loan.disbursed_at = Some(now);
+        loan.disbursement_wallet_id = Some(wallet_id);
+        loan.schedule = schedule;
+        let disbursed = loan.clone();
+        self.store.save_or_log(&*state, "loans");
+        Ok(disbursed)
+    }
+
+    async fn record_repayment(
+        &self,
+        request: LoanRepaymentRequest,
+        payer: &User,
+    ) -> ApiResult<Loan> {
+        // Cash repayments credit the loan without moving wallet funds, so only
+        // an admin who received the money can record one
+        if request.wallet_id.is_none() && !payer.is_admin() {
+            return Err(ApiError::Authorization {
+                message: "Only admins can record repayments received outside the wallets"
+                    .to_string(),
+            });
+        }
+        if request.amount == 0 {
+            return Err(ApiError::Validation {
+                message: "Repayment amount must be greater than zero".to_string(),
+            });
+        }
+
+        let mut state = self.state.write().await;
+        let loan = state
+            .loans
+            .get_mut(&request.loan_id)
+            .ok_or_else(|| loan_not_found(request.loan_id))?;
+        if loan.status != LoanStatus::Disbursed {
+            return Err(ApiError::Conflict {
+                message: "Repayments can only be recorded against disbursed loans".to_string(),
+            });
+        }
+        if !payer.is_admin() && payer.id != loan.user_id {
+            return Err(ApiError::Authorization {
+                message: "You can only repay your own loans".to_string(),
+            });
+        }
+        if request.amount > loan.outstanding() {
+            return Err(ApiError::Validation {
+                message: format!(
+                    "Repayment of {} sats exceeds the outstanding balance of {} sats",
+                    request.amount,
+                    loan.outstanding()
+                ),
+            });
+        }
+
+        // Work out the principal/interest split on a copy so nothing changes if posting fails
+        let mut schedule = loan.schedule.clone();
+        let (principal, interest) = allocate_repayment(&mut schedule, request.amount);
+        let mut allocation = Vec::new();
+        if principal > 0 {
+            allocation.push(PostingRequest::credit(
+                loans_receivable(loan.user_id),
+                principal,
+            ));
+        }
+        if interest > 0 {
+            allocation.push(PostingRequest::credit(
+                AccountKey::sacco(AccountPurpose::InterestIncome, LedgerCurrency::Sats),
+                interest,
+            ));
+        }
+
+        if let Some(wallet_id) = request.wallet_id {
+            let wallet = self.wallets.get_wallet(wallet_id).await?;
+            if wallet.user_id != loan.user_id {
+                return Err(ApiError::Validation {
+                    message: "Repayments must come from the borrower's own wallet".to_string(),
+                });
+            }
+            // The borrower already owes this, so it is not held to the withdrawal limits
+            self.wallets
+                .post(
+                    wallet_id,
+                    -signed_sats(request.amount)?,
+                    TransactionType::LoanRepayment,
+                    allocation,
+                )
+                .await?;
+        } else {
+            // Received outside the wallets, e.g. cash or M-Pesa paid into the SACCO
+            let mut postings = vec![PostingRequest::debit(
+                AccountKey::sacco(AccountPurpose::Treasury, LedgerCurrency::Sats),
+                request.amount,
+            )];
+            postings.extend(allocation);
+            self.ledger
+                .post_journal_entry(JournalEntryRequest {
+                    description: format!("Loan {} repayment", loan.id),
+                    reference_id: Some(loan.id),
+                    postings,
+                    created_by: Some(payer.id),
+                })
+                .await?;
+        }
+        loan.schedule = schedule;
+
+        if loan.outstanding() == 0 {
+            loan.status = LoanStatus::Repaid;
+        }
+        let repaid = loan.clone();
+        self.store.save_or_log(&*state, "loans");
+        Ok(repaid)
+    }
+}
+
+/// Apply a repayment to the oldest installments, interest before principal.
+/// Returns the (principal, interest) portions repaid.
+fn allocate_repayment(schedule: &mut [RepaymentInstallment], amount: u64) -> (u64, u64) {
+    let (mut principal, mut interest) = (0, 0);
+    let mut remaining = amount;
+    for installment in schedule.iter_mut() {
+        if remaining == 0 {
+            break;
+        }
+        let applied = remaining.min(installment.outstanding());
+        let interest_before = installment.paid.min(installment.interest);
+        installment.paid += applied;
+        let interest_part = installment.paid.min(installment.interest) - interest_before;
+
+        interest += interest_part;
+        principal += applied - interest_part;
+        remaining -= applied;
+    }
+    (principal, interest)
+}
+
+fn loans_receivable(user_id: Uuid) -> AccountKey {
+    AccountKey::member(
+        user_id,
+        AccountPurpose::LoansReceivable,
+        LedgerCurrency::Sats,
+    )
+}
+
+/// Enforce maker-checker: reviewers must be admins and independent of the application
+fn check_reviewer(loan: &Loan, checker: &User) -> ApiResult<()> {
+    if !checker.is_admin() {
+        return Err(ApiError::Authorization {
+            message: "Only admins can review loan applications".to_string(),
+        });
+    }
+    if checker.id == loan.submitted_by || checker.id == loan.user_id {
+        return Err(ApiError::Authorization {
+            message:
+                "Loan applications must be reviewed by someone other than the maker or borrower"
+                    .to_string(),
+        });
+    }
+    if loan.status != LoanStatus::PendingApproval {
+        return Err(ApiError::Conflict {
+            message: format!("Loan is already {}", loan.status.as_str().replace('_', " ")),
+        });
+    }
+    Ok(())
+}
+
+/// Equal monthly installments (annuity) with interest on the reducing balance.
+/// The final installment absorbs rounding so principal always sums to `amount`.
+fn amortization_schedule(
+    amount: u64,
+    annual_interest_rate: Decimal,
+    term_months: u32,
+    start: DateTime<Utc>,
+) -> ApiResult<Vec<RepaymentInstallment>> {
+    let too_large = || ApiError::Validation {
+        message: "The loan's repayments are too large to schedule".to_string(),
+    };
+    let principal = Decimal::from(amount);
+    let monthly_rate = annual_interest_rate / Decimal::from(1200);
+    let term = Decimal::from(term_months);
+
+    let payment = if monthly_rate.is_zero() {
+        principal / term
+    } else {
+        let growth = (0..term_months)
+            .try_fold(Decimal::ONE, |acc, _| {
+                acc.checked_mul(Decimal::ONE + monthly_rate)
+            })
+            .ok_or_else(too_large)?;
+        principal
+            .checked_mul(monthly_rate)
+            .and_then(|payment| payment.checked_mul(growth))
+            .and_then(|payment| payment.checked_div(growth - Decimal::ONE))
+            .ok_or_else(too_large)?
+    }
+    .round();
+
+    let mut balance = amount;
+    Ok((1..=term_months)
+        .map(|number| {
+            let interest = (Decimal::from(balance) * monthly_rate)
+                .round()
+                .to_u64()
+                .unwrap_or(0);
+            let principal = if number == term_months {
+                balance
+            } else {
+                (payment.to_u64().unwrap_or(0).saturating_sub(interest)).min(balance)
+            };
+            balance -= principal;
+
+            RepaymentInstallment {
+                number,
+                due_date: start
+                    .checked_add_months(Months::new(number))
+                    .unwrap_or(start),
+                principal,
+                interest,
+                paid: 0,
+            }
+        })
+        .collect())
+}
+
+fn loan_not_found(loan_id: Uuid) -> ApiError {
+    ApiError::NotFound {
+        resource: format!("Loan {}", loan_id),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::backends::rust::RustFxApi;
+    use crate::api::traits::{
+        wallets::CreateWalletRequest,
+        wallets::WalletType,
+        withdrawals::{WithdrawalLimits, WithdrawalPolicyApi},
+    };
+    use crate::api::types::Role;
+
+    fn user(roles: Vec<Role>) -> User {
+        User {
+            id: Uuid::new_v4(),
+            phone: None,
+            nostr: None,
+            profile: None,
+            roles,
+            verified: true,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn product_request() -> CreateLoanProductRequest {
+        CreateLoanProductRequest {
+            name: "Emergency".to_string(),
+            annual_interest_rate: Decimal::from(12),
+            term_months: 12,
+            min_amount: 10_000,
+            max_amount: 1_000_000,
+            max_savings_multiple: Some(Decimal::from(3)),
+            max_sats_per_share: None,
+            required_guarantors: 1,
+        }
+    }
+
+    async fn member_with_savings(wallets: &RustWalletsApi, sats: u64) -> (Uuid, Uuid) {
+        let user_id = Uuid::new_v4();
+        let wallet = wallets
+            .create_wallet(CreateWalletRequest {
+                user_id,
+                name: "Savings".to_string(),
+                wallet_type: WalletType::Fedimint,
+            })
+            .await
+            .unwrap();
+        if sats > 0 {
+            wallets
+                .credit(wallet.id, sats, TransactionType::Deposit)
+                .await
+                .unwrap();
+        }
+        (user_id, wallet.id)
+    }
+
+    struct Fixture {
+        ledger: RustLedgerApi,
+        loans: RustLoansApi,
+        wallets: RustWalletsApi,
+        product: LoanProduct,
+        borrower: Uuid,
+        borrower_wallet: Uuid,
+        guarantor: Uuid,
+    }
+
+    async fn fixture() -> Fixture {
+        let ledger = RustLedgerApi::new();
+        let wallets = RustWalletsApi::new(ledger.clone());
+        let loans = RustLoansApi::new(
+            ledger.clone(),
+            wallets.clone(),
+            RustSharesApi::new(ledger.clone(), wallets.clone(), RustFxApi::new(Vec::new())),
+        );
+        let product = loans
+            .create_loan_product(product_request(), &user(vec![Role::Admin]))
+            .await
+            .unwrap();
+        let (borrower, borrower_wallet) = member_with_savings(&wallets, 100_000).await;
+        let (guarantor, _) = member_with_savings(&wallets, 200_000).await;
+
+        Fixture {
+            ledger,
+            loans,
+            wallets,
+            product,
+            borrower,
+            borrower_wallet,
+            guarantor,
+        }
+    }
+
+    fn application(f: &Fixture, amount: u64) -> LoanApplicationRequest {
+        LoanApplicationRequest {
+            product_id: f.product.id,
+            user_id: f.borrower,
+            amount,
+            purpose: Some("School fees".to_string()),
+            guarantors: vec![Guarantor {
+                user_id: f.guarantor,
+                amount: amount / 2,
+                status: GuaranteeStatus::Accepted,
+            }],
+        }
+    }
+
+    async fn accept_guarantee(f: &Fixture, loan_id: Uuid) {
+        let guarantor = User {
+            id: f.guarantor,
+            ..user(vec![Role::Member])
+        };
+        f.loans
+            .respond_to_guarantee(loan_id, &guarantor, true)
+            .await
+            .unwrap();
+    }
+
+    #[test]
+    fn test_amortization_schedule_repays_principal() {
+        let schedule = amortization_schedule(120_000, Decimal::from(12), 12, Utc::now()).unwrap();
+
+        assert_eq!(schedule.len(), 12);
+        assert_eq!(schedule.iter().map(|i| i.principal).sum::<u64>(), 120_000);
+        // First month's interest is 1% of the full balance
+        assert_eq!(schedule[0].interest, 1_200);
+        // Reducing balance means interest falls over the term
+        assert!(schedule[11].interest < schedule[0].interest);
+        // Annuity installments are level apart from final rounding
+        assert!(schedule[0].amount_due().abs_diff(schedule[10].amount_due()) <= 1);
+    }
+
+    #[tokio::test]
+    async fn test_products_bound_term_and_rate() {
+        let f = fixture().await;
+        let admin = user(vec![Role::Admin]);
+        let long_term = CreateLoanProductRequest {
+            term_months: MAX_TERM_MONTHS + 1,
+            ..product_request()
+        };
+        assert!(matches!(
+            f.loans.create_loan_product(long_term, &admin).await,
+            Err(ApiError::Validation { .. })
+        ));
+        let high_rate = CreateLoanProductRequest {
+            annual_interest_rate: Decimal::from(MAX_ANNUAL_INTEREST_RATE + 1),
+            ..product_request()
+        };
+        assert!(matches!(
+            f.loans.create_loan_product(high_rate, &admin).await,
+            Err(ApiError::Validation { .. })
+        ));
+    }
+
+    #[test]
+    fn test_schedule_refuses_amounts_that_overflow() {
+        let overflow = amortization_schedule(
+            u64::MAX,
+            Decimal::from(MAX_ANNUAL_INTEREST_RATE),
+            MAX_TERM_MONTHS,
+            Utc::now(),
+        );
+        assert!(matches!(overflow, Err(ApiError::Validation { .. })));
+    }
+
+    #[test]
+    fn test_zero_interest_schedule() {
+        let schedule = amortization_schedule(100_000, Decimal::ZERO, 3, Utc::now()).unwrap();
+        assert_eq!(
+            schedule.iter().map(|i| i.amount_due()).collect::<Vec<_>>(),
+            vec![33_333, 33_333, 33_334]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_application_limits_and_guarantors() {
+        let f = fixture().await;
+        let maker = user(vec![Role::Admin]);
+
+        // Savings of 100k with a 3x multiple caps borrowing at 300k
+        let over_limit = f
+            .loans
+            .apply_for_loan(application(&f, 300_001), &maker)
+            .await;
+        assert!(matches!(over_limit, Err(ApiError::Validation { .. })));
+
+        let mut no_guarantor = application(&f, 50_000);
+        no_guarantor.guarantors.clear();
+        assert!(f.loans.apply_for_loan(no_guarantor, &maker).await.is_err());
+
+        let mut self_guarantee = application(&f, 50_000);
+        self_guarantee.guarantors[0].user_id = f.borrower;
+        assert!(f
+            .loans
+            .apply_for_loan(self_guarantee, &maker)
+            .await
+            .is_err());
+
+        // Guarantor holds 200k so can't pledge 250k
+        let mut overpledged = application(&f, 300_000);
+        overpledged.guarantors[0].amount = 250_000;
+        assert!(f.loans.apply_for_loan(overpledged, &maker).await.is_err());
+
+        let loan = f
+            .loans
+            .apply_for_loan(application(&f, 300_000), &maker)
+            .await
+            .unwrap();
+        assert_eq!(loan.status, LoanStatus::PendingApproval);
+        // Pledges wait for the guarantor whatever the application says
+        assert_eq!(loan.guarantors[0].status, GuaranteeStatus::Pending);
+
+        // Members can only apply for themselves
+        let member = user(vec![Role::Member]);
+        let for_someone_else = f
+            .loans
+            .apply_for_loan(application(&f, 50_000), &member)
+            .await;
+        assert!(matches!(
+            for_someone_else,
+            Err(ApiError::Authorization { .. })
+        ));
+        let borrower = User {
+            id: f.borrower,
+            ..member
+        };
+        let own = f
+            .loans
+            .apply_for_loan(application(&f, 50_000), &borrower)
+            .await
+            .unwrap();
+        assert_eq!(own.submitted_by, f.borrower);
+    }
+
+    #[tokio::test]
+    async fn test_guarantors_cannot_pledge_savings_twice() {
+        let f = fixture().await;
+        let maker = user(vec![Role::Admin]);
+        let guarantor = User {
+            id: f.guarantor,
+            ..user(vec![Role::Member])
+        };
+
+        // The guarantor's 200k covers one 150k pledge but not two
+        let first = f
+            .loans
+            .apply_for_loan(application(&f, 300_000), &maker)
+            .await
+            .unwrap();
+        let second = f
+            .loans
+            .apply_for_loan(application(&f, 300_000), &maker)
+            .await
+            .unwrap();
+        accept_guarantee(&f, first.id).await;
+        let double_pledge = f
+            .loans
+            .respond_to_guarantee(second.id, &guarantor, true)
+            .await;
+        assert!(matches!(double_pledge, Err(ApiError::Validation { .. })));
+        let third = f
+            .loans
+            .apply_for_loan(application(&f, 300_000), &maker)
+            .await;
+        assert!(matches!(third, Err(ApiError::Validation { .. })));
+
+        // A rejected loan releases its pledges
+        f.loans
+            .reject_loan(first.id, &user(vec![Role::Admin]), None)
+            .await
+            .unwrap();
+        let accepted = f
+            .loans
+            .respond_to_guarantee(second.id, &guarantor, true)
+            .await
+            .unwrap();
+        assert_eq!(accepted.guarantors[0].status, GuaranteeStatus::Accepted);
+    }
+
+    #[tokio::test]
+    async fn test_guarantors_must_accept_before_approval() {
+        let f = fixture().await;
+        let maker = user(vec![Role::Admin]);
+        let checker = user(vec![Role::Admin]);
+        let loan = f
+            .loans
+            .apply_for_loan(application(&f, 50_000), &maker)
+            .await
+            .unwrap();
+
+        let unconfirmed = f.loans.approve_loan(loan.id, &checker).await;
+        assert!(matches!(unconfirmed, Err(ApiError::Conflict { .. })));
+
+        // Only the named guarantor can answer, and only once
+        let stranger = user(vec![Role::Member]);
+        let by_stranger = f.loans.respond_to_guarantee(loan.id, &stranger, true).await;
+        assert!(matches!(by_stranger, Err(ApiError::Authorization { .. })));
+        let guarantor = User {
+            id: f.guarantor,
+            ..stranger
+        };
+        f.loans
+            .respond_to_guarantee(loan.id, &guarantor, false)
+            .await
+            .unwrap();
+        let changed_mind = f
+            .loans
+            .respond_to_guarantee(loan.id, &guarantor, true)
+            .await;
+        assert!(matches!(changed_mind, Err(ApiError::Conflict { .. })));
+        assert!(f.loans.approve_loan(loan.id, &checker).await.is_err());
+
+        let loan = f
+            .loans
+            .apply_for_loan(application(&f, 50_000), &maker)
+            .await
+            .unwrap();
+        accept_guarantee(&f, loan.id).await;
+        let approved = f.loans.approve_loan(loan.id, &checker).await.unwrap();
+        assert_eq!(approved.status, LoanStatus::Approved);
+        let guaranteed = f
+            .loans
+            .get_loans(
+                LoanFilter {
+                    guarantor_id: Some(f.guarantor),
+                    ..LoanFilter::default()
+                },
+                PaginationQuery {
+                    page: Some(1),
+                    limit: Some(10),
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(guaranteed.data.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_maker_checker_approval() {
+        let f = fixture().await;
+        let maker = user(vec![Role::Admin]);
+        let loan = f
+            .loans
+            .apply_for_loan(application(&f, 50_000), &maker)
+            .await
+            .unwrap();
+        accept_guarantee(&f, loan.id).await;
+
+        let self_approval = f.loans.approve_loan(loan.id, &maker).await;
+        assert!(matches!(self_approval, Err(ApiError::Authorization { .. })));
+
+        let member = user(vec![Role::Member]);
+        assert!(f.loans.approve_loan(loan.id, &member).await.is_err());
+
+        let checker = user(vec![Role::Admin]);
+        let approved = f.loans.approve_loan(loan.id, &checker).await.unwrap();
+        assert_eq!(approved.status, LoanStatus::Approved);
+        assert_eq!(approved.reviewed_by, Some(checker.id));
+
+        let again = f.loans.reject_loan(loan.id, &checker, None).await;
+        assert!(matches!(again, Err(ApiError::Conflict { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_disbursement_repayment_and_arrears() {
+        let f = fixture().await;
+        let maker = user(vec![Role::Admin]);
+        let checker = user(vec![Role::SuperAdmin]);
+        let loan = f
+            .loans
+            .apply_for_loan(application(&f, 120_000), &maker)
+            .await
+            .unwrap();
+        accept_guarantee(&f, loan.id).await;
+
+        // Unapproved loans can't be paid out
+        let early = f
+            .loans
+            .disburse_loan(loan.id, f.borrower_wallet, &checker)
+            .await;
+        assert!(matches!(early, Err(ApiError::Conflict { .. })));
+
+        f.loans.approve_loan(loan.id, &checker).await.unwrap();
+        let loan = f
+            .loans
+            .disburse_loan(loan.id, f.borrower_wallet, &checker)
+            .await
+            .unwrap();
+        assert_eq!(loan.status, LoanStatus::Disbursed);
+        assert_eq!(loan.schedule.len(), 12);
+        assert_eq!(
+            f.wallets
+                .get_wallet_balance(f.borrower_wallet)
+                .await
+                .unwrap(),
+            220_000
+        );
+
+        // Backdate the first two installments so they fall into arrears
+        {
+            let mut state = f.loans.state.write().await;
+            let stored = state.loans.get_mut(&loan.id).unwrap();
+            stored.schedule[0].due_date = Utc::now() - chrono::Duration::days(40);
+            stored.schedule[1].due_date = Utc::now() - chrono::Duration::days(10);
+        }
+        let loan = f.loans.get_loan(loan.id).await.unwrap();
+        let arrears = loan.arrears(Utc::now());
+        assert_eq!(arrears.overdue_installments, 2);
+        assert_eq!(arrears.days_in_arrears, 40);
+        let first_due = loan.schedule[0].amount_due();
+        let borrower = User {
+            id: f.borrower,
+            ..user(vec![Role::Member])
+        };
+
+        // Members can't credit loans with cash or repay someone else's loan
+        let cash_by_member = f
+            .loans
+            .record_repayment(
+                LoanRepaymentRequest {
+                    loan_id: loan.id,
+                    amount: first_due,
+                    wallet_id: None,
+                },
+                &borrower,
+            )
+            .await;
+        assert!(matches!(
+            cash_by_member,
+            Err(ApiError::Authorization { .. })
+        ));
+        let by_stranger = f
+            .loans
+            .record_repayment(
+                LoanRepaymentRequest {
+                    loan_id: loan.id,
+                    amount: first_due,
+                    wallet_id: Some(f.borrower_wallet),
+                },
+                &user(vec![Role::Member]),
+            )
+            .await;
+        assert!(matches!(by_stranger, Err(ApiError::Authorization { .. })));
+
+        // Repayments aren't withdrawals, so member limits don't hold them up
+        f.wallets
+            .policy()
+            .set_role_limits(
+                Role::Member,
+                WithdrawalLimits {
+                    daily_sats: Some(1),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        let loan = f
+            .loans
+            .record_repayment(
+                LoanRepaymentRequest {
+                    loan_id: loan.id,
+                    amount: first_due,
+                    wallet_id: Some(f.borrower_wallet),
+                },
+                &borrower,
+            )
+            .await
+            .unwrap();
+        let arrears = loan.arrears(Utc::now());
+        assert_eq!(arrears.overdue_installments, 1);
+        assert_eq!(arrears.days_in_arrears, 10);
+        assert_eq!(
+            f.wallets
+                .get_wallet_balance(f.borrower_wallet)
+                .await
+                .unwrap(),
+            220_000 - first_due
+        );
+        assert!(f
+            .wallets
+            .policy()
+            .get_decisions(None, 10)
+            .await
+            .unwrap()
+            .is_empty());
+
+        let outstanding = loan.outstanding();
+        let overpay = f
+            .loans
+            .record_repayment(
+                LoanRepaymentRequest {
+                    loan_id: loan.id,
+                    amount: outstanding + 1,
+                    wallet_id: None,
+                },
+                &checker,
+            )
+            .await;
+        assert!(overpay.is_err());
+
+        let loan = f
+            .loans
+            .record_repayment(
+                LoanRepaymentRequest {
+                    loan_id: loan.id,
+                    amount: outstanding,
+                    wallet_id: None,
+                },
+                &checker,
+            )
+            .await
+            .unwrap();
+        assert_eq!(loan.status, LoanStatus::Repaid);
+        assert_eq!(loan.outstanding(), 0);
+
+        // Principal is cleared from the receivable and interest lands in income
+        let total_interest: u64 = loan.schedule.iter().map(|i| i.interest).sum();
+        assert!(f
+            .ledger
+            .get_account_balance(loans_receivable(f.borrower))
+            .await
+            .unwrap()
+            .is_zero());
+        assert_eq!(
+            f.ledger
+                .get_account_balance(AccountKey::sacco(
+                    AccountPurpose::InterestIncome,
+                    LedgerCurrency::Sats
+                ))
+                .await
+                .unwrap(),
+            Decimal::from(total_interest)
+        );
+        assert!(f
+            .ledger
+            .get_trial_balance()
+            .await
+            .unwrap()
+            .iter()
+            .all(|line| line.is_balanced()));
+    }
+
+    #[test]
+    fn test_repayment_allocation_pays_interest_first() {
+        let mut schedule =
+            amortization_schedule(120_000, Decimal::from(12), 12, Utc::now()).unwrap();
+        let first_interest = schedule[0].interest;
+
+        let (principal, interest) = allocate_repayment(&mut schedule, first_interest + 100);
+        assert_eq!(interest, first_interest);
+        assert_eq!(principal, 100);
+        assert_eq!(schedule[0].paid, first_interest + 100);
+    }
+}
@@ -1,8 +1,52 @@
 // Graceful degradation implementation for Rust backend
-// This provides friendly error handling when Rust backend is configured but not implemented
+// Auth, users and groups are not available on the Rust backend and return a friendly error.
+// Subsystems that run in-process (ledger, shares, wallets, loans, contributions,
+// dividends, statements, lightning, onchain, fx, swaps, reconciliation, withdrawals, saved reports, alert acknowledgements) live in their own modules and share one double-entry ledger.
+// The journal, wallets and the records that refer to them are kept under RUST_DATA_DIR across restarts.
+
+pub mod alerts;
+pub mod contributions;
+pub mod dividends;
+pub mod fx;
+pub mod ledger;
+pub mod lightning;
+pub mod loans;
+pub mod onchain;
+pub mod reconciliation;
+pub mod reports;
+pub mod shares;
+pub mod statements;
+pub mod store;
+pub mod swaps;
+pub mod wallets;
+pub mod withdrawals;
+
+pub use alerts::RustAlertsApi;
+pub use contributions::RustContributionsApi;
+pub use dividends::RustDividendsApi;
+pub use fx::RustFxApi;
+pub use ledger::RustLedgerApi;
+pub use lightning::RustLightningApi;
+pub use loans::RustLoansApi;
+pub use onchain::{OnChainConfig, RustOnChainApi};
+pub use reconciliation::RustReconciliationApi;
+pub use reports::RustSavedReportsApi;
+pub use shares::RustSharesApi;
+pub use statements::RustStatementsApi;
+pub use store::{JsonLog, JsonStore};
+pub use swaps::RustSwapsApi;
+pub use wallets::RustWalletsApi;
+pub use withdrawals::RustWithdrawalPolicy;
 
 use crate::api::{
-    config::ApiConfig,
+    backends::{
+        chain::chain_source_from_env,
+        fmcd::{FmcdClient, FmcdConfig},
+        fx::rate_sources_from_env,
+        mpesa::{mpesa_gateway_from_env, MockMpesaGateway},
+        nestjs::{NestJsClient, NestJsGroupsApi},
+    },
+    config::{ApiConfig, Backend},
     errors::{ApiError, ApiResult},
     traits::{AuthApi, GroupsApi, UsersApi, WalletsApi},
     types::{
@@ -12,28 +56,105 @@ use crate::api::{
     },
 };
 use async_trait::async_trait;
+use std::sync::Arc;
 
 pub struct RustBackend {
     #[allow(dead_code)]
     base_url: String,
     friendly_error_message: String,
+    pub ledger: RustLedgerApi,
+    pub shares: RustSharesApi,
+    pub wallets: RustWalletsApi,
+    pub loans: RustLoansApi,
+    pub contributions: RustContributionsApi,
+    pub dividends: RustDividendsApi,
+    pub statements: RustStatementsApi,
+    pub lightning: RustLightningApi,
+    pub onchain: RustOnChainApi,
+    pub fx: RustFxApi,
+    pub swaps: RustSwapsApi,
+    pub reconciliation: RustReconciliationApi,
+    /// Custom analytics reports admins saved from the report builder
+    pub reports: RustSavedReportsApi,
+    /// Limits on withdrawals, shared with the wallet store that enforces them
+    pub withdrawal_policy: RustWithdrawalPolicy,
+    /// Gateway the swaps run against; only the mock exists until a Daraja client is added
+    pub mpesa: MockMpesaGateway,
 }
 
 impl RustBackend {
     pub fn new(config: &ApiConfig) -> ApiResult<Self> {
+        let ledger = RustLedgerApi::new().with_log(JsonLog::from_env("ledger.jsonl"))?;
+        let wallets = RustWalletsApi::new(ledger.clone())
+            .with_log(JsonLog::from_env("wallets.jsonl"))?
+            .with_policy(
+                RustWithdrawalPolicy::new()
+                    .with_store(JsonStore::from_env("withdrawal-policy.json"))?,
+            );
+        let fmcd = FmcdClient::new(FmcdConfig::from_env())?;
+        let onchain = RustOnChainApi::new(
+            wallets.clone(),
+            chain_source_from_env()?,
+            OnChainConfig::from_env()?,
+        )
+        .with_store(JsonStore::from_env("onchain-addresses.json"))?;
+        let fx = RustFxApi::new(rate_sources_from_env()?)
+            .with_log(JsonLog::from_env("fx-rates.jsonl"))?;
+        let shares = RustSharesApi::new(ledger.clone(), wallets.clone(), fx.clone())
+            .with_store(JsonStore::from_env("shares.json"))?;
+        // Chamas aren't kept in-process yet, so plan members are checked against
+        // the NestJS service's chama records
+        let chamas = NestJsGroupsApi::new(NestJsClient::new(&ApiConfig::new(
+            Backend::NestJs,
+            std::env::var("NESTJS_API_URL")
+                .unwrap_or_else(|_| "http://localhost:4000/v1".to_string()),
+        ))?);
+        let mpesa = mpesa_gateway_from_env()?;
+        let lightning = RustLightningApi::new(ledger.clone(), wallets.clone(), fmcd.clone())
+            .with_store(JsonStore::from_env("lightning.json"))?;
+        let reconciliation = RustReconciliationApi::new(
+            ledger.clone(),
+            lightning.clone(),
+            onchain.clone(),
+            vec![Arc::new(fmcd), Arc::new(onchain.clone())],
+            RustReconciliationApi::threshold_from_env()?,
+        )
+        .with_store(JsonStore::from_env("reconciliation.json"))?;
+
         // Instead of panicking, create a backend that provides helpful error messages
         Ok(Self {
             base_url: config.base_url.clone(),
             friendly_error_message: format!(
-                "Rust backend is not currently implemented. The application is configured to use \
-                 the Rust backend (API_BACKEND=rust), but only the NestJS backend is supported at this time. \
-                 \n\nTo use the application:\n\
+                "Sign-in, user and group management are not available on the Rust backend \
+                 (API_BACKEND=rust); they are only provided by the NestJS backend. \
+                 \n\nTo use them:\n\
                  1. Set API_BACKEND=nestjs in your environment\n\
                  2. Or remove the API_BACKEND environment variable (defaults to NestJS)\n\
                  3. Ensure your NestJS backend is running at: {}\n\n\
                  For technical support, please contact your system administrator.",
-                std::env::var("NESTJS_API_URL").unwrap_or_else(|_| "http://localhost:4000".to_string())
+                std::env::var("NESTJS_API_URL")
+                    .unwrap_or_else(|_| "http://localhost:4000".to_string())
             ),
+            shares: shares.clone(),
+            wallets: wallets.clone(),
+            loans: RustLoansApi::new(ledger.clone(), wallets.clone(), shares)
+                .with_store(JsonStore::from_env("loans.json"))?,
+            dividends: RustDividendsApi::new(ledger.clone(), wallets.clone())
+                .with_store(JsonStore::from_env("dividends.json"))?,
+            statements: RustStatementsApi::new(ledger.clone(), wallets.clone()),
+            lightning,
+            onchain,
+            swaps: RustSwapsApi::new(wallets.clone(), fx.clone(), Arc::new(mpesa.clone()))
+                .with_store(JsonStore::from_env("swaps.json"))?,
+            reconciliation,
+            reports: RustSavedReportsApi::new()
+                .with_store(JsonStore::from_env("saved-reports.json"))?,
+            withdrawal_policy: wallets.policy().clone(),
+            fx,
+            mpesa,
+            ledger,
+            contributions: RustContributionsApi::new(wallets, Arc::new(chamas))
+                .with_store(JsonStore::from_env("contributions.json"))?,
         })
     }
 
@@ -46,7 +167,7 @@ impl RustBackend {
 
     /// Check if this backend instance represents a graceful degradation scenario
     pub fn is_graceful_degradation(&self) -> bool {
-        true // All Rust backend instances are currently graceful degradations
+        true // Auth, users and groups still degrade gracefully
     }
 }
 
@@ -166,52 +287,64 @@ impl GroupsApi for RustBackend {
     }
 }
 
-// Implement WalletsApi trait with graceful degradation
+// Wallets are served by the in-process wallet store
 #[async_trait]
 impl WalletsApi for RustBackend {
     async fn get_wallet(
         &self,
-        _wallet_id: uuid::Uuid,
+        wallet_id: uuid::Uuid,
     ) -> ApiResult<crate::api::traits::wallets::Wallet> {
-        Err(self.get_friendly_error())
+        self.wallets.get_wallet(wallet_id).await
     }
 
     async fn get_user_wallets(
         &self,
-        _user_id: uuid::Uuid,
+        user_id: uuid::Uuid,
     ) -> ApiResult<Vec<crate::api::traits::wallets::Wallet>> {
-        Err(self.get_friendly_error())
+        self.wallets.get_user_wallets(user_id).await
     }
 
     async fn get_wallets(
         &self,
-        _pagination: crate::api::types::PaginationQuery,
+        pagination: crate::api::types::PaginationQuery,
     ) -> ApiResult<crate::api::types::PaginatedResponse<crate::api::traits::wallets::Wallet>> {
-        Err(self.get_friendly_error())
+        self.wallets.get_wallets(pagination).await
     }
 
     async fn create_wallet(
         &self,
-        _request: crate::api::traits::wallets::CreateWalletRequest,
+        request: crate::api::traits::wallets::CreateWalletRequest,
     ) -> ApiResult<crate::api::traits::wallets::Wallet> {
-        Err(self.get_friendly_error())
+        self.wallets.create_wallet(request).await
     }
 
-    async fn delete_wallet(&self, _wallet_id: uuid::Uuid) -> ApiResult<()> {
-        Err(self.get_friendly_error())
+    async fn delete_wallet(&self, wallet_id: uuid::Uuid) -> ApiResult<()> {
+        self.wallets.delete_wallet(wallet_id).await
     }
 
     async fn get_wallet_transactions(
         &self,
-        _wallet_id: uuid::Uuid,
-        _pagination: crate::api::types::PaginationQuery,
+        wallet_id: uuid::Uuid,
+        pagination: crate::api::types::PaginationQuery,
     ) -> ApiResult<
         crate::api::types::PaginatedResponse<crate::api::traits::wallets::WalletTransaction>,
     > {
-        Err(self.get_friendly_error())
+        self.wallets
+            .get_wallet_transactions(wallet_id, pagination)
+            .await
     }
 
-    async fn get_wallet_balance(&self, _wallet_id: uuid::Uuid) -> ApiResult<u64> {
-        Err(self.get_friendly_error())
+    async fn get_transactions(
+        &self,
+        filter: crate::api::traits::wallets::TransactionFilter,
+        pagination: crate::api::types::PaginationQuery,
+    ) -> ApiResult<
+        crate::api::types::PaginatedResponse<crate::api::traits::wallets::WalletTransaction>,
+    > {
+        self.wallets.get_transactions(filter, pagination).await
+    }
+
+    async fn get_wallet_balance(&self, wallet_id: uuid::Uuid) -> ApiResult<u64> {
+        self.wallets.get_wallet_balance(wallet_id).await
     }
 }
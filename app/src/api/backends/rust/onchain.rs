@@ -0,0 +1,684 @@
+// On-chain deposits for the Rust backend
+// Deposit addresses are derived from the SACCO's watch-only descriptor, one child
+// index per address, and the wallet is credited once a chain source reports the
+// payment buried under enough confirmations. Issued addresses are kept on disk so
+// an index is never handed out twice, even across restarts.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use bitcoin::{
+    base58,
+    bip32::{ChildNumber, Xpub},
+    secp256k1::Secp256k1,
+    Address, Network, NetworkKind,
+};
+use chrono::Utc;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use super::{JsonStore, RustWalletsApi};
+use crate::api::{
+    errors::{ApiError, ApiResult},
+    traits::{
+        onchain::{ChainOutput, ChainSource, DepositAddress, OnChainApi, OnChainDeposit},
+        reconciliation::{CustodyProvider, ReconciliationItem},
+        wallets::{TransactionStatus, TransactionType, WalletType, WalletsApi},
+    },
+};
+
+// Extended public key version bytes; SLIP-132 native segwit keys are read as plain ones
+const XPUB: [u8; 4] = [0x04, 0x88, 0xb2, 0x1e];
+const TPUB: [u8; 4] = [0x04, 0x35, 0x87, 0xcf];
+const ZPUB: [u8; 4] = [0x04, 0xb2, 0x47, 0x46];
+const VPUB: [u8; 4] = [0x04, 0x5f, 0x1c, 0xf6];
+
+/// A watch-only `wpkh()` descriptor (or bare extended public key) that receive
+/// addresses are derived from
+#[derive(Debug, Clone)]
+pub struct AddressDescriptor {
+    xpub: Xpub,
+    /// Path from the key to the receive chain, before the wildcard index
+    chain: Vec<ChildNumber>,
+    network: Network,
+}
+
+impl AddressDescriptor {
+    /// Parse `wpkh([origin]xpub/0/*)#checksum`, or a bare xpub/tpub/zpub/vpub which
+    /// derives receive addresses at `/0/*`. The network defaults to the key's own.
+    pub fn parse(descriptor: &str, network: Option<Network>) -> ApiResult<Self> {
+        let invalid = |reason: String| ApiError::Validation {
+            message: format!("Invalid deposit descriptor: {}", reason),
+        };
+
+        let descriptor = descriptor.split('#').next().unwrap_or_default().trim();
+        let body = match descriptor.strip_prefix("wpkh(") {
+            Some(inner) => inner
+                .strip_suffix(')')
+                .ok_or_else(|| invalid("unbalanced parentheses".to_string()))?,
+            None if descriptor.contains('(') => {
+                return Err(invalid("only wpkh() descriptors are supported".to_string()))
+            }
+            None => descriptor,
+        };
+        // Key origin information is not needed to derive addresses
+        let body = match body.strip_prefix('[') {
+            Some(rest) => {
+                rest.split_once(']')
+                    .ok_or_else(|| invalid("unterminated key origin".to_string()))?
+                    .1
+            }
+            None => body,
+        };
+
+        let mut parts = body.split('/');
+        let key = parts.next().unwrap_or_default();
+        let mut path: Vec<&str> = parts.collect();
+        if path.is_empty() {
+            path = vec!["0", "*"];
+        }
+        if path.pop() != Some("*") {
+            return Err(invalid("the path must end in /*".to_string()));
+        }
+        let chain = path
+            .iter()
+            .map(|step| {
+                step.parse::<u32>()
+                    .ok()
+                    .and_then(|index| ChildNumber::from_normal_idx(index).ok())
+                    .ok_or_else(|| {
+                        invalid(format!(
+                            "'{}' is not an unhardened step; public keys cannot derive hardened children",
+                            step
+                        ))
+                    })
+            })
+            .collect::<ApiResult<Vec<_>>>()?;
+
+        let mut data = base58::decode_check(key)
+            .map_err(|_| invalid("the key is not an extended public key".to_string()))?;
+        if data.len() != 78 {
+            return Err(invalid("the key is not an extended public key".to_string()));
+        }
+        let version = match [data[0], data[1], data[2], data[3]] {
+            XPUB | ZPUB => XPUB,
+            TPUB | VPUB => TPUB,
+            _ => {
+                return Err(invalid(
+                    "only xpub, tpub, zpub and vpub keys are supported".to_string(),
+                ))
+            }
+        };
+        data[..4].copy_from_slice(&version);
+        let xpub = Xpub::decode(&data).map_err(|e| invalid(e.to_string()))?;
+
+        let network = match network {
+            Some(network) if NetworkKind::from(network) != xpub.network => {
+                return Err(invalid(format!(
+                    "the key does not belong to the {} network",
+                    network
+                )))
+            }
+            Some(network) => network,
+            None if xpub.network == NetworkKind::Main => Network::Bitcoin,
+            None => Network::Testnet,
+        };
+
+        Ok(Self {
+            xpub,
+            chain,
+            network,
+        })
+    }
+
+    pub fn network(&self) -> Network {
+        self.network
+    }
+
+    /// The native segwit address at `index` on the receive chain
+    pub fn address(&self, index: u32) -> ApiResult<String> {
+        let child = ChildNumber::from_normal_idx(index).map_err(|e| ApiError::Validation {
+            message: e.to_string(),
+        })?;
+        let mut path = self.chain.clone();
+        path.push(child);
+
+        let key = self
+            .xpub
+            .derive_pub(&Secp256k1::verification_only(), &path)
+            .map_err(|e| ApiError::Server {
+                message: format!("Address derivation failed: {}", e),
+            })?;
+        Ok(Address::p2wpkh(&key.to_pub(), self.network).to_string())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct OnChainConfig {
+    /// `None` until the SACCO configures a descriptor; addresses cannot be issued without one
+    pub descriptor: Option<AddressDescriptor>,
+    pub required_confirmations: u32,
+}
+
+impl OnChainConfig {
+    pub fn from_env() -> ApiResult<Self> {
+        let non_empty = |key: &str| std::env::var(key).ok().filter(|value| !value.is_empty());
+
+        let network = non_empty("BITCOIN_NETWORK")
+            .map(|name| {
+                name.parse::<Network>().map_err(|_| ApiError::Validation {
+                    message: format!("Unknown BITCOIN_NETWORK '{}'", name),
+                })
+            })
+            .transpose()?;
+        let descriptor = non_empty("ONCHAIN_DESCRIPTOR")
+            .map(|descriptor| AddressDescriptor::parse(&descriptor, network))
+            .transpose()?;
+
+        Ok(Self {
+            descriptor,
+            required_confirmations: non_empty("ONCHAIN_CONFIRMATIONS")
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(3),
+        })
+    }
+}
+
+#[derive(Default)]
+struct OnChainState {
+    next_index: u32,
+    addresses: Vec<DepositAddress>,
+    deposits: Vec<OnChainDeposit>,
+}
+
+#[derive(Clone)]
+pub struct RustOnChainApi {
+    state: Arc<RwLock<OnChainState>>,
+    wallets: RustWalletsApi,
+    chain: Arc<dyn ChainSource>,
+    config: OnChainConfig,
+    /// Every address issued so far
+    store: JsonStore,
+}
+
+impl RustOnChainApi {
+    pub fn new(
+        wallets: RustWalletsApi,
+        chain: Arc<dyn ChainSource>,
+        config: OnChainConfig,
+    ) -> Self {
+        Self {
+            state: Arc::default(),
+            wallets,
+            chain,
+            config,
+            store: JsonStore::in_memory(),
+        }
+    }
+
+    /// Keep issued addresses in `store`, resuming derivation after the highest
+    /// index it already holds
+    pub fn with_store(mut self, store: JsonStore) -> ApiResult<Self> {
+        let addresses: Vec<DepositAddress> = store.load()?;
+        let next_index = addresses
+            .iter()
+            .map(|address| address.derivation_index + 1)
+            .max()
+            .unwrap_or_default();
+        self.state = Arc::new(RwLock::new(OnChainState {
+            next_index,
+            addresses,
+            deposits: Vec::new(),
+        }));
+        self.store = store;
+        Ok(self)
+    }
+
+    /// Check for deposits every `every` until the process exits
+    pub fn spawn_deposit_poller(&self, every: Duration) -> tokio::task::JoinHandle<()> {
+        let api = self.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(every);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                ticker.tick().await;
+                match api.sync_deposits().await {
+                    Ok(0) => {}
+                    Ok(changed) => tracing::info!("Updated {} on-chain deposit(s)", changed),
+                    Err(e) => tracing::warn!("On-chain deposit check failed: {}", e),
+                }
+            }
+        })
+    }
+
+    /// Credited deposits, keyed by the output that paid them
+    pub async fn settled_movements(&self) -> Vec<ReconciliationItem> {
+        self.state
+            .read()
+            .await
+            .deposits
+            .iter()
+            .filter(|deposit| deposit.status == TransactionStatus::Confirmed)
+            .map(|deposit| ReconciliationItem {
+                reference: output_reference(&deposit.txid, deposit.vout),
+                amount_sats: deposit.amount_sats as i64,
+                description: format!("On-chain deposit to wallet {}", deposit.wallet_id),
+                occurred_at: deposit.settled_at.unwrap_or(deposit.first_seen_at),
+            })
+            .collect()
+    }
+
+    /// Outputs paying our addresses that are buried deep enough to count as held
+    async fn confirmed_outputs(&self) -> ApiResult<Vec<ChainOutput>> {
+        let addresses: Vec<String> = self
+            .state
+            .read()
+            .await
+            .addresses
+            .iter()
+            .map(|address| address.address.clone())
+            .collect();
+        if addresses.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut outputs = self.chain.outputs_to(&addresses).await?;
+        outputs.retain(|output| output.confirmations >= self.config.required_confirmations);
+        Ok(outputs)
+    }
+
+    async fn on_chain_wallet(&self, wallet_id: Uuid) -> ApiResult<()> {
+        let wallet = self.wallets.get_wallet(wallet_id).await?;
+        if !matches!(wallet.wallet_type, WalletType::OnChain) {
+            return Err(ApiError::Validation {
+                message: format!("Wallet {} is not an on-chain wallet", wallet.name),
+            });
+        }
+        Ok(())
+    }
+}
+
+fn output_reference(txid: &str, vout: u32) -> String {
+    format!("{}:{}", txid, vout)
+}
+
+/// Coins paid to the descriptor's addresses, as the chain source sees them
+#[async_trait]
+impl CustodyProvider for RustOnChainApi {
+    fn name(&self) -> String {
+        "On-chain descriptor".to_string()
+    }
+
+    async fn balance_sats(&self) -> ApiResult<u64> {
+        Ok(self
+            .confirmed_outputs()
+            .await?
+            .iter()
+            .map(|output| output.amount_sats)
+            .sum())
+    }
+
+    async fn movements(&self) -> ApiResult<Vec<ReconciliationItem>> {
+        let outputs = self.confirmed_outputs().await?;
+        let state = self.state.read().await;
+        Ok(outputs
+            .into_iter()
+            .map(|output| {
+                // The chain source gives no timestamps; use when we first saw the payment
+                let occurred_at = state
+                    .deposits
+                    .iter()
+                    .find(|d| d.txid == output.txid && d.vout == output.vout)
+                    .map(|d| d.first_seen_at)
+                    .unwrap_or_else(Utc::now);
+                ReconciliationItem {
+                    reference: output_reference(&output.txid, output.vout),
+                    amount_sats: output.amount_sats as i64,
+                    description: format!("On-chain payment to {}", output.address),
+                    occurred_at,
+                }
+            })
+            .collect())
+    }
+}
+
+#[async_trait]
+impl OnChainApi for RustOnChainApi {
+    async fn get_deposit_address(&self, wallet_id: Uuid) -> ApiResult<DepositAddress> {
+        self.on_chain_wallet(wallet_id).await?;
+        let descriptor = self
+            .config
+            .descriptor
+            .as_ref()
+            .ok_or_else(|| ApiError::Validation {
+                message: "On-chain deposits are not configured; set ONCHAIN_DESCRIPTOR".to_string(),
+            })?;
+
+        let mut state = self.state.write().await;
+        if let Some(current) = state.addresses.iter().rfind(|a| a.wallet_id == wallet_id) {
+            let used = state.deposits.iter().any(|d| d.address == current.address);
+            if !used {
+                return Ok(current.clone());
+            }
+        }
+
+        let index = state.next_index;
+        let address = DepositAddress {
+            address: descriptor.address(index)?,
+            wallet_id,
+            derivation_index: index,
+            created_at: Utc::now(),
+        };
+        // The index is spent even if the save fails, so it is never reissued
+        state.next_index += 1;
+        state.addresses.push(address.clone());
+        if let Err(e) = self.store.save(&state.addresses) {
+            state.addresses.pop();
+            return Err(e);
+        }
+        Ok(address)
+    }
+
+    async fn get_wallet_addresses(&self, wallet_id: Uuid) -> ApiResult<Vec<DepositAddress>> {
+        let mut addresses: Vec<DepositAddress> = self
+            .state
+            .read()
+            .await
+            .addresses
+            .iter()
+            .filter(|address| address.wallet_id == wallet_id)
+            .cloned()
+            .collect();
+        addresses.sort_by_key(|address| std::cmp::Reverse(address.derivation_index));
+        Ok(addresses)
+    }
+
+    async fn get_wallet_deposits(&self, wallet_id: Uuid) -> ApiResult<Vec<OnChainDeposit>> {
+        let mut deposits: Vec<OnChainDeposit> = self
+            .state
+            .read()
+            .await
+            .deposits
+            .iter()
+            .filter(|deposit| deposit.wallet_id == wallet_id)
+            .cloned()
+            .collect();
+        deposits.sort_by_key(|deposit| std::cmp::Reverse(deposit.first_seen_at));
+        Ok(deposits)
+    }
+
+    fn required_confirmations(&self) -> u32 {
+        self.config.required_confirmations
+    }
+
+    async fn sync_deposits(&self) -> ApiResult<usize> {
+        let addresses: Vec<String> = self
+            .state
+            .read()
+            .await
+            .addresses
+            .iter()
+            .map(|address| address.address.clone())
+            .collect();
+        if addresses.is_empty() {
+            return Ok(0);
+        }
+        let outputs = self.chain.outputs_to(&addresses).await?;
+
+        // Held across the wallet updates so concurrent syncs cannot record a deposit twice
+        let mut state = self.state.write().await;
+        let mut changed = 0;
+        for output in &outputs {
+            let position = state
+                .deposits
+                .iter()
+                .position(|d| d.txid == output.txid && d.vout == output.vout);
+            let (position, mut was_changed) = match position {
+                Some(position) => (position, false),
+                None => {
+                    let Some(wallet_id) = state
+                        .addresses
+                        .iter()
+                        .find(|a| a.address == output.address)
+                        .map(|a| a.wallet_id)
+                    else {
+                        continue;
+                    };
+                    let transaction = self
+                        .wallets
+                        .record_pending_credit(
+                            wallet_id,
+                            output.amount_sats,
+                            TransactionType::Deposit,
+                        )
+                        .await?;
+                    state.deposits.push(OnChainDeposit {
+                        id: Uuid::new_v4(),
+                        wallet_id,
+                        transaction_id: transaction.id,
+                        address: output.address.clone(),
+                        txid: output.txid.clone(),
+                        vout: output.vout,
+                        amount_sats: output.amount_sats,
+                        confirmations: output.confirmations,
+                        status: TransactionStatus::Pending,
+                        first_seen_at: Utc::now(),
+                        settled_at: None,
+                    });
+                    (state.deposits.len() - 1, true)
+                }
+            };
+
+            let deposit = &mut state.deposits[position];
+            deposit.confirmations = output.confirmations;
+            if deposit.status == TransactionStatus::Pending
+                && output.confirmations >= self.config.required_confirmations
+            {
+                self.wallets
+                    .settle(deposit.transaction_id, TransactionStatus::Confirmed)
+                    .await?;
+                deposit.status = TransactionStatus::Confirmed;
+                deposit.settled_at = Some(Utc::now());
+                was_changed = true;
+            }
+            if was_changed {
+                changed += 1;
+            }
+        }
+
+        // Pending payments the chain no longer knows about were replaced or evicted
+        for deposit in state.deposits.iter_mut() {
+            let still_seen = outputs
+                .iter()
+                .any(|o| o.txid == deposit.txid && o.vout == deposit.vout);
+            if deposit.status == TransactionStatus::Pending && !still_seen {
+                self.wallets
+                    .settle(deposit.transaction_id, TransactionStatus::Failed)
+                    .await?;
+                deposit.status = TransactionStatus::Failed;
+                deposit.settled_at = Some(Utc::now());
+                changed += 1;
+            }
+        }
+        Ok(changed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::{
+        backends::{chain::InMemoryChainSource, rust::RustLedgerApi},
+        traits::wallets::CreateWalletRequest,
+    };
+
+    // BIP84 test vector: account 0 of "abandon abandon ... about"
+    const BIP84_ZPUB: &str = "zpub6rFR7y4Q2AijBEqTUquhVz398htDFrtymD9xYYfG1m4wAcvPhXNfE3EfH1r1ADqtfSdVCToUG868RvUUkgDKf31mGDtKsAYz2oz2AGutZYs";
+
+    #[test]
+    fn test_descriptor_derivation() {
+        let bare = AddressDescriptor::parse(BIP84_ZPUB, None).unwrap();
+        assert_eq!(bare.network(), Network::Bitcoin);
+        assert_eq!(
+            bare.address(0).unwrap(),
+            "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu"
+        );
+        assert_eq!(
+            bare.address(1).unwrap(),
+            "bc1qnjg0jd8228aq7egyzacy8cys3knf9xvrerkf9g"
+        );
+
+        let xpub = Xpub::decode(&{
+            let mut data = base58::decode_check(BIP84_ZPUB).unwrap();
+            data[..4].copy_from_slice(&XPUB);
+            data
+        })
+        .unwrap();
+        let descriptor = AddressDescriptor::parse(
+            &format!("wpkh([73c5da0a/84h/0h/0h]{}/0/*)#abcd", xpub),
+            None,
+        )
+        .unwrap();
+        assert_eq!(descriptor.address(1).unwrap(), bare.address(1).unwrap());
+
+        assert!(AddressDescriptor::parse(&format!("pkh({}/0/*)", xpub), None).is_err());
+        assert!(AddressDescriptor::parse(&format!("wpkh({}/0h/*)", xpub), None).is_err());
+        assert!(AddressDescriptor::parse(&format!("wpkh({}/0/1)", xpub), None).is_err());
+        assert!(AddressDescriptor::parse(BIP84_ZPUB, Some(Network::Regtest)).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_deposits_credit_after_confirmations() {
+        let wallets = RustWalletsApi::new(RustLedgerApi::new());
+        let chain = InMemoryChainSource::new();
+        let api = RustOnChainApi::new(
+            wallets.clone(),
+            Arc::new(chain.clone()),
+            OnChainConfig {
+                descriptor: Some(AddressDescriptor::parse(BIP84_ZPUB, None).unwrap()),
+                required_confirmations: 3,
+            },
+        );
+        let wallet = wallets
+            .create_wallet(CreateWalletRequest {
+                user_id: Uuid::new_v4(),
+                name: "Cold savings".to_string(),
+                wallet_type: WalletType::OnChain,
+            })
+            .await
+            .unwrap();
+
+        // The address is reused until it receives a payment
+        let first = api.get_deposit_address(wallet.id).await.unwrap();
+        assert_eq!(
+            api.get_deposit_address(wallet.id).await.unwrap().address,
+            first.address
+        );
+
+        let paid = chain.broadcast(&first.address, 50_000).await;
+        assert_eq!(api.sync_deposits().await.unwrap(), 1);
+        assert_eq!(wallets.get_wallet_balance(wallet.id).await.unwrap(), 0);
+        let second = api.get_deposit_address(wallet.id).await.unwrap();
+        assert_eq!(second.derivation_index, 1);
+
+        let dropped = chain.broadcast(&second.address, 8_000).await;
+        chain.mine(2).await;
+        // Seen for the first time, but not yet buried deep enough
+        let evicted = chain.broadcast(&second.address, 1_000).await;
+        chain.drop_transaction(&dropped).await.unwrap();
+        assert_eq!(api.sync_deposits().await.unwrap(), 1);
+        assert_eq!(wallets.get_wallet_balance(wallet.id).await.unwrap(), 0);
+
+        chain.mine(1).await;
+        assert_eq!(api.sync_deposits().await.unwrap(), 1);
+        assert_eq!(wallets.get_wallet_balance(wallet.id).await.unwrap(), 50_000);
+
+        let deposits = api.get_wallet_deposits(wallet.id).await.unwrap();
+        let confirmed = deposits.iter().find(|d| d.txid == paid).unwrap();
+        assert_eq!(confirmed.status, TransactionStatus::Confirmed);
+        assert_eq!(confirmed.confirmations, 3);
+        assert_eq!(
+            deposits
+                .iter()
+                .filter(|d| d.status == TransactionStatus::Pending)
+                .count(),
+            1
+        );
+
+        // A pending payment that disappears from the chain fails without crediting
+        chain.drop_transaction(&evicted).await.unwrap();
+        assert_eq!(api.sync_deposits().await.unwrap(), 1);
+        assert_eq!(wallets.get_wallet_balance(wallet.id).await.unwrap(), 50_000);
+        assert!(api
+            .get_wallet_deposits(wallet.id)
+            .await
+            .unwrap()
+            .iter()
+            .all(|d| d.status != TransactionStatus::Pending));
+
+        let other = wallets
+            .create_wallet(CreateWalletRequest {
+                user_id: Uuid::new_v4(),
+                name: "Spending".to_string(),
+                wallet_type: WalletType::Fedimint,
+            })
+            .await
+            .unwrap();
+        assert!(matches!(
+            api.get_deposit_address(other.id).await,
+            Err(ApiError::Validation { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_issued_indices_survive_a_restart() {
+        let path = std::env::temp_dir().join(format!("bitsacco-onchain-{}.json", Uuid::new_v4()));
+        let wallets = RustWalletsApi::new(RustLedgerApi::new());
+        let start = |wallets: RustWalletsApi| {
+            RustOnChainApi::new(
+                wallets,
+                Arc::new(InMemoryChainSource::new()),
+                OnChainConfig {
+                    descriptor: Some(AddressDescriptor::parse(BIP84_ZPUB, None).unwrap()),
+                    required_confirmations: 3,
+                },
+            )
+            .with_store(JsonStore::at(&path))
+            .unwrap()
+        };
+        let mut wallet_ids = Vec::new();
+        for name in ["First", "Second"] {
+            let wallet = wallets
+                .create_wallet(CreateWalletRequest {
+                    user_id: Uuid::new_v4(),
+                    name: name.to_string(),
+                    wallet_type: WalletType::OnChain,
+                })
+                .await
+                .unwrap();
+            wallet_ids.push(wallet.id);
+        }
+
+        let first = start(wallets.clone())
+            .get_deposit_address(wallet_ids[0])
+            .await
+            .unwrap();
+        assert_eq!(first.derivation_index, 0);
+
+        let restarted = start(wallets);
+        let second = restarted.get_deposit_address(wallet_ids[1]).await.unwrap();
+        assert_eq!(second.derivation_index, 1);
+        assert_ne!(second.address, first.address);
+        // The first wallet still gets the address it was given
+        assert_eq!(
+            restarted
+                .get_deposit_address(wallet_ids[0])
+                .await
+                .unwrap()
+                .address,
+            first.address
+        );
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
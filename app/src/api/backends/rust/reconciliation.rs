@@ -0,0 +1,413 @@
+// Reconciliation of the ledger against custody providers for the Rust backend
+// Each run sums the ledger's treasury and member wallet accounts, asks every
+// provider what it holds, and pairs up the movements both sides recorded. Runs
+// are kept on disk for treasurers to review.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use rust_decimal::Decimal;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use super::{JsonStore, RustLedgerApi, RustLightningApi, RustOnChainApi};
+use crate::api::{
+    errors::{ApiError, ApiResult},
+    traits::{
+        ledger::{AccountKey, AccountPurpose, LedgerApi, LedgerCurrency},
+        reconciliation::{
+            match_items, CustodyProvider, ProviderBalance, ReconciliationApi, ReconciliationItem,
+            ReconciliationRun, ReconciliationStatus, DEFAULT_DRIFT_THRESHOLD_SATS,
+        },
+    },
+};
+
+/// Runs kept for review; older ones are dropped as new ones finish
+const MAX_RUNS: usize = 2_000;
+
+/// Add a finished run, dropping the oldest beyond `MAX_RUNS`
+fn keep_run(runs: &mut Vec<ReconciliationRun>, run: ReconciliationRun) {
+    runs.push(run);
+    let excess = runs.len().saturating_sub(MAX_RUNS);
+    runs.drain(..excess);
+}
+
+#[derive(Clone)]
+pub struct RustReconciliationApi {
+    runs: Arc<RwLock<Vec<ReconciliationRun>>>,
+    store: JsonStore,
+    ledger: RustLedgerApi,
+    lightning: RustLightningApi,
+    onchain: RustOnChainApi,
+    providers: Vec<Arc<dyn CustodyProvider>>,
+    threshold_sats: u64,
+}
+
+impl RustReconciliationApi {
+    pub fn new(
+        ledger: RustLedgerApi,
+        lightning: RustLightningApi,
+        onchain: RustOnChainApi,
+        providers: Vec<Arc<dyn CustodyProvider>>,
+        threshold_sats: u64,
+    ) -> Self {
+        Self {
+            runs: Arc::default(),
+            store: JsonStore::in_memory(),
+            ledger,
+            lightning,
+            onchain,
+            providers,
+            threshold_sats,
+        }
+    }
+
+    /// Keep runs in `store`, starting from the ones it already holds
+    pub fn with_store(mut self, store: JsonStore) -> ApiResult<Self> {
+        let runs: Vec<ReconciliationRun> = store.load()?;
+        self.runs = Arc::new(RwLock::new(runs));
+        self.store = store;
+        Ok(self)
+    }
+
+    /// Drift threshold from RECONCILIATION_DRIFT_SATS
+    pub fn threshold_from_env() -> ApiResult<u64> {
+        match std::env::var("RECONCILIATION_DRIFT_SATS") {
+            Ok(value) if !value.trim().is_empty() => {
+                value.trim().parse().map_err(|_| ApiError::Validation {
+                    message: format!("RECONCILIATION_DRIFT_SATS '{}' is not a number", value),
+                })
+            }
+            _ => Ok(DEFAULT_DRIFT_THRESHOLD_SATS),
+        }
+    }
+
+    /// Reconcile every `every` until the process exits
+    pub fn spawn_reconciliation_poller(&self, every: Duration) -> tokio::task::JoinHandle<()> {
+        let api = self.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(every);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                ticker.tick().await;
+                match api.run_reconciliation(None).await {
+                    Ok(run) if run.status == ReconciliationStatus::Balanced => {}
+                    Ok(run) => tracing::warn!(
+                        "Reconciliation {} finished with {:?}: drift {} sats",
+                        run.id,
+                        run.status,
+                        run.drift_sats
+                    ),
+                    Err(e) => tracing::warn!("Reconciliation failed: {}", e),
+                }
+            }
+        })
+    }
+
+    /// Treasury balance and the sum of member wallet accounts, in sats
+    async fn ledger_balances(&self) -> ApiResult<(Decimal, Decimal)> {
+        let treasury = self
+            .ledger
+            .get_account_balance(AccountKey::sacco(
+                AccountPurpose::Treasury,
+                LedgerCurrency::Sats,
+            ))
+            .await?;
+        let members = self
+            .ledger
+            .get_accounts(None)
+            .await?
+            .iter()
+            .filter(|account| {
+                account.account.key.currency == LedgerCurrency::Sats
+                    && matches!(account.account.key.purpose, AccountPurpose::Wallet(_))
+            })
+            .map(|account| account.balance)
+            .sum();
+        Ok((treasury, members))
+    }
+}
+
+#[async_trait]
+impl ReconciliationApi for RustReconciliationApi {
+    async fn run_reconciliation(&self, triggered_by: Option<Uuid>) -> ApiResult<ReconciliationRun> {
+        let started_at = Utc::now();
+
+        let mut providers = Vec::with_capacity(self.providers.len());
+        let mut provider_items = Vec::new();
+        let mut provider_balance = Decimal::ZERO;
+        for provider in &self.providers {
+            let report = async {
+                let balance = provider.balance_sats().await?;
+                Ok::<_, ApiError>((balance, provider.movements().await?))
+            }
+            .await;
+            match report {
+                Ok((balance, items)) => {
+                    provider_balance += Decimal::from(balance);
+                    provider_items.extend(items);
+                    providers.push(ProviderBalance {
+                        provider: provider.name(),
+                        balance_sats: Some(balance),
+                        error: None,
+                    });
+                }
+                Err(e) => providers.push(ProviderBalance {
+                    provider: provider.name(),
+                    balance_sats: None,
+                    error: Some(e.to_string()),
+                }),
+            }
+        }
+        let failed = providers.iter().any(|p| p.error.is_some());
+
+        let (ledger_balance, member_balances) = self.ledger_balances().await?;
+        let drift = provider_balance - ledger_balance;
+
+        // Without every provider's log, any ledger movement could look unmatched
+        let (unmatched_ledger, unmatched_provider) = if failed {
+            (Vec::new(), Vec::new())
+        } else {
+            let mut ledger_items: Vec<ReconciliationItem> =
+                self.lightning.settled_movements().await;
+            ledger_items.extend(self.onchain.settled_movements().await);
+            match_items(ledger_items, provider_items)
+        };
+
+        let status = if failed {
+            ReconciliationStatus::Failed
+        } else if drift.abs() > Decimal::from(self.threshold_sats)
+            || !unmatched_ledger.is_empty()
+            || !unmatched_provider.is_empty()
+        {
+            ReconciliationStatus::Drift
+        } else {
+            ReconciliationStatus::Balanced
+        };
+
+        let run = ReconciliationRun {
+            id: Uuid::new_v4(),
+            triggered_by,
+            started_at,
+            finished_at: Utc::now(),
+            threshold_sats: self.threshold_sats,
+            member_balances_sats: member_balances,
+            ledger_balance_sats: ledger_balance,
+            providers,
+            provider_balance_sats: provider_balance,
+            drift_sats: drift,
+            unmatched_ledger,
+            unmatched_provider,
+            status,
+        };
+        let mut runs = self.runs.write().await;
+        keep_run(&mut runs, run.clone());
+        // The run reflects balances already read; it stands even if it can't be saved
+        self.store.save_or_log(&*runs, "reconciliation runs");
+        Ok(run)
+    }
+
+    async fn list_runs(&self) -> ApiResult<Vec<ReconciliationRun>> {
+        let mut runs = self.runs.read().await.clone();
+        runs.sort_by_key(|run| std::cmp::Reverse(run.started_at));
+        Ok(runs)
+    }
+
+    async fn get_run(&self, run_id: Uuid) -> ApiResult<ReconciliationRun> {
+        self.runs
+            .read()
+            .await
+            .iter()
+            .find(|run| run.id == run_id)
+            .cloned()
+            .ok_or_else(|| ApiError::NotFound {
+                resource: format!("Reconciliation run {}", run_id),
+            })
+    }
+
+    fn drift_threshold_sats(&self) -> u64 {
+        self.threshold_sats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::{
+        backends::{
+            chain::InMemoryChainSource,
+            fmcd::{mock::MockFmcd, FmcdClient, FmcdConfig},
+            rust::{OnChainConfig, RustWalletsApi},
+        },
+        traits::{
+            lightning::{CreateInvoiceRequest, LightningApi, PayInvoiceRequest},
+            wallets::{CreateWalletRequest, WalletType, WalletsApi},
+        },
+        types::{common::Role, User},
+    };
+
+    struct Setup {
+        api: RustReconciliationApi,
+        lightning: RustLightningApi,
+        wallet_id: Uuid,
+        owner: User,
+    }
+
+    async fn setup(fmcd: FmcdClient) -> Setup {
+        let ledger = RustLedgerApi::new();
+        let wallets = RustWalletsApi::new(ledger.clone());
+        let owner = User {
+            id: Uuid::new_v4(),
+            phone: None,
+            nostr: None,
+            profile: None,
+            roles: vec![Role::Member],
+            verified: true,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        let wallet = wallets
+            .create_wallet(CreateWalletRequest {
+                user_id: owner.id,
+                name: "Savings".to_string(),
+                wallet_type: WalletType::Lightning,
+            })
+            .await
+            .unwrap();
+        let lightning = RustLightningApi::new(ledger.clone(), wallets.clone(), fmcd.clone());
+        let onchain = RustOnChainApi::new(
+            wallets,
+            Arc::new(InMemoryChainSource::new()),
+            OnChainConfig {
+                descriptor: None,
+                required_confirmations: 1,
+            },
+        );
+        let api = RustReconciliationApi::new(
+            ledger,
+            lightning.clone(),
+            onchain.clone(),
+            vec![Arc::new(fmcd), Arc::new(onchain)],
+            1_000,
+        );
+        Setup {
+            api,
+            lightning,
+            wallet_id: wallet.id,
+            owner,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_runs_compare_ledger_with_providers() {
+        let fmcd = MockFmcd::start().await;
+        let Setup {
+            api,
+            lightning,
+            wallet_id,
+            owner,
+        } = setup(fmcd.client()).await;
+
+        let invoice = lightning
+            .create_deposit_invoice(CreateInvoiceRequest {
+                wallet_id,
+                amount_sats: 50_000,
+                description: "Deposit".to_string(),
+                expiry_secs: 3_600,
+            })
+            .await
+            .unwrap();
+        fmcd.mark_paid(&invoice.operation_id).await;
+        lightning.sync_pending_invoices().await.unwrap();
+
+        let run = api.run_reconciliation(None).await.unwrap();
+        assert_eq!(run.status, ReconciliationStatus::Balanced);
+        assert_eq!(run.ledger_balance_sats, Decimal::from(50_000));
+        assert_eq!(run.provider_balance_sats, Decimal::from(50_000));
+        assert!(run.covers_members());
+
+        // The routing fee is charged to the SACCO, so the ledger follows the federation
+        lightning
+            .pay_invoice(
+                PayInvoiceRequest {
+                    wallet_id,
+                    bolt11: "lnbcrt40u1pexample".to_string(),
+                },
+                &owner,
+            )
+            .await
+            .unwrap();
+        let run = api.run_reconciliation(None).await.unwrap();
+        assert_eq!(run.drift_sats, Decimal::ZERO);
+        assert_eq!(run.ledger_balance_sats, Decimal::from(45_998));
+        assert_eq!(run.member_balances_sats, Decimal::from(46_000));
+        assert_eq!(run.status, ReconciliationStatus::Balanced);
+        assert!(!run.covers_members());
+
+        // Funds the ledger never recorded are flagged on the provider side
+        let stray = fmcd.receive_external(5_000_000).await;
+        let admin = Uuid::new_v4();
+        let run = api.run_reconciliation(Some(admin)).await.unwrap();
+        assert_eq!(run.status, ReconciliationStatus::Drift);
+        assert_eq!(run.drift_sats, Decimal::from(5_000));
+        assert!(run.unmatched_ledger.is_empty());
+        assert_eq!(run.unmatched_provider.len(), 1);
+        assert_eq!(run.unmatched_provider[0].reference, stray);
+        assert_eq!(run.unmatched_provider[0].amount_sats, 5_000);
+
+        let runs = api.list_runs().await.unwrap();
+        assert_eq!(runs.len(), 3);
+        assert_eq!(runs[0].triggered_by, Some(admin));
+        assert_eq!(
+            api.get_run(run.id).await.unwrap().drift_sats,
+            run.drift_sats
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_provider_fails_the_run() {
+        let unreachable = FmcdClient::new(FmcdConfig {
+            base_url: "http://127.0.0.1:9".to_string(),
+            ..FmcdConfig::default()
+        })
+        .unwrap();
+        let Setup { api, .. } = setup(unreachable).await;
+
+        let run = api.run_reconciliation(None).await.unwrap();
+        assert_eq!(run.status, ReconciliationStatus::Failed);
+        assert!(run.providers[0].balance_sats.is_none());
+        assert!(run.providers[0].error.is_some());
+        // The on-chain descriptor has no addresses yet and holds nothing
+        assert_eq!(run.providers[1].balance_sats, Some(0));
+    }
+
+    #[tokio::test]
+    async fn test_runs_survive_a_restart_and_are_capped() {
+        let dir = std::env::temp_dir().join(format!("bitsacco-reconciliation-{}", Uuid::new_v4()));
+        let path = dir.join("reconciliation.json");
+        let unreachable = FmcdClient::new(FmcdConfig {
+            base_url: "http://127.0.0.1:9".to_string(),
+            ..FmcdConfig::default()
+        })
+        .unwrap();
+        let Setup { api, .. } = setup(unreachable).await;
+        let api = api.with_store(JsonStore::at(&path)).unwrap();
+
+        let run = api.run_reconciliation(None).await.unwrap();
+        let restarted = api.clone().with_store(JsonStore::at(&path)).unwrap();
+        let runs = restarted.list_runs().await.unwrap();
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].id, run.id);
+        assert_eq!(runs[0].status, ReconciliationStatus::Failed);
+
+        let mut runs = Vec::new();
+        for _ in 0..=MAX_RUNS {
+            keep_run(&mut runs, run.clone());
+        }
+        assert_eq!(runs.len(), MAX_RUNS);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,239 @@
+// Saved custom analytics reports for the Rust backend
+// Reports are stored per admin. Saving under a name the admin already used
+// replaces that report, so "save" doubles as "update". Reports are kept on disk
+// so they outlive a restart.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use super::JsonStore;
+use crate::api::{
+    errors::{ApiError, ApiResult},
+    traits::reports::{SaveReportRequest, SavedReport, SavedReportsApi, MAX_REPORT_NAME_CHARS},
+};
+
+#[derive(Clone, Default)]
+pub struct RustSavedReportsApi {
+    reports: Arc<RwLock<Vec<SavedReport>>>,
+    store: JsonStore,
+}
+
+impl RustSavedReportsApi {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keep reports in `store`, starting from the ones it already holds
+    pub fn with_store(mut self, store: JsonStore) -> ApiResult<Self> {
+        let reports: Vec<SavedReport> = store.load()?;
+        self.reports = Arc::new(RwLock::new(reports));
+        self.store = store;
+        Ok(self)
+    }
+}
+
+#[async_trait]
+impl SavedReportsApi for RustSavedReportsApi {
+    async fn list_reports(&self, owner_id: &str) -> ApiResult<Vec<SavedReport>> {
+        let mut reports: Vec<SavedReport> = self
+            .reports
+            .read()
+            .await
+            .iter()
+            .filter(|report| report.owner_id == owner_id)
+            .cloned()
+            .collect();
+        reports.sort_by_key(|report| std::cmp::Reverse(report.updated_at));
+        Ok(reports)
+    }
+
+    async fn get_report(&self, owner_id: &str, report_id: Uuid) -> ApiResult<SavedReport> {
+        self.reports
+            .read()
+            .await
+            .iter()
+            .find(|report| report.id == report_id && report.owner_id == owner_id)
+            .cloned()
+            .ok_or_else(|| ApiError::NotFound {
+                resource: format!("Saved report {}", report_id),
+            })
+    }
+
+    async fn save_report(
+        &self,
+        owner_id: &str,
+        request: SaveReportRequest,
+    ) -> ApiResult<SavedReport> {
+        let name = request.name.trim().to_string();
+        if name.is_empty() {
+            return Err(ApiError::Validation {
+                message: "Give the report a name".to_string(),
+            });
+        }
+        if name.chars().count() > MAX_REPORT_NAME_CHARS {
+            return Err(ApiError::Validation {
+                message: format!(
+                    "Report names may be at most {} characters",
+                    MAX_REPORT_NAME_CHARS
+                ),
+            });
+        }
+        // Refuse specs that could never be run, so a saved report always opens
+        request.spec.query(Utc::now().date_naive())?;
+
+        let now = Utc::now();
+        let mut reports = self.reports.write().await;
+        let existing = reports.iter().position(|report| {
+            report.owner_id == owner_id && report.name.to_lowercase() == name.to_lowercase()
+        });
+        let report = match existing {
+            Some(index) => {
+                let previous = reports[index].clone();
+                let report = &mut reports[index];
+                report.name = name;
+                report.spec = request.spec;
+                report.view = request.view;
+                report.updated_at = now;
+                let report = report.clone();
+                self.store
+                    .save_or_undo(&mut *reports, |reports| reports[index] = previous)?;
+                report
+            }
+            None => {
+                let report = SavedReport {
+                    id: Uuid::new_v4(),
+                    owner_id: owner_id.to_string(),
+                    name,
+                    spec: request.spec,
+                    view: request.view,
+                    created_at: now,
+                    updated_at: now,
+                };
+                reports.push(report.clone());
+                self.store.save_or_undo(&mut *reports, |reports| {
+                    reports.pop();
+                })?;
+                report
+            }
+        };
+        Ok(report)
+    }
+
+    async fn delete_report(&self, owner_id: &str, report_id: Uuid) -> ApiResult<()> {
+        let mut reports = self.reports.write().await;
+        let index = reports
+            .iter()
+            .position(|report| report.id == report_id && report.owner_id == owner_id)
+            .ok_or_else(|| ApiError::NotFound {
+                resource: format!("Saved report {}", report_id),
+            })?;
+        let removed = reports.remove(index);
+        self.store
+            .save_or_undo(&mut *reports, |reports| reports.insert(index, removed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::{
+        traits::reports::ReportView,
+        types::{AnalyticsMetric, ReportSpec},
+    };
+
+    fn request(name: &str, metrics: &[AnalyticsMetric]) -> SaveReportRequest {
+        SaveReportRequest {
+            name: name.to_string(),
+            spec: ReportSpec {
+                metrics: metrics.to_vec(),
+                ..Default::default()
+            },
+            view: ReportView::Chart,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reports_are_saved_per_owner_and_replaced_by_name() {
+        let api = RustSavedReportsApi::new();
+        let saved = api
+            .save_report(
+                "admin-1",
+                request("Weekly growth", &[AnalyticsMetric::NewMembers]),
+            )
+            .await
+            .unwrap();
+
+        let mut replacement = request("weekly GROWTH ", &[AnalyticsMetric::ActiveMembers]);
+        replacement.view = ReportView::Table;
+        let replaced = api.save_report("admin-1", replacement).await.unwrap();
+        assert_eq!(replaced.id, saved.id);
+        assert_eq!(replaced.name, "weekly GROWTH");
+        assert_eq!(replaced.spec.metrics, vec![AnalyticsMetric::ActiveMembers]);
+        assert_eq!(replaced.view, ReportView::Table);
+
+        api.save_report(
+            "admin-2",
+            request("Weekly growth", &[AnalyticsMetric::NewMembers]),
+        )
+        .await
+        .unwrap();
+        assert_eq!(api.list_reports("admin-1").await.unwrap().len(), 1);
+        assert!(matches!(
+            api.get_report("admin-2", saved.id).await,
+            Err(ApiError::NotFound { .. })
+        ));
+        assert!(matches!(
+            api.delete_report("admin-2", saved.id).await,
+            Err(ApiError::NotFound { .. })
+        ));
+
+        assert!(matches!(
+            api.save_report("admin-1", request("  ", &[AnalyticsMetric::NewMembers]))
+                .await,
+            Err(ApiError::Validation { .. })
+        ));
+        assert!(matches!(
+            api.save_report("admin-1", request("Empty", &[])).await,
+            Err(ApiError::Validation { .. })
+        ));
+
+        api.delete_report("admin-1", saved.id).await.unwrap();
+        assert!(api.list_reports("admin-1").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_reports_survive_a_restart() {
+        let dir = std::env::temp_dir().join(format!("bitsacco-reports-{}", Uuid::new_v4()));
+        let path = dir.join("saved-reports.json");
+
+        let api = RustSavedReportsApi::new()
+            .with_store(JsonStore::at(&path))
+            .unwrap();
+        let kept = api
+            .save_report("admin-1", request("Growth", &[AnalyticsMetric::NewMembers]))
+            .await
+            .unwrap();
+        let dropped = api
+            .save_report(
+                "admin-1",
+                request("Churn", &[AnalyticsMetric::ActiveMembers]),
+            )
+            .await
+            .unwrap();
+        api.delete_report("admin-1", dropped.id).await.unwrap();
+
+        let restarted = RustSavedReportsApi::new()
+            .with_store(JsonStore::at(&path))
+            .unwrap();
+        let listed = restarted.list_reports("admin-1").await.unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].id, kept.id);
+        assert_eq!(listed[0].spec.metrics, vec![AnalyticsMetric::NewMembers]);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
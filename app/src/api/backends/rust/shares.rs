@@ -0,0 +1,1282 @@
+// In-process shares engine for the Rust backend
+// Offers, subscriptions, transfers and the per-member share ledger are held
+// in memory behind a shared lock and saved to a JSON store after each change;
+// share capital is posted to the double-entry ledger. Subscriptions are owed by
+// the member on their shares-receivable account until they pay for them from a
+// wallet or in cash; only shares whose subscription is paid in full become share
+// capital, and only those count towards borrowing, dividends and transfers.
+// Transfers are reviewed by an admin other than whoever requested them.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use rust_decimal::{prelude::ToPrimitive, Decimal};
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use super::{wallets::signed_sats, JsonStore, RustFxApi, RustLedgerApi, RustWalletsApi};
+use crate::api::{
+    errors::{ApiError, ApiResult},
+    traits::{
+        fx::FxApi,
+        ledger::{
+            AccountKey, AccountPurpose, JournalEntryRequest, LedgerApi, LedgerCurrency,
+            PostingRequest,
+        },
+        shares::{
+            CreateShareOfferRequest, PaySharesRequest, ShareLedgerEntry, ShareLedgerEntryKind,
+            ShareOffer, ShareOfferStatus, ShareSubscription, ShareTransfer, ShareTransferFilter,
+            ShareTransferStatus, SharesApi, SubscribeSharesRequest, TransferSharesRequest,
+        },
+        wallets::{TransactionType, WalletsApi},
+    },
+    types::{PaginatedResponse, PaginationQuery, User},
+};
+
+#[derive(Default, serde::Serialize, serde::Deserialize)]
+struct SharesState {
+    offers: HashMap<Uuid, ShareOffer>,
+    subscriptions: Vec<ShareSubscription>,
+    transfers: HashMap<Uuid, ShareTransfer>,
+    ledger: Vec<ShareLedgerEntry>,
+}
+
+impl SharesState {
+    /// Shares currently held by a member according to the ledger
+    fn holdings(&self, user_id: Uuid) -> u64 {
+        self.ledger
+            .iter()
+            .rev()
+            .find(|entry| entry.user_id == user_id)
+            .map(|entry| entry.balance_after)
+            .unwrap_or(0)
+    }
+
+    /// Shares held under subscriptions the member has not finished paying for
+    fn unpaid_shares(&self, user_id: Uuid) -> u64 {
+        self.subscriptions
+            .iter()
+            .filter(|sub| sub.user_id == user_id && !sub.is_paid_up())
+            .map(|sub| sub.quantity)
+            .sum()
+    }
+
+    /// Shares the member holds and has paid for
+    fn paid_up_shares(&self, user_id: Uuid) -> u64 {
+        self.holdings(user_id)
+            .saturating_sub(self.unpaid_shares(user_id))
+    }
+
+    /// Shares already committed to the member's pending outgoing transfers
+    fn pending_outgoing(&self, user_id: Uuid) -> u64 {
+        self.transfers
+            .values()
+            .filter(|t| t.from_user_id == user_id && t.status == ShareTransferStatus::Pending)
+            .map(|t| t.quantity)
+            .sum()
+    }
+
+    fn post(
+        &mut self,
+        user_id: Uuid,
+        kind: ShareLedgerEntryKind,
+        quantity: u64,
+        reference_id: Uuid,
+        counterparty_id: Option<Uuid>,
+        price_per_share: Option<Decimal>,
+    ) {
+        let held = self.holdings(user_id);
+        let (delta, balance_after) = match kind {
+            ShareLedgerEntryKind::TransferOut => (-(quantity as i64), held - quantity),
+            _ => (quantity as i64, held + quantity),
+        };
+
+        self.ledger.push(ShareLedgerEntry {
+            id: Uuid::new_v4(),
+            user_id,
+            kind,
+            quantity: delta,
+            balance_after,
+            reference_id,
+            counterparty_id,
+            price_per_share,
+            created_at: Utc::now(),
+        });
+    }
+}
+
+#[derive(Clone)]
+pub struct RustSharesApi {
+    state: Arc<RwLock<SharesState>>,
+    ledger: RustLedgerApi,
+    wallets: RustWalletsApi,
+    fx: RustFxApi,
+    store: JsonStore,
+}
+
+impl RustSharesApi {
+    pub fn new(ledger: RustLedgerApi, wallets: RustWalletsApi, fx: RustFxApi) -> Self {
+        Self {
+            state: Arc::default(),
+            ledger,
+            wallets,
+            fx,
+            store: JsonStore::in_memory(),
+        }
+    }
+
+    /// Keep offers, subscriptions and transfers in `store`, starting from what it
+    /// already holds
+    pub fn with_store(mut self, store: JsonStore) -> ApiResult<Self> {
+        let state: SharesState = store.load()?;
+        self.state = Arc::new(RwLock::new(state));
+        self.store = store;
+        Ok(self)
+    }
+
+    /// Shares a member holds and has paid for in full
+    pub async fn paid_up_shares(&self, user_id: Uuid) -> u64 {
+        self.state.read().await.paid_up_shares(user_id)
+    }
+
+    /// Post a movement of share capital in KES, skipping zero-value movements
+    async fn post_share_capital(
+        &self,
+        description: String,
+        reference_id: Uuid,
+        debit: AccountKey,
+        credit: AccountKey,
+        value: Decimal,
+    ) -> ApiResult<()> {
+        let value = value.round_dp(LedgerCurrency::Kes.decimal_places());
+        if value <= Decimal::ZERO {
+            return Ok(());
+        }
+
+        self.ledger
+            .post_journal_entry(JournalEntryRequest {
+                description,
+                reference_id: Some(reference_id),
+                postings: vec![
+                    PostingRequest::debit(debit, value),
+                    PostingRequest::credit(credit, value),
+                ],
+                created_by: None,
+            })
+            .await?;
+        Ok(())
+    }
+}
+
+fn share_capital_account(user_id: Uuid) -> AccountKey {
+    AccountKey::member(user_id, AccountPurpose::ShareCapital, LedgerCurrency::Kes)
+}
+
+fn shares_receivable(user_id: Uuid) -> AccountKey {
+    AccountKey::member(
+        user_id,
+        AccountPurpose::SharesReceivable,
+        LedgerCurrency::Kes,
+    )
+}
+
+fn unpaid_share_capital(user_id: Uuid) -> AccountKey {
+    AccountKey::member(
+        user_id,
+        AccountPurpose::UnpaidShareCapital,
+        LedgerCurrency::Kes,
+    )
+}
+
+#[async_trait]
+impl SharesApi for RustSharesApi {
+    async fn get_share_offer(&self, offer_id: Uuid) -> ApiResult<ShareOffer> {
+        let mut state = self.state.write().await;
+        let offer = state
+            .offers
+            .get_mut(&offer_id)
+            .ok_or_else(|| offer_not_found(offer_id))?;
+        refresh_status(offer);
+        Ok(offer.clone())
+    }
+
+    async fn get_share_offers(
+        &self,
+        pagination: PaginationQuery,
+    ) -> ApiResult<PaginatedResponse<ShareOffer>> {
+        let mut state = self.state.write().await;
+        state.offers.values_mut().for_each(refresh_status);
+
+        let mut offers: Vec<ShareOffer> = state.offers.values().cloned().collect();
+        offers.sort_by_key(|item| std::cmp::Reverse(item.created_at));
+        Ok(PaginatedResponse::paginate(offers, &pagination))
+    }
+
+    async fn create_share_offer(&self, request: CreateShareOfferRequest) -> ApiResult<ShareOffer> {
+        let now = Utc::now();
+        let available_from = request.available_from.unwrap_or(now);
+
+        if request.title.trim().is_empty() {
+            return Err(ApiError::Validation {
+                message: "Share offer title is required".to_string(),
+            });
+        }
+        if request.total_quantity == 0 {
+            return Err(ApiError::Validation {
+                message: "Share offer quantity must be greater than zero".to_string(),
+            });
+        }
+        if request.price_per_share <= Decimal::ZERO {
+            return Err(ApiError::Validation {
+                message: "Price per share must be greater than zero".to_string(),
+            });
+        }
+        if request.expires_at <= available_from || request.expires_at <= now {
+            return Err(ApiError::Validation {
+                message: "Share offer expiry must be in the future".to_string(),
+            });
+        }
+
+        let offer = ShareOffer {
+            id: Uuid::new_v4(),
+            title: request.title.trim().to_string(),
+            description: request.description.filter(|d| !d.trim().is_empty()),
+            total_quantity: request.total_quantity,
+            subscribed_quantity: 0,
+            price_per_share: Some(request.price_per_share),
+            status: ShareOfferStatus::Open,
+            available_from,
+            expires_at: request.expires_at,
+            created_at: now,
+            updated_at: now,
+        };
+
+        let mut state = self.state.write().await;
+        state.offers.insert(offer.id, offer.clone());
+        self.store.save_or_undo(&mut *state, |state| {
+            state.offers.remove(&offer.id);
+        })?;
+        Ok(offer)
+    }
+
+    async fn subscribe_shares(
+        &self,
+        request: SubscribeSharesRequest,
+    ) -> ApiResult<ShareSubscription> {
+        if request.quantity == 0 {
+            return Err(ApiError::Validation {
+                message: "Subscription quantity must be greater than zero".to_string(),
+            });
+        }
+
+        let mut state = self.state.write().await;
+        let offer = state
+            .offers
+            .get_mut(&request.offer_id)
+            .ok_or_else(|| offer_not_found(request.offer_id))?;
+        refresh_status(offer);
+
+        let now = Utc::now();
+        if offer.available_from > now {
+            return Err(ApiError::Conflict {
+                message: format!(
+                    "Share offer opens for subscription on {}",
+                    offer.available_from.format("%Y-%m-%d")
+                ),
+            });
+        }
+        if offer.status != ShareOfferStatus::Open {
+            return Err(ApiError::Conflict {
+                message: format!(
+                    "Share offer is {} and no longer accepts subscriptions",
+                    offer.status.as_str().replace('_', " ")
+                ),
+            });
+        }
+        if request.quantity > offer.available_quantity() {
+            return Err(ApiError::Validation {
+                message: format!(
+                    "Requested {} shares but only {} are available",
+                    request.quantity,
+                    offer.available_quantity()
+                ),
+            });
+        }
+
+        let subscription = ShareSubscription {
+            id: Uuid::new_v4(),
+            offer_id: offer.id,
+            user_id: request.user_id,
+            quantity: request.quantity,
+            price_per_share: offer.price_per_share,
+            paid: Decimal::ZERO,
+            created_at: now,
+        };
+        if let Some(value) = subscription.total_value() {
+            self.post_share_capital(
+                format!(
+                    "Subscription for {} shares in {}",
+                    subscription.quantity, offer.title
+                ),
+                subscription.id,
+                shares_receivable(subscription.user_id),
+                unpaid_share_capital(subscription.user_id),
+                value,
+            )
+            .await?;
+        }
+
+        offer.subscribed_quantity += request.quantity;
+        offer.updated_at = now;
+        refresh_status(offer);
+        state.subscriptions.push(subscription.clone());
+        state.post(
+            subscription.user_id,
+            ShareLedgerEntryKind::Subscription,
+            subscription.quantity,
+            subscription.id,
+            None,
+            subscription.price_per_share,
+        );
+        self.store.save_or_log(&*state, "share subscriptions");
+
+        Ok(subscription)
+    }
+
+    async fn pay_for_shares(
+        &self,
+        request: PaySharesRequest,
+        payer: &User,
+    ) -> ApiResult<ShareSubscription> {
+        // Cash payments clear the receivable without moving wallet funds, so only
+        // an admin who received the money can record one
+        if request.wallet_id.is_none() && !payer.is_admin() {
+            return Err(ApiError::Authorization {
+                message: "Only admins can record share payments received outside the wallets"
+                    .to_string(),
+            });
+        }
+        let amount = request
+            .amount
+            .round_dp(LedgerCurrency::Kes.decimal_places());
+        if amount <= Decimal::ZERO {
+            return Err(ApiError::Validation {
+                message: "Payment amount must be greater than zero".to_string(),
+            });
+        }
+
+        let mut state = self.state.write().await;
+        let subscription = state
+            .subscriptions
+            .iter_mut()
+            .find(|sub| sub.id == request.subscription_id)
+            .ok_or_else(|| subscription_not_found(request.subscription_id))?;
+        if !payer.is_admin() && payer.id != subscription.user_id {
+            return Err(ApiError::Authorization {
+                message: "You can only pay for your own shares".to_string(),
+            });
+        }
+        if amount > subscription.outstanding() {
+            return Err(ApiError::Validation {
+                message: format!(
+                    "Payment of KES {} exceeds the KES {} still owed on the subscription",
+                    amount,
+                    subscription.outstanding()
+                ),
+            });
+        }
+
+        let user_id = subscription.user_id;
+        let mut postings = vec![PostingRequest::credit(shares_receivable(user_id), amount)];
+        // Paying the last of the subscription turns it into share capital
+        if amount == subscription.outstanding() {
+            let value = subscription.total_value().unwrap_or(Decimal::ZERO);
+            postings.push(PostingRequest::debit(unpaid_share_capital(user_id), value));
+            postings.push(PostingRequest::credit(
+                share_capital_account(user_id),
+                value,
+            ));
+        }
+
+        let treasury_kes = AccountKey::sacco(AccountPurpose::Treasury, LedgerCurrency::Kes);
+        if let Some(wallet_id) = request.wallet_id {
+            let wallet = self.wallets.get_wallet(wallet_id).await?;
+            if wallet.user_id != user_id {
+                return Err(ApiError::Validation {
+                    message: "Shares must be paid for from the member's own wallet".to_string(),
+                });
+            }
+            // Shares are priced in KES, so the wallet pays their value in sats
+            let sats = self
+                .fx
+                .current_rate()
+                .await?
+                .kes_to_sats(amount)
+                .to_u64()
+                .filter(|sats| *sats > 0)
+                .ok_or_else(|| ApiError::Validation {
+                    message: "The amount is too small to pay from a wallet".to_string(),
+                })?;
+            postings.push(PostingRequest::credit(
+                AccountKey::sacco(AccountPurpose::Treasury, LedgerCurrency::Sats),
+                sats,
+            ));
+            postings.push(PostingRequest::debit(treasury_kes, amount));
+            // The member already owes this, so it is not held to the withdrawal limits
+            self.wallets
+                .post(
+                    wallet_id,
+                    -signed_sats(sats)?,
+                    TransactionType::SharePayment,
+                    postings,
+                )
+                .await?;
+        } else {
+            // Received outside the wallets, e.g. cash or M-Pesa paid into the SACCO
+            postings.push(PostingRequest::debit(treasury_kes, amount));
+            self.ledger
+                .post_journal_entry(JournalEntryRequest {
+                    description: format!("Payment for share subscription {}", subscription.id),
+                    reference_id: Some(subscription.id),
+                    postings,
+                    created_by: Some(payer.id),
+                })
+                .await?;
+        }
+
+        subscription.paid += amount;
+        let paid = subscription.clone();
+        self.store.save_or_log(&*state, "share subscriptions");
+        Ok(paid)
+    }
+
+    async fn get_member_subscriptions(&self, user_id: Uuid) -> ApiResult<Vec<ShareSubscription>> {
+        let state = self.state.read().await;
+        let mut subscriptions: Vec<ShareSubscription> = state
+            .subscriptions
+            .iter()
+            .filter(|sub| sub.user_id == user_id)
+            .cloned()
+            .collect();
+        subscriptions.sort_by_key(|item| std::cmp::Reverse(item.created_at));
+        Ok(subscriptions)
+    }
+
+    async fn get_offer_subscriptions(
+        &self,
+        offer_id: Uuid,
+        pagination: PaginationQuery,
+    ) -> ApiResult<PaginatedResponse<ShareSubscription>> {
+        let state = self.state.read().await;
+        if !state.offers.contains_key(&offer_id) {
+            return Err(offer_not_found(offer_id));
+        }
+
+        let mut subscriptions: Vec<ShareSubscription> = state
+            .subscriptions
+            .iter()
+            .filter(|sub| sub.offer_id == offer_id)
+            .cloned()
+            .collect();
+        subscriptions.sort_by_key(|item| std::cmp::Reverse(item.created_at));
+        Ok(PaginatedResponse::paginate(subscriptions, &pagination))
+    }
+
+    async fn close_share_offer(&self, offer_id: Uuid) -> ApiResult<ShareOffer> {
+        let mut state = self.state.write().await;
+        let offer = state
+            .offers
+            .get_mut(&offer_id)
+            .ok_or_else(|| offer_not_found(offer_id))?;
+        refresh_status(offer);
+
+        if matches!(
+            offer.status,
+            ShareOfferStatus::Closed | ShareOfferStatus::Expired
+        ) {
+            return Err(ApiError::Conflict {
+                message: format!("Share offer is already {}", offer.status.as_str()),
+            });
+        }
+
+        let previous = offer.clone();
+        offer.status = ShareOfferStatus::Closed;
+        offer.updated_at = Utc::now();
+        let closed = offer.clone();
+        self.store.save_or_undo(&mut *state, |state| {
+            state.offers.insert(offer_id, previous);
+        })?;
+        Ok(closed)
+    }
+
+    async fn request_share_transfer(
+        &self,
+        request: TransferSharesRequest,
+        maker: &User,
+    ) -> ApiResult<ShareTransfer> {
+        if maker.id != request.from_user_id && !maker.is_admin() {
+            return Err(ApiError::Authorization {
+                message: "You can only transfer shares you own".to_string(),
+            });
+        }
+        if request.quantity == 0 {
+            return Err(ApiError::Validation {
+                message: "Transfer quantity must be greater than zero".to_string(),
+            });
+        }
+        if request.from_user_id == request.to_user_id {
+            return Err(ApiError::Validation {
+                message: "Shares cannot be transferred to the same member".to_string(),
+            });
+        }
+        if request
+            .price_per_share
+            .is_some_and(|price| price < Decimal::ZERO)
+        {
+            return Err(ApiError::Validation {
+                message: "Price per share cannot be negative".to_string(),
+            });
+        }
+
+        // Shares still being paid for stay with the subscriber
+        let mut state = self.state.write().await;
+        let transferable = state
+            .paid_up_shares(request.from_user_id)
+            .saturating_sub(state.pending_outgoing(request.from_user_id));
+        if request.quantity > transferable {
+            return Err(ApiError::Validation {
+                message: format!(
+                    "Member holds {} transferable shares but {} were requested",
+                    transferable, request.quantity
+                ),
+            });
+        }
+
+        let transfer = ShareTransfer {
+            id: Uuid::new_v4(),
+            from_user_id: request.from_user_id,
+            to_user_id: request.to_user_id,
+            quantity: request.quantity,
+            price_per_share: request.price_per_share,
+            note: request.note.filter(|n| !n.trim().is_empty()),
+            status: ShareTransferStatus::Pending,
+            requested_by: maker.id,
+            requested_at: Utc::now(),
+            reviewed_by: None,
+            reviewed_at: None,
+            rejection_reason: None,
+        };
+        state.transfers.insert(transfer.id, transfer.clone());
+        self.store.save_or_undo(&mut *state, |state| {
+            state.transfers.remove(&transfer.id);
+        })?;
+
+        Ok(transfer)
+    }
+
+    async fn approve_share_transfer(
+        &self,
+        transfer_id: Uuid,
+        reviewer: &User,
+    ) -> ApiResult<ShareTransfer> {
+        let mut state = self.state.write().await;
+        let transfer = pending_transfer(&state, transfer_id)?;
+        check_reviewer(&transfer, reviewer)?;
+
+        // Paid-up holdings can only have grown since the request, but guard anyway
+        let held = state.paid_up_shares(transfer.from_user_id);
+        if held < transfer.quantity {
+            return Err(ApiError::Conflict {
+                message: "Sender no longer holds enough paid-up shares for this transfer"
+                    .to_string(),
+            });
+        }
+
+        // Capital moves at the seller's book value; the agreed price is settled between
+        // members. Only paid-up shares move, so the seller's receivable stays with the
+        // unpaid shares it is owed on.
+        let seller_capital = self
+            .ledger
+            .get_account_balance(share_capital_account(transfer.from_user_id))
+            .await?;
+        let book_value = if transfer.quantity == held {
+            seller_capital
+        } else {
+            seller_capital * Decimal::from(transfer.quantity) / Decimal::from(held)
+        };
+        self.post_share_capital(
+            format!("Transfer of {} shares", transfer.quantity),
+            transfer.id,
+            share_capital_account(transfer.from_user_id),
+            share_capital_account(transfer.to_user_id),
+            book_value,
+        )
+        .await?;
+
+        state.post(
+            transfer.from_user_id,
+            ShareLedgerEntryKind::TransferOut,
+            transfer.quantity,
+            transfer.id,
+            Some(transfer.to_user_id),
+            transfer.price_per_share,
+        );
+        state.post(
+            transfer.to_user_id,
+            ShareLedgerEntryKind::TransferIn,
+            transfer.quantity,
+            transfer.id,
+            Some(transfer.from_user_id),
+            transfer.price_per_share,
+        );
+
+        let transfer = state
+            .transfers
+            .get_mut(&transfer_id)
+            .ok_or_else(|| transfer_not_found(transfer_id))?;
+        transfer.status = ShareTransferStatus::Approved;
+        transfer.reviewed_by = Some(reviewer.id);
+        transfer.reviewed_at = Some(Utc::now());
+        let approved = transfer.clone();
+        self.store.save_or_log(&*state, "share transfers");
+        Ok(approved)
+    }
+
+    async fn reject_share_transfer(
+        &self,
+        transfer_id: Uuid,
+        reviewer: &User,
+        reason: Option<String>,
+    ) -> ApiResult<ShareTransfer> {
+        let mut state = self.state.write().await;
+        let pending = pending_transfer(&state, transfer_id)?;
+        check_reviewer(&pending, reviewer)?;
+
+        let transfer = state
+            .transfers
+            .get_mut(&transfer_id)
+            .ok_or_else(|| transfer_not_found(transfer_id))?;
+        transfer.status = ShareTransferStatus::Rejected;
+        transfer.reviewed_by = Some(reviewer.id);
+        transfer.reviewed_at = Some(Utc::now());
+        transfer.rejection_reason = reason.filter(|r| !r.trim().is_empty());
+        let rejected = transfer.clone();
+        self.store.save_or_undo(&mut *state, |state| {
+            state.transfers.insert(transfer_id, pending);
+        })?;
+        Ok(rejected)
+    }
+
+    async fn get_share_transfers(
+        &self,
+        filter: ShareTransferFilter,
+        pagination: PaginationQuery,
+    ) -> ApiResult<PaginatedResponse<ShareTransfer>> {
+        let state = self.state.read().await;
+        let mut transfers: Vec<ShareTransfer> = state
+            .transfers
+            .values()
+            .filter(|t| {
+                filter
+                    .user_id
+                    .is_none_or(|id| t.from_user_id == id || t.to_user_id == id)
+            })
+            .filter(|t| filter.status.as_ref().is_none_or(|s| &t.status == s))
+            .cloned()
+            .collect();
+        transfers.sort_by_key(|item| std::cmp::Reverse(item.requested_at));
+        Ok(PaginatedResponse::paginate(transfers, &pagination))
+    }
+
+    async fn get_member_share_ledger(&self, user_id: Uuid) -> ApiResult<Vec<ShareLedgerEntry>> {
+        let state = self.state.read().await;
+        Ok(state
+            .ledger
+            .iter()
+            .filter(|entry| entry.user_id == user_id)
+            .cloned()
+            .collect())
+    }
+}
+
+/// Enforce maker-checker: reviewers must be admins other than whoever requested
+/// the transfer or either member in it
+fn check_reviewer(transfer: &ShareTransfer, reviewer: &User) -> ApiResult<()> {
+    if !reviewer.is_admin() {
+        return Err(ApiError::Authorization {
+            message: "Only admins can review share transfers".to_string(),
+        });
+    }
+    if [
+        transfer.requested_by,
+        transfer.from_user_id,
+        transfer.to_user_id,
+    ]
+    .contains(&reviewer.id)
+    {
+        return Err(ApiError::Authorization {
+            message: "Share transfers must be reviewed by someone other than the maker or \
+                      either member"
+                .to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Look up a transfer that is still awaiting review
+fn pending_transfer(state: &SharesState, transfer_id: Uuid) -> ApiResult<ShareTransfer> {
+    let transfer = state
+        .transfers
+        .get(&transfer_id)
+        .ok_or_else(|| transfer_not_found(transfer_id))?;
+    if transfer.status != ShareTransferStatus::Pending {
+        return Err(ApiError::Conflict {
+            message: format!("Share transfer is already {}", transfer.status.as_str()),
+        });
+    }
+    Ok(transfer.clone())
+}
+
+fn subscription_not_found(subscription_id: Uuid) -> ApiError {
+    ApiError::NotFound {
+        resource: format!("Share subscription {}", subscription_id),
+    }
+}
+
+fn transfer_not_found(transfer_id: Uuid) -> ApiError {
+    ApiError::NotFound {
+        resource: format!("Share transfer {}", transfer_id),
+    }
+}
+
+/// Apply expiry and full-subscription transitions to a stored offer
+fn refresh_status(offer: &mut ShareOffer) {
+    offer.status = offer.effective_status(Utc::now());
+}
+
+fn offer_not_found(offer_id: Uuid) -> ApiError {
+    ApiError::NotFound {
+        resource: format!("Share offer {}", offer_id),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::{
+        traits::wallets::{CreateWalletRequest, WalletType},
+        types::Role,
+    };
+    use chrono::Duration;
+
+    fn shares_api(ledger: RustLedgerApi) -> RustSharesApi {
+        RustSharesApi::new(
+            ledger.clone(),
+            RustWalletsApi::new(ledger),
+            RustFxApi::new(Vec::new()),
+        )
+    }
+
+    fn offer_request(quantity: u64) -> CreateShareOfferRequest {
+        CreateShareOfferRequest {
+            title: "2026 Share Issue".to_string(),
+            description: None,
+            total_quantity: quantity,
+            price_per_share: Decimal::from(1000),
+            available_from: None,
+            expires_at: Utc::now() + Duration::days(30),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_subscription_updates_progress() {
+        let shares = shares_api(RustLedgerApi::new());
+        let offer = shares.create_share_offer(offer_request(100)).await.unwrap();
+
+        let subscription = shares
+            .subscribe_shares(SubscribeSharesRequest {
+                offer_id: offer.id,
+                user_id: Uuid::new_v4(),
+                quantity: 40,
+            })
+            .await
+            .unwrap();
+        assert_eq!(subscription.total_value(), Some(Decimal::from(40_000)));
+
+        let offer = shares.get_share_offer(offer.id).await.unwrap();
+        assert_eq!(offer.available_quantity(), 60);
+        assert_eq!(offer.progress(), 0.4);
+    }
+
+    #[tokio::test]
+    async fn test_subscription_quantity_validation() {
+        let shares = shares_api(RustLedgerApi::new());
+        let offer = shares.create_share_offer(offer_request(10)).await.unwrap();
+        let user_id = Uuid::new_v4();
+
+        let zero = shares
+            .subscribe_shares(SubscribeSharesRequest {
+                offer_id: offer.id,
+                user_id,
+                quantity: 0,
+            })
+            .await;
+        assert!(matches!(zero, Err(ApiError::Validation { .. })));
+
+        let too_many = shares
+            .subscribe_shares(SubscribeSharesRequest {
+                offer_id: offer.id,
+                user_id,
+                quantity: 11,
+            })
+            .await;
+        assert!(matches!(too_many, Err(ApiError::Validation { .. })));
+
+        shares
+            .subscribe_shares(SubscribeSharesRequest {
+                offer_id: offer.id,
+                user_id,
+                quantity: 10,
+            })
+            .await
+            .unwrap();
+        let offer = shares.get_share_offer(offer.id).await.unwrap();
+        assert_eq!(offer.status, ShareOfferStatus::FullySubscribed);
+    }
+
+    #[tokio::test]
+    async fn test_closed_and_expired_offers_reject_subscriptions() {
+        let shares = shares_api(RustLedgerApi::new());
+        let offer = shares.create_share_offer(offer_request(10)).await.unwrap();
+        shares.close_share_offer(offer.id).await.unwrap();
+
+        let result = shares
+            .subscribe_shares(SubscribeSharesRequest {
+                offer_id: offer.id,
+                user_id: Uuid::new_v4(),
+                quantity: 1,
+            })
+            .await;
+        assert!(matches!(result, Err(ApiError::Conflict { .. })));
+
+        let expiring = shares.create_share_offer(offer_request(10)).await.unwrap();
+        shares
+            .state
+            .write()
+            .await
+            .offers
+            .get_mut(&expiring.id)
+            .unwrap()
+            .expires_at = Utc::now() - Duration::seconds(1);
+
+        let expired = shares.get_share_offer(expiring.id).await.unwrap();
+        assert_eq!(expired.status, ShareOfferStatus::Expired);
+        assert!(shares.close_share_offer(expiring.id).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_offer_validation() {
+        let shares = shares_api(RustLedgerApi::new());
+
+        let mut request = offer_request(0);
+        assert!(shares.create_share_offer(request.clone()).await.is_err());
+
+        request.total_quantity = 10;
+        request.expires_at = Utc::now() - Duration::days(1);
+        assert!(shares.create_share_offer(request).await.is_err());
+    }
+
+    fn user_with_roles(roles: Vec<Role>) -> User {
+        User {
+            id: Uuid::new_v4(),
+            phone: None,
+            nostr: None,
+            profile: None,
+            roles,
+            verified: true,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn as_member(user_id: Uuid) -> User {
+        User {
+            id: user_id,
+            ..user_with_roles(vec![Role::Member])
+        }
+    }
+
+    async fn member_with_shares(shares: &RustSharesApi, quantity: u64) -> Uuid {
+        let offer = shares.create_share_offer(offer_request(100)).await.unwrap();
+        let user_id = Uuid::new_v4();
+        let subscription = shares
+            .subscribe_shares(SubscribeSharesRequest {
+                offer_id: offer.id,
+                user_id,
+                quantity,
+            })
+            .await
+            .unwrap();
+        shares
+            .pay_for_shares(
+                PaySharesRequest {
+                    subscription_id: subscription.id,
+                    wallet_id: None,
+                    amount: subscription.outstanding(),
+                },
+                &user_with_roles(vec![Role::Admin]),
+            )
+            .await
+            .unwrap();
+        user_id
+    }
+
+    fn transfer_request(from: Uuid, to: Uuid, quantity: u64) -> TransferSharesRequest {
+        TransferSharesRequest {
+            from_user_id: from,
+            to_user_id: to,
+            quantity,
+            price_per_share: Some(Decimal::from(1200)),
+            note: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_transfer_ownership_checks() {
+        let shares = shares_api(RustLedgerApi::new());
+        let seller = member_with_shares(&shares, 10).await;
+        let buyer = Uuid::new_v4();
+
+        let too_many = shares
+            .request_share_transfer(transfer_request(seller, buyer, 11), &as_member(seller))
+            .await;
+        assert!(matches!(too_many, Err(ApiError::Validation { .. })));
+
+        shares
+            .request_share_transfer(transfer_request(seller, buyer, 6), &as_member(seller))
+            .await
+            .unwrap();
+
+        // Pending transfers reserve the shares they cover
+        let over_committed = shares
+            .request_share_transfer(transfer_request(seller, buyer, 5), &as_member(seller))
+            .await;
+        assert!(matches!(over_committed, Err(ApiError::Validation { .. })));
+
+        let from_non_holder = shares
+            .request_share_transfer(transfer_request(buyer, seller, 1), &as_member(buyer))
+            .await;
+        assert!(from_non_holder.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_transfer_approval_requires_admin_and_updates_ledger() {
+        let shares = shares_api(RustLedgerApi::new());
+        let seller = member_with_shares(&shares, 10).await;
+        let buyer = Uuid::new_v4();
+        let transfer = shares
+            .request_share_transfer(transfer_request(seller, buyer, 4), &as_member(seller))
+            .await
+            .unwrap();
+
+        let member = user_with_roles(vec![Role::Member]);
+        let denied = shares.approve_share_transfer(transfer.id, &member).await;
+        assert!(matches!(denied, Err(ApiError::Authorization { .. })));
+
+        let admin = user_with_roles(vec![Role::Admin]);
+        let approved = shares
+            .approve_share_transfer(transfer.id, &admin)
+            .await
+            .unwrap();
+        assert_eq!(approved.status, ShareTransferStatus::Approved);
+        assert_eq!(approved.reviewed_by, Some(admin.id));
+
+        let seller_ledger = shares.get_member_share_ledger(seller).await.unwrap();
+        assert_eq!(seller_ledger.len(), 2);
+        assert_eq!(seller_ledger[1].kind, ShareLedgerEntryKind::TransferOut);
+        assert_eq!(seller_ledger[1].quantity, -4);
+        assert_eq!(seller_ledger[1].balance_after, 6);
+
+        let buyer_ledger = shares.get_member_share_ledger(buyer).await.unwrap();
+        assert_eq!(buyer_ledger.len(), 1);
+        assert_eq!(buyer_ledger[0].balance_after, 4);
+        assert_eq!(buyer_ledger[0].counterparty_id, Some(seller));
+
+        let again = shares.approve_share_transfer(transfer.id, &admin).await;
+        assert!(matches!(again, Err(ApiError::Conflict { .. })));
+
+        // An admin who records a transfer can't also approve it
+        let stranger = shares
+            .request_share_transfer(transfer_request(seller, buyer, 1), &member)
+            .await;
+        assert!(matches!(stranger, Err(ApiError::Authorization { .. })));
+        let transfer = shares
+            .request_share_transfer(transfer_request(seller, buyer, 1), &admin)
+            .await
+            .unwrap();
+        let own = shares.approve_share_transfer(transfer.id, &admin).await;
+        assert!(matches!(own, Err(ApiError::Authorization { .. })));
+        let seller_review = shares
+            .approve_share_transfer(
+                transfer.id,
+                &User {
+                    id: seller,
+                    ..user_with_roles(vec![Role::Admin])
+                },
+            )
+            .await;
+        assert!(matches!(seller_review, Err(ApiError::Authorization { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_share_capital_is_posted_to_ledger() {
+        let ledger = RustLedgerApi::new();
+        let shares = shares_api(ledger.clone());
+        let seller = member_with_shares(&shares, 10).await;
+        let buyer = Uuid::new_v4();
+        assert_eq!(
+            ledger
+                .get_account_balance(share_capital_account(seller))
+                .await
+                .unwrap(),
+            Decimal::from(10_000)
+        );
+
+        let transfer = shares
+            .request_share_transfer(transfer_request(seller, buyer, 4), &as_member(seller))
+            .await
+            .unwrap();
+        shares
+            .approve_share_transfer(transfer.id, &user_with_roles(vec![Role::Admin]))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            ledger
+                .get_account_balance(share_capital_account(seller))
+                .await
+                .unwrap(),
+            Decimal::from(6_000)
+        );
+        assert_eq!(
+            ledger
+                .get_account_balance(share_capital_account(buyer))
+                .await
+                .unwrap(),
+            Decimal::from(4_000)
+        );
+        assert_eq!(
+            ledger
+                .get_account_balance(shares_receivable(seller))
+                .await
+                .unwrap(),
+            Decimal::ZERO
+        );
+    }
+
+    #[tokio::test]
+    async fn test_share_payments_settle_the_receivable() {
+        let ledger = RustLedgerApi::new();
+        let wallets = RustWalletsApi::new(ledger.clone());
+        let fx = RustFxApi::new(Vec::new());
+        // 10 sats to the shilling
+        fx.record_rate(Decimal::from(10_000_000), "manual".to_string())
+            .await
+            .unwrap();
+        let shares = RustSharesApi::new(ledger.clone(), wallets.clone(), fx);
+
+        let member = Uuid::new_v4();
+        let wallet = wallets
+            .create_wallet(CreateWalletRequest {
+                user_id: member,
+                name: "Savings".to_string(),
+                wallet_type: WalletType::Fedimint,
+            })
+            .await
+            .unwrap();
+        wallets
+            .credit(wallet.id, 200_000, TransactionType::Deposit)
+            .await
+            .unwrap();
+        let offer = shares.create_share_offer(offer_request(100)).await.unwrap();
+        let subscription = shares
+            .subscribe_shares(SubscribeSharesRequest {
+                offer_id: offer.id,
+                user_id: member,
+                quantity: 10,
+            })
+            .await
+            .unwrap();
+        let balance = |key| {
+            let ledger = ledger.clone();
+            async move { ledger.get_account_balance(key).await.unwrap() }
+        };
+        assert_eq!(
+            balance(shares_receivable(member)).await,
+            Decimal::from(10_000)
+        );
+        assert_eq!(
+            balance(unpaid_share_capital(member)).await,
+            Decimal::from(10_000)
+        );
+        assert_eq!(balance(share_capital_account(member)).await, Decimal::ZERO);
+
+        // Unpaid shares can't be transferred and don't back loans
+        assert_eq!(shares.paid_up_shares(member).await, 0);
+        let transfer = shares
+            .request_share_transfer(
+                transfer_request(member, Uuid::new_v4(), 1),
+                &as_member(member),
+            )
+            .await;
+        assert!(matches!(transfer, Err(ApiError::Validation { .. })));
+
+        let pay = |wallet_id, amount: u64| PaySharesRequest {
+            subscription_id: subscription.id,
+            wallet_id,
+            amount: Decimal::from(amount),
+        };
+        let partly = shares
+            .pay_for_shares(pay(Some(wallet.id), 4_000), &as_member(member))
+            .await
+            .unwrap();
+        assert_eq!(partly.outstanding(), Decimal::from(6_000));
+        assert_eq!(
+            wallets.get_wallet(wallet.id).await.unwrap().balance,
+            160_000
+        );
+        assert_eq!(
+            balance(shares_receivable(member)).await,
+            Decimal::from(6_000)
+        );
+        assert_eq!(balance(share_capital_account(member)).await, Decimal::ZERO);
+        assert_eq!(shares.paid_up_shares(member).await, 0);
+
+        let stranger = as_member(Uuid::new_v4());
+        let not_theirs = shares
+            .pay_for_shares(pay(Some(wallet.id), 1_000), &stranger)
+            .await;
+        assert!(matches!(not_theirs, Err(ApiError::Authorization { .. })));
+        let cash = shares
+            .pay_for_shares(pay(None, 1_000), &as_member(member))
+            .await;
+        assert!(matches!(cash, Err(ApiError::Authorization { .. })));
+        let too_much = shares
+            .pay_for_shares(pay(Some(wallet.id), 6_001), &as_member(member))
+            .await;
+        assert!(matches!(too_much, Err(ApiError::Validation { .. })));
+
+        let paid = shares
+            .pay_for_shares(pay(Some(wallet.id), 6_000), &as_member(member))
+            .await
+            .unwrap();
+        assert!(paid.is_paid_up());
+        assert_eq!(
+            wallets.get_wallet(wallet.id).await.unwrap().balance,
+            100_000
+        );
+        assert_eq!(balance(shares_receivable(member)).await, Decimal::ZERO);
+        assert_eq!(balance(unpaid_share_capital(member)).await, Decimal::ZERO);
+        assert_eq!(
+            balance(share_capital_account(member)).await,
+            Decimal::from(10_000)
+        );
+        assert_eq!(shares.paid_up_shares(member).await, 10);
+
+        let subscriptions = shares.get_member_subscriptions(member).await.unwrap();
+        assert_eq!(subscriptions.len(), 1);
+        assert_eq!(subscriptions[0].paid, Decimal::from(10_000));
+        let again = shares
+            .pay_for_shares(pay(Some(wallet.id), 1), &as_member(member))
+            .await;
+        assert!(matches!(again, Err(ApiError::Validation { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_rejected_transfer_releases_shares() {
+        let shares = shares_api(RustLedgerApi::new());
+        let seller = member_with_shares(&shares, 5).await;
+        let buyer = Uuid::new_v4();
+        let admin = user_with_roles(vec![Role::SuperAdmin]);
+
+        let transfer = shares
+            .request_share_transfer(transfer_request(seller, buyer, 5), &as_member(seller))
+            .await
+            .unwrap();
+        let rejected = shares
+            .reject_share_transfer(transfer.id, &admin, Some("Price not agreed".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(rejected.status, ShareTransferStatus::Rejected);
+        assert!(shares
+            .get_member_share_ledger(buyer)
+            .await
+            .unwrap()
+            .is_empty());
+
+        // The full holding is transferable again once the request is rejected
+        shares
+            .request_share_transfer(transfer_request(seller, buyer, 5), &as_member(seller))
+            .await
+            .unwrap();
+
+        let pending = shares
+            .get_share_transfers(
+                ShareTransferFilter {
+                    user_id: Some(buyer),
+                    status: Some(ShareTransferStatus::Pending),
+                },
+                PaginationQuery::default(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(pending.data.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_offers_and_subscriptions_survive_restart() {
+        let dir = std::env::temp_dir().join(format!("bitsacco-shares-{}", Uuid::new_v4()));
+        let path = dir.join("shares.json");
+        let user_id = Uuid::new_v4();
+
+        let shares = shares_api(RustLedgerApi::new())
+            .with_store(JsonStore::at(&path))
+            .unwrap();
+        let offer = shares.create_share_offer(offer_request(100)).await.unwrap();
+        shares
+            .subscribe_shares(SubscribeSharesRequest {
+                offer_id: offer.id,
+                user_id,
+                quantity: 25,
+            })
+            .await
+            .unwrap();
+
+        let restarted = shares_api(RustLedgerApi::new())
+            .with_store(JsonStore::at(&path))
+            .unwrap();
+        let reloaded = restarted.get_share_offer(offer.id).await.unwrap();
+        assert_eq!(reloaded.available_quantity(), 75);
+        let ledger = restarted.get_member_share_ledger(user_id).await.unwrap();
+        assert_eq!(ledger.last().map(|entry| entry.balance_after), Some(25));
+
+        // An offer that can't be saved isn't created
+        std::fs::remove_dir_all(&dir).unwrap();
+        std::fs::write(&dir, b"").unwrap();
+        assert!(restarted
+            .create_share_offer(offer_request(10))
+            .await
+            .is_err());
+        let offers = restarted
+            .get_share_offers(PaginationQuery {
+                page: Some(1),
+                limit: Some(10),
+            })
+            .await
+            .unwrap();
+        assert_eq!(offers.data.len(), 1);
+        std::fs::remove_file(&dir).unwrap();
+    }
+}
@@ -0,0 +1,201 @@
+// Member statements for the Rust backend
+// Every account a member holds in the ledger (wallets, share capital, loans)
+// becomes a statement section with opening balance, movements and closing balance
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use super::{RustLedgerApi, RustWalletsApi};
+use crate::api::{
+    errors::{ApiError, ApiResult},
+    traits::{
+        ledger::{AccountOwner, AccountPurpose, LedgerApi},
+        statements::{MemberStatement, StatementSection, StatementsApi},
+        wallets::WalletsApi,
+    },
+};
+
+#[derive(Clone)]
+pub struct RustStatementsApi {
+    ledger: RustLedgerApi,
+    wallets: RustWalletsApi,
+}
+
+impl RustStatementsApi {
+    pub fn new(ledger: RustLedgerApi, wallets: RustWalletsApi) -> Self {
+        Self { ledger, wallets }
+    }
+}
+
+#[async_trait]
+impl StatementsApi for RustStatementsApi {
+    async fn get_member_statement(
+        &self,
+        user_id: Uuid,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> ApiResult<MemberStatement> {
+        if from >= to {
+            return Err(ApiError::Validation {
+                message: "Statement period must end after it starts".to_string(),
+            });
+        }
+
+        let mut accounts: Vec<_> = self
+            .ledger
+            .get_accounts(Some(AccountOwner::Member(user_id)))
+            .await?
+            .into_iter()
+            .map(|balance| balance.account)
+            .collect();
+        // Wallets first, then share capital, then loans
+        accounts.sort_by_key(|account| match account.key.purpose {
+            AccountPurpose::Wallet(_) => (0, account.created_at),
+            AccountPurpose::ShareCapital => (1, account.created_at),
+            _ => (2, account.created_at),
+        });
+
+        let mut sections = Vec::with_capacity(accounts.len());
+        for account in accounts {
+            let title = match account.key.purpose {
+                AccountPurpose::Wallet(wallet_id) => match self.wallets.get_wallet(wallet_id).await
+                {
+                    Ok(wallet) => format!("Wallet: {}", wallet.name),
+                    Err(_) => format!("Wallet {}", wallet_id),
+                },
+                AccountPurpose::LoansReceivable => "Loans outstanding".to_string(),
+                AccountPurpose::SharesReceivable => "Share subscriptions unpaid".to_string(),
+                purpose => purpose.label(),
+            };
+            let movements = self.ledger.get_account_movements(account.key).await?;
+            sections.push(StatementSection::from_movements(
+                title,
+                account.key.currency,
+                &movements,
+                from,
+                to,
+            ));
+        }
+
+        Ok(MemberStatement {
+            user_id,
+            from,
+            to,
+            sections,
+            generated_at: Utc::now(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::{
+        traits::{
+            ledger::{AccountKey, JournalEntryRequest, LedgerCurrency, PostingRequest},
+            wallets::{CreateWalletRequest, TransactionType, WalletType},
+        },
+        types::{common::Role, User},
+    };
+    use chrono::Duration;
+    use rust_decimal::Decimal;
+
+    fn user(id: Uuid) -> User {
+        User {
+            id,
+            phone: None,
+            nostr: None,
+            profile: None,
+            roles: vec![Role::Member],
+            verified: true,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_statement_sections_and_balances() {
+        let ledger = RustLedgerApi::new();
+        let wallets = RustWalletsApi::new(ledger.clone());
+        let statements = RustStatementsApi::new(ledger.clone(), wallets.clone());
+
+        let member = Uuid::new_v4();
+        let wallet = wallets
+            .create_wallet(CreateWalletRequest {
+                user_id: member,
+                name: "Savings".to_string(),
+                wallet_type: WalletType::Fedimint,
+            })
+            .await
+            .unwrap();
+        wallets
+            .credit(wallet.id, 10_000, TransactionType::Deposit)
+            .await
+            .unwrap();
+        ledger
+            .post_journal_entry(JournalEntryRequest {
+                description: "Share subscription".to_string(),
+                reference_id: None,
+                postings: vec![
+                    PostingRequest::debit(
+                        AccountKey::sacco(AccountPurpose::Treasury, LedgerCurrency::Kes),
+                        Decimal::from(2_000),
+                    ),
+                    PostingRequest::credit(
+                        AccountKey::member(
+                            member,
+                            AccountPurpose::ShareCapital,
+                            LedgerCurrency::Kes,
+                        ),
+                        Decimal::from(2_000),
+                    ),
+                ],
+                created_by: None,
+            })
+            .await
+            .unwrap();
+
+        // Everything so far happened before the second period starts
+        let opened = Utc::now();
+        wallets
+            .debit(wallet.id, 4_000, TransactionType::Withdrawal, &user(member))
+            .await
+            .unwrap();
+        let to = Utc::now() + Duration::days(1);
+
+        let full = statements
+            .get_member_statement(member, opened - Duration::days(1), to)
+            .await
+            .unwrap();
+        assert_eq!(full.sections.len(), 2);
+        assert_eq!(full.sections[0].title, "Wallet: Savings");
+        assert_eq!(full.sections[0].opening_balance, Decimal::ZERO);
+        assert_eq!(full.sections[0].lines.len(), 2);
+        assert_eq!(full.sections[0].closing_balance, Decimal::from(6_000));
+        assert_eq!(full.sections[1].currency, LedgerCurrency::Kes);
+        assert_eq!(full.sections[1].closing_balance, Decimal::from(2_000));
+
+        let later = statements
+            .get_member_statement(member, opened, to)
+            .await
+            .unwrap();
+        let wallet_section = &later.sections[0];
+        assert_eq!(wallet_section.opening_balance, Decimal::from(10_000));
+        assert_eq!(wallet_section.lines.len(), 1);
+        assert_eq!(wallet_section.total_out(), Decimal::from(4_000));
+        assert_eq!(wallet_section.closing_balance, Decimal::from(6_000));
+        assert!(later.sections[1].lines.is_empty());
+        assert_eq!(later.sections[1].closing_balance, Decimal::from(2_000));
+
+        let csv = later.to_csv();
+        assert!(csv.starts_with("account,currency,date,description,reference,amount,balance\n"));
+        assert!(csv.contains("Wallet: Savings,SATS,"));
+        assert!(csv.contains("Closing balance,,,6000"));
+
+        assert!(matches!(
+            statements.get_member_statement(member, to, opened).await,
+            Err(ApiError::Validation { .. })
+        ));
+    }
+}
@@ -0,0 +1,241 @@
+// State the Rust backend keeps on disk across restarts
+// Each subsystem keeps a JSON document under RUST_DATA_DIR, replaced whole on each
+// change. The ledger journal and wallet transactions only grow, so they are kept
+// as JSON lines instead and each change appends a record.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::api::errors::{ApiError, ApiResult};
+
+/// Where documents are kept when RUST_DATA_DIR is unset
+const DEFAULT_DATA_DIR: &str = "data";
+
+/// One JSON document on disk, or nowhere for stores that don't outlive the process
+#[derive(Debug, Clone, Default)]
+pub struct JsonStore {
+    path: Option<PathBuf>,
+}
+
+impl JsonStore {
+    /// Keeps nothing; loads always come back empty
+    pub fn in_memory() -> Self {
+        Self::default()
+    }
+
+    pub fn at(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: Some(path.into()),
+        }
+    }
+
+    /// `name` under RUST_DATA_DIR
+    pub fn from_env(name: &str) -> Self {
+        Self::at(data_path(name))
+    }
+
+    /// The stored document, or the default if nothing was saved yet
+    pub fn load<T: DeserializeOwned + Default>(&self) -> ApiResult<T> {
+        let Some(path) = &self.path else {
+            return Ok(T::default());
+        };
+        match std::fs::read(path) {
+            Ok(bytes) => serde_json::from_slice(&bytes).map_err(|e| ApiError::Serialization {
+                message: format!("Could not read {}: {}", path.display(), e),
+            }),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(T::default()),
+            Err(e) => Err(io_error(path, e)),
+        }
+    }
+
+    /// Replace the stored document. Written beside the old one and renamed over
+    /// it, so a crash mid-write leaves the previous version intact.
+    pub fn save<T: Serialize>(&self, value: &T) -> ApiResult<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir).map_err(|e| io_error(dir, e))?;
+        }
+        let bytes = serde_json::to_vec_pretty(value).map_err(|e| ApiError::Serialization {
+            message: e.to_string(),
+        })?;
+        let staged = path.with_extension("json.tmp");
+        std::fs::write(&staged, bytes).map_err(|e| io_error(&staged, e))?;
+        std::fs::rename(&staged, path).map_err(|e| io_error(path, e))
+    }
+
+    /// Save after a change to `value`, reverting it with `undo` if the save fails
+    pub fn save_or_undo<T: Serialize>(
+        &self,
+        value: &mut T,
+        undo: impl FnOnce(&mut T),
+    ) -> ApiResult<()> {
+        self.save(value).inspect_err(|_| undo(value))
+    }
+
+    /// Save after a change that has already taken effect, e.g. funds that have
+    /// moved on the ledger. It stands either way, so a failure is logged rather
+    /// than returned; the next save that succeeds writes it out.
+    pub fn save_or_log<T: Serialize>(&self, value: &T, what: &str) {
+        if let Err(e) = self.save(value) {
+            tracing::error!("Could not save {}: {}", what, e);
+        }
+    }
+}
+
+/// Records appended one JSON line at a time, or nowhere for logs that don't
+/// outlive the process. Readers replay the records in order.
+#[derive(Debug, Clone, Default)]
+pub struct JsonLog {
+    path: Option<PathBuf>,
+}
+
+impl JsonLog {
+    /// Keeps nothing; loads always come back empty
+    pub fn in_memory() -> Self {
+        Self::default()
+    }
+
+    pub fn at(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: Some(path.into()),
+        }
+    }
+
+    /// `name` under RUST_DATA_DIR
+    pub fn from_env(name: &str) -> Self {
+        Self::at(data_path(name))
+    }
+
+    /// Every record appended so far. A last line cut short by a crash mid-append
+    /// is dropped from the file; anything else that doesn't parse is an error.
+    pub fn load<T: DeserializeOwned>(&self) -> ApiResult<Vec<T>> {
+        let Some(path) = &self.path else {
+            return Ok(Vec::new());
+        };
+        let mut text = match std::fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(io_error(path, e)),
+        };
+
+        // Every append ends in a newline, so anything after the last one is unfinished
+        let finished = text.rfind('\n').map_or(0, |end| end + 1);
+        if finished < text.len() {
+            tracing::warn!(
+                "Dropping an unfinished record at the end of {}",
+                path.display()
+            );
+            std::fs::OpenOptions::new()
+                .write(true)
+                .open(path)
+                .and_then(|file| file.set_len(finished as u64))
+                .map_err(|e| io_error(path, e))?;
+            text.truncate(finished);
+        }
+
+        text.lines()
+            .enumerate()
+            .map(|(number, line)| {
+                serde_json::from_str(line).map_err(|e| ApiError::Serialization {
+                    message: format!(
+                        "Could not read {} line {}: {}",
+                        path.display(),
+                        number + 1,
+                        e
+                    ),
+                })
+            })
+            .collect()
+    }
+
+    /// Append `records` with a single write, so they land together or not at all
+    /// short of a crash mid-write
+    pub fn append<T: Serialize>(&self, records: &[T]) -> ApiResult<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let mut bytes = Vec::new();
+        for record in records {
+            serde_json::to_writer(&mut bytes, record).map_err(|e| ApiError::Serialization {
+                message: e.to_string(),
+            })?;
+            bytes.push(b'\n');
+        }
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir).map_err(|e| io_error(dir, e))?;
+        }
+        std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .and_then(|mut file| file.write_all(&bytes))
+            .map_err(|e| io_error(path, e))
+    }
+}
+
+fn data_path(name: &str) -> PathBuf {
+    let dir = std::env::var("RUST_DATA_DIR")
+        .ok()
+        .filter(|dir| !dir.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_DATA_DIR.to_string());
+    Path::new(&dir).join(name)
+}
+
+fn io_error(path: &Path, error: std::io::Error) -> ApiError {
+    ApiError::Server {
+        message: format!("Could not access {}: {}", path.display(), error),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_documents_survive_a_new_store() {
+        let dir = std::env::temp_dir().join(format!("bitsacco-store-{}", uuid::Uuid::new_v4()));
+        let path = dir.join("nested").join("doc.json");
+
+        let store = JsonStore::at(&path);
+        assert_eq!(store.load::<Vec<u32>>().unwrap(), Vec::<u32>::new());
+        store.save(&vec![1u32, 2, 3]).unwrap();
+        assert_eq!(JsonStore::at(&path).load::<Vec<u32>>().unwrap(), [1, 2, 3]);
+
+        let memory = JsonStore::in_memory();
+        memory.save(&vec![1u32]).unwrap();
+        assert!(memory.load::<Vec<u32>>().unwrap().is_empty());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_logs_replay_appended_records() {
+        let dir = std::env::temp_dir().join(format!("bitsacco-log-{}", uuid::Uuid::new_v4()));
+        let path = dir.join("records.jsonl");
+
+        let log = JsonLog::at(&path);
+        assert!(log.load::<u32>().unwrap().is_empty());
+        log.append(&[1u32, 2]).unwrap();
+        log.append(&[3u32]).unwrap();
+        assert_eq!(JsonLog::at(&path).load::<u32>().unwrap(), [1, 2, 3]);
+
+        // A record cut short by a crash is dropped, but damage elsewhere is an error
+        std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(b"4")
+            .unwrap();
+        assert_eq!(JsonLog::at(&path).load::<u32>().unwrap(), [1, 2, 3]);
+        log.append(&[5u32]).unwrap();
+        assert_eq!(log.load::<u32>().unwrap(), [1, 2, 3, 5]);
+        std::fs::write(&path, b"1\nnope\n3\n").unwrap();
+        assert!(JsonLog::at(&path).load::<u32>().is_err());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,767 @@
+// M-Pesa ⇄ bitcoin swaps for the Rust backend
+// Onramps prompt the member to pay KES over M-Pesa and credit their wallet once the
+// payment lands; offramps hold the sats and release them only if the M-Pesa payout
+// fails. The swap poller asks the gateway where pending swaps stand, finding payouts
+// whose request was never answered by their idempotency key. Swaps are saved to a
+// JSON store whenever they change.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use rust_decimal::{prelude::ToPrimitive, Decimal};
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use super::{JsonStore, RustFxApi, RustWalletsApi};
+use crate::api::{
+    errors::{ApiError, ApiResult},
+    traits::{
+        fx::{FxApi, FxRate},
+        swaps::{
+            normalize_msisdn, MpesaGateway, MpesaState, OfframpRequest, OnrampRequest, Swap,
+            SwapDirection, SwapFilter, SwapStatus, SwapsApi, MPESA_MAX_KES,
+        },
+        wallets::{TransactionStatus, TransactionType, WalletType, WalletsApi},
+    },
+    types::User,
+};
+
+/// How long a payout M-Pesa never answered may take to show up under its key
+/// before it is treated as never sent
+const PAYOUT_LOOKUP_GRACE_SECS: i64 = 60;
+
+/// Whether a failed payout call may still have reached M-Pesa: the gateway never
+/// answered, or answered in a way we couldn't read
+fn payout_may_be_in_flight(error: &ApiError) -> bool {
+    matches!(
+        error,
+        ApiError::Network { .. } | ApiError::Serialization { .. } | ApiError::Unknown { .. }
+    )
+}
+
+#[derive(Clone)]
+pub struct RustSwapsApi {
+    swaps: Arc<RwLock<Vec<Swap>>>,
+    wallets: RustWalletsApi,
+    fx: RustFxApi,
+    gateway: Arc<dyn MpesaGateway>,
+    store: JsonStore,
+}
+
+impl RustSwapsApi {
+    pub fn new(wallets: RustWalletsApi, fx: RustFxApi, gateway: Arc<dyn MpesaGateway>) -> Self {
+        Self {
+            swaps: Arc::default(),
+            wallets,
+            fx,
+            gateway,
+            store: JsonStore::in_memory(),
+        }
+    }
+
+    /// Keep swaps in `store`, starting from what it already holds
+    pub fn with_store(mut self, store: JsonStore) -> ApiResult<Self> {
+        let swaps: Vec<Swap> = store.load()?;
+        self.swaps = Arc::new(RwLock::new(swaps));
+        self.store = store;
+        Ok(self)
+    }
+
+    /// Reconcile pending swaps every `every` until the process exits
+    pub fn spawn_swap_poller(&self, every: Duration) -> tokio::task::JoinHandle<()> {
+        let api = self.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(every);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                ticker.tick().await;
+                match api.sync_pending_swaps().await {
+                    Ok(0) => {}
+                    Ok(finished) => tracing::info!("Finished {} swap(s)", finished),
+                    Err(e) => tracing::warn!("Swap reconciliation failed: {}", e),
+                }
+            }
+        })
+    }
+
+    /// Swaps settle into wallets backed by the federation
+    async fn swap_wallet(&self, wallet_id: Uuid) -> ApiResult<()> {
+        let wallet = self.wallets.get_wallet(wallet_id).await?;
+        match wallet.wallet_type {
+            WalletType::Lightning | WalletType::Fedimint => Ok(()),
+            WalletType::OnChain => Err(ApiError::Validation {
+                message: format!("Wallet {} is an on-chain wallet", wallet.name),
+            }),
+        }
+    }
+
+    /// Price a swap at the current rate. Onramps keep their KES amount and offramps
+    /// their sats; offramp payouts are rounded down to whole shillings.
+    async fn price(&self, swap: &mut Swap) -> ApiResult<()> {
+        let rate = self.fx.current_rate().await?;
+        let (amount_kes, amount_sats) = match swap.direction {
+            SwapDirection::Onramp => (swap.amount_kes, rate.kes_to_sats(swap.amount_kes)),
+            SwapDirection::Offramp => {
+                let sats = Decimal::from(swap.amount_sats);
+                (rate.sats_to_kes(sats).floor(), sats)
+            }
+        };
+        validate_kes(amount_kes)?;
+        swap.amount_sats = amount_sats
+            .to_u64()
+            .filter(|sats| *sats > 0)
+            .ok_or_else(|| ApiError::Validation {
+                message: "The amount is too small to swap".to_string(),
+            })?;
+        swap.amount_kes = amount_kes;
+        swap.rate = rate;
+        Ok(())
+    }
+
+    /// Start the M-Pesa leg and the wallet movement that goes with it. An offramp
+    /// whose payout is refused gets its held sats back and is marked failed; one
+    /// whose payout went unanswered stays pending, holding the sats, until the
+    /// gateway reports on it. Offramps are withdrawals, so need the `requester`
+    /// whose limits they are held to.
+    async fn start(&self, swap: &mut Swap, requester: Option<&User>) -> ApiResult<()> {
+        let reference = account_reference(swap.id);
+        match swap.direction {
+            SwapDirection::Onramp => {
+                let request_id = self
+                    .gateway
+                    .stk_push(&swap.phone, swap.amount_kes, &reference)
+                    .await?;
+                let transaction = self
+                    .wallets
+                    .record_pending_credit(
+                        swap.wallet_id,
+                        swap.amount_sats,
+                        TransactionType::Deposit,
+                    )
+                    .await?;
+                swap.mpesa_request_id = request_id;
+                swap.wallet_transaction_id = transaction.id;
+            }
+            SwapDirection::Offramp => {
+                let requester = requester.ok_or_else(|| ApiError::Validation {
+                    message: "Offramps must be started by a signed-in user".to_string(),
+                })?;
+                let transaction = self
+                    .wallets
+                    .hold_debit(
+                        swap.wallet_id,
+                        swap.amount_sats,
+                        TransactionType::Withdrawal,
+                        requester,
+                    )
+                    .await?;
+                swap.wallet_transaction_id = transaction.id;
+                // A payout M-Pesa may still have is resent under the same key
+                let payout_key = swap
+                    .payout_key
+                    .get_or_insert_with(|| payout_key(swap.id, swap.attempts + 1))
+                    .clone();
+                match self
+                    .gateway
+                    .b2c_payout(&swap.phone, swap.amount_kes, &reference, &payout_key)
+                    .await
+                {
+                    Ok(request_id) => swap.mpesa_request_id = request_id,
+                    Err(e) if payout_may_be_in_flight(&e) => {
+                        tracing::warn!(
+                            "No answer to the payout for swap {}; holding it until M-Pesa reports on {}: {}",
+                            swap.id,
+                            payout_key,
+                            e
+                        );
+                        swap.mpesa_request_id.clear();
+                    }
+                    Err(e) => {
+                        self.wallets
+                            .settle(transaction.id, TransactionStatus::Failed)
+                            .await?;
+                        swap.mpesa_request_id.clear();
+                        swap.payout_key = None;
+                        swap.attempts += 1;
+                        mark_failed(swap, e.to_string());
+                        return Err(e);
+                    }
+                }
+            }
+        }
+
+        swap.mpesa_reference = None;
+        swap.mpesa_status = TransactionStatus::Pending;
+        swap.lightning_status = TransactionStatus::Pending;
+        swap.status = SwapStatus::Pending;
+        swap.failure_reason = None;
+        swap.attempts += 1;
+        swap.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// The request a pending swap is waiting on and where it stands, or `None` if
+    /// there is nothing to ask the gateway about yet. A payout that never got an
+    /// answer is looked up by its key; if M-Pesa still doesn't have it after
+    /// [`PAYOUT_LOOKUP_GRACE_SECS`] it is reported failed and its key kept for
+    /// the retry.
+    async fn lookup(&self, swap: &Swap) -> ApiResult<Option<(String, MpesaState)>> {
+        if !swap.mpesa_request_id.is_empty() {
+            let state = self.gateway.request_state(&swap.mpesa_request_id).await?;
+            return Ok(Some((swap.mpesa_request_id.clone(), state)));
+        }
+        // A retry that is still starting its leg
+        let Some(payout_key) = &swap.payout_key else {
+            return Ok(None);
+        };
+        match self.gateway.find_payout(payout_key).await? {
+            Some(request_id) => {
+                let state = self.gateway.request_state(&request_id).await?;
+                Ok(Some((request_id, state)))
+            }
+            None if Utc::now() - swap.updated_at
+                >= chrono::Duration::seconds(PAYOUT_LOOKUP_GRACE_SECS) =>
+            {
+                Ok(Some((
+                    String::new(),
+                    MpesaState::Failed {
+                        reason: "the payout never reached M-Pesa".to_string(),
+                    },
+                )))
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn create(&self, mut swap: Swap, requester: Option<&User>) -> ApiResult<Swap> {
+        self.swap_wallet(swap.wallet_id).await?;
+        self.price(&mut swap).await?;
+        let result = self.start(&mut swap, requester).await;
+        // Refused offramps are kept so they can be retried
+        if result.is_ok() || swap.status == SwapStatus::Failed {
+            let mut swaps = self.swaps.write().await;
+            swaps.push(swap.clone());
+            self.store.save_or_log(&*swaps, "swaps");
+        }
+        result.map(|_| swap)
+    }
+
+    /// Settle a swap with what the gateway reported for `request_id`. `seen` is
+    /// the request the swap was waiting on when the gateway was asked; it is empty
+    /// for a payout that was found by its key.
+    async fn apply(
+        &self,
+        swap_id: Uuid,
+        seen: &str,
+        request_id: String,
+        state: MpesaState,
+    ) -> ApiResult<Swap> {
+        let mut swaps = self.swaps.write().await;
+        let swap = swaps
+            .iter_mut()
+            .find(|swap| swap.id == swap_id)
+            .ok_or_else(|| swap_not_found(swap_id))?;
+        // Another reconciliation may have settled it while the gateway was asked
+        if swap.status != SwapStatus::Pending || swap.mpesa_request_id != seen {
+            return Ok(swap.clone());
+        }
+        if !request_id.is_empty() {
+            swap.mpesa_request_id = request_id;
+        }
+
+        match state {
+            MpesaState::Pending => {}
+            MpesaState::Completed { receipt } => {
+                self.wallets
+                    .settle(swap.wallet_transaction_id, TransactionStatus::Confirmed)
+                    .await?;
+                let now = Utc::now();
+                swap.mpesa_reference = Some(receipt);
+                swap.mpesa_status = TransactionStatus::Confirmed;
+                swap.lightning_status = TransactionStatus::Confirmed;
+                swap.status = SwapStatus::Completed;
+                swap.updated_at = now;
+                swap.completed_at = Some(now);
+            }
+            MpesaState::Failed { reason } => {
+                self.wallets
+                    .settle(swap.wallet_transaction_id, TransactionStatus::Failed)
+                    .await?;
+                // M-Pesa answered under this key, so a retry is a new payout
+                if !swap.mpesa_request_id.is_empty() {
+                    swap.payout_key = None;
+                }
+                mark_failed(swap, format!("M-Pesa: {}", reason));
+            }
+        }
+        let applied = swap.clone();
+        self.store.save_or_log(&*swaps, "swaps");
+        Ok(applied)
+    }
+}
+
+fn swap_not_found(swap_id: Uuid) -> ApiError {
+    ApiError::NotFound {
+        resource: format!("Swap {}", swap_id),
+    }
+}
+
+fn validate_kes(amount_kes: Decimal) -> ApiResult<()> {
+    if amount_kes < Decimal::ONE || !amount_kes.fract().is_zero() {
+        return Err(ApiError::Validation {
+            message: "M-Pesa amounts must be whole shillings of at least KES 1".to_string(),
+        });
+    }
+    if amount_kes > Decimal::from(MPESA_MAX_KES) {
+        return Err(ApiError::Validation {
+            message: format!("M-Pesa transactions are limited to KES {}", MPESA_MAX_KES),
+        });
+    }
+    Ok(())
+}
+
+/// Unique to a payout attempt, so M-Pesa can tell a resent payout from a new one
+fn payout_key(swap_id: Uuid, attempt: u32) -> String {
+    format!("{}-{}", swap_id.simple(), attempt)
+}
+
+/// Daraja account references are at most 12 characters
+fn account_reference(swap_id: Uuid) -> String {
+    format!("BS{}", swap_id.simple().to_string()[..10].to_uppercase())
+}
+
+fn mark_failed(swap: &mut Swap, reason: String) {
+    swap.mpesa_status = TransactionStatus::Failed;
+    swap.lightning_status = TransactionStatus::Failed;
+    swap.status = SwapStatus::Failed;
+    swap.failure_reason = Some(reason);
+    swap.updated_at = Utc::now();
+}
+
+#[async_trait]
+impl SwapsApi for RustSwapsApi {
+    async fn create_onramp(&self, request: OnrampRequest) -> ApiResult<Swap> {
+        validate_kes(request.amount_kes)?;
+        let phone = normalize_msisdn(&request.phone)?;
+        self.create(
+            new_swap(
+                SwapDirection::Onramp,
+                request.wallet_id,
+                phone,
+                request.amount_kes,
+                0,
+            ),
+            None,
+        )
+        .await
+    }
+
+    async fn create_offramp(&self, request: OfframpRequest, requester: &User) -> ApiResult<Swap> {
+        if request.amount_sats == 0 {
+            return Err(ApiError::Validation {
+                message: "Swap amount must be greater than zero".to_string(),
+            });
+        }
+        let phone = normalize_msisdn(&request.phone)?;
+        self.create(
+            new_swap(
+                SwapDirection::Offramp,
+                request.wallet_id,
+                phone,
+                Decimal::ZERO,
+                request.amount_sats,
+            ),
+            Some(requester),
+        )
+        .await
+    }
+
+    async fn list_swaps(&self, filter: SwapFilter) -> ApiResult<Vec<Swap>> {
+        let mut swaps: Vec<Swap> = self
+            .swaps
+            .read()
+            .await
+            .iter()
+            .filter(|swap| filter.direction.is_none_or(|d| swap.direction == d))
+            .filter(|swap| filter.status.is_none_or(|s| swap.status == s))
+            .filter(|swap| filter.wallet_id.is_none_or(|w| swap.wallet_id == w))
+            .cloned()
+            .collect();
+        swaps.sort_by_key(|swap| std::cmp::Reverse(swap.created_at));
+        Ok(swaps)
+    }
+
+    async fn get_swap(&self, swap_id: Uuid) -> ApiResult<Swap> {
+        self.swaps
+            .read()
+            .await
+            .iter()
+            .find(|swap| swap.id == swap_id)
+            .cloned()
+            .ok_or_else(|| swap_not_found(swap_id))
+    }
+
+    async fn reconcile_swap(&self, swap_id: Uuid) -> ApiResult<Swap> {
+        let swap = self.get_swap(swap_id).await?;
+        if swap.status != SwapStatus::Pending {
+            return Err(ApiError::Conflict {
+                message: format!("Swap {} is no longer pending", swap_id),
+            });
+        }
+        match self.lookup(&swap).await? {
+            Some((request_id, state)) => {
+                self.apply(swap.id, &swap.mpesa_request_id, request_id, state)
+                    .await
+            }
+            None => Ok(swap),
+        }
+    }
+
+    async fn retry_swap(&self, swap_id: Uuid, requester: &User) -> ApiResult<Swap> {
+        // Claim the swap so two retries cannot both start a new M-Pesa leg
+        let mut swap = {
+            let mut swaps = self.swaps.write().await;
+            let swap = swaps
+                .iter_mut()
+                .find(|swap| swap.id == swap_id)
+                .ok_or_else(|| swap_not_found(swap_id))?;
+            if swap.status != SwapStatus::Failed {
+                return Err(ApiError::Conflict {
+                    message: "Only failed swaps can be retried".to_string(),
+                });
+            }
+            swap.status = SwapStatus::Pending;
+            // Keeps the poller off the old request while the new one starts, and
+            // gives a resent payout the full grace period to show up
+            swap.mpesa_request_id.clear();
+            swap.updated_at = Utc::now();
+            let claimed = swap.clone();
+            self.store.save_or_log(&*swaps, "swaps");
+            claimed
+        };
+
+        let result = match self.price(&mut swap).await {
+            Ok(()) => self.start(&mut swap, Some(requester)).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = &result {
+            if swap.status != SwapStatus::Failed {
+                mark_failed(&mut swap, e.to_string());
+            }
+        }
+
+        let mut swaps = self.swaps.write().await;
+        if let Some(stored) = swaps.iter_mut().find(|stored| stored.id == swap_id) {
+            *stored = swap.clone();
+        }
+        self.store.save_or_log(&*swaps, "swaps");
+        result.map(|_| swap)
+    }
+
+    async fn sync_pending_swaps(&self) -> ApiResult<usize> {
+        let pending = self
+            .list_swaps(SwapFilter {
+                status: Some(SwapStatus::Pending),
+                ..SwapFilter::default()
+            })
+            .await?;
+
+        let mut finished = 0;
+        for swap in pending {
+            let (request_id, state) = match self.lookup(&swap).await {
+                Ok(Some(found)) => found,
+                Ok(None) => continue,
+                Err(e) => {
+                    tracing::warn!("Could not check swap {}: {}", swap.id, e);
+                    continue;
+                }
+            };
+            match self
+                .apply(swap.id, &swap.mpesa_request_id, request_id, state)
+                .await
+            {
+                Ok(swap) if swap.status != SwapStatus::Pending => finished += 1,
+                Ok(_) => {}
+                Err(e) => tracing::warn!("Could not settle swap {}: {}", swap.id, e),
+            }
+        }
+        Ok(finished)
+    }
+}
+
+fn new_swap(
+    direction: SwapDirection,
+    wallet_id: Uuid,
+    phone: String,
+    amount_kes: Decimal,
+    amount_sats: u64,
+) -> Swap {
+    let now = Utc::now();
+    Swap {
+        id: Uuid::new_v4(),
+        direction,
+        wallet_id,
+        phone,
+        amount_kes,
+        amount_sats,
+        // Replaced when the swap is priced
+        rate: FxRate {
+            kes_per_btc: Decimal::ZERO,
+            source: String::new(),
+            observed_at: now,
+        },
+        mpesa_request_id: String::new(),
+        payout_key: None,
+        mpesa_reference: None,
+        mpesa_status: TransactionStatus::Pending,
+        wallet_transaction_id: Uuid::nil(),
+        lightning_status: TransactionStatus::Pending,
+        status: SwapStatus::Pending,
+        failure_reason: None,
+        attempts: 0,
+        created_at: now,
+        updated_at: now,
+        completed_at: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::{
+        backends::{mpesa::MockMpesaGateway, rust::RustLedgerApi},
+        traits::wallets::CreateWalletRequest,
+        types::common::Role,
+    };
+
+    fn admin() -> User {
+        User {
+            id: Uuid::new_v4(),
+            phone: None,
+            nostr: None,
+            profile: None,
+            roles: vec![Role::Admin],
+            verified: true,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    async fn setup() -> (RustSwapsApi, RustWalletsApi, MockMpesaGateway, Uuid) {
+        let wallets = RustWalletsApi::new(RustLedgerApi::new());
+        let wallet = wallets
+            .create_wallet(CreateWalletRequest {
+                user_id: Uuid::new_v4(),
+                name: "Savings".to_string(),
+                wallet_type: WalletType::Fedimint,
+            })
+            .await
+            .unwrap();
+        let fx = RustFxApi::new(Vec::new());
+        fx.record_rate(Decimal::from(13_000_000), "manual".to_string())
+            .await
+            .unwrap();
+        let mpesa = MockMpesaGateway::new();
+        let api = RustSwapsApi::new(wallets.clone(), fx, Arc::new(mpesa.clone()));
+        (api, wallets, mpesa, wallet.id)
+    }
+
+    #[test]
+    fn test_msisdn_normalisation() {
+        assert_eq!(normalize_msisdn("0712 345 678").unwrap(), "254712345678");
+        assert_eq!(normalize_msisdn("+254-110-345678").unwrap(), "254110345678");
+        assert_eq!(normalize_msisdn("712345678").unwrap(), "254712345678");
+        assert!(normalize_msisdn("0812345678").is_err());
+        assert!(normalize_msisdn("07123").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_onramp_credits_wallet_and_retries() {
+        let (api, wallets, mpesa, wallet_id) = setup().await;
+        let swap = api
+            .create_onramp(OnrampRequest {
+                wallet_id,
+                phone: "0712345678".to_string(),
+                amount_kes: Decimal::from(1_000),
+            })
+            .await
+            .unwrap();
+        // KES 1,000 at KES 13,000,000 per BTC
+        assert_eq!(swap.amount_sats, 7_692);
+        assert_eq!(swap.phone, "254712345678");
+        assert_eq!(swap.attempts, 1);
+
+        // The customer cancels the prompt; nothing is credited
+        mpesa
+            .fail(&swap.mpesa_request_id, "Request cancelled by user")
+            .await
+            .unwrap();
+        assert_eq!(api.sync_pending_swaps().await.unwrap(), 1);
+        let failed = api.get_swap(swap.id).await.unwrap();
+        assert_eq!(failed.status, SwapStatus::Failed);
+        assert_eq!(failed.lightning_status, TransactionStatus::Failed);
+        assert_eq!(wallets.get_wallet_balance(wallet_id).await.unwrap(), 0);
+        assert!(matches!(
+            api.reconcile_swap(swap.id).await,
+            Err(ApiError::Conflict { .. })
+        ));
+
+        let retried = api.retry_swap(swap.id, &admin()).await.unwrap();
+        assert_eq!(retried.attempts, 2);
+        assert_ne!(retried.mpesa_request_id, swap.mpesa_request_id);
+        assert!(matches!(
+            api.retry_swap(swap.id, &admin()).await,
+            Err(ApiError::Conflict { .. })
+        ));
+
+        // Still waiting on the customer
+        let pending = api.reconcile_swap(swap.id).await.unwrap();
+        assert_eq!(pending.status, SwapStatus::Pending);
+
+        let receipt = mpesa.complete(&retried.mpesa_request_id).await.unwrap();
+        let completed = api.reconcile_swap(swap.id).await.unwrap();
+        assert_eq!(completed.status, SwapStatus::Completed);
+        assert_eq!(completed.mpesa_reference, Some(receipt));
+        assert_eq!(completed.lightning_status, TransactionStatus::Confirmed);
+        assert_eq!(wallets.get_wallet_balance(wallet_id).await.unwrap(), 7_692);
+        assert_eq!(api.sync_pending_swaps().await.unwrap(), 0);
+
+        assert!(api
+            .create_onramp(OnrampRequest {
+                wallet_id,
+                phone: "0712345678".to_string(),
+                amount_kes: Decimal::new(1_050, 2),
+            })
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_offramp_holds_sats_until_paid_out() {
+        let (api, wallets, mpesa, wallet_id) = setup().await;
+        wallets
+            .credit(wallet_id, 150_000, TransactionType::Deposit)
+            .await
+            .unwrap();
+
+        // A refused payout returns the held sats and is kept for retrying
+        mpesa.set_unavailable(true).await;
+        let request = OfframpRequest {
+            wallet_id,
+            phone: "0712345678".to_string(),
+            amount_sats: 100_000,
+        };
+        assert!(matches!(
+            api.create_offramp(request.clone(), &admin()).await,
+            Err(ApiError::Server { .. })
+        ));
+        assert_eq!(
+            wallets.get_wallet_balance(wallet_id).await.unwrap(),
+            150_000
+        );
+        let refused = api
+            .list_swaps(SwapFilter {
+                status: Some(SwapStatus::Failed),
+                ..SwapFilter::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(refused.len(), 1);
+        assert_eq!(refused[0].amount_kes, Decimal::from(13_000));
+
+        mpesa.set_unavailable(false).await;
+        let retried = api.retry_swap(refused[0].id, &admin()).await.unwrap();
+        assert_eq!(retried.status, SwapStatus::Pending);
+        assert_eq!(wallets.get_wallet_balance(wallet_id).await.unwrap(), 50_000);
+
+        // A second offramp cannot spend the held sats
+        assert!(api.create_offramp(request, &admin()).await.is_err());
+
+        // A swap nobody has heard back on is flagged as stuck
+        assert!(!retried.is_stuck(Utc::now()));
+        assert!(retried.is_stuck(Utc::now() + chrono::Duration::minutes(11)));
+
+        mpesa.complete(&retried.mpesa_request_id).await.unwrap();
+        assert_eq!(api.sync_pending_swaps().await.unwrap(), 1);
+        let completed = api.get_swap(retried.id).await.unwrap();
+        assert_eq!(completed.status, SwapStatus::Completed);
+        assert_eq!(completed.attempts, 2);
+        assert_eq!(wallets.get_wallet_balance(wallet_id).await.unwrap(), 50_000);
+    }
+
+    #[tokio::test]
+    async fn test_unanswered_payouts_stay_held_until_mpesa_reports_them() {
+        let (api, wallets, mpesa, wallet_id) = setup().await;
+        wallets
+            .credit(wallet_id, 150_000, TransactionType::Deposit)
+            .await
+            .unwrap();
+        let request = OfframpRequest {
+            wallet_id,
+            phone: "0712345678".to_string(),
+            amount_sats: 100_000,
+        };
+
+        // M-Pesa takes the payout but the reply is lost: the sats stay held
+        mpesa.set_losing_replies(true).await;
+        let swap = api.create_offramp(request.clone(), &admin()).await.unwrap();
+        mpesa.set_losing_replies(false).await;
+        assert_eq!(swap.status, SwapStatus::Pending);
+        assert!(swap.mpesa_request_id.is_empty());
+        assert_eq!(wallets.get_wallet_balance(wallet_id).await.unwrap(), 50_000);
+        assert!(matches!(
+            api.retry_swap(swap.id, &admin()).await,
+            Err(ApiError::Conflict { .. })
+        ));
+
+        // The payout is found under its key and settles the swap
+        let found = api.reconcile_swap(swap.id).await.unwrap();
+        assert_eq!(found.status, SwapStatus::Pending);
+        assert!(!found.mpesa_request_id.is_empty());
+        mpesa.complete(&found.mpesa_request_id).await.unwrap();
+        assert_eq!(api.sync_pending_swaps().await.unwrap(), 1);
+        assert_eq!(
+            api.get_swap(swap.id).await.unwrap().status,
+            SwapStatus::Completed
+        );
+        assert_eq!(wallets.get_wallet_balance(wallet_id).await.unwrap(), 50_000);
+
+        // A payout that never reached M-Pesa is failed once the grace period is up
+        mpesa.set_losing_requests(true).await;
+        let lost = api
+            .create_offramp(
+                OfframpRequest {
+                    amount_sats: 40_000,
+                    ..request
+                },
+                &admin(),
+            )
+            .await
+            .unwrap();
+        mpesa.set_losing_requests(false).await;
+        assert_eq!(api.sync_pending_swaps().await.unwrap(), 0);
+        assert_eq!(wallets.get_wallet_balance(wallet_id).await.unwrap(), 10_000);
+        for stored in api.swaps.write().await.iter_mut() {
+            stored.updated_at -= chrono::Duration::seconds(PAYOUT_LOOKUP_GRACE_SECS);
+        }
+        assert_eq!(api.sync_pending_swaps().await.unwrap(), 1);
+        let failed = api.get_swap(lost.id).await.unwrap();
+        assert_eq!(failed.status, SwapStatus::Failed);
+        assert_eq!(wallets.get_wallet_balance(wallet_id).await.unwrap(), 50_000);
+
+        // The retry resends under the original key, so M-Pesa pays it only once
+        let retried = api.retry_swap(lost.id, &admin()).await.unwrap();
+        assert_eq!(retried.payout_key, lost.payout_key);
+        let payouts: Vec<_> = mpesa
+            .requests()
+            .await
+            .into_iter()
+            .filter(|request| request.payout_key == lost.payout_key)
+            .collect();
+        assert_eq!(payouts.len(), 1);
+        assert_eq!(payouts[0].request_id, retried.mpesa_request_id);
+        assert_eq!(wallets.get_wallet_balance(wallet_id).await.unwrap(), 10_000);
+    }
+}
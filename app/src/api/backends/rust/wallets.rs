@@ -0,0 +1,953 @@
+// In-process wallet store for the Rust backend
+// Balances are computed from the double-entry ledger: every movement posts a
+// journal entry against the member's wallet account. Outgoing withdrawals and
+// payments are checked against the withdrawal policy first. Wallets and their
+// transactions are appended to a JSON-lines log and replayed from it on start.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use rust_decimal::prelude::ToPrimitive;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use super::{withdrawals::WithdrawalAttempt, JsonLog, RustLedgerApi, RustWithdrawalPolicy};
+use crate::api::{
+    errors::{ApiError, ApiResult},
+    traits::{
+        ledger::{
+            AccountKey, AccountPurpose, JournalEntryRequest, LedgerApi, LedgerCurrency,
+            PostingRequest,
+        },
+        wallets::{
+            CreateWalletRequest, TransactionFilter, TransactionStatus, TransactionType, Wallet,
+            WalletTransaction, WalletsApi,
+        },
+    },
+    types::{common::Role, PaginatedResponse, PaginationQuery, User},
+};
+
+/// A line of the wallet log; later records for the same ID replace earlier ones
+#[derive(serde::Serialize, serde::Deserialize)]
+enum WalletRecord {
+    Wallet(Wallet),
+    Deleted(Uuid),
+    Transaction(WalletTransaction),
+}
+
+#[derive(Default)]
+struct WalletsState {
+    wallets: HashMap<Uuid, Wallet>,
+    transactions: Vec<WalletTransaction>,
+    log: JsonLog,
+}
+
+impl WalletsState {
+    /// Write a transaction to the log. Its funds have already moved on the
+    /// ledger, which balances are read from, so a failure only loses the
+    /// history line and is logged rather than returned.
+    fn log_transaction(&self, transaction: &WalletTransaction) {
+        if let Err(e) = self
+            .log
+            .append(&[WalletRecord::Transaction(transaction.clone())])
+        {
+            tracing::error!(
+                "Could not save wallet transaction {}: {}",
+                transaction.id,
+                e
+            );
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct RustWalletsApi {
+    state: Arc<RwLock<WalletsState>>,
+    ledger: RustLedgerApi,
+    policy: RustWithdrawalPolicy,
+}
+
+impl RustWalletsApi {
+    pub fn new(ledger: RustLedgerApi) -> Self {
+        Self {
+            state: Arc::default(),
+            ledger,
+            policy: RustWithdrawalPolicy::new(),
+        }
+    }
+
+    /// Keep wallets and transactions in `log`, starting from the ones it already holds
+    pub fn with_log(self, log: JsonLog) -> ApiResult<Self> {
+        let mut state = WalletsState::default();
+        let mut positions = HashMap::new();
+        for record in log.load()? {
+            match record {
+                WalletRecord::Wallet(wallet) => {
+                    state.wallets.insert(wallet.id, wallet);
+                }
+                WalletRecord::Deleted(wallet_id) => {
+                    state.wallets.remove(&wallet_id);
+                }
+                WalletRecord::Transaction(transaction) => {
+                    if let Some(wallet) = state.wallets.get_mut(&transaction.wallet_id) {
+                        wallet.updated_at = wallet.updated_at.max(transaction.updated_at);
+                    }
+                    match positions.get(&transaction.id) {
+                        Some(&index) => state.transactions[index] = transaction,
+                        None => {
+                            positions.insert(transaction.id, state.transactions.len());
+                            state.transactions.push(transaction);
+                        }
+                    }
+                }
+            }
+        }
+        state.log = log;
+        Ok(Self {
+            state: Arc::new(RwLock::new(state)),
+            ..self
+        })
+    }
+
+    /// Check withdrawals against `policy` instead of a fresh in-memory one
+    pub fn with_policy(mut self, policy: RustWithdrawalPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Limits every outgoing withdrawal and payment is checked against
+    pub fn policy(&self) -> &RustWithdrawalPolicy {
+        &self.policy
+    }
+
+    /// Credit a wallet with funds arriving from outside the SACCO
+    pub async fn credit(
+        &self,
+        wallet_id: Uuid,
+        amount: u64,
+        transaction_type: TransactionType,
+    ) -> ApiResult<WalletTransaction> {
+        let treasury = AccountKey::sacco(AccountPurpose::Treasury, LedgerCurrency::Sats);
+        self.post(
+            wallet_id,
+            signed_sats(amount)?,
+            transaction_type,
+            vec![PostingRequest::debit(treasury, amount)],
+        )
+        .await
+    }
+
+    /// Debit a wallet with funds leaving the SACCO, refusing overdrafts
+    pub async fn debit(
+        &self,
+        wallet_id: Uuid,
+        amount: u64,
+        transaction_type: TransactionType,
+        withdrawer: &User,
+    ) -> ApiResult<WalletTransaction> {
+        let treasury = AccountKey::sacco(AccountPurpose::Treasury, LedgerCurrency::Sats);
+        self.post_debit(
+            wallet_id,
+            amount,
+            transaction_type,
+            vec![PostingRequest::credit(treasury, amount)],
+            withdrawer,
+        )
+        .await
+    }
+
+    /// Record a confirmed wallet movement and post it to the ledger. `counterparts`
+    /// are the other side of the entry and must balance the wallet posting. Only
+    /// payments the member owes within the SACCO may be taken out this way; money
+    /// leaving it goes through `debit` and the withdrawal limits.
+    pub(super) async fn post(
+        &self,
+        wallet_id: Uuid,
+        amount: i64,
+        transaction_type: TransactionType,
+        counterparts: Vec<PostingRequest>,
+    ) -> ApiResult<WalletTransaction> {
+        if amount < 0 && !is_internal_debit(&transaction_type) {
+            return Err(ApiError::Validation {
+                message: format!(
+                    "{:?} transactions must be made by a signed-in user within the withdrawal limits",
+                    transaction_type
+                ),
+            });
+        }
+        self.record(
+            wallet_id,
+            amount,
+            transaction_type,
+            counterparts,
+            TransactionStatus::Confirmed,
+            None,
+        )
+        .await
+    }
+
+    /// Record a confirmed outgoing movement made by `withdrawer`, so withdrawals
+    /// and payments are held to the limits of their role. `counterparts` must
+    /// balance the amount taken from the wallet.
+    async fn post_debit(
+        &self,
+        wallet_id: Uuid,
+        amount: u64,
+        transaction_type: TransactionType,
+        counterparts: Vec<PostingRequest>,
+        withdrawer: &User,
+    ) -> ApiResult<WalletTransaction> {
+        self.record(
+            wallet_id,
+            -signed_sats(amount)?,
+            transaction_type,
+            counterparts,
+            TransactionStatus::Confirmed,
+            Some(withdrawer),
+        )
+        .await
+    }
+
+    /// Record an incoming movement that has not arrived yet. Nothing is posted to
+    /// the ledger until the transaction is settled as confirmed.
+    pub async fn record_pending_credit(
+        &self,
+        wallet_id: Uuid,
+        amount: u64,
+        transaction_type: TransactionType,
+    ) -> ApiResult<WalletTransaction> {
+        if amount == 0 {
+            return Err(zero_amount());
+        }
+        let signed = signed_sats(amount)?;
+
+        let mut state = self.state.write().await;
+        if !state.wallets.contains_key(&wallet_id) {
+            return Err(wallet_not_found(wallet_id));
+        }
+
+        let now = Utc::now();
+        let transaction = WalletTransaction {
+            id: Uuid::new_v4(),
+            wallet_id,
+            amount: signed,
+            transaction_type,
+            status: TransactionStatus::Pending,
+            created_at: now,
+            updated_at: now,
+        };
+        state
+            .log
+            .append(&[WalletRecord::Transaction(transaction.clone())])?;
+        state.transactions.push(transaction.clone());
+        Ok(transaction)
+    }
+
+    /// Debit a wallet for an outgoing movement that is still in flight. The funds
+    /// leave the wallet straight away so they cannot be spent twice, and are
+    /// returned if the transaction is settled as failed.
+    pub async fn hold_debit(
+        &self,
+        wallet_id: Uuid,
+        amount: u64,
+        transaction_type: TransactionType,
+        withdrawer: &User,
+    ) -> ApiResult<WalletTransaction> {
+        let treasury = AccountKey::sacco(AccountPurpose::Treasury, LedgerCurrency::Sats);
+        self.record(
+            wallet_id,
+            -signed_sats(amount)?,
+            transaction_type,
+            vec![PostingRequest::credit(treasury, amount)],
+            TransactionStatus::Pending,
+            Some(withdrawer),
+        )
+        .await
+    }
+
+    /// Move a pending transaction to its final status, crediting confirmed
+    /// incoming funds and returning held funds when an outgoing movement fails
+    pub async fn settle(
+        &self,
+        transaction_id: Uuid,
+        status: TransactionStatus,
+    ) -> ApiResult<WalletTransaction> {
+        if status == TransactionStatus::Pending {
+            return Err(ApiError::Validation {
+                message: "Transactions can only be settled as confirmed, failed or cancelled"
+                    .to_string(),
+            });
+        }
+
+        let mut state = self.state.write().await;
+        let transaction = state
+            .transactions
+            .iter()
+            .find(|tx| tx.id == transaction_id)
+            .cloned()
+            .ok_or_else(|| ApiError::NotFound {
+                resource: format!("Wallet transaction {}", transaction_id),
+            })?;
+        if transaction.status != TransactionStatus::Pending {
+            return Err(ApiError::Conflict {
+                message: format!(
+                    "Transaction {} is already {:?}",
+                    transaction_id, transaction.status
+                ),
+            });
+        }
+        let wallet = state
+            .wallets
+            .get(&transaction.wallet_id)
+            .cloned()
+            .ok_or_else(|| wallet_not_found(transaction.wallet_id))?;
+        let amount = transaction.amount.unsigned_abs();
+        let reversal = signed_sats(amount)?;
+
+        // Record the new status before anything is posted, so a restart can't
+        // settle the same transaction twice
+        let now = Utc::now();
+        let settled = WalletTransaction {
+            status,
+            updated_at: now,
+            ..transaction.clone()
+        };
+        state
+            .log
+            .append(&[WalletRecord::Transaction(settled.clone())])?;
+
+        let treasury = AccountKey::sacco(AccountPurpose::Treasury, LedgerCurrency::Sats);
+        let label = transaction_type_label(&transaction.transaction_type);
+        let posted = match (transaction.amount > 0, &settled.status) {
+            (true, TransactionStatus::Confirmed) => {
+                self.post_to_ledger(
+                    &wallet,
+                    transaction.id,
+                    transaction.amount,
+                    format!("Wallet {} {}", wallet.name, label),
+                    vec![PostingRequest::debit(treasury, amount)],
+                )
+                .await
+            }
+            (false, TransactionStatus::Failed | TransactionStatus::Cancelled) => {
+                self.post_to_ledger(
+                    &wallet,
+                    transaction.id,
+                    reversal,
+                    format!("Wallet {} {} reversal", wallet.name, label),
+                    vec![PostingRequest::debit(treasury, amount)],
+                )
+                .await
+            }
+            // Unconfirmed credits never reached the ledger and confirmed debits
+            // were posted when the funds were held
+            _ => Ok(()),
+        };
+        if let Err(e) = posted {
+            // Still pending, so it can be settled again
+            state.log_transaction(&transaction);
+            return Err(e);
+        }
+
+        if let Some(stored) = state
+            .transactions
+            .iter_mut()
+            .find(|tx| tx.id == transaction_id)
+        {
+            *stored = settled.clone();
+        }
+        if let Some(wallet) = state.wallets.get_mut(&settled.wallet_id) {
+            wallet.updated_at = now;
+        }
+        Ok(settled)
+    }
+
+    async fn record(
+        &self,
+        wallet_id: Uuid,
+        amount: i64,
+        transaction_type: TransactionType,
+        counterparts: Vec<PostingRequest>,
+        status: TransactionStatus,
+        withdrawer: Option<&User>,
+    ) -> ApiResult<WalletTransaction> {
+        if amount == 0 {
+            return Err(zero_amount());
+        }
+
+        let mut state = self.state.write().await;
+        let wallet = state
+            .wallets
+            .get(&wallet_id)
+            .cloned()
+            .ok_or_else(|| wallet_not_found(wallet_id))?;
+        let decision = if amount < 0
+            && matches!(
+                transaction_type,
+                TransactionType::Withdrawal | TransactionType::Payment
+            ) {
+            let withdrawer = withdrawer.ok_or_else(|| ApiError::Validation {
+                message: "Withdrawals and payments must be made by a signed-in user".to_string(),
+            })?;
+            if withdrawer.id != wallet.user_id && !withdrawer.is_admin() {
+                return Err(ApiError::Authorization {
+                    message: "Only the wallet's owner or an admin can withdraw from it".to_string(),
+                });
+            }
+            // Checked under the write lock so concurrent withdrawals see each other
+            let decision = self
+                .policy
+                .check(withdrawal_attempt(
+                    &state,
+                    &wallet,
+                    amount.unsigned_abs(),
+                    &transaction_type,
+                    withdrawer,
+                ))
+                .await?;
+            Some(decision)
+        } else {
+            None
+        };
+
+        let now = Utc::now();
+        let transaction = WalletTransaction {
+            id: Uuid::new_v4(),
+            wallet_id,
+            amount,
+            transaction_type,
+            status,
+            created_at: now,
+            updated_at: now,
+        };
+
+        self.post_to_ledger(
+            &wallet,
+            transaction.id,
+            amount,
+            format!(
+                "Wallet {} {}",
+                wallet.name,
+                transaction_type_label(&transaction.transaction_type)
+            ),
+            counterparts,
+        )
+        .await?;
+        // Only now that the funds have left is the decision logged and any
+        // approval it relied on used up
+        if let Some(decision) = decision {
+            self.policy.commit(decision).await;
+        }
+
+        if let Some(wallet) = state.wallets.get_mut(&wallet_id) {
+            wallet.updated_at = now;
+        }
+        state.log_transaction(&transaction);
+        state.transactions.push(transaction.clone());
+
+        Ok(transaction)
+    }
+
+    /// Post a movement on the wallet's account, refusing to overdraw it
+    async fn post_to_ledger(
+        &self,
+        wallet: &Wallet,
+        transaction_id: Uuid,
+        amount: i64,
+        description: String,
+        counterparts: Vec<PostingRequest>,
+    ) -> ApiResult<()> {
+        let account = AccountKey::member_wallet(wallet.user_id, wallet.id);
+        let wallet_posting = if amount > 0 {
+            PostingRequest::credit(account, amount.unsigned_abs())
+        } else {
+            PostingRequest::debit(account, amount.unsigned_abs())
+        };
+        let mut postings = vec![wallet_posting];
+        postings.extend(counterparts);
+
+        self.ledger
+            .post_without_overdraft(
+                JournalEntryRequest {
+                    description,
+                    reference_id: Some(transaction_id),
+                    postings,
+                    created_by: None,
+                },
+                account,
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Fill in a wallet's balance from its ledger account
+    async fn with_balance(&self, mut wallet: Wallet) -> ApiResult<Wallet> {
+        wallet.balance = self
+            .ledger
+            .get_account_balance(AccountKey::member_wallet(wallet.user_id, wallet.id))
+            .await?
+            .to_u64()
+            .unwrap_or(0);
+        Ok(wallet)
+    }
+
+    async fn with_balances(&self, wallets: Vec<Wallet>) -> ApiResult<Vec<Wallet>> {
+        let mut filled = Vec::with_capacity(wallets.len());
+        for wallet in wallets {
+            filled.push(self.with_balance(wallet).await?);
+        }
+        Ok(filled)
+    }
+}
+
+fn transaction_type_label(transaction_type: &TransactionType) -> &'static str {
+    match transaction_type {
+        TransactionType::Deposit => "deposit",
+        TransactionType::Withdrawal => "withdrawal",
+        TransactionType::Transfer => "transfer",
+        TransactionType::Payment => "payment",
+        TransactionType::Contribution => "contribution",
+        TransactionType::LoanRepayment => "loan repayment",
+        TransactionType::SharePayment => "share payment",
+    }
+}
+
+#[async_trait]
+impl WalletsApi for RustWalletsApi {
+    async fn get_wallet(&self, wallet_id: Uuid) -> ApiResult<Wallet> {
+        let wallet = self
+            .state
+            .read()
+            .await
+            .wallets
+            .get(&wallet_id)
+            .cloned()
+            .ok_or_else(|| wallet_not_found(wallet_id))?;
+        self.with_balance(wallet).await
+    }
+
+    async fn get_user_wallets(&self, user_id: Uuid) -> ApiResult<Vec<Wallet>> {
+        let state = self.state.read().await;
+        let mut wallets: Vec<Wallet> = state
+            .wallets
+            .values()
+            .filter(|wallet| wallet.user_id == user_id)
+            .cloned()
+            .collect();
+        wallets.sort_by_key(|wallet| wallet.created_at);
+        self.with_balances(wallets).await
+    }
+
+    async fn get_wallets(
+        &self,
+        pagination: PaginationQuery,
+    ) -> ApiResult<PaginatedResponse<Wallet>> {
+        let state = self.state.read().await;
+        let mut wallets: Vec<Wallet> = state.wallets.values().cloned().collect();
+        wallets.sort_by_key(|wallet| wallet.created_at);
+
+        let mut page = PaginatedResponse::paginate(wallets, &pagination);
+        page.data = self.with_balances(page.data).await?;
+        Ok(page)
+    }
+
+    async fn create_wallet(&self, request: CreateWalletRequest) -> ApiResult<Wallet> {
+        if request.name.trim().is_empty() {
+            return Err(ApiError::Validation {
+                message: "Wallet name is required".to_string(),
+            });
+        }
+
+        let now = Utc::now();
+        let wallet = Wallet {
+            id: Uuid::new_v4(),
+            user_id: request.user_id,
+            name: request.name.trim().to_string(),
+            balance: 0,
+            wallet_type: request.wallet_type,
+            created_at: now,
+            updated_at: now,
+        };
+
+        let mut state = self.state.write().await;
+        state.log.append(&[WalletRecord::Wallet(wallet.clone())])?;
+        state.wallets.insert(wallet.id, wallet.clone());
+        Ok(wallet)
+    }
+
+    async fn delete_wallet(&self, wallet_id: Uuid) -> ApiResult<()> {
+        let mut state = self.state.write().await;
+        let wallet = state
+            .wallets
+            .get(&wallet_id)
+            .cloned()
+            .ok_or_else(|| wallet_not_found(wallet_id))?;
+
+        if self.with_balance(wallet).await?.balance > 0 {
+            return Err(ApiError::Conflict {
+                message: "Wallets with a remaining balance cannot be deleted".to_string(),
+            });
+        }
+
+        state.log.append(&[WalletRecord::Deleted(wallet_id)])?;
+        state.wallets.remove(&wallet_id);
+        Ok(())
+    }
+
+    async fn get_wallet_transactions(
+        &self,
+        wallet_id: Uuid,
+        pagination: PaginationQuery,
+    ) -> ApiResult<PaginatedResponse<WalletTransaction>> {
+        let state = self.state.read().await;
+        if !state.wallets.contains_key(&wallet_id) {
+            return Err(wallet_not_found(wallet_id));
+        }
+
+        let mut transactions: Vec<WalletTransaction> = state
+            .transactions
+            .iter()
+            .filter(|tx| tx.wallet_id == wallet_id)
+            .cloned()
+            .collect();
+        transactions.sort_by_key(|tx| std::cmp::Reverse(tx.created_at));
+        Ok(PaginatedResponse::paginate(transactions, &pagination))
+    }
+
+    async fn get_transactions(
+        &self,
+        filter: TransactionFilter,
+        pagination: PaginationQuery,
+    ) -> ApiResult<PaginatedResponse<WalletTransaction>> {
+        let mut transactions: Vec<WalletTransaction> = self
+            .state
+            .read()
+            .await
+            .transactions
+            .iter()
+            .filter(|tx| filter.matches(tx))
+            .cloned()
+            .collect();
+        transactions.sort_by_key(|tx| std::cmp::Reverse(tx.created_at));
+        Ok(PaginatedResponse::paginate(transactions, &pagination))
+    }
+
+    async fn get_wallet_balance(&self, wallet_id: Uuid) -> ApiResult<u64> {
+        self.get_wallet(wallet_id)
+            .await
+            .map(|wallet| wallet.balance)
+    }
+}
+
+/// Sats as a signed wallet movement, refusing amounts too large to record
+pub(super) fn signed_sats(amount: u64) -> ApiResult<i64> {
+    i64::try_from(amount).map_err(|_| ApiError::Validation {
+        message: format!(
+            "{} sats is more than a wallet transaction can record",
+            amount
+        ),
+    })
+}
+
+/// Movements out of a wallet into a SACCO or chama account the member owes,
+/// which are not withdrawals
+fn is_internal_debit(transaction_type: &TransactionType) -> bool {
+    matches!(
+        transaction_type,
+        TransactionType::LoanRepayment
+            | TransactionType::Contribution
+            | TransactionType::SharePayment
+    )
+}
+
+fn zero_amount() -> ApiError {
+    ApiError::Validation {
+        message: "Transaction amount must be greater than zero".to_string(),
+    }
+}
+
+/// What the policy needs to judge a withdrawal: who is making it, how long the
+/// member has had a wallet and what has been withdrawn recently. Failed and
+/// cancelled withdrawals don't count.
+fn withdrawal_attempt(
+    state: &WalletsState,
+    wallet: &Wallet,
+    amount_sats: u64,
+    transaction_type: &TransactionType,
+    withdrawer: &User,
+) -> WithdrawalAttempt {
+    let now = Utc::now();
+    let day_ago = now - chrono::Duration::days(1);
+    let week_ago = now - chrono::Duration::days(7);
+
+    let mut day_sats_by_member: HashMap<Uuid, u64> = HashMap::new();
+    let mut member_week_sats = 0;
+    for tx in &state.transactions {
+        let counts = tx.amount < 0
+            && matches!(
+                tx.transaction_type,
+                TransactionType::Withdrawal | TransactionType::Payment
+            )
+            && matches!(
+                tx.status,
+                TransactionStatus::Pending | TransactionStatus::Confirmed
+            );
+        let Some(owner) = state.wallets.get(&tx.wallet_id).map(|w| w.user_id) else {
+            continue;
+        };
+        if !counts || tx.created_at < week_ago {
+            continue;
+        }
+        if owner == wallet.user_id {
+            member_week_sats += tx.amount.unsigned_abs();
+        }
+        if tx.created_at >= day_ago {
+            *day_sats_by_member.entry(owner).or_default() += tx.amount.unsigned_abs();
+        }
+    }
+
+    WithdrawalAttempt {
+        user_id: wallet.user_id,
+        wallet_id: wallet.id,
+        amount_sats,
+        transaction_type: transaction_type.clone(),
+        // Held to the owner's limits whoever withdraws. Only the owner's own
+        // session tells us their role, so anyone else acting on the wallet is
+        // held to member limits.
+        role: if withdrawer.id == wallet.user_id {
+            withdrawer
+                .roles
+                .iter()
+                .copied()
+                .max_by_key(|role| *role as u8)
+                .unwrap_or(Role::Member)
+        } else {
+            Role::Member
+        },
+        acted_by: withdrawer.id,
+        member_since: state
+            .wallets
+            .values()
+            .filter(|w| w.user_id == wallet.user_id)
+            .map(|w| w.created_at)
+            .min()
+            .unwrap_or(wallet.created_at),
+        day_sats_by_member,
+        member_week_sats,
+    }
+}
+
+fn wallet_not_found(wallet_id: Uuid) -> ApiError {
+    ApiError::NotFound {
+        resource: format!("Wallet {}", wallet_id),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::traits::wallets::WalletType;
+
+    fn user(id: Uuid) -> User {
+        User {
+            id,
+            phone: None,
+            nostr: None,
+            profile: None,
+            roles: vec![Role::Member],
+            verified: true,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_wallets_and_balances_survive_restart() {
+        let dir = std::env::temp_dir().join(format!("bitsacco-wallets-{}", Uuid::new_v4()));
+        let owner = user(Uuid::new_v4());
+
+        let ledger = RustLedgerApi::new()
+            .with_log(JsonLog::at(dir.join("ledger.jsonl")))
+            .unwrap();
+        let wallets = RustWalletsApi::new(ledger)
+            .with_log(JsonLog::at(dir.join("wallets.jsonl")))
+            .unwrap();
+        let wallet = wallets
+            .create_wallet(CreateWalletRequest {
+                user_id: owner.id,
+                name: "Savings".to_string(),
+                wallet_type: WalletType::Fedimint,
+            })
+            .await
+            .unwrap();
+        wallets
+            .credit(wallet.id, 10_000, TransactionType::Deposit)
+            .await
+            .unwrap();
+        let pending = wallets
+            .record_pending_credit(wallet.id, 5_000, TransactionType::Deposit)
+            .await
+            .unwrap();
+        wallets
+            .settle(pending.id, TransactionStatus::Confirmed)
+            .await
+            .unwrap();
+
+        let ledger = RustLedgerApi::new()
+            .with_log(JsonLog::at(dir.join("ledger.jsonl")))
+            .unwrap();
+        let restarted = RustWalletsApi::new(ledger)
+            .with_log(JsonLog::at(dir.join("wallets.jsonl")))
+            .unwrap();
+        assert_eq!(
+            restarted.get_wallet_balance(wallet.id).await.unwrap(),
+            15_000
+        );
+        // The settled deposit replaces its pending record rather than adding one
+        let state = restarted.state.read().await;
+        assert_eq!(state.transactions.len(), 2);
+        assert!(state
+            .transactions
+            .iter()
+            .all(|transaction| transaction.status == TransactionStatus::Confirmed));
+        drop(state);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_credit_and_debit_track_balance() {
+        let wallets = RustWalletsApi::new(RustLedgerApi::new());
+        let owner = user(Uuid::new_v4());
+        let wallet = wallets
+            .create_wallet(CreateWalletRequest {
+                user_id: owner.id,
+                name: "Savings".to_string(),
+                wallet_type: WalletType::Fedimint,
+            })
+            .await
+            .unwrap();
+
+        wallets
+            .credit(wallet.id, 10_000, TransactionType::Deposit)
+            .await
+            .unwrap();
+        wallets
+            .debit(wallet.id, 2_500, TransactionType::Withdrawal, &owner)
+            .await
+            .unwrap();
+        assert_eq!(wallets.get_wallet_balance(wallet.id).await.unwrap(), 7_500);
+
+        let overdraft = wallets
+            .debit(wallet.id, 7_501, TransactionType::Withdrawal, &owner)
+            .await;
+        assert!(matches!(overdraft, Err(ApiError::Validation { .. })));
+
+        // Amounts that don't fit a signed transaction are refused, not wrapped
+        for amount in [u64::MAX, i64::MAX as u64 + 1] {
+            let huge = wallets
+                .credit(wallet.id, amount, TransactionType::Deposit)
+                .await;
+            assert!(matches!(huge, Err(ApiError::Validation { .. })));
+            let huge = wallets
+                .debit(wallet.id, amount, TransactionType::Withdrawal, &owner)
+                .await;
+            assert!(matches!(huge, Err(ApiError::Validation { .. })));
+        }
+        assert_eq!(wallets.get_wallet_balance(wallet.id).await.unwrap(), 7_500);
+
+        // Only the owner or an admin withdraws, and withdrawals always name who
+        let stranger = wallets
+            .debit(
+                wallet.id,
+                100,
+                TransactionType::Withdrawal,
+                &user(Uuid::new_v4()),
+            )
+            .await;
+        assert!(matches!(stranger, Err(ApiError::Authorization { .. })));
+        let anonymous = wallets
+            .post(wallet.id, -100, TransactionType::Payment, Vec::new())
+            .await;
+        assert!(matches!(anonymous, Err(ApiError::Validation { .. })));
+        let transfer = wallets
+            .post(wallet.id, -100, TransactionType::Transfer, Vec::new())
+            .await;
+        assert!(matches!(transfer, Err(ApiError::Validation { .. })));
+
+        let history = wallets
+            .get_wallet_transactions(wallet.id, PaginationQuery::default())
+            .await
+            .unwrap();
+        assert_eq!(history.total, 2);
+        assert_eq!(history.data.iter().map(|tx| tx.amount).sum::<i64>(), 7_500);
+    }
+
+    #[tokio::test]
+    async fn test_transactions_are_searched_across_wallets() {
+        let wallets = RustWalletsApi::new(RustLedgerApi::new());
+        let owner = user(Uuid::new_v4());
+        let mut ids = Vec::new();
+        for name in ["Savings", "Spending"] {
+            let wallet = wallets
+                .create_wallet(CreateWalletRequest {
+                    user_id: owner.id,
+                    name: name.to_string(),
+                    wallet_type: WalletType::Fedimint,
+                })
+                .await
+                .unwrap();
+            wallets
+                .credit(wallet.id, 5_000, TransactionType::Deposit)
+                .await
+                .unwrap();
+            ids.push(wallet.id);
+        }
+        wallets
+            .debit(ids[0], 1_000, TransactionType::Withdrawal, &owner)
+            .await
+            .unwrap();
+
+        let search = |filter: TransactionFilter| {
+            let wallets = &wallets;
+            async move {
+                wallets
+                    .get_transactions(filter, PaginationQuery::default())
+                    .await
+                    .unwrap()
+            }
+        };
+        let all = search(TransactionFilter::default()).await;
+        assert_eq!(all.total, 3);
+        assert_eq!(all.data[0].transaction_type, TransactionType::Withdrawal);
+
+        let deposits = search(TransactionFilter {
+            transaction_type: Some(TransactionType::Deposit),
+            ..Default::default()
+        })
+        .await;
+        assert_eq!(deposits.total, 2);
+
+        let failed = search(TransactionFilter {
+            status: Some(TransactionStatus::Failed),
+            ..Default::default()
+        })
+        .await;
+        assert_eq!(failed.total, 0);
+
+        let tomorrow = chrono::Utc::now() + chrono::Duration::days(1);
+        let future = search(TransactionFilter {
+            from: Some(tomorrow),
+            ..Default::default()
+        })
+        .await;
+        assert_eq!(future.total, 0);
+        let until_tomorrow = search(TransactionFilter {
+            to: Some(tomorrow),
+            ..Default::default()
+        })
+        .await;
+        assert_eq!(until_tomorrow.total, 3);
+    }
+}
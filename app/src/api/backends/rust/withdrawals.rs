@@ -0,0 +1,772 @@
+// Withdrawal limits and velocity controls for the Rust backend
+// The wallet store asks the policy about every outgoing withdrawal or payment
+// before posting it, passing in what the member has already withdrawn and the
+// role of the wallet's owner. Refusals are logged as they are made
+// and allowed withdrawals once they are posted, which is also when an approval is
+// used up. Withdrawals over the approval threshold wait for an admin, after which
+// the same withdrawal can be tried again. Limits, chama memberships, the decision
+// log and approvals are kept in a JSON store so they survive restarts; the log
+// keeps the most recent decisions only.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use tokio::sync::{Mutex, RwLock};
+use uuid::Uuid;
+
+use super::JsonStore;
+use crate::api::{
+    errors::{ApiError, ApiResult},
+    traits::{
+        wallets::TransactionType,
+        withdrawals::{
+            evaluate_withdrawal, ApprovalStatus, DecisionOutcome, GroupLimits, RoleLimits,
+            WithdrawalApproval, WithdrawalContext, WithdrawalDecision, WithdrawalLimits,
+            WithdrawalPolicy, WithdrawalPolicyApi,
+        },
+    },
+    types::{common::Role, User},
+};
+
+/// A withdrawal about to be posted and what the member has withdrawn so far
+#[derive(Debug, Clone)]
+pub struct WithdrawalAttempt {
+    /// Owner of the wallet
+    pub user_id: Uuid,
+    pub wallet_id: Uuid,
+    pub amount_sats: u64,
+    pub transaction_type: TransactionType,
+    /// Role of the wallet's owner, whose limits apply
+    pub role: Role,
+    /// Signed-in user making the withdrawal
+    pub acted_by: Uuid,
+    /// When the member's first wallet was opened
+    pub member_since: DateTime<Utc>,
+    /// Sats each member has withdrawn in the last 24 hours
+    pub day_sats_by_member: HashMap<Uuid, u64>,
+    /// Sats this member has withdrawn in the last 7 days
+    pub member_week_sats: u64,
+}
+
+/// Decisions kept in the log; older ones are dropped as new ones are made
+const MAX_DECISIONS: usize = 10_000;
+
+#[derive(Clone, Default, serde::Serialize, serde::Deserialize)]
+struct PolicyState {
+    policy: WithdrawalPolicy,
+    group_members: HashMap<Uuid, HashSet<Uuid>>,
+    decisions: Vec<WithdrawalDecision>,
+    approvals: Vec<WithdrawalApproval>,
+    /// Bumped on every change, so an older snapshot is never saved over a newer one
+    #[serde(skip)]
+    version: u64,
+}
+
+impl PolicyState {
+    fn log_decision(&mut self, decision: WithdrawalDecision) {
+        self.decisions.push(decision);
+        let excess = self.decisions.len().saturating_sub(MAX_DECISIONS);
+        self.decisions.drain(..excess);
+    }
+
+    /// Copy of the state to save once the lock is released
+    fn snapshot(&mut self) -> PolicyState {
+        self.version += 1;
+        self.clone()
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct RustWithdrawalPolicy {
+    state: Arc<RwLock<PolicyState>>,
+    store: JsonStore,
+    /// Version of the state last written to the store
+    saved: Arc<Mutex<u64>>,
+}
+
+impl RustWithdrawalPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keep limits, decisions and approvals in `store`, starting from what it
+    /// already holds
+    pub fn with_store(mut self, store: JsonStore) -> ApiResult<Self> {
+        let state: PolicyState = store.load()?;
+        self.state = Arc::new(RwLock::new(state));
+        self.store = store;
+        Ok(self)
+    }
+
+    /// Apply an admin's change, undoing it if it can't be saved
+    async fn update<T>(
+        &self,
+        change: impl FnOnce(&mut PolicyState) -> ApiResult<T>,
+    ) -> ApiResult<T> {
+        // Saved under the lock so the change can be undone; admin changes are rare
+        let mut state = self.state.write().await;
+        let previous = state.clone();
+        let changed = change(&mut state)?;
+        state.version += 1;
+        let mut saved = self.saved.lock().await;
+        if let Err(e) = self.store.save(&*state) {
+            *state = previous;
+            return Err(e);
+        }
+        *saved = state.version;
+        Ok(changed)
+    }
+
+    /// Save a snapshot taken after a withdrawal was decided or posted, outside
+    /// the state lock so withdrawals don't wait on the disk. The withdrawal stands
+    /// either way, so a failed save is logged rather than returned.
+    async fn persist(&self, snapshot: PolicyState) {
+        let mut saved = self.saved.lock().await;
+        // A later change has already been written
+        if snapshot.version <= *saved {
+            return;
+        }
+        match self.store.save(&snapshot) {
+            Ok(()) => *saved = snapshot.version,
+            Err(e) => tracing::warn!("Could not save the withdrawal policy state: {}", e),
+        }
+    }
+
+    /// Record that members belong to a chama, so its limits apply to them
+    pub async fn add_group_members(
+        &self,
+        group_id: Uuid,
+        user_ids: impl IntoIterator<Item = Uuid>,
+    ) {
+        let mut state = self.state.write().await;
+        state
+            .group_members
+            .entry(group_id)
+            .or_default()
+            .extend(user_ids);
+        let snapshot = state.snapshot();
+        drop(state);
+        self.persist(snapshot).await;
+    }
+
+    /// Decide on a withdrawal. Refusals and withdrawals waiting for approval are
+    /// logged and come back as authorization errors; an allowed withdrawal is only
+    /// logged, and its approval used, once it is passed to `commit`.
+    pub async fn check(&self, attempt: WithdrawalAttempt) -> ApiResult<WithdrawalDecision> {
+        let now = Utc::now();
+        let mut state = self.state.write().await;
+
+        let groups: Vec<Uuid> = state
+            .group_members
+            .iter()
+            .filter(|(_, members)| members.contains(&attempt.user_id))
+            .map(|(group_id, _)| *group_id)
+            .collect();
+        let group_day_sats = groups
+            .iter()
+            .map(|group_id| {
+                let used = state.group_members[group_id]
+                    .iter()
+                    .filter_map(|member| attempt.day_sats_by_member.get(member))
+                    .sum();
+                (*group_id, used)
+            })
+            .collect();
+        let approval = state.approvals.iter().position(|approval| {
+            approval.wallet_id == attempt.wallet_id
+                && approval.amount_sats == attempt.amount_sats
+                && approval.is_usable(now)
+        });
+
+        let context = WithdrawalContext {
+            amount_sats: attempt.amount_sats,
+            role: attempt.role,
+            groups,
+            member_since: attempt.member_since,
+            member_day_sats: attempt
+                .day_sats_by_member
+                .get(&attempt.user_id)
+                .copied()
+                .unwrap_or(0),
+            member_week_sats: attempt.member_week_sats,
+            group_day_sats,
+            approved: approval.is_some(),
+        };
+        let (outcome, reasons) = evaluate_withdrawal(&state.policy, &context, now);
+
+        let approval_id = match outcome {
+            DecisionOutcome::Allowed => approval.map(|position| state.approvals[position].id),
+            DecisionOutcome::NeedsApproval => Some(
+                match state.approvals.iter().find(|approval| {
+                    approval.wallet_id == attempt.wallet_id
+                        && approval.amount_sats == attempt.amount_sats
+                        && approval.status == ApprovalStatus::Pending
+                }) {
+                    Some(pending) => pending.id,
+                    None => {
+                        let approval = WithdrawalApproval {
+                            id: Uuid::new_v4(),
+                            user_id: attempt.user_id,
+                            requested_by: attempt.acted_by,
+                            wallet_id: attempt.wallet_id,
+                            amount_sats: attempt.amount_sats,
+                            transaction_type: attempt.transaction_type.clone(),
+                            status: ApprovalStatus::Pending,
+                            requested_at: now,
+                            reviewed_by: None,
+                            reviewed_at: None,
+                            note: None,
+                        };
+                        state.approvals.push(approval.clone());
+                        approval.id
+                    }
+                },
+            ),
+            DecisionOutcome::Denied => None,
+        };
+
+        let decision = WithdrawalDecision {
+            id: Uuid::new_v4(),
+            user_id: attempt.user_id,
+            acted_by: attempt.acted_by,
+            wallet_id: attempt.wallet_id,
+            amount_sats: attempt.amount_sats,
+            transaction_type: attempt.transaction_type,
+            outcome,
+            reasons,
+            approval_id,
+            decided_at: now,
+        };
+        let snapshot = (decision.outcome != DecisionOutcome::Allowed).then(|| {
+            state.log_decision(decision.clone());
+            state.snapshot()
+        });
+        drop(state);
+        if let Some(snapshot) = snapshot {
+            self.persist(snapshot).await;
+        }
+
+        match decision.outcome {
+            DecisionOutcome::Allowed => Ok(decision),
+            DecisionOutcome::Denied => {
+                tracing::warn!(
+                    "Withdrawal of {} sats from wallet {} refused: {}",
+                    decision.amount_sats,
+                    decision.wallet_id,
+                    decision.reasons.join("; ")
+                );
+                Err(ApiError::Authorization {
+                    message: format!("Withdrawal refused: {}", decision.reasons.join("; ")),
+                })
+            }
+            DecisionOutcome::NeedsApproval => Err(ApiError::Authorization {
+                message: format!(
+                    "{}; approval request {} is waiting for an admin",
+                    decision.reasons.join("; "),
+                    decision.approval_id.unwrap_or_default()
+                ),
+            }),
+        }
+    }
+
+    /// Log an allowed withdrawal once it has been posted. An approval lets one
+    /// withdrawal through, so the approval it used is spent here rather than
+    /// when the withdrawal was checked, in case posting failed.
+    pub async fn commit(&self, decision: WithdrawalDecision) {
+        let mut state = self.state.write().await;
+        if let Some(approval) = decision.approval_id.and_then(|id| {
+            state
+                .approvals
+                .iter_mut()
+                .find(|approval| approval.id == id)
+        }) {
+            approval.status = ApprovalStatus::Used;
+        }
+        state.log_decision(decision);
+        let snapshot = state.snapshot();
+        drop(state);
+        self.persist(snapshot).await;
+    }
+}
+
+#[async_trait]
+impl WithdrawalPolicyApi for RustWithdrawalPolicy {
+    async fn get_policy(&self) -> ApiResult<WithdrawalPolicy> {
+        Ok(self.state.read().await.policy.clone())
+    }
+
+    async fn set_role_limits(
+        &self,
+        role: Role,
+        limits: WithdrawalLimits,
+    ) -> ApiResult<WithdrawalPolicy> {
+        self.update(|state| {
+            state.policy.roles.retain(|entry| entry.role != role);
+            state.policy.roles.push(RoleLimits { role, limits });
+            state.policy.roles.sort_by_key(|entry| entry.role as u8);
+            Ok(state.policy.clone())
+        })
+        .await
+    }
+
+    async fn set_group_limits(&self, limits: GroupLimits) -> ApiResult<WithdrawalPolicy> {
+        self.update(|state| {
+            state
+                .policy
+                .groups
+                .retain(|entry| entry.group_id != limits.group_id);
+            state.policy.groups.push(limits);
+            Ok(state.policy.clone())
+        })
+        .await
+    }
+
+    async fn remove_group_limits(&self, group_id: Uuid) -> ApiResult<WithdrawalPolicy> {
+        self.update(|state| {
+            let before = state.policy.groups.len();
+            state
+                .policy
+                .groups
+                .retain(|entry| entry.group_id != group_id);
+            if state.policy.groups.len() == before {
+                return Err(ApiError::NotFound {
+                    resource: format!("Withdrawal limits for group {}", group_id),
+                });
+            }
+            Ok(state.policy.clone())
+        })
+        .await
+    }
+
+    async fn get_decisions(
+        &self,
+        user_id: Option<Uuid>,
+        limit: usize,
+    ) -> ApiResult<Vec<WithdrawalDecision>> {
+        Ok(self
+            .state
+            .read()
+            .await
+            .decisions
+            .iter()
+            .rev()
+            .filter(|decision| user_id.is_none_or(|id| decision.user_id == id))
+            .take(limit)
+            .cloned()
+            .collect())
+    }
+
+    async fn get_approvals(
+        &self,
+        status: Option<ApprovalStatus>,
+    ) -> ApiResult<Vec<WithdrawalApproval>> {
+        Ok(self
+            .state
+            .read()
+            .await
+            .approvals
+            .iter()
+            .rev()
+            .filter(|approval| status.is_none_or(|status| approval.status == status))
+            .cloned()
+            .collect())
+    }
+
+    async fn review_approval(
+        &self,
+        approval_id: Uuid,
+        reviewer: &User,
+        approve: bool,
+        note: Option<String>,
+    ) -> ApiResult<WithdrawalApproval> {
+        if !reviewer.is_admin() {
+            return Err(ApiError::Authorization {
+                message: "Only admins can review withdrawals".to_string(),
+            });
+        }
+
+        self.update(|state| {
+            let approval = state
+                .approvals
+                .iter_mut()
+                .find(|approval| approval.id == approval_id)
+                .ok_or_else(|| ApiError::NotFound {
+                    resource: format!("Withdrawal approval {}", approval_id),
+                })?;
+            if approval.user_id == reviewer.id {
+                return Err(ApiError::Authorization {
+                    message: "Members cannot approve their own withdrawals".to_string(),
+                });
+            }
+            if approval.requested_by == reviewer.id {
+                return Err(ApiError::Authorization {
+                    message: "Admins cannot approve withdrawals they made".to_string(),
+                });
+            }
+            if approval.status != ApprovalStatus::Pending {
+                return Err(ApiError::Conflict {
+                    message: format!(
+                        "Withdrawal approval {} has already been reviewed",
+                        approval_id
+                    ),
+                });
+            }
+
+            approval.status = if approve {
+                ApprovalStatus::Approved
+            } else {
+                ApprovalStatus::Rejected
+            };
+            approval.reviewed_by = Some(reviewer.id);
+            approval.reviewed_at = Some(Utc::now());
+            approval.note = note.filter(|note| !note.trim().is_empty());
+            Ok(approval.clone())
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::{
+        backends::rust::{RustLedgerApi, RustWalletsApi},
+        traits::wallets::{CreateWalletRequest, WalletType, WalletsApi},
+    };
+
+    async fn funded_wallet(wallets: &RustWalletsApi, user_id: Uuid, sats: u64) -> Uuid {
+        let wallet = wallets
+            .create_wallet(CreateWalletRequest {
+                user_id,
+                name: "Savings".to_string(),
+                wallet_type: WalletType::Fedimint,
+            })
+            .await
+            .unwrap();
+        wallets
+            .credit(wallet.id, sats, TransactionType::Deposit)
+            .await
+            .unwrap();
+        wallet.id
+    }
+
+    fn user(role: Role) -> User {
+        User {
+            id: Uuid::new_v4(),
+            phone: None,
+            nostr: None,
+            profile: None,
+            roles: vec![role],
+            verified: true,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_limits_refuse_and_log_withdrawals() {
+        let wallets = RustWalletsApi::new(RustLedgerApi::new());
+        let policy = wallets.policy().clone();
+        // Bob is an admin, so is held to the Admin limits
+        let (alice, bob) = (user(Role::Member), user(Role::Admin));
+        let alice_wallet = funded_wallet(&wallets, alice.id, 100_000).await;
+        let bob_wallet = funded_wallet(&wallets, bob.id, 100_000).await;
+
+        policy
+            .set_role_limits(
+                Role::Member,
+                WithdrawalLimits {
+                    daily_sats: Some(10_000),
+                    ..WithdrawalLimits::default()
+                },
+            )
+            .await
+            .unwrap();
+        wallets
+            .debit(alice_wallet, 6_000, TransactionType::Withdrawal, &alice)
+            .await
+            .unwrap();
+        let over = wallets
+            .debit(alice_wallet, 5_000, TransactionType::Withdrawal, &alice)
+            .await;
+        assert!(matches!(over, Err(ApiError::Authorization { .. })));
+        assert_eq!(
+            wallets.get_wallet_balance(alice_wallet).await.unwrap(),
+            94_000
+        );
+
+        wallets
+            .debit(bob_wallet, 20_000, TransactionType::Payment, &bob)
+            .await
+            .unwrap();
+
+        // The chama limit counts what every member has taken out today
+        let group_id = Uuid::new_v4();
+        policy.add_group_members(group_id, [alice.id, bob.id]).await;
+        policy
+            .set_group_limits(GroupLimits {
+                group_id,
+                member_limits: WithdrawalLimits::default(),
+                chama_daily_sats: Some(30_000),
+            })
+            .await
+            .unwrap();
+        let over = wallets
+            .debit(bob_wallet, 5_000, TransactionType::Withdrawal, &bob)
+            .await;
+        assert!(matches!(over, Err(ApiError::Authorization { .. })));
+
+        // New accounts wait out the cooldown
+        policy
+            .set_role_limits(
+                Role::Member,
+                WithdrawalLimits {
+                    cooldown_hours: 48,
+                    ..WithdrawalLimits::default()
+                },
+            )
+            .await
+            .unwrap();
+        let carol = user(Role::Member);
+        let carol_wallet = funded_wallet(&wallets, carol.id, 1_000).await;
+        let early = wallets
+            .debit(carol_wallet, 100, TransactionType::Withdrawal, &carol)
+            .await;
+        assert!(matches!(early, Err(ApiError::Authorization { .. })));
+
+        let decisions = policy.get_decisions(None, 10).await.unwrap();
+        let outcomes: Vec<_> = decisions.iter().map(|d| d.outcome).collect();
+        assert_eq!(
+            outcomes,
+            vec![
+                DecisionOutcome::Denied,
+                DecisionOutcome::Denied,
+                DecisionOutcome::Allowed,
+                DecisionOutcome::Denied,
+                DecisionOutcome::Allowed,
+            ]
+        );
+        assert!(decisions[0].reasons[0].starts_with("New accounts"));
+        assert!(decisions[1].reasons[0].starts_with("Chama"));
+        assert_eq!(
+            policy
+                .get_decisions(Some(alice.id), 10)
+                .await
+                .unwrap()
+                .len(),
+            2
+        );
+
+        // The log keeps only the most recent decisions
+        let mut state = PolicyState::default();
+        for _ in 0..=MAX_DECISIONS {
+            state.log_decision(decisions[0].clone());
+        }
+        assert_eq!(state.decisions.len(), MAX_DECISIONS);
+    }
+
+    #[tokio::test]
+    async fn test_admin_withdrawals_use_owner_limits() {
+        let wallets = RustWalletsApi::new(RustLedgerApi::new());
+        let policy = wallets.policy().clone();
+        let (member, admin) = (user(Role::Member), user(Role::Admin));
+        let wallet_id = funded_wallet(&wallets, member.id, 100_000).await;
+        policy
+            .set_role_limits(
+                Role::Member,
+                WithdrawalLimits {
+                    daily_sats: Some(10_000),
+                    approval_above_sats: Some(5_000),
+                    ..WithdrawalLimits::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        // Admins are unlimited, but not when withdrawing from a member's wallet
+        let over = wallets
+            .debit(wallet_id, 20_000, TransactionType::Withdrawal, &admin)
+            .await;
+        assert!(matches!(over, Err(ApiError::Authorization { .. })));
+        let large = wallets
+            .debit(wallet_id, 8_000, TransactionType::Withdrawal, &admin)
+            .await;
+        assert!(matches!(large, Err(ApiError::Authorization { .. })));
+        wallets
+            .debit(wallet_id, 1_000, TransactionType::Withdrawal, &admin)
+            .await
+            .unwrap();
+
+        let decisions = policy.get_decisions(Some(member.id), 10).await.unwrap();
+        assert_eq!(decisions.len(), 3);
+        assert!(decisions
+            .iter()
+            .all(|decision| decision.user_id == member.id && decision.acted_by == admin.id));
+
+        // The admin who asked can't approve their own request
+        let pending = policy
+            .get_approvals(Some(ApprovalStatus::Pending))
+            .await
+            .unwrap();
+        assert_eq!(pending[0].requested_by, admin.id);
+        let self_review = policy
+            .review_approval(pending[0].id, &admin, true, None)
+            .await;
+        assert!(matches!(self_review, Err(ApiError::Authorization { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_large_withdrawals_wait_for_approval() {
+        let wallets = RustWalletsApi::new(RustLedgerApi::new());
+        let policy = wallets.policy().clone();
+        let member = user(Role::Member);
+        let wallet_id = funded_wallet(&wallets, member.id, 100_000).await;
+        policy
+            .set_role_limits(
+                Role::Member,
+                WithdrawalLimits {
+                    approval_above_sats: Some(50_000),
+                    ..WithdrawalLimits::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        let first = wallets
+            .debit(wallet_id, 60_000, TransactionType::Withdrawal, &member)
+            .await;
+        assert!(matches!(first, Err(ApiError::Authorization { .. })));
+        // Retrying before review does not open a second request
+        let _ = wallets
+            .debit(wallet_id, 60_000, TransactionType::Withdrawal, &member)
+            .await;
+        let pending = policy
+            .get_approvals(Some(ApprovalStatus::Pending))
+            .await
+            .unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].user_id, member.id);
+
+        let mut own = user(Role::Admin);
+        own.id = member.id;
+        let self_review = policy
+            .review_approval(pending[0].id, &own, true, None)
+            .await;
+        assert!(matches!(self_review, Err(ApiError::Authorization { .. })));
+
+        let reviewer = user(Role::Admin);
+        let approved = policy
+            .review_approval(pending[0].id, &reviewer, true, Some("Checked".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(approved.status, ApprovalStatus::Approved);
+        assert_eq!(approved.reviewed_by, Some(reviewer.id));
+        let again = policy
+            .review_approval(pending[0].id, &reviewer, false, None)
+            .await;
+        assert!(matches!(again, Err(ApiError::Conflict { .. })));
+
+        // An approved withdrawal the wallet can't cover leaves the approval unused
+        wallets
+            .debit(wallet_id, 50_000, TransactionType::Withdrawal, &member)
+            .await
+            .unwrap();
+        let overdraft = wallets
+            .debit(wallet_id, 60_000, TransactionType::Withdrawal, &member)
+            .await;
+        assert!(matches!(overdraft, Err(ApiError::Validation { .. })));
+        let approvals = policy.get_approvals(None).await.unwrap();
+        assert_eq!(approvals[0].status, ApprovalStatus::Approved);
+        let logged = policy.get_decisions(Some(member.id), 10).await.unwrap();
+        assert_eq!(logged.len(), 3);
+
+        wallets
+            .credit(wallet_id, 10_000, TransactionType::Deposit)
+            .await
+            .unwrap();
+        let paid = wallets
+            .debit(wallet_id, 60_000, TransactionType::Withdrawal, &member)
+            .await
+            .unwrap();
+        let approvals = policy.get_approvals(None).await.unwrap();
+        assert_eq!(approvals[0].status, ApprovalStatus::Used);
+        let logged = policy.get_decisions(Some(member.id), 1).await.unwrap();
+        assert_eq!(logged[0].outcome, DecisionOutcome::Allowed);
+        assert_eq!(logged[0].approval_id, Some(approvals[0].id));
+        assert_eq!(paid.amount, -60_000);
+
+        // The approval covered one withdrawal only
+        let topped_up = wallets
+            .credit(wallet_id, 60_000, TransactionType::Deposit)
+            .await;
+        assert!(topped_up.is_ok());
+        let repeat = wallets
+            .debit(wallet_id, 60_000, TransactionType::Withdrawal, &member)
+            .await;
+        assert!(matches!(repeat, Err(ApiError::Authorization { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_policy_survives_restart() {
+        let dir = std::env::temp_dir().join(format!("bitsacco-policy-{}", Uuid::new_v4()));
+        let path = dir.join("withdrawal-policy.json");
+        let member = user(Role::Member);
+        let group_id = Uuid::new_v4();
+
+        let policy = RustWithdrawalPolicy::new()
+            .with_store(JsonStore::at(&path))
+            .unwrap();
+        let wallets = RustWalletsApi::new(RustLedgerApi::new()).with_policy(policy.clone());
+        let wallet_id = funded_wallet(&wallets, member.id, 100_000).await;
+        policy
+            .set_role_limits(
+                Role::Member,
+                WithdrawalLimits {
+                    approval_above_sats: Some(50_000),
+                    ..WithdrawalLimits::default()
+                },
+            )
+            .await
+            .unwrap();
+        policy.add_group_members(group_id, [member.id]).await;
+        let _ = wallets
+            .debit(wallet_id, 60_000, TransactionType::Withdrawal, &member)
+            .await;
+
+        let restarted = RustWithdrawalPolicy::new()
+            .with_store(JsonStore::at(&path))
+            .unwrap();
+        assert_eq!(
+            restarted.get_policy().await.unwrap(),
+            policy.get_policy().await.unwrap()
+        );
+        assert!(restarted.state.read().await.group_members[&group_id].contains(&member.id));
+        assert_eq!(restarted.get_decisions(None, 10).await.unwrap().len(), 1);
+        let pending = restarted
+            .get_approvals(Some(ApprovalStatus::Pending))
+            .await
+            .unwrap();
+        assert_eq!(pending.len(), 1);
+        restarted
+            .review_approval(pending[0].id, &user(Role::Admin), true, None)
+            .await
+            .unwrap();
+        let reloaded = RustWithdrawalPolicy::new()
+            .with_store(JsonStore::at(&path))
+            .unwrap();
+        let approvals = reloaded.get_approvals(None).await.unwrap();
+        assert_eq!(approvals[0].status, ApprovalStatus::Approved);
+
+        // A change that can't be saved is undone
+        std::fs::remove_dir_all(&dir).unwrap();
+        std::fs::write(&dir, b"").unwrap();
+        let blocked = reloaded
+            .set_role_limits(Role::Admin, WithdrawalLimits::default())
+            .await;
+        assert!(blocked.is_err());
+        assert_eq!(reloaded.get_policy().await.unwrap().roles.len(), 1);
+        std::fs::remove_file(&dir).unwrap();
+    }
+}
@@ -0,0 +1,164 @@
+use crate::api::ApiError;
+use serde::{Deserialize, Serialize};
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+/// Whether calls to the NestJS API are currently let through
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CircuitState {
+    /// Calls go through as normal
+    Closed,
+    /// Too many consecutive failures; calls fail fast until the reset timeout passes
+    Open,
+    /// The reset timeout has passed; the next call decides whether to close or reopen
+    HalfOpen,
+}
+
+/// Point-in-time view of a breaker, for health reporting
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CircuitSnapshot {
+    pub state: CircuitState,
+    pub consecutive_failures: u32,
+    pub failure_threshold: u32,
+    /// Seconds until an open circuit lets a trial call through
+    pub retry_in_secs: Option<u64>,
+    pub last_failure: Option<String>,
+}
+
+#[derive(Debug, Default)]
+struct BreakerState {
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+    last_failure: Option<String>,
+}
+
+/// Stops hammering the NestJS API while it is down. Only network errors and
+/// 5xx responses count as failures; a 4xx still proves the API is up.
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    failure_threshold: u32,
+    reset_timeout: Duration,
+    state: Mutex<BreakerState>,
+}
+
+impl CircuitBreaker {
+    pub fn new(failure_threshold: u32, reset_timeout: Duration) -> Self {
+        Self {
+            failure_threshold: failure_threshold.max(1),
+            reset_timeout,
+            state: Mutex::new(BreakerState::default()),
+        }
+    }
+
+    /// Refuse the call if the circuit is open
+    pub fn try_acquire(&self) -> Result<(), ApiError> {
+        self.try_acquire_at(Instant::now())
+    }
+
+    /// Record the outcome of a call that `try_acquire` let through
+    pub fn record(&self, error: Option<&ApiError>) {
+        self.record_at(error, Instant::now())
+    }
+
+    pub fn snapshot(&self) -> CircuitSnapshot {
+        self.snapshot_at(Instant::now())
+    }
+
+    fn try_acquire_at(&self, now: Instant) -> Result<(), ApiError> {
+        let snapshot = self.snapshot_at(now);
+        match snapshot.state {
+            CircuitState::Open => Err(ApiError::NetworkError(format!(
+                "NestJS API circuit is open after {} consecutive failures; retrying in {}s",
+                snapshot.consecutive_failures,
+                snapshot.retry_in_secs.unwrap_or_default()
+            ))),
+            CircuitState::Closed | CircuitState::HalfOpen => Ok(()),
+        }
+    }
+
+    fn record_at(&self, error: Option<&ApiError>, now: Instant) {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        match error {
+            Some(error @ (ApiError::NetworkError(_) | ApiError::ServerError(_))) => {
+                state.consecutive_failures += 1;
+                state.last_failure = Some(error.to_string());
+                // A failed trial call reopens straight away
+                if state.consecutive_failures >= self.failure_threshold {
+                    state.opened_at = Some(now);
+                }
+            }
+            _ => *state = BreakerState::default(),
+        }
+    }
+
+    fn snapshot_at(&self, now: Instant) -> CircuitSnapshot {
+        let state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        let (circuit, retry_in) = match state.opened_at {
+            None => (CircuitState::Closed, None),
+            Some(opened_at) => {
+                let elapsed = now.saturating_duration_since(opened_at);
+                match self.reset_timeout.checked_sub(elapsed) {
+                    Some(remaining) if !remaining.is_zero() => {
+                        (CircuitState::Open, Some(remaining.as_secs().max(1)))
+                    }
+                    _ => (CircuitState::HalfOpen, None),
+                }
+            }
+        };
+        CircuitSnapshot {
+            state: circuit,
+            consecutive_failures: state.consecutive_failures,
+            failure_threshold: self.failure_threshold,
+            retry_in_secs: retry_in,
+            last_failure: state.last_failure.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_circuit_opens_and_recovers() {
+        let breaker = CircuitBreaker::new(2, Duration::from_secs(30));
+        let start = Instant::now();
+        let down = ApiError::NetworkError("connection refused".to_string());
+
+        // Client errors prove the API is up
+        breaker.record_at(Some(&ApiError::Unauthorized), start);
+        breaker.record_at(Some(&down), start);
+        assert_eq!(breaker.snapshot_at(start).state, CircuitState::Closed);
+        assert!(breaker.try_acquire_at(start).is_ok());
+
+        breaker.record_at(Some(&down), start);
+        let open = breaker.snapshot_at(start + Duration::from_secs(10));
+        assert_eq!(open.state, CircuitState::Open);
+        assert_eq!(open.retry_in_secs, Some(20));
+        assert!(breaker
+            .try_acquire_at(start + Duration::from_secs(10))
+            .is_err());
+
+        // A failed trial reopens for another full timeout
+        let trial = start + Duration::from_secs(30);
+        assert_eq!(breaker.snapshot_at(trial).state, CircuitState::HalfOpen);
+        assert!(breaker.try_acquire_at(trial).is_ok());
+        breaker.record_at(
+            Some(&ApiError::ServerError("Server error: 503".to_string())),
+            trial,
+        );
+        assert_eq!(
+            breaker.snapshot_at(trial + Duration::from_secs(29)).state,
+            CircuitState::Open
+        );
+
+        // A successful trial closes it
+        let retry = trial + Duration::from_secs(30);
+        assert!(breaker.try_acquire_at(retry).is_ok());
+        breaker.record_at(None, retry);
+        let closed = breaker.snapshot_at(retry);
+        assert_eq!(closed.state, CircuitState::Closed);
+        assert_eq!(closed.consecutive_failures, 0);
+        assert_eq!(closed.last_failure, None);
+    }
+}
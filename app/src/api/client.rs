@@ -7,7 +7,7 @@ use std::sync::OnceLock;
 static DASHBOARD_CLIENT: OnceLock<DashboardApiClient> = OnceLock::new();
 
 /// Get shared dashboard client instance
-fn get_dashboard_client() -> &'static DashboardApiClient {
+pub(crate) fn get_dashboard_client() -> &'static DashboardApiClient {
     DASHBOARD_CLIENT.get_or_init(DashboardApiClient::new)
 }
 
@@ -18,27 +18,32 @@ async fn extract_auth_token_from_request() -> Option<String> {
     use leptos_axum::extract;
 
     // Try to extract headers from request context
-    if let Ok(headers) = extract::<HeaderMap>().await {
-        if let Some(auth_header) = headers.get("authorization") {
-            if let Ok(auth_str) = auth_header.to_str() {
-                if let Some(token) = auth_str.strip_prefix("Bearer ") {
-                    return Some(token.to_string());
-                }
+    let headers = extract::<HeaderMap>().await.ok()?;
+    auth_token_from_headers(&headers)
+}
+
+/// Read the JWT from a bearer `Authorization` header or the auth cookie (SSR only)
+#[cfg(feature = "ssr")]
+pub fn auth_token_from_headers(headers: &axum::http::HeaderMap) -> Option<String> {
+    if let Some(auth_header) = headers.get("authorization") {
+        if let Ok(auth_str) = auth_header.to_str() {
+            if let Some(token) = auth_str.strip_prefix("Bearer ") {
+                return Some(token.to_string());
             }
         }
+    }
 
-        // Also check for token in cookies
-        if let Some(cookie_header) = headers.get("cookie") {
-            if let Ok(cookie_str) = cookie_header.to_str() {
-                // Simple cookie parsing for auth token
-                for part in cookie_str.split(';') {
-                    let part = part.trim();
-                    if let Some(token) = part.strip_prefix("auth_token=") {
-                        return Some(token.to_string());
-                    }
-                    if let Some(token) = part.strip_prefix("access_token=") {
-                        return Some(token.to_string());
-                    }
+    // Also check for token in cookies
+    if let Some(cookie_header) = headers.get("cookie") {
+        if let Ok(cookie_str) = cookie_header.to_str() {
+            // Simple cookie parsing for auth token
+            for part in cookie_str.split(';') {
+                let part = part.trim();
+                if let Some(token) = part.strip_prefix("auth_token=") {
+                    return Some(token.to_string());
+                }
+                if let Some(token) = part.strip_prefix("access_token=") {
+                    return Some(token.to_string());
                 }
             }
         }
@@ -54,6 +59,56 @@ async fn extract_auth_token_from_request() -> Option<String> {
     None
 }
 
+/// Checks session tokens against `JWT_SECRET`, as the NestJS client does (SSR only)
+#[cfg(feature = "ssr")]
+fn session_validator() -> &'static crate::api::backends::nestjs::JwtValidator {
+    static VALIDATOR: OnceLock<crate::api::backends::nestjs::JwtValidator> = OnceLock::new();
+    VALIDATOR.get_or_init(|| {
+        let secret = std::env::var("JWT_SECRET").unwrap_or_else(|_| "default_secret".to_string());
+        crate::api::backends::nestjs::JwtValidator::new(&secret)
+    })
+}
+
+/// Resolve the user a session token belongs to, rejecting forged or expired
+/// tokens (SSR only)
+#[cfg(feature = "ssr")]
+pub fn user_from_token(token: &str) -> Result<crate::api::types::User, String> {
+    crate::contexts::auth::verify_jwt_token(token, session_validator())
+}
+
+/// Resolve the signed-in user from the request's JWT; answers 401 when there
+/// is no valid token (SSR only)
+#[cfg(feature = "ssr")]
+pub async fn get_request_user() -> Result<crate::api::types::User, ServerFnError> {
+    let user = match extract_auth_token_from_request().await {
+        Some(token) => user_from_token(&token).map_err(|e| {
+            tracing::warn!("Rejected session token: {}", e);
+            "Invalid or expired session".to_string()
+        }),
+        None => Err("Authentication required".to_string()),
+    };
+    user.map_err(|message| {
+        if let Some(response) = use_context::<leptos_axum::ResponseOptions>() {
+            response.set_status(axum::http::StatusCode::UNAUTHORIZED);
+        }
+        ServerFnError::new(message)
+    })
+}
+
+/// Resolve the signed-in user and require the admin role; `action` finishes
+/// the sentence "Only admins can ..." (SSR only)
+#[cfg(feature = "ssr")]
+pub async fn require_admin(action: &str) -> Result<crate::api::types::User, ServerFnError> {
+    let user = get_request_user().await?;
+    if !user.is_admin() {
+        if let Some(response) = use_context::<leptos_axum::ResponseOptions>() {
+            response.set_status(axum::http::StatusCode::FORBIDDEN);
+        }
+        return Err(ServerFnError::new(format!("Only admins can {}", action)));
+    }
+    Ok(user)
+}
+
 #[derive(Debug, Clone)]
 pub enum ApiError {
     NetworkError(String),
@@ -124,46 +179,161 @@ pub struct ApiResponse<T> {
     pub errors: Option<Vec<String>>,
 }
 
-/// Server function for getting dashboard metrics from NestJS API
+/// Server function for the dashboard's sections. Each reports whether its figures
+/// are fresh, the last good ones from before an outage, or unavailable.
 #[server(GetDashboardMetrics, "/api", "GetJson")]
 pub async fn get_dashboard_metrics(
-) -> Result<ApiResponse<crate::pages::dashboard::DashboardMetrics>, ServerFnError> {
+) -> Result<ApiResponse<crate::pages::dashboard::DashboardSections>, ServerFnError> {
+    use crate::api::dashboard_client::SharesMetrics;
+    use crate::pages::dashboard::{DashboardMetrics, DashboardSections};
+    use crate::server::last_good::LastGood;
+
+    static LAST_OVERVIEW: LastGood<DashboardMetrics> = LastGood::new();
+    static LAST_SHARES: LastGood<SharesMetrics> = LastGood::new();
+
     // Extract auth token from the current request
     let auth_token = extract_auth_token_from_request().await;
     let client = get_dashboard_client();
 
-    match client.get_overview_with_auth(auth_token.as_deref()).await {
-        Ok(nestjs_response) => {
-            if nestjs_response.success {
-                let converted_metrics =
-                    convert_nestjs_overview_to_dashboard_metrics(nestjs_response.data);
+    let (overview, financial, offers) = tokio::join!(
+        client.get_overview_with_auth(auth_token.as_deref()),
+        client.get_financial_analytics_with_auth(auth_token.as_deref()),
+        fetch_share_offers(),
+    );
 
-                Ok(ApiResponse {
-                    success: true,
-                    data: Some(converted_metrics),
-                    message: Some("Dashboard metrics retrieved from NestJS API".to_string()),
-                    errors: None,
-                })
-            } else {
-                leptos::logging::warn!(
-                    "Dashboard: NestJS API returned success=false: {:?}",
-                    nestjs_response.message
-                );
-                Err(ServerFnError::new(format!(
-                    "NestJS API error: {:?}",
-                    nestjs_response.message
-                )))
-            }
-        }
-        Err(api_error) => {
-            leptos::logging::error!(
-                "Dashboard: Failed to connect to NestJS API: {:?}",
-                api_error
-            );
-            Err(ServerFnError::new(format!(
-                "API connection failed: {:?}",
-                api_error
-            )))
+    let shares = LAST_SHARES.settle(
+        section_data("Shares", financial).map(|(financial, meta)| (financial.shares, meta)),
+    );
+    let overview =
+        LAST_OVERVIEW.settle(section_data("Overview", overview).map(|(overview, meta)| {
+            let metrics =
+                convert_nestjs_overview_to_dashboard_metrics(overview, shares.data(), offers);
+            (metrics, meta)
+        }));
+
+    Ok(ApiResponse {
+        success: true,
+        data: Some(DashboardSections { overview, shares }),
+        message: None,
+        errors: None,
+    })
+}
+
+/// A dashboard response's data and metadata, or why there is none
+#[cfg(feature = "ssr")]
+fn section_data<T>(
+    section: &str,
+    response: Result<crate::api::dashboard_client::NestJsApiResponse<T>, ApiError>,
+) -> Result<(T, Option<crate::api::dashboard_client::ResponseMeta>), String> {
+    let reason = match response {
+        Ok(response) if response.success => return Ok((response.data, response.meta)),
+        Ok(response) => response
+            .message
+            .unwrap_or_else(|| "the backend reported a failure".to_string()),
+        Err(e) => e.to_string(),
+    };
+    leptos::logging::warn!("Dashboard: {} unavailable: {}", section, reason);
+    Err(reason)
+}
+
+/// Server function for the overview's critical alerts with any acknowledgement or
+/// snooze in force, unacknowledged alerts first
+#[server(GetSystemAlerts, "/api", "GetJson")]
+pub async fn get_system_alerts(
+) -> Result<Vec<crate::api::traits::alerts::SystemAlert>, ServerFnError> {
+    use crate::api::{backends::alerts_api, config::ApiConfig};
+
+    let auth_token = extract_auth_token_from_request().await;
+    let overview = get_dashboard_client()
+        .get_overview_with_auth(auth_token.as_deref())
+        .await;
+    let (overview, _) = section_data("Alerts", overview).map_err(ServerFnError::new)?;
+
+    // Alerts still show if the acknowledgements can't be read
+    let acknowledgements = match alerts_api(&ApiConfig::from_env()) {
+        Ok(api) => api.list_acknowledgements().await,
+        Err(e) => Err(e),
+    }
+    .unwrap_or_else(|e| {
+        leptos::logging::warn!("Dashboard: alert acknowledgements unavailable: {}", e);
+        Vec::new()
+    });
+    Ok(join_alert_acknowledgements(
+        overview.alerts.critical_alerts,
+        acknowledgements,
+    ))
+}
+
+/// Server function for acknowledging a system alert for every admin
+#[server(AcknowledgeAlert, "/api")]
+pub async fn acknowledge_alert(
+    alert_id: String,
+) -> Result<crate::api::traits::alerts::AlertAcknowledgement, ServerFnError> {
+    use crate::api::{backends::alerts_api, config::ApiConfig};
+
+    let user = require_admin("acknowledge alerts").await?;
+    let api = alerts_api(&ApiConfig::from_env()).map_err(|e| ServerFnError::new(e.to_string()))?;
+    api.acknowledge_alert(&alert_id, user.id)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))
+}
+
+/// Server function for silencing a system alert for `minutes`
+#[server(SnoozeAlert, "/api")]
+pub async fn snooze_alert(
+    alert_id: String,
+    minutes: u32,
+) -> Result<crate::api::traits::alerts::AlertAcknowledgement, ServerFnError> {
+    use crate::api::{backends::alerts_api, config::ApiConfig};
+
+    let user = require_admin("snooze alerts").await?;
+    let api = alerts_api(&ApiConfig::from_env()).map_err(|e| ServerFnError::new(e.to_string()))?;
+    api.snooze_alert(&alert_id, user.id, minutes)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))
+}
+
+/// Pair alerts with their acknowledgements: active alerts first, newest first within each
+#[cfg(feature = "ssr")]
+fn join_alert_acknowledgements(
+    alerts: Vec<crate::api::dashboard_client::AlertItem>,
+    acknowledgements: Vec<crate::api::traits::alerts::AlertAcknowledgement>,
+) -> Vec<crate::api::traits::alerts::SystemAlert> {
+    let mut joined: Vec<_> = alerts
+        .into_iter()
+        .map(|alert| crate::api::traits::alerts::SystemAlert {
+            acknowledgement: acknowledgements
+                .iter()
+                .find(|acknowledgement| acknowledgement.alert_id == alert.id)
+                .cloned(),
+            alert,
+        })
+        .collect();
+    joined.sort_by(|a, b| {
+        b.is_active()
+            .cmp(&a.is_active())
+            .then_with(|| b.alert.timestamp.cmp(&a.alert.timestamp))
+    });
+    joined
+}
+
+/// All share offers from the configured backend, or `None` if they can't be listed
+#[cfg(feature = "ssr")]
+async fn fetch_share_offers() -> Option<Vec<crate::api::traits::shares::ShareOffer>> {
+    use crate::api::{backends::shares_api, config::ApiConfig, types::PaginationQuery};
+
+    let api = shares_api(&ApiConfig::from_env()).ok()?;
+    match api
+        .get_share_offers(PaginationQuery {
+            page: Some(1),
+            limit: Some(u32::MAX),
+        })
+        .await
+    {
+        Ok(offers) => Some(offers.data),
+        Err(e) => {
+            leptos::logging::warn!("Dashboard: Failed to fetch share offers: {}", e);
+            None
         }
     }
 }
@@ -171,51 +341,78 @@ pub async fn get_dashboard_metrics(
 /// Convert NestJS overview response to current dashboard metrics format
 fn convert_nestjs_overview_to_dashboard_metrics(
     overview: crate::api::dashboard_client::DashboardOverviewResponse,
+    shares: Option<&crate::api::dashboard_client::SharesMetrics>,
+    offers: Option<Vec<crate::api::traits::shares::ShareOffer>>,
 ) -> crate::pages::dashboard::DashboardMetrics {
+    use crate::api::types::Money;
     use rust_decimal::Decimal;
 
+    let volume = overview.summary.total_volume.money;
+    let transaction_count = overview.summary.transaction_count.total;
+    let average_transaction_size = volume
+        .checked_div(Decimal::from(transaction_count))
+        .unwrap_or_else(|| Money::zero(&volume.currency));
+
+    let total_shares_in_circulation = shares.map(|s| Decimal::from(s.ownership.distributed_shares));
+    let share_price = offers.as_deref().and_then(latest_share_price);
+
     crate::pages::dashboard::DashboardMetrics {
+        total_members: overview.summary.total_members,
+        // Shareholder counts aren't reported by the backend yet; members aren't
+        // necessarily shareholders
         shareholders: crate::pages::dashboard::ShareholderSummary {
-            total_shareholders: overview.summary.total_members,
-            member_shareholders: overview.summary.total_members, // Estimate for now
-            group_shareholders: 0, // TODO: Get from NestJS when available
-            active_shareholders: overview.summary.active_members_today,
+            total_shareholders: None,
+            member_shareholders: None,
+            group_shareholders: None,
+            active_shareholders: None,
         },
         market: crate::pages::dashboard::MarketAnalytics {
-            // Use actual data from NestJS API without capping
-            total_market_value_kes: Decimal::from_f64_retain(overview.summary.total_volume.amount)
-                .unwrap_or_default(),
-            total_shares_in_circulation: Decimal::from(overview.summary.total_members), // Actual member count
-            share_price_kes: Decimal::from(1000), // Fixed at 1000 KES per share
-        },
-        offers: crate::pages::dashboard::ShareOfferAnalytics {
-            total_offers: 10,              // TODO: Get from NestJS shares metrics
-            active_offers: 5,              // TODO: Get from NestJS shares metrics
-            completed_offers: 5,           // TODO: Get from NestJS shares metrics
-            average_completion_rate: 75.0, // TODO: Calculate from actual data
+            total_market_value: total_shares_in_circulation
+                .zip(share_price)
+                .map(|(shares, price)| Money::kes(shares * price)),
+            total_shares_in_circulation,
+            share_price: share_price.map(Money::kes),
         },
+        offers: offers.as_deref().map(summarize_share_offers),
         transactions: crate::pages::dashboard::TransactionAnalytics {
-            total_transactions: overview.summary.transaction_count.total,
-            total_transaction_value_kes: Decimal::from_f64_retain(
-                overview.summary.total_volume.amount,
-            )
-            .unwrap_or_default(),
-            average_transaction_size_kes: {
-                let avg = if overview.summary.transaction_count.total > 0 {
-                    overview.summary.total_volume.amount
-                        / overview.summary.transaction_count.total as f64
-                } else {
-                    0.0
-                };
-                Decimal::from_f64_retain(avg).unwrap_or_else(|| {
-                    leptos::logging::warn!(
-                        "Dashboard: Failed to convert average transaction size to Decimal: {}",
-                        avg
-                    );
-                    Decimal::ZERO
-                })
-            },
+            total_transactions: transaction_count,
+            total_transaction_value: volume,
+            average_transaction_size,
         },
+        trends: overview.trends,
+    }
+}
+
+/// Price per share of the most recently created offer that carries one
+fn latest_share_price(
+    offers: &[crate::api::traits::shares::ShareOffer],
+) -> Option<rust_decimal::Decimal> {
+    offers
+        .iter()
+        .filter(|offer| offer.price_per_share.is_some())
+        .max_by_key(|offer| offer.created_at)
+        .and_then(|offer| offer.price_per_share)
+}
+
+fn summarize_share_offers(
+    offers: &[crate::api::traits::shares::ShareOffer],
+) -> crate::pages::dashboard::ShareOfferAnalytics {
+    use crate::api::traits::shares::ShareOfferStatus;
+
+    let count = |status: ShareOfferStatus| {
+        offers.iter().filter(|offer| offer.status == status).count() as u64
+    };
+    let average_completion_rate = if offers.is_empty() {
+        0.0
+    } else {
+        offers.iter().map(|offer| offer.progress()).sum::<f64>() / offers.len() as f64 * 100.0
+    };
+
+    crate::pages::dashboard::ShareOfferAnalytics {
+        total_offers: offers.len() as u64,
+        active_offers: count(ShareOfferStatus::Open),
+        completed_offers: count(ShareOfferStatus::FullySubscribed),
+        average_completion_rate,
     }
 }
 
@@ -223,17 +420,60 @@ fn convert_nestjs_overview_to_dashboard_metrics(
 #[server(GetUserAnalytics, "/api", "GetJson")]
 pub async fn get_user_analytics(
 ) -> Result<ApiResponse<crate::api::dashboard_client::UserAnalyticsResponse>, ServerFnError> {
-    // TEMPORARY: Bypass API call to prevent stack overflow
-    Err(ServerFnError::new("Temporarily disabled".to_string()))
+    let auth_token = extract_auth_token_from_request().await;
+    let client = get_dashboard_client();
+
+    match client
+        .get_user_analytics_with_auth(auth_token.as_deref())
+        .await
+    {
+        Ok(nestjs_response) => {
+            if nestjs_response.success {
+                Ok(ApiResponse {
+                    success: true,
+                    data: Some(nestjs_response.data),
+                    message: Some(
+                        "User analytics retrieved successfully from NestJS API".to_string(),
+                    ),
+                    errors: None,
+                })
+            } else {
+                Ok(ApiResponse {
+                    success: false,
+                    data: None,
+                    message: Some("Failed to retrieve user analytics".to_string()),
+                    errors: nestjs_response.errors,
+                })
+            }
+        }
+        Err(api_error) => {
+            leptos::logging::error!(
+                "Failed to connect to NestJS user analytics API: {:?}",
+                api_error
+            );
+            Err(ServerFnError::new(format!("API Error: {}", api_error)))
+        }
+    }
 }
 
-/// Server function for getting financial analytics from NestJS API
+/// Server function for getting financial analytics from NestJS API, optionally
+/// limited to a date range (`YYYY-MM-DD`, inclusive)
 #[server(GetFinancialAnalytics, "/api", "GetJson")]
 pub async fn get_financial_analytics(
+    start_date: Option<String>,
+    end_date: Option<String>,
 ) -> Result<ApiResponse<crate::api::dashboard_client::FinancialAnalyticsResponse>, ServerFnError> {
+    let auth_token = extract_auth_token_from_request().await;
     let client = get_dashboard_client();
 
-    match client.get_financial_analytics().await {
+    match client
+        .get_financial_analytics_for_range(
+            auth_token.as_deref(),
+            start_date.as_deref(),
+            end_date.as_deref(),
+        )
+        .await
+    {
         Ok(nestjs_response) => {
             if nestjs_response.success {
                 let response = ApiResponse {
@@ -303,43 +543,100 @@ pub async fn get_operational_metrics(
     }
 }
 
-/// Server function for custom analytics with date range
+/// Operational metrics from the backend plus the dashboard server's own probes
+#[server(GetSystemHealth, "/api", "GetJson")]
+pub async fn get_system_health() -> Result<crate::server::health::SystemHealthReport, ServerFnError>
+{
+    use crate::server::health::{backend_probe, circuit_probe, jwt_probe, SystemHealthReport};
+
+    let auth_token = extract_auth_token_from_request().await;
+    let client = get_dashboard_client();
+    let started = std::time::Instant::now();
+    let fetched = client
+        .get_operational_metrics_with_auth(auth_token.as_deref())
+        .await;
+    let elapsed = started.elapsed();
+
+    let (metrics, metrics_error) = match &fetched {
+        Ok(response) if response.success => (Some(response.data.clone()), None),
+        Ok(response) => (
+            None,
+            Some(
+                response
+                    .message
+                    .clone()
+                    .unwrap_or_else(|| "Failed to retrieve operational metrics".to_string()),
+            ),
+        ),
+        Err(api_error) => (None, Some(api_error.to_string())),
+    };
+    // Any answer short of a network or 5xx error means the backend is reachable
+    let reachability = match &fetched {
+        Err(e @ (ApiError::NetworkError(_) | ApiError::ServerError(_))) => Err(e.to_string()),
+        _ => Ok(elapsed),
+    };
+
+    Ok(SystemHealthReport {
+        probes: vec![
+            backend_probe(client.base_url(), reachability),
+            jwt_probe(std::env::var("JWT_SECRET").ok().as_deref()),
+            circuit_probe(&client.circuit()),
+        ],
+        metrics,
+        metrics_error,
+        checked_at: chrono::Utc::now(),
+    })
+}
+
+/// The latest live update, for the first render and when the live stream is down
+#[server(GetLiveMetrics, "/api", "GetJson")]
+pub async fn get_live_metrics(
+) -> Result<Option<crate::api::dashboard_client::LiveMetricsUpdate>, ServerFnError> {
+    use crate::server::live::{live_hub, poll_live_update};
+
+    // The hub was filled with the server's own token, so only signed-in users see it
+    get_request_user().await?;
+    if let Some(latest) = live_hub().latest() {
+        return Ok(Some(latest));
+    }
+    let auth_token = extract_auth_token_from_request().await;
+    match poll_live_update(get_dashboard_client(), auth_token.as_deref()).await {
+        Ok(update) => Ok(Some(update)),
+        Err(api_error) => {
+            leptos::logging::warn!("Live: no metrics available yet: {:?}", api_error);
+            Ok(None)
+        }
+    }
+}
+
+/// Server function for custom analytics. The spec is validated here as well as
+/// in the browser, since anyone can call the endpoint directly.
 #[server(GetCustomAnalytics, "/api", "GetJson")]
 pub async fn get_custom_analytics(
-    start_date: String,
-    end_date: String,
-    metrics: Vec<String>,
-    granularity: String,
-) -> Result<ApiResponse<serde_json::Value>, ServerFnError> {
-    let client = get_dashboard_client();
+    spec: crate::api::types::ReportSpec,
+) -> Result<crate::api::types::AnalyticsResult, ServerFnError> {
+    use crate::api::types::AnalyticsResult;
 
-    // Convert Vec<String> to Vec<&str>
-    let metrics_slice: Vec<&str> = metrics.iter().map(|s| s.as_str()).collect();
+    let query = spec
+        .query(chrono::Utc::now().date_naive())
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+    let auth_token = extract_auth_token_from_request().await;
+    let client = get_dashboard_client();
 
     match client
-        .get_custom_analytics(&start_date, &end_date, &metrics_slice, &granularity)
+        .get_custom_analytics(&query, auth_token.as_deref())
         .await
     {
+        Ok(nestjs_response) if nestjs_response.success => {
+            AnalyticsResult::from_value(&query, &nestjs_response.data).map_err(|e| {
+                leptos::logging::error!("Unexpected custom analytics response: {}", e);
+                ServerFnError::new(format!("Unexpected analytics response: {}", e))
+            })
+        }
         Ok(nestjs_response) => {
-            if nestjs_response.success {
-                let response = ApiResponse {
-                    success: true,
-                    data: Some(nestjs_response.data),
-                    message: Some(
-                        "Custom analytics retrieved successfully from NestJS API".to_string(),
-                    ),
-                    errors: None,
-                };
-                Ok(response)
-            } else {
-                let response = ApiResponse {
-                    success: false,
-                    data: None,
-                    message: Some("Failed to retrieve custom analytics".to_string()),
-                    errors: nestjs_response.errors,
-                };
-                Ok(response)
-            }
+            Err(ServerFnError::new(nestjs_response.message.unwrap_or_else(
+                || "Failed to retrieve custom analytics".to_string(),
+            )))
         }
         Err(api_error) => {
             leptos::logging::error!(
@@ -351,41 +648,27 @@ pub async fn get_custom_analytics(
     }
 }
 
-/// Server function for exporting dashboard data
+/// Server function for exporting dashboard data. Exports are rendered on this
+/// server in the background; poll `get_export_status` for progress and the link.
 #[server(ExportDashboardData, "/api")]
 pub async fn export_dashboard_data(
     export_request: crate::api::dashboard_client::ExportRequest,
 ) -> Result<ApiResponse<crate::api::dashboard_client::ExportResponse>, ServerFnError> {
-    let client = get_dashboard_client();
+    use crate::server::export::{export_jobs, DashboardExportSource};
 
-    match client.export_dashboard_data(&export_request).await {
-        Ok(nestjs_response) => {
-            if nestjs_response.success {
-                let response = ApiResponse {
-                    success: true,
-                    data: Some(nestjs_response.data),
-                    message: Some("Export request submitted successfully".to_string()),
-                    errors: None,
-                };
-                Ok(response)
-            } else {
-                let response = ApiResponse {
-                    success: false,
-                    data: None,
-                    message: Some("Failed to submit export request".to_string()),
-                    errors: nestjs_response.errors,
-                };
-                Ok(response)
-            }
-        }
-        Err(api_error) => {
-            leptos::logging::error!(
-                "Failed to submit export request to NestJS API: {:?}",
-                api_error
-            );
-            Err(ServerFnError::new(format!("API Error: {}", api_error)))
-        }
-    }
+    let user = require_admin("export dashboard data").await?;
+    let source = DashboardExportSource::new(extract_auth_token_from_request().await);
+    let response = export_jobs()
+        .start(&export_request, user.id, std::sync::Arc::new(source))
+        .await
+        .map_err(ServerFnError::new)?;
+
+    Ok(ApiResponse {
+        success: true,
+        data: Some(response),
+        message: Some("Export started".to_string()),
+        errors: None,
+    })
 }
 
 /// Server function for getting export status
@@ -393,36 +676,41 @@ pub async fn export_dashboard_data(
 pub async fn get_export_status(
     export_id: String,
 ) -> Result<ApiResponse<crate::api::dashboard_client::ExportStatus>, ServerFnError> {
-    let client = get_dashboard_client();
+    let user = get_request_user().await?;
+    let status = crate::server::export::export_jobs()
+        .status(&export_id, user.id)
+        .await
+        .ok_or_else(|| ServerFnError::new("Export not found; it may have expired"))?;
 
-    match client.get_export_status(&export_id).await {
-        Ok(nestjs_response) => {
-            if nestjs_response.success {
-                let response = ApiResponse {
-                    success: true,
-                    data: Some(nestjs_response.data),
-                    message: Some("Export status retrieved successfully".to_string()),
-                    errors: None,
-                };
-                Ok(response)
-            } else {
-                let response = ApiResponse {
-                    success: false,
-                    data: None,
-                    message: Some("Failed to get export status".to_string()),
-                    errors: nestjs_response.errors,
-                };
-                Ok(response)
-            }
-        }
-        Err(api_error) => {
-            leptos::logging::error!(
-                "Failed to get export status from NestJS API: {:?}",
-                api_error
-            );
-            Err(ServerFnError::new(format!("API Error: {}", api_error)))
-        }
-    }
+    Ok(ApiResponse {
+        success: true,
+        data: Some(status),
+        message: None,
+        errors: None,
+    })
+}
+
+/// Server function for the signed-in admin's recent exports, newest first
+#[server(ListExports, "/api", "GetJson")]
+pub async fn list_exports() -> Result<Vec<crate::api::dashboard_client::ExportRecord>, ServerFnError>
+{
+    let user = get_request_user().await?;
+    Ok(crate::server::export::export_jobs().list(user.id).await)
+}
+
+/// Server function for running a failed export again
+#[server(RetryExport, "/api")]
+pub async fn retry_export(
+    export_id: String,
+) -> Result<crate::api::dashboard_client::ExportResponse, ServerFnError> {
+    use crate::server::export::{export_jobs, DashboardExportSource};
+
+    let user = require_admin("export dashboard data").await?;
+    let source = DashboardExportSource::new(extract_auth_token_from_request().await);
+    export_jobs()
+        .retry(&export_id, user.id, std::sync::Arc::new(source))
+        .await
+        .map_err(ServerFnError::new)
 }
 
 // SSR-compatible client stub for backend API modules
@@ -496,3 +784,255 @@ pub fn api() -> &'static ApiClient {
 // 3. Backend adapter will route to appropriate implementation (NestJS or Rust with friendly errors)
 //
 // This maintains clean separation between UI and backend implementation
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::traits::shares::{ShareOffer, ShareOfferStatus};
+    use rust_decimal::Decimal;
+
+    fn overview() -> crate::api::dashboard_client::DashboardOverviewResponse {
+        serde_json::from_value(serde_json::json!({
+            "summary": {
+                "totalMembers": 40,
+                "activeMembersToday": 12,
+                "activeChamas": 3,
+                "totalChamas": 4,
+                "totalVolume": { "amount": 5000.0, "currency": "KES", "period": "all-time" },
+                "transactionCount": { "total": 10, "successful": 9, "failed": 1, "pending": 0 }
+            },
+            "trends": { "memberGrowth": [], "volumeTrend": [], "transactionTrend": [], "chamaGrowth": [] },
+            "alerts": { "systemHealth": "healthy", "errorRate": 0.0, "avgResponseTime": 100, "criticalAlerts": [] },
+            "quickStats": { "todayTransactions": 0, "todayVolume": 0.0, "activeSessionsNow": 0, "newMembersToday": 0 }
+        }))
+        .unwrap()
+    }
+
+    fn offer(
+        quantity: u64,
+        subscribed: u64,
+        price: Option<i64>,
+        age_days: i64,
+        status: ShareOfferStatus,
+    ) -> ShareOffer {
+        let created_at = chrono::Utc::now() - chrono::Duration::days(age_days);
+        ShareOffer {
+            id: uuid::Uuid::new_v4(),
+            title: "Offer".to_string(),
+            description: None,
+            total_quantity: quantity,
+            subscribed_quantity: subscribed,
+            price_per_share: price.map(Decimal::from),
+            status,
+            available_from: created_at,
+            expires_at: created_at + chrono::Duration::days(90),
+            created_at,
+            updated_at: created_at,
+        }
+    }
+
+    #[test]
+    fn test_missing_share_data_is_not_invented() {
+        let metrics = convert_nestjs_overview_to_dashboard_metrics(overview(), None, None);
+
+        assert_eq!(metrics.total_members, 40);
+        assert!(metrics.shareholders.total_shareholders.is_none());
+        assert!(metrics.shareholders.active_shareholders.is_none());
+        assert!(metrics.market.total_market_value.is_none());
+        assert_eq!(
+            metrics.transactions.average_transaction_size,
+            crate::api::types::Money::kes(Decimal::from(500))
+        );
+        assert!(metrics.shareholders.group_shareholders.is_none());
+        assert!(metrics.market.share_price.is_none());
+        assert!(metrics.market.total_shares_in_circulation.is_none());
+        assert!(metrics.offers.is_none());
+    }
+
+    #[test]
+    fn test_acknowledged_alerts_sort_after_active_ones() {
+        use crate::api::dashboard_client::AlertItem;
+        use crate::api::traits::alerts::AlertAcknowledgement;
+
+        let alert = |id: &str, timestamp: &str| AlertItem {
+            id: id.to_string(),
+            severity: "critical".to_string(),
+            message: format!("{} failing", id),
+            timestamp: timestamp.to_string(),
+            service: Some("swaps".to_string()),
+        };
+        let alerts = vec![
+            alert("old", "2025-03-01T08:00:00Z"),
+            alert("acked", "2025-03-01T10:00:00Z"),
+            alert("new", "2025-03-01T09:00:00Z"),
+        ];
+        let acknowledgement = AlertAcknowledgement {
+            alert_id: "acked".to_string(),
+            acknowledged_by: uuid::Uuid::new_v4(),
+            acknowledged_at: chrono::Utc::now(),
+            snoozed_until: None,
+        };
+
+        let joined = join_alert_acknowledgements(alerts, vec![acknowledgement.clone()]);
+        let order: Vec<&str> = joined.iter().map(|alert| alert.alert.id.as_str()).collect();
+        assert_eq!(order, vec!["new", "old", "acked"]);
+        assert_eq!(joined[2].acknowledgement, Some(acknowledgement));
+        assert!(joined[0].is_active());
+    }
+
+    #[test]
+    fn test_trends_are_kept_for_the_charts() {
+        let mut overview = overview();
+        overview.trends = serde_json::from_value(serde_json::json!({
+            "memberGrowth": [
+                { "date": "2025-01-01", "value": 38 },
+                { "date": "2025-01-02T00:00:00Z", "value": 40, "change": 5.26 }
+            ],
+            "volumeTrend": [{ "date": "2025-01-02", "value": 1250.50 }],
+            "transactionTrend": [],
+            "chamaGrowth": []
+        }))
+        .unwrap();
+
+        let metrics = convert_nestjs_overview_to_dashboard_metrics(overview, None, None);
+        assert_eq!(metrics.trends.member_growth.len(), 2);
+        assert_eq!(metrics.trends.member_growth[1].value, Decimal::from(40));
+        assert_eq!(
+            metrics.trends.volume_trend[0].value,
+            Decimal::new(125050, 2)
+        );
+        assert!(metrics.trends.transaction_trend.is_empty());
+    }
+
+    /// `/dashboard/users` as the NestJS API sends it
+    const USER_ANALYTICS_PAYLOAD: &str = r#"{
+        "success": true,
+        "timestamp": "2025-03-01T00:00:00.000Z",
+        "meta": { "cached": true, "cacheAge": 42, "dataSource": "aggregated" },
+        "data": {
+            "engagement": {
+                "dailyActiveUsers": 12,
+                "weeklyActiveUsers": 30,
+                "monthlyActiveUsers": 48,
+                "dauMauRatio": 0.25,
+                "sessionMetrics": {
+                    "averageDuration": 95.37,
+                    "totalSessions": 400,
+                    "sessionsToday": 20,
+                    "peakConcurrentUsers": 6
+                }
+            },
+            "retention": {
+                "day1": 60,
+                "day7": 42.5,
+                "day30": 30,
+                "day90": 18.25,
+                "cohortData": [
+                    { "cohortMonth": "2024-12", "newUsers": 31, "retention": { "month1": 61.3, "month2": 45.2, "month3": 38.7, "month6": 0 } },
+                    { "cohortMonth": "2025-01", "newUsers": 20, "retention": { "month1": 55, "month2": 40, "month3": 35, "month6": 0 } }
+                ]
+            },
+            "demographics": {
+                "byRegion": { "Nairobi": 30, "Mombasa": 18 },
+                "byDeviceType": { "android": 40, "ios": 8 },
+                "byAppVersion": { "1.4.0": 41, "1.3.2": 7 },
+                "registrationTrend": [
+                    { "date": "2025-02-01", "value": 4, "change": null, "label": null },
+                    { "date": "2025-02-02", "value": 6, "change": 50, "label": "Feb 2" }
+                ]
+            },
+            "featureUsage": {
+                "topFeatures": [
+                    { "featureId": "swap", "featureName": "M-Pesa swap", "usageCount": 120, "uniqueUsers": 35, "averageDuration": 42.8, "successRate": 97.5 }
+                ],
+                "adoption": [
+                    { "featureId": "swap", "featureName": "M-Pesa swap", "adoptionRate": 72.9, "timeToAdoption": 3.5 }
+                ],
+                "successRates": { "swap": 97.5 }
+            },
+            "membershipActivity": {
+                "newRegistrations": { "today": 1, "thisWeek": 4, "thisMonth": 9 },
+                "chamaParticipation": {
+                    "activeMembersInChamas": 25,
+                    "averageChamasPerMember": 1.4,
+                    "chamaMembershipTrend": [{ "date": "2025-02-01", "value": 25 }]
+                }
+            }
+        }
+    }"#;
+
+    #[test]
+    fn test_user_analytics_payload_deserializes() {
+        // Parsed from the wire text on a small stack, as a server worker would
+        let response = std::thread::Builder::new()
+            .stack_size(256 * 1024)
+            .spawn(|| {
+                serde_json::from_str::<
+                    crate::api::dashboard_client::NestJsApiResponse<
+                        crate::api::dashboard_client::UserAnalyticsResponse,
+                    >,
+                >(USER_ANALYTICS_PAYLOAD)
+            })
+            .unwrap()
+            .join()
+            .unwrap()
+            .unwrap();
+
+        let data = response.data;
+        assert_eq!(data.engagement.dau_mau_ratio, 0.25);
+        assert_eq!(data.engagement.session_metrics.average_duration, 95.37);
+        assert_eq!(data.retention.cohort_data.len(), 2);
+        assert_eq!(data.retention.cohort_data[1].retention.month2, 40.0);
+        assert_eq!(data.feature_usage.top_features[0].usage_count, 120);
+        assert_eq!(data.demographics.by_region["Nairobi"], 30);
+        assert_eq!(data.demographics.registration_trend.len(), 2);
+        assert!(response.meta.is_some_and(|meta| meta.cached));
+    }
+
+    #[test]
+    fn test_share_metrics_come_from_backend_data() {
+        let shares: crate::api::dashboard_client::SharesMetrics =
+            serde_json::from_value(serde_json::json!({
+                "ownership": {
+                    "totalShares": 1000,
+                    "distributedShares": 600,
+                    "availableShares": 400,
+                    "ownershipConcentration": 0.2
+                },
+                "trading": {
+                    "totalTransfers": 3,
+                    "transferVolume": 30,
+                    "averageTransferSize": 10.0,
+                    "transferTrend": []
+                }
+            }))
+            .unwrap();
+        let offers = vec![
+            offer(100, 100, Some(500), 60, ShareOfferStatus::FullySubscribed),
+            offer(100, 50, Some(750), 10, ShareOfferStatus::Open),
+            offer(100, 0, None, 1, ShareOfferStatus::Open),
+        ];
+
+        let metrics =
+            convert_nestjs_overview_to_dashboard_metrics(overview(), Some(&shares), Some(offers));
+
+        assert_eq!(
+            metrics.market.total_shares_in_circulation,
+            Some(Decimal::from(600))
+        );
+        assert_eq!(
+            metrics.market.share_price,
+            Some(crate::api::types::Money::kes(Decimal::from(750)))
+        );
+        assert_eq!(
+            metrics.market.total_market_value,
+            Some(crate::api::types::Money::kes(Decimal::from(450_000)))
+        );
+
+        let offers = metrics.offers.unwrap();
+        assert_eq!(offers.total_offers, 3);
+        assert_eq!(offers.active_offers, 2);
+        assert_eq!(offers.completed_offers, 1);
+        assert_eq!(offers.average_completion_rate, 50.0);
+    }
+}
@@ -1,4 +1,9 @@
-use crate::api::ApiError;
+use crate::api::{
+    circuit_breaker::{CircuitBreaker, CircuitSnapshot},
+    types::{AnalyticsQuery, Money},
+    ApiError,
+};
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use std::sync::OnceLock;
 use std::time::Duration;
@@ -10,6 +15,10 @@ pub struct DashboardApiConfig {
     pub timeout: Duration,
     pub retry_attempts: u32,
     pub cache_duration: Duration,
+    /// Consecutive failures before calls start failing fast
+    pub circuit_failure_threshold: u32,
+    /// How long an open circuit waits before letting a trial call through
+    pub circuit_reset_timeout: Duration,
 }
 
 impl Default for DashboardApiConfig {
@@ -20,6 +29,8 @@ impl Default for DashboardApiConfig {
             timeout: Duration::from_secs(30),
             retry_attempts: 3,
             cache_duration: Duration::from_secs(300), // 5 minutes
+            circuit_failure_threshold: 5,
+            circuit_reset_timeout: Duration::from_secs(30),
         }
     }
 }
@@ -72,16 +83,15 @@ pub struct DashboardSummary {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MonetaryAmount {
-    pub amount: f64,
-    pub currency: String, // "KES"
-    pub period: String,   // "all-time" | "30d" | "7d"
+    #[serde(flatten)]
+    pub money: Money,
+    pub period: String, // "all-time" | "30d" | "7d"
 }
 
 impl Default for MonetaryAmount {
     fn default() -> Self {
         Self {
-            amount: 0.0,
-            currency: "KES".to_string(),
+            money: Money::zero(Money::REPORTING_CURRENCY),
             period: "all-time".to_string(),
         }
     }
@@ -110,8 +120,9 @@ pub struct DashboardTrends {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TrendDataPoint {
     pub date: String,
-    pub value: f64,
-    pub change: Option<f64>,
+    /// Money for volume trends, a count or duration for the rest
+    pub value: Decimal,
+    pub change: Option<Decimal>,
     pub label: Option<String>,
 }
 
@@ -141,7 +152,7 @@ pub struct QuickStats {
     #[serde(rename = "todayTransactions")]
     pub today_transactions: u64,
     #[serde(rename = "todayVolume")]
-    pub today_volume: f64,
+    pub today_volume: Money,
     #[serde(rename = "activeSessionsNow")]
     pub active_sessions_now: u64,
     #[serde(rename = "newMembersToday")]
@@ -152,7 +163,7 @@ impl Default for QuickStats {
     fn default() -> Self {
         Self {
             today_transactions: 0,
-            today_volume: 0.0,
+            today_volume: Money::zero(Money::REPORTING_CURRENCY),
             active_sessions_now: 0,
             new_members_today: 0,
         }
@@ -179,6 +190,7 @@ pub struct UserEngagement {
     pub monthly_active_users: u64,
     #[serde(rename = "weeklyActiveUsers")]
     pub weekly_active_users: u64,
+    #[serde(rename = "dauMauRatio", alias = "dau_mau_ratio")]
     pub dau_mau_ratio: f64,
     #[serde(rename = "sessionMetrics")]
     pub session_metrics: SessionMetrics,
@@ -186,8 +198,9 @@ pub struct UserEngagement {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SessionMetrics {
+    /// Seconds; an average, so rarely a whole number
     #[serde(rename = "averageDuration")]
-    pub average_duration: u32,
+    pub average_duration: f64,
     #[serde(rename = "totalSessions")]
     pub total_sessions: u64,
     #[serde(rename = "sessionsToday")]
@@ -317,12 +330,12 @@ pub struct TransactionMetrics {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VolumeMetrics {
-    pub total: f64,
-    pub today: f64,
+    pub total: Money,
+    pub today: Money,
     #[serde(rename = "thisWeek")]
-    pub this_week: f64,
+    pub this_week: Money,
     #[serde(rename = "thisMonth")]
-    pub this_month: f64,
+    pub this_month: Money,
     #[serde(rename = "byCurrency")]
     pub by_currency: std::collections::HashMap<String, CurrencyVolume>,
     #[serde(rename = "byOperation")]
@@ -330,19 +343,46 @@ pub struct VolumeMetrics {
     pub trend: Vec<TrendDataPoint>,
 }
 
+/// Volume in one currency; on the wire the amounts are bare and the currency is a sibling field
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(from = "CurrencyVolumeWire", into = "CurrencyVolumeWire")]
 pub struct CurrencyVolume {
-    pub total: f64,
-    pub today: f64,
-    pub currency: String,
+    pub total: Money,
+    pub today: Money,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CurrencyVolumeWire {
+    total: Decimal,
+    today: Decimal,
+    currency: String,
+}
+
+impl From<CurrencyVolumeWire> for CurrencyVolume {
+    fn from(wire: CurrencyVolumeWire) -> Self {
+        Self {
+            total: Money::new(wire.total, &wire.currency),
+            today: Money::new(wire.today, &wire.currency),
+        }
+    }
+}
+
+impl From<CurrencyVolume> for CurrencyVolumeWire {
+    fn from(volume: CurrencyVolume) -> Self {
+        Self {
+            total: volume.total.amount,
+            today: volume.today.amount,
+            currency: volume.total.currency,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OperationVolume {
-    pub total: f64,
+    pub total: Money,
     pub count: u64,
     #[serde(rename = "averageAmount")]
-    pub average_amount: f64,
+    pub average_amount: Money,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -383,11 +423,11 @@ pub struct SwapAnalytics {
     #[serde(rename = "successRate")]
     pub success_rate: f64,
     #[serde(rename = "totalKes")]
-    pub total_kes: f64,
+    pub total_kes: Money,
     #[serde(rename = "totalSats")]
     pub total_sats: u64,
     #[serde(rename = "averageAmount")]
-    pub average_amount: f64,
+    pub average_amount: Money,
     pub trend: Vec<TrendDataPoint>,
     #[serde(rename = "byPaymentMethod")]
     pub by_payment_method: std::collections::HashMap<String, u64>,
@@ -402,10 +442,10 @@ pub struct FxRates {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CurrentFxRate {
     #[serde(rename = "buyRate")]
-    pub buy_rate: f64,
+    pub buy_rate: Decimal,
     #[serde(rename = "sellRate")]
-    pub sell_rate: f64,
-    pub spread: f64,
+    pub sell_rate: Decimal,
+    pub spread: Decimal,
     #[serde(rename = "lastUpdated")]
     pub last_updated: String,
 }
@@ -414,19 +454,19 @@ pub struct CurrentFxRate {
 pub struct FxRateHistory {
     pub timestamp: String,
     #[serde(rename = "buyRate")]
-    pub buy_rate: f64,
+    pub buy_rate: Decimal,
     #[serde(rename = "sellRate")]
-    pub sell_rate: f64,
-    pub spread: f64,
-    pub volume: Option<f64>,
+    pub sell_rate: Decimal,
+    pub spread: Decimal,
+    pub volume: Option<Money>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SwapVolume {
     #[serde(rename = "totalOnrampKes")]
-    pub total_onramp_kes: f64,
+    pub total_onramp_kes: Money,
     #[serde(rename = "totalOfframpKes")]
-    pub total_offramp_kes: f64,
+    pub total_offramp_kes: Money,
     #[serde(rename = "totalOnrampSats")]
     pub total_onramp_sats: u64,
     #[serde(rename = "totalOfframpSats")]
@@ -443,13 +483,13 @@ pub struct ChamaMetrics {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChamaFinancial {
     #[serde(rename = "totalBalance")]
-    pub total_balance: f64,
+    pub total_balance: Money,
     #[serde(rename = "totalDeposits")]
-    pub total_deposits: f64,
+    pub total_deposits: Money,
     #[serde(rename = "totalWithdrawals")]
-    pub total_withdrawals: f64,
+    pub total_withdrawals: Money,
     #[serde(rename = "netFlow")]
-    pub net_flow: f64,
+    pub net_flow: Money,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -474,7 +514,7 @@ pub struct PatternData {
     pub pattern: String,
     pub frequency: u64,
     #[serde(rename = "averageAmount")]
-    pub average_amount: f64,
+    pub average_amount: Money,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -486,7 +526,7 @@ pub struct ChamaActivity {
     #[serde(rename = "pendingWithdrawals")]
     pub pending_withdrawals: u64,
     #[serde(rename = "averageBalance")]
-    pub average_balance: f64,
+    pub average_balance: Money,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -766,23 +806,43 @@ pub struct ExportStatus {
     pub completed_at: Option<String>,
 }
 
+/// An export as listed in the export history: what was asked for and how it went
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportRecord {
+    pub request: ExportRequest,
+    pub status: ExportStatus,
+}
+
 /// Shared HTTP client instance for efficiency
 static HTTP_CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
 
 /// Dashboard API Client
 pub struct DashboardApiClient {
     config: DashboardApiConfig,
+    breaker: CircuitBreaker,
 }
 
 impl DashboardApiClient {
     pub fn new() -> Self {
-        Self {
-            config: DashboardApiConfig::default(),
-        }
+        Self::with_config(DashboardApiConfig::default())
     }
 
     pub fn with_config(config: DashboardApiConfig) -> Self {
-        Self { config }
+        let breaker = CircuitBreaker::new(
+            config.circuit_failure_threshold,
+            config.circuit_reset_timeout,
+        );
+        Self { config, breaker }
+    }
+
+    /// Base URL of the NestJS API this client talks to
+    pub fn base_url(&self) -> &str {
+        &self.config.base_url
+    }
+
+    /// Current state of the circuit breaker in front of the NestJS API
+    pub fn circuit(&self) -> CircuitSnapshot {
+        self.breaker.snapshot()
     }
 
     /// Get shared HTTP client instance
@@ -811,7 +871,15 @@ impl DashboardApiClient {
     pub async fn get_user_analytics(
         &self,
     ) -> Result<NestJsApiResponse<UserAnalyticsResponse>, ApiError> {
-        self.make_request::<UserAnalyticsResponse>("/dashboard/users", None)
+        self.get_user_analytics_with_auth(None).await
+    }
+
+    /// Get user analytics data with authentication
+    pub async fn get_user_analytics_with_auth(
+        &self,
+        auth_token: Option<&str>,
+    ) -> Result<NestJsApiResponse<UserAnalyticsResponse>, ApiError> {
+        self.make_request::<UserAnalyticsResponse>("/dashboard/users", auth_token)
             .await
     }
 
@@ -819,37 +887,107 @@ impl DashboardApiClient {
     pub async fn get_financial_analytics(
         &self,
     ) -> Result<NestJsApiResponse<FinancialAnalyticsResponse>, ApiError> {
-        self.make_request::<FinancialAnalyticsResponse>("/dashboard/financial", None)
+        self.get_financial_analytics_with_auth(None).await
+    }
+
+    /// Get financial analytics data with authentication
+    pub async fn get_financial_analytics_with_auth(
+        &self,
+        auth_token: Option<&str>,
+    ) -> Result<NestJsApiResponse<FinancialAnalyticsResponse>, ApiError> {
+        self.make_request::<FinancialAnalyticsResponse>("/dashboard/financial", auth_token)
             .await
     }
 
+    /// Get financial analytics for a date range; either bound may be left open
+    pub async fn get_financial_analytics_for_range(
+        &self,
+        auth_token: Option<&str>,
+        start_date: Option<&str>,
+        end_date: Option<&str>,
+    ) -> Result<NestJsApiResponse<FinancialAnalyticsResponse>, ApiError> {
+        let query_params: Vec<(&str, &str)> = [("start_date", start_date), ("end_date", end_date)]
+            .into_iter()
+            .filter_map(|(name, value)| value.map(|value| (name, value)))
+            .collect();
+        self.make_request_with_params::<FinancialAnalyticsResponse>(
+            "/dashboard/financial",
+            &query_params,
+            auth_token,
+        )
+        .await
+    }
+
     /// Get operational metrics data
     pub async fn get_operational_metrics(
         &self,
     ) -> Result<NestJsApiResponse<OperationalMetricsResponse>, ApiError> {
-        self.make_request::<OperationalMetricsResponse>("/dashboard/operations", None)
+        self.get_operational_metrics_with_auth(None).await
+    }
+
+    /// Get operational metrics data with authentication
+    pub async fn get_operational_metrics_with_auth(
+        &self,
+        auth_token: Option<&str>,
+    ) -> Result<NestJsApiResponse<OperationalMetricsResponse>, ApiError> {
+        self.make_request::<OperationalMetricsResponse>("/dashboard/operations", auth_token)
             .await
     }
 
-    /// Get custom analytics with date range
+    /// Open the backend's live metrics stream (`text/event-stream`). The
+    /// response is returned as soon as the backend accepts, for the caller to
+    /// read event by event; a 404 means the backend can't push.
+    #[cfg(feature = "ssr")]
+    pub async fn open_live_stream(
+        &self,
+        auth_token: Option<&str>,
+    ) -> Result<reqwest::Response, ApiError> {
+        use reqwest::header::{HeaderValue, ACCEPT, AUTHORIZATION};
+
+        self.breaker.try_acquire()?;
+        let result = async {
+            let mut request = Self::get_http_client()
+                .get(format!("{}/dashboard/live", self.config.base_url))
+                .header(ACCEPT, HeaderValue::from_static("text/event-stream"));
+            if let Some(token) = auth_token {
+                request = request.header(AUTHORIZATION, format!("Bearer {}", token));
+            }
+            // No timeout: the stream stays open for as long as the backend keeps it
+            let response = request
+                .send()
+                .await
+                .map_err(|e| ApiError::NetworkError(e.to_string()))?;
+            match response.status().as_u16() {
+                200..=299 => Ok(response),
+                401 => Err(ApiError::Unauthorized),
+                404 => Err(ApiError::NotFound),
+                status @ 500..=599 => {
+                    Err(ApiError::ServerError(format!("Server error: {}", status)))
+                }
+                status => Err(ApiError::BadRequest(format!("Client error: {}", status))),
+            }
+        }
+        .await;
+        self.breaker.record(result.as_ref().err());
+        result
+    }
+
+    /// Get custom analytics for a validated query
     pub async fn get_custom_analytics(
         &self,
-        start_date: &str,
-        end_date: &str,
-        metrics: &[&str],
-        granularity: &str,
+        query: &AnalyticsQuery,
+        auth_token: Option<&str>,
     ) -> Result<NestJsApiResponse<serde_json::Value>, ApiError> {
-        let metrics_string = metrics.join(",");
-        let query_params = vec![
-            ("start_date", start_date),
-            ("end_date", end_date),
-            ("metrics", &metrics_string),
-            ("granularity", granularity),
-        ];
+        let params = query.params();
+        let query_params: Vec<(&str, &str)> = params
+            .iter()
+            .map(|(name, value)| (*name, value.as_str()))
+            .collect();
 
         self.make_request_with_params::<serde_json::Value>(
             "/dashboard/analytics/custom",
             &query_params,
+            auth_token,
         )
         .await
     }
@@ -883,53 +1021,60 @@ impl DashboardApiClient {
     {
         #[cfg(feature = "ssr")]
         {
-            use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_TYPE};
-
-            let client = Self::get_http_client();
-            let url = format!("{}{}", self.config.base_url, endpoint);
-
-            let mut headers = HeaderMap::new();
-            headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
-
-            if let Some(token) = auth_token {
-                headers.insert(
-                    AUTHORIZATION,
-                    HeaderValue::from_str(&format!("Bearer {}", token))
-                        .map_err(|e| ApiError::BadRequest(format!("Invalid auth token: {}", e)))?,
-                );
-            }
-
-            let response = client
-                .get(&url)
-                .headers(headers)
-                .timeout(self.config.timeout)
-                .send()
-                .await
-                .map_err(|e| ApiError::NetworkError(e.to_string()))?;
+            self.breaker.try_acquire()?;
+            let result = async {
+                use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_TYPE};
+
+                let client = Self::get_http_client();
+                let url = format!("{}{}", self.config.base_url, endpoint);
+
+                let mut headers = HeaderMap::new();
+                headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+
+                if let Some(token) = auth_token {
+                    headers.insert(
+                        AUTHORIZATION,
+                        HeaderValue::from_str(&format!("Bearer {}", token)).map_err(|e| {
+                            ApiError::BadRequest(format!("Invalid auth token: {}", e))
+                        })?,
+                    );
+                }
 
-            if response.status().is_success() {
-                response
-                    .json::<NestJsApiResponse<T>>()
+                let response = client
+                    .get(&url)
+                    .headers(headers)
+                    .timeout(self.config.timeout)
+                    .send()
                     .await
-                    .map_err(|e| ApiError::ParseError(e.to_string()))
-            } else {
-                match response.status().as_u16() {
-                    401 => Err(ApiError::Unauthorized),
-                    404 => Err(ApiError::NotFound),
-                    400..=499 => Err(ApiError::BadRequest(format!(
-                        "Client error: {}",
-                        response.status()
-                    ))),
-                    500..=599 => Err(ApiError::ServerError(format!(
-                        "Server error: {}",
-                        response.status()
-                    ))),
-                    _ => Err(ApiError::NetworkError(format!(
-                        "Unexpected status: {}",
-                        response.status()
-                    ))),
+                    .map_err(|e| ApiError::NetworkError(e.to_string()))?;
+
+                if response.status().is_success() {
+                    response
+                        .json::<NestJsApiResponse<T>>()
+                        .await
+                        .map_err(|e| ApiError::ParseError(e.to_string()))
+                } else {
+                    match response.status().as_u16() {
+                        401 => Err(ApiError::Unauthorized),
+                        404 => Err(ApiError::NotFound),
+                        400..=499 => Err(ApiError::BadRequest(format!(
+                            "Client error: {}",
+                            response.status()
+                        ))),
+                        500..=599 => Err(ApiError::ServerError(format!(
+                            "Server error: {}",
+                            response.status()
+                        ))),
+                        _ => Err(ApiError::NetworkError(format!(
+                            "Unexpected status: {}",
+                            response.status()
+                        ))),
+                    }
                 }
             }
+            .await;
+            self.breaker.record(result.as_ref().err());
+            result
         }
 
         #[cfg(not(feature = "ssr"))]
@@ -947,52 +1092,68 @@ impl DashboardApiClient {
         &self,
         endpoint: &str,
         query_params: &[(&str, &str)],
+        auth_token: Option<&str>,
     ) -> Result<NestJsApiResponse<T>, ApiError>
     where
         T: for<'de> Deserialize<'de>,
     {
         #[cfg(feature = "ssr")]
         {
-            use reqwest::header::{HeaderMap, HeaderValue, CONTENT_TYPE};
-
-            let client = Self::get_http_client();
-            let url = format!("{}{}", self.config.base_url, endpoint);
-
-            let mut headers = HeaderMap::new();
-            headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
-
-            let response = client
-                .get(&url)
-                .headers(headers)
-                .query(query_params)
-                .timeout(self.config.timeout)
-                .send()
-                .await
-                .map_err(|e| ApiError::NetworkError(e.to_string()))?;
+            self.breaker.try_acquire()?;
+            let result = async {
+                use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_TYPE};
+
+                let client = Self::get_http_client();
+                let url = format!("{}{}", self.config.base_url, endpoint);
+
+                let mut headers = HeaderMap::new();
+                headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+
+                if let Some(token) = auth_token {
+                    headers.insert(
+                        AUTHORIZATION,
+                        HeaderValue::from_str(&format!("Bearer {}", token)).map_err(|e| {
+                            ApiError::BadRequest(format!("Invalid auth token: {}", e))
+                        })?,
+                    );
+                }
 
-            if response.status().is_success() {
-                response
-                    .json::<NestJsApiResponse<T>>()
+                let response = client
+                    .get(&url)
+                    .headers(headers)
+                    .query(query_params)
+                    .timeout(self.config.timeout)
+                    .send()
                     .await
-                    .map_err(|e| ApiError::ParseError(e.to_string()))
-            } else {
-                match response.status().as_u16() {
-                    401 => Err(ApiError::Unauthorized),
-                    404 => Err(ApiError::NotFound),
-                    400..=499 => Err(ApiError::BadRequest(format!(
-                        "Client error: {}",
-                        response.status()
-                    ))),
-                    500..=599 => Err(ApiError::ServerError(format!(
-                        "Server error: {}",
-                        response.status()
-                    ))),
-                    _ => Err(ApiError::NetworkError(format!(
-                        "Unexpected status: {}",
-                        response.status()
-                    ))),
+                    .map_err(|e| ApiError::NetworkError(e.to_string()))?;
+
+                if response.status().is_success() {
+                    response
+                        .json::<NestJsApiResponse<T>>()
+                        .await
+                        .map_err(|e| ApiError::ParseError(e.to_string()))
+                } else {
+                    match response.status().as_u16() {
+                        401 => Err(ApiError::Unauthorized),
+                        404 => Err(ApiError::NotFound),
+                        400..=499 => Err(ApiError::BadRequest(format!(
+                            "Client error: {}",
+                            response.status()
+                        ))),
+                        500..=599 => Err(ApiError::ServerError(format!(
+                            "Server error: {}",
+                            response.status()
+                        ))),
+                        _ => Err(ApiError::NetworkError(format!(
+                            "Unexpected status: {}",
+                            response.status()
+                        ))),
+                    }
                 }
             }
+            .await;
+            self.breaker.record(result.as_ref().err());
+            result
         }
 
         #[cfg(not(feature = "ssr"))]
@@ -1015,46 +1176,52 @@ impl DashboardApiClient {
     {
         #[cfg(feature = "ssr")]
         {
-            use reqwest::header::{HeaderMap, HeaderValue, CONTENT_TYPE};
-
-            let client = Self::get_http_client();
-            let url = format!("{}{}", self.config.base_url, endpoint);
-
-            let mut headers = HeaderMap::new();
-            headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
-
-            let response = client
-                .post(&url)
-                .headers(headers)
-                .json(data)
-                .timeout(self.config.timeout)
-                .send()
-                .await
-                .map_err(|e| ApiError::NetworkError(e.to_string()))?;
-
-            if response.status().is_success() {
-                response
-                    .json::<NestJsApiResponse<U>>()
+            self.breaker.try_acquire()?;
+            let result = async {
+                use reqwest::header::{HeaderMap, HeaderValue, CONTENT_TYPE};
+
+                let client = Self::get_http_client();
+                let url = format!("{}{}", self.config.base_url, endpoint);
+
+                let mut headers = HeaderMap::new();
+                headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+
+                let response = client
+                    .post(&url)
+                    .headers(headers)
+                    .json(data)
+                    .timeout(self.config.timeout)
+                    .send()
                     .await
-                    .map_err(|e| ApiError::ParseError(e.to_string()))
-            } else {
-                match response.status().as_u16() {
-                    401 => Err(ApiError::Unauthorized),
-                    404 => Err(ApiError::NotFound),
-                    400..=499 => Err(ApiError::BadRequest(format!(
-                        "Client error: {}",
-                        response.status()
-                    ))),
-                    500..=599 => Err(ApiError::ServerError(format!(
-                        "Server error: {}",
-                        response.status()
-                    ))),
-                    _ => Err(ApiError::NetworkError(format!(
-                        "Unexpected status: {}",
-                        response.status()
-                    ))),
+                    .map_err(|e| ApiError::NetworkError(e.to_string()))?;
+
+                if response.status().is_success() {
+                    response
+                        .json::<NestJsApiResponse<U>>()
+                        .await
+                        .map_err(|e| ApiError::ParseError(e.to_string()))
+                } else {
+                    match response.status().as_u16() {
+                        401 => Err(ApiError::Unauthorized),
+                        404 => Err(ApiError::NotFound),
+                        400..=499 => Err(ApiError::BadRequest(format!(
+                            "Client error: {}",
+                            response.status()
+                        ))),
+                        500..=599 => Err(ApiError::ServerError(format!(
+                            "Server error: {}",
+                            response.status()
+                        ))),
+                        _ => Err(ApiError::NetworkError(format!(
+                            "Unexpected status: {}",
+                            response.status()
+                        ))),
+                    }
                 }
             }
+            .await;
+            self.breaker.record(result.as_ref().err());
+            result
         }
 
         #[cfg(not(feature = "ssr"))]
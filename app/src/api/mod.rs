@@ -1,4 +1,5 @@
 // Existing SSR API modules
+pub mod circuit_breaker;
 pub mod client;
 pub mod dashboard_client;
 
@@ -11,9 +12,11 @@ pub mod types;
 
 // Re-export commonly used items for SSR mode
 pub use client::{
-    api, export_dashboard_data, get_custom_analytics, get_dashboard_metrics, get_export_status,
-    get_financial_analytics, get_operational_metrics, get_user_analytics, ApiClient, ApiError,
-    ApiResponse, PaginatedResponse, PaginationQuery, SearchQuery,
+    acknowledge_alert, api, export_dashboard_data, get_custom_analytics, get_dashboard_metrics,
+    get_export_status, get_financial_analytics, get_live_metrics, get_operational_metrics,
+    get_system_alerts, get_system_health, get_user_analytics, list_exports, retry_export,
+    snooze_alert, ApiClient, ApiError, ApiResponse, PaginatedResponse, PaginationQuery,
+    SearchQuery,
 };
 
 // Re-export config and error items
@@ -21,7 +24,11 @@ pub use config::{ApiConfig, Backend};
 pub use errors::{ApiError as AbstractedApiError, ApiResult};
 
 // Specific re-exports to avoid ambiguous glob imports
-pub use traits::{AuthApi, GroupsApi, UsersApi, WalletsApi};
+pub use traits::{
+    AlertsApi, AuthApi, ContributionsApi, DividendsApi, FxApi, GroupsApi, LedgerApi, LightningApi,
+    LoansApi, OnChainApi, ReconciliationApi, SavedReportsApi, SharesApi, StatementsApi, SwapsApi,
+    UsersApi, WalletsApi, WithdrawalPolicyApi,
+};
 pub use types::{
     auth::{AuthRequest, AuthResponse, LoginRequest, LogoutRequest, LogoutResponse},
     common::{PaginationQuery as ApiPaginationQuery, SearchQuery as ApiSearchQuery},
@@ -0,0 +1,62 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::api::{dashboard_client::AlertItem, errors::ApiResult};
+
+/// Longest an alert may be snoozed for, in minutes
+pub const MAX_SNOOZE_MINUTES: u32 = 7 * 24 * 60;
+
+/// An admin's response to a system alert, shared with every other admin
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct AlertAcknowledgement {
+    pub alert_id: String,
+    pub acknowledged_by: Uuid,
+    pub acknowledged_at: DateTime<Utc>,
+    /// A snoozed alert comes back once this passes; `None` means it was
+    /// acknowledged for good
+    pub snoozed_until: Option<DateTime<Utc>>,
+}
+
+impl AlertAcknowledgement {
+    /// Whether this still silences the alert at `now`
+    pub fn in_force(&self, now: DateTime<Utc>) -> bool {
+        self.snoozed_until.is_none_or(|until| until > now)
+    }
+}
+
+/// A critical alert from the dashboard overview with any acknowledgement in force
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SystemAlert {
+    pub alert: AlertItem,
+    pub acknowledgement: Option<AlertAcknowledgement>,
+}
+
+impl SystemAlert {
+    /// Alerts nobody has acknowledged or snoozed
+    pub fn is_active(&self) -> bool {
+        self.acknowledgement.is_none()
+    }
+}
+
+/// Acknowledgements are keyed by the backend's alert id, so one admin's
+/// acknowledgement silences the alert for everyone
+#[async_trait]
+pub trait AlertsApi: Send + Sync {
+    /// Acknowledgements and snoozes still in force
+    async fn list_acknowledgements(&self) -> ApiResult<Vec<AlertAcknowledgement>>;
+
+    async fn acknowledge_alert(
+        &self,
+        alert_id: &str,
+        acknowledged_by: Uuid,
+    ) -> ApiResult<AlertAcknowledgement>;
+
+    /// Silence an alert for `minutes`, after which it is active again
+    async fn snooze_alert(
+        &self,
+        alert_id: &str,
+        acknowledged_by: Uuid,
+        minutes: u32,
+    ) -> ApiResult<AlertAcknowledgement>;
+}
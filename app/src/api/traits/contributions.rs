@@ -0,0 +1,270 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Months, Utc};
+use uuid::Uuid;
+
+use crate::api::{
+    errors::ApiResult,
+    traits::wallets::{TransactionStatus, TransactionType, WalletTransaction},
+    types::User,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum ContributionFrequency {
+    #[serde(rename = "weekly")]
+    Weekly,
+    #[serde(rename = "fortnightly")]
+    Fortnightly,
+    #[serde(rename = "monthly")]
+    Monthly,
+}
+
+impl ContributionFrequency {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ContributionFrequency::Weekly => "weekly",
+            ContributionFrequency::Fortnightly => "fortnightly",
+            ContributionFrequency::Monthly => "monthly",
+        }
+    }
+}
+
+impl std::str::FromStr for ContributionFrequency {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "weekly" => Ok(ContributionFrequency::Weekly),
+            "fortnightly" => Ok(ContributionFrequency::Fortnightly),
+            "monthly" => Ok(ContributionFrequency::Monthly),
+            other => Err(format!("Unknown contribution frequency '{}'", other)),
+        }
+    }
+}
+
+/// A chama member taking part in a contribution plan
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ContributionMember {
+    pub user_id: Uuid,
+    /// Wallet the member pays contributions from into the chama's pool
+    pub wallet_id: Uuid,
+}
+
+/// A periodic contribution schedule (merry-go-round) attached to a group.
+/// Amounts are in satoshis.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ContributionPlan {
+    pub id: Uuid,
+    pub group_id: Uuid,
+    pub name: String,
+    /// Amount each member contributes per period
+    pub amount: u64,
+    pub frequency: ContributionFrequency,
+    /// Due date of the first contribution
+    pub start_date: DateTime<Utc>,
+    /// Members in payout rotation order
+    pub members: Vec<ContributionMember>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ContributionPlan {
+    /// Due date of a zero-based contribution period
+    pub fn due_date(&self, period: u32) -> DateTime<Utc> {
+        match self.frequency {
+            ContributionFrequency::Weekly => self.start_date + Duration::weeks(period as i64),
+            ContributionFrequency::Fortnightly => {
+                self.start_date + Duration::weeks(2 * period as i64)
+            }
+            ContributionFrequency::Monthly => self
+                .start_date
+                .checked_add_months(Months::new(period))
+                .unwrap_or(DateTime::<Utc>::MAX_UTC),
+        }
+    }
+
+    /// Number of periods whose contribution has fallen due by `as_of`
+    pub fn periods_due(&self, as_of: DateTime<Utc>) -> u32 {
+        let mut periods = 0;
+        while self.due_date(periods) <= as_of {
+            periods += 1;
+        }
+        periods
+    }
+
+    /// Amount paid out to the recipient each period
+    pub fn pot(&self) -> u64 {
+        self.amount * self.members.len() as u64
+    }
+
+    /// Payout slots for `count` periods starting at `from_period`
+    pub fn payout_rotation(&self, from_period: u32, count: u32) -> Vec<PayoutSlot> {
+        if self.members.is_empty() {
+            return Vec::new();
+        }
+
+        (from_period..from_period + count)
+            .map(|period| PayoutSlot {
+                period,
+                due_date: self.due_date(period),
+                recipient_id: self.members[period as usize % self.members.len()].user_id,
+                amount: self.pot(),
+            })
+            .collect()
+    }
+
+    /// The full rotation round containing the current period
+    pub fn current_round(&self, as_of: DateTime<Utc>) -> Vec<PayoutSlot> {
+        let size = self.members.len() as u32;
+        if size == 0 {
+            return Vec::new();
+        }
+        let current = self.periods_due(as_of).saturating_sub(1);
+        self.payout_rotation(current - current % size, size)
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct PayoutSlot {
+    pub period: u32,
+    pub due_date: DateTime<Utc>,
+    pub recipient_id: Uuid,
+    pub amount: u64,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct CreateContributionPlanRequest {
+    pub group_id: Uuid,
+    pub name: String,
+    pub amount: u64,
+    pub frequency: ContributionFrequency,
+    pub start_date: DateTime<Utc>,
+    pub members: Vec<ContributionMember>,
+}
+
+/// Expected-vs-actual position of one member
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct MemberContributionStatus {
+    pub user_id: Uuid,
+    pub wallet_id: Uuid,
+    pub expected: u64,
+    pub paid: u64,
+    pub arrears: u64,
+    /// Paid ahead of the schedule
+    pub credit: u64,
+    /// Due periods not fully covered by contributions
+    pub periods_missed: u32,
+    pub last_contribution_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ContributionReconciliation {
+    pub plan_id: Uuid,
+    pub as_of: DateTime<Utc>,
+    pub periods_due: u32,
+    pub expected_total: u64,
+    pub paid_total: u64,
+    pub arrears_total: u64,
+    pub members: Vec<MemberContributionStatus>,
+}
+
+impl ContributionReconciliation {
+    pub fn members_in_arrears(&self) -> usize {
+        self.members.iter().filter(|m| m.arrears > 0).count()
+    }
+}
+
+/// Reconcile a plan against the transactions on its members' wallets.
+/// Confirmed contributions between the start date and `as_of` count, allocated to
+/// the oldest periods first; deposits, payouts and other credits do not. Each
+/// wallet pays into a single plan, so every contribution on it belongs to `plan`.
+pub fn reconcile_contributions(
+    plan: &ContributionPlan,
+    transactions: &HashMap<Uuid, Vec<WalletTransaction>>,
+    as_of: DateTime<Utc>,
+) -> ContributionReconciliation {
+    let periods_due = plan.periods_due(as_of);
+    let expected = plan.amount * periods_due as u64;
+
+    let members: Vec<MemberContributionStatus> = plan
+        .members
+        .iter()
+        .map(|member| {
+            let contributions: Vec<&WalletTransaction> = transactions
+                .get(&member.wallet_id)
+                .map(|txs| {
+                    txs.iter()
+                        .filter(|tx| {
+                            tx.transaction_type == TransactionType::Contribution
+                                && tx.status == TransactionStatus::Confirmed
+                                && tx.created_at >= plan.start_date
+                                && tx.created_at <= as_of
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+
+            let paid: u64 = contributions
+                .iter()
+                .map(|tx| tx.amount.unsigned_abs())
+                .sum();
+            let periods_covered = paid
+                .checked_div(plan.amount)
+                .map_or(periods_due, |covered| {
+                    covered.min(periods_due as u64) as u32
+                });
+
+            MemberContributionStatus {
+                user_id: member.user_id,
+                wallet_id: member.wallet_id,
+                expected,
+                paid,
+                arrears: expected.saturating_sub(paid),
+                credit: paid.saturating_sub(expected),
+                periods_missed: periods_due - periods_covered,
+                last_contribution_at: contributions.iter().map(|tx| tx.created_at).max(),
+            }
+        })
+        .collect();
+
+    ContributionReconciliation {
+        plan_id: plan.id,
+        as_of,
+        periods_due,
+        expected_total: expected * members.len() as u64,
+        paid_total: members.iter().map(|m| m.paid).sum(),
+        arrears_total: members.iter().map(|m| m.arrears).sum(),
+        members,
+    }
+}
+
+#[async_trait]
+pub trait ContributionsApi: Send + Sync {
+    /// Get the contribution plans attached to a group
+    async fn get_contribution_plans(&self, group_id: Uuid) -> ApiResult<Vec<ContributionPlan>>;
+
+    /// Get a contribution plan by ID
+    async fn get_contribution_plan(&self, plan_id: Uuid) -> ApiResult<ContributionPlan>;
+
+    /// Attach a new contribution plan to a group. Members must belong to the group
+    /// and pay from a wallet that isn't enrolled in another plan.
+    async fn create_contribution_plan(
+        &self,
+        request: CreateContributionPlanRequest,
+    ) -> ApiResult<ContributionPlan>;
+
+    /// Pay a contribution from the payer's plan wallet into the chama's pool
+    async fn pay_contribution(
+        &self,
+        plan_id: Uuid,
+        payer: &User,
+        amount: u64,
+    ) -> ApiResult<WalletTransaction>;
+
+    /// Compare expected contributions with wallet transactions as of a point in time
+    async fn reconcile_contribution_plan(
+        &self,
+        plan_id: Uuid,
+        as_of: DateTime<Utc>,
+    ) -> ApiResult<ContributionReconciliation>;
+}
@@ -0,0 +1,232 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+use crate::api::{
+    errors::ApiResult,
+    traits::ledger::AccountMovement,
+    types::{PaginatedResponse, PaginationQuery, User},
+};
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct DividendRunRequest {
+    pub period_start: DateTime<Utc>,
+    pub period_end: DateTime<Utc>,
+    /// Annual dividend rate on share capital, as a percentage
+    pub dividend_rate: Decimal,
+    /// Annual interest rate on wallet savings, as a percentage
+    pub savings_interest_rate: Decimal,
+    /// Rate used to pay KES dividends into sats wallets
+    pub sats_per_kes: Decimal,
+    pub created_by: Uuid,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum DividendRunStatus {
+    #[serde(rename = "preview")]
+    Preview,
+    #[serde(rename = "approved")]
+    Approved,
+    #[serde(rename = "rejected")]
+    Rejected,
+    /// Paid out, except to members whose payout failed; paying again retries them
+    #[serde(rename = "partially_paid")]
+    PartiallyPaid,
+    #[serde(rename = "paid")]
+    Paid,
+}
+
+impl DividendRunStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DividendRunStatus::Preview => "preview",
+            DividendRunStatus::Approved => "approved",
+            DividendRunStatus::Rejected => "rejected",
+            DividendRunStatus::PartiallyPaid => "partially paid",
+            DividendRunStatus::Paid => "paid",
+        }
+    }
+
+    /// Approved runs, paid or not, own their period; no other run may cover it
+    pub fn is_committed(&self) -> bool {
+        matches!(
+            self,
+            DividendRunStatus::Approved
+                | DividendRunStatus::PartiallyPaid
+                | DividendRunStatus::Paid
+        )
+    }
+}
+
+/// What one member earned over the period
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct MemberEntitlement {
+    pub user_id: Uuid,
+    /// Time-weighted average share capital in KES
+    pub average_share_capital: Decimal,
+    pub dividend_kes: Decimal,
+    pub dividend_sats: u64,
+    /// Time-weighted average wallet savings in sats
+    pub average_savings: Decimal,
+    pub interest_sats: u64,
+    /// Wallet the payout goes to; `None` when the member has no wallet
+    pub payout_wallet_id: Option<Uuid>,
+    pub paid_at: Option<DateTime<Utc>>,
+    /// Why the last payout attempt didn't reach the member
+    #[serde(default)]
+    pub payout_error: Option<String>,
+}
+
+impl MemberEntitlement {
+    pub fn total_sats(&self) -> u64 {
+        self.dividend_sats + self.interest_sats
+    }
+
+    /// Owed but not yet paid
+    pub fn is_outstanding(&self) -> bool {
+        self.paid_at.is_none() && self.total_sats() > 0
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct DividendRun {
+    pub id: Uuid,
+    pub period_start: DateTime<Utc>,
+    pub period_end: DateTime<Utc>,
+    pub dividend_rate: Decimal,
+    pub savings_interest_rate: Decimal,
+    pub sats_per_kes: Decimal,
+    pub status: DividendRunStatus,
+    pub entitlements: Vec<MemberEntitlement>,
+    pub created_by: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub reviewed_by: Option<Uuid>,
+    pub reviewed_at: Option<DateTime<Utc>>,
+    pub rejection_reason: Option<String>,
+    pub paid_at: Option<DateTime<Utc>>,
+}
+
+impl DividendRun {
+    pub fn total_dividend_kes(&self) -> Decimal {
+        self.entitlements.iter().map(|e| e.dividend_kes).sum()
+    }
+
+    pub fn total_dividend_sats(&self) -> u64 {
+        self.entitlements.iter().map(|e| e.dividend_sats).sum()
+    }
+
+    pub fn total_interest_sats(&self) -> u64 {
+        self.entitlements.iter().map(|e| e.interest_sats).sum()
+    }
+
+    /// Payout schedule as CSV, one row per member
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from(
+            "member_id,average_share_capital_kes,dividend_kes,dividend_sats,\
+             average_savings_sats,interest_sats,total_sats,payout_wallet_id,paid_at,payout_error\n",
+        );
+        for e in &self.entitlements {
+            csv.push_str(&format!(
+                "{},{},{},{},{},{},{},{},{},{}\n",
+                e.user_id,
+                e.average_share_capital.round_dp(2),
+                e.dividend_kes,
+                e.dividend_sats,
+                e.average_savings.round_dp(0),
+                e.interest_sats,
+                e.total_sats(),
+                e.payout_wallet_id
+                    .map(|id| id.to_string())
+                    .unwrap_or_default(),
+                e.paid_at.map(|at| at.to_rfc3339()).unwrap_or_default(),
+                e.payout_error
+                    .as_deref()
+                    .map(|error| format!("\"{}\"", error.replace('"', "\"\"")))
+                    .unwrap_or_default(),
+            ));
+        }
+        csv
+    }
+}
+
+/// Average balance over `[start, end)`, weighting each balance by how long it was held.
+/// `movements` must be in posting order.
+pub fn time_weighted_average(
+    movements: &[AccountMovement],
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Decimal {
+    let period = (end - start).num_seconds();
+    if period <= 0 {
+        return Decimal::ZERO;
+    }
+
+    let mut balance: Decimal = movements
+        .iter()
+        .filter(|m| m.created_at < start)
+        .map(|m| m.amount)
+        .sum();
+    let mut cursor = start;
+    let mut weighted = Decimal::ZERO;
+    for movement in movements
+        .iter()
+        .filter(|m| m.created_at >= start && m.created_at < end)
+    {
+        weighted += balance * Decimal::from((movement.created_at - cursor).num_seconds());
+        balance += movement.amount;
+        cursor = movement.created_at;
+    }
+    weighted += balance * Decimal::from((end - cursor).num_seconds());
+
+    weighted / Decimal::from(period)
+}
+
+/// Pro-rated return on an average balance at an annual percentage rate
+pub fn pro_rated_return(
+    average_balance: Decimal,
+    annual_rate: Decimal,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Decimal {
+    let year_fraction =
+        Decimal::from((end - start).num_seconds()) / Decimal::from(365 * 24 * 60 * 60);
+    (average_balance * annual_rate / Decimal::ONE_HUNDRED * year_fraction).max(Decimal::ZERO)
+}
+
+/// Round a sats amount down to whole sats
+pub fn whole_sats(amount: Decimal) -> u64 {
+    amount.floor().to_u64().unwrap_or(0)
+}
+
+#[async_trait]
+pub trait DividendsApi: Send + Sync {
+    /// Compute entitlements for a period and store them as a preview
+    async fn create_dividend_run(&self, request: DividendRunRequest) -> ApiResult<DividendRun>;
+
+    /// Get a dividend run by ID
+    async fn get_dividend_run(&self, run_id: Uuid) -> ApiResult<DividendRun>;
+
+    /// Get dividend runs, newest first
+    async fn get_dividend_runs(
+        &self,
+        pagination: PaginationQuery,
+    ) -> ApiResult<PaginatedResponse<DividendRun>>;
+
+    /// Approve a preview; `checker` must be an admin other than its creator
+    async fn approve_dividend_run(&self, run_id: Uuid, checker: &User) -> ApiResult<DividendRun>;
+
+    /// Reject a preview; `checker` must be an admin
+    async fn reject_dividend_run(
+        &self,
+        run_id: Uuid,
+        checker: &User,
+        reason: Option<String>,
+    ) -> ApiResult<DividendRun>;
+
+    /// Credit approved entitlements to member wallets. Members who can't be paid
+    /// keep their entitlement outstanding, with the reason, and leave the run
+    /// partially paid; paying it again retries just them.
+    async fn pay_dividend_run(&self, run_id: Uuid, operator: &User) -> ApiResult<DividendRun>;
+}
@@ -0,0 +1,139 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rust_decimal::{Decimal, RoundingStrategy};
+
+use crate::api::{
+    errors::ApiResult,
+    traits::{ledger::LedgerCurrency, statements::MemberStatement},
+};
+
+pub const SATS_PER_BTC: i64 = 100_000_000;
+
+/// The bitcoin price in KES observed at a point in time
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct FxRate {
+    pub kes_per_btc: Decimal,
+    /// Rate source name, or `manual` for rates entered by an admin
+    pub source: String,
+    pub observed_at: DateTime<Utc>,
+}
+
+impl FxRate {
+    pub fn sats_per_kes(&self) -> Decimal {
+        Decimal::from(SATS_PER_BTC) / self.kes_per_btc
+    }
+
+    pub fn sats_to_kes(&self, sats: Decimal) -> Decimal {
+        self.convert(sats, LedgerCurrency::Sats, LedgerCurrency::Kes)
+    }
+
+    pub fn kes_to_sats(&self, kes: Decimal) -> Decimal {
+        self.convert(kes, LedgerCurrency::Kes, LedgerCurrency::Sats)
+    }
+
+    /// Convert between currencies, rounded half away from zero to the target
+    /// currency's smallest unit
+    pub fn convert(&self, amount: Decimal, from: LedgerCurrency, to: LedgerCurrency) -> Decimal {
+        let converted = match (from, to) {
+            (LedgerCurrency::Sats, LedgerCurrency::Kes) => {
+                amount * self.kes_per_btc / Decimal::from(SATS_PER_BTC)
+            }
+            (LedgerCurrency::Kes, LedgerCurrency::Sats) => {
+                amount * Decimal::from(SATS_PER_BTC) / self.kes_per_btc
+            }
+            _ => amount,
+        };
+        converted
+            .round_dp_with_strategy(to.decimal_places(), RoundingStrategy::MidpointAwayFromZero)
+    }
+}
+
+/// One account balance shown in both currencies
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ConvertedBalance {
+    pub title: String,
+    pub currency: LedgerCurrency,
+    pub sats: Option<Decimal>,
+    pub kes: Option<Decimal>,
+}
+
+/// Every balance a member holds at a point in time, valued at the rate in force then
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct MemberBalances {
+    pub as_of: DateTime<Utc>,
+    /// `None` when no rate had been recorded yet, leaving the other currency blank
+    pub rate: Option<FxRate>,
+    pub balances: Vec<ConvertedBalance>,
+}
+
+impl MemberBalances {
+    /// Closing balances of a statement that ends at `as_of`
+    pub fn from_statement(statement: &MemberStatement, rate: Option<FxRate>) -> Self {
+        let balances = statement
+            .sections
+            .iter()
+            .map(|section| {
+                let amount = section.closing_balance;
+                let in_currency = |currency: LedgerCurrency| {
+                    if currency == section.currency {
+                        Some(amount)
+                    } else {
+                        rate.as_ref()
+                            .map(|rate| rate.convert(amount, section.currency, currency))
+                    }
+                };
+                ConvertedBalance {
+                    title: section.title.clone(),
+                    currency: section.currency,
+                    sats: in_currency(LedgerCurrency::Sats),
+                    kes: in_currency(LedgerCurrency::Kes),
+                }
+            })
+            .collect();
+
+        Self {
+            as_of: statement.to,
+            rate,
+            balances,
+        }
+    }
+
+    pub fn total_sats(&self) -> Option<Decimal> {
+        self.balances.iter().map(|b| b.sats).sum()
+    }
+
+    pub fn total_kes(&self) -> Option<Decimal> {
+        self.balances.iter().map(|b| b.kes).sum()
+    }
+}
+
+/// Somewhere the current bitcoin price can be fetched from
+#[async_trait]
+pub trait RateSource: Send + Sync {
+    fn name(&self) -> &str;
+
+    /// The current price of one bitcoin in KES
+    async fn fetch_kes_per_btc(&self) -> ApiResult<Decimal>;
+}
+
+#[async_trait]
+pub trait FxApi: Send + Sync {
+    /// The most recently observed rate
+    async fn current_rate(&self) -> ApiResult<FxRate>;
+
+    /// The rate in force at `at`: the latest one observed at or before it
+    async fn rate_at(&self, at: DateTime<Utc>) -> ApiResult<FxRate>;
+
+    /// Rates observed in `[from, to)`, oldest first
+    async fn get_rate_history(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> ApiResult<Vec<FxRate>>;
+
+    /// Record a rate entered by hand
+    async fn record_rate(&self, kes_per_btc: Decimal, source: String) -> ApiResult<FxRate>;
+
+    /// Fetch a rate from the configured sources, in order, and record the first one that answers
+    async fn refresh_rate(&self) -> ApiResult<FxRate>;
+}
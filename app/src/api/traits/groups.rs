@@ -12,10 +12,25 @@ pub struct Group {
     pub id: Uuid,
     pub name: String,
     pub description: Option<String>,
+    /// Members as the chama records them; empty when the backend doesn't list them
+    #[serde(default)]
+    pub members: Vec<GroupMember>,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
 }
 
+impl Group {
+    pub fn has_member(&self, user_id: Uuid) -> bool {
+        self.members.iter().any(|member| member.user_id == user_id)
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct GroupMember {
+    #[serde(rename = "userId")]
+    pub user_id: Uuid,
+}
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct CreateGroupRequest {
     pub name: String,
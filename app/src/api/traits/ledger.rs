@@ -0,0 +1,428 @@
+use std::collections::BTreeMap;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+use crate::api::{
+    errors::{ApiError, ApiResult},
+    types::{PaginatedResponse, PaginationQuery, User},
+};
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
+)]
+pub enum LedgerCurrency {
+    #[serde(rename = "SATS")]
+    Sats,
+    #[serde(rename = "KES")]
+    Kes,
+}
+
+impl LedgerCurrency {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LedgerCurrency::Sats => "SATS",
+            LedgerCurrency::Kes => "KES",
+        }
+    }
+
+    /// Smallest unit the currency can be posted in
+    pub fn decimal_places(&self) -> u32 {
+        match self {
+            LedgerCurrency::Sats => 0,
+            LedgerCurrency::Kes => 2,
+        }
+    }
+
+    /// Display an amount with thousands separators, e.g. `12,500 sats` or `KES 1,250.00`
+    pub fn format(&self, amount: Decimal) -> String {
+        let places = self.decimal_places();
+        let rounded = amount.abs().round_dp(places);
+        let text = format!("{:.*}", places as usize, rounded);
+        let (whole, fraction) = match text.split_once('.') {
+            Some((whole, fraction)) => (whole.to_string(), format!(".{}", fraction)),
+            None => (text, String::new()),
+        };
+        let mut grouped = String::with_capacity(whole.len() + whole.len() / 3);
+        for (i, ch) in whole.chars().enumerate() {
+            if i > 0 && (whole.len() - i).is_multiple_of(3) {
+                grouped.push(',');
+            }
+            grouped.push(ch);
+        }
+        let sign = if amount.is_sign_negative() && !rounded.is_zero() {
+            "-"
+        } else {
+            ""
+        };
+        match self {
+            LedgerCurrency::Sats => format!("{}{}{} sats", sign, grouped, fraction),
+            LedgerCurrency::Kes => format!("{}KES {}{}", sign, grouped, fraction),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum AccountType {
+    #[serde(rename = "asset")]
+    Asset,
+    #[serde(rename = "liability")]
+    Liability,
+    #[serde(rename = "equity")]
+    Equity,
+    #[serde(rename = "income")]
+    Income,
+    #[serde(rename = "expense")]
+    Expense,
+}
+
+impl AccountType {
+    /// Assets and expenses grow with debits; everything else grows with credits
+    pub fn is_debit_normal(&self) -> bool {
+        matches!(self, AccountType::Asset | AccountType::Expense)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type", content = "id")]
+pub enum AccountOwner {
+    #[serde(rename = "member")]
+    Member(Uuid),
+    #[serde(rename = "chama")]
+    Chama(Uuid),
+    #[serde(rename = "sacco")]
+    Sacco,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type", content = "id")]
+pub enum AccountPurpose {
+    /// Balance the SACCO owes a member on one of their wallets
+    #[serde(rename = "wallet")]
+    Wallet(Uuid),
+    #[serde(rename = "share_capital")]
+    ShareCapital,
+    #[serde(rename = "loans_receivable")]
+    LoansReceivable,
+    /// Share subscriptions a member has yet to pay for
+    #[serde(rename = "shares_receivable")]
+    SharesReceivable,
+    /// Share capital subscribed but not yet paid for; it becomes share capital
+    /// once the subscription is paid in full
+    #[serde(rename = "unpaid_share_capital")]
+    UnpaidShareCapital,
+    /// Funds held by the SACCO backing member balances
+    #[serde(rename = "treasury")]
+    Treasury,
+    #[serde(rename = "interest_income")]
+    InterestIncome,
+    /// Funds a chama has pooled from its members
+    #[serde(rename = "contributions_pool")]
+    ContributionsPool,
+    /// Interest the SACCO pays on member savings
+    #[serde(rename = "interest_expense")]
+    InterestExpense,
+    /// Dividends distributed to members out of equity
+    #[serde(rename = "dividends")]
+    Dividends,
+    /// Lightning routing fees the SACCO pays on member withdrawals
+    #[serde(rename = "routing_fees")]
+    RoutingFees,
+}
+
+impl AccountPurpose {
+    pub fn account_type(&self) -> AccountType {
+        match self {
+            AccountPurpose::Wallet(_) | AccountPurpose::ContributionsPool => AccountType::Liability,
+            AccountPurpose::ShareCapital
+            | AccountPurpose::UnpaidShareCapital
+            | AccountPurpose::Dividends => AccountType::Equity,
+            AccountPurpose::LoansReceivable
+            | AccountPurpose::SharesReceivable
+            | AccountPurpose::Treasury => AccountType::Asset,
+            AccountPurpose::InterestIncome => AccountType::Income,
+            AccountPurpose::InterestExpense | AccountPurpose::RoutingFees => AccountType::Expense,
+        }
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            AccountPurpose::Wallet(id) => format!("Wallet {}", id),
+            AccountPurpose::ShareCapital => "Share capital".to_string(),
+            AccountPurpose::LoansReceivable => "Loans receivable".to_string(),
+            AccountPurpose::SharesReceivable => "Share subscriptions receivable".to_string(),
+            AccountPurpose::UnpaidShareCapital => "Share capital awaiting payment".to_string(),
+            AccountPurpose::Treasury => "Treasury".to_string(),
+            AccountPurpose::InterestIncome => "Interest income".to_string(),
+            AccountPurpose::ContributionsPool => "Contributions pool".to_string(),
+            AccountPurpose::InterestExpense => "Interest on savings".to_string(),
+            AccountPurpose::Dividends => "Dividends".to_string(),
+            AccountPurpose::RoutingFees => "Lightning routing fees".to_string(),
+        }
+    }
+}
+
+/// Identifies a ledger account; accounts are opened on their first posting
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub struct AccountKey {
+    pub owner: AccountOwner,
+    pub purpose: AccountPurpose,
+    pub currency: LedgerCurrency,
+}
+
+impl AccountKey {
+    pub fn member_wallet(user_id: Uuid, wallet_id: Uuid) -> Self {
+        Self {
+            owner: AccountOwner::Member(user_id),
+            purpose: AccountPurpose::Wallet(wallet_id),
+            currency: LedgerCurrency::Sats,
+        }
+    }
+
+    pub fn member(user_id: Uuid, purpose: AccountPurpose, currency: LedgerCurrency) -> Self {
+        Self {
+            owner: AccountOwner::Member(user_id),
+            purpose,
+            currency,
+        }
+    }
+
+    pub fn chama(group_id: Uuid, purpose: AccountPurpose, currency: LedgerCurrency) -> Self {
+        Self {
+            owner: AccountOwner::Chama(group_id),
+            purpose,
+            currency,
+        }
+    }
+
+    pub fn sacco(purpose: AccountPurpose, currency: LedgerCurrency) -> Self {
+        Self {
+            owner: AccountOwner::Sacco,
+            purpose,
+            currency,
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct LedgerAccount {
+    pub id: Uuid,
+    pub key: AccountKey,
+    pub name: String,
+    pub account_type: AccountType,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum EntrySide {
+    #[serde(rename = "debit")]
+    Debit,
+    #[serde(rename = "credit")]
+    Credit,
+}
+
+impl EntrySide {
+    pub fn opposite(&self) -> Self {
+        match self {
+            EntrySide::Debit => EntrySide::Credit,
+            EntrySide::Credit => EntrySide::Debit,
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Posting {
+    pub account_id: Uuid,
+    pub side: EntrySide,
+    pub amount: Decimal,
+    pub currency: LedgerCurrency,
+}
+
+/// An immutable, balanced journal entry. Corrections are made by posting a reversal.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct JournalEntry {
+    pub id: Uuid,
+    pub description: String,
+    /// Wallet transaction, subscription, loan or other record this entry accounts for
+    pub reference_id: Option<Uuid>,
+    pub postings: Vec<Posting>,
+    /// Entry this one reverses, if it is a reversal
+    pub reverses: Option<Uuid>,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct PostingRequest {
+    pub account: AccountKey,
+    pub side: EntrySide,
+    pub amount: Decimal,
+}
+
+impl PostingRequest {
+    pub fn debit(account: AccountKey, amount: impl Into<Decimal>) -> Self {
+        Self {
+            account,
+            side: EntrySide::Debit,
+            amount: amount.into(),
+        }
+    }
+
+    pub fn credit(account: AccountKey, amount: impl Into<Decimal>) -> Self {
+        Self {
+            account,
+            side: EntrySide::Credit,
+            amount: amount.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct JournalEntryRequest {
+    pub description: String,
+    pub reference_id: Option<Uuid>,
+    pub postings: Vec<PostingRequest>,
+    pub created_by: Option<Uuid>,
+}
+
+/// An account with totals computed from the journal
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct AccountBalance {
+    pub account: LedgerAccount,
+    pub debits: Decimal,
+    pub credits: Decimal,
+    /// Net balance on the account's normal side
+    pub balance: Decimal,
+}
+
+impl AccountBalance {
+    pub fn new(account: LedgerAccount, debits: Decimal, credits: Decimal) -> Self {
+        let balance = if account.account_type.is_debit_normal() {
+            debits - credits
+        } else {
+            credits - debits
+        };
+        Self {
+            account,
+            debits,
+            credits,
+            balance,
+        }
+    }
+}
+
+/// One posting to an account, signed on the account's normal side
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct AccountMovement {
+    pub entry_id: Uuid,
+    pub description: String,
+    pub reference_id: Option<Uuid>,
+    pub amount: Decimal,
+    pub balance_after: Decimal,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct TrialBalanceLine {
+    pub currency: LedgerCurrency,
+    pub debits: Decimal,
+    pub credits: Decimal,
+}
+
+impl TrialBalanceLine {
+    pub fn is_balanced(&self) -> bool {
+        self.debits == self.credits
+    }
+}
+
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct JournalFilter {
+    pub account_id: Option<Uuid>,
+    pub reference_id: Option<Uuid>,
+}
+
+/// Check that a set of postings forms a valid double entry: at least two lines,
+/// positive amounts in each currency's smallest unit, and debits equal to credits
+/// in every currency
+pub fn validate_postings(postings: &[PostingRequest]) -> ApiResult<()> {
+    if postings.len() < 2 {
+        return Err(ApiError::Validation {
+            message: "A journal entry needs at least two postings".to_string(),
+        });
+    }
+
+    let mut totals: BTreeMap<LedgerCurrency, (Decimal, Decimal)> = BTreeMap::new();
+    for posting in postings {
+        let currency = posting.account.currency;
+        if posting.amount <= Decimal::ZERO {
+            return Err(ApiError::Validation {
+                message: "Posting amounts must be greater than zero".to_string(),
+            });
+        }
+        if posting.amount.round_dp(currency.decimal_places()) != posting.amount {
+            return Err(ApiError::Validation {
+                message: format!(
+                    "{} amounts allow at most {} decimal places",
+                    currency.as_str(),
+                    currency.decimal_places()
+                ),
+            });
+        }
+
+        let (debits, credits) = totals.entry(currency).or_default();
+        match posting.side {
+            EntrySide::Debit => *debits += posting.amount,
+            EntrySide::Credit => *credits += posting.amount,
+        }
+    }
+
+    for (currency, (debits, credits)) in totals {
+        if debits != credits {
+            return Err(ApiError::Validation {
+                message: format!(
+                    "Journal entry is unbalanced in {}: debits {} vs credits {}",
+                    currency.as_str(),
+                    debits,
+                    credits
+                ),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+#[async_trait]
+pub trait LedgerApi: Send + Sync {
+    /// Get accounts with computed balances, optionally limited to one owner
+    async fn get_accounts(&self, owner: Option<AccountOwner>) -> ApiResult<Vec<AccountBalance>>;
+
+    /// Get the computed balance of an account; unopened accounts have a zero balance
+    async fn get_account_balance(&self, key: AccountKey) -> ApiResult<Decimal>;
+
+    /// Get every posting to an account in the order it was made
+    async fn get_account_movements(&self, key: AccountKey) -> ApiResult<Vec<AccountMovement>>;
+
+    /// Post a balanced journal entry
+    async fn post_journal_entry(&self, request: JournalEntryRequest) -> ApiResult<JournalEntry>;
+
+    /// Post the mirror image of an entry; `reviewer` must be an admin
+    async fn reverse_journal_entry(
+        &self,
+        entry_id: Uuid,
+        reviewer: &User,
+        reason: String,
+    ) -> ApiResult<JournalEntry>;
+
+    /// Get journal entries matching a filter, newest first
+    async fn get_journal_entries(
+        &self,
+        filter: JournalFilter,
+        pagination: PaginationQuery,
+    ) -> ApiResult<PaginatedResponse<JournalEntry>>;
+
+    /// Total debits and credits per currency across the whole journal
+    async fn get_trial_balance(&self) -> ApiResult<Vec<TrialBalanceLine>>;
+}
@@ -0,0 +1,139 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::api::{
+    errors::{ApiError, ApiResult},
+    traits::wallets::TransactionStatus,
+    types::User,
+};
+
+/// A BOLT11 invoice issued for a member deposit
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct LightningInvoice {
+    pub id: Uuid,
+    pub wallet_id: Uuid,
+    /// Pending wallet transaction that is confirmed once the invoice is paid
+    pub transaction_id: Uuid,
+    pub bolt11: String,
+    pub amount_sats: u64,
+    pub description: String,
+    /// Operation the fedimint client tracks the invoice under
+    pub operation_id: String,
+    pub status: TransactionStatus,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub settled_at: Option<DateTime<Utc>>,
+}
+
+/// A BOLT11 invoice paid out of a member wallet
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct LightningPayment {
+    pub id: Uuid,
+    pub wallet_id: Uuid,
+    /// Wallet transaction holding the funds while the payment is in flight
+    pub transaction_id: Uuid,
+    pub bolt11: String,
+    pub amount_sats: u64,
+    /// Routing fee reported by the gateway
+    pub fee_sats: u64,
+    pub operation_id: Option<String>,
+    pub status: TransactionStatus,
+    pub failure_reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub settled_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct CreateInvoiceRequest {
+    pub wallet_id: Uuid,
+    pub amount_sats: u64,
+    pub description: String,
+    /// Seconds until the invoice expires
+    pub expiry_secs: u32,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct PayInvoiceRequest {
+    pub wallet_id: Uuid,
+    pub bolt11: String,
+}
+
+/// Amount encoded in a BOLT11 invoice's human-readable part, in millisatoshis.
+/// Returns `None` for invoices that leave the amount to the payer.
+pub fn bolt11_amount_msat(invoice: &str) -> ApiResult<Option<u64>> {
+    let invalid = |reason: &str| ApiError::Validation {
+        message: format!("Invalid BOLT11 invoice: {}", reason),
+    };
+
+    let invoice = invoice.trim().to_lowercase();
+    let invoice = invoice.strip_prefix("lightning:").unwrap_or(&invoice);
+    let (hrp, _) = invoice
+        .rsplit_once('1')
+        .ok_or_else(|| invalid("missing separator"))?;
+    let rest = hrp
+        .strip_prefix("ln")
+        .ok_or_else(|| invalid("must start with 'ln'"))?;
+
+    // Network prefix, longest first so "bcrt" isn't read as "bc"
+    let amount = ["bcrt", "tbs", "bc", "tb", "sb"]
+        .iter()
+        .find_map(|network| rest.strip_prefix(network))
+        .ok_or_else(|| invalid("unknown network"))?;
+    if amount.is_empty() {
+        return Ok(None);
+    }
+
+    let (digits, multiplier) = match amount.chars().last() {
+        Some(unit @ ('m' | 'u' | 'n' | 'p')) => (&amount[..amount.len() - 1], Some(unit)),
+        _ => (amount, None),
+    };
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) || digits.starts_with('0') {
+        return Err(invalid("malformed amount"));
+    }
+    let value: u64 = digits.parse().map_err(|_| invalid("amount too large"))?;
+
+    // Amounts are in bitcoin; one bitcoin is 10^11 millisatoshis
+    let msat = match multiplier {
+        None => value.checked_mul(100_000_000_000),
+        Some('m') => value.checked_mul(100_000_000),
+        Some('u') => value.checked_mul(100_000),
+        Some('n') => value.checked_mul(100),
+        Some(_) => {
+            // Pico-bitcoin is a tenth of a millisatoshi, so it must be a multiple of ten
+            if !value.is_multiple_of(10) {
+                return Err(invalid("sub-millisatoshi amount"));
+            }
+            Some(value / 10)
+        }
+    };
+    msat.map(Some).ok_or_else(|| invalid("amount too large"))
+}
+
+#[async_trait]
+pub trait LightningApi: Send + Sync {
+    /// Issue an invoice for a deposit; the wallet is credited once it is paid
+    async fn create_deposit_invoice(
+        &self,
+        request: CreateInvoiceRequest,
+    ) -> ApiResult<LightningInvoice>;
+
+    /// Pay an invoice from a wallet; funds are held while the payment is in flight.
+    /// A payment whose outcome fmcd didn't report is returned pending. `payer` is
+    /// held to their role's withdrawal limits.
+    async fn pay_invoice(
+        &self,
+        request: PayInvoiceRequest,
+        payer: &User,
+    ) -> ApiResult<LightningPayment>;
+
+    /// Get the invoices issued for a wallet, newest first
+    async fn get_wallet_invoices(&self, wallet_id: Uuid) -> ApiResult<Vec<LightningInvoice>>;
+
+    /// Get the payments made from a wallet, newest first
+    async fn get_wallet_payments(&self, wallet_id: Uuid) -> ApiResult<Vec<LightningPayment>>;
+
+    /// Check pending invoices and payments with the fedimint client and settle
+    /// the ones that were paid, failed or expired. Returns how many changed status.
+    async fn sync_pending_invoices(&self) -> ApiResult<usize>;
+}
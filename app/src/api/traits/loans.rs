@@ -0,0 +1,270 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+use crate::api::{
+    errors::ApiResult,
+    types::{PaginatedResponse, PaginationQuery, User},
+};
+
+/// Longest term a loan product may have
+pub const MAX_TERM_MONTHS: u32 = 360;
+
+/// Highest annual interest rate a loan product may charge, as a percentage
+pub const MAX_ANNUAL_INTEREST_RATE: u32 = 100;
+
+/// A loan product members can apply for. Amounts are in satoshis.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct LoanProduct {
+    pub id: Uuid,
+    pub name: String,
+    /// Annual interest rate as a percentage, e.g. `12` for 12% p.a.
+    pub annual_interest_rate: Decimal,
+    pub term_months: u32,
+    pub min_amount: u64,
+    pub max_amount: u64,
+    /// Maximum loan as a multiple of the member's wallet savings
+    pub max_savings_multiple: Option<Decimal>,
+    /// Maximum loan in sats per share the member holds
+    pub max_sats_per_share: Option<u64>,
+    pub required_guarantors: u32,
+    pub active: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct CreateLoanProductRequest {
+    pub name: String,
+    pub annual_interest_rate: Decimal,
+    pub term_months: u32,
+    pub min_amount: u64,
+    pub max_amount: u64,
+    pub max_savings_multiple: Option<Decimal>,
+    pub max_sats_per_share: Option<u64>,
+    pub required_guarantors: u32,
+}
+
+/// Whether a guarantor has agreed to the pledge made in their name
+#[derive(Debug, Clone, Copy, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum GuaranteeStatus {
+    #[default]
+    #[serde(rename = "pending")]
+    Pending,
+    #[serde(rename = "accepted")]
+    Accepted,
+    #[serde(rename = "declined")]
+    Declined,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Guarantor {
+    pub user_id: Uuid,
+    /// Portion of the loan this member guarantees, in sats
+    pub amount: u64,
+    /// Pending until the guarantor accepts; applications ignore what callers send
+    #[serde(default)]
+    pub status: GuaranteeStatus,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct LoanApplicationRequest {
+    pub product_id: Uuid,
+    pub user_id: Uuid,
+    pub amount: u64,
+    pub purpose: Option<String>,
+    pub guarantors: Vec<Guarantor>,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum LoanStatus {
+    #[serde(rename = "pending_approval")]
+    PendingApproval,
+    #[serde(rename = "approved")]
+    Approved,
+    #[serde(rename = "rejected")]
+    Rejected,
+    #[serde(rename = "disbursed")]
+    Disbursed,
+    #[serde(rename = "repaid")]
+    Repaid,
+}
+
+impl LoanStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LoanStatus::PendingApproval => "pending_approval",
+            LoanStatus::Approved => "approved",
+            LoanStatus::Rejected => "rejected",
+            LoanStatus::Disbursed => "disbursed",
+            LoanStatus::Repaid => "repaid",
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct RepaymentInstallment {
+    pub number: u32,
+    pub due_date: DateTime<Utc>,
+    pub principal: u64,
+    pub interest: u64,
+    pub paid: u64,
+}
+
+impl RepaymentInstallment {
+    pub fn amount_due(&self) -> u64 {
+        self.principal + self.interest
+    }
+
+    pub fn outstanding(&self) -> u64 {
+        self.amount_due().saturating_sub(self.paid)
+    }
+
+    pub fn is_overdue(&self, now: DateTime<Utc>) -> bool {
+        self.due_date < now && self.outstanding() > 0
+    }
+}
+
+/// Overdue position of a disbursed loan
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct LoanArrears {
+    pub overdue_installments: u32,
+    pub overdue_amount: u64,
+    pub days_in_arrears: i64,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Loan {
+    pub id: Uuid,
+    pub product_id: Uuid,
+    pub user_id: Uuid,
+    pub amount: u64,
+    pub annual_interest_rate: Decimal,
+    pub term_months: u32,
+    pub purpose: Option<String>,
+    pub guarantors: Vec<Guarantor>,
+    pub status: LoanStatus,
+    pub submitted_by: Uuid,
+    pub submitted_at: DateTime<Utc>,
+    pub reviewed_by: Option<Uuid>,
+    pub reviewed_at: Option<DateTime<Utc>>,
+    pub rejection_reason: Option<String>,
+    pub disbursed_at: Option<DateTime<Utc>>,
+    pub disbursement_wallet_id: Option<Uuid>,
+    /// Empty until the loan is disbursed
+    pub schedule: Vec<RepaymentInstallment>,
+}
+
+impl Loan {
+    /// Principal and interest still owed across the schedule
+    pub fn outstanding(&self) -> u64 {
+        self.schedule.iter().map(|i| i.outstanding()).sum()
+    }
+
+    pub fn total_repaid(&self) -> u64 {
+        self.schedule.iter().map(|i| i.paid).sum()
+    }
+
+    /// Earliest installment that still has an outstanding balance
+    pub fn next_installment(&self) -> Option<&RepaymentInstallment> {
+        self.schedule.iter().find(|i| i.outstanding() > 0)
+    }
+
+    pub fn arrears(&self, now: DateTime<Utc>) -> LoanArrears {
+        let overdue: Vec<&RepaymentInstallment> =
+            self.schedule.iter().filter(|i| i.is_overdue(now)).collect();
+
+        LoanArrears {
+            overdue_installments: overdue.len() as u32,
+            overdue_amount: overdue.iter().map(|i| i.outstanding()).sum(),
+            days_in_arrears: overdue
+                .first()
+                .map(|i| (now - i.due_date).num_days())
+                .unwrap_or(0),
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct LoanRepaymentRequest {
+    pub loan_id: Uuid,
+    pub amount: u64,
+    /// Wallet to debit; `None` records a repayment received outside the wallets
+    pub wallet_id: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct LoanFilter {
+    pub user_id: Option<Uuid>,
+    /// Loans this member has been asked to guarantee
+    pub guarantor_id: Option<Uuid>,
+    pub status: Option<LoanStatus>,
+}
+
+#[async_trait]
+pub trait LoansApi: Send + Sync {
+    /// Get all loan products
+    async fn get_loan_products(&self) -> ApiResult<Vec<LoanProduct>>;
+
+    /// Create a new loan product; `operator` must be an admin
+    async fn create_loan_product(
+        &self,
+        request: CreateLoanProductRequest,
+        operator: &User,
+    ) -> ApiResult<LoanProduct>;
+
+    /// Submit a loan application for approval. `maker` may only apply for
+    /// someone else's loan if they are an admin, and may not approve it.
+    async fn apply_for_loan(
+        &self,
+        request: LoanApplicationRequest,
+        maker: &User,
+    ) -> ApiResult<Loan>;
+
+    /// Accept or decline a pledge made in `guarantor`'s name on a pending application
+    async fn respond_to_guarantee(
+        &self,
+        loan_id: Uuid,
+        guarantor: &User,
+        accept: bool,
+    ) -> ApiResult<Loan>;
+
+    /// Get a loan by ID
+    async fn get_loan(&self, loan_id: Uuid) -> ApiResult<Loan>;
+
+    /// Get loans matching a filter, newest first
+    async fn get_loans(
+        &self,
+        filter: LoanFilter,
+        pagination: PaginationQuery,
+    ) -> ApiResult<PaginatedResponse<Loan>>;
+
+    /// Approve an application once every guarantor has accepted; `checker` must
+    /// be an admin other than the maker
+    async fn approve_loan(&self, loan_id: Uuid, checker: &User) -> ApiResult<Loan>;
+
+    /// Reject an application; `checker` must be an admin other than the maker
+    async fn reject_loan(
+        &self,
+        loan_id: Uuid,
+        checker: &User,
+        reason: Option<String>,
+    ) -> ApiResult<Loan>;
+
+    /// Pay an approved loan into one of the borrower's wallets and start its schedule
+    async fn disburse_loan(
+        &self,
+        loan_id: Uuid,
+        wallet_id: Uuid,
+        operator: &User,
+    ) -> ApiResult<Loan>;
+
+    /// Apply a repayment to the oldest outstanding installments. Only admins
+    /// record repayments received outside the wallets; a member may only repay
+    /// their own loan from their own wallet.
+    async fn record_repayment(
+        &self,
+        request: LoanRepaymentRequest,
+        payer: &User,
+    ) -> ApiResult<Loan>;
+}
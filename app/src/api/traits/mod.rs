@@ -1,10 +1,38 @@
+pub mod alerts;
 pub mod auth;
+pub mod contributions;
+pub mod dividends;
+pub mod fx;
 pub mod groups;
+pub mod ledger;
+pub mod lightning;
+pub mod loans;
+pub mod onchain;
+pub mod reconciliation;
+pub mod reports;
+pub mod shares;
+pub mod statements;
+pub mod swaps;
 pub mod users;
 pub mod wallets;
+pub mod withdrawals;
 
 // Re-export all traits
+pub use alerts::AlertsApi;
 pub use auth::AuthApi;
+pub use contributions::ContributionsApi;
+pub use dividends::DividendsApi;
+pub use fx::FxApi;
 pub use groups::GroupsApi;
+pub use ledger::LedgerApi;
+pub use lightning::LightningApi;
+pub use loans::LoansApi;
+pub use onchain::OnChainApi;
+pub use reconciliation::ReconciliationApi;
+pub use reports::SavedReportsApi;
+pub use shares::SharesApi;
+pub use statements::StatementsApi;
+pub use swaps::SwapsApi;
 pub use users::UsersApi;
 pub use wallets::WalletsApi;
+pub use withdrawals::WithdrawalPolicyApi;
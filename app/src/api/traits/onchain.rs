@@ -0,0 +1,71 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::api::{errors::ApiResult, traits::wallets::TransactionStatus};
+
+/// An address derived from the SACCO's descriptor for a member's on-chain wallet
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct DepositAddress {
+    pub address: String,
+    pub wallet_id: Uuid,
+    /// Child index on the descriptor's receive chain
+    pub derivation_index: u32,
+    pub created_at: DateTime<Utc>,
+}
+
+/// An output paying one of our addresses, as seen by a chain source
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ChainOutput {
+    pub txid: String,
+    pub vout: u32,
+    pub address: String,
+    pub amount_sats: u64,
+    /// Zero while the transaction is unconfirmed
+    pub confirmations: u32,
+}
+
+/// An incoming on-chain payment and the wallet transaction tracking it
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct OnChainDeposit {
+    pub id: Uuid,
+    pub wallet_id: Uuid,
+    /// Pending wallet transaction that is confirmed once the output is buried deep enough
+    pub transaction_id: Uuid,
+    pub address: String,
+    pub txid: String,
+    pub vout: u32,
+    pub amount_sats: u64,
+    pub confirmations: u32,
+    pub status: TransactionStatus,
+    pub first_seen_at: DateTime<Utc>,
+    pub settled_at: Option<DateTime<Utc>>,
+}
+
+/// Where on-chain deposits are observed: a block explorer, a node or a test double
+#[async_trait]
+pub trait ChainSource: Send + Sync {
+    /// Every output, confirmed or not, that pays one of `addresses`
+    async fn outputs_to(&self, addresses: &[String]) -> ApiResult<Vec<ChainOutput>>;
+}
+
+#[async_trait]
+pub trait OnChainApi: Send + Sync {
+    /// The address members should pay into; a fresh one is derived once the
+    /// current address has received a deposit
+    async fn get_deposit_address(&self, wallet_id: Uuid) -> ApiResult<DepositAddress>;
+
+    /// Every address derived for a wallet, newest first
+    async fn get_wallet_addresses(&self, wallet_id: Uuid) -> ApiResult<Vec<DepositAddress>>;
+
+    /// Deposits seen for a wallet, newest first
+    async fn get_wallet_deposits(&self, wallet_id: Uuid) -> ApiResult<Vec<OnChainDeposit>>;
+
+    /// Confirmations a deposit needs before the wallet is credited
+    fn required_confirmations(&self) -> u32;
+
+    /// Ask the chain source about our addresses, recording new deposits and
+    /// crediting the ones with enough confirmations. Returns how many deposits
+    /// were seen for the first time or changed status.
+    async fn sync_deposits(&self) -> ApiResult<usize>;
+}
@@ -0,0 +1,122 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+use crate::api::errors::ApiResult;
+
+/// Drift tolerated between the ledger and the providers before a run is flagged, in sats
+pub const DEFAULT_DRIFT_THRESHOLD_SATS: u64 = 1_000;
+
+/// A completed movement of funds as one side of the reconciliation recorded it
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ReconciliationItem {
+    /// Identifier both sides know the movement by: an fmcd operation ID or `txid:vout`
+    pub reference: String,
+    /// Sats, negative for funds leaving custody
+    pub amount_sats: i64,
+    pub description: String,
+    pub occurred_at: DateTime<Utc>,
+}
+
+/// What one custody provider reported during a run
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ProviderBalance {
+    pub provider: String,
+    /// `None` when the provider could not be reached
+    pub balance_sats: Option<u64>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum ReconciliationStatus {
+    /// Balances agree within the threshold and every movement was matched
+    #[serde(rename = "balanced")]
+    Balanced,
+    /// Drift above the threshold, or movements only one side knows about
+    #[serde(rename = "drift")]
+    Drift,
+    /// A provider could not be asked, so the run proves nothing
+    #[serde(rename = "failed")]
+    Failed,
+}
+
+/// One comparison of the ledger against what the custody providers hold
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ReconciliationRun {
+    pub id: Uuid,
+    /// Admin who started the run; `None` for scheduled runs
+    pub triggered_by: Option<Uuid>,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    pub threshold_sats: u64,
+    /// Sum of every member wallet account
+    pub member_balances_sats: Decimal,
+    /// The ledger's treasury account: the funds the books say are in custody
+    pub ledger_balance_sats: Decimal,
+    pub providers: Vec<ProviderBalance>,
+    pub provider_balance_sats: Decimal,
+    /// Provider balance minus ledger balance; negative when funds are missing
+    pub drift_sats: Decimal,
+    /// Movements the ledger recorded that no provider reported
+    pub unmatched_ledger: Vec<ReconciliationItem>,
+    /// Movements a provider reported that the ledger has no record of
+    pub unmatched_provider: Vec<ReconciliationItem>,
+    pub status: ReconciliationStatus,
+}
+
+impl ReconciliationRun {
+    /// Whether the providers hold enough to pay out every member balance
+    pub fn covers_members(&self) -> bool {
+        self.provider_balance_sats >= self.member_balances_sats
+    }
+}
+
+/// Where member funds are actually held: the federation, the on-chain descriptor, ...
+#[async_trait]
+pub trait CustodyProvider: Send + Sync {
+    fn name(&self) -> String;
+
+    /// Balance the provider reports holding, in sats
+    async fn balance_sats(&self) -> ApiResult<u64>;
+
+    /// Completed movements the provider reports, in any order
+    async fn movements(&self) -> ApiResult<Vec<ReconciliationItem>>;
+}
+
+/// Pair up movements by reference and amount, returning what is left on each side
+pub fn match_items(
+    ledger: Vec<ReconciliationItem>,
+    provider: Vec<ReconciliationItem>,
+) -> (Vec<ReconciliationItem>, Vec<ReconciliationItem>) {
+    let mut provider = provider;
+    let mut unmatched_ledger = Vec::new();
+    for item in ledger {
+        match provider
+            .iter()
+            .position(|p| p.reference == item.reference && p.amount_sats == item.amount_sats)
+        {
+            Some(position) => {
+                provider.swap_remove(position);
+            }
+            None => unmatched_ledger.push(item),
+        }
+    }
+    unmatched_ledger.sort_by_key(|item| item.occurred_at);
+    provider.sort_by_key(|item| item.occurred_at);
+    (unmatched_ledger, provider)
+}
+
+#[async_trait]
+pub trait ReconciliationApi: Send + Sync {
+    /// Compare the ledger against every custody provider and store the result
+    async fn run_reconciliation(&self, triggered_by: Option<Uuid>) -> ApiResult<ReconciliationRun>;
+
+    /// Stored runs, newest first
+    async fn list_runs(&self) -> ApiResult<Vec<ReconciliationRun>>;
+
+    async fn get_run(&self, run_id: Uuid) -> ApiResult<ReconciliationRun>;
+
+    /// Drift above which a run is flagged, in sats
+    fn drift_threshold_sats(&self) -> u64;
+}
@@ -0,0 +1,55 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::api::{errors::ApiResult, types::ReportSpec};
+
+/// Longest name a saved report may have, in characters
+pub const MAX_REPORT_NAME_CHARS: usize = 80;
+
+/// How a saved report is shown when it is opened
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum ReportView {
+    #[default]
+    #[serde(rename = "chart")]
+    Chart,
+    #[serde(rename = "table")]
+    Table,
+}
+
+/// A custom analytics query an admin named and kept for later
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SavedReport {
+    pub id: Uuid,
+    pub owner_id: String,
+    pub name: String,
+    pub spec: ReportSpec,
+    pub view: ReportView,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SaveReportRequest {
+    pub name: String,
+    pub spec: ReportSpec,
+    pub view: ReportView,
+}
+
+/// Reports are private to the admin who saved them
+#[async_trait]
+pub trait SavedReportsApi: Send + Sync {
+    /// The owner's reports, most recently updated first
+    async fn list_reports(&self, owner_id: &str) -> ApiResult<Vec<SavedReport>>;
+
+    async fn get_report(&self, owner_id: &str, report_id: Uuid) -> ApiResult<SavedReport>;
+
+    /// Save a report, replacing the owner's report of the same name (ignoring case)
+    async fn save_report(
+        &self,
+        owner_id: &str,
+        request: SaveReportRequest,
+    ) -> ApiResult<SavedReport>;
+
+    async fn delete_report(&self, owner_id: &str, report_id: Uuid) -> ApiResult<()>;
+}
@@ -0,0 +1,307 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+use crate::api::{
+    errors::ApiResult,
+    types::{PaginatedResponse, PaginationQuery, User},
+};
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ShareOffer {
+    pub id: Uuid,
+    pub title: String,
+    pub description: Option<String>,
+    pub total_quantity: u64,
+    pub subscribed_quantity: u64,
+    /// Price per share in KES, `None` when the backend doesn't report one
+    pub price_per_share: Option<Decimal>,
+    pub status: ShareOfferStatus,
+    pub available_from: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ShareOffer {
+    /// Number of shares still open for subscription
+    pub fn available_quantity(&self) -> u64 {
+        self.total_quantity.saturating_sub(self.subscribed_quantity)
+    }
+
+    /// Subscription progress from 0.0 to 1.0
+    pub fn progress(&self) -> f64 {
+        if self.total_quantity == 0 {
+            return 0.0;
+        }
+        (self.subscribed_quantity as f64 / self.total_quantity as f64).clamp(0.0, 1.0)
+    }
+
+    /// Total value of the offer in KES, if priced
+    pub fn total_value(&self) -> Option<Decimal> {
+        self.price_per_share
+            .map(|price| price * Decimal::from(self.total_quantity))
+    }
+
+    /// Status as of `now`, treating open offers past their expiry as expired
+    pub fn effective_status(&self, now: DateTime<Utc>) -> ShareOfferStatus {
+        match self.status {
+            ShareOfferStatus::Open if self.expires_at <= now => ShareOfferStatus::Expired,
+            ShareOfferStatus::Open if self.available_quantity() == 0 => {
+                ShareOfferStatus::FullySubscribed
+            }
+            ref status => status.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum ShareOfferStatus {
+    #[serde(rename = "open")]
+    Open,
+    #[serde(rename = "fully_subscribed")]
+    FullySubscribed,
+    #[serde(rename = "closed")]
+    Closed,
+    #[serde(rename = "expired")]
+    Expired,
+}
+
+impl ShareOfferStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ShareOfferStatus::Open => "open",
+            ShareOfferStatus::FullySubscribed => "fully_subscribed",
+            ShareOfferStatus::Closed => "closed",
+            ShareOfferStatus::Expired => "expired",
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct CreateShareOfferRequest {
+    pub title: String,
+    pub description: Option<String>,
+    pub total_quantity: u64,
+    pub price_per_share: Decimal,
+    pub available_from: Option<DateTime<Utc>>,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SubscribeSharesRequest {
+    pub offer_id: Uuid,
+    pub user_id: Uuid,
+    pub quantity: u64,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ShareSubscription {
+    pub id: Uuid,
+    pub offer_id: Uuid,
+    pub user_id: Uuid,
+    pub quantity: u64,
+    pub price_per_share: Option<Decimal>,
+    /// KES paid towards the subscription so far
+    #[serde(default)]
+    pub paid: Decimal,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ShareSubscription {
+    /// Value of the subscription in KES, if the offer was priced
+    pub fn total_value(&self) -> Option<Decimal> {
+        self.price_per_share
+            .map(|price| price * Decimal::from(self.quantity))
+    }
+
+    /// KES still owed on the subscription; unpriced subscriptions owe nothing
+    pub fn outstanding(&self) -> Decimal {
+        (self.total_value().unwrap_or(Decimal::ZERO) - self.paid).max(Decimal::ZERO)
+    }
+
+    /// Whether the member has paid for every share in the subscription
+    pub fn is_paid_up(&self) -> bool {
+        self.outstanding() == Decimal::ZERO
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct PaySharesRequest {
+    pub subscription_id: Uuid,
+    /// Member wallet to pay from, or `None` for money received outside the wallets
+    pub wallet_id: Option<Uuid>,
+    /// Amount to pay in KES
+    pub amount: Decimal,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ShareTransfer {
+    pub id: Uuid,
+    pub from_user_id: Uuid,
+    pub to_user_id: Uuid,
+    pub quantity: u64,
+    /// Agreed price per share in KES, if the members recorded one
+    pub price_per_share: Option<Decimal>,
+    pub note: Option<String>,
+    pub status: ShareTransferStatus,
+    /// Member or admin who asked for the transfer
+    #[serde(default)]
+    pub requested_by: Uuid,
+    pub requested_at: DateTime<Utc>,
+    pub reviewed_by: Option<Uuid>,
+    pub reviewed_at: Option<DateTime<Utc>>,
+    pub rejection_reason: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum ShareTransferStatus {
+    #[serde(rename = "pending")]
+    Pending,
+    #[serde(rename = "approved")]
+    Approved,
+    #[serde(rename = "rejected")]
+    Rejected,
+}
+
+impl ShareTransferStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ShareTransferStatus::Pending => "pending",
+            ShareTransferStatus::Approved => "approved",
+            ShareTransferStatus::Rejected => "rejected",
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct TransferSharesRequest {
+    pub from_user_id: Uuid,
+    pub to_user_id: Uuid,
+    pub quantity: u64,
+    pub price_per_share: Option<Decimal>,
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct ShareTransferFilter {
+    /// Match transfers where the member is either sender or recipient
+    pub user_id: Option<Uuid>,
+    pub status: Option<ShareTransferStatus>,
+}
+
+/// A single movement in a member's share holdings
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ShareLedgerEntry {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub kind: ShareLedgerEntryKind,
+    /// Positive for credits, negative for debits
+    pub quantity: i64,
+    /// Member holdings immediately after this entry
+    pub balance_after: u64,
+    /// Subscription or transfer that produced this entry
+    pub reference_id: Uuid,
+    pub counterparty_id: Option<Uuid>,
+    pub price_per_share: Option<Decimal>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum ShareLedgerEntryKind {
+    #[serde(rename = "subscription")]
+    Subscription,
+    #[serde(rename = "transfer_in")]
+    TransferIn,
+    #[serde(rename = "transfer_out")]
+    TransferOut,
+}
+
+impl ShareLedgerEntryKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ShareLedgerEntryKind::Subscription => "subscription",
+            ShareLedgerEntryKind::TransferIn => "transfer_in",
+            ShareLedgerEntryKind::TransferOut => "transfer_out",
+        }
+    }
+}
+
+#[async_trait]
+pub trait SharesApi: Send + Sync {
+    /// Get a share offer by ID
+    async fn get_share_offer(&self, offer_id: Uuid) -> ApiResult<ShareOffer>;
+
+    /// Get all share offers with pagination
+    async fn get_share_offers(
+        &self,
+        pagination: PaginationQuery,
+    ) -> ApiResult<PaginatedResponse<ShareOffer>>;
+
+    /// Create a new share offer
+    async fn create_share_offer(&self, request: CreateShareOfferRequest) -> ApiResult<ShareOffer>;
+
+    /// Subscribe a member to shares from an open offer
+    async fn subscribe_shares(
+        &self,
+        request: SubscribeSharesRequest,
+    ) -> ApiResult<ShareSubscription>;
+
+    /// Pay towards a subscription from the member's wallet or, for admins, record
+    /// a payment received outside the wallets. Shares count towards borrowing,
+    /// dividends and transfers once their subscription is paid in full.
+    async fn pay_for_shares(
+        &self,
+        request: PaySharesRequest,
+        payer: &User,
+    ) -> ApiResult<ShareSubscription>;
+
+    /// Get a member's subscriptions across all offers, newest first
+    async fn get_member_subscriptions(&self, user_id: Uuid) -> ApiResult<Vec<ShareSubscription>>;
+
+    /// Get subscriptions made against an offer
+    async fn get_offer_subscriptions(
+        &self,
+        offer_id: Uuid,
+        pagination: PaginationQuery,
+    ) -> ApiResult<PaginatedResponse<ShareSubscription>>;
+
+    /// Close an offer to further subscriptions
+    async fn close_share_offer(&self, offer_id: Uuid) -> ApiResult<ShareOffer>;
+
+    /// Request a transfer of shares between members, pending admin approval.
+    /// `maker` may only offer someone else's shares if they are an admin.
+    async fn request_share_transfer(
+        &self,
+        request: TransferSharesRequest,
+        maker: &User,
+    ) -> ApiResult<ShareTransfer>;
+
+    /// Approve a pending transfer; `reviewer` must be an admin other than the
+    /// maker and either member
+    async fn approve_share_transfer(
+        &self,
+        transfer_id: Uuid,
+        reviewer: &User,
+    ) -> ApiResult<ShareTransfer>;
+
+    /// Reject a pending transfer; `reviewer` must be an admin other than the
+    /// maker and either member
+    async fn reject_share_transfer(
+        &self,
+        transfer_id: Uuid,
+        reviewer: &User,
+        reason: Option<String>,
+    ) -> ApiResult<ShareTransfer>;
+
+    /// Get transfers matching a filter, newest first
+    async fn get_share_transfers(
+        &self,
+        filter: ShareTransferFilter,
+        pagination: PaginationQuery,
+    ) -> ApiResult<PaginatedResponse<ShareTransfer>>;
+
+    /// Get a member's share ledger, oldest entry first
+    async fn get_member_share_ledger(&self, user_id: Uuid) -> ApiResult<Vec<ShareLedgerEntry>>;
+}
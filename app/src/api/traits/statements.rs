@@ -0,0 +1,174 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+use crate::api::{
+    errors::ApiResult,
+    traits::ledger::{AccountMovement, LedgerCurrency},
+};
+
+/// One movement on a statement, signed so that positive amounts grow the balance
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct StatementLine {
+    pub date: DateTime<Utc>,
+    pub description: String,
+    pub reference_id: Option<Uuid>,
+    pub amount: Decimal,
+    pub balance: Decimal,
+}
+
+/// Movements on one of the member's accounts (a wallet, share capital or a loan)
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct StatementSection {
+    pub title: String,
+    pub currency: LedgerCurrency,
+    pub opening_balance: Decimal,
+    pub lines: Vec<StatementLine>,
+    pub closing_balance: Decimal,
+}
+
+impl StatementSection {
+    /// Build a section for `[from, to)` from an account's full movement history.
+    /// `movements` must be in posting order.
+    pub fn from_movements(
+        title: String,
+        currency: LedgerCurrency,
+        movements: &[AccountMovement],
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Self {
+        let opening_balance = movements
+            .iter()
+            .take_while(|m| m.created_at < from)
+            .last()
+            .map(|m| m.balance_after)
+            .unwrap_or(Decimal::ZERO);
+        let lines: Vec<StatementLine> = movements
+            .iter()
+            .filter(|m| m.created_at >= from && m.created_at < to)
+            .map(|m| StatementLine {
+                date: m.created_at,
+                description: m.description.clone(),
+                reference_id: m.reference_id,
+                amount: m.amount,
+                balance: m.balance_after,
+            })
+            .collect();
+        let closing_balance = lines
+            .last()
+            .map(|line| line.balance)
+            .unwrap_or(opening_balance);
+
+        Self {
+            title,
+            currency,
+            opening_balance,
+            lines,
+            closing_balance,
+        }
+    }
+
+    pub fn total_in(&self) -> Decimal {
+        self.lines
+            .iter()
+            .map(|line| line.amount)
+            .filter(|amount| *amount > Decimal::ZERO)
+            .sum()
+    }
+
+    pub fn total_out(&self) -> Decimal {
+        -self
+            .lines
+            .iter()
+            .map(|line| line.amount)
+            .filter(|amount| *amount < Decimal::ZERO)
+            .sum::<Decimal>()
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct MemberStatement {
+    pub user_id: Uuid,
+    /// Start of the period, inclusive
+    pub from: DateTime<Utc>,
+    /// End of the period, exclusive
+    pub to: DateTime<Utc>,
+    pub sections: Vec<StatementSection>,
+    pub generated_at: DateTime<Utc>,
+}
+
+impl MemberStatement {
+    /// All sections as one CSV, with opening and closing rows around each account's movements
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("account,currency,date,description,reference,amount,balance\n");
+        for section in &self.sections {
+            let account = csv_field(&section.title);
+            let currency = section.currency.as_str();
+            csv.push_str(&format!(
+                "{},{},{},Opening balance,,,{}\n",
+                account,
+                currency,
+                self.from.format("%Y-%m-%d"),
+                section.opening_balance
+            ));
+            for line in &section.lines {
+                csv.push_str(&format!(
+                    "{},{},{},{},{},{},{}\n",
+                    account,
+                    currency,
+                    line.date.to_rfc3339(),
+                    csv_field(&line.description),
+                    line.reference_id
+                        .map(|id| id.to_string())
+                        .unwrap_or_default(),
+                    line.amount,
+                    line.balance
+                ));
+            }
+            csv.push_str(&format!(
+                "{},{},{},Closing balance,,,{}\n",
+                account,
+                currency,
+                self.last_day().format("%Y-%m-%d"),
+                section.closing_balance
+            ));
+        }
+        csv
+    }
+
+    /// Last day covered by the statement
+    pub fn last_day(&self) -> DateTime<Utc> {
+        self.to - chrono::Duration::days(1)
+    }
+
+    /// Suggested download file name, without extension
+    pub fn file_stem(&self) -> String {
+        format!(
+            "statement-{}-{}-{}",
+            self.user_id,
+            self.from.format("%Y%m%d"),
+            self.last_day().format("%Y%m%d")
+        )
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[async_trait]
+pub trait StatementsApi: Send + Sync {
+    /// Opening balance, movements and closing balance of every account a member holds,
+    /// for movements made in `[from, to)`
+    async fn get_member_statement(
+        &self,
+        user_id: Uuid,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> ApiResult<MemberStatement>;
+}
@@ -0,0 +1,196 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+use crate::api::{
+    errors::{ApiError, ApiResult},
+    traits::{fx::FxRate, wallets::TransactionStatus},
+    types::User,
+};
+
+/// Largest single M-Pesa transaction, in KES
+pub const MPESA_MAX_KES: i64 = 250_000;
+
+/// Swaps with a leg pending for longer than this are flagged for manual reconciliation
+pub const STUCK_AFTER_MINUTES: i64 = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum SwapDirection {
+    /// KES paid in over M-Pesa, sats credited to the member's wallet
+    #[serde(rename = "onramp")]
+    Onramp,
+    /// Sats taken from the member's wallet, KES paid out over M-Pesa
+    #[serde(rename = "offramp")]
+    Offramp,
+}
+
+impl SwapDirection {
+    pub fn label(&self) -> &'static str {
+        match self {
+            SwapDirection::Onramp => "Onramp",
+            SwapDirection::Offramp => "Offramp",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum SwapStatus {
+    #[serde(rename = "pending")]
+    Pending,
+    #[serde(rename = "completed")]
+    Completed,
+    #[serde(rename = "failed")]
+    Failed,
+}
+
+/// An M-Pesa ⇄ bitcoin swap and the state of both of its legs
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Swap {
+    pub id: Uuid,
+    pub direction: SwapDirection,
+    pub wallet_id: Uuid,
+    /// MSISDN in 2547XXXXXXXX form
+    pub phone: String,
+    /// Whole shillings; M-Pesa does not move cents
+    pub amount_kes: Decimal,
+    pub amount_sats: u64,
+    /// Rate the amounts were priced at
+    pub rate: FxRate,
+    /// Request the M-Pesa gateway tracks the current attempt under; empty while
+    /// a payout's fate is unknown
+    pub mpesa_request_id: String,
+    /// Idempotency key (B2C OriginatorConversationID) the latest payout was sent
+    /// under. Kept while M-Pesa may still have the payout, so a retry reuses it
+    pub payout_key: Option<String>,
+    /// M-Pesa receipt number, once the payment has gone through
+    pub mpesa_reference: Option<String>,
+    pub mpesa_status: TransactionStatus,
+    /// Wallet transaction crediting or holding the sats
+    pub wallet_transaction_id: Uuid,
+    pub lightning_status: TransactionStatus,
+    pub status: SwapStatus,
+    pub failure_reason: Option<String>,
+    /// Number of times the M-Pesa leg has been started
+    pub attempts: u32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+impl Swap {
+    /// Pending without progress for longer than [`STUCK_AFTER_MINUTES`]
+    pub fn is_stuck(&self, now: DateTime<Utc>) -> bool {
+        self.status == SwapStatus::Pending
+            && now - self.updated_at >= chrono::Duration::minutes(STUCK_AFTER_MINUTES)
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct OnrampRequest {
+    pub wallet_id: Uuid,
+    pub phone: String,
+    pub amount_kes: Decimal,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct OfframpRequest {
+    pub wallet_id: Uuid,
+    pub phone: String,
+    pub amount_sats: u64,
+}
+
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct SwapFilter {
+    pub direction: Option<SwapDirection>,
+    pub status: Option<SwapStatus>,
+    pub wallet_id: Option<Uuid>,
+}
+
+/// Where an M-Pesa request stands, as reported by the gateway
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MpesaState {
+    Pending,
+    Completed { receipt: String },
+    Failed { reason: String },
+}
+
+/// Normalise a Kenyan mobile number to the 2547XXXXXXXX / 2541XXXXXXXX form M-Pesa expects
+pub fn normalize_msisdn(phone: &str) -> ApiResult<String> {
+    let digits: String = phone
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '-')
+        .collect();
+    let digits = digits.strip_prefix('+').unwrap_or(&digits);
+    let local = if let Some(local) = digits.strip_prefix("254") {
+        local
+    } else if let Some(local) = digits.strip_prefix('0') {
+        local
+    } else {
+        digits
+    };
+
+    if local.len() == 9
+        && local.chars().all(|c| c.is_ascii_digit())
+        && (local.starts_with('7') || local.starts_with('1'))
+    {
+        Ok(format!("254{}", local))
+    } else {
+        Err(ApiError::Validation {
+            message: format!("'{}' is not a Kenyan mobile number", phone.trim()),
+        })
+    }
+}
+
+/// The M-Pesa side of a swap: STK pushes for collections, B2C for payouts
+#[async_trait]
+pub trait MpesaGateway: Send + Sync {
+    /// Prompt the customer's phone to pay `amount_kes`, returning the request ID
+    async fn stk_push(
+        &self,
+        phone: &str,
+        amount_kes: Decimal,
+        reference: &str,
+    ) -> ApiResult<String>;
+
+    /// Send `amount_kes` to the customer, returning the request ID. Payouts sent
+    /// again under the same `payout_key` are not paid twice. A network,
+    /// serialization or unknown error leaves it open whether M-Pesa took the payout.
+    async fn b2c_payout(
+        &self,
+        phone: &str,
+        amount_kes: Decimal,
+        reference: &str,
+        payout_key: &str,
+    ) -> ApiResult<String>;
+
+    async fn request_state(&self, request_id: &str) -> ApiResult<MpesaState>;
+
+    /// The request a payout sent under `payout_key` became, if M-Pesa has it
+    async fn find_payout(&self, payout_key: &str) -> ApiResult<Option<String>>;
+}
+
+#[async_trait]
+pub trait SwapsApi: Send + Sync {
+    /// Start an onramp by prompting the member to pay over M-Pesa
+    async fn create_onramp(&self, request: OnrampRequest) -> ApiResult<Swap>;
+
+    /// Start an offramp, holding the sats until the M-Pesa payout completes.
+    /// `requester` is held to their role's withdrawal limits.
+    async fn create_offramp(&self, request: OfframpRequest, requester: &User) -> ApiResult<Swap>;
+
+    /// Swaps matching the filter, newest first
+    async fn list_swaps(&self, filter: SwapFilter) -> ApiResult<Vec<Swap>>;
+
+    async fn get_swap(&self, swap_id: Uuid) -> ApiResult<Swap>;
+
+    /// Ask the gateway where a pending swap stands and settle it if it has finished
+    async fn reconcile_swap(&self, swap_id: Uuid) -> ApiResult<Swap>;
+
+    /// Start the M-Pesa leg of a failed swap again, repriced at the current rate.
+    /// Payouts M-Pesa never reported on are resent under their original key.
+    async fn retry_swap(&self, swap_id: Uuid, requester: &User) -> ApiResult<Swap>;
+
+    /// Reconcile every pending swap, returning how many finished
+    async fn sync_pending_swaps(&self) -> ApiResult<usize>;
+}
@@ -56,6 +56,15 @@ pub enum TransactionType {
     Transfer,
     #[serde(rename = "payment")]
     Payment,
+    /// Paid from a member's wallet into their chama's contributions pool
+    #[serde(rename = "contribution")]
+    Contribution,
+    /// Paid from a member's wallet towards their own loan
+    #[serde(rename = "loan_repayment")]
+    LoanRepayment,
+    /// Paid from a member's wallet towards shares they subscribed for
+    #[serde(rename = "share_payment")]
+    SharePayment,
 }
 
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
@@ -70,6 +79,32 @@ pub enum TransactionStatus {
     Cancelled,
 }
 
+/// Narrows a search across every wallet's transactions; unset fields match anything
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct TransactionFilter {
+    /// Earliest creation time, inclusive
+    pub from: Option<chrono::DateTime<chrono::Utc>>,
+    /// Latest creation time, exclusive
+    pub to: Option<chrono::DateTime<chrono::Utc>>,
+    pub transaction_type: Option<TransactionType>,
+    pub status: Option<TransactionStatus>,
+}
+
+impl TransactionFilter {
+    pub fn matches(&self, transaction: &WalletTransaction) -> bool {
+        self.from.is_none_or(|from| transaction.created_at >= from)
+            && self.to.is_none_or(|to| transaction.created_at < to)
+            && self
+                .transaction_type
+                .as_ref()
+                .is_none_or(|kind| transaction.transaction_type == *kind)
+            && self
+                .status
+                .as_ref()
+                .is_none_or(|status| transaction.status == *status)
+    }
+}
+
 #[async_trait]
 pub trait WalletsApi: Send + Sync {
     /// Get a wallet by ID
@@ -97,6 +132,13 @@ pub trait WalletsApi: Send + Sync {
         pagination: PaginationQuery,
     ) -> ApiResult<PaginatedResponse<WalletTransaction>>;
 
+    /// Transactions across all wallets matching a filter, newest first
+    async fn get_transactions(
+        &self,
+        filter: TransactionFilter,
+        pagination: PaginationQuery,
+    ) -> ApiResult<PaginatedResponse<WalletTransaction>>;
+
     /// Get wallet balance
     async fn get_wallet_balance(&self, wallet_id: Uuid) -> ApiResult<u64>;
 }
@@ -0,0 +1,270 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::api::{
+    errors::ApiResult,
+    traits::wallets::TransactionType,
+    types::{common::Role, User},
+};
+
+/// How long an approved withdrawal may be retried before the approval lapses
+pub const APPROVAL_VALID_HOURS: i64 = 24;
+
+/// Limits on what one member may take out of their wallets; amounts are in sats
+/// and `None` means no limit
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct WithdrawalLimits {
+    /// Most a member may withdraw in any 24 hours
+    pub daily_sats: Option<u64>,
+    /// Most a member may withdraw in any 7 days
+    pub weekly_sats: Option<u64>,
+    /// Single withdrawals above this wait for an admin's approval
+    pub approval_above_sats: Option<u64>,
+    /// Hours after a member's first wallet is opened before they may withdraw
+    pub cooldown_hours: u32,
+}
+
+impl WithdrawalLimits {
+    /// The stricter of each limit in `self` and `other`
+    pub fn tightest(&self, other: &WithdrawalLimits) -> WithdrawalLimits {
+        let min = |a: Option<u64>, b: Option<u64>| match (a, b) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        WithdrawalLimits {
+            daily_sats: min(self.daily_sats, other.daily_sats),
+            weekly_sats: min(self.weekly_sats, other.weekly_sats),
+            approval_above_sats: min(self.approval_above_sats, other.approval_above_sats),
+            cooldown_hours: self.cooldown_hours.max(other.cooldown_hours),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct RoleLimits {
+    pub role: Role,
+    pub limits: WithdrawalLimits,
+}
+
+/// Limits for the members of one chama
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct GroupLimits {
+    pub group_id: Uuid,
+    /// Applied to each member on top of their role's limits
+    pub member_limits: WithdrawalLimits,
+    /// Most the chama's members may withdraw between them in any 24 hours
+    pub chama_daily_sats: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct WithdrawalPolicy {
+    pub roles: Vec<RoleLimits>,
+    pub groups: Vec<GroupLimits>,
+}
+
+impl WithdrawalPolicy {
+    /// Limits for a role; roles without an entry are unlimited
+    pub fn role_limits(&self, role: Role) -> WithdrawalLimits {
+        self.roles
+            .iter()
+            .find(|entry| entry.role == role)
+            .map(|entry| entry.limits.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn group_limits(&self, group_id: Uuid) -> Option<&GroupLimits> {
+        self.groups.iter().find(|entry| entry.group_id == group_id)
+    }
+}
+
+/// Everything the policy looks at when deciding on one withdrawal
+#[derive(Debug, Clone)]
+pub struct WithdrawalContext {
+    pub amount_sats: u64,
+    pub role: Role,
+    pub groups: Vec<Uuid>,
+    /// When the member's first wallet was opened
+    pub member_since: DateTime<Utc>,
+    /// Sats the member has withdrawn in the last 24 hours and 7 days
+    pub member_day_sats: u64,
+    pub member_week_sats: u64,
+    /// Sats each of the member's chamas has withdrawn in the last 24 hours
+    pub group_day_sats: HashMap<Uuid, u64>,
+    /// An admin has approved this withdrawal
+    pub approved: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum DecisionOutcome {
+    #[serde(rename = "allowed")]
+    Allowed,
+    #[serde(rename = "denied")]
+    Denied,
+    #[serde(rename = "needs_approval")]
+    NeedsApproval,
+}
+
+/// Decide on a withdrawal, giving the reasons for the outcome
+pub fn evaluate_withdrawal(
+    policy: &WithdrawalPolicy,
+    context: &WithdrawalContext,
+    now: DateTime<Utc>,
+) -> (DecisionOutcome, Vec<String>) {
+    let mut limits = policy.role_limits(context.role);
+    for group_id in &context.groups {
+        if let Some(group) = policy.group_limits(*group_id) {
+            limits = limits.tightest(&group.member_limits);
+        }
+    }
+
+    let mut denials = Vec::new();
+    let opens_at = context.member_since + chrono::Duration::hours(limits.cooldown_hours.into());
+    if now < opens_at {
+        denials.push(format!(
+            "New accounts cannot withdraw until {}",
+            opens_at.format("%Y-%m-%d %H:%M UTC")
+        ));
+    }
+    let mut window = |label: &str, used: u64, limit: Option<u64>| {
+        if let Some(limit) = limit {
+            if used.saturating_add(context.amount_sats) > limit {
+                denials.push(format!(
+                    "{} limit of {} sats would be exceeded ({} sats already withdrawn)",
+                    label, limit, used
+                ));
+            }
+        }
+    };
+    window("Daily", context.member_day_sats, limits.daily_sats);
+    window("Weekly", context.member_week_sats, limits.weekly_sats);
+    for group_id in &context.groups {
+        let chama_limit = policy
+            .group_limits(*group_id)
+            .and_then(|group| group.chama_daily_sats);
+        let used = context.group_day_sats.get(group_id).copied().unwrap_or(0);
+        window(&format!("Chama {} daily", group_id), used, chama_limit);
+    }
+    if !denials.is_empty() {
+        return (DecisionOutcome::Denied, denials);
+    }
+
+    match limits.approval_above_sats {
+        Some(threshold) if context.amount_sats > threshold && context.approved => (
+            DecisionOutcome::Allowed,
+            vec![format!(
+                "Above the {} sat approval threshold; approved by an admin",
+                threshold
+            )],
+        ),
+        Some(threshold) if context.amount_sats > threshold => (
+            DecisionOutcome::NeedsApproval,
+            vec![format!(
+                "Withdrawals above {} sats need an admin's approval",
+                threshold
+            )],
+        ),
+        _ => (DecisionOutcome::Allowed, vec!["Within limits".to_string()]),
+    }
+}
+
+/// A logged policy decision on one attempted withdrawal or payment
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct WithdrawalDecision {
+    pub id: Uuid,
+    /// Owner of the wallet, whose limits were applied
+    pub user_id: Uuid,
+    /// Signed-in user who made the withdrawal; an admin when not the owner
+    pub acted_by: Uuid,
+    pub wallet_id: Uuid,
+    pub amount_sats: u64,
+    pub transaction_type: TransactionType,
+    pub outcome: DecisionOutcome,
+    pub reasons: Vec<String>,
+    /// Approval requested or used by this decision
+    pub approval_id: Option<Uuid>,
+    pub decided_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum ApprovalStatus {
+    #[serde(rename = "pending")]
+    Pending,
+    #[serde(rename = "approved")]
+    Approved,
+    #[serde(rename = "rejected")]
+    Rejected,
+    /// Approved and since spent by the withdrawal it was for
+    #[serde(rename = "used")]
+    Used,
+}
+
+/// A large withdrawal waiting on, or decided by, an admin. Once approved, the same
+/// withdrawal from the same wallet goes through when it is tried again.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct WithdrawalApproval {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    /// Signed-in user whose withdrawal needed the approval
+    pub requested_by: Uuid,
+    pub wallet_id: Uuid,
+    pub amount_sats: u64,
+    pub transaction_type: TransactionType,
+    pub status: ApprovalStatus,
+    pub requested_at: DateTime<Utc>,
+    pub reviewed_by: Option<Uuid>,
+    pub reviewed_at: Option<DateTime<Utc>>,
+    pub note: Option<String>,
+}
+
+impl WithdrawalApproval {
+    /// Approved recently enough to still be used
+    pub fn is_usable(&self, now: DateTime<Utc>) -> bool {
+        self.status == ApprovalStatus::Approved
+            && self
+                .reviewed_at
+                .is_some_and(|at| now - at < chrono::Duration::hours(APPROVAL_VALID_HOURS))
+    }
+}
+
+#[async_trait]
+pub trait WithdrawalPolicyApi: Send + Sync {
+    async fn get_policy(&self) -> ApiResult<WithdrawalPolicy>;
+
+    /// Replace the limits for a role
+    async fn set_role_limits(
+        &self,
+        role: Role,
+        limits: WithdrawalLimits,
+    ) -> ApiResult<WithdrawalPolicy>;
+
+    /// Replace the limits for a chama
+    async fn set_group_limits(&self, limits: GroupLimits) -> ApiResult<WithdrawalPolicy>;
+
+    async fn remove_group_limits(&self, group_id: Uuid) -> ApiResult<WithdrawalPolicy>;
+
+    /// Logged decisions, newest first, optionally for one member
+    async fn get_decisions(
+        &self,
+        user_id: Option<Uuid>,
+        limit: usize,
+    ) -> ApiResult<Vec<WithdrawalDecision>>;
+
+    /// Approval requests, newest first, optionally in one status
+    async fn get_approvals(
+        &self,
+        status: Option<ApprovalStatus>,
+    ) -> ApiResult<Vec<WithdrawalApproval>>;
+
+    /// Approve or reject a pending request; `reviewer` must be an admin other
+    /// than the wallet's owner and whoever requested the withdrawal
+    async fn review_approval(
+        &self,
+        approval_id: Uuid,
+        reviewer: &User,
+        approve: bool,
+        note: Option<String>,
+    ) -> ApiResult<WithdrawalApproval>;
+}
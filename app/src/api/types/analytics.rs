@@ -0,0 +1,674 @@
+use chrono::{Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+
+use crate::api::errors::ApiError;
+
+/// Most metrics one query may chart together
+pub const MAX_METRICS: usize = 6;
+
+/// Longest range a query may cover, in days
+pub const MAX_RANGE_DAYS: i64 = 730;
+
+/// Most buckets a query may return per series
+pub const MAX_BUCKETS: i64 = 400;
+
+/// Whether a metric counts things or adds up money
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetricUnit {
+    Count,
+    Money,
+}
+
+/// The metrics the custom analytics endpoint can report
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnalyticsMetric {
+    NewMembers,
+    ActiveMembers,
+    TransactionCount,
+    TransactionVolume,
+    FailedTransactions,
+    SwapVolume,
+    ChamaDeposits,
+    ShareTransfers,
+}
+
+impl AnalyticsMetric {
+    pub const ALL: [AnalyticsMetric; 8] = [
+        AnalyticsMetric::NewMembers,
+        AnalyticsMetric::ActiveMembers,
+        AnalyticsMetric::TransactionCount,
+        AnalyticsMetric::TransactionVolume,
+        AnalyticsMetric::FailedTransactions,
+        AnalyticsMetric::SwapVolume,
+        AnalyticsMetric::ChamaDeposits,
+        AnalyticsMetric::ShareTransfers,
+    ];
+
+    /// Name on the wire
+    pub fn key(self) -> &'static str {
+        match self {
+            AnalyticsMetric::NewMembers => "new_members",
+            AnalyticsMetric::ActiveMembers => "active_members",
+            AnalyticsMetric::TransactionCount => "transaction_count",
+            AnalyticsMetric::TransactionVolume => "transaction_volume",
+            AnalyticsMetric::FailedTransactions => "failed_transactions",
+            AnalyticsMetric::SwapVolume => "swap_volume",
+            AnalyticsMetric::ChamaDeposits => "chama_deposits",
+            AnalyticsMetric::ShareTransfers => "share_transfers",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        // The backend may answer in camelCase
+        let key = key.replace('_', "").to_lowercase();
+        Self::ALL
+            .into_iter()
+            .find(|metric| metric.key().replace('_', "") == key)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            AnalyticsMetric::NewMembers => "New members",
+            AnalyticsMetric::ActiveMembers => "Active members",
+            AnalyticsMetric::TransactionCount => "Transactions",
+            AnalyticsMetric::TransactionVolume => "Transaction volume",
+            AnalyticsMetric::FailedTransactions => "Failed transactions",
+            AnalyticsMetric::SwapVolume => "Swap volume",
+            AnalyticsMetric::ChamaDeposits => "Chama deposits",
+            AnalyticsMetric::ShareTransfers => "Share transfers",
+        }
+    }
+
+    pub fn unit(self) -> MetricUnit {
+        match self {
+            AnalyticsMetric::TransactionVolume
+            | AnalyticsMetric::SwapVolume
+            | AnalyticsMetric::ChamaDeposits => MetricUnit::Money,
+            _ => MetricUnit::Count,
+        }
+    }
+
+    /// Dimensions this metric can be broken down by
+    pub fn dimensions(self) -> &'static [AnalyticsDimension] {
+        match self {
+            AnalyticsMetric::NewMembers | AnalyticsMetric::ActiveMembers => {
+                &[AnalyticsDimension::Region]
+            }
+            AnalyticsMetric::TransactionCount | AnalyticsMetric::TransactionVolume => {
+                &[AnalyticsDimension::Currency, AnalyticsDimension::Operation]
+            }
+            AnalyticsMetric::FailedTransactions => &[AnalyticsDimension::Operation],
+            AnalyticsMetric::SwapVolume => &[AnalyticsDimension::PaymentMethod],
+            AnalyticsMetric::ChamaDeposits | AnalyticsMetric::ShareTransfers => &[],
+        }
+    }
+}
+
+/// What a series can be split by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnalyticsDimension {
+    Region,
+    Currency,
+    Operation,
+    PaymentMethod,
+}
+
+impl AnalyticsDimension {
+    pub const ALL: [AnalyticsDimension; 4] = [
+        AnalyticsDimension::Region,
+        AnalyticsDimension::Currency,
+        AnalyticsDimension::Operation,
+        AnalyticsDimension::PaymentMethod,
+    ];
+
+    pub fn key(self) -> &'static str {
+        match self {
+            AnalyticsDimension::Region => "region",
+            AnalyticsDimension::Currency => "currency",
+            AnalyticsDimension::Operation => "operation",
+            AnalyticsDimension::PaymentMethod => "payment_method",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|dimension| dimension.key() == key)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            AnalyticsDimension::Region => "Region",
+            AnalyticsDimension::Currency => "Currency",
+            AnalyticsDimension::Operation => "Operation",
+            AnalyticsDimension::PaymentMethod => "Payment method",
+        }
+    }
+}
+
+/// Width of the buckets a series is reported in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Granularity {
+    Hour,
+    #[default]
+    Day,
+    Week,
+    Month,
+}
+
+impl Granularity {
+    pub const ALL: [Granularity; 4] = [
+        Granularity::Hour,
+        Granularity::Day,
+        Granularity::Week,
+        Granularity::Month,
+    ];
+
+    pub fn key(self) -> &'static str {
+        match self {
+            Granularity::Hour => "hour",
+            Granularity::Day => "day",
+            Granularity::Week => "week",
+            Granularity::Month => "month",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|granularity| granularity.key() == key)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Granularity::Hour => "Hourly",
+            Granularity::Day => "Daily",
+            Granularity::Week => "Weekly",
+            Granularity::Month => "Monthly",
+        }
+    }
+
+    /// Buckets needed to cover `days` whole days
+    fn buckets(self, days: i64) -> i64 {
+        match self {
+            Granularity::Hour => days * 24,
+            Granularity::Day => days,
+            Granularity::Week => (days + 6) / 7,
+            Granularity::Month => (days + 29) / 30,
+        }
+    }
+}
+
+/// The period a report covers. Relative ranges move with the calendar, so a
+/// saved "last 30 days" report stays current.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportRange {
+    /// The given number of days, ending today
+    LastDays(u32),
+    /// Both dates inclusive
+    Fixed { start: NaiveDate, end: NaiveDate },
+}
+
+impl Default for ReportRange {
+    fn default() -> Self {
+        ReportRange::LastDays(30)
+    }
+}
+
+impl ReportRange {
+    /// The inclusive dates this range covers as of `today`
+    pub fn resolve(self, today: NaiveDate) -> (NaiveDate, NaiveDate) {
+        match self {
+            ReportRange::LastDays(days) => {
+                (today - Duration::days(i64::from(days.max(1)) - 1), today)
+            }
+            ReportRange::Fixed { start, end } => (start, end),
+        }
+    }
+}
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum AnalyticsQueryError {
+    #[error("Pick at least one metric")]
+    NoMetrics,
+    #[error("Pick at most {MAX_METRICS} metrics")]
+    TooManyMetrics,
+    #[error("The start date must not be after the end date")]
+    RangeReversed,
+    #[error("The range must not end in the future")]
+    RangeInFuture,
+    #[error("The range may cover at most {MAX_RANGE_DAYS} days")]
+    RangeTooLong,
+    #[error("{granularity} buckets over this range would give more than {MAX_BUCKETS} points; pick a coarser granularity")]
+    TooManyBuckets { granularity: &'static str },
+    #[error("{metric} can't be broken down by {dimension}")]
+    UnsupportedDimension {
+        metric: &'static str,
+        dimension: &'static str,
+    },
+}
+
+impl From<AnalyticsQueryError> for ApiError {
+    fn from(error: AnalyticsQueryError) -> Self {
+        ApiError::Validation {
+            message: error.to_string(),
+        }
+    }
+}
+
+/// What a report asks for; this is what gets saved
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct ReportSpec {
+    #[serde(default)]
+    pub metrics: Vec<AnalyticsMetric>,
+    pub dimension: Option<AnalyticsDimension>,
+    pub granularity: Granularity,
+    pub range: ReportRange,
+}
+
+impl ReportSpec {
+    /// Validate the spec and pin its range to dates as of `today`
+    pub fn query(&self, today: NaiveDate) -> Result<AnalyticsQuery, AnalyticsQueryError> {
+        let mut metrics = Vec::with_capacity(self.metrics.len());
+        for metric in &self.metrics {
+            if !metrics.contains(metric) {
+                metrics.push(*metric);
+            }
+        }
+        if metrics.is_empty() {
+            return Err(AnalyticsQueryError::NoMetrics);
+        }
+        if metrics.len() > MAX_METRICS {
+            return Err(AnalyticsQueryError::TooManyMetrics);
+        }
+        if let Some(dimension) = self.dimension {
+            if let Some(metric) = metrics
+                .iter()
+                .find(|metric| !metric.dimensions().contains(&dimension))
+            {
+                return Err(AnalyticsQueryError::UnsupportedDimension {
+                    metric: metric.label(),
+                    dimension: dimension.label(),
+                });
+            }
+        }
+
+        let (start, end) = self.range.resolve(today);
+        if start > end {
+            return Err(AnalyticsQueryError::RangeReversed);
+        }
+        if end > today {
+            return Err(AnalyticsQueryError::RangeInFuture);
+        }
+        let days = (end - start).num_days() + 1;
+        if days > MAX_RANGE_DAYS {
+            return Err(AnalyticsQueryError::RangeTooLong);
+        }
+        if self.granularity.buckets(days) > MAX_BUCKETS {
+            return Err(AnalyticsQueryError::TooManyBuckets {
+                granularity: self.granularity.label(),
+            });
+        }
+
+        Ok(AnalyticsQuery {
+            start,
+            end,
+            metrics,
+            dimension: self.dimension,
+            granularity: self.granularity,
+        })
+    }
+}
+
+/// A validated query with concrete dates; built by [`ReportSpec::query`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnalyticsQuery {
+    start: NaiveDate,
+    end: NaiveDate,
+    metrics: Vec<AnalyticsMetric>,
+    dimension: Option<AnalyticsDimension>,
+    granularity: Granularity,
+}
+
+impl AnalyticsQuery {
+    pub fn start(&self) -> NaiveDate {
+        self.start
+    }
+
+    pub fn end(&self) -> NaiveDate {
+        self.end
+    }
+
+    pub fn metrics(&self) -> &[AnalyticsMetric] {
+        &self.metrics
+    }
+
+    pub fn dimension(&self) -> Option<AnalyticsDimension> {
+        self.dimension
+    }
+
+    pub fn granularity(&self) -> Granularity {
+        self.granularity
+    }
+
+    /// Query string for the custom analytics endpoint
+    pub fn params(&self) -> Vec<(&'static str, String)> {
+        let mut params = vec![
+            ("start_date", self.start.format("%Y-%m-%d").to_string()),
+            ("end_date", self.end.format("%Y-%m-%d").to_string()),
+            (
+                "metrics",
+                self.metrics
+                    .iter()
+                    .map(|metric| metric.key())
+                    .collect::<Vec<_>>()
+                    .join(","),
+            ),
+            ("granularity", self.granularity.key().to_string()),
+        ];
+        if let Some(dimension) = self.dimension {
+            params.push(("dimension", dimension.key().to_string()));
+        }
+        params
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnalyticsPoint {
+    /// Bucket start as the backend reported it, e.g. `2026-10-01`
+    pub period: String,
+    pub value: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnalyticsSeries {
+    pub metric: AnalyticsMetric,
+    /// Dimension value when the query was broken down, e.g. `KES`
+    pub group: Option<String>,
+    /// Oldest first
+    pub points: Vec<AnalyticsPoint>,
+}
+
+impl AnalyticsSeries {
+    pub fn label(&self) -> String {
+        match &self.group {
+            Some(group) => format!("{} · {}", self.metric.label(), group),
+            None => self.metric.label().to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct AnalyticsResult {
+    pub series: Vec<AnalyticsSeries>,
+}
+
+impl AnalyticsResult {
+    /// Read the endpoint's untyped answer. Both shapes it is known to use are
+    /// accepted: `{"series": [{"metric", "group", "points": [...]}]}` and a map
+    /// from metric to points, or to groups of points. Series for metrics the
+    /// query didn't ask for are dropped.
+    pub fn from_value(query: &AnalyticsQuery, value: &Value) -> Result<Self, String> {
+        let mut series = Vec::new();
+        match value.get("series").and_then(Value::as_array) {
+            Some(entries) => {
+                for entry in entries {
+                    let Some(metric) = entry
+                        .get("metric")
+                        .and_then(Value::as_str)
+                        .and_then(AnalyticsMetric::from_key)
+                    else {
+                        continue;
+                    };
+                    let group = ["group", "dimensionValue", "dimension_value"]
+                        .iter()
+                        .find_map(|key| entry.get(*key))
+                        .and_then(value_as_label);
+                    let points = ["points", "data"]
+                        .iter()
+                        .find_map(|key| entry.get(*key))
+                        .ok_or_else(|| format!("Series for {} has no points", metric.key()))?;
+                    series.push(AnalyticsSeries {
+                        metric,
+                        group,
+                        points: parse_points(points)?,
+                    });
+                }
+            }
+            None => {
+                let entries = value
+                    .as_object()
+                    .ok_or_else(|| "Expected an object of analytics series".to_string())?;
+                for (key, entry) in entries {
+                    let Some(metric) = AnalyticsMetric::from_key(key) else {
+                        continue;
+                    };
+                    match entry {
+                        Value::Object(groups) => {
+                            for (group, points) in groups {
+                                series.push(AnalyticsSeries {
+                                    metric,
+                                    group: Some(group.clone()),
+                                    points: parse_points(points)?,
+                                });
+                            }
+                        }
+                        points => series.push(AnalyticsSeries {
+                            metric,
+                            group: None,
+                            points: parse_points(points)?,
+                        }),
+                    }
+                }
+            }
+        }
+
+        series.retain(|series| query.metrics.contains(&series.metric));
+        // Catalogue order, then group name, so the same query always lays out the same
+        series.sort_by(|a, b| {
+            let position = |metric| query.metrics.iter().position(|m| *m == metric);
+            position(a.metric)
+                .cmp(&position(b.metric))
+                .then_with(|| a.group.cmp(&b.group))
+        });
+        Ok(Self { series })
+    }
+
+    /// Every bucket any series reported, oldest first, for laying out a table
+    pub fn periods(&self) -> Vec<String> {
+        let mut periods: Vec<String> = self
+            .series
+            .iter()
+            .flat_map(|series| series.points.iter().map(|point| point.period.clone()))
+            .collect();
+        periods.sort();
+        periods.dedup();
+        periods
+    }
+}
+
+fn value_as_label(value: &Value) -> Option<String> {
+    match value {
+        Value::String(label) => Some(label.clone()),
+        Value::Null => None,
+        other => Some(other.to_string()),
+    }
+}
+
+fn value_as_number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(number) => number.as_f64(),
+        // Money amounts arrive as decimal strings
+        Value::String(text) => text.trim().parse().ok(),
+        Value::Object(money) => money.get("amount").and_then(value_as_number),
+        _ => None,
+    }
+}
+
+fn parse_points(points: &Value) -> Result<Vec<AnalyticsPoint>, String> {
+    let points = points
+        .as_array()
+        .ok_or_else(|| "Expected a list of points".to_string())?;
+    let mut parsed = points
+        .iter()
+        .map(|point| {
+            let period = ["period", "date", "timestamp"]
+                .iter()
+                .find_map(|key| point.get(*key))
+                .and_then(value_as_label)
+                .ok_or_else(|| format!("Point without a date: {}", point))?;
+            let value = point
+                .get("value")
+                .and_then(value_as_number)
+                .ok_or_else(|| format!("Point without a numeric value: {}", point))?;
+            Ok(AnalyticsPoint { period, value })
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    parsed.sort_by(|a, b| a.period.cmp(&b.period));
+    Ok(parsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, 18).unwrap()
+    }
+
+    fn spec(metrics: &[AnalyticsMetric]) -> ReportSpec {
+        ReportSpec {
+            metrics: metrics.to_vec(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_specs_are_validated_before_querying() {
+        assert_eq!(
+            spec(&[]).query(today()),
+            Err(AnalyticsQueryError::NoMetrics)
+        );
+
+        let query = spec(&[
+            AnalyticsMetric::NewMembers,
+            AnalyticsMetric::NewMembers,
+            AnalyticsMetric::SwapVolume,
+        ])
+        .query(today())
+        .unwrap();
+        assert_eq!(
+            query.metrics(),
+            &[AnalyticsMetric::NewMembers, AnalyticsMetric::SwapVolume]
+        );
+        assert_eq!(query.start(), NaiveDate::from_ymd_opt(2026, 9, 19).unwrap());
+        assert_eq!(query.end(), today());
+        assert!(query
+            .params()
+            .contains(&("metrics", "new_members,swap_volume".to_string())));
+
+        let mut by_region = spec(&[AnalyticsMetric::NewMembers, AnalyticsMetric::SwapVolume]);
+        by_region.dimension = Some(AnalyticsDimension::Region);
+        assert!(matches!(
+            by_region.query(today()),
+            Err(AnalyticsQueryError::UnsupportedDimension {
+                metric: "Swap volume",
+                ..
+            })
+        ));
+
+        let mut hourly = spec(&[AnalyticsMetric::TransactionCount]);
+        hourly.granularity = Granularity::Hour;
+        assert!(matches!(
+            hourly.query(today()),
+            Err(AnalyticsQueryError::TooManyBuckets { .. })
+        ));
+        hourly.range = ReportRange::LastDays(7);
+        assert!(hourly.query(today()).is_ok());
+
+        let mut fixed = spec(&[AnalyticsMetric::TransactionCount]);
+        fixed.range = ReportRange::Fixed {
+            start: today(),
+            end: today() - Duration::days(1),
+        };
+        assert_eq!(
+            fixed.query(today()),
+            Err(AnalyticsQueryError::RangeReversed)
+        );
+        fixed.range = ReportRange::Fixed {
+            start: today(),
+            end: today() + Duration::days(1),
+        };
+        assert_eq!(
+            fixed.query(today()),
+            Err(AnalyticsQueryError::RangeInFuture)
+        );
+        fixed.range = ReportRange::LastDays(1_000);
+        assert_eq!(fixed.query(today()), Err(AnalyticsQueryError::RangeTooLong));
+    }
+
+    #[test]
+    fn test_results_parse_from_either_shape() {
+        let mut by_currency = spec(&[
+            AnalyticsMetric::TransactionVolume,
+            AnalyticsMetric::TransactionCount,
+        ]);
+        by_currency.dimension = Some(AnalyticsDimension::Currency);
+        let query = by_currency.query(today()).unwrap();
+
+        let listed = AnalyticsResult::from_value(
+            &query,
+            &json!({ "series": [
+                { "metric": "transactionCount", "points": [
+                    { "date": "2026-10-02", "value": 4 },
+                    { "date": "2026-10-01", "value": 3 }
+                ]},
+                { "metric": "transaction_volume", "group": "KES", "data": [
+                    { "period": "2026-10-01", "value": "1250.50" }
+                ]},
+                { "metric": "share_transfers", "points": [] }
+            ]}),
+        )
+        .unwrap();
+        assert_eq!(listed.series.len(), 2);
+        assert_eq!(listed.series[0].label(), "Transaction volume · KES");
+        assert_eq!(listed.series[0].points[0].value, 1250.5);
+        assert_eq!(listed.series[1].points[0].period, "2026-10-01");
+        assert_eq!(listed.periods(), vec!["2026-10-01", "2026-10-02"]);
+
+        let mapped = AnalyticsResult::from_value(
+            &query,
+            &json!({
+                "transaction_volume": {
+                    "SATS": [{ "date": "2026-10-01", "value": { "amount": "900", "currency": "SATS" } }],
+                    "KES": [{ "date": "2026-10-01", "value": 10 }]
+                },
+                "transaction_count": [{ "date": "2026-10-01", "value": 2 }]
+            }),
+        )
+        .unwrap();
+        let labels: Vec<String> = mapped.series.iter().map(AnalyticsSeries::label).collect();
+        assert_eq!(
+            labels,
+            vec![
+                "Transaction volume · KES",
+                "Transaction volume · SATS",
+                "Transactions"
+            ]
+        );
+        assert_eq!(mapped.series[1].points[0].value, 900.0);
+
+        assert!(AnalyticsResult::from_value(
+            &query,
+            &json!({ "transaction_count": [{ "date": "2026-10-01" }] })
+        )
+        .is_err());
+    }
+}
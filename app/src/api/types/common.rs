@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum Role {
     #[serde(rename = "member")]
     Member = 0,
@@ -19,6 +19,19 @@ impl Role {
             Role::SuperAdmin => "superadmin".to_string(), // Note: no underscore for frontend
         }
     }
+
+    /// Whether this role carries administrative privileges
+    pub fn is_admin(&self) -> bool {
+        matches!(self, Role::Admin | Role::SuperAdmin)
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Role::Member => "Member",
+            Role::Admin => "Admin",
+            Role::SuperAdmin => "Super admin",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -54,6 +67,32 @@ pub struct PaginatedResponse<T> {
     pub total_pages: u32,
 }
 
+impl<T: Clone> PaginatedResponse<T> {
+    /// Slice an in-memory result set into the requested page
+    pub fn paginate(items: Vec<T>, pagination: &PaginationQuery) -> Self {
+        let total = items.len() as u64;
+        let page = pagination.page.unwrap_or(1).max(1);
+        let limit = pagination.limit.unwrap_or(20).max(1);
+        // Very large pages saturate past the end instead of overflowing
+        let start = (page as usize - 1).saturating_mul(limit as usize);
+        let end = start.saturating_add(limit as usize).min(items.len());
+
+        let data = if start < items.len() {
+            items[start..end].to_vec()
+        } else {
+            Vec::new()
+        };
+
+        Self {
+            data,
+            total,
+            page,
+            limit,
+            total_pages: (total as f64 / limit as f64).ceil() as u32,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PaginationQuery {
     pub page: Option<u32>,
@@ -74,3 +113,29 @@ pub struct SearchQuery {
     pub query: Option<String>,
     pub filters: Option<std::collections::HashMap<String, String>>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paginate_clamps_out_of_range_pages() {
+        let page = |page, limit| PaginationQuery {
+            page: Some(page),
+            limit: Some(limit),
+        };
+        let items: Vec<u32> = (1..=5).collect();
+
+        let first = PaginatedResponse::paginate(items.clone(), &page(0, 2));
+        assert_eq!(first.page, 1);
+        assert_eq!(first.data, vec![1, 2]);
+        assert_eq!(first.total_pages, 3);
+
+        let last = PaginatedResponse::paginate(items.clone(), &page(3, 2));
+        assert_eq!(last.data, vec![5]);
+
+        let beyond = PaginatedResponse::paginate(items, &page(u32::MAX, u32::MAX));
+        assert!(beyond.data.is_empty());
+        assert_eq!(beyond.total, 5);
+    }
+}
@@ -1,8 +1,15 @@
+pub mod analytics;
 pub mod auth;
 pub mod common;
+pub mod money;
 pub mod user;
 
 // Re-export commonly used types
+pub use analytics::{
+    AnalyticsDimension, AnalyticsMetric, AnalyticsPoint, AnalyticsQuery, AnalyticsQueryError,
+    AnalyticsResult, AnalyticsSeries, Granularity, MetricUnit, ReportRange, ReportSpec,
+};
 pub use auth::*;
 pub use common::*;
+pub use money::{CurrencyMismatch, Money};
 pub use user::*;
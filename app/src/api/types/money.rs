@@ -0,0 +1,212 @@
+use rust_decimal::Decimal;
+use serde::{Deserialize, Deserializer, Serialize};
+use thiserror::Error;
+
+use crate::api::{errors::ApiError, traits::ledger::LedgerCurrency};
+
+/// An amount of money in a named currency.
+///
+/// Amounts are exact decimals. They serialize as strings and deserialize from either a
+/// string or a JSON number. A bare amount with no currency is read as KES, the currency
+/// the dashboard reports in. Arithmetic between different currencies is refused.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub struct Money {
+    pub amount: Decimal,
+    /// Upper-case ISO 4217 code, or `SATS`
+    pub currency: String,
+}
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("Cannot combine {left} with {right}")]
+pub struct CurrencyMismatch {
+    pub left: String,
+    pub right: String,
+}
+
+impl From<CurrencyMismatch> for ApiError {
+    fn from(error: CurrencyMismatch) -> Self {
+        ApiError::Validation {
+            message: error.to_string(),
+        }
+    }
+}
+
+impl Money {
+    pub const REPORTING_CURRENCY: &'static str = "KES";
+
+    pub fn new(amount: Decimal, currency: &str) -> Self {
+        Self {
+            amount,
+            currency: currency.trim().to_uppercase(),
+        }
+    }
+
+    pub fn kes(amount: Decimal) -> Self {
+        Self::new(amount, "KES")
+    }
+
+    pub fn sats(amount: Decimal) -> Self {
+        Self::new(amount, "SATS")
+    }
+
+    pub fn zero(currency: &str) -> Self {
+        Self::new(Decimal::ZERO, currency)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.amount.is_zero()
+    }
+
+    /// The ledger currency with the same code, if there is one
+    pub fn ledger_currency(&self) -> Option<LedgerCurrency> {
+        match self.currency.as_str() {
+            "KES" => Some(LedgerCurrency::Kes),
+            "SATS" => Some(LedgerCurrency::Sats),
+            _ => None,
+        }
+    }
+
+    fn same_currency(&self, other: &Money) -> Result<(), CurrencyMismatch> {
+        if self.currency == other.currency {
+            Ok(())
+        } else {
+            Err(CurrencyMismatch {
+                left: self.currency.clone(),
+                right: other.currency.clone(),
+            })
+        }
+    }
+
+    pub fn checked_add(&self, other: &Money) -> Result<Money, CurrencyMismatch> {
+        self.same_currency(other)?;
+        Ok(Money::new(self.amount + other.amount, &self.currency))
+    }
+
+    pub fn checked_sub(&self, other: &Money) -> Result<Money, CurrencyMismatch> {
+        self.same_currency(other)?;
+        Ok(Money::new(self.amount - other.amount, &self.currency))
+    }
+
+    /// Multiply by a plain number, such as a rate or a count
+    pub fn times(&self, factor: Decimal) -> Money {
+        Money::new(self.amount * factor, &self.currency)
+    }
+
+    /// Divide by a plain number; `None` when dividing by zero
+    pub fn checked_div(&self, divisor: Decimal) -> Option<Money> {
+        self.amount
+            .checked_div(divisor)
+            .map(|amount| Money::new(amount, &self.currency))
+    }
+
+    /// Total of `amounts`, all of which must be in `currency`
+    pub fn sum<'a>(
+        currency: &str,
+        amounts: impl IntoIterator<Item = &'a Money>,
+    ) -> Result<Money, CurrencyMismatch> {
+        amounts
+            .into_iter()
+            .try_fold(Money::zero(currency), |total, amount| {
+                total.checked_add(amount)
+            })
+    }
+
+    /// Fail unless this amount is in `currency`
+    pub fn expect_currency(&self, currency: &str) -> Result<&Money, CurrencyMismatch> {
+        self.same_currency(&Money::zero(currency)).map(|_| self)
+    }
+}
+
+impl std::fmt::Display for Money {
+    /// `KES 1,250.50` and `12,500 sats`; other currencies as `USD 12.5`
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.ledger_currency() {
+            Some(currency) => write!(f, "{}", currency.format(self.amount)),
+            None => write!(f, "{} {}", self.currency, self.amount.normalize()),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Money {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        // Decimal itself reads strings and numbers alike
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Wire {
+            Tagged { amount: Decimal, currency: String },
+            Bare(Decimal),
+        }
+
+        Ok(match Wire::deserialize(deserializer)? {
+            Wire::Tagged { amount, currency } => Money::new(amount, &currency),
+            Wire::Bare(amount) => Money::new(amount, Money::REPORTING_CURRENCY),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_reads_strings_and_numbers() {
+        let tagged: Money =
+            serde_json::from_value(json!({ "amount": "1250.10", "currency": "kes" })).unwrap();
+        assert_eq!(tagged, Money::kes(Decimal::new(125_010, 2)));
+
+        // The literal is kept, not its nearest binary float
+        let numeric: Money =
+            serde_json::from_value(json!({ "amount": 0.1, "currency": "USD" })).unwrap();
+        assert_eq!(numeric.amount, Decimal::new(1, 1));
+
+        let bare: Money = serde_json::from_value(json!(1_234_567.89)).unwrap();
+        assert_eq!(bare, Money::kes(Decimal::new(123_456_789, 2)));
+        let bare: Money = serde_json::from_value(json!("42")).unwrap();
+        assert_eq!(bare, Money::kes(Decimal::from(42)));
+
+        assert!(serde_json::from_value::<Money>(json!("lots")).is_err());
+        assert_eq!(
+            serde_json::to_value(Money::sats(Decimal::from(21))).unwrap(),
+            json!({ "amount": "21", "currency": "SATS" })
+        );
+    }
+
+    #[test]
+    fn test_refuses_mixed_currencies() {
+        let kes = Money::kes(Decimal::new(1_050, 2));
+        let sats = Money::sats(Decimal::from(500));
+
+        assert_eq!(
+            kes.checked_add(&kes).unwrap(),
+            Money::kes(Decimal::from(21))
+        );
+        assert_eq!(
+            kes.checked_sub(&sats),
+            Err(CurrencyMismatch {
+                left: "KES".to_string(),
+                right: "SATS".to_string(),
+            })
+        );
+        assert!(Money::sum("KES", [&kes, &sats]).is_err());
+        assert_eq!(
+            Money::sum("KES", [&kes, &kes, &kes]).unwrap(),
+            Money::kes(Decimal::new(3_150, 2))
+        );
+        assert!(kes.expect_currency("SATS").is_err());
+        assert_eq!(kes.checked_div(Decimal::ZERO), None);
+
+        assert_eq!(kes.to_string(), "KES 10.50");
+        assert_eq!(
+            Money::sats(Decimal::from(12_500)).to_string(),
+            "12,500 sats"
+        );
+        assert_eq!(
+            Money::new(Decimal::new(1_250, 2), "usd").to_string(),
+            "USD 12.5"
+        );
+    }
+}
@@ -18,6 +18,13 @@ pub struct User {
     pub updated_at: DateTime<Utc>,
 }
 
+impl User {
+    /// Whether the user holds `Role::Admin` or above
+    pub fn is_admin(&self) -> bool {
+        self.roles.iter().any(Role::is_admin)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UserUpdates {
     pub phone: Option<Phone>,
@@ -1,3 +1,4 @@
+use crate::contexts::app_state::{use_app_state, Notification, NotificationType};
 use leptos::prelude::*;
 use leptos_router::hooks::use_location;
 
@@ -5,6 +6,8 @@ use leptos_router::hooks::use_location;
 pub fn Header(set_mobile_open: WriteSignal<bool>) -> impl IntoView {
     let _location = use_location();
     let (notifications_open, set_notifications_open) = signal(false);
+    let app_state = use_app_state();
+    let unread = move || app_state.unread_notifications();
 
     view! {
         <div class="sticky top-0 z-10 flex-shrink-0 flex h-16 bg-white border-b border-gray-200 shadow-sm">
@@ -47,9 +50,11 @@ pub fn Header(set_mobile_open: WriteSignal<bool>) -> impl IntoView {
                                 <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M15 17h5l1.405-1.405A2.032 2.032 0 0118 14.158V11a6.002 6.002 0 00-4-5.659V5a2 2 0 10-4 0v.341C7.67 6.165 6 8.388 6 11v3.159c0 .538-.214 1.055-.595 1.436L4 17h5m6 0v1a3 3 0 11-6 0v-1m6 0H9" />
                             </svg>
                             // Notification badge
-                            <span class="absolute -top-0.5 -right-0.5 h-5 w-5 bg-red-500 text-white text-xs rounded-full flex items-center justify-center font-medium">
-                                "3"
-                            </span>
+                            {move || (unread() > 0).then(|| view! {
+                                <span class="absolute -top-0.5 -right-0.5 h-5 w-5 bg-red-500 text-white text-xs rounded-full flex items-center justify-center font-medium">
+                                    {unread().min(99)}
+                                </span>
+                            })}
                         </button>
 
                         // Notifications dropdown
@@ -68,15 +73,47 @@ pub fn Header(set_mobile_open: WriteSignal<bool>) -> impl IntoView {
     }
 }
 
+/// "just now", "5 minutes ago", "3 hours ago" or "2 days ago"
+fn time_ago(timestamp: chrono::DateTime<chrono::Utc>) -> String {
+    let minutes = (chrono::Utc::now() - timestamp).num_minutes().max(0);
+    let (count, unit) = match minutes {
+        0 => return "just now".to_string(),
+        1..60 => (minutes, "minute"),
+        60..1_440 => (minutes / 60, "hour"),
+        _ => (minutes / 1_440, "day"),
+    };
+    format!(
+        "{} {}{} ago",
+        count,
+        unit,
+        if count == 1 { "" } else { "s" }
+    )
+}
+
 #[component]
 fn NotificationDropdown(on_close: impl Fn() + 'static + Copy) -> impl IntoView {
+    let app_state = use_app_state();
+    let notifications = app_state.notifications;
+    let mark_all_read = {
+        let app_state = app_state.clone();
+        move |_| app_state.mark_all_notifications_read()
+    };
+    let unread_text = {
+        let app_state = app_state.clone();
+        move || match app_state.unread_notifications() {
+            0 => "No unread messages".to_string(),
+            1 => "1 unread message".to_string(),
+            unread => format!("{} unread messages", unread),
+        }
+    };
+
     view! {
         <div class="origin-top-right absolute right-0 mt-2 w-96 rounded-xl shadow-xl bg-white ring-1 ring-black ring-opacity-5 focus:outline-none z-50 border border-gray-200 animate-fade-in animate-slide-in-down">
             <div class="p-0">
                 <div class="flex items-center justify-between p-4 border-b border-gray-100">
                     <div>
                         <h3 class="text-lg font-semibold font-heading text-gray-900">"Notifications"</h3>
-                        <p class="text-sm font-body text-gray-500">"3 unread messages"</p>
+                        <p class="text-sm font-body text-gray-500">{unread_text}</p>
                     </div>
                     <button
                         type="button"
@@ -90,50 +127,33 @@ fn NotificationDropdown(on_close: impl Fn() + 'static + Copy) -> impl IntoView {
                 </div>
 
                 <div class="max-h-96 overflow-y-auto scrollbar-thin">
-                    <NotificationItem
-                        title="New member registration"
-                        message="John Doe has registered as a new member and is awaiting approval"
-                        time="2 minutes ago"
-                        unread=true
-                        icon_type="user"
-                    />
-                    <NotificationItem
-                        title="Share purchase completed"
-                        message="Alice Smith purchased 10 shares for $1,500 total value"
-                        time="1 hour ago"
-                        unread=true
-                        icon_type="money"
-                    />
-                    <NotificationItem
-                        title="System maintenance scheduled"
-                        message="Scheduled maintenance will begin at 2:00 AM tonight"
-                        time="2 hours ago"
-                        unread=true
-                        icon_type="warning"
-                    />
-                    <NotificationItem
-                        title="System backup completed"
-                        message="Daily backup completed successfully. All data secure"
-                        time="3 hours ago"
-                        unread=false
-                        icon_type="success"
-                    />
-                    <NotificationItem
-                        title="Group meeting reminder"
-                        message="Monthly board meeting scheduled for tomorrow at 10 AM"
-                        time="5 hours ago"
-                        unread=false
-                        icon_type="calendar"
-                    />
+                    {move || {
+                        // Newest first
+                        let mut notifications = notifications.get();
+                        notifications.reverse();
+                        if notifications.is_empty() {
+                            view! {
+                                <p class="px-4 py-6 text-sm font-body text-gray-500 text-center">"You're all caught up."</p>
+                            }.into_any()
+                        } else {
+                            notifications.into_iter().map(|notification| view! {
+                                <NotificationItem notification=notification />
+                            }).collect_view().into_any()
+                        }
+                    }}
                 </div>
 
                 <div class="p-4 border-t border-gray-100 bg-gray-50">
                     <div class="flex justify-between items-center">
-                        <button class="text-sm text-teal-600 hover:text-teal-700 font-medium px-3 py-1.5 rounded-lg hover:bg-teal-50 transition-colors">
+                        <button
+                            type="button"
+                            class="text-sm text-teal-600 hover:text-teal-700 font-medium px-3 py-1.5 rounded-lg hover:bg-teal-50 transition-colors"
+                            on:click=mark_all_read
+                        >
                             "Mark all as read"
                         </button>
-                        <a href="/notifications" class="text-sm text-teal-600 hover:text-teal-700 font-medium px-3 py-1.5 rounded-lg hover:bg-teal-50 transition-colors">
-                            "View all notifications"
+                        <a href="/dashboard#system-alerts" class="text-sm text-teal-600 hover:text-teal-700 font-medium px-3 py-1.5 rounded-lg hover:bg-teal-50 transition-colors">
+                            "View system alerts"
                         </a>
                     </div>
                 </div>
@@ -143,31 +163,20 @@ fn NotificationDropdown(on_close: impl Fn() + 'static + Copy) -> impl IntoView {
 }
 
 #[component]
-fn NotificationItem(
-    title: &'static str,
-    message: &'static str,
-    time: &'static str,
-    unread: bool,
-    icon_type: &'static str,
-) -> impl IntoView {
-    let (icon_bg, icon_svg) = match icon_type {
-        "user" => (
-            "bg-teal-100",
+fn NotificationItem(notification: Notification) -> impl IntoView {
+    let app_state = use_app_state();
+    let id = notification.id;
+    let unread = !notification.read;
+    let (icon_bg, icon_svg) = match notification.notification_type {
+        NotificationType::Error => (
+            "bg-red-100",
             view! {
-                <svg class="h-4 w-4 text-teal-600" fill="none" viewBox="0 0 24 24" stroke="currentColor">
-                    <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M16 7a4 4 0 11-8 0 4 4 0 018 0zM12 14a7 7 0 00-7 7h14a7 7 0 00-7-7z" />
-                </svg>
-            },
-        ),
-        "money" => (
-            "bg-green-100",
-            view! {
-                <svg class="h-4 w-4 text-green-600" fill="none" viewBox="0 0 24 24" stroke="currentColor">
-                    <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M12 8c-1.657 0-3 .895-3 2s1.343 2 3 2 3 .895 3 2-1.343 2-3 2m0-8c1.11 0 2.08.402 2.599 1M12 8V7m0 1v8m0 0v1m0-1c-1.11 0-2.08-.402-2.599-1" />
+                <svg class="h-4 w-4 text-red-600" fill="none" viewBox="0 0 24 24" stroke="currentColor">
+                    <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M12 9v2m0 4h.01m-6.938 4h13.856c1.54 0 2.502-1.667 1.732-2.5L13.732 4c-.77-.833-1.732-.833-2.464 0L4.35 16.5c-.77.833.192 2.5 1.732 2.5z" />
                 </svg>
             },
         ),
-        "warning" => (
+        NotificationType::Warning => (
             "bg-yellow-100",
             view! {
                 <svg class="h-4 w-4 text-yellow-600" fill="none" viewBox="0 0 24 24" stroke="currentColor">
@@ -175,7 +184,7 @@ fn NotificationItem(
                 </svg>
             },
         ),
-        "success" => (
+        NotificationType::Success => (
             "bg-green-100",
             view! {
                 <svg class="h-4 w-4 text-green-600" fill="none" viewBox="0 0 24 24" stroke="currentColor">
@@ -183,15 +192,7 @@ fn NotificationItem(
                 </svg>
             },
         ),
-        "calendar" => (
-            "bg-purple-100",
-            view! {
-                <svg class="h-4 w-4 text-purple-600" fill="none" viewBox="0 0 24 24" stroke="currentColor">
-                    <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M8 7V3m8 4V3m-9 8h10M5 21h14a2 2 0 002-2V7a2 2 0 00-2-2H5a2 2 0 00-2 2v12a2 2 0 002 2z" />
-                </svg>
-            },
-        ),
-        _ => (
+        NotificationType::Info => (
             "bg-gray-100",
             view! {
                 <svg class="h-4 w-4 text-gray-600" fill="none" viewBox="0 0 24 24" stroke="currentColor">
@@ -202,9 +203,12 @@ fn NotificationItem(
     };
 
     view! {
-        <div class={format!("px-4 py-3 hover:bg-gray-50 transition-colors cursor-pointer border-l-4 {}",
-            if unread { "border-teal-400 bg-teal-50/30" } else { "border-transparent" }
-        )}>
+        <div
+            class={format!("px-4 py-3 hover:bg-gray-50 transition-colors cursor-pointer border-l-4 {}",
+                if unread { "border-teal-400 bg-teal-50/30" } else { "border-transparent" }
+            )}
+            on:click=move |_| app_state.mark_notification_read(id)
+        >
             <div class="flex items-start space-x-3">
                 <div class={format!("flex-shrink-0 h-8 w-8 rounded-full {} flex items-center justify-center", icon_bg)}>
                     {icon_svg}
@@ -214,7 +218,7 @@ fn NotificationItem(
                         <p class={format!("text-sm font-medium font-body truncate {}",
                             if unread { "text-gray-900" } else { "text-gray-700" }
                         )}>
-                            {title}
+                            {notification.title}
                         </p>
                         <div class="flex items-center space-x-2">
                             {if unread {
@@ -225,12 +229,12 @@ fn NotificationItem(
                                 view! { <div></div> }.into_any()
                             }}
                             <p class="text-xs font-body text-gray-500 whitespace-nowrap">
-                                {time}
+                                {time_ago(notification.timestamp)}
                             </p>
                         </div>
                     </div>
                     <p class="text-sm font-body text-gray-600 mt-1 line-clamp-2">
-                        {message}
+                        {notification.message}
                     </p>
                 </div>
             </div>
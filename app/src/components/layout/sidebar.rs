@@ -64,6 +64,42 @@ fn MobileSidebarContent(set_mobile_open: WriteSignal<bool>) -> impl IntoView {
                     <span class="truncate font-body">"Dashboard"</span>
                 </a>
 
+                <a href="/user-analytics" class="group flex items-center px-4 py-3 text-xl font-semibold rounded-lg text-white hover:bg-slate-700/50 transition-all duration-200"
+                   on:click=move |_| set_mobile_open.set(false)>
+                    <svg class="w-6 h-6 mr-3 text-gray-400 group-hover:text-gray-300" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+                        <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M16 8v8m-4-5v5m-4-2v2m-2 4h12a2 2 0 002-2V6a2 2 0 00-2-2H6a2 2 0 00-2 2v12a2 2 0 002 2z" />
+                        <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M8 5a2 2 0 012-2h4a2 2 0 012 2v4H8V5z" />
+                    </svg>
+                    <span class="truncate font-body">"User Analytics"</span>
+                </a>
+
+                <a href="/financial-analytics" class="group flex items-center px-4 py-3 text-xl font-semibold rounded-lg text-white hover:bg-slate-700/50 transition-all duration-200"
+                   on:click=move |_| set_mobile_open.set(false)>
+                    <svg class="w-6 h-6 mr-3 text-gray-400 group-hover:text-gray-300" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+                        <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M9 19v-6a2 2 0 00-2-2H5a2 2 0 00-2 2v6a2 2 0 002 2h2a2 2 0 002-2zm0 0V9a2 2 0 012-2h2a2 2 0 012 2v10m-6 0a2 2 0 002 2h2a2 2 0 002-2m0 0V5a2 2 0 012-2h2a2 2 0 012 2v14a2 2 0 01-2 2h-2a2 2 0 01-2-2z" />
+                        <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M8 5a2 2 0 012-2h4a2 2 0 012 2v4H8V5z" />
+                    </svg>
+                    <span class="truncate font-body">"Financial Analytics"</span>
+                </a>
+
+                <a href="/report-builder" class="group flex items-center px-4 py-3 text-xl font-semibold rounded-lg text-white hover:bg-slate-700/50 transition-all duration-200"
+                   on:click=move |_| set_mobile_open.set(false)>
+                    <svg class="w-6 h-6 mr-3 text-gray-400 group-hover:text-gray-300" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+                        <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M9 17v-2m3 2v-4m3 4v-6m2 10H7a2 2 0 01-2-2V5a2 2 0 012-2h5.586a1 1 0 01.707.293l5.414 5.414a1 1 0 01.293.707V19a2 2 0 01-2 2z" />
+                        <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M8 5a2 2 0 012-2h4a2 2 0 012 2v4H8V5z" />
+                    </svg>
+                    <span class="truncate font-body">"Report Builder"</span>
+                </a>
+
+                <a href="/export-center" class="group flex items-center px-4 py-3 text-xl font-semibold rounded-lg text-white hover:bg-slate-700/50 transition-all duration-200"
+                   on:click=move |_| set_mobile_open.set(false)>
+                    <svg class="w-6 h-6 mr-3 text-gray-400 group-hover:text-gray-300" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+                        <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M4 16v1a3 3 0 003 3h10a3 3 0 003-3v-1m-4-4l-4 4m0 0l-4-4m4 4V4" />
+                        <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M8 5a2 2 0 012-2h4a2 2 0 012 2v4H8V5z" />
+                    </svg>
+                    <span class="truncate font-body">"Export Center"</span>
+                </a>
+
                 <a href="/members" class="group flex items-center px-4 py-3 text-xl font-semibold rounded-lg text-white hover:bg-slate-700/50 transition-all duration-200"
                    on:click=move |_| set_mobile_open.set(false)>
                     <svg class="w-6 h-6 mr-3 text-gray-400 group-hover:text-gray-300" fill="none" stroke="currentColor" viewBox="0 0 24 24">
@@ -88,6 +124,62 @@ fn MobileSidebarContent(set_mobile_open: WriteSignal<bool>) -> impl IntoView {
                     <span class="truncate font-body">"Shares"</span>
                 </a>
 
+                <a href="/loans" class="group flex items-center px-4 py-3 text-xl font-semibold rounded-lg text-white hover:bg-slate-700/50 transition-all duration-200"
+                   on:click=move |_| set_mobile_open.set(false)>
+                    <svg class="w-6 h-6 mr-3 text-gray-400 group-hover:text-gray-300" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+                        <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M12 8c-1.657 0-3 .895-3 2s1.343 2 3 2 3 .895 3 2-1.343 2-3 2m0-8c1.11 0 2.08.402 2.599 1M12 8V7m0 1v8m0 0v1m0-1c-1.11 0-2.08-.402-2.599-1M21 12a9 9 0 11-18 0 9 9 0 0118 0z" />
+                    </svg>
+                    <span class="truncate font-body">"Loans"</span>
+                </a>
+
+                <a href="/dividends" class="group flex items-center px-4 py-3 text-xl font-semibold rounded-lg text-white hover:bg-slate-700/50 transition-all duration-200"
+                   on:click=move |_| set_mobile_open.set(false)>
+                    <svg class="w-6 h-6 mr-3 text-gray-400 group-hover:text-gray-300" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+                        <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M17 9V7a2 2 0 00-2-2H5a2 2 0 00-2 2v6a2 2 0 002 2h2m2 4h10a2 2 0 002-2v-6a2 2 0 00-2-2H9a2 2 0 00-2 2v6a2 2 0 002 2zm7-5a2 2 0 11-4 0 2 2 0 014 0z" />
+                    </svg>
+                    <span class="truncate font-body">"Dividends"</span>
+                </a>
+
+                <a href="/fx" class="group flex items-center px-4 py-3 text-xl font-semibold rounded-lg text-white hover:bg-slate-700/50 transition-all duration-200"
+                   on:click=move |_| set_mobile_open.set(false)>
+                    <svg class="w-6 h-6 mr-3 text-gray-400 group-hover:text-gray-300" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+                        <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M7 16V4m0 0L3 8m4-4l4 4m6 0v12m0 0l4-4m-4 4l-4-4" />
+                    </svg>
+                    <span class="truncate font-body">"Exchange Rates"</span>
+                </a>
+
+                <a href="/swaps" class="group flex items-center px-4 py-3 text-xl font-semibold rounded-lg text-white hover:bg-slate-700/50 transition-all duration-200"
+                   on:click=move |_| set_mobile_open.set(false)>
+                    <svg class="w-6 h-6 mr-3 text-gray-400 group-hover:text-gray-300" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+                        <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M8 7h12m0 0l-4-4m4 4l-4 4m0 6H4m0 0l4 4m-4-4l4-4" />
+                    </svg>
+                    <span class="truncate font-body">"Swaps"</span>
+                </a>
+
+                <a href="/reconciliation" class="group flex items-center px-4 py-3 text-xl font-semibold rounded-lg text-white hover:bg-slate-700/50 transition-all duration-200"
+                   on:click=move |_| set_mobile_open.set(false)>
+                    <svg class="w-6 h-6 mr-3 text-gray-400 group-hover:text-gray-300" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+                        <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M9 12l2 2 4-4m5.618-4.016A11.955 11.955 0 0112 2.944a11.955 11.955 0 01-8.618 3.04A12.02 12.02 0 003 9c0 5.591 3.824 10.29 9 11.622 5.176-1.332 9-6.03 9-11.622 0-1.042-.133-2.052-.382-3.016z" />
+                    </svg>
+                    <span class="truncate font-body">"Reconciliation"</span>
+                </a>
+
+                <a href="/withdrawal-policy" class="group flex items-center px-4 py-3 text-xl font-semibold rounded-lg text-white hover:bg-slate-700/50 transition-all duration-200"
+                   on:click=move |_| set_mobile_open.set(false)>
+                    <svg class="w-6 h-6 mr-3 text-gray-400 group-hover:text-gray-300" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+                        <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M12 15v2m-6 4h12a2 2 0 002-2v-6a2 2 0 00-2-2H6a2 2 0 00-2 2v6a2 2 0 002 2zm10-10V7a4 4 0 00-8 0v4h8z" />
+                    </svg>
+                    <span class="truncate font-body">"Withdrawal Limits"</span>
+                </a>
+
+                <a href="/health" class="group flex items-center px-4 py-3 text-xl font-semibold rounded-lg text-white hover:bg-slate-700/50 transition-all duration-200"
+                   on:click=move |_| set_mobile_open.set(false)>
+                    <svg class="w-6 h-6 mr-3 text-gray-400 group-hover:text-gray-300" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+                        <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M4.318 6.318a4.5 4.5 0 000 6.364L12 20.364l7.682-7.682a4.5 4.5 0 00-6.364-6.364L12 7.636l-1.318-1.318a4.5 4.5 0 00-6.364 0z" />
+                    </svg>
+                    <span class="truncate font-body">"System Health"</span>
+                </a>
+
                 <a href="/settings" class="group flex items-center px-4 py-3 text-xl font-semibold rounded-lg text-white hover:bg-slate-700/50 transition-all duration-200 mt-8"
                    on:click=move |_| set_mobile_open.set(false)>
                     <svg class="w-6 h-6 mr-3 text-gray-400 group-hover:text-gray-300" fill="none" stroke="currentColor" viewBox="0 0 24 24">
@@ -185,6 +277,50 @@ fn DesktopSidebarContent() -> impl IntoView {
                         text="Dashboard"
                         current_path=location.pathname.into()
                     />
+                    <NavItem
+                        href="/user-analytics"
+                        icon_svg=view! {
+                            <svg class="w-5 h-5" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+                                <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M16 8v8m-4-5v5m-4-2v2m-2 4h12a2 2 0 002-2V6a2 2 0 00-2-2H6a2 2 0 00-2 2v12a2 2 0 002 2z" />
+                                <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M8 5a2 2 0 012-2h4a2 2 0 012 2v4H8V5z" />
+                            </svg>
+                        }
+                        text="User Analytics"
+                        current_path=location.pathname.into()
+                    />
+                    <NavItem
+                        href="/financial-analytics"
+                        icon_svg=view! {
+                            <svg class="w-5 h-5" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+                                <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M9 19v-6a2 2 0 00-2-2H5a2 2 0 00-2 2v6a2 2 0 002 2h2a2 2 0 002-2zm0 0V9a2 2 0 012-2h2a2 2 0 012 2v10m-6 0a2 2 0 002 2h2a2 2 0 002-2m0 0V5a2 2 0 012-2h2a2 2 0 012 2v14a2 2 0 01-2 2h-2a2 2 0 01-2-2z" />
+                                <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M8 5a2 2 0 012-2h4a2 2 0 012 2v4H8V5z" />
+                            </svg>
+                        }
+                        text="Financial Analytics"
+                        current_path=location.pathname.into()
+                    />
+                    <NavItem
+                        href="/report-builder"
+                        icon_svg=view! {
+                            <svg class="w-5 h-5" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+                                <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M9 17v-2m3 2v-4m3 4v-6m2 10H7a2 2 0 01-2-2V5a2 2 0 012-2h5.586a1 1 0 01.707.293l5.414 5.414a1 1 0 01.293.707V19a2 2 0 01-2 2z" />
+                                <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M8 5a2 2 0 012-2h4a2 2 0 012 2v4H8V5z" />
+                            </svg>
+                        }
+                        text="Report Builder"
+                        current_path=location.pathname.into()
+                    />
+                    <NavItem
+                        href="/export-center"
+                        icon_svg=view! {
+                            <svg class="w-5 h-5" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+                                <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M4 16v1a3 3 0 003 3h10a3 3 0 003-3v-1m-4-4l-4 4m0 0l-4-4m4 4V4" />
+                                <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M8 5a2 2 0 012-2h4a2 2 0 012 2v4H8V5z" />
+                            </svg>
+                        }
+                        text="Export Center"
+                        current_path=location.pathname.into()
+                    />
                     <NavItem
                         href="/members"
                         icon_svg=view! {
@@ -215,6 +351,76 @@ fn DesktopSidebarContent() -> impl IntoView {
                         text="Shares"
                         current_path=location.pathname.into()
                     />
+                    <NavItem
+                        href="/loans"
+                        icon_svg=view! {
+                            <svg class="w-5 h-5" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+                                <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M12 8c-1.657 0-3 .895-3 2s1.343 2 3 2 3 .895 3 2-1.343 2-3 2m0-8c1.11 0 2.08.402 2.599 1M12 8V7m0 1v8m0 0v1m0-1c-1.11 0-2.08-.402-2.599-1M21 12a9 9 0 11-18 0 9 9 0 0118 0z" />
+                            </svg>
+                        }
+                        text="Loans"
+                        current_path=location.pathname.into()
+                    />
+                    <NavItem
+                        href="/dividends"
+                        icon_svg=view! {
+                            <svg class="w-5 h-5" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+                                <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M17 9V7a2 2 0 00-2-2H5a2 2 0 00-2 2v6a2 2 0 002 2h2m2 4h10a2 2 0 002-2v-6a2 2 0 00-2-2H9a2 2 0 00-2 2v6a2 2 0 002 2zm7-5a2 2 0 11-4 0 2 2 0 014 0z" />
+                            </svg>
+                        }
+                        text="Dividends"
+                        current_path=location.pathname.into()
+                    />
+                    <NavItem
+                        href="/fx"
+                        icon_svg=view! {
+                            <svg class="w-5 h-5" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+                                <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M7 16V4m0 0L3 8m4-4l4 4m6 0v12m0 0l4-4m-4 4l-4-4" />
+                            </svg>
+                        }
+                        text="Exchange Rates"
+                        current_path=location.pathname.into()
+                    />
+                    <NavItem
+                        href="/swaps"
+                        icon_svg=view! {
+                            <svg class="w-5 h-5" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+                                <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M8 7h12m0 0l-4-4m4 4l-4 4m0 6H4m0 0l4 4m-4-4l4-4" />
+                            </svg>
+                        }
+                        text="Swaps"
+                        current_path=location.pathname.into()
+                    />
+                    <NavItem
+                        href="/reconciliation"
+                        icon_svg=view! {
+                            <svg class="w-5 h-5" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+                                <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M9 12l2 2 4-4m5.618-4.016A11.955 11.955 0 0112 2.944a11.955 11.955 0 01-8.618 3.04A12.02 12.02 0 003 9c0 5.591 3.824 10.29 9 11.622 5.176-1.332 9-6.03 9-11.622 0-1.042-.133-2.052-.382-3.016z" />
+                            </svg>
+                        }
+                        text="Reconciliation"
+                        current_path=location.pathname.into()
+                    />
+                    <NavItem
+                        href="/withdrawal-policy"
+                        icon_svg=view! {
+                            <svg class="w-5 h-5" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+                                <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M12 15v2m-6 4h12a2 2 0 002-2v-6a2 2 0 00-2-2H6a2 2 0 00-2 2v6a2 2 0 002 2zm10-10V7a4 4 0 00-8 0v4h8z" />
+                            </svg>
+                        }
+                        text="Withdrawal Limits"
+                        current_path=location.pathname.into()
+                    />
+                    <NavItem
+                        href="/health"
+                        icon_svg=view! {
+                            <svg class="w-5 h-5" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+                                <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M4.318 6.318a4.5 4.5 0 000 6.364L12 20.364l7.682-7.682a4.5 4.5 0 00-6.364-6.364L12 7.636l-1.318-1.318a4.5 4.5 0 00-6.364 0z" />
+                            </svg>
+                        }
+                        text="System Health"
+                        current_path=location.pathname.into()
+                    />
 
                     <div class="pt-4">
                         <div class="text-xs font-semibold font-body text-gray-400 uppercase tracking-widest px-2 mb-2">
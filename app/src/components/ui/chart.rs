@@ -0,0 +1,252 @@
+use leptos::prelude::*;
+
+// Drawing area of every chart, in SVG user units; the SVG stretches to its container
+const WIDTH: f64 = 400.0;
+const HEIGHT: f64 = 160.0;
+const PADDING: f64 = 8.0;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChartPoint {
+    /// Shown on the x axis and in the tooltip, e.g. a date
+    pub label: String,
+    pub value: f64,
+    /// Exact value for the tooltip and axis, when formatting `value` would lose precision
+    pub display: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChartKind {
+    Line,
+    Area,
+    Bar,
+}
+
+/// Where one point lands in the drawing area
+#[derive(Debug, Clone, Copy)]
+struct Plotted {
+    x: f64,
+    y: f64,
+}
+
+/// Scale points into the drawing area, with zero (or the lowest negative value)
+/// on the baseline
+fn plot(points: &[ChartPoint], kind: ChartKind) -> (Vec<Plotted>, f64) {
+    let max = points.iter().map(|p| p.value).fold(0.0, f64::max);
+    let min = points.iter().map(|p| p.value).fold(0.0, f64::min);
+    let span = if max > min { max - min } else { 1.0 };
+    let inner_height = HEIGHT - 2.0 * PADDING;
+    let y_of = |value: f64| PADDING + (max - value) / span * inner_height;
+
+    let band = (WIDTH - 2.0 * PADDING) / points.len().max(1) as f64;
+    let plotted = points
+        .iter()
+        .enumerate()
+        .map(|(i, point)| {
+            let x = match kind {
+                ChartKind::Bar => PADDING + band * (i as f64 + 0.5),
+                _ if points.len() == 1 => WIDTH / 2.0,
+                _ => PADDING + (WIDTH - 2.0 * PADDING) * i as f64 / (points.len() - 1) as f64,
+            };
+            Plotted {
+                x,
+                y: y_of(point.value),
+            }
+        })
+        .collect();
+    (plotted, y_of(0.0))
+}
+
+fn line_path(plotted: &[Plotted]) -> String {
+    plotted
+        .iter()
+        .enumerate()
+        .map(|(i, p)| format!("{}{:.2},{:.2}", if i == 0 { "M" } else { "L" }, p.x, p.y))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn default_format(value: f64) -> String {
+    format!("{:.0}", value)
+}
+
+fn shown(point: &ChartPoint, format: fn(f64) -> String) -> String {
+    point.display.clone().unwrap_or_else(|| format(point.value))
+}
+
+/// A line, area or bar chart drawn as SVG on the server. Hovering a point shows
+/// its label and value once the page has hydrated; before that, and for screen
+/// readers, each point carries an SVG `<title>`.
+#[component]
+pub fn TrendChart(
+    points: Vec<ChartPoint>,
+    kind: ChartKind,
+    /// Accessible name for the chart
+    #[prop(into)]
+    title: String,
+    /// Stroke and fill colour; any CSS colour
+    #[prop(optional)]
+    color: Option<&'static str>,
+    /// Formats values for the tooltip and axis
+    #[prop(optional)]
+    format: Option<fn(f64) -> String>,
+) -> impl IntoView {
+    let color = color.unwrap_or("#4f46e5");
+    let format = format.unwrap_or(default_format);
+    let hovered = RwSignal::new(None::<usize>);
+
+    if points.is_empty() {
+        return view! {
+            <div class="flex items-center justify-center h-40 text-sm text-gray-500">
+                "No data for this period"
+            </div>
+        }
+        .into_any();
+    }
+
+    let (plotted, baseline) = plot(&points, kind);
+    let band = (WIDTH - 2.0 * PADDING) / points.len() as f64;
+    let max_label = points
+        .iter()
+        .max_by(|a, b| a.value.total_cmp(&b.value))
+        .map(|point| shown(point, format))
+        .unwrap_or_default();
+    let first_label = points[0].label.clone();
+    let last_label = points[points.len() - 1].label.clone();
+
+    let shape = match kind {
+        ChartKind::Bar => plotted
+            .iter()
+            .map(|p| {
+                let (top, height) = if p.y < baseline {
+                    (p.y, baseline - p.y)
+                } else {
+                    (baseline, p.y - baseline)
+                };
+                view! {
+                    <rect
+                        x=format!("{:.2}", p.x - band * 0.35)
+                        y=format!("{:.2}", top)
+                        width=format!("{:.2}", band * 0.7)
+                        height=format!("{:.2}", height.max(1.0))
+                        rx="2"
+                        fill=color
+                    />
+                }
+            })
+            .collect_view()
+            .into_any(),
+        ChartKind::Line | ChartKind::Area => {
+            let line = line_path(&plotted);
+            let area = (kind == ChartKind::Area).then(|| {
+                format!(
+                    "{} L{:.2},{:.2} L{:.2},{:.2} Z",
+                    line,
+                    plotted[plotted.len() - 1].x,
+                    baseline,
+                    plotted[0].x,
+                    baseline
+                )
+            });
+            view! {
+                {area.map(|d| view! { <path d=d fill=color fill-opacity="0.15" stroke="none"/> })}
+                <path d=line fill="none" stroke=color stroke-width="2" stroke-linejoin="round" vector-effect="non-scaling-stroke"/>
+            }
+            .into_any()
+        }
+    };
+
+    // One invisible column per point catches the pointer
+    let hit_width = if kind == ChartKind::Bar || points.len() == 1 {
+        band
+    } else {
+        (WIDTH - 2.0 * PADDING) / (points.len() - 1) as f64
+    };
+    let targets = points
+        .iter()
+        .zip(&plotted)
+        .enumerate()
+        .map(|(i, (point, p))| {
+            let tip = format!("{}: {}", point.label, shown(point, format));
+            view! {
+                <rect
+                    x=format!("{:.2}", (p.x - hit_width / 2.0).max(0.0))
+                    y="0"
+                    width=format!("{:.2}", hit_width)
+                    height=HEIGHT.to_string()
+                    fill="transparent"
+                    on:mouseenter=move |_| hovered.set(Some(i))
+                >
+                    <title>{tip}</title>
+                </rect>
+            }
+        })
+        .collect_view();
+
+    let tooltip_points = points.clone();
+    let tooltip_plotted = plotted.clone();
+    let tooltip = move || {
+        hovered.get().map(|i| {
+            let point = &tooltip_points[i];
+            let p = tooltip_plotted[i];
+            view! {
+                <div
+                    class="absolute z-10 -translate-x-1/2 -translate-y-full pointer-events-none bg-gray-900 text-white text-xs rounded px-2 py-1 whitespace-nowrap"
+                    style=format!("left: {:.2}%; top: {:.2}%", p.x / WIDTH * 100.0, p.y / HEIGHT * 100.0)
+                >
+                    <div class="font-medium">{shown(point, format)}</div>
+                    <div class="text-gray-300">{point.label.clone()}</div>
+                </div>
+            }
+        })
+    };
+    let marker = move || {
+        hovered.get().map(|i| {
+            let p = plotted[i];
+            view! {
+                <line
+                    x1=format!("{:.2}", p.x)
+                    x2=format!("{:.2}", p.x)
+                    y1="0"
+                    y2=HEIGHT.to_string()
+                    stroke="#9ca3af"
+                    stroke-dasharray="3 3"
+                    vector-effect="non-scaling-stroke"
+                />
+            }
+        })
+    };
+
+    view! {
+        <figure class="space-y-1">
+            <div class="flex justify-end text-xs text-gray-500">{format!("max {}", max_label)}</div>
+            <div class="relative">
+                <svg
+                    class="w-full h-40"
+                    viewBox=format!("0 0 {} {}", WIDTH, HEIGHT)
+                    preserveAspectRatio="none"
+                    role="img"
+                    aria-label=title
+                    on:mouseleave=move |_| hovered.set(None)
+                >
+                    <line
+                        x1="0"
+                        x2=WIDTH.to_string()
+                        y1=format!("{:.2}", baseline)
+                        y2=format!("{:.2}", baseline)
+                        stroke="#e5e7eb"
+                        vector-effect="non-scaling-stroke"
+                    />
+                    {shape}
+                    {marker}
+                    {targets}
+                </svg>
+                {tooltip}
+            </div>
+            <figcaption class="flex justify-between text-xs text-gray-500">
+                <span>{first_label}</span>
+                <span>{last_label}</span>
+            </figcaption>
+        </figure>
+    }
+    .into_any()
+}
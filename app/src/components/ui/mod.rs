@@ -1,4 +1,5 @@
 pub mod button;
+pub mod chart;
 pub mod checkbox;
 pub mod input;
 pub mod loading;
@@ -8,6 +9,7 @@ pub mod table;
 pub mod toast;
 
 pub use button::*;
+pub use chart::*;
 pub use checkbox::*;
 pub use input::*;
 pub use loading::*;
@@ -11,7 +11,13 @@ pub fn ToastContainer() -> impl IntoView {
         <div class="fixed inset-0 flex items-end justify-center px-4 py-6 pointer-events-none sm:p-6 sm:items-start sm:justify-end z-50">
             <div class="w-full flex flex-col items-center space-y-4 sm:items-end">
                 <For
-                    each=move || notifications.get()
+                    each=move || {
+                        notifications
+                            .get()
+                            .into_iter()
+                            .filter(|notification| !notification.dismissed)
+                            .collect::<Vec<_>>()
+                    }
                     key=|notification| notification.id
                     children=move |notification| {
                         view! {
@@ -35,10 +41,10 @@ pub fn ToastItem(notification: Notification) -> impl IntoView {
     let set_visible_clone = set_visible;
     let timeout = Timeout::new(5_000, move || {
         set_visible_clone.set(false);
-        // Remove from notifications after animation
+        // Leave the toast stack after the animation; the header list keeps it
         let app_state_clone2 = app_state_clone.clone();
         let timeout = Timeout::new(300, move || {
-            app_state_clone2.remove_notification(id);
+            app_state_clone2.dismiss_notification(id);
         });
         timeout.forget();
     });
@@ -90,7 +96,7 @@ pub fn ToastItem(notification: Notification) -> impl IntoView {
                                     set_visible.set(false);
                                     let app_state_clone2 = app_state_clone.clone();
                                     let timeout = Timeout::new(300, move || {
-                                        app_state_clone2.remove_notification(id);
+                                        app_state_clone2.dismiss_notification(id);
                                     });
                                     timeout.forget();
                                 }
@@ -118,6 +124,7 @@ pub fn show_success(title: &str, message: &str) {
         notification_type: NotificationType::Success,
         timestamp: chrono::Utc::now(),
         read: false,
+        dismissed: false,
     };
     app_state.add_notification(notification);
 }
@@ -131,6 +138,7 @@ pub fn show_error(title: &str, message: &str) {
         notification_type: NotificationType::Error,
         timestamp: chrono::Utc::now(),
         read: false,
+        dismissed: false,
     };
     app_state.add_notification(notification);
 }
@@ -144,6 +152,7 @@ pub fn show_warning(title: &str, message: &str) {
         notification_type: NotificationType::Warning,
         timestamp: chrono::Utc::now(),
         read: false,
+        dismissed: false,
     };
     app_state.add_notification(notification);
 }
@@ -157,6 +166,7 @@ pub fn show_info(title: &str, message: &str) {
         notification_type: NotificationType::Info,
         timestamp: chrono::Utc::now(),
         read: false,
+        dismissed: false,
     };
     app_state.add_notification(notification);
 }
@@ -2,6 +2,9 @@ use leptos::prelude::*;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+/// Notifications kept for the header list; older ones are dropped
+const MAX_NOTIFICATIONS: usize = 50;
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct UserContext {
     pub user_id: Uuid,
@@ -27,6 +30,9 @@ pub struct Notification {
     pub notification_type: NotificationType,
     pub timestamp: chrono::DateTime<chrono::Utc>,
     pub read: bool,
+    /// Hidden from the toast stack; it stays in the header's notification list
+    #[serde(default)]
+    pub dismissed: bool,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -62,6 +68,8 @@ impl AppState {
     pub fn add_notification(&self, notification: Notification) {
         self.notifications.update(|notifications| {
             notifications.push(notification);
+            let overflow = notifications.len().saturating_sub(MAX_NOTIFICATIONS);
+            notifications.drain(..overflow);
         });
     }
 
@@ -79,6 +87,28 @@ impl AppState {
         });
     }
 
+    pub fn mark_all_notifications_read(&self) {
+        self.notifications.update(|notifications| {
+            for notification in notifications.iter_mut() {
+                notification.read = true;
+            }
+        });
+    }
+
+    /// Take a notification off the toast stack without removing it from the list
+    pub fn dismiss_notification(&self, id: Uuid) {
+        self.notifications.update(|notifications| {
+            if let Some(notification) = notifications.iter_mut().find(|n| n.id == id) {
+                notification.dismissed = true;
+            }
+        });
+    }
+
+    pub fn unread_notifications(&self) -> usize {
+        self.notifications
+            .with(|notifications| notifications.iter().filter(|n| !n.read).count())
+    }
+
     pub fn clear_error(&self) {
         self.error.set(None);
     }
@@ -787,16 +787,11 @@ pub async fn get_auth_user() -> Result<Option<UserInfo>, ServerFnError> {
 }
 
 /// Decode JWT token to extract user information
-/// This is a client-side only function (no signature verification)
+/// This is a client-side only function (no signature verification); the
+/// server resolves users with `verify_jwt_token`
 pub fn decode_jwt_token(token: &str) -> Result<UserInfo, String> {
     use jsonwebtoken::{decode, DecodingKey, Validation};
 
-    tracing::info!("🔥 JWT: Starting decode of token (length: {})", token.len());
-    tracing::info!(
-        "🔥 JWT: Token preview: {}...",
-        &token[..std::cmp::min(50, token.len())]
-    );
-
     // For client-side decoding, we don't verify the signature
     // since we don't have the secret key and we trust the token from our own server
     let mut validation = Validation::default();
@@ -806,8 +801,6 @@ pub fn decode_jwt_token(token: &str) -> Result<UserInfo, String> {
     validation.validate_aud = false; // Don't validate audience for now
     validation.required_spec_claims.clear(); // Clear required claims
 
-    tracing::info!("🔥 JWT: Attempting to decode with validation settings");
-
     let token_data = decode::<JwtClaims>(
         token,
         &DecodingKey::from_secret(&[]), // Empty key since we're not validating signature
@@ -815,18 +808,29 @@ pub fn decode_jwt_token(token: &str) -> Result<UserInfo, String> {
     )
     .map_err(|e| {
         tracing::error!("🔥 JWT decode failed: {}", e);
-        tracing::error!("🔥 JWT: Full token was: {}", token);
         format!("JWT decode error: {}", e)
     })?;
 
-    let claims = token_data.claims;
-    tracing::info!(
-        "🔥 JWT: Successfully decoded claims for user: {}",
-        claims.user.id
-    );
-    tracing::info!("🔥 JWT: User roles: {:?}", claims.user.roles);
-    tracing::info!("🔥 JWT: User phone: {:?}", claims.user.phone);
+    Ok(user_from_claims(token_data.claims))
+}
 
+/// Verify a session token's signature and expiry with the server's secret and
+/// resolve its user (SSR only)
+#[cfg(feature = "ssr")]
+pub fn verify_jwt_token(
+    token: &str,
+    validator: &crate::api::backends::nestjs::JwtValidator,
+) -> Result<UserInfo, String> {
+    let claims: JwtClaims = validator
+        .validate_session_token(token)
+        .map_err(|e| e.to_string())?;
+    if uuid::Uuid::parse_str(&claims.user.id).is_err() {
+        return Err("Invalid JWT token: user id is not a UUID".to_string());
+    }
+    Ok(user_from_claims(claims))
+}
+
+fn user_from_claims(claims: JwtClaims) -> UserInfo {
     // Convert numeric roles to string format expected by auth guards
     let roles: Vec<String> = claims
         .user
@@ -848,7 +852,7 @@ pub fn decode_jwt_token(token: &str) -> Result<UserInfo, String> {
         .map(|p| p.verified)
         .unwrap_or(false);
 
-    Ok(User {
+    User {
         id: uuid::Uuid::parse_str(&claims.user.id).unwrap_or_else(|_| uuid::Uuid::new_v4()),
         phone: phone_number.map(|number| crate::api::types::Phone { number }),
         nostr: claims
@@ -870,7 +874,7 @@ pub fn decode_jwt_token(token: &str) -> Result<UserInfo, String> {
         verified: phone_verified,
         created_at: chrono::Utc::now(),
         updated_at: chrono::Utc::now(),
-    })
+    }
 }
 
 /// Enhanced SSR-compatible auth provider that uses middleware-injected auth state
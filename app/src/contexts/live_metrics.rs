@@ -0,0 +1,158 @@
+use crate::api::dashboard_client::LiveMetricsUpdate;
+use leptos::prelude::*;
+use std::time::Duration;
+
+/// Longest wait between reconnection attempts
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(30);
+
+/// Failed reconnects after which the latest metrics are polled while we wait
+#[cfg(target_arch = "wasm32")]
+const POLL_AFTER_FAILURES: u32 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LiveConnection {
+    Connecting,
+    Live,
+    /// The stream dropped; `attempt` counts reconnects since it was last up
+    Reconnecting {
+        attempt: u32,
+        retry_in_secs: u64,
+    },
+}
+
+/// Live updates from `/api/live` and the state of the connection delivering them
+#[derive(Clone, Copy)]
+pub struct LiveMetricsHandle {
+    pub latest: ReadSignal<Option<LiveMetricsUpdate>>,
+    pub connection: ReadSignal<LiveConnection>,
+}
+
+/// Exponential backoff: 1s, 2s, 4s... up to `MAX_RECONNECT_DELAY`
+pub fn reconnect_delay(attempt: u32) -> Duration {
+    Duration::from_secs(1 << attempt.saturating_sub(1).min(5)).min(MAX_RECONNECT_DELAY)
+}
+
+/// Subscribe to the live metrics stream for as long as the calling component
+/// is mounted. The browser's own EventSource retry is replaced by ours, so a
+/// backend outage backs off instead of reconnecting every few seconds.
+pub fn use_live_metrics() -> LiveMetricsHandle {
+    let (latest, set_latest) = signal(None::<LiveMetricsUpdate>);
+    let (connection, set_connection) = signal(LiveConnection::Connecting);
+
+    #[cfg(target_arch = "wasm32")]
+    {
+        let source = StoredValue::new_local(None::<web_sys::EventSource>);
+        let stopped = StoredValue::new(false);
+        Effect::new(move |_| {
+            connect(
+                LiveStream {
+                    source,
+                    stopped,
+                    set_latest,
+                    set_connection,
+                },
+                0,
+            )
+        });
+        on_cleanup(move || {
+            stopped.set_value(true);
+            source.with_value(|source| {
+                if let Some(source) = source {
+                    source.close();
+                }
+            });
+        });
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    let _ = (set_latest, set_connection);
+
+    LiveMetricsHandle { latest, connection }
+}
+
+#[cfg(target_arch = "wasm32")]
+#[derive(Clone, Copy)]
+struct LiveStream {
+    source: StoredValue<Option<web_sys::EventSource>, LocalStorage>,
+    stopped: StoredValue<bool>,
+    set_latest: WriteSignal<Option<LiveMetricsUpdate>>,
+    set_connection: WriteSignal<LiveConnection>,
+}
+
+#[cfg(target_arch = "wasm32")]
+impl LiveStream {
+    /// A timer may fire after the component is gone; treat that as stopped
+    fn is_stopped(&self) -> bool {
+        self.stopped.try_get_value().unwrap_or(true)
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn connect(live: LiveStream, attempt: u32) {
+    use std::{cell::Cell, rc::Rc};
+    use wasm_bindgen::{closure::Closure, JsCast};
+
+    if live.is_stopped() {
+        return;
+    }
+    let Ok(source) = web_sys::EventSource::new("/api/live") else {
+        schedule_reconnect(live, attempt + 1);
+        return;
+    };
+    let opened = Rc::new(Cell::new(false));
+
+    let on_open = Closure::<dyn FnMut()>::new({
+        let opened = opened.clone();
+        move || {
+            opened.set(true);
+            let _ = live.set_connection.try_set(LiveConnection::Live);
+        }
+    });
+    let on_message =
+        Closure::<dyn FnMut(web_sys::MessageEvent)>::new(move |event: web_sys::MessageEvent| {
+            let update = event
+                .data()
+                .as_string()
+                .and_then(|data| serde_json::from_str::<LiveMetricsUpdate>(&data).ok());
+            if let Some(update) = update {
+                let _ = live.set_latest.try_set(Some(update));
+            }
+        });
+    let on_error = Closure::<dyn FnMut(web_sys::Event)>::new({
+        let source = source.clone();
+        move |_: web_sys::Event| {
+            source.close();
+            // Back off from scratch if the stream had been up
+            let next = if opened.get() { 1 } else { attempt + 1 };
+            schedule_reconnect(live, next);
+        }
+    });
+    source.set_onopen(Some(on_open.as_ref().unchecked_ref()));
+    source.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+    source.set_onerror(Some(on_error.as_ref().unchecked_ref()));
+    on_open.forget();
+    on_message.forget();
+    on_error.forget();
+
+    live.source.set_value(Some(source));
+}
+
+#[cfg(target_arch = "wasm32")]
+fn schedule_reconnect(live: LiveStream, attempt: u32) {
+    if live.is_stopped() {
+        return;
+    }
+    let delay = reconnect_delay(attempt);
+    let _ = live.set_connection.try_set(LiveConnection::Reconnecting {
+        attempt,
+        retry_in_secs: delay.as_secs(),
+    });
+    // Keep the numbers moving while the stream stays down
+    if attempt >= POLL_AFTER_FAILURES {
+        leptos::task::spawn_local(async move {
+            if let Ok(Some(update)) = crate::api::get_live_metrics().await {
+                let _ = live.set_latest.try_set(Some(update));
+            }
+        });
+    }
+    set_timeout(move || connect(live, attempt), delay);
+}
@@ -1,2 +1,4 @@
 pub mod app_state;
 pub mod auth;
+pub mod live_metrics;
+pub mod system_alerts;
@@ -0,0 +1,80 @@
+use crate::api::dashboard_client::AlertItem;
+use crate::api::get_system_alerts;
+use crate::contexts::app_state::{use_app_state, Notification, NotificationType};
+use leptos::prelude::*;
+use std::collections::HashMap;
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Seconds between checks for new system alerts
+const ALERT_POLL_SECS: u64 = 60;
+
+fn alert_notification(alert: &AlertItem) -> Notification {
+    let notification_type = match alert.severity.as_str() {
+        "critical" | "error" => NotificationType::Error,
+        "warning" => NotificationType::Warning,
+        _ => NotificationType::Info,
+    };
+    let mut severity = alert.severity.clone();
+    if let Some(first) = severity.get_mut(0..1) {
+        first.make_ascii_uppercase();
+    }
+    let title = match &alert.service {
+        Some(service) => format!("{} alert: {}", severity, service),
+        None => format!("{} alert", severity),
+    };
+    Notification {
+        id: Uuid::new_v4(),
+        title,
+        message: alert.message.clone(),
+        notification_type,
+        timestamp: chrono::DateTime::parse_from_rfc3339(&alert.timestamp)
+            .map(|timestamp| timestamp.with_timezone(&chrono::Utc))
+            .unwrap_or_else(|_| chrono::Utc::now()),
+        read: false,
+        dismissed: false,
+    }
+}
+
+/// Raise a notification for each system alert nobody has acknowledged or
+/// snoozed, and mark it read once an admin does. A snoozed alert that comes
+/// back is raised again.
+pub fn watch_system_alerts() {
+    let app_state = use_app_state();
+    // Alert id -> the notification raised for it
+    let raised = StoredValue::new(HashMap::<String, Uuid>::new());
+
+    let check = move || {
+        let app_state = app_state.clone();
+        leptos::task::spawn_local(async move {
+            // Signed out, or the backend is down; try again next time
+            let Ok(alerts) = get_system_alerts().await else {
+                return;
+            };
+            raised.update_value(|raised| {
+                raised.retain(|alert_id, notification_id| {
+                    let active = alerts
+                        .iter()
+                        .any(|alert| alert.is_active() && &alert.alert.id == alert_id);
+                    if !active {
+                        app_state.mark_notification_read(*notification_id);
+                    }
+                    active
+                });
+                for alert in alerts.iter().filter(|alert| alert.is_active()) {
+                    if raised.contains_key(&alert.alert.id) {
+                        continue;
+                    }
+                    let notification = alert_notification(&alert.alert);
+                    raised.insert(alert.alert.id.clone(), notification.id);
+                    app_state.add_notification(notification);
+                }
+            });
+        });
+    };
+
+    Effect::new(move |_| {
+        check();
+        set_interval(check.clone(), Duration::from_secs(ALERT_POLL_SECS));
+    });
+}
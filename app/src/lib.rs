@@ -5,19 +5,25 @@ pub mod components;
 pub mod contexts;
 pub mod pages;
 pub mod server;
+pub mod utils;
 
 use components::auth::AuthGuard;
 use components::layout::{AppLayout, ThemeProvider};
 use components::ui::*;
 use contexts::app_state::provide_app_state;
 use contexts::auth::SSRAuthProvider;
+use contexts::system_alerts::watch_system_alerts;
 use leptos::prelude::*;
 use leptos_router::{components::*, path};
-use pages::{DashboardContent, GroupsPage, LoginPage, MembersPage, Settings};
+use pages::{
+    ChamaDetailPage, DashboardContent, GroupsPage, LoginPage, MemberDetailPage, MembersPage,
+    Settings,
+};
 
 #[component]
 pub fn App() -> impl IntoView {
     provide_app_state();
+    watch_system_alerts();
 
     view! {
         <SSRAuthProvider>
@@ -26,11 +32,23 @@ pub fn App() -> impl IntoView {
                     <Route path=path!("/") view=LayoutedLogin/>
                     <Route path=path!("/login") view=LayoutedLogin/>
                     <Route path=path!("/dashboard") view=LayoutedDashboard/>
+                    <Route path=path!("/user-analytics") view=LayoutedUserAnalytics/>
+                    <Route path=path!("/financial-analytics") view=LayoutedFinancialAnalytics/>
+                    <Route path=path!("/report-builder") view=LayoutedReportBuilder/>
+                    <Route path=path!("/export-center") view=LayoutedExportCenter/>
                     <Route path=path!("/settings") view=LayoutedSettings/>
                     <Route path=path!("/members") view=LayoutedMembers/>
+                    <Route path=path!("/members/:id") view=LayoutedMemberDetail/>
                     <Route path=path!("/groups") view=LayoutedGroups/>
+                    <Route path=path!("/groups/:id") view=LayoutedChamaDetail/>
                     <Route path=path!("/shares") view=LayoutedShares/>
-                    <Route path=path!("/health") view=HealthPage/>
+                    <Route path=path!("/loans") view=LayoutedLoans/>
+                    <Route path=path!("/dividends") view=LayoutedDividends/>
+                    <Route path=path!("/fx") view=LayoutedFx/>
+                    <Route path=path!("/swaps") view=LayoutedSwaps/>
+                    <Route path=path!("/reconciliation") view=LayoutedReconciliation/>
+                    <Route path=path!("/withdrawal-policy") view=LayoutedWithdrawalPolicy/>
+                    <Route path=path!("/health") view=LayoutedHealth/>
                 </Routes>
             </Router>
             <ToastContainer/>
@@ -136,6 +154,39 @@ fn LayoutedMembers() -> impl IntoView {
     }
 }
 
+#[component]
+fn LayoutedMemberDetail() -> impl IntoView {
+    view! {
+        <html>
+            <head>
+                <title>"Member - Bitsacco Admin"</title>
+                <meta charset="utf-8"/>
+                <meta name="viewport" content="width=device-width, initial-scale=1"/>
+                <link rel="icon" type="image/svg+xml" href="/assets/favicon.svg"/>
+                <link rel="apple-touch-icon" href="/assets/apple-touch-icon.png"/>
+                <link rel="manifest" href="/assets/manifest.json"/>
+                <meta name="theme-color" content="#14b8a6"/>
+                <link rel="stylesheet" href="/assets/styles.css"/>
+                <style>
+                    r#"
+                    * { box-sizing: border-box; margin: 0; padding: 0; }
+                    body { font-family: 'Nunito', system-ui, sans-serif; }
+                    "#
+                </style>
+            </head>
+            <body>
+                <ThemeProvider>
+                    <AuthGuard>
+                        <AppLayout>
+                            <MemberDetailPage/>
+                        </AppLayout>
+                    </AuthGuard>
+                </ThemeProvider>
+            </body>
+        </html>
+    }
+}
+
 #[component]
 fn LayoutedGroups() -> impl IntoView {
     view! {
@@ -169,6 +220,39 @@ fn LayoutedGroups() -> impl IntoView {
     }
 }
 
+#[component]
+fn LayoutedChamaDetail() -> impl IntoView {
+    view! {
+        <html>
+            <head>
+                <title>"Chama - Bitsacco Admin"</title>
+                <meta charset="utf-8"/>
+                <meta name="viewport" content="width=device-width, initial-scale=1"/>
+                <link rel="icon" type="image/svg+xml" href="/assets/favicon.svg"/>
+                <link rel="apple-touch-icon" href="/assets/apple-touch-icon.png"/>
+                <link rel="manifest" href="/assets/manifest.json"/>
+                <meta name="theme-color" content="#14b8a6"/>
+                <link rel="stylesheet" href="/assets/styles.css"/>
+                <style>
+                    r#"
+                    * { box-sizing: border-box; margin: 0; padding: 0; }
+                    body { font-family: 'Nunito', system-ui, sans-serif; }
+                    "#
+                </style>
+            </head>
+            <body>
+                <ThemeProvider>
+                    <AuthGuard>
+                        <AppLayout>
+                            <ChamaDetailPage/>
+                        </AppLayout>
+                    </AuthGuard>
+                </ThemeProvider>
+            </body>
+        </html>
+    }
+}
+
 #[component]
 fn LayoutedShares() -> impl IntoView {
     view! {
@@ -202,6 +286,369 @@ fn LayoutedShares() -> impl IntoView {
     }
 }
 
+#[component]
+fn LayoutedLoans() -> impl IntoView {
+    view! {
+        <html>
+            <head>
+                <title>"Loans - Bitsacco Admin"</title>
+                <meta charset="utf-8"/>
+                <meta name="viewport" content="width=device-width, initial-scale=1"/>
+                <link rel="icon" type="image/svg+xml" href="/assets/favicon.svg"/>
+                <link rel="apple-touch-icon" href="/assets/apple-touch-icon.png"/>
+                <link rel="manifest" href="/assets/manifest.json"/>
+                <meta name="theme-color" content="#14b8a6"/>
+                <link rel="stylesheet" href="/assets/styles.css"/>
+                <style>
+                    r#"
+                    * { box-sizing: border-box; margin: 0; padding: 0; }
+                    body { font-family: 'Nunito', system-ui, sans-serif; }
+                    "#
+                </style>
+            </head>
+            <body>
+                <ThemeProvider>
+                    <AuthGuard>
+                        <AppLayout>
+                            <pages::loans::LoansPage/>
+                        </AppLayout>
+                    </AuthGuard>
+                </ThemeProvider>
+            </body>
+        </html>
+    }
+}
+
+#[component]
+fn LayoutedDividends() -> impl IntoView {
+    view! {
+        <html>
+            <head>
+                <title>"Dividends - Bitsacco Admin"</title>
+                <meta charset="utf-8"/>
+                <meta name="viewport" content="width=device-width, initial-scale=1"/>
+                <link rel="icon" type="image/svg+xml" href="/assets/favicon.svg"/>
+                <link rel="apple-touch-icon" href="/assets/apple-touch-icon.png"/>
+                <link rel="manifest" href="/assets/manifest.json"/>
+                <meta name="theme-color" content="#14b8a6"/>
+                <link rel="stylesheet" href="/assets/styles.css"/>
+                <style>
+                    r#"
+                    * { box-sizing: border-box; margin: 0; padding: 0; }
+                    body { font-family: 'Nunito', system-ui, sans-serif; }
+                    "#
+                </style>
+            </head>
+            <body>
+                <ThemeProvider>
+                    <AuthGuard>
+                        <AppLayout>
+                            <pages::dividends::DividendsPage/>
+                        </AppLayout>
+                    </AuthGuard>
+                </ThemeProvider>
+            </body>
+        </html>
+    }
+}
+
+#[component]
+fn LayoutedFx() -> impl IntoView {
+    view! {
+        <html>
+            <head>
+                <title>"Exchange Rates - Bitsacco Admin"</title>
+                <meta charset="utf-8"/>
+                <meta name="viewport" content="width=device-width, initial-scale=1"/>
+                <link rel="icon" type="image/svg+xml" href="/assets/favicon.svg"/>
+                <link rel="apple-touch-icon" href="/assets/apple-touch-icon.png"/>
+                <link rel="manifest" href="/assets/manifest.json"/>
+                <meta name="theme-color" content="#14b8a6"/>
+                <link rel="stylesheet" href="/assets/styles.css"/>
+                <style>
+                    r#"
+                    * { box-sizing: border-box; margin: 0; padding: 0; }
+                    body { font-family: 'Nunito', system-ui, sans-serif; }
+                    "#
+                </style>
+            </head>
+            <body>
+                <ThemeProvider>
+                    <AuthGuard>
+                        <AppLayout>
+                            <pages::fx::FxPage/>
+                        </AppLayout>
+                    </AuthGuard>
+                </ThemeProvider>
+            </body>
+        </html>
+    }
+}
+
+#[component]
+fn LayoutedSwaps() -> impl IntoView {
+    view! {
+        <html>
+            <head>
+                <title>"Swaps - Bitsacco Admin"</title>
+                <meta charset="utf-8"/>
+                <meta name="viewport" content="width=device-width, initial-scale=1"/>
+                <link rel="icon" type="image/svg+xml" href="/assets/favicon.svg"/>
+                <link rel="apple-touch-icon" href="/assets/apple-touch-icon.png"/>
+                <link rel="manifest" href="/assets/manifest.json"/>
+                <meta name="theme-color" content="#14b8a6"/>
+                <link rel="stylesheet" href="/assets/styles.css"/>
+                <style>
+                    r#"
+                    * { box-sizing: border-box; margin: 0; padding: 0; }
+                    body { font-family: 'Nunito', system-ui, sans-serif; }
+                    "#
+                </style>
+            </head>
+            <body>
+                <ThemeProvider>
+                    <AuthGuard>
+                        <AppLayout>
+                            <pages::swaps::SwapsPage/>
+                        </AppLayout>
+                    </AuthGuard>
+                </ThemeProvider>
+            </body>
+        </html>
+    }
+}
+
+#[component]
+fn LayoutedReconciliation() -> impl IntoView {
+    view! {
+        <html>
+            <head>
+                <title>"Reconciliation - Bitsacco Admin"</title>
+                <meta charset="utf-8"/>
+                <meta name="viewport" content="width=device-width, initial-scale=1"/>
+                <link rel="icon" type="image/svg+xml" href="/assets/favicon.svg"/>
+                <link rel="apple-touch-icon" href="/assets/apple-touch-icon.png"/>
+                <link rel="manifest" href="/assets/manifest.json"/>
+                <meta name="theme-color" content="#14b8a6"/>
+                <link rel="stylesheet" href="/assets/styles.css"/>
+                <style>
+                    r#"
+                    * { box-sizing: border-box; margin: 0; padding: 0; }
+                    body { font-family: 'Nunito', system-ui, sans-serif; }
+                    "#
+                </style>
+            </head>
+            <body>
+                <ThemeProvider>
+                    <AuthGuard>
+                        <AppLayout>
+                            <pages::reconciliation::ReconciliationPage/>
+                        </AppLayout>
+                    </AuthGuard>
+                </ThemeProvider>
+            </body>
+        </html>
+    }
+}
+
+#[component]
+fn LayoutedWithdrawalPolicy() -> impl IntoView {
+    view! {
+        <html>
+            <head>
+                <title>"Withdrawal Limits - Bitsacco Admin"</title>
+                <meta charset="utf-8"/>
+                <meta name="viewport" content="width=device-width, initial-scale=1"/>
+                <link rel="icon" type="image/svg+xml" href="/assets/favicon.svg"/>
+                <link rel="apple-touch-icon" href="/assets/apple-touch-icon.png"/>
+                <link rel="manifest" href="/assets/manifest.json"/>
+                <meta name="theme-color" content="#14b8a6"/>
+                <link rel="stylesheet" href="/assets/styles.css"/>
+                <style>
+                    r#"
+                    * { box-sizing: border-box; margin: 0; padding: 0; }
+                    body { font-family: 'Nunito', system-ui, sans-serif; }
+                    "#
+                </style>
+            </head>
+            <body>
+                <ThemeProvider>
+                    <AuthGuard>
+                        <AppLayout>
+                            <pages::withdrawal_policy::WithdrawalPolicyPage/>
+                        </AppLayout>
+                    </AuthGuard>
+                </ThemeProvider>
+            </body>
+        </html>
+    }
+}
+
+#[component]
+fn LayoutedUserAnalytics() -> impl IntoView {
+    view! {
+        <html>
+            <head>
+                <title>"User Analytics - Bitsacco Admin"</title>
+                <meta charset="utf-8"/>
+                <meta name="viewport" content="width=device-width, initial-scale=1"/>
+                <link rel="icon" type="image/svg+xml" href="/assets/favicon.svg"/>
+                <link rel="apple-touch-icon" href="/assets/apple-touch-icon.png"/>
+                <link rel="manifest" href="/assets/manifest.json"/>
+                <meta name="theme-color" content="#14b8a6"/>
+                <link rel="stylesheet" href="/assets/styles.css"/>
+                <style>
+                    r#"
+                    * { box-sizing: border-box; margin: 0; padding: 0; }
+                    body { font-family: 'Nunito', system-ui, sans-serif; }
+                    "#
+                </style>
+            </head>
+            <body>
+                <ThemeProvider>
+                    <AuthGuard>
+                        <AppLayout>
+                            <pages::user_analytics::UserAnalyticsPage/>
+                        </AppLayout>
+                    </AuthGuard>
+                </ThemeProvider>
+            </body>
+        </html>
+    }
+}
+
+#[component]
+fn LayoutedFinancialAnalytics() -> impl IntoView {
+    view! {
+        <html>
+            <head>
+                <title>"Financial Analytics - Bitsacco Admin"</title>
+                <meta charset="utf-8"/>
+                <meta name="viewport" content="width=device-width, initial-scale=1"/>
+                <link rel="icon" type="image/svg+xml" href="/assets/favicon.svg"/>
+                <link rel="apple-touch-icon" href="/assets/apple-touch-icon.png"/>
+                <link rel="manifest" href="/assets/manifest.json"/>
+                <meta name="theme-color" content="#14b8a6"/>
+                <link rel="stylesheet" href="/assets/styles.css"/>
+                <style>
+                    r#"
+                    * { box-sizing: border-box; margin: 0; padding: 0; }
+                    body { font-family: 'Nunito', system-ui, sans-serif; }
+                    "#
+                </style>
+            </head>
+            <body>
+                <ThemeProvider>
+                    <AuthGuard>
+                        <AppLayout>
+                            <pages::financial_analytics::FinancialAnalyticsPage/>
+                        </AppLayout>
+                    </AuthGuard>
+                </ThemeProvider>
+            </body>
+        </html>
+    }
+}
+
+#[component]
+fn LayoutedHealth() -> impl IntoView {
+    view! {
+        <html>
+            <head>
+                <title>"System Health - Bitsacco Admin"</title>
+                <meta charset="utf-8"/>
+                <meta name="viewport" content="width=device-width, initial-scale=1"/>
+                <link rel="icon" type="image/svg+xml" href="/assets/favicon.svg"/>
+                <link rel="apple-touch-icon" href="/assets/apple-touch-icon.png"/>
+                <link rel="manifest" href="/assets/manifest.json"/>
+                <meta name="theme-color" content="#14b8a6"/>
+                <link rel="stylesheet" href="/assets/styles.css"/>
+                <style>
+                    r#"
+                    * { box-sizing: border-box; margin: 0; padding: 0; }
+                    body { font-family: 'Nunito', system-ui, sans-serif; }
+                    "#
+                </style>
+            </head>
+            <body>
+                <ThemeProvider>
+                    <AuthGuard>
+                        <AppLayout>
+                            <pages::health::SystemHealthPage/>
+                        </AppLayout>
+                    </AuthGuard>
+                </ThemeProvider>
+            </body>
+        </html>
+    }
+}
+
+#[component]
+fn LayoutedReportBuilder() -> impl IntoView {
+    view! {
+        <html>
+            <head>
+                <title>"Report Builder - Bitsacco Admin"</title>
+                <meta charset="utf-8"/>
+                <meta name="viewport" content="width=device-width, initial-scale=1"/>
+                <link rel="icon" type="image/svg+xml" href="/assets/favicon.svg"/>
+                <link rel="apple-touch-icon" href="/assets/apple-touch-icon.png"/>
+                <link rel="manifest" href="/assets/manifest.json"/>
+                <meta name="theme-color" content="#14b8a6"/>
+                <link rel="stylesheet" href="/assets/styles.css"/>
+                <style>
+                    r#"
+                    * { box-sizing: border-box; margin: 0; padding: 0; }
+                    body { font-family: 'Nunito', system-ui, sans-serif; }
+                    "#
+                </style>
+            </head>
+            <body>
+                <ThemeProvider>
+                    <AuthGuard>
+                        <AppLayout>
+                            <pages::report_builder::ReportBuilderPage/>
+                        </AppLayout>
+                    </AuthGuard>
+                </ThemeProvider>
+            </body>
+        </html>
+    }
+}
+
+#[component]
+fn LayoutedExportCenter() -> impl IntoView {
+    view! {
+        <html>
+            <head>
+                <title>"Export Center - Bitsacco Admin"</title>
+                <meta charset="utf-8"/>
+                <meta name="viewport" content="width=device-width, initial-scale=1"/>
+                <link rel="icon" type="image/svg+xml" href="/assets/favicon.svg"/>
+                <link rel="apple-touch-icon" href="/assets/apple-touch-icon.png"/>
+                <link rel="manifest" href="/assets/manifest.json"/>
+                <meta name="theme-color" content="#14b8a6"/>
+                <link rel="stylesheet" href="/assets/styles.css"/>
+                <style>
+                    r#"
+                    * { box-sizing: border-box; margin: 0; padding: 0; }
+                    body { font-family: 'Nunito', system-ui, sans-serif; }
+                    "#
+                </style>
+            </head>
+            <body>
+                <ThemeProvider>
+                    <AuthGuard>
+                        <AppLayout>
+                            <pages::export_center::ExportCenterPage/>
+                        </AppLayout>
+                    </AuthGuard>
+                </ThemeProvider>
+            </body>
+        </html>
+    }
+}
+
 #[component]
 fn LayoutedLogin() -> impl IntoView {
     view! {
@@ -248,78 +695,3 @@ fn NotFoundPage() -> impl IntoView {
         </div>
     }
 }
-
-#[component]
-fn HealthPage() -> impl IntoView {
-    view! {
-        <div class="bg-white shadow rounded-lg p-6">
-            <h1 class="text-2xl font-bold text-green-600 mb-6">"System Health"</h1>
-            <div class="grid grid-cols-1 md:grid-cols-2 lg:grid-cols-3 gap-4">
-                <HealthCard
-                    title="Server"
-                    status="Online"
-                    color="green"
-                    details="Running smoothly"
-                />
-                <HealthCard
-                    title="Database"
-                    status="Connected"
-                    color="green"
-                    details="PostgreSQL 16"
-                />
-                <HealthCard
-                    title="Cache"
-                    status="Ready"
-                    color="green"
-                    details="Redis active"
-                />
-                <HealthCard
-                    title="Authentication"
-                    status="Active"
-                    color="green"
-                    details="Keycloak integration"
-                />
-                <HealthCard
-                    title="API"
-                    status="Operational"
-                    color="green"
-                    details="All endpoints responding"
-                />
-                <HealthCard
-                    title="Storage"
-                    status="Available"
-                    color="green"
-                    details="85% capacity"
-                />
-            </div>
-        </div>
-    }
-}
-
-#[component]
-fn HealthCard(
-    title: &'static str,
-    status: &'static str,
-    color: &'static str,
-    details: &'static str,
-) -> impl IntoView {
-    let (bg_color, text_color, dot_color) = match color {
-        "green" => ("bg-green-50", "text-green-800", "bg-green-500"),
-        "yellow" => ("bg-yellow-50", "text-yellow-800", "bg-yellow-500"),
-        "red" => ("bg-red-50", "text-red-800", "bg-red-500"),
-        _ => ("bg-gray-50", "text-gray-800", "bg-gray-500"),
-    };
-
-    view! {
-        <div class={format!("p-4 rounded-lg {}", bg_color)}>
-            <div class="flex items-center">
-                <div class={format!("w-3 h-3 rounded-full mr-3 {}", dot_color)}></div>
-                <h3 class={format!("font-medium {}", text_color)}>{title}</h3>
-            </div>
-            <div class="mt-2">
-                <p class={format!("text-sm font-semibold {}", text_color)}>{status}</p>
-                <p class={format!("text-xs {}", text_color.replace("800", "600"))}>{details}</p>
-            </div>
-        </div>
-    }
-}
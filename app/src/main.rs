@@ -46,6 +46,22 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         // API info endpoint
         .route("/api/info", axum::routing::get(api_info))
         .route("/api/health", axum::routing::get(health_check))
+        // Live metrics and alerts (SSE, or WebSocket for clients that prefer it)
+        .route("/api/live", axum::routing::get(app::server::live::live_sse))
+        .route(
+            "/api/live/ws",
+            axum::routing::get(app::server::live::live_ws),
+        )
+        // Member statement downloads (CSV and PDF)
+        .route(
+            "/api/members/{member_id}/statement",
+            axum::routing::get(app::server::statements::member_statement_download),
+        )
+        // Finished dashboard exports, served from signed, expiring links
+        .route(
+            "/api/exports/{export_id}/download",
+            axum::routing::get(app::server::export::export_download),
+        )
         // Serve static files (needed for hot-reload assets)
         .nest_service(
             "/pkg",
@@ -63,6 +79,33 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         )
         .with_state(leptos_options);
 
+    // Feed the live metrics stream from whichever backend is configured
+    app::server::live::live_hub().spawn_aggregator(poll_interval("LIVE_POLL_SECONDS", 10));
+
+    // Settle Lightning invoices, on-chain deposits and M-Pesa swaps, keep exchange rates
+    // fresh and reconcile custody, in the background when the in-process backend is active
+    let api_config = app::api::ApiConfig::from_env();
+    if matches!(api_config.backend, app::api::Backend::Rust) {
+        let backend = app::api::backends::rust_backend(&api_config)?;
+        backend
+            .lightning
+            .spawn_settlement_poller(poll_interval("FMCD_POLL_SECONDS", 5));
+        backend
+            .onchain
+            .spawn_deposit_poller(poll_interval("ONCHAIN_POLL_SECONDS", 60));
+        backend
+            .swaps
+            .spawn_swap_poller(poll_interval("SWAPS_POLL_SECONDS", 15));
+        backend
+            .reconciliation
+            .spawn_reconciliation_poller(poll_interval("RECONCILIATION_POLL_SECONDS", 3600));
+        if backend.fx.has_sources() {
+            backend
+                .fx
+                .spawn_rate_poller(poll_interval("FX_POLL_SECONDS", 300));
+        }
+    }
+
     // Start server
     let listener = tokio::net::TcpListener::bind(&config.server_addr).await?;
     tracing::info!(
@@ -124,3 +167,13 @@ fn cors_layer() -> tower_http::cors::CorsLayer {
         ])
         .allow_credentials(true)
 }
+
+// Background poll interval from the environment, in seconds
+fn poll_interval(key: &str, default_seconds: u64) -> std::time::Duration {
+    let seconds = std::env::var(key)
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .filter(|seconds| *seconds > 0)
+        .unwrap_or(default_seconds);
+    std::time::Duration::from_secs(seconds)
+}
@@ -1,32 +1,78 @@
-use crate::api::get_dashboard_metrics;
-use crate::components::ui::Button;
+use crate::api::client::{AcknowledgeAlert, SnoozeAlert};
+use crate::api::dashboard_client::{DashboardTrends, ResponseMeta, SharesMetrics, TrendDataPoint};
+use crate::api::traits::alerts::SystemAlert;
+use crate::api::types::Money;
+use crate::api::{get_dashboard_metrics, get_system_alerts};
+use crate::components::ui::{Button, ChartKind, ChartPoint, TrendChart};
+use crate::contexts::live_metrics::{use_live_metrics, LiveConnection};
+use crate::utils::{format_ms, percent};
+use chrono::{NaiveDate, Utc};
 use leptos::prelude::*;
-use rust_decimal::prelude::ToPrimitive;
-use rust_decimal::Decimal;
+use leptos_router::hooks::use_query_map;
+use rust_decimal::{prelude::ToPrimitive, Decimal};
 use serde::{Deserialize, Serialize};
 
 // Dashboard data structures matching backend analytics
+// Metrics the backend can't supply are `None` and rendered as "n/a"
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DashboardMetrics {
+    /// Registered members, whether or not they hold shares
+    pub total_members: u64,
     pub shareholders: ShareholderSummary,
     pub market: MarketAnalytics,
-    pub offers: ShareOfferAnalytics,
+    pub offers: Option<ShareOfferAnalytics>,
     pub transactions: TransactionAnalytics,
+    pub trends: DashboardTrends,
+}
+
+/// A dashboard section's figures and how far they can be trusted. Nothing is
+/// ever filled in when the backend can't supply it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum SectionState<T> {
+    /// Fresh from the backend, with what it said about their source
+    Loaded { data: T, meta: Option<ResponseMeta> },
+    /// The backend is failing; these are the last figures it gave, `age_secs` old
+    Stale {
+        data: T,
+        meta: Option<ResponseMeta>,
+        age_secs: u64,
+        reason: String,
+    },
+    /// Nothing has loaded since the server started
+    Unavailable { reason: String },
+}
+
+impl<T> SectionState<T> {
+    pub fn data(&self) -> Option<&T> {
+        match self {
+            SectionState::Loaded { data, .. } | SectionState::Stale { data, .. } => Some(data),
+            SectionState::Unavailable { .. } => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DashboardSections {
+    /// Key metrics, trends and the financial overview, all from the overview endpoint
+    pub overview: SectionState<DashboardMetrics>,
+    /// Share ownership and trading, from financial analytics
+    pub shares: SectionState<SharesMetrics>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ShareholderSummary {
-    pub total_shareholders: u64,
-    pub member_shareholders: u64,
-    pub group_shareholders: u64,
-    pub active_shareholders: u64,
+    pub total_shareholders: Option<u64>,
+    pub member_shareholders: Option<u64>,
+    pub group_shareholders: Option<u64>,
+    pub active_shareholders: Option<u64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MarketAnalytics {
-    pub total_market_value_kes: Decimal, // Total value in KES
-    pub total_shares_in_circulation: Decimal,
-    pub share_price_kes: Decimal, // Fixed at 1000 KES per share
+    /// Shares in circulation at the latest offer price
+    pub total_market_value: Option<Money>,
+    pub total_shares_in_circulation: Option<Decimal>,
+    pub share_price: Option<Money>, // Price of the most recent priced offer
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -40,8 +86,8 @@ pub struct ShareOfferAnalytics {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TransactionAnalytics {
     pub total_transactions: u64,
-    pub total_transaction_value_kes: Decimal, // Total value in KES
-    pub average_transaction_size_kes: Decimal, // Average size in KES
+    pub total_transaction_value: Money,
+    pub average_transaction_size: Money,
 }
 
 // Enhanced data structures for dashboard display
@@ -69,6 +115,196 @@ pub enum TrendDirection {
     Stable,
 }
 
+impl ChangeIndicator {
+    /// Percentage change from `previous` to `current`; `None` when there is
+    /// nothing to compare against
+    pub fn between(previous: f64, current: f64, period: String) -> Option<Self> {
+        if previous == 0.0 {
+            return None;
+        }
+        let percentage = (current - previous) / previous.abs() * 100.0;
+        let direction = if percentage.abs() < 0.05 {
+            TrendDirection::Stable
+        } else if percentage > 0.0 {
+            TrendDirection::Up
+        } else {
+            TrendDirection::Down
+        };
+        Some(Self {
+            percentage: percentage.abs(),
+            direction,
+            period,
+        })
+    }
+}
+
+/// How far back the trend charts and change indicators look
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrendPeriod {
+    Week,
+    Month,
+    Quarter,
+    All,
+}
+
+impl TrendPeriod {
+    pub const ALL: [TrendPeriod; 4] = [
+        TrendPeriod::Week,
+        TrendPeriod::Month,
+        TrendPeriod::Quarter,
+        TrendPeriod::All,
+    ];
+
+    /// Period named by the `period` query parameter; 30 days when absent or unknown
+    pub fn from_query(value: Option<&str>) -> Self {
+        match value {
+            Some("7d") => TrendPeriod::Week,
+            Some("90d") => TrendPeriod::Quarter,
+            Some("all") => TrendPeriod::All,
+            _ => TrendPeriod::Month,
+        }
+    }
+
+    pub fn as_query(self) -> &'static str {
+        match self {
+            TrendPeriod::Week => "7d",
+            TrendPeriod::Month => "30d",
+            TrendPeriod::Quarter => "90d",
+            TrendPeriod::All => "all",
+        }
+    }
+
+    fn days(self) -> Option<i64> {
+        match self {
+            TrendPeriod::Week => Some(7),
+            TrendPeriod::Month => Some(30),
+            TrendPeriod::Quarter => Some(90),
+            TrendPeriod::All => None,
+        }
+    }
+}
+
+/// Whether a series counts something that accumulates (members) or happens
+/// per day (volume), which decides how a period's change is measured
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SeriesKind {
+    Level,
+    Flow,
+}
+
+/// Points with a readable date, oldest first
+fn dated_exact(points: &[TrendDataPoint]) -> Vec<(NaiveDate, Decimal)> {
+    let mut dated: Vec<(NaiveDate, Decimal)> = points
+        .iter()
+        .filter_map(|point| {
+            let date = NaiveDate::parse_from_str(point.date.get(..10)?, "%Y-%m-%d").ok()?;
+            Some((date, point.value))
+        })
+        .collect();
+    dated.sort_by_key(|(date, _)| *date);
+    dated
+}
+
+fn dated(points: &[TrendDataPoint]) -> Vec<(NaiveDate, f64)> {
+    dated_exact(points)
+        .into_iter()
+        .filter_map(|(date, value)| Some((date, value.to_f64()?)))
+        .collect()
+}
+
+/// Chart points for the period ending today
+pub fn chart_points(
+    points: &[TrendDataPoint],
+    period: TrendPeriod,
+    today: NaiveDate,
+) -> Vec<ChartPoint> {
+    period_points(points, period, today, None)
+}
+
+/// Chart points for the period ending today, labelled with `show`'s rendering
+/// of the exact value rather than the plotted approximation
+fn exact_chart_points(
+    points: &[TrendDataPoint],
+    period: TrendPeriod,
+    today: NaiveDate,
+    show: fn(Decimal) -> String,
+) -> Vec<ChartPoint> {
+    period_points(points, period, today, Some(show))
+}
+
+fn period_points(
+    points: &[TrendDataPoint],
+    period: TrendPeriod,
+    today: NaiveDate,
+    show: Option<fn(Decimal) -> String>,
+) -> Vec<ChartPoint> {
+    let since = period
+        .days()
+        .map(|days| today - chrono::Duration::days(days));
+    dated_exact(points)
+        .into_iter()
+        .filter(|(date, _)| since.is_none_or(|since| *date > since))
+        .filter_map(|(date, value)| {
+            Some(ChartPoint {
+                label: date.format("%b %-d").to_string(),
+                value: value.to_f64()?,
+                display: show.map(|show| show(value)),
+            })
+        })
+        .collect()
+}
+
+/// Change over the period ending today. Levels compare the latest value with
+/// the last one before the period; flows compare the period's total with the
+/// period before it.
+fn period_change(
+    points: &[TrendDataPoint],
+    kind: SeriesKind,
+    period: TrendPeriod,
+    today: NaiveDate,
+) -> Option<ChangeIndicator> {
+    let points = dated(points);
+    let (first_date, first_value) = *points.first()?;
+    let (_, latest) = *points.last()?;
+
+    let Some(days) = period.days() else {
+        // Flows have nothing before "all time" to compare with
+        return match kind {
+            SeriesKind::Level => ChangeIndicator::between(
+                first_value,
+                latest,
+                format!("since {}", first_date.format("%b %-d, %Y")),
+            ),
+            SeriesKind::Flow => None,
+        };
+    };
+    let since = today - chrono::Duration::days(days);
+    match kind {
+        SeriesKind::Level => {
+            let (_, before) = points.iter().rev().find(|(date, _)| *date <= since)?;
+            ChangeIndicator::between(*before, latest, format!("over the last {} days", days))
+        }
+        SeriesKind::Flow => {
+            let before_since = since - chrono::Duration::days(days);
+            let total = |from: NaiveDate, to: NaiveDate| -> f64 {
+                points
+                    .iter()
+                    .filter(|(date, _)| *date > from && *date <= to)
+                    .map(|(_, value)| value)
+                    .sum()
+            };
+            if first_date > since {
+                return None;
+            }
+            ChangeIndicator::between(
+                total(before_since, since),
+                total(since, today),
+                format!("vs the previous {} days", days),
+            )
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum ColorScheme {
     Blue,
@@ -82,26 +318,15 @@ pub enum ColorScheme {
 #[component]
 pub fn DashboardContent() -> impl IntoView {
     // Create a Resource that calls our server function (which now calls NestJS API)
+    let query = use_query_map();
+    let period = move || TrendPeriod::from_query(query.read().get("period").as_deref());
     let dashboard_resource = Resource::new(
         || (), // No dependencies, fetch immediately
         |_| async move {
             match get_dashboard_metrics().await {
-                Ok(response) => {
-                    if response.success {
-                        Ok(response.data.unwrap_or_else(|| {
-                            leptos::logging::warn!(
-                                "Dashboard: No data in response, using fallback"
-                            );
-                            create_fallback_metrics()
-                        }))
-                    } else {
-                        leptos::logging::error!(
-                            "Dashboard: Server function returned error: {:?}",
-                            response.errors
-                        );
-                        Err("Server function returned error".to_string())
-                    }
-                }
+                Ok(response) => response
+                    .data
+                    .ok_or_else(|| "The server returned no dashboard data".to_string()),
                 Err(e) => {
                     leptos::logging::error!("Dashboard: Server function failed: {:?}", e);
                     Err(format!("Server function error: {}", e))
@@ -112,21 +337,47 @@ pub fn DashboardContent() -> impl IntoView {
 
     view! {
         <div class="space-y-8">
+            <LiveMetricsBar />
+
             <Suspense fallback=move || view! { <DashboardSkeleton /> }>
                 {move || {
                     dashboard_resource.get().map(|result| {
                         match result {
-                            Ok(metrics) => {
-                                // Merged dashboard content - simplified single component
-                                view! {
-                                    <div class="space-y-8">
-                                        // Key Performance Indicators
-                                        <KeyMetricsSection metrics=metrics.clone() />
-
-                                        // Financial Overview
-                                        <FinancialOverviewSection metrics=metrics.clone() />
-                                    </div>
-                                }.into_any()
+                            Ok(sections) => {
+                                // Changing the period re-renders from the loaded metrics
+                                let period = period();
+                                let freshness = Freshness::of(&sections.overview);
+                                let stale = match &sections.overview {
+                                    SectionState::Stale { age_secs, reason, .. } => Some(format!(
+                                        "The backend is unavailable ({}). Showing figures from {}.",
+                                        reason,
+                                        format_age(*age_secs)
+                                    )),
+                                    _ => None,
+                                };
+                                match sections.overview {
+                                    SectionState::Loaded { data: metrics, .. }
+                                    | SectionState::Stale { data: metrics, .. } => view! {
+                                        <div class="space-y-8">
+                                            {stale.map(|message| view! {
+                                                <div class="bg-amber-50 border border-amber-200 rounded-lg p-4 text-sm text-amber-800" role="status">
+                                                    {message}
+                                                </div>
+                                            })}
+
+                                            // Key Performance Indicators
+                                            <KeyMetricsSection metrics=metrics.clone() period=period freshness=freshness.clone() />
+
+                                            <TrendsSection trends=metrics.trends.clone() period=period freshness=freshness.clone() />
+
+                                            // Financial Overview
+                                            <FinancialOverviewSection metrics=metrics freshness=freshness shares=sections.shares />
+                                        </div>
+                                    }.into_any(),
+                                    SectionState::Unavailable { reason } => {
+                                        view! { <ErrorState message=format!("Dashboard figures are unavailable: {}", reason) /> }.into_any()
+                                    }
+                                }
                             },
                             Err(error_msg) => {
                                 leptos::logging::error!("Dashboard error: {}", error_msg);
@@ -136,101 +387,375 @@ pub fn DashboardContent() -> impl IntoView {
                     })
                 }}
             </Suspense>
+
+            <SystemAlertsPanel />
         </div>
     }
 }
 
-// Helper function to create fallback metrics if API fails
-fn create_fallback_metrics() -> DashboardMetrics {
-    DashboardMetrics {
-        shareholders: ShareholderSummary {
-            total_shareholders: 150,
-            member_shareholders: 120,
-            group_shareholders: 30,
-            active_shareholders: 145,
-        },
-        market: MarketAnalytics {
-            total_market_value_kes: rust_decimal::Decimal::from(1500000),
-            total_shares_in_circulation: rust_decimal::Decimal::from(75000),
-            share_price_kes: rust_decimal::Decimal::from(1000),
-        },
-        offers: ShareOfferAnalytics {
-            total_offers: 25,
-            active_offers: 8,
-            completed_offers: 17,
-            average_completion_rate: 68.0,
+const ALERTS_REFRESH_SECS: u64 = 30;
+
+/// Snooze lengths offered on each alert, in minutes
+const SNOOZE_OPTIONS: [(u32, &str); 3] = [(60, "1 h"), (240, "4 h"), (1_440, "1 day")];
+
+fn format_alert_time(value: &str) -> String {
+    chrono::DateTime::parse_from_rfc3339(value)
+        .map(|time| time.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_else(|_| value.to_string())
+}
+
+fn severity_badge_class(severity: &str) -> &'static str {
+    match severity {
+        "critical" => "bg-red-100 text-red-800",
+        "error" => "bg-orange-100 text-orange-800",
+        "warning" => "bg-yellow-100 text-yellow-800",
+        _ => "bg-gray-100 text-gray-700",
+    }
+}
+
+// Critical alerts from the overview, with any admin's acknowledgement or snooze
+#[component]
+fn SystemAlertsPanel() -> impl IntoView {
+    let acknowledge_action = ServerAction::<AcknowledgeAlert>::new();
+    let snooze_action = ServerAction::<SnoozeAlert>::new();
+    let (refresh_tick, set_refresh_tick) = signal(0u32);
+
+    // Pick up other admins' acknowledgements without a reload
+    Effect::new(move |_| {
+        set_interval(
+            move || set_refresh_tick.update(|tick| *tick += 1),
+            std::time::Duration::from_secs(ALERTS_REFRESH_SECS),
+        );
+    });
+
+    let alerts = Resource::new(
+        move || {
+            (
+                acknowledge_action.version().get(),
+                snooze_action.version().get(),
+                refresh_tick.get(),
+            )
         },
-        transactions: TransactionAnalytics {
-            total_transactions: 1250,
-            total_transaction_value_kes: rust_decimal::Decimal::from(750000),
-            average_transaction_size_kes: rust_decimal::Decimal::from(600),
+        |_| get_system_alerts(),
+    );
+
+    let action_error = move || {
+        [
+            acknowledge_action.value().get().and_then(|r| r.err()),
+            snooze_action.value().get().and_then(|r| r.err()),
+        ]
+        .into_iter()
+        .flatten()
+        .next()
+        .map(|e| e.to_string())
+    };
+
+    view! {
+        <section id="system-alerts" aria-labelledby="alerts-heading">
+            <div class="bg-white rounded-xl shadow-sm border p-6 space-y-4">
+                <h3 id="alerts-heading" class="text-lg font-semibold text-gray-900">"System Alerts"</h3>
+
+                {move || action_error().map(|message| view! {
+                    <div class="bg-red-50 border border-red-200 rounded-lg p-4 text-sm text-red-700" role="alert">
+                        {message}
+                    </div>
+                })}
+
+                <Transition fallback=move || view! { <div class="animate-pulse h-24 bg-gray-100 rounded-lg"></div> }>
+                    {move || alerts.get().map(|result| match result {
+                        Ok(alerts) if alerts.is_empty() => view! {
+                            <p class="text-sm text-gray-500">"No critical alerts."</p>
+                        }.into_any(),
+                        Ok(alerts) => view! {
+                            <ul class="divide-y divide-gray-100">
+                                {alerts.into_iter().map(|alert| view! {
+                                    <SystemAlertRow alert=alert acknowledge_action=acknowledge_action snooze_action=snooze_action />
+                                }).collect_view()}
+                            </ul>
+                        }.into_any(),
+                        Err(e) => view! {
+                            <div class="bg-red-50 border border-red-200 rounded-lg p-4 text-sm text-red-700" role="alert">
+                                {format!("Alerts are unavailable: {}", e)}
+                            </div>
+                        }.into_any(),
+                    })}
+                </Transition>
+            </div>
+        </section>
+    }
+}
+
+#[component]
+fn SystemAlertRow(
+    alert: SystemAlert,
+    acknowledge_action: ServerAction<AcknowledgeAlert>,
+    snooze_action: ServerAction<SnoozeAlert>,
+) -> impl IntoView {
+    let active = alert.is_active();
+    let SystemAlert {
+        alert,
+        acknowledgement,
+    } = alert;
+    let badge_class = format!(
+        "inline-flex px-2 py-0.5 rounded-full text-xs font-medium {}",
+        severity_badge_class(&alert.severity)
+    );
+    let handled = acknowledgement.map(|ack| {
+        let by: String = ack.acknowledged_by.to_string().chars().take(8).collect();
+        match ack.snoozed_until {
+            Some(until) => format!("Snoozed by {} until {}", by, until.format("%Y-%m-%d %H:%M")),
+            None => format!(
+                "Acknowledged by {} at {}",
+                by,
+                ack.acknowledged_at.format("%Y-%m-%d %H:%M")
+            ),
+        }
+    });
+    let snooze_forms = SNOOZE_OPTIONS
+        .into_iter()
+        .map(|(minutes, label)| {
+            let alert_id = alert.id.clone();
+            view! {
+                <ActionForm action=snooze_action>
+                    <input type="hidden" name="alert_id" value=alert_id/>
+                    <input type="hidden" name="minutes" value=minutes.to_string()/>
+                    <button type="submit" class="border border-gray-300 hover:bg-gray-50 text-gray-700 text-xs font-medium py-1.5 px-3 rounded-md">
+                        {format!("Snooze {}", label)}
+                    </button>
+                </ActionForm>
+            }
+        })
+        .collect_view();
+    let alert_id = alert.id.clone();
+
+    view! {
+        <li class=if active { "py-3 flex items-start justify-between gap-4" } else { "py-3 flex items-start justify-between gap-4 opacity-60" }>
+            <div class="space-y-1 min-w-0">
+                <div class="flex items-center gap-2 text-sm">
+                    <span class=badge_class>{alert.severity.clone()}</span>
+                    {alert.service.clone().map(|service| view! {
+                        <span class="font-medium text-gray-900">{service}</span>
+                    })}
+                    <span class="text-xs text-gray-500">{format_alert_time(&alert.timestamp)}</span>
+                </div>
+                <p class="text-sm text-gray-700">{alert.message.clone()}</p>
+                {handled.map(|text| view! { <p class="text-xs text-gray-500">{text}</p> })}
+            </div>
+            {active.then(|| view! {
+                <div class="flex items-center gap-2 flex-shrink-0">
+                    <ActionForm action=acknowledge_action>
+                        <input type="hidden" name="alert_id" value=alert_id/>
+                        <button type="submit" class="bg-indigo-600 hover:bg-indigo-700 text-white text-xs font-medium py-1.5 px-3 rounded-md">
+                            "Acknowledge"
+                        </button>
+                    </ActionForm>
+                    {snooze_forms}
+                </div>
+            })}
+        </li>
+    }
+}
+
+/// "just now", "12 min ago", "3 h ago" or "2 days ago"
+fn format_age(age_secs: u64) -> String {
+    match age_secs {
+        0..60 => "just now".to_string(),
+        60..3_600 => format!("{} min ago", age_secs / 60),
+        3_600..86_400 => format!("{} h ago", age_secs / 3_600),
+        _ => match age_secs / 86_400 {
+            1 => "1 day ago".to_string(),
+            days => format!("{} days ago", days),
         },
     }
 }
 
+/// What a section's badge says about where its figures came from and how old they are
+#[derive(Debug, Clone)]
+struct Freshness {
+    label: String,
+    detail: Option<String>,
+    class: &'static str,
+}
+
+impl Freshness {
+    fn of<T>(state: &SectionState<T>) -> Self {
+        // The backend reports how it produced the figures and, when cached, their age
+        let source = |meta: &Option<ResponseMeta>| {
+            meta.as_ref().map(|meta| {
+                let source = match meta.data_source.as_str() {
+                    "realtime" => "Realtime",
+                    "aggregated" => "Aggregated",
+                    "cached" => "Cached",
+                    other => other,
+                };
+                (source.to_string(), u64::from(meta.cache_age))
+            })
+        };
+        match state {
+            SectionState::Loaded { meta, .. } => match source(meta) {
+                Some((source, 0)) => Freshness {
+                    label: format!("{} · updated just now", source),
+                    detail: None,
+                    class: "bg-green-50 text-green-700",
+                },
+                Some((source, age)) => Freshness {
+                    label: format!("{} · updated {}", source, format_age(age)),
+                    detail: None,
+                    class: "bg-gray-100 text-gray-700",
+                },
+                None => Freshness {
+                    label: "Updated just now".to_string(),
+                    detail: Some(
+                        "The backend did not say how these figures were produced".to_string(),
+                    ),
+                    class: "bg-gray-100 text-gray-700",
+                },
+            },
+            SectionState::Stale {
+                meta,
+                age_secs,
+                reason,
+                ..
+            } => Freshness {
+                label: match source(meta) {
+                    Some((source, _)) => format!("Stale · {} · {}", source, format_age(*age_secs)),
+                    None => format!("Stale · {}", format_age(*age_secs)),
+                },
+                detail: Some(reason.clone()),
+                class: "bg-amber-50 text-amber-800",
+            },
+            SectionState::Unavailable { reason } => Freshness {
+                label: "Unavailable".to_string(),
+                detail: Some(reason.clone()),
+                class: "bg-red-50 text-red-700",
+            },
+        }
+    }
+}
+
+#[component]
+fn FreshnessBadge(freshness: Freshness) -> impl IntoView {
+    view! {
+        <span
+            class=format!("inline-flex items-center px-2 py-0.5 rounded-full text-xs font-medium {}", freshness.class)
+            title=freshness.detail
+        >
+            {freshness.label}
+        </span>
+    }
+}
+
 // Key metrics overview with large cards
+/// Current activity from the live stream, updating without a reload
+#[component]
+fn LiveMetricsBar() -> impl IntoView {
+    let live = use_live_metrics();
+    let status = move || match live.connection.get() {
+        LiveConnection::Live => ("bg-green-500", "Live".to_string()),
+        LiveConnection::Connecting => ("bg-gray-400", "Connecting…".to_string()),
+        LiveConnection::Reconnecting { retry_in_secs, .. } => (
+            "bg-yellow-500",
+            format!("Reconnecting in {}s", retry_in_secs),
+        ),
+    };
+
+    view! {
+        <section aria-label="Live metrics" class="bg-white shadow rounded-lg px-6 py-4">
+            <div class="flex flex-wrap items-center gap-x-8 gap-y-3 text-sm">
+                <span class="inline-flex items-center text-gray-700 font-medium">
+                    <span class=move || format!("w-2.5 h-2.5 rounded-full mr-2 {}", status().0)></span>
+                    {move || status().1}
+                </span>
+                {move || match live.latest.get() {
+                    None => view! { <span class="text-gray-500">"Waiting for live metrics…"</span> }.into_any(),
+                    Some(update) => {
+                        let metrics = update.metrics;
+                        let alerts = update.alerts.unwrap_or_default();
+                        let readings = vec![
+                            ("Active users", metrics.active_users.to_string()),
+                            ("In progress", metrics.transactions_in_progress.to_string()),
+                            ("Load", format!("{:.0}%", metrics.system_load)),
+                            ("Errors", format!("{:.2}%", metrics.error_rate)),
+                            ("Response", format_ms(metrics.response_time)),
+                        ];
+                        view! {
+                            {readings.into_iter().map(|(label, value)| view! {
+                                <span class="text-gray-500">
+                                    {label}": "<span class="font-semibold text-gray-900">{value}</span>
+                                </span>
+                            }).collect_view()}
+                            {alerts.first().map(|alert| view! {
+                                <span class="text-red-700" title=alert.message.clone()>
+                                    {format!(
+                                        "⚠ {} alert{}: {}",
+                                        alerts.len(),
+                                        if alerts.len() == 1 { "" } else { "s" },
+                                        alert.message
+                                    )}
+                                </span>
+                            })}
+                        }
+                        .into_any()
+                    }
+                }}
+            </div>
+        </section>
+    }
+}
+
 #[component]
-fn KeyMetricsSection(metrics: DashboardMetrics) -> impl IntoView {
+fn KeyMetricsSection(
+    metrics: DashboardMetrics,
+    period: TrendPeriod,
+    freshness: Freshness,
+) -> impl IntoView {
+    let today = Utc::now().date_naive();
     let key_metrics = vec![
         MetricCardData {
             title: "Total Members".to_string(),
-            value: metrics.shareholders.total_shareholders.to_string(),
-            change: Some(ChangeIndicator {
-                percentage: 4.75,
-                direction: TrendDirection::Up,
-                period: "this month".to_string(),
-            }),
+            value: metrics.total_members.to_string(),
+            change: period_change(
+                &metrics.trends.member_growth,
+                SeriesKind::Level,
+                period,
+                today,
+            ),
             icon: "👥".to_string(),
             description: Some("Active SACCO members".to_string()),
             color_scheme: ColorScheme::Blue,
         },
         MetricCardData {
             title: "Market Value".to_string(),
-            value: format!(
-                "{:.0} KES",
-                metrics
-                    .market
-                    .total_market_value_kes
-                    .to_f64()
-                    .unwrap_or(0.0)
-            ),
-            change: Some(ChangeIndicator {
-                percentage: 12.3,
-                direction: TrendDirection::Up,
-                period: "vs last quarter".to_string(),
-            }),
+            value: or_na(metrics.market.total_market_value.as_ref()),
+            change: None,
             icon: "💰".to_string(),
-            description: Some("Total portfolio value in KES".to_string()),
+            description: Some("Shares in circulation at the latest offer price".to_string()),
             color_scheme: ColorScheme::Green,
         },
         MetricCardData {
             title: "Active Offers".to_string(),
-            value: metrics.offers.active_offers.to_string(),
-            change: Some(ChangeIndicator {
-                percentage: 2.1,
-                direction: TrendDirection::Down,
-                period: "this week".to_string(),
-            }),
+            value: or_na(metrics.offers.as_ref().map(|offers| offers.active_offers)),
+            change: None,
             icon: "📊".to_string(),
             description: Some("Available share offers".to_string()),
             color_scheme: ColorScheme::Purple,
         },
         MetricCardData {
             title: "Share Price".to_string(),
-            value: format!(
-                "{} KES",
-                metrics.market.share_price_kes.to_u64().unwrap_or(1000)
-            ),
-            change: None, // Share price is fixed, no change indicator
+            value: or_na(metrics.market.share_price.map(|price| price.to_string())),
+            change: None,
             icon: "📈".to_string(),
-            description: Some("Fixed share price".to_string()),
+            description: Some("Price of the latest share offer".to_string()),
             color_scheme: ColorScheme::Yellow,
         },
     ];
 
     view! {
         <section aria-labelledby="kpi-heading">
-            <h2 id="kpi-heading" class="text-lg font-semibold text-gray-900 mb-4">"Key Performance Indicators"</h2>
+            <div class="flex flex-wrap items-center justify-between gap-3 mb-4">
+                <h2 id="kpi-heading" class="text-lg font-semibold text-gray-900">"Key Performance Indicators"</h2>
+                <FreshnessBadge freshness=freshness />
+            </div>
             <div class="grid grid-cols-1 sm:grid-cols-2 lg:grid-cols-4 gap-6">
                 {key_metrics.into_iter().map(|metric| view! {
                     <MetricCard data=metric />
@@ -240,6 +765,116 @@ fn KeyMetricsSection(metrics: DashboardMetrics) -> impl IntoView {
     }
 }
 
+fn format_kes(value: Decimal) -> String {
+    Money::kes(value.round_dp(0)).to_string()
+}
+
+fn format_whole(value: Decimal) -> String {
+    value.round_dp(0).to_string()
+}
+
+// Member, volume, transaction and chama trends with a period selector
+#[component]
+fn TrendsSection(
+    trends: DashboardTrends,
+    period: TrendPeriod,
+    freshness: Freshness,
+) -> impl IntoView {
+    let today = Utc::now().date_naive();
+    let charts = vec![
+        (
+            "Member Growth",
+            exact_chart_points(
+                &trends.member_growth,
+                period,
+                today,
+                format_whole as fn(Decimal) -> String,
+            ),
+            ChartKind::Line,
+            "#2563eb",
+            period_change(&trends.member_growth, SeriesKind::Level, period, today),
+        ),
+        (
+            "Transaction Volume",
+            exact_chart_points(&trends.volume_trend, period, today, format_kes),
+            ChartKind::Area,
+            "#16a34a",
+            period_change(&trends.volume_trend, SeriesKind::Flow, period, today),
+        ),
+        (
+            "Transactions",
+            exact_chart_points(&trends.transaction_trend, period, today, format_whole),
+            ChartKind::Bar,
+            "#9333ea",
+            period_change(&trends.transaction_trend, SeriesKind::Flow, period, today),
+        ),
+        (
+            "Chama Growth",
+            exact_chart_points(&trends.chama_growth, period, today, format_whole),
+            ChartKind::Line,
+            "#ca8a04",
+            period_change(&trends.chama_growth, SeriesKind::Level, period, today),
+        ),
+    ];
+
+    view! {
+        <section aria-labelledby="trends-heading" class="space-y-4">
+            <div class="flex flex-wrap items-center justify-between gap-3">
+                <div class="flex items-center gap-3">
+                    <h2 id="trends-heading" class="text-lg font-semibold text-gray-900">"Trends"</h2>
+                    <FreshnessBadge freshness=freshness />
+                </div>
+                <nav class="inline-flex rounded-md shadow-sm" aria-label="Trend period">
+                    {TrendPeriod::ALL.into_iter().map(|option| {
+                        let class = if option == period {
+                            "bg-indigo-600 text-white border-indigo-600"
+                        } else {
+                            "bg-white text-gray-700 border-gray-300 hover:bg-gray-50"
+                        };
+                        view! {
+                            <a
+                                href=format!("/dashboard?period={}", option.as_query())
+                                class=format!("px-3 py-1.5 text-xs font-medium border first:rounded-l-md last:rounded-r-md -ml-px first:ml-0 {}", class)
+                                aria-current=(option == period).then_some("page")
+                            >
+                                {option.as_query()}
+                            </a>
+                        }
+                    }).collect_view()}
+                </nav>
+            </div>
+            <div class="grid grid-cols-1 lg:grid-cols-2 gap-6">
+                {charts.into_iter().map(|(title, points, kind, color, change)| view! {
+                    <div class="bg-white rounded-xl shadow-sm border p-6">
+                        <div class="flex items-center justify-between mb-2">
+                            <h3 class="text-sm font-medium text-gray-900">{title}</h3>
+                            {change.map(|change| view! { <ChangeBadge change=change /> })}
+                        </div>
+                        <TrendChart points=points kind=kind title=title color=color />
+                    </div>
+                }).collect_view()}
+            </div>
+        </section>
+    }
+}
+
+#[component]
+fn ChangeBadge(change: ChangeIndicator) -> impl IntoView {
+    let (arrow, color_class) = match change.direction {
+        TrendDirection::Up => ("↗", "text-green-600 bg-green-50"),
+        TrendDirection::Down => ("↘", "text-red-600 bg-red-50"),
+        TrendDirection::Stable => ("→", "text-gray-600 bg-gray-50"),
+    };
+    view! {
+        <div class=format!("flex items-center px-2 py-1 rounded-full text-xs font-medium {}", color_class)
+             role="img"
+             aria-label=format!("{:.1}% change {}", change.percentage, change.period)>
+            <span class="mr-1" aria-hidden="true">{arrow}</span>
+            <span>{percent(change.percentage)}</span>
+        </div>
+    }
+}
+
 // Reusable metric card component
 #[component]
 fn MetricCard(data: MetricCardData) -> impl IntoView {
@@ -291,20 +926,8 @@ fn MetricCard(data: MetricCardData) -> impl IntoView {
                 <div class=format!("p-2 rounded-lg {}", icon_bg_class) aria-hidden="true">
                     <span class="text-xl">{icon}</span>
                 </div>
-                {change.as_ref().map(|change| {
-                    let (arrow, color_class) = match change.direction {
-                        TrendDirection::Up => ("↗", "text-green-600 bg-green-50"),
-                        TrendDirection::Down => ("↘", "text-red-600 bg-red-50"),
-                        TrendDirection::Stable => ("→", "text-gray-600 bg-gray-50"),
-                    };
-                    view! {
-                        <div class=format!("flex items-center px-2 py-1 rounded-full text-xs font-medium {}", color_class)
-                             role="img"
-                             aria-label=format!("{:.1}% change", change.percentage)>
-                            <span class="mr-1" aria-hidden="true">{arrow}</span>
-                            <span>{format!("{:.1}%", change.percentage)}</span>
-                        </div>
-                    }.into_any()
+                {change.clone().map(|change| {
+                    view! { <ChangeBadge change=change /> }.into_any()
                 }).unwrap_or_else(|| view! { <div></div> }.into_any())}
             </div>
 
@@ -324,69 +947,103 @@ fn MetricCard(data: MetricCardData) -> impl IntoView {
 
 // Financial Overview Section
 #[component]
-fn FinancialOverviewSection(metrics: DashboardMetrics) -> impl IntoView {
-    let market_value = metrics
-        .market
-        .total_market_value_kes
-        .to_f64()
-        .unwrap_or(0.0);
-    let total_transactions = metrics
-        .transactions
-        .total_transaction_value_kes
-        .to_f64()
-        .unwrap_or(0.0);
-    let avg_transaction = metrics
-        .transactions
-        .average_transaction_size_kes
-        .to_f64()
-        .unwrap_or(0.0);
+fn FinancialOverviewSection(
+    metrics: DashboardMetrics,
+    freshness: Freshness,
+    shares: SectionState<SharesMetrics>,
+) -> impl IntoView {
+    let market_value = or_na(metrics.market.total_market_value.as_ref());
+    let total_transactions = metrics.transactions.total_transaction_value.to_string();
+    let avg_transaction = metrics.transactions.average_transaction_size.to_string();
 
     view! {
         <section aria-labelledby="financial-heading">
             <div class="bg-white rounded-xl shadow-sm border p-6">
                 <div class="flex items-center justify-between mb-6">
                     <h3 id="financial-heading" class="text-lg font-semibold text-gray-900">"Financial Overview"</h3>
-                    <div class="flex items-center text-sm text-gray-500">
-                        <svg class="w-4 h-4 mr-1" fill="none" stroke="currentColor" viewBox="0 0 24 24" aria-hidden="true">
-                            <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M13 7h8m0 0v8m0-8l-8 8-4-4-6 6"></path>
-                        </svg>
-                        "Updated 5 min ago"
-                    </div>
+                    <FreshnessBadge freshness=freshness />
                 </div>
 
                 <div class="grid grid-cols-1 sm:grid-cols-3 gap-4 mb-6">
                     <div class="text-center p-4 bg-gray-50 rounded-lg">
-                        <div class="text-2xl font-bold text-gray-900">{format!("{:.0} KES", market_value)}</div>
-                        <div class="text-sm text-gray-600">"Total Assets"</div>
+                        <div class="text-2xl font-bold text-gray-900">{market_value}</div>
+                        <div class="text-sm text-gray-600">"Share Market Value"</div>
                     </div>
                     <div class="text-center p-4 bg-gray-50 rounded-lg">
-                        <div class="text-2xl font-bold text-gray-900">{format!("{:.0} KES", total_transactions)}</div>
+                        <div class="text-2xl font-bold text-gray-900">{total_transactions}</div>
                         <div class="text-sm text-gray-600">"Transaction Volume"</div>
                     </div>
                     <div class="text-center p-4 bg-gray-50 rounded-lg">
-                        <div class="text-2xl font-bold text-gray-900">{format!("{:.0} KES", avg_transaction)}</div>
+                        <div class="text-2xl font-bold text-gray-900">{avg_transaction}</div>
                         <div class="text-sm text-gray-600">"Avg. Transaction"</div>
                     </div>
                 </div>
 
-                <div class="space-y-3">
-                    <div class="flex justify-between items-center">
-                        <span class="text-sm font-medium text-gray-600">"Portfolio Growth"</span>
-                        <span class="text-sm font-semibold text-green-600">"+12.3%"</span>
-                    </div>
-                    <div class="w-full bg-gray-200 rounded-full h-2" role="progressbar" aria-valuenow="75" aria-valuemin="0" aria-valuemax="100">
-                        <div class="bg-green-500 h-2 rounded-full" style="width: 75%"></div>
-                    </div>
-                    <div class="flex justify-between text-xs text-gray-500">
-                        <span>"Target: $2M"</span>
-                        <span>"Current: $1.5M"</span>
+                <ShareDistribution shares=shares />
+            </div>
+        </section>
+    }
+}
+
+// Share ownership and secondary-market activity
+#[component]
+fn ShareDistribution(shares: SectionState<SharesMetrics>) -> impl IntoView {
+    let freshness = Freshness::of(&shares);
+    let ownership = shares.data().map(|s| s.ownership.clone());
+    let trading = shares.data().map(|s| s.trading.clone());
+    let distributed_pct = ownership
+        .as_ref()
+        .filter(|o| o.total_shares > 0)
+        .map(|o| o.distributed_shares as f64 / o.total_shares as f64 * 100.0);
+
+    view! {
+        <div class="space-y-3">
+            <div class="flex justify-between items-center">
+                <span class="flex items-center gap-2 text-sm font-medium text-gray-600">
+                    "Shares Distributed"
+                    <FreshnessBadge freshness=freshness />
+                </span>
+                <span class="text-sm font-semibold text-gray-900">
+                    {or_na(distributed_pct.map(percent))}
+                </span>
+            </div>
+            <div class="w-full bg-gray-200 rounded-full h-2" role="progressbar"
+                 aria-valuenow=distributed_pct.unwrap_or(0.0).round().to_string()
+                 aria-valuemin="0" aria-valuemax="100">
+                <div class="bg-green-500 h-2 rounded-full"
+                     style=format!("width: {:.1}%", distributed_pct.unwrap_or(0.0))></div>
+            </div>
+            <div class="flex justify-between text-xs text-gray-500">
+                <span>{format!("Distributed: {}", or_na(ownership.as_ref().map(|o| o.distributed_shares)))}</span>
+                <span>{format!("Available: {}", or_na(ownership.as_ref().map(|o| o.available_shares)))}</span>
+            </div>
+            <div class="grid grid-cols-3 gap-4 pt-3 text-center">
+                <div>
+                    <div class="text-lg font-semibold text-gray-900">{or_na(trading.as_ref().map(|t| t.total_transfers))}</div>
+                    <div class="text-xs text-gray-500">"Share Transfers"</div>
+                </div>
+                <div>
+                    <div class="text-lg font-semibold text-gray-900">{or_na(trading.as_ref().map(|t| t.transfer_volume))}</div>
+                    <div class="text-xs text-gray-500">"Shares Transferred"</div>
+                </div>
+                <div>
+                    <div class="text-lg font-semibold text-gray-900">
+                        {or_na(trading.as_ref().map(|t| format!("{:.1}", t.average_transfer_size)))}
                     </div>
+                    <div class="text-xs text-gray-500">"Avg. Transfer Size"</div>
                 </div>
             </div>
-        </section>
+        </div>
     }
 }
 
+/// Render a metric, or "n/a" when the backend couldn't provide it
+fn or_na<T: ToString>(value: Option<T>) -> String {
+    value
+        .map(|v| v.to_string())
+        .unwrap_or_else(|| "n/a".to_string())
+}
+
 // Loading skeleton component
 #[component]
 fn DashboardSkeleton() -> impl IntoView {
@@ -0,0 +1,386 @@
+use crate::api::traits::dividends::{DividendRun, DividendRunStatus};
+use crate::utils::format_sats;
+use leptos::prelude::*;
+
+#[server(GetDividendRuns, "/api", "GetJson")]
+pub async fn get_dividend_runs() -> Result<Vec<DividendRun>, ServerFnError> {
+    use crate::api::{backends::dividends_api, config::ApiConfig, types::PaginationQuery};
+
+    // Runs list every member's entitlement
+    crate::api::client::require_admin("view dividend runs").await?;
+    let api =
+        dividends_api(&ApiConfig::from_env()).map_err(|e| ServerFnError::new(e.to_string()))?;
+    api.get_dividend_runs(PaginationQuery {
+        page: Some(1),
+        limit: Some(50),
+    })
+    .await
+    .map(|runs| runs.data)
+    .map_err(|e| ServerFnError::new(e.to_string()))
+}
+
+#[server(CreateDividendRun, "/api")]
+pub async fn create_dividend_run(
+    period_from: String,
+    period_to: String,
+    dividend_rate: String,
+    savings_interest_rate: String,
+    sats_per_kes: String,
+) -> Result<DividendRun, ServerFnError> {
+    use crate::api::{
+        backends::dividends_api, client::require_admin, config::ApiConfig,
+        traits::dividends::DividendRunRequest,
+    };
+
+    let maker = require_admin("prepare dividend runs").await?;
+
+    // The period covers whole days, so it ends at midnight after the last day
+    let parse_date = |value: &str, label: &str| {
+        chrono::NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d")
+            .map(|date| date.and_time(chrono::NaiveTime::MIN).and_utc())
+            .map_err(|_| ServerFnError::new(format!("{} must be a date", label)))
+    };
+    let parse_decimal = |value: &str, label: &str| {
+        value
+            .trim()
+            .parse::<rust_decimal::Decimal>()
+            .map_err(|_| ServerFnError::new(format!("{} must be a number", label)))
+    };
+
+    let api =
+        dividends_api(&ApiConfig::from_env()).map_err(|e| ServerFnError::new(e.to_string()))?;
+    api.create_dividend_run(DividendRunRequest {
+        period_start: parse_date(&period_from, "Period start")?,
+        period_end: parse_date(&period_to, "Period end")? + chrono::Duration::days(1),
+        dividend_rate: parse_decimal(&dividend_rate, "Dividend rate")?,
+        savings_interest_rate: parse_decimal(&savings_interest_rate, "Savings interest rate")?,
+        sats_per_kes: parse_decimal(&sats_per_kes, "Sats per KES")?,
+        created_by: maker.id,
+    })
+    .await
+    .map_err(|e| ServerFnError::new(e.to_string()))
+}
+
+#[server(ApproveDividendRun, "/api")]
+pub async fn approve_dividend_run(run_id: uuid::Uuid) -> Result<DividendRun, ServerFnError> {
+    use crate::api::{backends::dividends_api, client::get_request_user, config::ApiConfig};
+
+    let checker = get_request_user().await?;
+    let api =
+        dividends_api(&ApiConfig::from_env()).map_err(|e| ServerFnError::new(e.to_string()))?;
+    api.approve_dividend_run(run_id, &checker)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))
+}
+
+#[server(RejectDividendRun, "/api")]
+pub async fn reject_dividend_run(
+    run_id: uuid::Uuid,
+    reason: Option<String>,
+) -> Result<DividendRun, ServerFnError> {
+    use crate::api::{backends::dividends_api, client::get_request_user, config::ApiConfig};
+
+    let checker = get_request_user().await?;
+    let api =
+        dividends_api(&ApiConfig::from_env()).map_err(|e| ServerFnError::new(e.to_string()))?;
+    api.reject_dividend_run(run_id, &checker, reason)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))
+}
+
+#[server(PayDividendRun, "/api")]
+pub async fn pay_dividend_run(run_id: uuid::Uuid) -> Result<DividendRun, ServerFnError> {
+    use crate::api::{backends::dividends_api, client::get_request_user, config::ApiConfig};
+
+    let operator = get_request_user().await?;
+    let api =
+        dividends_api(&ApiConfig::from_env()).map_err(|e| ServerFnError::new(e.to_string()))?;
+    api.pay_dividend_run(run_id, &operator)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))
+}
+
+#[component]
+pub fn DividendsPage() -> impl IntoView {
+    let create_action = ServerAction::<CreateDividendRun>::new();
+    let approve_action = ServerAction::<ApproveDividendRun>::new();
+    let reject_action = ServerAction::<RejectDividendRun>::new();
+    let pay_action = ServerAction::<PayDividendRun>::new();
+
+    let runs = Resource::new(
+        move || {
+            (
+                create_action.version().get(),
+                approve_action.version().get(),
+                reject_action.version().get(),
+                pay_action.version().get(),
+            )
+        },
+        |_| get_dividend_runs(),
+    );
+
+    let action_error = move || {
+        [
+            create_action.value().get().and_then(|r| r.err()),
+            approve_action.value().get().and_then(|r| r.err()),
+            reject_action.value().get().and_then(|r| r.err()),
+            pay_action.value().get().and_then(|r| r.err()),
+        ]
+        .into_iter()
+        .flatten()
+        .next()
+        .map(|e| e.to_string())
+    };
+
+    view! {
+        <div class="space-y-6">
+            <div>
+                <h1 class="text-2xl font-semibold text-gray-900">"Dividends & Interest"</h1>
+                <p class="mt-1 text-sm text-gray-500">"Dividends on share capital and interest on savings, pro-rated by how long balances were held"</p>
+            </div>
+
+            {move || action_error().map(|message| view! {
+                <div class="bg-red-50 border border-red-200 rounded-lg p-4 text-sm text-red-700" role="alert">
+                    {message}
+                </div>
+            })}
+
+            <RunForm action=create_action />
+
+            <Suspense fallback=move || view! { <div class="bg-white shadow rounded-lg p-6 animate-pulse h-40"></div> }>
+                {move || {
+                    runs.get().map(|result| match result {
+                        Ok(runs) if runs.is_empty() => view! {
+                            <div class="bg-white shadow rounded-lg p-6 text-sm text-gray-500">
+                                "No dividend runs yet. Preview one above to see each member's entitlement."
+                            </div>
+                        }.into_any(),
+                        Ok(runs) => view! {
+                            <div class="space-y-4">
+                                {runs.into_iter().map(|run| view! {
+                                    <RunCard
+                                        run=run
+                                        approve_action=approve_action
+                                        reject_action=reject_action
+                                        pay_action=pay_action
+                                    />
+                                }).collect_view()}
+                            </div>
+                        }.into_any(),
+                        Err(e) => view! {
+                            <div class="bg-red-50 border border-red-200 rounded-xl p-6 text-center" role="alert">
+                                <h3 class="text-sm font-semibold text-red-800">"Unable to load dividend runs"</h3>
+                                <p class="mt-1 text-sm text-red-600">{e.to_string()}</p>
+                            </div>
+                        }.into_any(),
+                    })
+                }}
+            </Suspense>
+        </div>
+    }
+}
+
+#[component]
+fn RunForm(action: ServerAction<CreateDividendRun>) -> impl IntoView {
+    let input_class = "block w-full rounded-md border-gray-300 shadow-sm sm:text-sm";
+
+    view! {
+        <div class="bg-white shadow rounded-lg p-6 space-y-4">
+            <h3 class="text-lg font-medium text-gray-900">"Preview a Run"</h3>
+            <ActionForm action=action attr:class="grid grid-cols-2 lg:grid-cols-6 gap-3 items-end">
+                <label class="text-xs text-gray-500">
+                    "From"
+                    <input name="period_from" type="date" required=true class=input_class/>
+                </label>
+                <label class="text-xs text-gray-500">
+                    "To (inclusive)"
+                    <input name="period_to" type="date" required=true class=input_class/>
+                </label>
+                <label class="text-xs text-gray-500">
+                    "Dividend % p.a."
+                    <input name="dividend_rate" type="number" step="0.01" min="0" value="0" required=true class=input_class/>
+                </label>
+                <label class="text-xs text-gray-500">
+                    "Savings interest % p.a."
+                    <input name="savings_interest_rate" type="number" step="0.01" min="0" value="0" required=true class=input_class/>
+                </label>
+                <label class="text-xs text-gray-500">
+                    "Sats per KES"
+                    <input name="sats_per_kes" type="number" step="0.0001" min="0" required=true class=input_class/>
+                </label>
+                <button type="submit" class="bg-indigo-600 hover:bg-indigo-700 text-white text-sm font-medium py-2 px-4 rounded-md">
+                    "Compute Preview"
+                </button>
+            </ActionForm>
+            <p class="text-xs text-gray-500">
+                "Dividends are earned on KES share capital and paid in sats at the given rate. A second admin must approve a run before it can be paid."
+            </p>
+        </div>
+    }
+}
+
+#[component]
+fn RunCard(
+    run: DividendRun,
+    approve_action: ServerAction<ApproveDividendRun>,
+    reject_action: ServerAction<RejectDividendRun>,
+    pay_action: ServerAction<PayDividendRun>,
+) -> impl IntoView {
+    let (badge_class, badge_label) = match run.status {
+        DividendRunStatus::Preview => ("bg-yellow-100 text-yellow-800", "Preview"),
+        DividendRunStatus::Approved => ("bg-blue-100 text-blue-800", "Approved"),
+        DividendRunStatus::Rejected => ("bg-gray-100 text-gray-800", "Rejected"),
+        DividendRunStatus::PartiallyPaid => ("bg-orange-100 text-orange-800", "Partially paid"),
+        DividendRunStatus::Paid => ("bg-green-100 text-green-800", "Paid"),
+    };
+    let last_day = run.period_end - chrono::Duration::days(1);
+    let period = format!(
+        "{} – {}",
+        run.period_start.format("%Y-%m-%d"),
+        last_day.format("%Y-%m-%d")
+    );
+    let schedule_href = format!(
+        "data:text/csv;charset=utf-8,{}",
+        urlencoding::encode(&run.to_csv())
+    );
+    let schedule_name = format!(
+        "dividend-schedule-{}-{}.csv",
+        run.period_start.format("%Y%m%d"),
+        last_day.format("%Y%m%d")
+    );
+
+    let is_preview = run.status == DividendRunStatus::Preview;
+    let is_payable = matches!(
+        run.status,
+        DividendRunStatus::Approved | DividendRunStatus::PartiallyPaid
+    );
+    let pay_label = if run.status == DividendRunStatus::PartiallyPaid {
+        "Retry Unpaid"
+    } else {
+        "Pay to Wallets"
+    };
+    let approve_id = run.id.to_string();
+    let reject_id = run.id.to_string();
+    let pay_id = run.id.to_string();
+    let unpaid = run
+        .entitlements
+        .iter()
+        .filter(|e| {
+            e.is_outstanding() && (e.payout_wallet_id.is_none() || e.payout_error.is_some())
+        })
+        .count();
+
+    let totals = vec![
+        ("Members", run.entitlements.len().to_string()),
+        ("Dividends (KES)", run.total_dividend_kes().to_string()),
+        ("Dividends", format_sats(run.total_dividend_sats())),
+        ("Savings interest", format_sats(run.total_interest_sats())),
+    ];
+
+    view! {
+        <div class="bg-white shadow rounded-lg p-6 space-y-4">
+            <div class="flex justify-between items-start">
+                <div>
+                    <h3 class="text-lg font-medium text-gray-900">{period}</h3>
+                    <p class="text-xs text-gray-500">
+                        {format!(
+                            "{}% dividend · {}% savings interest · {} sats/KES · prepared {}",
+                            run.dividend_rate.normalize(),
+                            run.savings_interest_rate.normalize(),
+                            run.sats_per_kes.normalize(),
+                            run.created_at.format("%Y-%m-%d")
+                        )}
+                    </p>
+                    {run.rejection_reason.clone().map(|reason| view! { <p class="text-xs text-gray-500">{reason}</p> })}
+                </div>
+                <span class=format!("px-2 py-1 rounded-full text-xs font-medium {}", badge_class)>{badge_label}</span>
+            </div>
+
+            <div class="grid grid-cols-2 lg:grid-cols-4 gap-4">
+                {totals.into_iter().map(|(label, value)| view! {
+                    <div class="bg-gray-50 rounded-lg p-3">
+                        <p class="text-xs text-gray-500">{label}</p>
+                        <p class="mt-1 text-lg font-semibold text-gray-900">{value}</p>
+                    </div>
+                }).collect_view()}
+            </div>
+
+            {(unpaid > 0).then(|| view! {
+                <p class="text-xs text-yellow-700">
+                    {format!("{} member(s) have no wallet or a failed payout; they stay owed until a payout reaches them.", unpaid)}
+                </p>
+            })}
+
+            <div class="flex flex-wrap gap-2 items-center">
+                {is_preview.then(move || view! {
+                    <ActionForm action=approve_action>
+                        <input type="hidden" name="run_id" value=approve_id/>
+                        <button type="submit" class="bg-green-600 hover:bg-green-700 text-white text-xs font-medium py-1.5 px-3 rounded-md">
+                            "Approve"
+                        </button>
+                    </ActionForm>
+                    <ActionForm action=reject_action attr:class="flex gap-1">
+                        <input type="hidden" name="run_id" value=reject_id/>
+                        <input name="reason" type="text" placeholder="Reason" class="w-32 rounded-md border-gray-300 text-xs"/>
+                        <button type="submit" class="bg-red-600 hover:bg-red-700 text-white text-xs font-medium py-1.5 px-3 rounded-md">
+                            "Reject"
+                        </button>
+                    </ActionForm>
+                })}
+                {is_payable.then(move || view! {
+                    <ActionForm action=pay_action>
+                        <input type="hidden" name="run_id" value=pay_id/>
+                        <button type="submit" class="bg-indigo-600 hover:bg-indigo-700 text-white text-xs font-medium py-1.5 px-3 rounded-md">
+                            {pay_label}
+                        </button>
+                    </ActionForm>
+                })}
+                <a href=schedule_href download=schedule_name class="text-xs font-medium text-indigo-600 hover:underline">
+                    "Download schedule (CSV)"
+                </a>
+            </div>
+
+            <details>
+                <summary class="text-xs font-medium text-indigo-600 cursor-pointer">"Entitlements"</summary>
+                <table class="mt-2 min-w-full divide-y divide-gray-200 text-xs">
+                    <thead class="bg-gray-50">
+                        <tr>
+                            <th class="px-3 py-1.5 text-left font-medium text-gray-500">"Member"</th>
+                            <th class="px-3 py-1.5 text-right font-medium text-gray-500">"Avg share capital"</th>
+                            <th class="px-3 py-1.5 text-right font-medium text-gray-500">"Dividend"</th>
+                            <th class="px-3 py-1.5 text-right font-medium text-gray-500">"Avg savings"</th>
+                            <th class="px-3 py-1.5 text-right font-medium text-gray-500">"Interest"</th>
+                            <th class="px-3 py-1.5 text-right font-medium text-gray-500">"Total"</th>
+                            <th class="px-3 py-1.5 text-left font-medium text-gray-500">"Payout"</th>
+                        </tr>
+                    </thead>
+                    <tbody class="divide-y divide-gray-100">
+                        {run.entitlements.into_iter().map(|e| {
+                            let payout = match (e.paid_at, &e.payout_error, e.payout_wallet_id) {
+                                (Some(at), _, _) => format!("Paid {}", at.format("%Y-%m-%d")),
+                                (None, Some(error), _) => format!("Unpaid: {}", error),
+                                (None, None, Some(_)) => "To first wallet".to_string(),
+                                (None, None, None) => "No wallet".to_string(),
+                            };
+                            view! {
+                                <tr>
+                                    <td class="px-3 py-1.5">
+                                        <a href=format!("/members/{}", e.user_id) class="text-indigo-600 hover:underline font-mono">
+                                            {e.user_id.to_string()}
+                                        </a>
+                                    </td>
+                                    <td class="px-3 py-1.5 text-right">{format!("KES {}", e.average_share_capital.round_dp(2))}</td>
+                                    <td class="px-3 py-1.5 text-right">{format!("KES {} · {}", e.dividend_kes, format_sats(e.dividend_sats))}</td>
+                                    <td class="px-3 py-1.5 text-right">{format_sats(e.average_savings.round_dp(0).try_into().unwrap_or(0))}</td>
+                                    <td class="px-3 py-1.5 text-right">{format_sats(e.interest_sats)}</td>
+                                    <td class="px-3 py-1.5 text-right font-medium">{format_sats(e.total_sats())}</td>
+                                    <td class="px-3 py-1.5">{payout}</td>
+                                </tr>
+                            }
+                        }).collect_view()}
+                    </tbody>
+                </table>
+            </details>
+        </div>
+    }
+}
@@ -0,0 +1,385 @@
+use crate::api::client::RetryExport;
+use crate::api::dashboard_client::{ExportRecord, ExportRequest, ExportResponse};
+use crate::api::list_exports;
+use crate::components::ui::{show_error, show_success, Spinner};
+use chrono::DateTime;
+use leptos::prelude::*;
+use std::collections::HashMap;
+
+/// Seconds between status checks while an export is running
+const POLL_SECS: u64 = 2;
+
+const FORMATS: [(&str, &str); 4] = [
+    ("csv", "CSV"),
+    ("xlsx", "Excel (XLSX)"),
+    ("pdf", "PDF"),
+    ("json", "JSON"),
+];
+
+const DATA_TYPES: [(&str, &str); 5] = [
+    ("all", "Everything"),
+    ("overview", "Overview"),
+    ("users", "Users"),
+    ("financial", "Financial"),
+    ("operations", "Operations"),
+];
+
+/// Filters the export engine understands, with the hint shown under each
+const FILTERS: [(&str, &str, &str); 3] = [
+    ("currency", "Currency", "e.g. KES"),
+    ("operation", "Operation", "e.g. deposit"),
+    ("service", "Service", "e.g. swap"),
+];
+
+/// Start an export from the form. Blank fields are left out of the request.
+#[server(RequestExport, "/api")]
+#[allow(clippy::too_many_arguments)]
+pub async fn request_export(
+    format: String,
+    data_type: String,
+    start: Option<String>,
+    end: Option<String>,
+    currency: Option<String>,
+    operation: Option<String>,
+    service: Option<String>,
+    include_charts: Option<bool>,
+) -> Result<ExportResponse, ServerFnError> {
+    use crate::api::dashboard_client::DateRange;
+
+    let present = |value: Option<String>| value.filter(|value| !value.trim().is_empty());
+    let date_range = match (present(start), present(end)) {
+        (Some(start), Some(end)) => Some(DateRange { start, end }),
+        (None, None) => None,
+        _ => return Err(ServerFnError::new("Give both dates, or neither")),
+    };
+    let filters: HashMap<String, serde_json::Value> = [
+        ("currency", currency),
+        ("operation", operation),
+        ("service", service),
+    ]
+    .into_iter()
+    .filter_map(|(key, value)| Some((key.to_string(), present(value)?.into())))
+    .collect();
+
+    let request = ExportRequest {
+        format,
+        data_type,
+        date_range,
+        filters: (!filters.is_empty()).then_some(filters),
+        include_charts,
+    };
+    let response = crate::api::export_dashboard_data(request).await?;
+    response.data.ok_or_else(|| {
+        ServerFnError::new(
+            response
+                .message
+                .unwrap_or_else(|| "The export could not be started".to_string()),
+        )
+    })
+}
+
+fn label<'a>(options: &[(&'a str, &'a str)], key: &'a str) -> &'a str {
+    options
+        .iter()
+        .find(|(value, _)| value.eq_ignore_ascii_case(key))
+        .map_or(key, |(_, label)| label)
+}
+
+/// One-line summary of what an export contains, for the history and toasts
+fn describe(request: &ExportRequest) -> String {
+    let mut parts = vec![
+        label(&DATA_TYPES, &request.data_type).to_string(),
+        label(&FORMATS, &request.format).to_string(),
+    ];
+    if let Some(range) = &request.date_range {
+        parts.push(format!("{} to {}", range.start, range.end));
+    }
+    parts.join(" · ")
+}
+
+fn describe_filters(request: &ExportRequest) -> String {
+    let mut filters: Vec<String> = request
+        .filters
+        .iter()
+        .flatten()
+        .map(|(key, value)| format!("{}: {}", key, value.as_str().unwrap_or_default()))
+        .collect();
+    filters.sort();
+    if request.include_charts == Some(true) {
+        filters.push("with charts".to_string());
+    }
+    if filters.is_empty() {
+        "—".to_string()
+    } else {
+        filters.join(", ")
+    }
+}
+
+fn format_time(value: &str) -> String {
+    DateTime::parse_from_rfc3339(value)
+        .map(|time| time.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_else(|_| value.to_string())
+}
+
+#[component]
+pub fn ExportCenterPage() -> impl IntoView {
+    let request_action = ServerAction::<RequestExport>::new();
+    let retry_action = ServerAction::<RetryExport>::new();
+    let (refresh_tick, set_refresh_tick) = signal(0u32);
+    let (running, set_running) = signal(false);
+
+    // Only poll while something is still being rendered
+    Effect::new(move |_| {
+        set_interval(
+            move || {
+                if running.get_untracked() {
+                    set_refresh_tick.update(|tick| *tick += 1);
+                }
+            },
+            std::time::Duration::from_secs(POLL_SECS),
+        );
+    });
+
+    let exports = Resource::new(
+        move || {
+            (
+                request_action.version().get(),
+                retry_action.version().get(),
+                refresh_tick.get(),
+            )
+        },
+        |_| list_exports(),
+    );
+
+    // Statuses from the last check; None until the history first loads, so
+    // exports that finished before the page was opened aren't announced
+    let seen = StoredValue::new(None::<HashMap<String, String>>);
+    Effect::new(move |_| {
+        let Some(Ok(records)) = exports.get() else {
+            return;
+        };
+        set_running.set(
+            records
+                .iter()
+                .any(|record| record.status.status == "processing"),
+        );
+        let previous = seen.get_value();
+        for record in &records {
+            let status = &record.status;
+            let was = previous
+                .as_ref()
+                .map(|seen| seen.get(&status.export_id).map(String::as_str));
+            // Announce exports that finished since the last check
+            if !matches!(was, Some(None | Some("processing"))) {
+                continue;
+            }
+            match status.status.as_str() {
+                "completed" => show_success(
+                    "Export ready",
+                    &format!("{} is ready to download", describe(&record.request)),
+                ),
+                "failed" => show_error(
+                    "Export failed",
+                    status
+                        .error
+                        .as_deref()
+                        .unwrap_or("The export could not be completed"),
+                ),
+                _ => {}
+            }
+        }
+        seen.set_value(Some(
+            records
+                .iter()
+                .map(|record| {
+                    (
+                        record.status.export_id.clone(),
+                        record.status.status.clone(),
+                    )
+                })
+                .collect(),
+        ));
+    });
+
+    let action_error = move || {
+        [
+            request_action.value().get().and_then(|r| r.err()),
+            retry_action.value().get().and_then(|r| r.err()),
+        ]
+        .into_iter()
+        .flatten()
+        .next()
+    };
+
+    let input_class = "mt-1 block w-full border border-gray-300 rounded-md px-3 py-2 text-sm";
+
+    view! {
+        <div class="space-y-6">
+            <div>
+                <h1 class="text-2xl font-semibold text-gray-900">"Export Center"</h1>
+                <p class="mt-1 text-sm text-gray-500">
+                    "Download dashboard data as CSV, Excel, PDF or JSON. Download links expire, after which the export can be run again."
+                </p>
+            </div>
+
+            {move || action_error().map(|error| view! {
+                <div class="bg-red-50 border border-red-200 rounded-lg p-4 text-sm text-red-700" role="alert">
+                    {error.to_string()}
+                </div>
+            })}
+
+            <div class="bg-white shadow rounded-lg p-6 space-y-4">
+                <h2 class="text-lg font-medium text-gray-900">"New export"</h2>
+                <ActionForm action=request_action attr:class="space-y-4">
+                    <div class="grid grid-cols-1 md:grid-cols-4 gap-4">
+                        <label class="block text-sm font-medium text-gray-700">
+                            "Format"
+                            <select name="format" class=input_class>
+                                {FORMATS.iter().map(|(value, label)| view! {
+                                    <option value=*value>{*label}</option>
+                                }).collect_view()}
+                            </select>
+                        </label>
+                        <label class="block text-sm font-medium text-gray-700">
+                            "Data"
+                            <select name="data_type" class=input_class>
+                                {DATA_TYPES.iter().map(|(value, label)| view! {
+                                    <option value=*value>{*label}</option>
+                                }).collect_view()}
+                            </select>
+                        </label>
+                        <label class="block text-sm font-medium text-gray-700">
+                            "From"
+                            <input name="start" type="date" class=input_class/>
+                        </label>
+                        <label class="block text-sm font-medium text-gray-700">
+                            "To"
+                            <input name="end" type="date" class=input_class/>
+                        </label>
+                    </div>
+                    <p class="text-xs text-gray-500">"The date range applies to financial figures."</p>
+                    <div class="grid grid-cols-1 md:grid-cols-3 gap-4">
+                        {FILTERS.iter().map(|(name, label, hint)| view! {
+                            <label class="block text-sm font-medium text-gray-700">
+                                {*label}
+                                <input name=*name type="text" placeholder=*hint class=input_class/>
+                            </label>
+                        }).collect_view()}
+                    </div>
+                    <p class="text-xs text-gray-500">
+                        "Filters narrow the tables that have that column and leave the rest whole."
+                    </p>
+                    <div class="flex items-center justify-between gap-4">
+                        <label class="flex items-center gap-2 text-sm text-gray-700">
+                            <input name="include_charts" type="checkbox" value="true" class="rounded border-gray-300"/>
+                            "Include charts (Excel only)"
+                        </label>
+                        <button
+                            type="submit"
+                            class="bg-teal-600 hover:bg-teal-700 text-white text-sm font-medium py-2 px-4 rounded-md disabled:opacity-50"
+                            disabled=move || request_action.pending().get()
+                        >
+                            "Start export"
+                        </button>
+                    </div>
+                </ActionForm>
+            </div>
+
+            <div class="bg-white shadow rounded-lg p-6 space-y-4">
+                <h2 class="text-lg font-medium text-gray-900">"Recent exports"</h2>
+                // Transition keeps the history on screen while the next check loads
+                <Transition fallback=move || view! { <div class="flex justify-center py-12"><Spinner /></div> }>
+                    {move || exports.get().map(|result| match result {
+                        Ok(records) => view! { <ExportHistory records=records retry_action=retry_action /> }.into_any(),
+                        Err(e) => view! {
+                            <div class="bg-red-50 border border-red-200 rounded-lg p-4 text-sm text-red-700" role="alert">
+                                {format!("Failed to load exports: {}", e)}
+                            </div>
+                        }.into_any(),
+                    })}
+                </Transition>
+            </div>
+        </div>
+    }
+}
+
+#[component]
+fn ExportHistory(
+    records: Vec<ExportRecord>,
+    retry_action: ServerAction<RetryExport>,
+) -> impl IntoView {
+    if records.is_empty() {
+        return view! { <p class="text-sm text-gray-500">"No exports yet."</p> }.into_any();
+    }
+
+    view! {
+        <div class="overflow-x-auto">
+            <table class="min-w-full divide-y divide-gray-200 text-sm">
+                <thead class="bg-gray-50">
+                    <tr>
+                        <th class="px-4 py-2 text-left font-medium text-gray-500">"Requested"</th>
+                        <th class="px-4 py-2 text-left font-medium text-gray-500">"Export"</th>
+                        <th class="px-4 py-2 text-left font-medium text-gray-500">"Filters"</th>
+                        <th class="px-4 py-2 text-left font-medium text-gray-500">"Status"</th>
+                        <th class="px-4 py-2"></th>
+                    </tr>
+                </thead>
+                <tbody class="divide-y divide-gray-200">
+                    {records.into_iter().map(|record| {
+                        let status = record.status;
+                        let state = match status.status.as_str() {
+                            "completed" => view! {
+                                <span class="px-2 py-0.5 rounded-full text-xs font-medium bg-green-100 text-green-800">"Ready"</span>
+                            }.into_any(),
+                            "failed" => view! {
+                                <div class="space-y-1">
+                                    <span class="px-2 py-0.5 rounded-full text-xs font-medium bg-red-100 text-red-800">"Failed"</span>
+                                    <p class="text-xs text-red-700">{status.error.clone().unwrap_or_default()}</p>
+                                </div>
+                            }.into_any(),
+                            _ => {
+                                let progress = status.progress.clamp(0.0, 100.0);
+                                view! {
+                                    <div class="w-40 space-y-1">
+                                        <div class="w-full bg-gray-200 rounded-full h-2">
+                                            <div class="bg-teal-600 h-2 rounded-full" style=format!("width: {:.0}%", progress)></div>
+                                        </div>
+                                        <p class="text-xs text-gray-500">{format!("Running · {:.0}%", progress)}</p>
+                                    </div>
+                                }.into_any()
+                            }
+                        };
+                        let action = match (status.status.as_str(), status.download_url.clone()) {
+                            ("completed", Some(url)) => view! {
+                                <a href=url download="" class="text-teal-600 hover:text-teal-800 text-xs font-medium">"Download"</a>
+                            }.into_any(),
+                            ("failed", _) => view! {
+                                <ActionForm action=retry_action>
+                                    <input type="hidden" name="export_id" value=status.export_id.clone()/>
+                                    <button
+                                        type="submit"
+                                        class="text-teal-600 hover:text-teal-800 text-xs font-medium disabled:opacity-50"
+                                        disabled=move || retry_action.pending().get()
+                                    >
+                                        "Retry"
+                                    </button>
+                                </ActionForm>
+                            }.into_any(),
+                            _ => ().into_any(),
+                        };
+                        view! {
+                            <tr>
+                                <td class="px-4 py-2 text-gray-500 whitespace-nowrap">{format_time(&status.created_at)}</td>
+                                <td class="px-4 py-2 text-gray-900">{describe(&record.request)}</td>
+                                <td class="px-4 py-2 text-gray-500">{describe_filters(&record.request)}</td>
+                                <td class="px-4 py-2">{state}</td>
+                                <td class="px-4 py-2 text-right">{action}</td>
+                            </tr>
+                        }
+                    }).collect_view()}
+                </tbody>
+            </table>
+        </div>
+    }
+    .into_any()
+}
@@ -0,0 +1,581 @@
+use crate::api::dashboard_client::{
+    ChamaMetrics, CountMetrics, DistributionData, FinancialAnalyticsResponse, PerformanceMetrics,
+    VolumeMetrics,
+};
+use crate::api::get_financial_analytics;
+use crate::api::traits::wallets::{TransactionStatus, TransactionType, WalletTransaction};
+use crate::api::types::PaginatedResponse;
+use crate::components::ui::{ChartKind, Spinner, TrendChart};
+use crate::pages::dashboard::{chart_points, TrendPeriod};
+use crate::utils::{format_ms, format_sats, percent};
+use chrono::Utc;
+use leptos::prelude::*;
+use leptos_router::hooks::use_query_map;
+
+/// Transactions per drill-down page
+const TRANSACTIONS_PER_PAGE: u32 = 25;
+
+fn parse_transaction_type(value: &str) -> Option<TransactionType> {
+    match value {
+        "deposit" => Some(TransactionType::Deposit),
+        "withdrawal" => Some(TransactionType::Withdrawal),
+        "transfer" => Some(TransactionType::Transfer),
+        "payment" => Some(TransactionType::Payment),
+        "loan_repayment" => Some(TransactionType::LoanRepayment),
+        _ => None,
+    }
+}
+
+fn parse_status(value: &str) -> Option<TransactionStatus> {
+    match value {
+        "pending" => Some(TransactionStatus::Pending),
+        "confirmed" => Some(TransactionStatus::Confirmed),
+        "failed" => Some(TransactionStatus::Failed),
+        "cancelled" => Some(TransactionStatus::Cancelled),
+        _ => None,
+    }
+}
+
+/// Transactions behind the analytics, for the same date range
+#[server(GetFinancialTransactions, "/api", "GetJson")]
+pub async fn get_financial_transactions(
+    from: Option<String>,
+    to: Option<String>,
+    transaction_type: Option<String>,
+    status: Option<String>,
+    page: Option<u32>,
+) -> Result<PaginatedResponse<WalletTransaction>, ServerFnError> {
+    use crate::api::{
+        backends::wallets_api, config::ApiConfig, traits::wallets::TransactionFilter,
+        types::PaginationQuery,
+    };
+    use chrono::NaiveDate;
+
+    crate::api::client::require_admin("drill into transactions").await?;
+    // Both ends of the range are whole days
+    let day = |value: Option<String>, days_after: i64| -> Result<_, ServerFnError> {
+        match value.filter(|v| !v.trim().is_empty()) {
+            None => Ok(None),
+            Some(value) => NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d")
+                .map(|date| {
+                    Some(
+                        (date + chrono::Duration::days(days_after))
+                            .and_time(Default::default())
+                            .and_utc(),
+                    )
+                })
+                .map_err(|_| ServerFnError::new("Dates must be in YYYY-MM-DD form")),
+        }
+    };
+    let filter = TransactionFilter {
+        from: day(from, 0)?,
+        to: day(to, 1)?,
+        transaction_type: transaction_type.as_deref().and_then(parse_transaction_type),
+        status: status.as_deref().and_then(parse_status),
+    };
+
+    let api = wallets_api(&ApiConfig::from_env()).map_err(|e| ServerFnError::new(e.to_string()))?;
+    api.get_transactions(
+        filter,
+        PaginationQuery {
+            page,
+            limit: Some(TRANSACTIONS_PER_PAGE),
+        },
+    )
+    .await
+    .map_err(|e| ServerFnError::new(e.to_string()))
+}
+
+/// The date range every section is filtered by, carried through drill-down links
+#[derive(Debug, Clone, Default, PartialEq)]
+struct DateRange {
+    from: Option<String>,
+    to: Option<String>,
+}
+
+impl DateRange {
+    /// Link to the transactions behind a figure; `kind` and `status` use the
+    /// query values `parse_transaction_type` and `parse_status` accept
+    fn drill_href(&self, kind: Option<&str>, status: Option<&str>) -> String {
+        let params: Vec<String> = [
+            ("from", self.from.as_deref()),
+            ("to", self.to.as_deref()),
+            ("type", kind),
+            ("status", status),
+        ]
+        .into_iter()
+        .filter_map(|(name, value)| value.map(|value| format!("{}={}", name, value)))
+        .chain(std::iter::once("drill=1".to_string()))
+        .collect();
+        format!("/financial-analytics?{}#transactions", params.join("&"))
+    }
+}
+
+fn type_label(kind: &TransactionType) -> &'static str {
+    match kind {
+        TransactionType::Deposit => "Deposit",
+        TransactionType::Withdrawal => "Withdrawal",
+        TransactionType::Transfer => "Transfer",
+        TransactionType::Payment => "Payment",
+        TransactionType::Contribution => "Contribution",
+        TransactionType::LoanRepayment => "Loan repayment",
+        TransactionType::SharePayment => "Share payment",
+    }
+}
+
+fn status_badge(status: &TransactionStatus) -> (&'static str, &'static str) {
+    match status {
+        TransactionStatus::Pending => ("bg-yellow-100 text-yellow-800", "Pending"),
+        TransactionStatus::Confirmed => ("bg-green-100 text-green-800", "Confirmed"),
+        TransactionStatus::Failed => ("bg-red-100 text-red-800", "Failed"),
+        TransactionStatus::Cancelled => ("bg-gray-100 text-gray-800", "Cancelled"),
+    }
+}
+
+#[component]
+pub fn FinancialAnalyticsPage() -> impl IntoView {
+    let query = use_query_map();
+    let range = move || {
+        let query = query.read();
+        let date = |key| query.get(key).filter(|value: &String| !value.is_empty());
+        DateRange {
+            from: date("from"),
+            to: date("to"),
+        }
+    };
+    let analytics = Resource::new(range, |range| get_financial_analytics(range.from, range.to));
+
+    let drill = move || {
+        let query = query.read();
+        query.get("drill").map(|_| {
+            (
+                query.get("type").filter(|v| !v.is_empty()),
+                query.get("status").filter(|v| !v.is_empty()),
+                query.get("page").and_then(|p| p.parse::<u32>().ok()),
+            )
+        })
+    };
+    let transactions = Resource::new(
+        move || (range(), drill()),
+        |(range, drill)| async move {
+            match drill {
+                Some((kind, status, page)) => {
+                    get_financial_transactions(range.from, range.to, kind, status, page)
+                        .await
+                        .map(Some)
+                }
+                None => Ok(None),
+            }
+        },
+    );
+
+    let field = move |key: &'static str| move || query.read().get(key).unwrap_or_default();
+    let input_class = "block w-full rounded-md border-gray-300 shadow-sm sm:text-sm";
+
+    view! {
+        <div class="space-y-6">
+            <div class="flex flex-col md:flex-row md:items-end md:justify-between gap-4">
+                <div>
+                    <h1 class="text-2xl font-semibold text-gray-900">"Financial Analytics"</h1>
+                    <p class="mt-1 text-sm text-gray-500">"Volume, outcomes, performance and chama balances, with the transactions behind them"</p>
+                </div>
+                <form method="get" action="/financial-analytics" class="flex flex-wrap items-end gap-3">
+                    <label class="text-xs text-gray-500">
+                        "From"
+                        <input name="from" type="date" value=field("from") class=input_class/>
+                    </label>
+                    <label class="text-xs text-gray-500">
+                        "To"
+                        <input name="to" type="date" value=field("to") class=input_class/>
+                    </label>
+                    <button type="submit" class="border border-gray-300 text-gray-700 hover:bg-gray-50 text-sm font-medium py-2 px-4 rounded-md">
+                        "Apply"
+                    </button>
+                    <a href="/financial-analytics" class="text-sm text-indigo-600 hover:text-indigo-800 py-2">"All time"</a>
+                </form>
+            </div>
+
+            <Suspense fallback=move || view! { <div class="flex justify-center py-12"><Spinner /></div> }>
+                {move || analytics.get().map(|result| match result {
+                    Ok(response) => match response.data {
+                        Some(data) => view! { <AnalyticsSections data=data range=range() /> }.into_any(),
+                        None => view! {
+                            <div class="bg-red-50 border border-red-200 rounded-lg p-4 text-sm text-red-700" role="alert">
+                                {response.message.unwrap_or_else(|| "Financial analytics are unavailable".to_string())}
+                            </div>
+                        }.into_any(),
+                    },
+                    Err(e) => view! {
+                        <div class="bg-red-50 border border-red-200 rounded-lg p-4 text-sm text-red-700" role="alert">
+                            {e.to_string()}
+                        </div>
+                    }.into_any(),
+                })}
+            </Suspense>
+
+            <Suspense fallback=|| ()>
+                {move || transactions.get().map(|result| match result {
+                    Ok(Some(page)) => view! { <TransactionDrillDown page=page /> }.into_any(),
+                    Ok(None) => ().into_any(),
+                    Err(e) => view! {
+                        <div id="transactions" class="bg-red-50 border border-red-200 rounded-lg p-4 text-sm text-red-700" role="alert">
+                            {e.to_string()}
+                        </div>
+                    }.into_any(),
+                })}
+            </Suspense>
+        </div>
+    }
+}
+
+#[component]
+fn AnalyticsSections(data: FinancialAnalyticsResponse, range: DateRange) -> impl IntoView {
+    view! {
+        <VolumeSection volume=data.transactions.volume range=range.clone() />
+        <div class="grid grid-cols-1 lg:grid-cols-2 gap-6">
+            <OutcomeSection counts=data.transactions.counts range=range.clone() />
+            <PerformanceSection performance=data.transactions.performance />
+        </div>
+        <ChamaSection chamas=data.chamas range=range />
+    }
+    .into_any()
+}
+
+#[component]
+fn VolumeSection(volume: VolumeMetrics, range: DateRange) -> impl IntoView {
+    let totals = vec![
+        ("Total", volume.total.to_string()),
+        ("Today", volume.today.to_string()),
+        ("This week", volume.this_week.to_string()),
+        ("This month", volume.this_month.to_string()),
+    ];
+    let mut currencies: Vec<_> = volume.by_currency.into_iter().collect();
+    currencies.sort_by(|a, b| a.0.cmp(&b.0));
+    let mut operations: Vec<_> = volume.by_operation.into_iter().collect();
+    operations.sort_by_key(|(_, op)| std::cmp::Reverse(op.count));
+    let trend = chart_points(&volume.trend, TrendPeriod::All, Utc::now().date_naive());
+
+    view! {
+        <div class="bg-white shadow rounded-lg p-6 space-y-6">
+            <h3 class="text-lg font-medium text-gray-900">"Volume"</h3>
+            <dl class="grid grid-cols-2 lg:grid-cols-4 gap-4">
+                {totals.into_iter().map(|(label, value)| view! {
+                    <div class="p-4 bg-gray-50 rounded-lg">
+                        <dt class="text-xs text-gray-500">{label}</dt>
+                        <dd class="text-lg font-semibold text-gray-900">{value}</dd>
+                    </div>
+                }).collect_view()}
+            </dl>
+            <TrendChart points=trend kind=ChartKind::Area title="Volume trend" color="#16a34a" />
+
+            <div class="grid grid-cols-1 lg:grid-cols-2 gap-6">
+                <div>
+                    <h4 class="text-sm font-medium text-gray-900 mb-2">"By Currency"</h4>
+                    <table class="min-w-full divide-y divide-gray-200 text-sm">
+                        <thead class="bg-gray-50">
+                            <tr>
+                                <th class="px-4 py-2 text-left font-medium text-gray-500">"Currency"</th>
+                                <th class="px-4 py-2 text-right font-medium text-gray-500">"Total"</th>
+                                <th class="px-4 py-2 text-right font-medium text-gray-500">"Today"</th>
+                            </tr>
+                        </thead>
+                        <tbody class="divide-y divide-gray-100">
+                            {currencies.into_iter().map(|(currency, volume)| view! {
+                                <tr>
+                                    <td class="px-4 py-2 text-gray-700">{currency}</td>
+                                    <td class="px-4 py-2 text-right text-gray-900">{volume.total.to_string()}</td>
+                                    <td class="px-4 py-2 text-right text-gray-700">{volume.today.to_string()}</td>
+                                </tr>
+                            }).collect_view()}
+                        </tbody>
+                    </table>
+                </div>
+                <div>
+                    <h4 class="text-sm font-medium text-gray-900 mb-2">"By Operation"</h4>
+                    <table class="min-w-full divide-y divide-gray-200 text-sm">
+                        <thead class="bg-gray-50">
+                            <tr>
+                                <th class="px-4 py-2 text-left font-medium text-gray-500">"Operation"</th>
+                                <th class="px-4 py-2 text-right font-medium text-gray-500">"Count"</th>
+                                <th class="px-4 py-2 text-right font-medium text-gray-500">"Total"</th>
+                                <th class="px-4 py-2 text-right font-medium text-gray-500">"Average"</th>
+                                <th class="px-4 py-2"></th>
+                            </tr>
+                        </thead>
+                        <tbody class="divide-y divide-gray-100">
+                            {operations.into_iter().map(|(operation, volume)| {
+                                // Only operations the wallets record can be drilled into
+                                let href = parse_transaction_type(&operation)
+                                    .map(|_| range.drill_href(Some(&operation), None));
+                                view! {
+                                    <tr>
+                                        <td class="px-4 py-2 text-gray-700">{operation}</td>
+                                        <td class="px-4 py-2 text-right text-gray-700">{volume.count}</td>
+                                        <td class="px-4 py-2 text-right text-gray-900">{volume.total.to_string()}</td>
+                                        <td class="px-4 py-2 text-right text-gray-700">{volume.average_amount.to_string()}</td>
+                                        <td class="px-4 py-2 text-right">
+                                            {href.map(|href| view! {
+                                                <a href=href class="text-indigo-600 hover:text-indigo-800 text-xs font-medium">"Transactions"</a>
+                                            })}
+                                        </td>
+                                    </tr>
+                                }
+                            }).collect_view()}
+                        </tbody>
+                    </table>
+                </div>
+            </div>
+        </div>
+    }
+    .into_any()
+}
+
+#[component]
+fn OutcomeSection(counts: CountMetrics, range: DateRange) -> impl IntoView {
+    let outcomes = vec![
+        (
+            "Successful",
+            counts.successful,
+            "bg-green-500",
+            Some("confirmed"),
+        ),
+        ("Failed", counts.failed, "bg-red-500", Some("failed")),
+        ("Pending", counts.pending, "bg-yellow-500", Some("pending")),
+    ];
+    let total = counts.total;
+
+    view! {
+        <div class="bg-white shadow rounded-lg p-6 space-y-4">
+            <div class="flex items-center justify-between">
+                <h3 class="text-lg font-medium text-gray-900">"Outcomes"</h3>
+                <a href=range.drill_href(None, None) class="text-indigo-600 hover:text-indigo-800 text-xs font-medium">
+                    {format!("All {} transactions", total)}
+                </a>
+            </div>
+            <ul class="space-y-3 text-sm">
+                {outcomes.into_iter().map(|(label, count, bar, status)| {
+                    let share = if total > 0 { count as f64 / total as f64 * 100.0 } else { 0.0 };
+                    view! {
+                        <li>
+                            <div class="flex justify-between">
+                                <a href=range.drill_href(None, status) class="text-gray-700 hover:text-indigo-700">{label}</a>
+                                <span class="text-gray-900">{format!("{} ({})", count, percent(share))}</span>
+                            </div>
+                            <div class="w-full bg-gray-200 rounded-full h-2 mt-1">
+                                <div class=format!("{} h-2 rounded-full", bar) style=format!("width: {:.1}%", share)></div>
+                            </div>
+                        </li>
+                    }
+                }).collect_view()}
+            </ul>
+            <p class="text-xs text-gray-500">{format!("{:.1} transactions per day on average", counts.average_per_day)}</p>
+        </div>
+    }
+    .into_any()
+}
+
+#[component]
+fn PerformanceSection(performance: PerformanceMetrics) -> impl IntoView {
+    let mut errors: Vec<_> = performance.errors_by_type.into_iter().collect();
+    errors.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    let durations = chart_points(
+        &performance.duration_trend,
+        TrendPeriod::All,
+        Utc::now().date_naive(),
+    );
+
+    view! {
+        <div class="bg-white shadow rounded-lg p-6 space-y-4">
+            <h3 class="text-lg font-medium text-gray-900">"Performance"</h3>
+            <dl class="grid grid-cols-2 gap-4">
+                <div class="p-4 bg-gray-50 rounded-lg">
+                    <dt class="text-xs text-gray-500">"Average duration"</dt>
+                    <dd class="text-lg font-semibold text-gray-900">{format_ms(performance.average_duration)}</dd>
+                </div>
+                <div class="p-4 bg-gray-50 rounded-lg">
+                    <dt class="text-xs text-gray-500">"Success rate"</dt>
+                    <dd class="text-lg font-semibold text-gray-900">{percent(performance.success_rate)}</dd>
+                </div>
+            </dl>
+            <TrendChart points=durations kind=ChartKind::Line title="Duration trend" color="#9333ea" format=format_ms />
+            {if errors.is_empty() {
+                view! { <p class="text-sm text-gray-500">"No errors reported."</p> }.into_any()
+            } else {
+                view! {
+                    <ul class="divide-y divide-gray-100 text-sm">
+                        {errors.into_iter().map(|(kind, count)| view! {
+                            <li class="flex justify-between py-1.5">
+                                <span class="text-gray-700">{kind}</span>
+                                <span class="text-gray-900">{count}</span>
+                            </li>
+                        }).collect_view()}
+                    </ul>
+                }.into_any()
+            }}
+        </div>
+    }
+    .into_any()
+}
+
+#[component]
+fn ChamaSection(chamas: ChamaMetrics, range: DateRange) -> impl IntoView {
+    let financial = chamas.financial;
+    let activity = chamas.activity;
+    let totals = vec![
+        ("Total balance", financial.total_balance.to_string(), None),
+        (
+            "Deposits",
+            financial.total_deposits.to_string(),
+            Some(range.drill_href(Some("deposit"), None)),
+        ),
+        (
+            "Withdrawals",
+            financial.total_withdrawals.to_string(),
+            Some(range.drill_href(Some("withdrawal"), None)),
+        ),
+        ("Net flow", financial.net_flow.to_string(), None),
+    ];
+
+    view! {
+        <div class="bg-white shadow rounded-lg p-6 space-y-6">
+            <div class="flex flex-wrap items-center justify-between gap-2">
+                <h3 class="text-lg font-medium text-gray-900">"Chama Finances"</h3>
+                <p class="text-xs text-gray-500">
+                    {format!(
+                        "Today: {} deposits, {} withdrawals, {} withdrawals pending · average balance {}",
+                        activity.deposits_today, activity.withdrawals_today, activity.pending_withdrawals, activity.average_balance
+                    )}
+                </p>
+            </div>
+            <dl class="grid grid-cols-2 lg:grid-cols-4 gap-4">
+                {totals.into_iter().map(|(label, value, href)| view! {
+                    <div class="p-4 bg-gray-50 rounded-lg">
+                        <dt class="text-xs text-gray-500">{label}</dt>
+                        <dd class="text-lg font-semibold text-gray-900">{value}</dd>
+                        {href.map(|href| view! {
+                            <a href=href class="text-indigo-600 hover:text-indigo-800 text-xs font-medium">"Transactions"</a>
+                        })}
+                    </div>
+                }).collect_view()}
+            </dl>
+            <div class="grid grid-cols-1 lg:grid-cols-3 gap-6">
+                <Distribution title="Chama balances" buckets=chamas.distribution.balance_distribution />
+                <Distribution title="Member balances" buckets=chamas.distribution.member_balance_distribution />
+                <div>
+                    <h4 class="text-sm font-medium text-gray-900 mb-2">"Deposit patterns"</h4>
+                    <ul class="divide-y divide-gray-100 text-sm">
+                        {chamas.distribution.deposit_patterns.into_iter().map(|pattern| view! {
+                            <li class="flex justify-between py-1.5">
+                                <span class="text-gray-700">{format!("{} ({}×)", pattern.pattern, pattern.frequency)}</span>
+                                <span class="text-gray-900">{pattern.average_amount.to_string()}</span>
+                            </li>
+                        }).collect_view()}
+                    </ul>
+                </div>
+            </div>
+        </div>
+    }
+    .into_any()
+}
+
+#[component]
+fn Distribution(title: &'static str, buckets: Vec<DistributionData>) -> impl IntoView {
+    view! {
+        <div>
+            <h4 class="text-sm font-medium text-gray-900 mb-2">{title}</h4>
+            {if buckets.is_empty() {
+                view! { <p class="text-sm text-gray-500">"Nothing reported."</p> }.into_any()
+            } else {
+                view! {
+                    <ul class="space-y-2 text-sm">
+                        {buckets.into_iter().map(|bucket| view! {
+                            <li>
+                                <div class="flex justify-between">
+                                    <span class="text-gray-700">{bucket.range}</span>
+                                    <span class="text-gray-900">{format!("{} ({})", bucket.count, percent(bucket.percentage))}</span>
+                                </div>
+                                <div class="w-full bg-gray-200 rounded-full h-1.5 mt-1">
+                                    <div class="bg-indigo-500 h-1.5 rounded-full"
+                                         style=format!("width: {:.1}%", bucket.percentage.clamp(0.0, 100.0))></div>
+                                </div>
+                            </li>
+                        }).collect_view()}
+                    </ul>
+                }.into_any()
+            }}
+        </div>
+    }
+    .into_any()
+}
+
+#[component]
+fn TransactionDrillDown(page: PaginatedResponse<WalletTransaction>) -> impl IntoView {
+    let query = use_query_map();
+    // Page links keep every other filter
+    let page_href = move |target: u32| {
+        let query = query.read();
+        let params: Vec<String> = ["from", "to", "type", "status", "drill"]
+            .into_iter()
+            .filter_map(|key| query.get(key).map(|value| format!("{}={}", key, value)))
+            .chain(std::iter::once(format!("page={}", target)))
+            .collect();
+        format!("/financial-analytics?{}#transactions", params.join("&"))
+    };
+    let previous = (page.page > 1).then(|| page_href(page.page - 1));
+    let next = (page.page < page.total_pages).then(|| page_href(page.page + 1));
+
+    view! {
+        <div id="transactions" class="bg-white shadow rounded-lg p-6 space-y-4">
+            <div class="flex items-center justify-between">
+                <h3 class="text-lg font-medium text-gray-900">{format!("Transactions ({})", page.total)}</h3>
+                <a href="/financial-analytics" class="text-sm text-gray-500 hover:text-gray-700">"Close"</a>
+            </div>
+            {if page.data.is_empty() {
+                view! { <p class="text-sm text-gray-500">"No transactions match."</p> }.into_any()
+            } else {
+                view! {
+                    <div class="overflow-x-auto">
+                        <table class="min-w-full divide-y divide-gray-200 text-sm">
+                            <thead class="bg-gray-50">
+                                <tr>
+                                    <th class="px-4 py-2 text-left font-medium text-gray-500">"When"</th>
+                                    <th class="px-4 py-2 text-left font-medium text-gray-500">"Wallet"</th>
+                                    <th class="px-4 py-2 text-left font-medium text-gray-500">"Type"</th>
+                                    <th class="px-4 py-2 text-right font-medium text-gray-500">"Amount"</th>
+                                    <th class="px-4 py-2 text-left font-medium text-gray-500">"Status"</th>
+                                </tr>
+                            </thead>
+                            <tbody class="divide-y divide-gray-100">
+                                {page.data.into_iter().map(|tx| {
+                                    let (badge_class, badge_label) = status_badge(&tx.status);
+                                    let amount = if tx.amount < 0 {
+                                        format!("-{}", format_sats(tx.amount.unsigned_abs()))
+                                    } else {
+                                        format_sats(tx.amount.unsigned_abs())
+                                    };
+                                    view! {
+                                        <tr>
+                                            <td class="px-4 py-2 text-gray-700">{tx.created_at.format("%Y-%m-%d %H:%M").to_string()}</td>
+                                            <td class="px-4 py-2 text-gray-700 font-mono text-xs">{tx.wallet_id.to_string()}</td>
+                                            <td class="px-4 py-2 text-gray-700">{type_label(&tx.transaction_type)}</td>
+                                            <td class="px-4 py-2 text-right text-gray-900">{amount}</td>
+                                            <td class="px-4 py-2">
+                                                <span class=format!("px-2 py-1 rounded-full text-xs font-medium {}", badge_class)>{badge_label}</span>
+                                            </td>
+                                        </tr>
+                                    }
+                                }).collect_view()}
+                            </tbody>
+                        </table>
+                    </div>
+                }.into_any()
+            }}
+            <div class="flex justify-between text-sm">
+                {previous.map(|href| view! { <a href=href class="text-indigo-600 hover:text-indigo-800">"← Newer"</a> })}
+                <span class="text-gray-500">{format!("Page {} of {}", page.page, page.total_pages.max(1))}</span>
+                {next.map(|href| view! { <a href=href class="text-indigo-600 hover:text-indigo-800">"Older →"</a> })}
+            </div>
+        </div>
+    }
+    .into_any()
+}
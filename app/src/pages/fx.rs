@@ -0,0 +1,348 @@
+use crate::api::traits::{fx::FxRate, ledger::LedgerCurrency};
+use crate::components::ui::Spinner;
+use leptos::prelude::*;
+use leptos_router::hooks::use_query_map;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FxOverview {
+    pub current: Option<FxRate>,
+    /// Newest first
+    pub history: Vec<FxRate>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FxConversion {
+    pub amount: Decimal,
+    pub from: LedgerCurrency,
+    pub converted: Decimal,
+    pub to: LedgerCurrency,
+    pub rate: FxRate,
+}
+
+/// Read a `datetime-local` value as UTC, defaulting to now when it is blank
+pub fn parse_as_of(value: Option<&str>) -> Result<chrono::DateTime<chrono::Utc>, String> {
+    let value = value.map(str::trim).unwrap_or_default();
+    if value.is_empty() {
+        return Ok(chrono::Utc::now());
+    }
+    ["%Y-%m-%dT%H:%M", "%Y-%m-%dT%H:%M:%S"]
+        .iter()
+        .find_map(|format| chrono::NaiveDateTime::parse_from_str(value, format).ok())
+        .map(|time| time.and_utc())
+        .ok_or_else(|| "The time must look like 2024-06-30T18:00".to_string())
+}
+
+/// Format for `datetime-local` inputs
+pub fn as_of_input_value(time: chrono::DateTime<chrono::Utc>) -> String {
+    time.format("%Y-%m-%dT%H:%M").to_string()
+}
+
+#[server(GetFxOverview, "/api", "GetJson")]
+pub async fn get_fx_overview(days: i64) -> Result<FxOverview, ServerFnError> {
+    use crate::api::{
+        backends::fx_api, client::get_request_user, config::ApiConfig, errors::ApiError,
+    };
+
+    get_request_user().await?;
+    let api = fx_api(&ApiConfig::from_env()).map_err(|e| ServerFnError::new(e.to_string()))?;
+    let current = match api.current_rate().await {
+        Ok(rate) => Some(rate),
+        Err(ApiError::NotFound { .. }) => None,
+        Err(e) => return Err(ServerFnError::new(e.to_string())),
+    };
+
+    let now = chrono::Utc::now();
+    let mut history = api
+        .get_rate_history(
+            now - chrono::Duration::days(days.max(1)),
+            now + chrono::Duration::seconds(1),
+        )
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+    history.reverse();
+    Ok(FxOverview { current, history })
+}
+
+#[server(RecordFxRate, "/api")]
+pub async fn record_fx_rate(kes_per_btc: String) -> Result<FxRate, ServerFnError> {
+    use crate::api::{backends::fx_api, client::require_admin, config::ApiConfig};
+
+    require_admin("record exchange rates").await?;
+    let kes_per_btc = kes_per_btc
+        .trim()
+        .replace(',', "")
+        .parse::<Decimal>()
+        .map_err(|_| ServerFnError::new("The bitcoin price must be a number"))?;
+
+    let api = fx_api(&ApiConfig::from_env()).map_err(|e| ServerFnError::new(e.to_string()))?;
+    api.record_rate(kes_per_btc, "manual".to_string())
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))
+}
+
+#[server(RefreshFxRate, "/api")]
+pub async fn refresh_fx_rate() -> Result<FxRate, ServerFnError> {
+    use crate::api::{backends::fx_api, client::require_admin, config::ApiConfig};
+
+    require_admin("refresh exchange rates").await?;
+    let api = fx_api(&ApiConfig::from_env()).map_err(|e| ServerFnError::new(e.to_string()))?;
+    api.refresh_rate()
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))
+}
+
+#[server(ConvertAmount, "/api", "GetJson")]
+pub async fn convert_amount(
+    amount: String,
+    currency: String,
+    at: Option<String>,
+) -> Result<FxConversion, ServerFnError> {
+    use crate::api::{backends::fx_api, client::get_request_user, config::ApiConfig};
+
+    get_request_user().await?;
+    let amount = amount
+        .trim()
+        .replace(',', "")
+        .parse::<Decimal>()
+        .map_err(|_| ServerFnError::new("The amount must be a number"))?;
+    let (from, to) = match currency.as_str() {
+        "SATS" => (LedgerCurrency::Sats, LedgerCurrency::Kes),
+        "KES" => (LedgerCurrency::Kes, LedgerCurrency::Sats),
+        other => return Err(ServerFnError::new(format!("Unknown currency '{}'", other))),
+    };
+    let at = parse_as_of(at.as_deref()).map_err(ServerFnError::new)?;
+
+    let api = fx_api(&ApiConfig::from_env()).map_err(|e| ServerFnError::new(e.to_string()))?;
+    let rate = api
+        .rate_at(at)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+    Ok(FxConversion {
+        amount,
+        from,
+        converted: rate.convert(amount, from, to),
+        to,
+        rate,
+    })
+}
+
+/// `KES 13,000,000 per BTC (coingecko, 2024-06-30 18:00 UTC)`
+pub fn describe_rate(rate: &FxRate) -> String {
+    format!(
+        "{} per BTC ({}, {})",
+        LedgerCurrency::Kes.format(rate.kes_per_btc),
+        rate.source,
+        rate.observed_at.format("%Y-%m-%d %H:%M UTC")
+    )
+}
+
+#[component]
+pub fn FxPage() -> impl IntoView {
+    let record_action = ServerAction::<RecordFxRate>::new();
+    let refresh_action = ServerAction::<RefreshFxRate>::new();
+
+    let overview = Resource::new(
+        move || {
+            (
+                record_action.version().get(),
+                refresh_action.version().get(),
+            )
+        },
+        |_| get_fx_overview(30),
+    );
+
+    let action_error = move || {
+        [
+            record_action.value().get().and_then(|r| r.err()),
+            refresh_action.value().get().and_then(|r| r.err()),
+        ]
+        .into_iter()
+        .flatten()
+        .next()
+        .map(|e| e.to_string())
+    };
+    let input_class = "block w-full rounded-md border-gray-300 shadow-sm sm:text-sm";
+
+    view! {
+        <div class="space-y-6">
+            <div>
+                <h1 class="text-2xl font-semibold text-gray-900">"Exchange Rates"</h1>
+                <p class="mt-1 text-sm text-gray-500">"Bitcoin price in KES, used to show every balance in both sats and shillings"</p>
+            </div>
+
+            {move || action_error().map(|message| view! {
+                <div class="bg-red-50 border border-red-200 rounded-lg p-4 text-sm text-red-700" role="alert">
+                    {message}
+                </div>
+            })}
+
+            <div class="grid grid-cols-1 lg:grid-cols-3 gap-6">
+                <div class="bg-white shadow rounded-lg p-6">
+                    <p class="text-sm text-gray-500">"Current rate"</p>
+                    <Suspense fallback=move || view! { <div class="h-10 animate-pulse bg-gray-100 rounded mt-2"></div> }>
+                        {move || overview.get().map(|result| match result {
+                            Ok(FxOverview { current: Some(rate), .. }) => view! {
+                                <p class="mt-1 text-3xl font-semibold text-gray-900">{LedgerCurrency::Kes.format(rate.kes_per_btc)}</p>
+                                <p class="mt-1 text-xs text-gray-500">
+                                    {format!(
+                                        "per BTC · {} sats per KES · {} · {}",
+                                        rate.sats_per_kes().round_dp(4),
+                                        rate.source,
+                                        rate.observed_at.format("%Y-%m-%d %H:%M UTC")
+                                    )}
+                                </p>
+                            }.into_any(),
+                            Ok(_) => view! {
+                                <p class="mt-1 text-sm text-gray-500">"No rate recorded yet. Record one or refresh from the configured sources."</p>
+                            }.into_any(),
+                            Err(e) => view! { <p class="mt-1 text-sm text-red-600">{e.to_string()}</p> }.into_any(),
+                        })}
+                    </Suspense>
+                </div>
+
+                <div class="bg-white shadow rounded-lg p-6 lg:col-span-2 space-y-4">
+                    <h3 class="text-lg font-medium text-gray-900">"Update Rate"</h3>
+                    <div class="flex flex-wrap items-end gap-3">
+                        <ActionForm action=record_action attr:class="flex items-end gap-3">
+                            <label class="text-xs text-gray-500">
+                                "KES per BTC"
+                                <input name="kes_per_btc" type="text" inputmode="decimal" required=true class=input_class/>
+                            </label>
+                            <button type="submit" class="bg-indigo-600 hover:bg-indigo-700 text-white text-sm font-medium py-2 px-4 rounded-md">
+                                "Record"
+                            </button>
+                        </ActionForm>
+                        <ActionForm action=refresh_action>
+                            <button
+                                type="submit"
+                                class="border border-gray-300 text-gray-700 hover:bg-gray-50 text-sm font-medium py-2 px-4 rounded-md disabled:opacity-50"
+                                disabled=move || refresh_action.pending().get()
+                            >
+                                "Fetch from sources"
+                            </button>
+                        </ActionForm>
+                    </div>
+                    <p class="text-xs text-gray-500">"Rates are kept as history, so past balances are valued at the rate in force at the time."</p>
+                </div>
+            </div>
+
+            <Converter />
+
+            <div class="bg-white shadow rounded-lg p-6">
+                <h3 class="text-lg font-medium text-gray-900 mb-4">"Last 30 Days"</h3>
+                <Suspense fallback=move || view! { <div class="flex justify-center py-6"><Spinner /></div> }>
+                    {move || overview.get().map(|result| match result {
+                        Ok(overview) if overview.history.is_empty() => view! {
+                            <p class="text-sm text-gray-500">"No rates recorded in the last 30 days."</p>
+                        }.into_any(),
+                        Ok(overview) => view! { <RateHistoryTable rates=overview.history /> }.into_any(),
+                        Err(e) => view! {
+                            <div class="bg-red-50 border border-red-200 rounded-lg p-4 text-sm text-red-700" role="alert">
+                                {e.to_string()}
+                            </div>
+                        }.into_any(),
+                    })}
+                </Suspense>
+            </div>
+        </div>
+    }
+}
+
+/// Convert an amount at the rate in force at a chosen time, driven by the query string
+#[component]
+fn Converter() -> impl IntoView {
+    let query = use_query_map();
+    let request = move || {
+        let query = query.read();
+        query
+            .get("amount")
+            .filter(|a| !a.trim().is_empty())
+            .map(|amount| {
+                (
+                    amount,
+                    query.get("currency").unwrap_or_else(|| "SATS".to_string()),
+                    query.get("at"),
+                )
+            })
+    };
+    let conversion = Resource::new(request, |request| async move {
+        match request {
+            Some((amount, currency, at)) => convert_amount(amount, currency, at).await.map(Some),
+            None => Ok(None),
+        }
+    });
+    let field = move |key: &'static str| move || query.read().get(key).unwrap_or_default();
+    let input_class = "block w-full rounded-md border-gray-300 shadow-sm sm:text-sm";
+
+    view! {
+        <div class="bg-white shadow rounded-lg p-6 space-y-4">
+            <h3 class="text-lg font-medium text-gray-900">"Convert"</h3>
+            <form method="get" action="/fx" class="grid grid-cols-2 lg:grid-cols-4 gap-3 items-end">
+                <label class="text-xs text-gray-500">
+                    "Amount"
+                    <input name="amount" type="text" inputmode="decimal" required=true value=field("amount") class=input_class/>
+                </label>
+                <label class="text-xs text-gray-500">
+                    "Currency"
+                    <select name="currency" class=input_class>
+                        <option value="SATS" selected=move || field("currency")() != "KES">"sats → KES"</option>
+                        <option value="KES" selected=move || field("currency")() == "KES">"KES → sats"</option>
+                    </select>
+                </label>
+                <label class="text-xs text-gray-500">
+                    "As of (UTC, blank for now)"
+                    <input name="at" type="datetime-local" value=field("at") class=input_class/>
+                </label>
+                <button type="submit" class="bg-indigo-600 hover:bg-indigo-700 text-white text-sm font-medium py-2 px-4 rounded-md">
+                    "Convert"
+                </button>
+            </form>
+            <Suspense fallback=|| ()>
+                {move || conversion.get().map(|result| match result {
+                    Ok(Some(conversion)) => view! {
+                        <p class="text-lg font-semibold text-gray-900">
+                            {format!(
+                                "{} = {}",
+                                conversion.from.format(conversion.amount),
+                                conversion.to.format(conversion.converted)
+                            )}
+                        </p>
+                        <p class="text-xs text-gray-500">{format!("At {}", describe_rate(&conversion.rate))}</p>
+                    }.into_any(),
+                    Ok(None) => ().into_any(),
+                    Err(e) => view! { <p class="text-sm text-red-600">{e.to_string()}</p> }.into_any(),
+                })}
+            </Suspense>
+        </div>
+    }
+}
+
+#[component]
+fn RateHistoryTable(rates: Vec<FxRate>) -> impl IntoView {
+    view! {
+        <div class="overflow-x-auto">
+            <table class="min-w-full divide-y divide-gray-200 text-sm">
+                <thead class="bg-gray-50">
+                    <tr>
+                        <th class="px-4 py-2 text-left font-medium text-gray-500">"Observed"</th>
+                        <th class="px-4 py-2 text-right font-medium text-gray-500">"KES per BTC"</th>
+                        <th class="px-4 py-2 text-right font-medium text-gray-500">"Sats per KES"</th>
+                        <th class="px-4 py-2 text-left font-medium text-gray-500">"Source"</th>
+                    </tr>
+                </thead>
+                <tbody class="divide-y divide-gray-100">
+                    {rates.into_iter().map(|rate| view! {
+                        <tr>
+                            <td class="px-4 py-2 text-gray-700">{rate.observed_at.format("%Y-%m-%d %H:%M UTC").to_string()}</td>
+                            <td class="px-4 py-2 text-right text-gray-900">{LedgerCurrency::Kes.format(rate.kes_per_btc)}</td>
+                            <td class="px-4 py-2 text-right text-gray-700">{rate.sats_per_kes().round_dp(4).to_string()}</td>
+                            <td class="px-4 py-2 text-gray-700">{rate.source.clone()}</td>
+                        </tr>
+                    }).collect_view()}
+                </tbody>
+            </table>
+        </div>
+    }
+}
@@ -1,7 +1,11 @@
-// PLACEHOLDER: Groups page simplified for frontend-only mode
-// CRUD operations should be implemented through API adapter pattern
-
+use crate::api::traits::contributions::{ContributionPlan, ContributionReconciliation, PayoutSlot};
+use crate::components::ui::Spinner;
+use crate::utils::format_sats;
 use leptos::prelude::*;
+use leptos_router::{
+    components::Redirect,
+    hooks::{use_params_map, use_query_map},
+};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -15,9 +19,164 @@ pub struct GroupResponse {
     pub children_count: Option<u64>,
 }
 
+/// A contribution plan with its reconciliation and current payout round
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContributionPlanOverview {
+    pub plan: ContributionPlan,
+    pub reconciliation: ContributionReconciliation,
+    pub rotation: Vec<PayoutSlot>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChamaContributions {
+    pub group_id: uuid::Uuid,
+    /// `None` when the configured backend cannot serve group details
+    pub group_name: Option<String>,
+    pub plans: Vec<ContributionPlanOverview>,
+}
+
+#[server(GetChamaContributions, "/api", "GetJson")]
+pub async fn get_chama_contributions(
+    group_id: uuid::Uuid,
+) -> Result<ChamaContributions, ServerFnError> {
+    use crate::api::{
+        backends::{contributions_api, groups_api},
+        client::get_request_user,
+        config::ApiConfig,
+    };
+
+    let user = get_request_user().await?;
+    let config = ApiConfig::from_env();
+    let api = contributions_api(&config).map_err(|e| ServerFnError::new(e.to_string()))?;
+    let chama_plans = api
+        .get_contribution_plans(group_id)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+    // Plans list every member's wallet and arrears, so only the chama sees them
+    let in_chama = chama_plans
+        .iter()
+        .any(|plan| plan.members.iter().any(|member| member.user_id == user.id));
+    if !user.is_admin() && !in_chama {
+        return Err(ServerFnError::new(
+            "Only admins and the chama's members can view its contributions",
+        ));
+    }
+
+    let group_name = match groups_api(&config) {
+        Ok(api) => api.get_group(group_id).await.ok().map(|group| group.name),
+        Err(_) => None,
+    };
+
+    let now = chrono::Utc::now();
+    let mut plans = Vec::new();
+    for plan in chama_plans {
+        let reconciliation = api
+            .reconcile_contribution_plan(plan.id, now)
+            .await
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
+        plans.push(ContributionPlanOverview {
+            rotation: plan.current_round(now),
+            plan,
+            reconciliation,
+        });
+    }
+
+    Ok(ChamaContributions {
+        group_id,
+        group_name,
+        plans,
+    })
+}
+
+#[server(CreateContributionPlan, "/api")]
+pub async fn create_contribution_plan(
+    group_id: uuid::Uuid,
+    name: String,
+    amount: u64,
+    frequency: String,
+    start_on: String,
+    members: String,
+) -> Result<ContributionPlan, ServerFnError> {
+    use crate::api::{
+        backends::contributions_api,
+        client::require_admin,
+        config::ApiConfig,
+        traits::contributions::{ContributionMember, CreateContributionPlanRequest},
+    };
+
+    require_admin("set up contribution plans").await?;
+
+    let frequency = frequency.parse().map_err(ServerFnError::new)?;
+    let start_date = chrono::NaiveDate::parse_from_str(start_on.trim(), "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|dt| dt.and_utc())
+        .ok_or_else(|| ServerFnError::new("Start date must be in YYYY-MM-DD format"))?;
+
+    // Members are entered one "member_id:wallet_id" pair per line, in payout order
+    let members = members
+        .split(['\n', ','])
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            let (user_id, wallet_id) = entry.split_once(':').ok_or_else(|| {
+                ServerFnError::new(format!(
+                    "Member '{}' must be written as member_id:wallet_id",
+                    entry
+                ))
+            })?;
+            Ok(ContributionMember {
+                user_id: user_id
+                    .trim()
+                    .parse()
+                    .map_err(|_| ServerFnError::new(format!("Invalid member ID '{}'", user_id)))?,
+                wallet_id: wallet_id.trim().parse().map_err(|_| {
+                    ServerFnError::new(format!("Invalid wallet ID '{}'", wallet_id))
+                })?,
+            })
+        })
+        .collect::<Result<Vec<_>, ServerFnError>>()?;
+
+    contributions_api(&ApiConfig::from_env())
+        .map_err(|e| ServerFnError::new(e.to_string()))?
+        .create_contribution_plan(CreateContributionPlanRequest {
+            group_id,
+            name,
+            amount,
+            frequency,
+            start_date,
+            members,
+        })
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))
+}
+
+#[server(PayContribution, "/api")]
+pub async fn pay_contribution(plan_id: uuid::Uuid, amount: u64) -> Result<(), ServerFnError> {
+    use crate::api::{backends::contributions_api, client::get_request_user, config::ApiConfig};
+
+    // Members pay from their own plan wallet
+    let payer = get_request_user().await?;
+    contributions_api(&ApiConfig::from_env())
+        .map_err(|e| ServerFnError::new(e.to_string()))?
+        .pay_contribution(plan_id, &payer, amount)
+        .await
+        .map(|_| ())
+        .map_err(|e| ServerFnError::new(e.to_string()))
+}
+
 #[component]
 pub fn GroupsPage() -> impl IntoView {
+    let query = use_query_map();
+    let lookup_id = move || {
+        query
+            .read()
+            .get("id")
+            .filter(|id| uuid::Uuid::parse_str(id.trim()).is_ok())
+    };
+
     view! {
+        {move || lookup_id().map(|id| view! { <Redirect path=format!("/groups/{}", id.trim())/> })}
         <div class="space-y-6">
             <div class="flex justify-between items-center">
                 <div>
@@ -32,6 +191,19 @@ pub fn GroupsPage() -> impl IntoView {
                 </button>
             </div>
 
+            <form method="get" action="/groups" class="bg-white shadow rounded-lg p-4 flex gap-3">
+                <input
+                    name="id"
+                    type="text"
+                    required=true
+                    placeholder="Chama ID"
+                    class="flex-1 rounded-md border-gray-300 shadow-sm sm:text-sm"
+                />
+                <button type="submit" class="bg-indigo-600 hover:bg-indigo-700 text-white text-sm font-medium py-2 px-4 rounded-md">
+                    "Open Chama"
+                </button>
+            </form>
+
             <div class="bg-white shadow rounded-lg p-8">
                 <div class="text-center">
                     <div class="mx-auto flex items-center justify-center h-12 w-12 rounded-full bg-yellow-100">
@@ -51,3 +223,283 @@ pub fn GroupsPage() -> impl IntoView {
         </div>
     }
 }
+
+#[component]
+pub fn ChamaDetailPage() -> impl IntoView {
+    let params = use_params_map();
+    let group_id = move || {
+        params
+            .read()
+            .get("id")
+            .and_then(|id| uuid::Uuid::parse_str(&id).ok())
+    };
+
+    let create_action = ServerAction::<CreateContributionPlan>::new();
+    let pay_action = ServerAction::<PayContribution>::new();
+
+    let contributions = Resource::new(
+        move || {
+            (
+                group_id(),
+                create_action.version().get(),
+                pay_action.version().get(),
+            )
+        },
+        |(group_id, _, _)| async move {
+            match group_id {
+                Some(id) => get_chama_contributions(id).await,
+                None => Err(ServerFnError::new("Invalid chama ID")),
+            }
+        },
+    );
+
+    view! {
+        <div class="space-y-6">
+            <div>
+                <a href="/groups" class="text-sm text-indigo-600 hover:underline">"← Groups"</a>
+                <h1 class="mt-2 text-2xl font-semibold text-gray-900">
+                    {move || {
+                        contributions
+                            .get()
+                            .and_then(|result| result.ok())
+                            .and_then(|c| c.group_name)
+                            .unwrap_or_else(|| "Chama".to_string())
+                    }}
+                </h1>
+                <p class="mt-1 text-sm text-gray-500 font-mono">
+                    {move || group_id().map(|id| id.to_string()).unwrap_or_default()}
+                </p>
+            </div>
+
+            {move || {
+                create_action
+                    .value()
+                    .get()
+                    .and_then(|r| r.err())
+                    .or_else(|| pay_action.value().get().and_then(|r| r.err()))
+                    .map(|e| view! {
+                        <div class="bg-red-50 border border-red-200 rounded-lg p-4 text-sm text-red-700" role="alert">
+                            {e.to_string()}
+                        </div>
+                    })
+            }}
+
+            <Suspense fallback=move || view! { <div class="flex justify-center py-12"><Spinner /></div> }>
+                {move || {
+                    contributions.get().map(|result| match result {
+                        Ok(contributions) => {
+                            let group_id = contributions.group_id.to_string();
+                            view! {
+                                <div class="space-y-6">
+                                    {if contributions.plans.is_empty() {
+                                        view! {
+                                            <div class="bg-white shadow rounded-lg p-6 text-center text-sm text-gray-500">
+                                                "This chama has no contribution plan yet."
+                                            </div>
+                                        }.into_any()
+                                    } else {
+                                        contributions.plans.into_iter().map(|overview| view! {
+                                            <ContributionPlanCard overview=overview pay_action=pay_action />
+                                        }).collect_view().into_any()
+                                    }}
+                                    <CreatePlanForm group_id=group_id action=create_action />
+                                </div>
+                            }.into_any()
+                        }
+                        Err(e) => view! {
+                            <div class="bg-red-50 border border-red-200 rounded-xl p-6 text-center" role="alert">
+                                <h3 class="text-sm font-semibold text-red-800">"Unable to load contributions"</h3>
+                                <p class="mt-1 text-sm text-red-600">{e.to_string()}</p>
+                            </div>
+                        }.into_any(),
+                    })
+                }}
+            </Suspense>
+        </div>
+    }
+}
+
+#[component]
+fn ContributionPlanCard(
+    overview: ContributionPlanOverview,
+    pay_action: ServerAction<PayContribution>,
+) -> impl IntoView {
+    let ContributionPlanOverview {
+        plan,
+        reconciliation,
+        rotation,
+    } = overview;
+    let now = chrono::Utc::now();
+    let plan_id = plan.id.to_string();
+    let next_due = plan.due_date(reconciliation.periods_due);
+    let in_arrears = reconciliation.members_in_arrears();
+    let badge_class = if in_arrears > 0 {
+        "bg-red-100 text-red-800"
+    } else {
+        "bg-green-100 text-green-800"
+    };
+
+    let summary = vec![
+        (
+            "Per member",
+            format!("{} {}", format_sats(plan.amount), plan.frequency.as_str()),
+        ),
+        ("Periods due", reconciliation.periods_due.to_string()),
+        ("Expected", format_sats(reconciliation.expected_total)),
+        ("Collected", format_sats(reconciliation.paid_total)),
+        ("In arrears", format_sats(reconciliation.arrears_total)),
+    ];
+
+    view! {
+        <div class="bg-white shadow rounded-lg p-6 space-y-6">
+            <div class="flex justify-between items-start">
+                <div>
+                    <h3 class="text-lg font-medium text-gray-900">{plan.name.clone()}</h3>
+                    <p class="text-sm text-gray-500">
+                        {format!(
+                            "{} members · started {} · next contribution due {}",
+                            plan.members.len(),
+                            plan.start_date.format("%Y-%m-%d"),
+                            next_due.format("%Y-%m-%d")
+                        )}
+                    </p>
+                </div>
+                <span class=format!("px-2 py-1 rounded-full text-xs font-medium {}", badge_class)>
+                    {format!("{} member(s) in arrears", in_arrears)}
+                </span>
+            </div>
+
+            <div class="grid grid-cols-2 lg:grid-cols-5 gap-4">
+                {summary.into_iter().map(|(label, value)| view! {
+                    <div class="bg-gray-50 rounded-lg p-3">
+                        <p class="text-xs text-gray-500">{label}</p>
+                        <p class="mt-1 text-sm font-semibold text-gray-900">{value}</p>
+                    </div>
+                }).collect_view()}
+            </div>
+
+            <ActionForm action=pay_action attr:class="flex flex-wrap gap-2 items-end">
+                <input type="hidden" name="plan_id" value=plan_id/>
+                <label class="text-xs text-gray-500">
+                    "Contribute from your plan wallet (sats)"
+                    <input name="amount" type="number" min="1" required=true value=plan.amount.to_string() class="block w-40 rounded-md border-gray-300 shadow-sm sm:text-sm"/>
+                </label>
+                <button type="submit" class="bg-indigo-600 hover:bg-indigo-700 text-white text-sm font-medium py-2 px-4 rounded-md">
+                    "Pay Contribution"
+                </button>
+            </ActionForm>
+
+            <div>
+                <h4 class="text-sm font-medium text-gray-900 mb-2">"Member contributions"</h4>
+                <table class="min-w-full divide-y divide-gray-200 text-sm">
+                    <thead class="bg-gray-50">
+                        <tr>
+                            <th class="px-3 py-2 text-left text-xs font-medium text-gray-500">"Member"</th>
+                            <th class="px-3 py-2 text-right text-xs font-medium text-gray-500">"Expected"</th>
+                            <th class="px-3 py-2 text-right text-xs font-medium text-gray-500">"Paid"</th>
+                            <th class="px-3 py-2 text-right text-xs font-medium text-gray-500">"Arrears"</th>
+                            <th class="px-3 py-2 text-right text-xs font-medium text-gray-500">"Missed"</th>
+                            <th class="px-3 py-2 text-left text-xs font-medium text-gray-500">"Last paid"</th>
+                        </tr>
+                    </thead>
+                    <tbody class="divide-y divide-gray-100">
+                        {reconciliation.members.into_iter().map(|member| {
+                            let arrears_class = if member.arrears > 0 {
+                                "px-3 py-2 text-right font-medium text-red-700"
+                            } else {
+                                "px-3 py-2 text-right text-gray-500"
+                            };
+                            view! {
+                            <tr>
+                                <td class="px-3 py-2">
+                                    <a href=format!("/members/{}", member.user_id) class="text-indigo-600 hover:underline font-mono text-xs">
+                                        {member.user_id.to_string()}
+                                    </a>
+                                </td>
+                                <td class="px-3 py-2 text-right">{format_sats(member.expected)}</td>
+                                <td class="px-3 py-2 text-right">
+                                    {format_sats(member.paid)}
+                                    {(member.credit > 0).then(|| view! {
+                                        <span class="ml-1 text-xs text-green-700">{format!("(+{} ahead)", format_sats(member.credit))}</span>
+                                    })}
+                                </td>
+                                <td class=arrears_class>
+                                    {format_sats(member.arrears)}
+                                </td>
+                                <td class="px-3 py-2 text-right">{member.periods_missed}</td>
+                                <td class="px-3 py-2 text-gray-500">
+                                    {member.last_contribution_at.map(|at| at.format("%Y-%m-%d").to_string()).unwrap_or_else(|| "Never".to_string())}
+                                </td>
+                            </tr>
+                            }
+                        }).collect_view()}
+                    </tbody>
+                </table>
+            </div>
+
+            <div>
+                <h4 class="text-sm font-medium text-gray-900 mb-2">"Payout rotation"</h4>
+                <ol class="grid grid-cols-1 sm:grid-cols-2 lg:grid-cols-3 gap-3">
+                    {rotation.into_iter().map(|slot| {
+                        let (state_class, state_label) = if slot.period + 1 < reconciliation.periods_due {
+                            ("border-gray-200 bg-gray-50", "Completed")
+                        } else if slot.due_date <= now {
+                            ("border-indigo-300 bg-indigo-50", "Current")
+                        } else {
+                            ("border-gray-200 bg-white", "Upcoming")
+                        };
+                        view! {
+                            <li class=format!("border rounded-lg p-3 text-sm {}", state_class)>
+                                <div class="flex justify-between text-xs text-gray-500">
+                                    <span>{format!("Cycle {} · {}", slot.period + 1, slot.due_date.format("%Y-%m-%d"))}</span>
+                                    <span>{state_label}</span>
+                                </div>
+                                <a href=format!("/members/{}", slot.recipient_id) class="block mt-1 text-indigo-600 hover:underline font-mono text-xs truncate">
+                                    {slot.recipient_id.to_string()}
+                                </a>
+                                <p class="font-semibold text-gray-900">{format_sats(slot.amount)}</p>
+                            </li>
+                        }
+                    }).collect_view()}
+                </ol>
+            </div>
+        </div>
+    }
+}
+
+#[component]
+fn CreatePlanForm(group_id: String, action: ServerAction<CreateContributionPlan>) -> impl IntoView {
+    let input_class = "block w-full rounded-md border-gray-300 shadow-sm sm:text-sm";
+
+    view! {
+        <div class="bg-white shadow rounded-lg p-6">
+            <h3 class="text-lg font-medium text-gray-900 mb-4">"New Contribution Plan"</h3>
+            <ActionForm action=action attr:class="grid grid-cols-1 sm:grid-cols-2 gap-4">
+                <input type="hidden" name="group_id" value=group_id/>
+                <input name="name" type="text" required=true placeholder="Plan name" class=input_class/>
+                <input name="amount" type="number" min="1" required=true placeholder="Amount per member (sats)" class=input_class/>
+                <select name="frequency" class=input_class>
+                    <option value="weekly">"Weekly"</option>
+                    <option value="fortnightly">"Fortnightly"</option>
+                    <option value="monthly" selected=true>"Monthly"</option>
+                </select>
+                <input name="start_on" type="date" required=true class=input_class/>
+                <div class="sm:col-span-2">
+                    <label for="plan-members" class="block text-sm font-medium text-gray-700 mb-1">
+                        "Members in payout order, one member_id:wallet_id per line"
+                    </label>
+                    <textarea id="plan-members" name="members" rows="4" required=true class=format!("font-mono {}", input_class)></textarea>
+                </div>
+                <div class="sm:col-span-2 flex justify-end">
+                    <button
+                        type="submit"
+                        class="bg-indigo-600 hover:bg-indigo-700 text-white font-medium py-2 px-4 rounded-lg disabled:opacity-50"
+                        disabled=move || action.pending().get()
+                    >
+                        {move || if action.pending().get() { "Creating..." } else { "Create Plan" }}
+                    </button>
+                </div>
+            </ActionForm>
+        </div>
+    }
+}
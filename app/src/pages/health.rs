@@ -0,0 +1,441 @@
+use crate::api::dashboard_client::{
+    InfrastructureMetrics, OperationalMetricsResponse, ResourceMetrics, ServiceMetrics,
+    SystemMetrics,
+};
+use crate::api::get_system_health;
+use crate::components::ui::{ChartKind, Spinner, TrendChart};
+use crate::pages::dashboard::{chart_points, TrendPeriod};
+use crate::server::health::{Probe, ProbeStatus, SystemHealthReport};
+use crate::utils::{format_ms, percent};
+use chrono::Utc;
+use leptos::prelude::*;
+
+/// How often the page re-checks while it is open
+const REFRESH_SECS: u64 = 15;
+
+fn status_colors(status: ProbeStatus) -> (&'static str, &'static str, &'static str) {
+    match status {
+        ProbeStatus::Ok => ("bg-green-50", "text-green-800", "bg-green-500"),
+        ProbeStatus::Warning => ("bg-yellow-50", "text-yellow-800", "bg-yellow-500"),
+        ProbeStatus::Failed => ("bg-red-50", "text-red-800", "bg-red-500"),
+    }
+}
+
+fn status_label(status: ProbeStatus) -> &'static str {
+    match status {
+        ProbeStatus::Ok => "Healthy",
+        ProbeStatus::Warning => "Degraded",
+        ProbeStatus::Failed => "Failing",
+    }
+}
+
+/// Map the backend's own status words onto probe statuses
+fn reported_status(status: &str) -> ProbeStatus {
+    match status {
+        "healthy" | "online" => ProbeStatus::Ok,
+        "warning" | "degraded" => ProbeStatus::Warning,
+        _ => ProbeStatus::Failed,
+    }
+}
+
+fn format_uptime(seconds: f64) -> String {
+    let seconds = seconds.max(0.0) as u64;
+    let (days, hours, minutes) = (
+        seconds / 86_400,
+        seconds % 86_400 / 3_600,
+        seconds % 3_600 / 60,
+    );
+    if days > 0 {
+        format!("{}d {}h", days, hours)
+    } else if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else {
+        format!("{}m", minutes)
+    }
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+#[component]
+pub fn SystemHealthPage() -> impl IntoView {
+    let (refresh_tick, set_refresh_tick) = signal(0u32);
+
+    // Keep the page live while it is open
+    Effect::new(move |_| {
+        set_interval(
+            move || set_refresh_tick.update(|tick| *tick += 1),
+            std::time::Duration::from_secs(REFRESH_SECS),
+        );
+    });
+
+    let report = Resource::new(move || refresh_tick.get(), |_| get_system_health());
+
+    view! {
+        <div class="space-y-6">
+            <div class="flex flex-col md:flex-row md:items-center md:justify-between gap-4">
+                <div>
+                    <h1 class="text-2xl font-semibold text-gray-900">"System Health"</h1>
+                    <p class="mt-1 text-sm text-gray-500">
+                        {format!("Backend metrics and this server's own checks, refreshed every {}s", REFRESH_SECS)}
+                    </p>
+                </div>
+                <button
+                    type="button"
+                    class="border border-gray-300 text-gray-700 hover:bg-gray-50 text-sm font-medium py-2 px-4 rounded-md"
+                    on:click=move |_| set_refresh_tick.update(|tick| *tick += 1)
+                >
+                    "Refresh now"
+                </button>
+            </div>
+
+            // Transition keeps the last report on screen while the next one loads
+            <Transition fallback=move || view! { <div class="flex justify-center py-12"><Spinner /></div> }>
+                {move || report.get().map(|result| match result {
+                    Ok(report) => view! { <HealthReport report=report /> }.into_any(),
+                    Err(e) => view! {
+                        <div class="bg-red-50 border border-red-200 rounded-lg p-4 text-sm text-red-700" role="alert">
+                            {format!("Could not run the health checks: {}", e)}
+                        </div>
+                    }.into_any(),
+                })}
+            </Transition>
+        </div>
+    }
+}
+
+#[component]
+fn HealthReport(report: SystemHealthReport) -> impl IntoView {
+    let overall = report.overall();
+    let (bg, text, dot) = status_colors(overall);
+    let checked = report
+        .checked_at
+        .with_timezone(&chrono::Local)
+        .format("%H:%M:%S")
+        .to_string();
+
+    view! {
+        <div class=format!("flex items-center justify-between p-4 rounded-lg {}", bg)>
+            <div class="flex items-center">
+                <div class=format!("w-3 h-3 rounded-full mr-3 {}", dot)></div>
+                <span class=format!("font-medium {}", text)>{status_label(overall)}</span>
+            </div>
+            <span class=format!("text-xs {}", text)>{format!("Checked at {}", checked)}</span>
+        </div>
+
+        <div class="grid grid-cols-1 md:grid-cols-3 gap-4">
+            {report.probes.into_iter().map(|probe| view! { <ProbeCard probe=probe /> }).collect_view()}
+        </div>
+
+        {match (report.metrics, report.metrics_error) {
+            (Some(metrics), _) => view! { <OperationalMetrics metrics=metrics /> }.into_any(),
+            (None, error) => view! {
+                <div class="bg-yellow-50 border border-yellow-200 rounded-lg p-4 text-sm text-yellow-800" role="alert">
+                    {format!(
+                        "Backend metrics are unavailable: {}",
+                        error.unwrap_or_else(|| "no data returned".to_string())
+                    )}
+                </div>
+            }.into_any(),
+        }}
+    }
+    .into_any()
+}
+
+#[component]
+fn ProbeCard(probe: Probe) -> impl IntoView {
+    let (bg, text, dot) = status_colors(probe.status);
+    view! {
+        <div class=format!("p-4 rounded-lg {}", bg)>
+            <div class="flex items-center">
+                <div class=format!("w-3 h-3 rounded-full mr-3 {}", dot)></div>
+                <h3 class=format!("font-medium {}", text)>{probe.name}</h3>
+            </div>
+            <p class=format!("mt-2 text-xs break-words {}", text)>{probe.detail}</p>
+        </div>
+    }
+    .into_any()
+}
+
+#[component]
+fn OperationalMetrics(metrics: OperationalMetricsResponse) -> impl IntoView {
+    let mut services: Vec<_> = metrics.services.into_iter().collect();
+    services.sort_by(|a, b| a.0.cmp(&b.0));
+
+    view! {
+        <SystemSection system=metrics.system />
+        <ServicesSection services=services />
+        <ResourcesSection resources=metrics.resources />
+        <InfrastructureSection infrastructure=metrics.infrastructure />
+    }
+    .into_any()
+}
+
+#[component]
+fn SystemSection(system: SystemMetrics) -> impl IntoView {
+    let health = system.health;
+    let performance = system.performance;
+    let (_, text, dot) = status_colors(reported_status(&health.status));
+    let today = Utc::now().date_naive();
+    let response_trend = chart_points(&performance.response_time.trend, TrendPeriod::All, today);
+    let throughput_trend = chart_points(&performance.throughput.trend, TrendPeriod::All, today);
+    let facts = vec![
+        ("Uptime", format_uptime(health.uptime)),
+        ("Version", health.version),
+        ("Last restart", health.last_restart),
+        ("Error rate", percent(performance.errors.error_rate)),
+        ("Errors", performance.errors.total_errors.to_string()),
+        (
+            "Requests",
+            format!(
+                "{:.1}/s (peak {:.1}/s)",
+                performance.throughput.requests_per_second, performance.throughput.peak_rps
+            ),
+        ),
+    ];
+    let latencies = vec![
+        ("Average", performance.response_time.average),
+        ("p50", performance.response_time.p50),
+        ("p95", performance.response_time.p95),
+        ("p99", performance.response_time.p99),
+    ];
+
+    view! {
+        <div class="bg-white shadow rounded-lg p-6 space-y-6">
+            <div class="flex items-center">
+                <div class=format!("w-3 h-3 rounded-full mr-3 {}", dot)></div>
+                <h3 class="text-lg font-medium text-gray-900">"Backend"</h3>
+                <span class=format!("ml-3 text-sm {}", text)>{health.status}</span>
+            </div>
+            <dl class="grid grid-cols-2 lg:grid-cols-6 gap-4">
+                {facts.into_iter().map(|(label, value)| view! {
+                    <div class="p-4 bg-gray-50 rounded-lg">
+                        <dt class="text-xs text-gray-500">{label}</dt>
+                        <dd class="text-sm font-semibold text-gray-900 break-words">{value}</dd>
+                    </div>
+                }).collect_view()}
+            </dl>
+            <dl class="grid grid-cols-4 gap-4">
+                {latencies.into_iter().map(|(label, value)| view! {
+                    <div class="p-4 bg-gray-50 rounded-lg">
+                        <dt class="text-xs text-gray-500">{label}</dt>
+                        <dd class="text-lg font-semibold text-gray-900">{format_ms(value)}</dd>
+                    </div>
+                }).collect_view()}
+            </dl>
+            <div class="grid grid-cols-1 lg:grid-cols-2 gap-6">
+                <TrendChart points=response_trend kind=ChartKind::Line title="Response time" color="#9333ea" format=format_ms />
+                <TrendChart points=throughput_trend kind=ChartKind::Area title="Throughput" color="#0d9488" />
+            </div>
+        </div>
+    }
+    .into_any()
+}
+
+#[component]
+fn ServicesSection(services: Vec<(String, ServiceMetrics)>) -> impl IntoView {
+    view! {
+        <div class="bg-white shadow rounded-lg p-6 space-y-4">
+            <h3 class="text-lg font-medium text-gray-900">"Services"</h3>
+            {if services.is_empty() {
+                view! { <p class="text-sm text-gray-500">"The backend reported no services."</p> }.into_any()
+            } else {
+                view! {
+                    <div class="overflow-x-auto">
+                        <table class="min-w-full divide-y divide-gray-200 text-sm">
+                            <thead class="bg-gray-50">
+                                <tr>
+                                    <th class="px-4 py-2 text-left font-medium text-gray-500">"Service"</th>
+                                    <th class="px-4 py-2 text-left font-medium text-gray-500">"Status"</th>
+                                    <th class="px-4 py-2 text-right font-medium text-gray-500">"Response"</th>
+                                    <th class="px-4 py-2 text-right font-medium text-gray-500">"Error rate"</th>
+                                    <th class="px-4 py-2 text-left font-medium text-gray-500">"Dependencies"</th>
+                                    <th class="px-4 py-2 text-left font-medium text-gray-500">"Last check"</th>
+                                </tr>
+                            </thead>
+                            <tbody class="divide-y divide-gray-100">
+                                {services.into_iter().map(|(name, service)| {
+                                    let (_, text, dot) = status_colors(reported_status(&service.status));
+                                    view! {
+                                        <tr>
+                                            <td class="px-4 py-2 text-gray-900">{name}</td>
+                                            <td class="px-4 py-2">
+                                                <span class=format!("inline-flex items-center {}", text)>
+                                                    <span class=format!("w-2 h-2 rounded-full mr-2 {}", dot)></span>
+                                                    {service.status}
+                                                </span>
+                                            </td>
+                                            <td class="px-4 py-2 text-right text-gray-700">{format_ms(service.response_time)}</td>
+                                            <td class="px-4 py-2 text-right text-gray-700">{percent(service.error_rate)}</td>
+                                            <td class="px-4 py-2 text-gray-700">
+                                                {service.dependencies.into_iter().map(|dependency| {
+                                                    let (_, text, _) = status_colors(reported_status(&dependency.status));
+                                                    view! {
+                                                        <span class=format!("mr-3 {}", text)>
+                                                            {format!(
+                                                                "{}{}: {}",
+                                                                dependency.name,
+                                                                if dependency.critical { "*" } else { "" },
+                                                                dependency.status
+                                                            )}
+                                                        </span>
+                                                    }
+                                                }).collect_view()}
+                                            </td>
+                                            <td class="px-4 py-2 text-gray-500">{service.last_health_check}</td>
+                                        </tr>
+                                    }
+                                }).collect_view()}
+                            </tbody>
+                        </table>
+                    </div>
+                    <p class="text-xs text-gray-500">"* critical dependency"</p>
+                }.into_any()
+            }}
+        </div>
+    }
+    .into_any()
+}
+
+#[component]
+fn UsageBar(label: &'static str, value: f64, detail: String) -> impl IntoView {
+    let bar = if value >= 90.0 {
+        "bg-red-500"
+    } else if value >= 75.0 {
+        "bg-yellow-500"
+    } else {
+        "bg-green-500"
+    };
+    view! {
+        <div>
+            <div class="flex justify-between text-sm">
+                <span class="text-gray-700">{label}</span>
+                <span class="text-gray-900">{detail}</span>
+            </div>
+            <div class="w-full bg-gray-200 rounded-full h-2 mt-1">
+                <div class=format!("{} h-2 rounded-full", bar) style=format!("width: {:.1}%", value.clamp(0.0, 100.0))></div>
+            </div>
+        </div>
+    }
+    .into_any()
+}
+
+#[component]
+fn ResourcesSection(resources: ResourceMetrics) -> impl IntoView {
+    let server = resources.server;
+    let pool = resources.database.connection_pool;
+    let database = resources.database.performance;
+    let cache = resources.cache;
+    let pool_usage = if pool.max_connections > 0 {
+        pool.active as f64 / pool.max_connections as f64 * 100.0
+    } else {
+        0.0
+    };
+
+    view! {
+        <div class="grid grid-cols-1 lg:grid-cols-3 gap-6">
+            <div class="bg-white shadow rounded-lg p-6 space-y-4">
+                <h3 class="text-lg font-medium text-gray-900">"Server"</h3>
+                <UsageBar label="CPU" value=server.cpu_usage detail=percent(server.cpu_usage) />
+                <UsageBar label="Memory" value=server.memory_usage detail=percent(server.memory_usage) />
+                <UsageBar label="Disk" value=server.disk_usage detail=percent(server.disk_usage) />
+                <p class="text-xs text-gray-500">
+                    {format!(
+                        "{} active connections · {} in · {} out",
+                        server.network_activity.connections_active,
+                        format_bytes(server.network_activity.bytes_in),
+                        format_bytes(server.network_activity.bytes_out)
+                    )}
+                </p>
+            </div>
+            <div class="bg-white shadow rounded-lg p-6 space-y-4">
+                <h3 class="text-lg font-medium text-gray-900">"Database"</h3>
+                <UsageBar
+                    label="Connection pool"
+                    value=pool_usage
+                    detail=format!("{} of {} in use", pool.active, pool.max_connections)
+                />
+                <dl class="grid grid-cols-2 gap-2 text-sm">
+                    <dt class="text-gray-500">"Idle / waiting"</dt>
+                    <dd class="text-gray-900 text-right">{format!("{} / {}", pool.idle, pool.waiting)}</dd>
+                    <dt class="text-gray-500">"Query time"</dt>
+                    <dd class="text-gray-900 text-right">{format_ms(database.query_time)}</dd>
+                    <dt class="text-gray-500">"Slow queries"</dt>
+                    <dd class="text-gray-900 text-right">{database.slow_queries}</dd>
+                    <dt class="text-gray-500">"Deadlocks"</dt>
+                    <dd class="text-gray-900 text-right">{database.deadlocks}</dd>
+                </dl>
+            </div>
+            <div class="bg-white shadow rounded-lg p-6 space-y-4">
+                <h3 class="text-lg font-medium text-gray-900">"Cache"</h3>
+                <UsageBar label="Hit rate" value=cache.hit_rate detail=percent(cache.hit_rate) />
+                <dl class="grid grid-cols-2 gap-2 text-sm">
+                    <dt class="text-gray-500">"Memory"</dt>
+                    <dd class="text-gray-900 text-right">{format_bytes(cache.memory_usage)}</dd>
+                    <dt class="text-gray-500">"Keys"</dt>
+                    <dd class="text-gray-900 text-right">{cache.key_count}</dd>
+                    <dt class="text-gray-500">"Evictions"</dt>
+                    <dd class="text-gray-900 text-right">{cache.evictions}</dd>
+                </dl>
+            </div>
+        </div>
+    }
+    .into_any()
+}
+
+#[component]
+fn InfrastructureSection(infrastructure: InfrastructureMetrics) -> impl IntoView {
+    let balancer = infrastructure.load_balancer;
+    let monitoring = infrastructure.monitoring;
+    let mut distribution: Vec<_> = balancer.request_distribution.into_iter().collect();
+    distribution.sort_by(|a, b| a.0.cmp(&b.0));
+
+    view! {
+        <div class="bg-white shadow rounded-lg p-6 space-y-4">
+            <h3 class="text-lg font-medium text-gray-900">"Infrastructure"</h3>
+            <dl class="grid grid-cols-2 lg:grid-cols-4 gap-4">
+                <div class="p-4 bg-gray-50 rounded-lg">
+                    <dt class="text-xs text-gray-500">"Servers up"</dt>
+                    <dd class="text-lg font-semibold text-gray-900">
+                        {format!("{} / {}", balancer.active_servers, balancer.total_servers)}
+                    </dd>
+                </div>
+                <div class="p-4 bg-gray-50 rounded-lg">
+                    <dt class="text-xs text-gray-500">"Active alerts"</dt>
+                    <dd class="text-lg font-semibold text-gray-900">{monitoring.alerts_active}</dd>
+                </div>
+                <div class="p-4 bg-gray-50 rounded-lg">
+                    <dt class="text-xs text-gray-500">"Resolved alerts"</dt>
+                    <dd class="text-lg font-semibold text-gray-900">{monitoring.alerts_resolved}</dd>
+                </div>
+                <div class="p-4 bg-gray-50 rounded-lg">
+                    <dt class="text-xs text-gray-500">"Monitoring coverage"</dt>
+                    <dd class="text-lg font-semibold text-gray-900">{percent(monitoring.monitoring_coverage)}</dd>
+                </div>
+            </dl>
+            {(!distribution.is_empty()).then(|| view! {
+                <ul class="divide-y divide-gray-100 text-sm">
+                    {distribution.into_iter().map(|(server, share)| view! {
+                        <li class="flex justify-between py-1.5">
+                            <span class="text-gray-700">{server}</span>
+                            <span class="text-gray-900">{percent(share)}</span>
+                        </li>
+                    }).collect_view()}
+                </ul>
+            })}
+        </div>
+    }
+    .into_any()
+}
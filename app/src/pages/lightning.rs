@@ -0,0 +1,304 @@
+// Lightning deposits and payments for a member's federation wallets, shown on
+// the member detail page
+
+use crate::api::traits::lightning::{LightningInvoice, LightningPayment};
+use crate::api::traits::wallets::TransactionStatus;
+use crate::components::ui::Spinner;
+use crate::utils::format_sats;
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A wallet that can send and receive over Lightning, with its activity
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LightningWallet {
+    pub wallet_id: uuid::Uuid,
+    pub name: String,
+    /// Newest first
+    pub invoices: Vec<LightningInvoice>,
+    /// Newest first
+    pub payments: Vec<LightningPayment>,
+}
+
+/// Members may only use their own wallets; admins can see and invoice any
+#[cfg(feature = "ssr")]
+async fn wallet_for(
+    wallet_id: uuid::Uuid,
+    user: &crate::api::types::User,
+) -> Result<crate::api::traits::wallets::Wallet, ServerFnError> {
+    use crate::api::{backends::wallets_api, config::ApiConfig};
+
+    let wallet = wallets_api(&ApiConfig::from_env())
+        .map_err(|e| ServerFnError::new(e.to_string()))?
+        .get_wallet(wallet_id)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+    if !user.is_admin() && wallet.user_id != user.id {
+        return Err(ServerFnError::new("You can only use your own wallets"));
+    }
+    Ok(wallet)
+}
+
+#[server(GetMemberLightning, "/api", "GetJson")]
+pub async fn get_member_lightning(
+    member_id: uuid::Uuid,
+) -> Result<Vec<LightningWallet>, ServerFnError> {
+    use crate::api::{
+        backends::{lightning_api, wallets_api},
+        client::get_request_user,
+        config::ApiConfig,
+        traits::wallets::WalletType,
+    };
+
+    let user = get_request_user().await?;
+    if !user.is_admin() && user.id != member_id {
+        return Err(ServerFnError::new("You can only view your own wallets"));
+    }
+
+    let config = ApiConfig::from_env();
+    let wallets = wallets_api(&config)
+        .map_err(|e| ServerFnError::new(e.to_string()))?
+        .get_user_wallets(member_id)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+    let api = lightning_api(&config).map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    let mut lightning_wallets = Vec::new();
+    for wallet in wallets.into_iter().filter(|wallet| {
+        matches!(
+            wallet.wallet_type,
+            WalletType::Lightning | WalletType::Fedimint
+        )
+    }) {
+        lightning_wallets.push(LightningWallet {
+            wallet_id: wallet.id,
+            name: wallet.name,
+            invoices: api
+                .get_wallet_invoices(wallet.id)
+                .await
+                .map_err(|e| ServerFnError::new(e.to_string()))?,
+            payments: api
+                .get_wallet_payments(wallet.id)
+                .await
+                .map_err(|e| ServerFnError::new(e.to_string()))?,
+        });
+    }
+    Ok(lightning_wallets)
+}
+
+#[server(CreateDepositInvoice, "/api")]
+pub async fn create_deposit_invoice(
+    wallet_id: uuid::Uuid,
+    amount_sats: u64,
+    description: Option<String>,
+) -> Result<LightningInvoice, ServerFnError> {
+    use crate::api::{
+        backends::lightning_api, client::get_request_user, config::ApiConfig,
+        traits::lightning::CreateInvoiceRequest,
+    };
+
+    let user = get_request_user().await?;
+    let wallet = wallet_for(wallet_id, &user).await?;
+    let description = description
+        .map(|d| d.trim().to_string())
+        .filter(|d| !d.is_empty())
+        .unwrap_or_else(|| format!("Deposit to {}", wallet.name));
+
+    let api =
+        lightning_api(&ApiConfig::from_env()).map_err(|e| ServerFnError::new(e.to_string()))?;
+    api.create_deposit_invoice(CreateInvoiceRequest {
+        wallet_id,
+        amount_sats,
+        description,
+        expiry_secs: DEPOSIT_INVOICE_EXPIRY_SECS,
+    })
+    .await
+    .map_err(|e| ServerFnError::new(e.to_string()))
+}
+
+#[server(PayLightningInvoice, "/api")]
+pub async fn pay_lightning_invoice(
+    wallet_id: uuid::Uuid,
+    bolt11: String,
+) -> Result<LightningPayment, ServerFnError> {
+    use crate::api::{
+        backends::lightning_api, client::get_request_user, config::ApiConfig,
+        traits::lightning::PayInvoiceRequest,
+    };
+
+    // Withdrawals are made by the wallet's owner only
+    let user = get_request_user().await?;
+    let wallet = wallet_for(wallet_id, &user).await?;
+    if wallet.user_id != user.id {
+        return Err(ServerFnError::new(
+            "Only the wallet's owner can pay from it",
+        ));
+    }
+
+    let api =
+        lightning_api(&ApiConfig::from_env()).map_err(|e| ServerFnError::new(e.to_string()))?;
+    api.pay_invoice(PayInvoiceRequest { wallet_id, bolt11 }, &user)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))
+}
+
+/// Deposit invoices stay payable for a day
+const DEPOSIT_INVOICE_EXPIRY_SECS: u32 = 24 * 60 * 60;
+
+fn status_badge(status: &TransactionStatus) -> (&'static str, &'static str) {
+    match status {
+        TransactionStatus::Pending => ("bg-yellow-100 text-yellow-800", "Pending"),
+        TransactionStatus::Confirmed => ("bg-green-100 text-green-800", "Confirmed"),
+        TransactionStatus::Failed => ("bg-red-100 text-red-800", "Failed"),
+        TransactionStatus::Cancelled => ("bg-gray-100 text-gray-700", "Cancelled"),
+    }
+}
+
+#[component]
+pub fn MemberLightningPanel(member_id: uuid::Uuid) -> impl IntoView {
+    let invoice_action = ServerAction::<CreateDepositInvoice>::new();
+    let pay_action = ServerAction::<PayLightningInvoice>::new();
+    let wallets = Resource::new(
+        move || (invoice_action.version().get(), pay_action.version().get()),
+        move |_| get_member_lightning(member_id),
+    );
+
+    let action_error = move || {
+        [
+            invoice_action.value().get().and_then(|r| r.err()),
+            pay_action.value().get().and_then(|r| r.err()),
+        ]
+        .into_iter()
+        .flatten()
+        .next()
+        .map(|e| e.to_string())
+    };
+    let new_invoice = move || {
+        invoice_action
+            .value()
+            .get()
+            .and_then(|r| r.ok())
+            .map(|invoice| invoice.bolt11)
+    };
+
+    view! {
+        <div class="bg-white shadow rounded-lg p-6 space-y-4">
+            <h3 class="text-lg font-medium text-gray-900">"Lightning"</h3>
+
+            {move || action_error().map(|message| view! {
+                <div class="bg-red-50 border border-red-200 rounded-lg p-4 text-sm text-red-700" role="alert">
+                    {message}
+                </div>
+            })}
+            {move || new_invoice().map(|bolt11| view! {
+                <div class="bg-green-50 border border-green-200 rounded-lg p-4 text-sm text-green-800 space-y-1">
+                    <p class="font-medium">"Invoice ready to pay"</p>
+                    <p class="font-mono text-xs break-all">{bolt11}</p>
+                </div>
+            })}
+
+            <Suspense fallback=move || view! { <div class="flex justify-center py-6"><Spinner /></div> }>
+                {move || {
+                    wallets.get().map(|result| match result {
+                        Ok(wallets) if wallets.is_empty() => view! {
+                            <p class="text-sm text-gray-500">"This member has no Lightning or federation wallets."</p>
+                        }.into_any(),
+                        Ok(wallets) => wallets.into_iter().map(|wallet| view! {
+                            <LightningWalletCard wallet=wallet invoice_action=invoice_action pay_action=pay_action />
+                        }).collect_view().into_any(),
+                        Err(e) => view! {
+                            <div class="bg-red-50 border border-red-200 rounded-lg p-4 text-sm text-red-700" role="alert">
+                                {e.to_string()}
+                            </div>
+                        }.into_any(),
+                    })
+                }}
+            </Suspense>
+        </div>
+    }
+}
+
+#[component]
+fn LightningWalletCard(
+    wallet: LightningWallet,
+    invoice_action: ServerAction<CreateDepositInvoice>,
+    pay_action: ServerAction<PayLightningInvoice>,
+) -> impl IntoView {
+    let input_class = "block w-full rounded-md border-gray-300 shadow-sm sm:text-sm";
+    let invoice_wallet_id = wallet.wallet_id.to_string();
+    let pay_wallet_id = invoice_wallet_id.clone();
+    let has_activity = !wallet.invoices.is_empty() || !wallet.payments.is_empty();
+
+    view! {
+        <div class="border border-gray-100 rounded-lg p-4 space-y-4">
+            <h4 class="text-sm font-semibold text-gray-900">{wallet.name}</h4>
+
+            <div class="grid grid-cols-1 md:grid-cols-2 gap-4">
+                <ActionForm action=invoice_action attr:class="grid grid-cols-2 gap-2">
+                    <input type="hidden" name="wallet_id" value=invoice_wallet_id/>
+                    <input name="amount_sats" type="number" min="1" required=true placeholder="Amount (sats)" class=input_class/>
+                    <input name="description" type="text" placeholder="Description" class=input_class/>
+                    <button type="submit" class="col-span-2 bg-indigo-600 hover:bg-indigo-700 text-white text-sm font-medium py-2 px-4 rounded-md">
+                        "Create Deposit Invoice"
+                    </button>
+                </ActionForm>
+                <ActionForm action=pay_action attr:class="grid grid-cols-1 gap-2">
+                    <input type="hidden" name="wallet_id" value=pay_wallet_id/>
+                    <input name="bolt11" type="text" required=true placeholder="BOLT11 invoice to pay" class=input_class/>
+                    <button type="submit" class="border border-gray-300 hover:bg-gray-50 text-gray-700 text-sm font-medium py-2 px-4 rounded-md">
+                        "Pay Invoice"
+                    </button>
+                </ActionForm>
+            </div>
+
+            {if has_activity {
+                view! {
+                    <div class="overflow-x-auto">
+                        <table class="min-w-full divide-y divide-gray-200 text-sm">
+                            <thead class="bg-gray-50">
+                                <tr>
+                                    <th class="px-4 py-2 text-left font-medium text-gray-500">"Created"</th>
+                                    <th class="px-4 py-2 text-left font-medium text-gray-500">"Kind"</th>
+                                    <th class="px-4 py-2 text-right font-medium text-gray-500">"Amount"</th>
+                                    <th class="px-4 py-2 text-right font-medium text-gray-500">"Fee"</th>
+                                    <th class="px-4 py-2 text-left font-medium text-gray-500">"Status"</th>
+                                </tr>
+                            </thead>
+                            <tbody class="divide-y divide-gray-100">
+                                {wallet.invoices.into_iter().map(|invoice| {
+                                    let (badge, label) = status_badge(&invoice.status);
+                                    view! {
+                                        <tr>
+                                            <td class="px-4 py-2 text-gray-700">{invoice.created_at.format("%Y-%m-%d %H:%M").to_string()}</td>
+                                            <td class="px-4 py-2 text-gray-700">"Deposit"</td>
+                                            <td class="px-4 py-2 text-right text-gray-900">{format_sats(invoice.amount_sats)}</td>
+                                            <td class="px-4 py-2 text-right text-gray-500">"—"</td>
+                                            <td class="px-4 py-2">
+                                                <span class=format!("inline-flex px-2 py-0.5 rounded-full text-xs font-medium {}", badge)>{label}</span>
+                                            </td>
+                                        </tr>
+                                    }
+                                }).collect_view()}
+                                {wallet.payments.into_iter().map(|payment| {
+                                    let (badge, label) = status_badge(&payment.status);
+                                    view! {
+                                        <tr>
+                                            <td class="px-4 py-2 text-gray-700">{payment.created_at.format("%Y-%m-%d %H:%M").to_string()}</td>
+                                            <td class="px-4 py-2 text-gray-700">"Payment"</td>
+                                            <td class="px-4 py-2 text-right text-gray-900">{format_sats(payment.amount_sats)}</td>
+                                            <td class="px-4 py-2 text-right text-gray-500">{format_sats(payment.fee_sats)}</td>
+                                            <td class="px-4 py-2" title=payment.failure_reason.unwrap_or_default()>
+                                                <span class=format!("inline-flex px-2 py-0.5 rounded-full text-xs font-medium {}", badge)>{label}</span>
+                                            </td>
+                                        </tr>
+                                    }
+                                }).collect_view()}
+                            </tbody>
+                        </table>
+                    </div>
+                }.into_any()
+            } else {
+                view! { <p class="text-sm text-gray-500">"No Lightning activity yet."</p> }.into_any()
+            }}
+        </div>
+    }
+}
@@ -0,0 +1,782 @@
+use crate::api::traits::loans::{GuaranteeStatus, Loan, LoanProduct, LoanStatus};
+use crate::utils::format_sats;
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoansOverview {
+    pub products: Vec<LoanProduct>,
+    pub loans: Vec<Loan>,
+    /// Pending applications the signed-in member has been asked to guarantee
+    pub guarantee_requests: Vec<Loan>,
+    pub user_id: uuid::Uuid,
+}
+
+#[server(GetLoansOverview, "/api", "GetJson")]
+pub async fn get_loans_overview() -> Result<LoansOverview, ServerFnError> {
+    use crate::api::{
+        backends::loans_api, client::get_request_user, config::ApiConfig,
+        traits::loans::LoanFilter, types::PaginationQuery,
+    };
+
+    // Members see only their own loans
+    let user = get_request_user().await?;
+    let filter = LoanFilter {
+        user_id: (!user.is_admin()).then_some(user.id),
+        ..LoanFilter::default()
+    };
+
+    let api = loans_api(&ApiConfig::from_env()).map_err(|e| ServerFnError::new(e.to_string()))?;
+    let products = api
+        .get_loan_products()
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+    let loans = api
+        .get_loans(
+            filter,
+            PaginationQuery {
+                page: Some(1),
+                limit: Some(200),
+            },
+        )
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+    let guarantee_requests = api
+        .get_loans(
+            LoanFilter {
+                guarantor_id: Some(user.id),
+                status: Some(LoanStatus::PendingApproval),
+                ..LoanFilter::default()
+            },
+            PaginationQuery {
+                page: Some(1),
+                limit: Some(200),
+            },
+        )
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    Ok(LoansOverview {
+        products,
+        loans: loans.data,
+        guarantee_requests: guarantee_requests.data,
+        user_id: user.id,
+    })
+}
+
+#[server(CreateLoanProduct, "/api")]
+#[allow(clippy::too_many_arguments)]
+pub async fn create_loan_product(
+    name: String,
+    annual_interest_rate: String,
+    term_months: u32,
+    min_amount: u64,
+    max_amount: u64,
+    max_savings_multiple: Option<String>,
+    max_sats_per_share: Option<String>,
+    required_guarantors: u32,
+) -> Result<LoanProduct, ServerFnError> {
+    use crate::api::{
+        backends::loans_api, client::require_admin, config::ApiConfig,
+        traits::loans::CreateLoanProductRequest,
+    };
+
+    let operator = require_admin("create loan products").await?;
+
+    let annual_interest_rate = annual_interest_rate
+        .trim()
+        .parse()
+        .map_err(|_| ServerFnError::new("Interest rate must be a number"))?;
+
+    let api = loans_api(&ApiConfig::from_env()).map_err(|e| ServerFnError::new(e.to_string()))?;
+    api.create_loan_product(
+        CreateLoanProductRequest {
+            name,
+            annual_interest_rate,
+            term_months,
+            min_amount,
+            max_amount,
+            max_savings_multiple: parse_optional(max_savings_multiple, "Savings multiple")?,
+            max_sats_per_share: parse_optional(max_sats_per_share, "Sats per share")?,
+            required_guarantors,
+        },
+        &operator,
+    )
+    .await
+    .map_err(|e| ServerFnError::new(e.to_string()))
+}
+
+#[server(ApplyForLoan, "/api")]
+pub async fn apply_for_loan(
+    product_id: uuid::Uuid,
+    user_id: uuid::Uuid,
+    amount: u64,
+    purpose: Option<String>,
+    guarantors: Option<String>,
+) -> Result<Loan, ServerFnError> {
+    use crate::api::{
+        backends::loans_api,
+        client::get_request_user,
+        config::ApiConfig,
+        traits::loans::{Guarantor, LoanApplicationRequest},
+    };
+
+    let maker = get_request_user().await?;
+    if user_id != maker.id && !maker.is_admin() {
+        return Err(ServerFnError::new(
+            "Only admins can apply for a loan on another member's behalf",
+        ));
+    }
+
+    // Guarantors are entered as "member_id:amount" pairs separated by commas
+    let guarantors = guarantors
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            let (id, amount) = entry.split_once(':').ok_or_else(|| {
+                ServerFnError::new(format!(
+                    "Guarantor '{}' must be written as member_id:amount",
+                    entry
+                ))
+            })?;
+            Ok(Guarantor {
+                user_id: id
+                    .trim()
+                    .parse()
+                    .map_err(|_| ServerFnError::new(format!("Invalid guarantor ID '{}'", id)))?,
+                amount: amount.trim().parse().map_err(|_| {
+                    ServerFnError::new(format!("Invalid guaranteed amount '{}'", amount))
+                })?,
+                status: GuaranteeStatus::Pending,
+            })
+        })
+        .collect::<Result<Vec<_>, ServerFnError>>()?;
+
+    let api = loans_api(&ApiConfig::from_env()).map_err(|e| ServerFnError::new(e.to_string()))?;
+    api.apply_for_loan(
+        LoanApplicationRequest {
+            product_id,
+            user_id,
+            amount,
+            purpose,
+            guarantors,
+        },
+        &maker,
+    )
+    .await
+    .map_err(|e| ServerFnError::new(e.to_string()))
+}
+
+#[server(RespondToGuarantee, "/api")]
+pub async fn respond_to_guarantee(
+    loan_id: uuid::Uuid,
+    accept: bool,
+) -> Result<Loan, ServerFnError> {
+    use crate::api::{backends::loans_api, client::get_request_user, config::ApiConfig};
+
+    let guarantor = get_request_user().await?;
+    let api = loans_api(&ApiConfig::from_env()).map_err(|e| ServerFnError::new(e.to_string()))?;
+    api.respond_to_guarantee(loan_id, &guarantor, accept)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))
+}
+
+#[server(ApproveLoan, "/api")]
+pub async fn approve_loan(loan_id: uuid::Uuid) -> Result<Loan, ServerFnError> {
+    use crate::api::{backends::loans_api, client::get_request_user, config::ApiConfig};
+
+    let checker = get_request_user().await?;
+    let api = loans_api(&ApiConfig::from_env()).map_err(|e| ServerFnError::new(e.to_string()))?;
+    api.approve_loan(loan_id, &checker)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))
+}
+
+#[server(RejectLoan, "/api")]
+pub async fn reject_loan(
+    loan_id: uuid::Uuid,
+    reason: Option<String>,
+) -> Result<Loan, ServerFnError> {
+    use crate::api::{backends::loans_api, client::get_request_user, config::ApiConfig};
+
+    let checker = get_request_user().await?;
+    let api = loans_api(&ApiConfig::from_env()).map_err(|e| ServerFnError::new(e.to_string()))?;
+    api.reject_loan(loan_id, &checker, reason)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))
+}
+
+#[server(DisburseLoan, "/api")]
+pub async fn disburse_loan(
+    loan_id: uuid::Uuid,
+    wallet_id: Option<String>,
+) -> Result<Loan, ServerFnError> {
+    use crate::api::{
+        backends::{loans_api, wallets_api},
+        client::get_request_user,
+        config::ApiConfig,
+    };
+
+    let operator = get_request_user().await?;
+    let config = ApiConfig::from_env();
+    let api = loans_api(&config).map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    // Default to the borrower's oldest wallet when none is chosen
+    let wallet_id = match parse_optional::<uuid::Uuid>(wallet_id, "Wallet ID")? {
+        Some(id) => id,
+        None => {
+            let loan = api
+                .get_loan(loan_id)
+                .await
+                .map_err(|e| ServerFnError::new(e.to_string()))?;
+            wallets_api(&config)
+                .map_err(|e| ServerFnError::new(e.to_string()))?
+                .get_user_wallets(loan.user_id)
+                .await
+                .map_err(|e| ServerFnError::new(e.to_string()))?
+                .first()
+                .map(|wallet| wallet.id)
+                .ok_or_else(|| ServerFnError::new("Borrower has no wallet to disburse into"))?
+        }
+    };
+
+    api.disburse_loan(loan_id, wallet_id, &operator)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))
+}
+
+#[server(RecordLoanRepayment, "/api")]
+pub async fn record_loan_repayment(
+    loan_id: uuid::Uuid,
+    amount: u64,
+    wallet_id: Option<String>,
+) -> Result<Loan, ServerFnError> {
+    use crate::api::{
+        backends::loans_api, client::get_request_user, config::ApiConfig,
+        traits::loans::LoanRepaymentRequest,
+    };
+
+    let payer = get_request_user().await?;
+    let api = loans_api(&ApiConfig::from_env()).map_err(|e| ServerFnError::new(e.to_string()))?;
+    api.record_repayment(
+        LoanRepaymentRequest {
+            loan_id,
+            amount,
+            wallet_id: parse_optional(wallet_id, "Wallet ID")?,
+        },
+        &payer,
+    )
+    .await
+    .map_err(|e| ServerFnError::new(e.to_string()))
+}
+
+/// Parse an optional form field, treating blank input as absent
+#[cfg(feature = "ssr")]
+fn parse_optional<T: std::str::FromStr>(
+    value: Option<String>,
+    label: &str,
+) -> Result<Option<T>, ServerFnError> {
+    match value.as_deref().map(str::trim) {
+        None | Some("") => Ok(None),
+        Some(raw) => raw
+            .parse()
+            .map(Some)
+            .map_err(|_| ServerFnError::new(format!("{} is not valid", label))),
+    }
+}
+
+#[component]
+pub fn LoansPage() -> impl IntoView {
+    let create_product_action = ServerAction::<CreateLoanProduct>::new();
+    let apply_action = ServerAction::<ApplyForLoan>::new();
+    let guarantee_action = ServerAction::<RespondToGuarantee>::new();
+    let approve_action = ServerAction::<ApproveLoan>::new();
+    let reject_action = ServerAction::<RejectLoan>::new();
+    let disburse_action = ServerAction::<DisburseLoan>::new();
+    let repay_action = ServerAction::<RecordLoanRepayment>::new();
+
+    let overview = Resource::new(
+        move || {
+            (
+                create_product_action.version().get(),
+                apply_action.version().get(),
+                guarantee_action.version().get(),
+                approve_action.version().get(),
+                reject_action.version().get(),
+                disburse_action.version().get(),
+                repay_action.version().get(),
+            )
+        },
+        |_| get_loans_overview(),
+    );
+
+    let action_error = move || {
+        [
+            create_product_action.value().get().and_then(|r| r.err()),
+            apply_action.value().get().and_then(|r| r.err()),
+            guarantee_action.value().get().and_then(|r| r.err()),
+            approve_action.value().get().and_then(|r| r.err()),
+            reject_action.value().get().and_then(|r| r.err()),
+            disburse_action.value().get().and_then(|r| r.err()),
+            repay_action.value().get().and_then(|r| r.err()),
+        ]
+        .into_iter()
+        .flatten()
+        .next()
+        .map(|e| e.to_string())
+    };
+
+    view! {
+        <div class="space-y-6">
+            <div>
+                <h1 class="text-2xl font-semibold text-gray-900">"Loans"</h1>
+                <p class="mt-1 text-sm text-gray-500">"Loan products, applications, disbursements and repayments"</p>
+            </div>
+
+            {move || action_error().map(|message| view! {
+                <div class="bg-red-50 border border-red-200 rounded-lg p-4 text-sm text-red-700" role="alert">
+                    {message}
+                </div>
+            })}
+
+            <Suspense fallback=move || view! { <div class="bg-white shadow rounded-lg p-6 animate-pulse h-40"></div> }>
+                {move || {
+                    overview.get().map(|result| match result {
+                        Ok(overview) => {
+                            let pending: Vec<Loan> = overview.loans.iter()
+                                .filter(|loan| loan.status == LoanStatus::PendingApproval)
+                                .cloned()
+                                .collect();
+                            let portfolio: Vec<Loan> = overview.loans.iter()
+                                .filter(|loan| loan.status != LoanStatus::PendingApproval)
+                                .cloned()
+                                .collect();
+
+                            view! {
+                                <div class="space-y-6">
+                                    <PortfolioSummary loans=overview.loans.clone() />
+                                    <div class="grid grid-cols-1 xl:grid-cols-2 gap-6">
+                                        <ProductsPanel products=overview.products.clone() action=create_product_action />
+                                        <ApplicationForm products=overview.products action=apply_action />
+                                    </div>
+                                    <GuaranteeRequests
+                                        loans=overview.guarantee_requests
+                                        user_id=overview.user_id
+                                        action=guarantee_action
+                                    />
+                                    <PendingApplications
+                                        loans=pending
+                                        approve_action=approve_action
+                                        reject_action=reject_action
+                                    />
+                                    <LoanPortfolio
+                                        loans=portfolio
+                                        disburse_action=disburse_action
+                                        repay_action=repay_action
+                                    />
+                                </div>
+                            }.into_any()
+                        }
+                        Err(e) => view! {
+                            <div class="bg-red-50 border border-red-200 rounded-xl p-6 text-center" role="alert">
+                                <h3 class="text-sm font-semibold text-red-800">"Unable to load loans"</h3>
+                                <p class="mt-1 text-sm text-red-600">{e.to_string()}</p>
+                            </div>
+                        }.into_any(),
+                    })
+                }}
+            </Suspense>
+        </div>
+    }
+}
+
+#[component]
+fn PortfolioSummary(loans: Vec<Loan>) -> impl IntoView {
+    let now = chrono::Utc::now();
+    let active: Vec<&Loan> = loans
+        .iter()
+        .filter(|loan| loan.status == LoanStatus::Disbursed)
+        .collect();
+    let outstanding: u64 = active.iter().map(|loan| loan.outstanding()).sum();
+    let in_arrears: Vec<_> = active
+        .iter()
+        .map(|loan| loan.arrears(now))
+        .filter(|arrears| arrears.overdue_installments > 0)
+        .collect();
+    let arrears_amount: u64 = in_arrears.iter().map(|a| a.overdue_amount).sum();
+    let pending = loans
+        .iter()
+        .filter(|loan| loan.status == LoanStatus::PendingApproval)
+        .count();
+
+    let cards = vec![
+        ("Active loans", active.len().to_string()),
+        ("Outstanding", format_sats(outstanding)),
+        ("Loans in arrears", in_arrears.len().to_string()),
+        ("Arrears amount", format_sats(arrears_amount)),
+        ("Awaiting approval", pending.to_string()),
+    ];
+
+    view! {
+        <div class="grid grid-cols-2 lg:grid-cols-5 gap-4">
+            {cards.into_iter().map(|(label, value)| view! {
+                <div class="bg-white shadow rounded-lg p-4">
+                    <p class="text-xs text-gray-500">{label}</p>
+                    <p class="mt-1 text-xl font-semibold text-gray-900">{value}</p>
+                </div>
+            }).collect_view()}
+        </div>
+    }
+}
+
+#[component]
+fn ProductsPanel(
+    products: Vec<LoanProduct>,
+    action: ServerAction<CreateLoanProduct>,
+) -> impl IntoView {
+    let input_class = "block w-full rounded-md border-gray-300 shadow-sm sm:text-sm";
+
+    view! {
+        <div class="bg-white shadow rounded-lg p-6 space-y-4">
+            <h3 class="text-lg font-medium text-gray-900">"Loan Products"</h3>
+            {if products.is_empty() {
+                view! { <p class="text-sm text-gray-500">"No loan products yet."</p> }.into_any()
+            } else {
+                view! {
+                    <ul class="divide-y divide-gray-100">
+                        {products.into_iter().map(|product| {
+                            let mut limits = Vec::new();
+                            if let Some(multiple) = product.max_savings_multiple {
+                                limits.push(format!("{}× savings", multiple.normalize()));
+                            }
+                            if let Some(per_share) = product.max_sats_per_share {
+                                limits.push(format!("{} per share", format_sats(per_share)));
+                            }
+                            view! {
+                                <li class="py-3 text-sm">
+                                    <div class="flex justify-between">
+                                        <span class="font-medium text-gray-900">{product.name.clone()}</span>
+                                        <span class="text-gray-700">
+                                            {format!("{}% p.a. · {} months", product.annual_interest_rate.normalize(), product.term_months)}
+                                        </span>
+                                    </div>
+                                    <p class="text-xs text-gray-500">
+                                        {format!(
+                                            "{} – {} · {} guarantor(s){}",
+                                            format_sats(product.min_amount),
+                                            format_sats(product.max_amount),
+                                            product.required_guarantors,
+                                            if limits.is_empty() { String::new() } else { format!(" · up to {}", limits.join(" + ")) }
+                                        )}
+                                    </p>
+                                </li>
+                            }
+                        }).collect_view()}
+                    </ul>
+                }.into_any()
+            }}
+
+            <details class="border-t border-gray-100 pt-4">
+                <summary class="text-sm font-medium text-indigo-600 cursor-pointer">"New product"</summary>
+                <ActionForm action=action attr:class="mt-4 grid grid-cols-2 gap-3">
+                    <input name="name" type="text" required=true placeholder="Name" class=format!("col-span-2 {}", input_class)/>
+                    <input name="annual_interest_rate" type="number" step="0.01" min="0" required=true placeholder="Interest % p.a." class=input_class/>
+                    <input name="term_months" type="number" min="1" required=true placeholder="Term (months)" class=input_class/>
+                    <input name="min_amount" type="number" min="1" required=true placeholder="Min amount (sats)" class=input_class/>
+                    <input name="max_amount" type="number" min="1" required=true placeholder="Max amount (sats)" class=input_class/>
+                    <input name="max_savings_multiple" type="number" step="0.1" min="0" placeholder="Max × savings" class=input_class/>
+                    <input name="max_sats_per_share" type="number" min="0" placeholder="Max sats per share" class=input_class/>
+                    <input name="required_guarantors" type="number" min="0" value="0" required=true placeholder="Guarantors" class=input_class/>
+                    <button type="submit" class="bg-indigo-600 hover:bg-indigo-700 text-white text-sm font-medium py-2 px-4 rounded-md">
+                        "Create Product"
+                    </button>
+                </ActionForm>
+            </details>
+        </div>
+    }
+}
+
+#[component]
+fn ApplicationForm(
+    products: Vec<LoanProduct>,
+    action: ServerAction<ApplyForLoan>,
+) -> impl IntoView {
+    let input_class = "block w-full rounded-md border-gray-300 shadow-sm sm:text-sm";
+    let has_products = !products.is_empty();
+
+    view! {
+        <div class="bg-white shadow rounded-lg p-6 space-y-4">
+            <h3 class="text-lg font-medium text-gray-900">"New Application"</h3>
+            {if has_products {
+                view! {
+                    <ActionForm action=action attr:class="grid grid-cols-2 gap-3">
+                        <select name="product_id" required=true class=format!("col-span-2 {}", input_class)>
+                            {products.into_iter().filter(|p| p.active).map(|product| view! {
+                                <option value=product.id.to_string()>{product.name}</option>
+                            }).collect_view()}
+                        </select>
+                        <input name="user_id" type="text" required=true placeholder="Member ID" class=format!("col-span-2 {}", input_class)/>
+                        <input name="amount" type="number" min="1" required=true placeholder="Amount (sats)" class=input_class/>
+                        <input name="purpose" type="text" placeholder="Purpose" class=input_class/>
+                        <input name="guarantors" type="text" placeholder="Guarantors: member_id:amount, ..." class=format!("col-span-2 {}", input_class)/>
+                        <button type="submit" class="bg-indigo-600 hover:bg-indigo-700 text-white text-sm font-medium py-2 px-4 rounded-md">
+                            "Submit Application"
+                        </button>
+                    </ActionForm>
+                    <p class="text-xs text-gray-500">"Each guarantor must accept their pledge, then a second admin approves the application before disbursement."</p>
+                }.into_any()
+            } else {
+                view! { <p class="text-sm text-gray-500">"Create a loan product before capturing applications."</p> }.into_any()
+            }}
+        </div>
+    }
+}
+
+#[component]
+fn GuaranteeRequests(
+    loans: Vec<Loan>,
+    user_id: uuid::Uuid,
+    action: ServerAction<RespondToGuarantee>,
+) -> impl IntoView {
+    // Only pledges still waiting on this member need an answer
+    let requests: Vec<(Loan, u64)> = loans
+        .into_iter()
+        .filter_map(|loan| {
+            let pledge = loan
+                .guarantors
+                .iter()
+                .find(|g| g.user_id == user_id && g.status == GuaranteeStatus::Pending)?
+                .amount;
+            Some((loan, pledge))
+        })
+        .collect();
+
+    (!requests.is_empty()).then(move || view! {
+        <div class="bg-white shadow rounded-lg p-6">
+            <h3 class="text-lg font-medium text-gray-900 mb-4">"Guarantee Requests"</h3>
+            <ul class="divide-y divide-gray-100">
+                {requests.into_iter().map(|(loan, pledge)| view! {
+                    <li class="py-3 flex flex-col md:flex-row md:items-center md:justify-between gap-3 text-sm">
+                        <div>
+                            <p class="font-semibold text-gray-900">
+                                {format!("Guarantee {} of a {} loan", format_sats(pledge), format_sats(loan.amount))}
+                            </p>
+                            <p class="text-xs text-gray-500">
+                                {format!(
+                                    "Borrower {} · {}",
+                                    loan.user_id,
+                                    loan.purpose.clone().unwrap_or_else(|| "No purpose given".to_string())
+                                )}
+                            </p>
+                        </div>
+                        <ActionForm action=action attr:class="flex gap-2">
+                            <input type="hidden" name="loan_id" value=loan.id.to_string()/>
+                            <button type="submit" name="accept" value="true" class="bg-green-600 hover:bg-green-700 text-white text-xs font-medium py-1.5 px-3 rounded-md">
+                                "Accept"
+                            </button>
+                            <button type="submit" name="accept" value="false" class="bg-red-600 hover:bg-red-700 text-white text-xs font-medium py-1.5 px-3 rounded-md">
+                                "Decline"
+                            </button>
+                        </ActionForm>
+                    </li>
+                }).collect_view()}
+            </ul>
+        </div>
+    })
+}
+
+#[component]
+fn PendingApplications(
+    loans: Vec<Loan>,
+    approve_action: ServerAction<ApproveLoan>,
+    reject_action: ServerAction<RejectLoan>,
+) -> impl IntoView {
+    view! {
+        <div class="bg-white shadow rounded-lg p-6">
+            <h3 class="text-lg font-medium text-gray-900 mb-4">"Pending Approval"</h3>
+            {if loans.is_empty() {
+                view! { <p class="text-sm text-gray-500">"No applications are waiting for review."</p> }.into_any()
+            } else {
+                view! {
+                    <ul class="divide-y divide-gray-100">
+                        {loans.into_iter().map(|loan| {
+                            let approve_id = loan.id.to_string();
+                            let reject_id = loan.id.to_string();
+                            view! {
+                                <li class="py-3 flex flex-col md:flex-row md:items-center md:justify-between gap-3 text-sm">
+                                    <div>
+                                        <a href=format!("/members/{}", loan.user_id) class="text-indigo-600 hover:underline font-mono text-xs">
+                                            {loan.user_id.to_string()}
+                                        </a>
+                                        <p class="font-semibold text-gray-900">{format_sats(loan.amount)}</p>
+                                        <p class="text-xs text-gray-500">
+                                            {format!(
+                                                "{} · {} of {} guarantor(s) accepted · submitted {}",
+                                                loan.purpose.clone().unwrap_or_else(|| "No purpose given".to_string()),
+                                                loan.guarantors.iter().filter(|g| g.status == GuaranteeStatus::Accepted).count(),
+                                                loan.guarantors.len(),
+                                                loan.submitted_at.format("%Y-%m-%d")
+                                            )}
+                                        </p>
+                                    </div>
+                                    <div class="flex gap-2">
+                                        <ActionForm action=approve_action>
+                                            <input type="hidden" name="loan_id" value=approve_id/>
+                                            <button type="submit" class="bg-green-600 hover:bg-green-700 text-white text-xs font-medium py-1.5 px-3 rounded-md">
+                                                "Approve"
+                                            </button>
+                                        </ActionForm>
+                                        <ActionForm action=reject_action attr:class="flex gap-1">
+                                            <input type="hidden" name="loan_id" value=reject_id/>
+                                            <input name="reason" type="text" placeholder="Reason" class="w-32 rounded-md border-gray-300 text-xs"/>
+                                            <button type="submit" class="bg-red-600 hover:bg-red-700 text-white text-xs font-medium py-1.5 px-3 rounded-md">
+                                                "Reject"
+                                            </button>
+                                        </ActionForm>
+                                    </div>
+                                </li>
+                            }
+                        }).collect_view()}
+                    </ul>
+                }.into_any()
+            }}
+        </div>
+    }
+}
+
+#[component]
+fn LoanPortfolio(
+    loans: Vec<Loan>,
+    disburse_action: ServerAction<DisburseLoan>,
+    repay_action: ServerAction<RecordLoanRepayment>,
+) -> impl IntoView {
+    let now = chrono::Utc::now();
+
+    view! {
+        <div class="bg-white shadow rounded-lg p-6">
+            <h3 class="text-lg font-medium text-gray-900 mb-4">"Loan Portfolio"</h3>
+            {if loans.is_empty() {
+                view! { <p class="text-sm text-gray-500">"No loans have been reviewed yet."</p> }.into_any()
+            } else {
+                view! {
+                    <div class="space-y-4">
+                        {loans.into_iter().map(|loan| {
+                            let arrears = loan.arrears(now);
+                            let (badge_class, badge_label) = match (&loan.status, arrears.overdue_installments) {
+                                (LoanStatus::Disbursed, n) if n > 0 => ("bg-red-100 text-red-800", format!("{} days in arrears", arrears.days_in_arrears)),
+                                (LoanStatus::Disbursed, _) => ("bg-green-100 text-green-800", "Current".to_string()),
+                                (LoanStatus::Approved, _) => ("bg-blue-100 text-blue-800", "Approved".to_string()),
+                                (LoanStatus::Rejected, _) => ("bg-gray-100 text-gray-800", "Rejected".to_string()),
+                                (LoanStatus::Repaid, _) => ("bg-teal-100 text-teal-800", "Repaid".to_string()),
+                                (LoanStatus::PendingApproval, _) => ("bg-yellow-100 text-yellow-800", "Pending".to_string()),
+                            };
+                            let disburse_id = loan.id.to_string();
+                            let repay_id = loan.id.to_string();
+                            let outstanding = loan.outstanding().to_string();
+                            let is_approved = loan.status == LoanStatus::Approved;
+                            let is_disbursed = loan.status == LoanStatus::Disbursed;
+                            let schedule = (!loan.schedule.is_empty()).then(|| loan.clone());
+                            let next_due = loan.next_installment().map(|i| {
+                                format!("Next: {} due {}", format_sats(i.outstanding()), i.due_date.format("%Y-%m-%d"))
+                            });
+
+                            view! {
+                                <div class="border border-gray-200 rounded-lg p-4 space-y-3">
+                                    <div class="flex justify-between items-start">
+                                        <div>
+                                            <a href=format!("/members/{}", loan.user_id) class="text-indigo-600 hover:underline font-mono text-xs">
+                                                {loan.user_id.to_string()}
+                                            </a>
+                                            <p class="text-sm font-semibold text-gray-900">
+                                                {format!("{} at {}% over {} months", format_sats(loan.amount), loan.annual_interest_rate.normalize(), loan.term_months)}
+                                            </p>
+                                            <p class="text-xs text-gray-500">
+                                                {format!("Repaid {} · Outstanding {}", format_sats(loan.total_repaid()), format_sats(loan.outstanding()))}
+                                            </p>
+                                            {next_due.map(|text| view! { <p class="text-xs text-gray-500">{text}</p> })}
+                                            {loan.rejection_reason.clone().map(|reason| view! { <p class="text-xs text-gray-500">{reason}</p> })}
+                                        </div>
+                                        <span class=format!("px-2 py-1 rounded-full text-xs font-medium {}", badge_class)>{badge_label}</span>
+                                    </div>
+
+                                    {is_approved.then(move || view! {
+                                        <ActionForm action=disburse_action attr:class="flex gap-2">
+                                            <input type="hidden" name="loan_id" value=disburse_id/>
+                                            <input name="wallet_id" type="text" placeholder="Wallet ID (defaults to borrower's first wallet)" class="flex-1 rounded-md border-gray-300 text-sm"/>
+                                            <button type="submit" class="bg-indigo-600 hover:bg-indigo-700 text-white text-sm font-medium py-1.5 px-3 rounded-md">
+                                                "Disburse"
+                                            </button>
+                                        </ActionForm>
+                                    })}
+
+                                    {is_disbursed.then(move || view! {
+                                        <ActionForm action=repay_action attr:class="flex gap-2">
+                                            <input type="hidden" name="loan_id" value=repay_id/>
+                                            <input name="amount" type="number" min="1" max=outstanding required=true placeholder="Amount (sats)" class="w-40 rounded-md border-gray-300 text-sm"/>
+                                            <input name="wallet_id" type="text" placeholder="Debit wallet ID (optional)" class="flex-1 rounded-md border-gray-300 text-sm"/>
+                                            <button type="submit" class="bg-indigo-600 hover:bg-indigo-700 text-white text-sm font-medium py-1.5 px-3 rounded-md">
+                                                "Record Repayment"
+                                            </button>
+                                        </ActionForm>
+                                    })}
+
+                                    {schedule.map(|loan| view! {
+                                        <details>
+                                            <summary class="text-xs font-medium text-indigo-600 cursor-pointer">"Repayment schedule"</summary>
+                                            <RepaymentSchedule loan=loan />
+                                        </details>
+                                    })}
+                                </div>
+                            }
+                        }).collect_view()}
+                    </div>
+                }.into_any()
+            }}
+        </div>
+    }
+}
+
+#[component]
+fn RepaymentSchedule(loan: Loan) -> impl IntoView {
+    let now = chrono::Utc::now();
+
+    view! {
+        <table class="mt-2 min-w-full divide-y divide-gray-200 text-xs">
+            <thead class="bg-gray-50">
+                <tr>
+                    <th class="px-3 py-1.5 text-left font-medium text-gray-500">"#"</th>
+                    <th class="px-3 py-1.5 text-left font-medium text-gray-500">"Due"</th>
+                    <th class="px-3 py-1.5 text-right font-medium text-gray-500">"Principal"</th>
+                    <th class="px-3 py-1.5 text-right font-medium text-gray-500">"Interest"</th>
+                    <th class="px-3 py-1.5 text-right font-medium text-gray-500">"Paid"</th>
+                    <th class="px-3 py-1.5 text-left font-medium text-gray-500">"Status"</th>
+                </tr>
+            </thead>
+            <tbody class="divide-y divide-gray-100">
+                {loan.schedule.into_iter().map(|installment| {
+                    let status = if installment.outstanding() == 0 {
+                        ("text-green-700", "Paid")
+                    } else if installment.is_overdue(now) {
+                        ("text-red-700", "Overdue")
+                    } else if installment.paid > 0 {
+                        ("text-yellow-700", "Part paid")
+                    } else {
+                        ("text-gray-600", "Upcoming")
+                    };
+                    view! {
+                        <tr>
+                            <td class="px-3 py-1.5">{installment.number}</td>
+                            <td class="px-3 py-1.5">{installment.due_date.format("%Y-%m-%d").to_string()}</td>
+                            <td class="px-3 py-1.5 text-right">{format_sats(installment.principal)}</td>
+                            <td class="px-3 py-1.5 text-right">{format_sats(installment.interest)}</td>
+                            <td class="px-3 py-1.5 text-right">{format_sats(installment.paid)}</td>
+                            <td class=format!("px-3 py-1.5 font-medium {}", status.0)>{status.1}</td>
+                        </tr>
+                    }
+                }).collect_view()}
+            </tbody>
+        </table>
+    }
+}
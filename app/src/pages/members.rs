@@ -1,7 +1,20 @@
 // PLACEHOLDER: Members page simplified for frontend-only mode
-// CRUD operations should be implemented through API adapter pattern
+// CRUD operations should be implemented through API adapter pattern;
+// the member detail page already shows share holdings, transfers, Lightning
+// activity and statements
 
+use crate::api::traits::{fx::MemberBalances, ledger::LedgerCurrency, statements::MemberStatement};
+use crate::components::ui::Spinner;
+use crate::pages::lightning::MemberLightningPanel;
+use crate::pages::shares::{
+    get_member_share_history, ApproveShareTransfer, PayForShares, RejectShareTransfer,
+    RequestShareTransfer, ShareTransferTable,
+};
 use leptos::prelude::*;
+use leptos_router::{
+    components::Redirect,
+    hooks::{use_params_map, use_query_map},
+};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -17,9 +30,72 @@ pub struct MemberResponse {
     pub groups: Option<Vec<String>>,
 }
 
+#[server(GetMemberStatement, "/api", "GetJson")]
+pub async fn get_member_statement(
+    member_id: uuid::Uuid,
+    from: String,
+    to: String,
+) -> Result<MemberStatement, ServerFnError> {
+    use crate::api::{backends::statements_api, client::get_request_user, config::ApiConfig};
+    use crate::server::statements::parse_statement_period;
+
+    let user = get_request_user().await?;
+    if !user.is_admin() && user.id != member_id {
+        return Err(ServerFnError::new("You can only view your own statement"));
+    }
+
+    let (from, to) = parse_statement_period(&from, &to).map_err(ServerFnError::new)?;
+    let api =
+        statements_api(&ApiConfig::from_env()).map_err(|e| ServerFnError::new(e.to_string()))?;
+    api.get_member_statement(member_id, from, to)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))
+}
+
+#[server(GetMemberBalances, "/api", "GetJson")]
+pub async fn get_member_balances(
+    member_id: uuid::Uuid,
+    as_of: Option<String>,
+) -> Result<MemberBalances, ServerFnError> {
+    use crate::api::{
+        backends::{fx_api, statements_api},
+        client::get_request_user,
+        config::ApiConfig,
+    };
+    use crate::pages::fx::parse_as_of;
+
+    let user = get_request_user().await?;
+    if !user.is_admin() && user.id != member_id {
+        return Err(ServerFnError::new("You can only view your own balances"));
+    }
+
+    let as_of = parse_as_of(as_of.as_deref()).map_err(ServerFnError::new)?;
+    let config = ApiConfig::from_env();
+    let statement = statements_api(&config)
+        .map_err(|e| ServerFnError::new(e.to_string()))?
+        .get_member_statement(member_id, chrono::DateTime::UNIX_EPOCH, as_of)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+    // Balances are still shown in their own currency when no rate is known
+    let rate = match fx_api(&config) {
+        Ok(api) => api.rate_at(as_of).await.ok(),
+        Err(_) => None,
+    };
+    Ok(MemberBalances::from_statement(&statement, rate))
+}
+
 #[component]
 pub fn MembersPage() -> impl IntoView {
+    let query = use_query_map();
+    let lookup_id = move || {
+        query
+            .read()
+            .get("id")
+            .filter(|id| uuid::Uuid::parse_str(id.trim()).is_ok())
+    };
+
     view! {
+        {move || lookup_id().map(|id| view! { <Redirect path=format!("/members/{}", id.trim())/> })}
         <div class="space-y-6">
             <div class="flex justify-between items-center">
                 <div>
@@ -34,6 +110,19 @@ pub fn MembersPage() -> impl IntoView {
                 </button>
             </div>
 
+            <form method="get" action="/members" class="bg-white shadow rounded-lg p-4 flex gap-3">
+                <input
+                    name="id"
+                    type="text"
+                    required=true
+                    placeholder="Member ID"
+                    class="flex-1 rounded-md border-gray-300 shadow-sm sm:text-sm"
+                />
+                <button type="submit" class="bg-indigo-600 hover:bg-indigo-700 text-white text-sm font-medium py-2 px-4 rounded-md">
+                    "Open Member"
+                </button>
+            </form>
+
             <div class="bg-white shadow rounded-lg p-8">
                 <div class="text-center">
                     <div class="mx-auto flex items-center justify-center h-12 w-12 rounded-full bg-yellow-100">
@@ -53,3 +142,507 @@ pub fn MembersPage() -> impl IntoView {
         </div>
     }
 }
+
+#[component]
+pub fn MemberDetailPage() -> impl IntoView {
+    let params = use_params_map();
+    let member_id = move || {
+        params
+            .read()
+            .get("id")
+            .and_then(|id| uuid::Uuid::parse_str(&id).ok())
+    };
+
+    let transfer_action = ServerAction::<RequestShareTransfer>::new();
+    let pay_action = ServerAction::<PayForShares>::new();
+    let approve_action = ServerAction::<ApproveShareTransfer>::new();
+    let reject_action = ServerAction::<RejectShareTransfer>::new();
+
+    let history = Resource::new(
+        move || {
+            (
+                member_id(),
+                transfer_action.version().get(),
+                pay_action.version().get(),
+                approve_action.version().get(),
+                reject_action.version().get(),
+            )
+        },
+        |(member_id, ..)| async move {
+            match member_id {
+                Some(id) => get_member_share_history(id).await,
+                None => Err(ServerFnError::new("Invalid member ID")),
+            }
+        },
+    );
+
+    let action_error = move || {
+        [
+            transfer_action.value().get().and_then(|r| r.err()),
+            pay_action.value().get().and_then(|r| r.err()),
+            approve_action.value().get().and_then(|r| r.err()),
+            reject_action.value().get().and_then(|r| r.err()),
+        ]
+        .into_iter()
+        .flatten()
+        .next()
+        .map(|e| e.to_string())
+    };
+
+    view! {
+        <div class="space-y-6">
+            <div>
+                <a href="/members" class="text-sm text-indigo-600 hover:underline">"← Members"</a>
+                <h1 class="mt-2 text-2xl font-semibold text-gray-900">"Member"</h1>
+                <p class="mt-1 text-sm text-gray-500 font-mono">
+                    {move || member_id().map(|id| id.to_string()).unwrap_or_default()}
+                </p>
+            </div>
+
+            {move || action_error().map(|message| view! {
+                <div class="bg-red-50 border border-red-200 rounded-lg p-4 text-sm text-red-700" role="alert">
+                    {message}
+                </div>
+            })}
+
+            <Suspense fallback=move || view! { <div class="flex justify-center py-12"><Spinner /></div> }>
+                {move || {
+                    history.get().map(|result| match result {
+                        Ok(history) => view! {
+                            <MemberShares
+                                history=history
+                                transfer_action=transfer_action
+                                pay_action=pay_action
+                                approve_action=approve_action
+                                reject_action=reject_action
+                            />
+                        }.into_any(),
+                        Err(e) => view! {
+                            <div class="bg-red-50 border border-red-200 rounded-xl p-6 text-center" role="alert">
+                                <h3 class="text-sm font-semibold text-red-800">"Unable to load member shares"</h3>
+                                <p class="mt-1 text-sm text-red-600">{e.to_string()}</p>
+                            </div>
+                        }.into_any(),
+                    })
+                }}
+            </Suspense>
+
+            {move || member_id().map(|id| view! { <MemberBalancesPanel member_id=id /> })}
+            {move || member_id().map(|id| view! { <MemberLightningPanel member_id=id /> })}
+            {move || member_id().map(|id| view! { <MemberStatementPanel member_id=id /> })}
+        </div>
+    }
+}
+
+/// Every balance in both sats and KES as of a chosen time, from the `as_of` query parameter
+#[component]
+fn MemberBalancesPanel(member_id: uuid::Uuid) -> impl IntoView {
+    let query = use_query_map();
+    let as_of = move || query.read().get("as_of").filter(|v| !v.is_empty());
+    let balances = Resource::new(as_of, move |as_of| get_member_balances(member_id, as_of));
+    let input_class = "block rounded-md border-gray-300 shadow-sm sm:text-sm";
+    let amount = |currency: LedgerCurrency, value: Option<rust_decimal::Decimal>| {
+        value
+            .map(|v| currency.format(v))
+            .unwrap_or_else(|| "—".to_string())
+    };
+
+    view! {
+        <div class="bg-white shadow rounded-lg p-6 space-y-4">
+            <div class="flex flex-col md:flex-row md:items-end md:justify-between gap-4">
+                <h3 class="text-lg font-medium text-gray-900">"Balances"</h3>
+                <form method="get" action=format!("/members/{}", member_id) class="flex flex-wrap items-end gap-3">
+                    // Keep the statement period when changing the valuation time
+                    {move || query.read().get("from").map(|from| view! { <input type="hidden" name="from" value=from/> })}
+                    {move || query.read().get("to").map(|to| view! { <input type="hidden" name="to" value=to/> })}
+                    <label class="text-xs text-gray-500">
+                        "As of (UTC, blank for now)"
+                        <input name="as_of" type="datetime-local" value=move || as_of().unwrap_or_default() class=input_class/>
+                    </label>
+                    <button type="submit" class="bg-indigo-600 hover:bg-indigo-700 text-white text-sm font-medium py-2 px-4 rounded-md">
+                        "Value"
+                    </button>
+                </form>
+            </div>
+
+            <Suspense fallback=move || view! { <div class="flex justify-center py-6"><Spinner /></div> }>
+                {move || {
+                    balances.get().map(|result| match result {
+                        Ok(balances) => {
+                            let rate_note = match &balances.rate {
+                                Some(rate) => format!("Valued at {}", crate::pages::fx::describe_rate(rate)),
+                                None => "No exchange rate was recorded by this time, so balances are only shown in their own currency".to_string(),
+                            };
+                            let total_sats = amount(LedgerCurrency::Sats, balances.total_sats());
+                            let total_kes = amount(LedgerCurrency::Kes, balances.total_kes());
+                            view! {
+                                <div class="overflow-x-auto">
+                                    <table class="min-w-full divide-y divide-gray-200 text-sm">
+                                        <thead class="bg-gray-50">
+                                            <tr>
+                                                <th class="px-4 py-2 text-left font-medium text-gray-500">"Account"</th>
+                                                <th class="px-4 py-2 text-right font-medium text-gray-500">"Sats"</th>
+                                                <th class="px-4 py-2 text-right font-medium text-gray-500">"KES"</th>
+                                            </tr>
+                                        </thead>
+                                        <tbody class="divide-y divide-gray-100">
+                                            {balances.balances.into_iter().map(|balance| view! {
+                                                <tr>
+                                                    <td class="px-4 py-2 text-gray-700">{balance.title}</td>
+                                                    <td class="px-4 py-2 text-right text-gray-900">{amount(LedgerCurrency::Sats, balance.sats)}</td>
+                                                    <td class="px-4 py-2 text-right text-gray-900">{amount(LedgerCurrency::Kes, balance.kes)}</td>
+                                                </tr>
+                                            }).collect_view()}
+                                        </tbody>
+                                        <tfoot>
+                                            <tr class="font-semibold">
+                                                <td class="px-4 py-2 text-gray-900">"Total"</td>
+                                                <td class="px-4 py-2 text-right text-gray-900">{total_sats}</td>
+                                                <td class="px-4 py-2 text-right text-gray-900">{total_kes}</td>
+                                            </tr>
+                                        </tfoot>
+                                    </table>
+                                </div>
+                                <p class="text-xs text-gray-500">
+                                    {format!("As of {} · {}", balances.as_of.format("%Y-%m-%d %H:%M UTC"), rate_note)}
+                                </p>
+                            }.into_any()
+                        }
+                        Err(e) => view! {
+                            <div class="bg-red-50 border border-red-200 rounded-lg p-4 text-sm text-red-700" role="alert">
+                                {e.to_string()}
+                            </div>
+                        }.into_any(),
+                    })
+                }}
+            </Suspense>
+        </div>
+    }
+}
+
+/// Account statement for a date range, chosen through the page's query string
+#[component]
+fn MemberStatementPanel(member_id: uuid::Uuid) -> impl IntoView {
+    use chrono::Datelike;
+
+    let query = use_query_map();
+    let today = chrono::Utc::now().date_naive();
+    let month_start = today.with_day(1).unwrap_or(today);
+    // Defaults to the current month so far
+    let period = move || {
+        let query = query.read();
+        (
+            query
+                .get("from")
+                .unwrap_or_else(|| month_start.format("%Y-%m-%d").to_string()),
+            query
+                .get("to")
+                .unwrap_or_else(|| today.format("%Y-%m-%d").to_string()),
+        )
+    };
+
+    let statement = Resource::new(period, move |(from, to)| {
+        get_member_statement(member_id, from, to)
+    });
+    let input_class = "rounded-md border-gray-300 shadow-sm sm:text-sm";
+
+    view! {
+        <div class="bg-white shadow rounded-lg p-6 space-y-4">
+            <div class="flex flex-col md:flex-row md:items-end md:justify-between gap-4">
+                <h3 class="text-lg font-medium text-gray-900">"Statement"</h3>
+                <form method="get" action=format!("/members/{}", member_id) class="flex flex-wrap items-end gap-3">
+                    {move || query.read().get("as_of").map(|as_of| view! { <input type="hidden" name="as_of" value=as_of/> })}
+                    <label class="text-xs text-gray-500">
+                        "From"
+                        <input name="from" type="date" required=true value=move || period().0 class=format!("block {}", input_class)/>
+                    </label>
+                    <label class="text-xs text-gray-500">
+                        "To"
+                        <input name="to" type="date" required=true value=move || period().1 class=format!("block {}", input_class)/>
+                    </label>
+                    <button type="submit" class="bg-indigo-600 hover:bg-indigo-700 text-white text-sm font-medium py-2 px-4 rounded-md">
+                        "View"
+                    </button>
+                </form>
+            </div>
+
+            <Suspense fallback=move || view! { <div class="flex justify-center py-6"><Spinner /></div> }>
+                {move || {
+                    statement.get().map(|result| match result {
+                        Ok(statement) => view! { <StatementView statement=statement /> }.into_any(),
+                        Err(e) => view! {
+                            <div class="bg-red-50 border border-red-200 rounded-lg p-4 text-sm text-red-700" role="alert">
+                                {e.to_string()}
+                            </div>
+                        }.into_any(),
+                    })
+                }}
+            </Suspense>
+        </div>
+    }
+}
+
+#[component]
+fn StatementView(statement: MemberStatement) -> impl IntoView {
+    let from = statement.from.format("%Y-%m-%d").to_string();
+    let to = statement.last_day().format("%Y-%m-%d").to_string();
+    let download = |format: &str| {
+        format!(
+            "/api/members/{}/statement?from={}&to={}&format={}",
+            statement.user_id, from, to, format
+        )
+    };
+    let csv_href = download("csv");
+    let pdf_href = download("pdf");
+    let has_sections = !statement.sections.is_empty();
+
+    view! {
+        <div class="space-y-4">
+            <div class="flex justify-between items-center text-sm">
+                <p class="text-gray-500">{format!("{} to {}", from, to)}</p>
+                <div class="flex gap-4">
+                    <a href=csv_href class="font-medium text-indigo-600 hover:underline">"Download CSV"</a>
+                    <a href=pdf_href class="font-medium text-indigo-600 hover:underline">"Download PDF"</a>
+                </div>
+            </div>
+            {if has_sections {
+                view! {
+                    <div class="space-y-6">
+                        {statement.sections.into_iter().map(|section| {
+                            let currency = section.currency;
+                            view! {
+                                <div class="overflow-x-auto">
+                                    <h4 class="text-sm font-semibold text-gray-900 mb-2">
+                                        {format!("{} ({})", section.title, currency.as_str())}
+                                    </h4>
+                                    <table class="min-w-full divide-y divide-gray-200 text-sm">
+                                        <thead class="bg-gray-50">
+                                            <tr>
+                                                <th class="px-4 py-2 text-left font-medium text-gray-500">"Date"</th>
+                                                <th class="px-4 py-2 text-left font-medium text-gray-500">"Description"</th>
+                                                <th class="px-4 py-2 text-right font-medium text-gray-500">"Amount"</th>
+                                                <th class="px-4 py-2 text-right font-medium text-gray-500">"Balance"</th>
+                                            </tr>
+                                        </thead>
+                                        <tbody class="divide-y divide-gray-100">
+                                            <tr class="bg-gray-50">
+                                                <td class="px-4 py-2"></td>
+                                                <td class="px-4 py-2 font-medium text-gray-700">"Opening balance"</td>
+                                                <td class="px-4 py-2"></td>
+                                                <td class="px-4 py-2 text-right font-medium text-gray-900">{currency.format(section.opening_balance)}</td>
+                                            </tr>
+                                            {section.lines.iter().map(|line| {
+                                                let amount_class = if line.amount < rust_decimal::Decimal::ZERO { "text-red-600" } else { "text-green-600" };
+                                                view! {
+                                                    <tr>
+                                                        <td class="px-4 py-2 text-gray-700">{line.date.format("%Y-%m-%d %H:%M").to_string()}</td>
+                                                        <td class="px-4 py-2 text-gray-700">{line.description.clone()}</td>
+                                                        <td class=format!("px-4 py-2 text-right font-semibold {}", amount_class)>
+                                                            {currency.format(line.amount)}
+                                                        </td>
+                                                        <td class="px-4 py-2 text-right text-gray-900">{currency.format(line.balance)}</td>
+                                                    </tr>
+                                                }
+                                            }).collect_view()}
+                                            <tr class="bg-gray-50">
+                                                <td class="px-4 py-2"></td>
+                                                <td class="px-4 py-2 font-medium text-gray-700">"Closing balance"</td>
+                                                <td class="px-4 py-2 text-right text-xs text-gray-500">
+                                                    {format!(
+                                                        "in {} · out {}",
+                                                        currency.format(section.total_in()),
+                                                        currency.format(section.total_out())
+                                                    )}
+                                                </td>
+                                                <td class="px-4 py-2 text-right font-medium text-gray-900">{currency.format(section.closing_balance)}</td>
+                                            </tr>
+                                        </tbody>
+                                    </table>
+                                </div>
+                            }
+                        }).collect_view()}
+                    </div>
+                }.into_any()
+            } else {
+                view! { <p class="text-sm text-gray-500">"This member has no accounts on record yet."</p> }.into_any()
+            }}
+        </div>
+    }
+}
+
+#[component]
+fn MemberShares(
+    history: crate::pages::shares::MemberShareHistory,
+    transfer_action: ServerAction<RequestShareTransfer>,
+    pay_action: ServerAction<PayForShares>,
+    approve_action: ServerAction<ApproveShareTransfer>,
+    reject_action: ServerAction<RejectShareTransfer>,
+) -> impl IntoView {
+    let input_class = "block w-full rounded-md border-gray-300 shadow-sm focus:ring-indigo-500 focus:border-indigo-500 sm:text-sm";
+    let has_transfers = !history.transfers.is_empty();
+    let has_ledger = !history.ledger.is_empty();
+    let transferable = history.holdings.saturating_sub(history.unpaid);
+    let unpaid: Vec<_> = history
+        .subscriptions
+        .into_iter()
+        .filter(|sub| !sub.is_paid_up())
+        .collect();
+
+    view! {
+        <div class="grid grid-cols-1 lg:grid-cols-3 gap-6">
+            <div class="bg-white shadow rounded-lg p-6">
+                <p class="text-sm text-gray-500">"Shares held"</p>
+                <p class="mt-1 text-3xl font-semibold text-gray-900">{history.holdings}</p>
+                {(history.unpaid > 0).then(|| view! {
+                    <p class="mt-1 text-xs text-gray-500">
+                        {format!("{} awaiting payment", history.unpaid)}
+                    </p>
+                })}
+            </div>
+
+            <div class="bg-white shadow rounded-lg p-6 lg:col-span-2">
+                <h3 class="text-lg font-medium text-gray-900 mb-4">"Transfer Shares"</h3>
+                <ActionForm action=transfer_action attr:class="grid grid-cols-1 gap-4 sm:grid-cols-2">
+                    <input type="hidden" name="from_user_id" value=history.user_id.to_string()/>
+                    <div class="sm:col-span-2">
+                        <label for="transfer-to" class="block text-sm font-medium text-gray-700 mb-1">"Recipient member ID"</label>
+                        <input id="transfer-to" name="to_user_id" type="text" required=true class=input_class/>
+                    </div>
+                    <div>
+                        <label for="transfer-quantity" class="block text-sm font-medium text-gray-700 mb-1">"Shares"</label>
+                        <input id="transfer-quantity" name="quantity" type="number" min="1" max=transferable.to_string() required=true class=input_class/>
+                    </div>
+                    <div>
+                        <label for="transfer-price" class="block text-sm font-medium text-gray-700 mb-1">"Agreed price per share (KES)"</label>
+                        <input id="transfer-price" name="price_per_share" type="number" min="0" step="0.01" class=input_class/>
+                    </div>
+                    <div class="sm:col-span-2">
+                        <label for="transfer-note" class="block text-sm font-medium text-gray-700 mb-1">"Note"</label>
+                        <input id="transfer-note" name="note" type="text" class=input_class/>
+                    </div>
+                    <div class="sm:col-span-2 flex justify-end">
+                        <button
+                            type="submit"
+                            class="bg-indigo-600 hover:bg-indigo-700 text-white font-medium py-2 px-4 rounded-lg disabled:opacity-50"
+                            disabled=move || transfer_action.pending().get()
+                        >
+                            "Request Transfer"
+                        </button>
+                    </div>
+                </ActionForm>
+                <p class="mt-2 text-xs text-gray-500">
+                    "Transfers take effect once an admin approves them. Only paid-up shares can be transferred."
+                </p>
+            </div>
+        </div>
+
+        {(!unpaid.is_empty()).then(|| view! {
+            <div class="bg-white shadow rounded-lg p-6">
+                <h3 class="text-lg font-medium text-gray-900 mb-4">"Unpaid Subscriptions"</h3>
+                <div class="space-y-4">
+                    {unpaid.into_iter().map(|sub| view! {
+                        <ActionForm action=pay_action attr:class="grid grid-cols-1 gap-3 sm:grid-cols-4 sm:items-end">
+                            <input type="hidden" name="subscription_id" value=sub.id.to_string()/>
+                            <div class="text-sm text-gray-700">
+                                <p>{format!("{} shares from {}", sub.quantity, sub.created_at.format("%Y-%m-%d"))}</p>
+                                <p class="text-xs text-gray-500">{format!("KES {} owed", sub.outstanding())}</p>
+                            </div>
+                            <label class="text-xs text-gray-500">
+                                "Wallet ID (blank for cash received)"
+                                <input name="wallet_id" type="text" class=input_class/>
+                            </label>
+                            <label class="text-xs text-gray-500">
+                                "Amount (KES)"
+                                <input name="amount" type="number" min="0.01" step="0.01" max=sub.outstanding().to_string() value=sub.outstanding().to_string() required=true class=input_class/>
+                            </label>
+                            <button
+                                type="submit"
+                                class="bg-indigo-600 hover:bg-indigo-700 text-white font-medium py-2 px-4 rounded-lg disabled:opacity-50"
+                                disabled=move || pay_action.pending().get()
+                            >
+                                "Pay"
+                            </button>
+                        </ActionForm>
+                    }).collect_view()}
+                </div>
+                <p class="mt-2 text-xs text-gray-500">
+                    "Shares count towards borrowing limits and dividends once their subscription is paid in full."
+                </p>
+            </div>
+        })}
+
+        <div class="bg-white shadow rounded-lg p-6">
+            <h3 class="text-lg font-medium text-gray-900 mb-4">"Transfer History"</h3>
+            {if has_transfers {
+                view! {
+                    <ShareTransferTable
+                        transfers=history.transfers
+                        approve_action=approve_action
+                        reject_action=reject_action
+                        can_review=history.can_review
+                    />
+                }.into_any()
+            } else {
+                view! { <p class="text-sm text-gray-500">"No transfers recorded for this member."</p> }.into_any()
+            }}
+        </div>
+
+        <div class="bg-white shadow rounded-lg p-6">
+            <h3 class="text-lg font-medium text-gray-900 mb-4">"Share Ledger"</h3>
+            {if has_ledger {
+                view! { <ShareLedgerTable entries=history.ledger /> }.into_any()
+            } else {
+                view! { <p class="text-sm text-gray-500">"This member does not hold any shares yet."</p> }.into_any()
+            }}
+        </div>
+    }
+}
+
+#[component]
+fn ShareLedgerTable(entries: Vec<crate::api::traits::shares::ShareLedgerEntry>) -> impl IntoView {
+    use crate::api::traits::shares::ShareLedgerEntryKind;
+
+    view! {
+        <div class="overflow-x-auto">
+            <table class="min-w-full divide-y divide-gray-200 text-sm">
+                <thead class="bg-gray-50">
+                    <tr>
+                        <th class="px-4 py-2 text-left font-medium text-gray-500">"Date"</th>
+                        <th class="px-4 py-2 text-left font-medium text-gray-500">"Type"</th>
+                        <th class="px-4 py-2 text-left font-medium text-gray-500">"Counterparty"</th>
+                        <th class="px-4 py-2 text-right font-medium text-gray-500">"Change"</th>
+                        <th class="px-4 py-2 text-right font-medium text-gray-500">"Balance"</th>
+                    </tr>
+                </thead>
+                <tbody class="divide-y divide-gray-100">
+                    {entries.into_iter().map(|entry| {
+                        let label = match entry.kind {
+                            ShareLedgerEntryKind::Subscription => "Subscription",
+                            ShareLedgerEntryKind::TransferIn => "Transfer in",
+                            ShareLedgerEntryKind::TransferOut => "Transfer out",
+                        };
+                        let change_class = if entry.quantity < 0 { "text-red-600" } else { "text-green-600" };
+
+                        view! {
+                            <tr>
+                                <td class="px-4 py-2 text-gray-700">{entry.created_at.format("%Y-%m-%d %H:%M").to_string()}</td>
+                                <td class="px-4 py-2 text-gray-700">{label}</td>
+                                <td class="px-4 py-2">
+                                    {entry.counterparty_id.map(|id| view! {
+                                        <a href=format!("/members/{}", id) class="text-indigo-600 hover:underline font-mono text-xs">
+                                            {id.to_string()}
+                                        </a>
+                                    })}
+                                </td>
+                                <td class=format!("px-4 py-2 text-right font-semibold {}", change_class)>
+                                    {format!("{:+}", entry.quantity)}
+                                </td>
+                                <td class="px-4 py-2 text-right text-gray-900">{entry.balance_after}</td>
+                            </tr>
+                        }
+                    }).collect_view()}
+                </tbody>
+            </table>
+        </div>
+    }
+}
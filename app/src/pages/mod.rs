@@ -1,13 +1,36 @@
 pub mod dashboard;
+pub mod dividends;
+pub mod export_center;
+pub mod financial_analytics;
+pub mod fx;
 pub mod groups;
+pub mod health;
+pub mod lightning;
+pub mod loans;
 pub mod login;
 pub mod members;
+pub mod reconciliation;
+pub mod report_builder;
 pub mod settings;
 pub mod shares;
+pub mod swaps;
+pub mod user_analytics;
+pub mod withdrawal_policy;
 
 pub use dashboard::DashboardContent;
-pub use groups::GroupsPage;
+pub use dividends::DividendsPage;
+pub use export_center::ExportCenterPage;
+pub use financial_analytics::FinancialAnalyticsPage;
+pub use fx::FxPage;
+pub use groups::{ChamaDetailPage, GroupsPage};
+pub use health::SystemHealthPage;
+pub use loans::LoansPage;
 pub use login::*;
-pub use members::MembersPage;
+pub use members::{MemberDetailPage, MembersPage};
+pub use reconciliation::ReconciliationPage;
+pub use report_builder::ReportBuilderPage;
 pub use settings::*;
 pub use shares::*;
+pub use swaps::SwapsPage;
+pub use user_analytics::UserAnalyticsPage;
+pub use withdrawal_policy::WithdrawalPolicyPage;
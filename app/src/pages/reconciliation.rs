@@ -0,0 +1,329 @@
+use crate::api::traits::{
+    ledger::LedgerCurrency,
+    reconciliation::{ReconciliationItem, ReconciliationRun, ReconciliationStatus},
+};
+use crate::components::ui::Spinner;
+use crate::utils::format_sats;
+use leptos::prelude::*;
+use leptos_router::hooks::use_query_map;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReconciliationOverview {
+    pub runs: Vec<ReconciliationRun>,
+    pub threshold_sats: u64,
+}
+
+#[server(GetReconciliationRuns, "/api", "GetJson")]
+pub async fn get_reconciliation_runs() -> Result<ReconciliationOverview, ServerFnError> {
+    use crate::api::{backends::reconciliation_api, config::ApiConfig};
+
+    crate::api::client::require_admin("review reconciliations").await?;
+    let api = reconciliation_api(&ApiConfig::from_env())
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+    let runs = api
+        .list_runs()
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+    Ok(ReconciliationOverview {
+        runs,
+        threshold_sats: api.drift_threshold_sats(),
+    })
+}
+
+#[server(GetReconciliationRun, "/api", "GetJson")]
+pub async fn get_reconciliation_run(
+    run_id: uuid::Uuid,
+) -> Result<ReconciliationRun, ServerFnError> {
+    use crate::api::{backends::reconciliation_api, config::ApiConfig};
+
+    crate::api::client::require_admin("review reconciliations").await?;
+    let api = reconciliation_api(&ApiConfig::from_env())
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+    api.get_run(run_id)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))
+}
+
+#[server(RunReconciliation, "/api")]
+pub async fn run_reconciliation() -> Result<ReconciliationRun, ServerFnError> {
+    use crate::api::{backends::reconciliation_api, config::ApiConfig};
+
+    let user = crate::api::client::require_admin("review reconciliations").await?;
+    let api = reconciliation_api(&ApiConfig::from_env())
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+    api.run_reconciliation(Some(user.id))
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))
+}
+
+fn status_badge(status: ReconciliationStatus) -> (&'static str, &'static str) {
+    match status {
+        ReconciliationStatus::Balanced => ("bg-green-100 text-green-800", "Balanced"),
+        ReconciliationStatus::Drift => ("bg-orange-100 text-orange-800", "Drift"),
+        ReconciliationStatus::Failed => ("bg-red-100 text-red-800", "Failed"),
+    }
+}
+
+/// Signed sats, with a plus sign on surpluses so drift reads at a glance
+fn format_drift(drift: rust_decimal::Decimal) -> String {
+    let formatted = LedgerCurrency::Sats.format(drift);
+    if drift.is_sign_positive() && !drift.is_zero() {
+        format!("+{}", formatted)
+    } else {
+        formatted
+    }
+}
+
+#[component]
+pub fn ReconciliationPage() -> impl IntoView {
+    let query = use_query_map();
+    let run_action = ServerAction::<RunReconciliation>::new();
+
+    let overview = Resource::new(
+        move || run_action.version().get(),
+        |_| get_reconciliation_runs(),
+    );
+    // A new run is shown straight away; otherwise the one picked from the history
+    let selected = move || {
+        query
+            .read()
+            .get("run")
+            .and_then(|id| uuid::Uuid::parse_str(&id).ok())
+    };
+    let detail = Resource::new(
+        move || (selected(), run_action.version().get()),
+        |(run_id, _)| async move {
+            match run_id {
+                Some(id) => get_reconciliation_run(id).await.map(Some),
+                None => Ok(None),
+            }
+        },
+    );
+    let shown = move || {
+        run_action
+            .value()
+            .get()
+            .and_then(|r| r.ok())
+            .filter(|_| selected().is_none())
+    };
+    let action_error = move || {
+        run_action
+            .value()
+            .get()
+            .and_then(|r| r.err())
+            .map(|e| e.to_string())
+    };
+
+    view! {
+        <div class="space-y-6">
+            <div class="flex flex-col sm:flex-row sm:items-end sm:justify-between gap-4">
+                <div>
+                    <h1 class="text-2xl font-semibold text-gray-900">"Reconciliation"</h1>
+                    <p class="mt-1 text-sm text-gray-500">"Ledger balances against the funds the federation and on-chain descriptor actually hold"</p>
+                </div>
+                <ActionForm action=run_action>
+                    <button type="submit" class="bg-indigo-600 hover:bg-indigo-700 text-white text-sm font-medium py-2 px-4 rounded-md">
+                        "Run Reconciliation"
+                    </button>
+                </ActionForm>
+            </div>
+
+            {move || action_error().map(|message| view! {
+                <div class="bg-red-50 border border-red-200 rounded-lg p-4 text-sm text-red-700" role="alert">
+                    {message}
+                </div>
+            })}
+
+            {move || shown().map(|run| view! { <RunDetail run=run /> })}
+            <Suspense fallback=|| ()>
+                {move || detail.get().map(|result| match result {
+                    Ok(Some(run)) => view! { <RunDetail run=run /> }.into_any(),
+                    Ok(None) => ().into_any(),
+                    Err(e) => view! {
+                        <div class="bg-red-50 border border-red-200 rounded-lg p-4 text-sm text-red-700" role="alert">
+                            {e.to_string()}
+                        </div>
+                    }.into_any(),
+                })}
+            </Suspense>
+
+            <div class="bg-white shadow rounded-lg p-6 space-y-4">
+                <h3 class="text-lg font-medium text-gray-900">"Runs"</h3>
+                <Suspense fallback=move || view! { <div class="flex justify-center py-6"><Spinner /></div> }>
+                    {move || overview.get().map(|result| match result {
+                        Ok(overview) if overview.runs.is_empty() => view! {
+                            <p class="text-sm text-gray-500">"No reconciliation has run yet."</p>
+                        }.into_any(),
+                        Ok(overview) => view! {
+                            <RunTable runs=overview.runs />
+                            <p class="text-xs text-gray-500">
+                                {format!("Runs are flagged when drift exceeds {}.", format_sats(overview.threshold_sats))}
+                            </p>
+                        }.into_any(),
+                        Err(e) => view! {
+                            <div class="bg-red-50 border border-red-200 rounded-lg p-4 text-sm text-red-700" role="alert">
+                                {e.to_string()}
+                            </div>
+                        }.into_any(),
+                    })}
+                </Suspense>
+            </div>
+        </div>
+    }
+}
+
+#[component]
+fn RunTable(runs: Vec<ReconciliationRun>) -> impl IntoView {
+    view! {
+        <div class="overflow-x-auto">
+            <table class="min-w-full divide-y divide-gray-200 text-sm">
+                <thead class="bg-gray-50">
+                    <tr>
+                        <th class="px-4 py-2 text-left font-medium text-gray-500">"Run"</th>
+                        <th class="px-4 py-2 text-left font-medium text-gray-500">"Trigger"</th>
+                        <th class="px-4 py-2 text-right font-medium text-gray-500">"Ledger"</th>
+                        <th class="px-4 py-2 text-right font-medium text-gray-500">"Providers"</th>
+                        <th class="px-4 py-2 text-right font-medium text-gray-500">"Drift"</th>
+                        <th class="px-4 py-2 text-right font-medium text-gray-500">"Unmatched"</th>
+                        <th class="px-4 py-2 text-left font-medium text-gray-500">"Status"</th>
+                        <th class="px-4 py-2"></th>
+                    </tr>
+                </thead>
+                <tbody class="divide-y divide-gray-100">
+                    {runs.into_iter().map(|run| {
+                        let (badge_class, badge_label) = status_badge(run.status);
+                        let unmatched = run.unmatched_ledger.len() + run.unmatched_provider.len();
+                        let trigger = if run.triggered_by.is_some() { "Manual" } else { "Scheduled" };
+                        view! {
+                            <tr>
+                                <td class="px-4 py-2 text-gray-700">{run.started_at.format("%Y-%m-%d %H:%M").to_string()}</td>
+                                <td class="px-4 py-2 text-gray-700">{trigger}</td>
+                                <td class="px-4 py-2 text-right text-gray-900">{LedgerCurrency::Sats.format(run.ledger_balance_sats)}</td>
+                                <td class="px-4 py-2 text-right text-gray-900">{LedgerCurrency::Sats.format(run.provider_balance_sats)}</td>
+                                <td class="px-4 py-2 text-right text-gray-900">{format_drift(run.drift_sats)}</td>
+                                <td class="px-4 py-2 text-right text-gray-700">{unmatched}</td>
+                                <td class="px-4 py-2">
+                                    <span class=format!("px-2 py-1 rounded-full text-xs font-medium {}", badge_class)>{badge_label}</span>
+                                </td>
+                                <td class="px-4 py-2 text-right">
+                                    <a href=format!("/reconciliation?run={}", run.id) class="text-indigo-600 hover:text-indigo-800 text-xs font-medium">"View"</a>
+                                </td>
+                            </tr>
+                        }
+                    }).collect_view()}
+                </tbody>
+            </table>
+        </div>
+    }
+}
+
+#[component]
+fn RunDetail(run: ReconciliationRun) -> impl IntoView {
+    let (badge_class, badge_label) = status_badge(run.status);
+    let coverage = if run.covers_members() {
+        ("text-green-700", "Providers cover every member balance")
+    } else {
+        ("text-red-700", "Providers hold less than members are owed")
+    };
+    let totals = vec![
+        (
+            "Member balances",
+            LedgerCurrency::Sats.format(run.member_balances_sats),
+        ),
+        (
+            "Ledger treasury",
+            LedgerCurrency::Sats.format(run.ledger_balance_sats),
+        ),
+        (
+            "Provider balances",
+            LedgerCurrency::Sats.format(run.provider_balance_sats),
+        ),
+        ("Drift", format_drift(run.drift_sats)),
+    ];
+
+    view! {
+        <div class="bg-white shadow rounded-lg p-6 space-y-6">
+            <div class="flex flex-wrap items-center justify-between gap-2">
+                <h3 class="text-lg font-medium text-gray-900">
+                    {format!("Run of {}", run.started_at.format("%Y-%m-%d %H:%M:%S UTC"))}
+                </h3>
+                <span class=format!("px-2 py-1 rounded-full text-xs font-medium {}", badge_class)>{badge_label}</span>
+            </div>
+
+            <dl class="grid grid-cols-2 lg:grid-cols-4 gap-4">
+                {totals.into_iter().map(|(label, value)| view! {
+                    <div class="p-4 bg-gray-50 rounded-lg">
+                        <dt class="text-xs text-gray-500">{label}</dt>
+                        <dd class="text-lg font-semibold text-gray-900">{value}</dd>
+                    </div>
+                }).collect_view()}
+            </dl>
+            <p class=format!("text-sm {}", coverage.0)>{coverage.1}</p>
+
+            <div>
+                <h4 class="text-sm font-medium text-gray-900 mb-2">"Providers"</h4>
+                <ul class="divide-y divide-gray-100 text-sm">
+                    {run.providers.into_iter().map(|provider| {
+                        let reported = match (provider.balance_sats, provider.error) {
+                            (Some(balance), _) => format_sats(balance),
+                            (None, Some(error)) => format!("Unavailable: {}", error),
+                            (None, None) => "Unavailable".to_string(),
+                        };
+                        view! {
+                            <li class="flex justify-between py-2">
+                                <span class="text-gray-700">{provider.provider}</span>
+                                <span class="text-gray-900">{reported}</span>
+                            </li>
+                        }
+                    }).collect_view()}
+                </ul>
+            </div>
+
+            <div class="grid grid-cols-1 lg:grid-cols-2 gap-6">
+                <UnmatchedList
+                    title="In the ledger only"
+                    empty="Every ledger movement was found at a provider."
+                    items=run.unmatched_ledger
+                />
+                <UnmatchedList
+                    title="At a provider only"
+                    empty="Every provider movement is in the ledger."
+                    items=run.unmatched_provider
+                />
+            </div>
+        </div>
+    }
+}
+
+#[component]
+fn UnmatchedList(
+    title: &'static str,
+    empty: &'static str,
+    items: Vec<ReconciliationItem>,
+) -> impl IntoView {
+    view! {
+        <div>
+            <h4 class="text-sm font-medium text-gray-900 mb-2">{title}</h4>
+            {if items.is_empty() {
+                view! { <p class="text-sm text-gray-500">{empty}</p> }.into_any()
+            } else {
+                view! {
+                    <ul class="divide-y divide-gray-100 text-sm">
+                        {items.into_iter().map(|item| view! {
+                            <li class="py-2">
+                                <div class="flex justify-between">
+                                    <span class="text-gray-700">{item.description}</span>
+                                    <span class="text-gray-900">{format_drift(item.amount_sats.into())}</span>
+                                </div>
+                                <div class="text-xs text-gray-500 font-mono truncate">
+                                    {format!("{} · {}", item.occurred_at.format("%Y-%m-%d %H:%M"), item.reference)}
+                                </div>
+                            </li>
+                        }).collect_view()}
+                    </ul>
+                }.into_any()
+            }}
+        </div>
+    }
+}
@@ -0,0 +1,564 @@
+use crate::api::get_custom_analytics;
+use crate::api::traits::reports::{ReportView, SavedReport};
+use crate::api::types::{
+    AnalyticsDimension, AnalyticsMetric, AnalyticsResult, AnalyticsSeries, Granularity, MetricUnit,
+    ReportRange, ReportSpec,
+};
+use crate::components::ui::{ChartKind, ChartPoint, Spinner, TrendChart};
+use crate::utils::format_count;
+use chrono::{NaiveDate, Utc};
+use leptos::prelude::*;
+use leptos_router::hooks::use_query_map;
+use leptos_router::params::ParamsMap;
+use uuid::Uuid;
+
+/// Relative ranges offered in the form, in days
+const RANGE_PRESETS: [u32; 4] = [7, 30, 90, 365];
+
+#[server(ListSavedReports, "/api", "GetJson")]
+pub async fn list_saved_reports() -> Result<Vec<SavedReport>, ServerFnError> {
+    use crate::api::{backends::saved_reports_api, config::ApiConfig};
+
+    let user = crate::api::client::require_admin("build reports").await?;
+    let api =
+        saved_reports_api(&ApiConfig::from_env()).map_err(|e| ServerFnError::new(e.to_string()))?;
+    api.list_reports(&user.id.to_string())
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))
+}
+
+#[server(SaveReport, "/api")]
+pub async fn save_report(
+    name: String,
+    spec: ReportSpec,
+    view: ReportView,
+) -> Result<SavedReport, ServerFnError> {
+    use crate::api::{
+        backends::saved_reports_api, config::ApiConfig, traits::reports::SaveReportRequest,
+    };
+
+    let user = crate::api::client::require_admin("build reports").await?;
+    let api =
+        saved_reports_api(&ApiConfig::from_env()).map_err(|e| ServerFnError::new(e.to_string()))?;
+    api.save_report(&user.id.to_string(), SaveReportRequest { name, spec, view })
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))
+}
+
+#[server(DeleteSavedReport, "/api")]
+pub async fn delete_saved_report(report_id: Uuid) -> Result<(), ServerFnError> {
+    use crate::api::{backends::saved_reports_api, config::ApiConfig};
+
+    let user = crate::api::client::require_admin("build reports").await?;
+    let api =
+        saved_reports_api(&ApiConfig::from_env()).map_err(|e| ServerFnError::new(e.to_string()))?;
+    api.delete_report(&user.id.to_string(), report_id)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))
+}
+
+/// Read the report the builder form submitted. Unknown metric names are
+/// ignored; a custom range needs both dates.
+fn spec_from_query(query: &ParamsMap) -> Result<ReportSpec, String> {
+    let metrics = query
+        .get_all("metric")
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|key| AnalyticsMetric::from_key(key))
+        .collect();
+    let dimension = query
+        .get("dimension")
+        .and_then(|key| AnalyticsDimension::from_key(&key));
+    let granularity = query
+        .get("granularity")
+        .and_then(|key| Granularity::from_key(&key))
+        .unwrap_or_default();
+    let range = match query.get("range").as_deref() {
+        Some("custom") => {
+            let date = |key| {
+                query
+                    .get(key)
+                    .and_then(|value| NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d").ok())
+                    .ok_or_else(|| "Pick both dates for a custom range".to_string())
+            };
+            ReportRange::Fixed {
+                start: date("start")?,
+                end: date("end")?,
+            }
+        }
+        Some(days) => days.parse().map(ReportRange::LastDays).unwrap_or_default(),
+        None => ReportRange::default(),
+    };
+    Ok(ReportSpec {
+        metrics,
+        dimension,
+        granularity,
+        range,
+    })
+}
+
+fn view_from_query(query: &ParamsMap) -> ReportView {
+    match query.get("view").as_deref() {
+        Some("table") => ReportView::Table,
+        _ => ReportView::Chart,
+    }
+}
+
+fn view_key(view: ReportView) -> &'static str {
+    match view {
+        ReportView::Chart => "chart",
+        ReportView::Table => "table",
+    }
+}
+
+/// Link that opens the builder on a report
+fn report_href(spec: &ReportSpec, view: ReportView) -> String {
+    let mut params: Vec<String> = spec
+        .metrics
+        .iter()
+        .map(|metric| format!("metric={}", metric.key()))
+        .collect();
+    if let Some(dimension) = spec.dimension {
+        params.push(format!("dimension={}", dimension.key()));
+    }
+    params.push(format!("granularity={}", spec.granularity.key()));
+    match spec.range {
+        ReportRange::LastDays(days) => params.push(format!("range={}", days)),
+        ReportRange::Fixed { start, end } => {
+            params.push("range=custom".to_string());
+            params.push(format!("start={}", start.format("%Y-%m-%d")));
+            params.push(format!("end={}", end.format("%Y-%m-%d")));
+        }
+    }
+    params.push(format!("view={}", view_key(view)));
+    format!("/report-builder?{}", params.join("&"))
+}
+
+/// Hidden inputs carrying a report into the save form, named the way the
+/// `SaveReport` arguments deserialize
+fn spec_fields(spec: &ReportSpec, view: ReportView) -> Vec<(String, String)> {
+    let mut fields: Vec<(String, String)> = spec
+        .metrics
+        .iter()
+        .enumerate()
+        .map(|(i, metric)| (format!("spec[metrics][{}]", i), metric.key().to_string()))
+        .collect();
+    if let Some(dimension) = spec.dimension {
+        fields.push(("spec[dimension]".to_string(), dimension.key().to_string()));
+    }
+    fields.push((
+        "spec[granularity]".to_string(),
+        spec.granularity.key().to_string(),
+    ));
+    match spec.range {
+        ReportRange::LastDays(days) => {
+            fields.push(("spec[range][last_days]".to_string(), days.to_string()))
+        }
+        ReportRange::Fixed { start, end } => {
+            fields.push((
+                "spec[range][fixed][start]".to_string(),
+                start.format("%Y-%m-%d").to_string(),
+            ));
+            fields.push((
+                "spec[range][fixed][end]".to_string(),
+                end.format("%Y-%m-%d").to_string(),
+            ));
+        }
+    }
+    fields.push(("view".to_string(), view_key(view).to_string()));
+    fields
+}
+
+/// `New members, Swap volume by region · Daily · Last 30 days`
+fn describe_spec(spec: &ReportSpec) -> String {
+    let metrics = spec
+        .metrics
+        .iter()
+        .map(|metric| metric.label())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let dimension = spec
+        .dimension
+        .map(|dimension| format!(" by {}", dimension.label().to_lowercase()))
+        .unwrap_or_default();
+    let range = match spec.range {
+        ReportRange::LastDays(days) => format!("Last {} days", days),
+        ReportRange::Fixed { start, end } => {
+            format!("{} to {}", start.format("%Y-%m-%d"), end.format("%Y-%m-%d"))
+        }
+    };
+    format!(
+        "{}{} · {} · {}",
+        metrics,
+        dimension,
+        spec.granularity.label(),
+        range
+    )
+}
+
+fn format_amount(value: f64) -> String {
+    format!("{:.2}", value)
+}
+
+fn formatter(metric: AnalyticsMetric) -> fn(f64) -> String {
+    match metric.unit() {
+        MetricUnit::Count => format_count,
+        MetricUnit::Money => format_amount,
+    }
+}
+
+#[component]
+pub fn ReportBuilderPage() -> impl IntoView {
+    let query = use_query_map();
+    let save_action = ServerAction::<SaveReport>::new();
+    let delete_action = ServerAction::<DeleteSavedReport>::new();
+
+    // Nothing runs until the form has been submitted or a saved report opened
+    let submitted = move || query.read().get("granularity").is_some();
+    let spec = move || spec_from_query(&query.read());
+    let view_mode = move || view_from_query(&query.read());
+
+    let result = Resource::new(
+        move || submitted().then(spec),
+        |spec| async move {
+            let spec = match spec {
+                None => return Ok(None),
+                Some(spec) => spec.map_err(ServerFnError::new)?,
+            };
+            // Catch mistakes here rather than with a round trip; the server checks again
+            spec.query(Utc::now().date_naive())
+                .map_err(|e| ServerFnError::new(e.to_string()))?;
+            get_custom_analytics(spec).await.map(Some)
+        },
+    );
+    let saved = Resource::new(
+        move || (save_action.version().get(), delete_action.version().get()),
+        |_| list_saved_reports(),
+    );
+
+    let action_error = move || {
+        [
+            save_action.value().get().and_then(|r| r.err()),
+            delete_action.value().get().and_then(|r| r.err()),
+        ]
+        .into_iter()
+        .flatten()
+        .next()
+        .map(|e| e.to_string())
+    };
+
+    view! {
+        <div class="space-y-6">
+            <div>
+                <h1 class="text-2xl font-semibold text-gray-900">"Report Builder"</h1>
+                <p class="mt-1 text-sm text-gray-500">"Chart any combination of metrics over a range, broken down by a dimension, and save it for later"</p>
+            </div>
+
+            {move || action_error().map(|message| view! {
+                <div class="bg-red-50 border border-red-200 rounded-lg p-4 text-sm text-red-700" role="alert">
+                    {message}
+                </div>
+            })}
+
+            {move || {
+                let spec = spec().unwrap_or_default();
+                view! { <BuilderForm spec=spec view=view_mode() /> }
+            }}
+
+            <Suspense fallback=move || view! { <div class="flex justify-center py-12"><Spinner /></div> }>
+                {move || result.get().map(|result| match result {
+                    Ok(Some(result)) => {
+                        let spec = spec().unwrap_or_default();
+                        view! { <ReportResult result=result spec=spec view=view_mode() save_action=save_action /> }.into_any()
+                    }
+                    Ok(None) => view! {
+                        <div class="bg-white shadow rounded-lg p-6 text-sm text-gray-500">
+                            "Pick at least one metric and run the report, or open a saved one below."
+                        </div>
+                    }.into_any(),
+                    Err(e) => view! {
+                        <div class="bg-red-50 border border-red-200 rounded-lg p-4 text-sm text-red-700" role="alert">
+                            {e.to_string()}
+                        </div>
+                    }.into_any(),
+                })}
+            </Suspense>
+
+            <div class="bg-white shadow rounded-lg p-6 space-y-4">
+                <h3 class="text-lg font-medium text-gray-900">"Saved Reports"</h3>
+                <Suspense fallback=move || view! { <div class="flex justify-center py-6"><Spinner /></div> }>
+                    {move || saved.get().map(|result| match result {
+                        Ok(reports) if reports.is_empty() => view! {
+                            <p class="text-sm text-gray-500">"You haven't saved any reports yet."</p>
+                        }.into_any(),
+                        Ok(reports) => view! { <SavedReportsTable reports=reports delete_action=delete_action /> }.into_any(),
+                        Err(e) => view! {
+                            <div class="bg-red-50 border border-red-200 rounded-lg p-4 text-sm text-red-700" role="alert">
+                                {e.to_string()}
+                            </div>
+                        }.into_any(),
+                    })}
+                </Suspense>
+            </div>
+        </div>
+    }
+}
+
+#[component]
+fn BuilderForm(spec: ReportSpec, view: ReportView) -> impl IntoView {
+    let input_class = "block w-full rounded-md border-gray-300 shadow-sm sm:text-sm";
+    let (range_key, start, end) = match spec.range {
+        ReportRange::LastDays(days) => (days.to_string(), String::new(), String::new()),
+        ReportRange::Fixed { start, end } => (
+            "custom".to_string(),
+            start.format("%Y-%m-%d").to_string(),
+            end.format("%Y-%m-%d").to_string(),
+        ),
+    };
+    let selected_metrics = spec.metrics.clone();
+
+    view! {
+        <form method="get" action="/report-builder" class="bg-white shadow rounded-lg p-6 space-y-4">
+            <fieldset>
+                <legend class="text-sm font-medium text-gray-900">"Metrics"</legend>
+                <div class="mt-2 grid grid-cols-2 md:grid-cols-4 gap-2">
+                    {AnalyticsMetric::ALL.into_iter().map(|metric| view! {
+                        <label class="flex items-center gap-2 text-sm text-gray-700">
+                            <input
+                                type="checkbox"
+                                name="metric"
+                                value=metric.key()
+                                checked=selected_metrics.contains(&metric)
+                                class="rounded border-gray-300 text-indigo-600"
+                            />
+                            {metric.label()}
+                        </label>
+                    }).collect_view()}
+                </div>
+            </fieldset>
+
+            <div class="flex flex-wrap items-end gap-3">
+                <label class="text-xs text-gray-500">
+                    "Break down by"
+                    <select name="dimension" class=input_class>
+                        <option value="" selected=spec.dimension.is_none()>"Nothing"</option>
+                        {AnalyticsDimension::ALL.into_iter().map(|dimension| view! {
+                            <option value=dimension.key() selected=spec.dimension == Some(dimension)>
+                                {dimension.label()}
+                            </option>
+                        }).collect_view()}
+                    </select>
+                </label>
+                <label class="text-xs text-gray-500">
+                    "Granularity"
+                    <select name="granularity" class=input_class>
+                        {Granularity::ALL.into_iter().map(|granularity| view! {
+                            <option value=granularity.key() selected=spec.granularity == granularity>
+                                {granularity.label()}
+                            </option>
+                        }).collect_view()}
+                    </select>
+                </label>
+                <label class="text-xs text-gray-500">
+                    "Range"
+                    <select name="range" class=input_class>
+                        {RANGE_PRESETS.into_iter().map(|days| view! {
+                            <option value=days.to_string() selected=range_key == days.to_string()>
+                                {format!("Last {} days", days)}
+                            </option>
+                        }).collect_view()}
+                        <option value="custom" selected=range_key == "custom">"Custom"</option>
+                    </select>
+                </label>
+                <label class="text-xs text-gray-500">
+                    "From"
+                    <input name="start" type="date" value=start class=input_class/>
+                </label>
+                <label class="text-xs text-gray-500">
+                    "To"
+                    <input name="end" type="date" value=end class=input_class/>
+                </label>
+                <fieldset class="flex items-center gap-3 text-sm text-gray-700 py-2">
+                    <label class="flex items-center gap-1">
+                        <input type="radio" name="view" value="chart" checked=view == ReportView::Chart/>
+                        "Chart"
+                    </label>
+                    <label class="flex items-center gap-1">
+                        <input type="radio" name="view" value="table" checked=view == ReportView::Table/>
+                        "Table"
+                    </label>
+                </fieldset>
+                <button type="submit" class="bg-indigo-600 hover:bg-indigo-700 text-white text-sm font-medium py-2 px-4 rounded-md">
+                    "Run report"
+                </button>
+            </div>
+            <p class="text-xs text-gray-500">
+                "From and To are only used with a custom range. Not every metric can be broken down by every dimension."
+            </p>
+        </form>
+    }
+    .into_any()
+}
+
+#[component]
+fn ReportResult(
+    result: AnalyticsResult,
+    spec: ReportSpec,
+    view: ReportView,
+    save_action: ServerAction<SaveReport>,
+) -> impl IntoView {
+    let input_class = "block w-full rounded-md border-gray-300 shadow-sm sm:text-sm";
+    let fields = spec_fields(&spec, view);
+
+    view! {
+        <div class="bg-white shadow rounded-lg p-6 space-y-4">
+            <div class="flex flex-col md:flex-row md:items-end md:justify-between gap-4">
+                <div>
+                    <h3 class="text-lg font-medium text-gray-900">"Result"</h3>
+                    <p class="text-xs text-gray-500">{describe_spec(&spec)}</p>
+                </div>
+                <ActionForm action=save_action attr:class="flex items-end gap-3">
+                    {fields.into_iter().map(|(name, value)| view! {
+                        <input type="hidden" name=name value=value/>
+                    }).collect_view()}
+                    <label class="text-xs text-gray-500">
+                        "Report name"
+                        <input name="name" type="text" required=true maxlength="80" class=input_class/>
+                    </label>
+                    <button
+                        type="submit"
+                        class="border border-gray-300 text-gray-700 hover:bg-gray-50 text-sm font-medium py-2 px-4 rounded-md disabled:opacity-50"
+                        disabled=move || save_action.pending().get()
+                    >
+                        "Save"
+                    </button>
+                </ActionForm>
+            </div>
+            {if result.series.is_empty() {
+                view! { <p class="text-sm text-gray-500">"No data for this range."</p> }.into_any()
+            } else {
+                match view {
+                    ReportView::Chart => view! { <SeriesCharts series=result.series /> }.into_any(),
+                    ReportView::Table => view! { <SeriesTable result=result /> }.into_any(),
+                }
+            }}
+        </div>
+    }
+    .into_any()
+}
+
+#[component]
+fn SeriesCharts(series: Vec<AnalyticsSeries>) -> impl IntoView {
+    view! {
+        <div class="grid grid-cols-1 lg:grid-cols-2 gap-6">
+            {series.into_iter().map(|series| {
+                let title = series.label();
+                let (kind, color) = match series.metric.unit() {
+                    MetricUnit::Count => (ChartKind::Bar, "#4f46e5"),
+                    MetricUnit::Money => (ChartKind::Area, "#16a34a"),
+                };
+                let format = formatter(series.metric);
+                let points = series
+                    .points
+                    .into_iter()
+                    .map(|point| ChartPoint {
+                        label: point.period,
+                        value: point.value,
+                        display: None,
+                    })
+                    .collect();
+                view! {
+                    <div>
+                        <h4 class="text-sm font-medium text-gray-900 mb-2">{title.clone()}</h4>
+                        <TrendChart points=points kind=kind title=title color=color format=format />
+                    </div>
+                }
+            }).collect_view()}
+        </div>
+    }
+    .into_any()
+}
+
+#[component]
+fn SeriesTable(result: AnalyticsResult) -> impl IntoView {
+    let periods = result.periods();
+    let series = result.series;
+    let headers: Vec<String> = series.iter().map(AnalyticsSeries::label).collect();
+
+    view! {
+        <div class="overflow-x-auto">
+            <table class="min-w-full divide-y divide-gray-200 text-sm">
+                <thead class="bg-gray-50">
+                    <tr>
+                        <th class="px-4 py-2 text-left font-medium text-gray-500">"Period"</th>
+                        {headers.into_iter().map(|header| view! {
+                            <th class="px-4 py-2 text-right font-medium text-gray-500">{header}</th>
+                        }).collect_view()}
+                    </tr>
+                </thead>
+                <tbody class="divide-y divide-gray-100">
+                    {periods.into_iter().map(|period| {
+                        let cells: Vec<String> = series
+                            .iter()
+                            .map(|series| {
+                                series
+                                    .points
+                                    .iter()
+                                    .find(|point| point.period == period)
+                                    .map(|point| formatter(series.metric)(point.value))
+                                    .unwrap_or_else(|| "—".to_string())
+                            })
+                            .collect();
+                        view! {
+                            <tr>
+                                <td class="px-4 py-2 text-gray-700">{period}</td>
+                                {cells.into_iter().map(|cell| view! {
+                                    <td class="px-4 py-2 text-right text-gray-900">{cell}</td>
+                                }).collect_view()}
+                            </tr>
+                        }
+                    }).collect_view()}
+                </tbody>
+            </table>
+        </div>
+    }
+    .into_any()
+}
+
+#[component]
+fn SavedReportsTable(
+    reports: Vec<SavedReport>,
+    delete_action: ServerAction<DeleteSavedReport>,
+) -> impl IntoView {
+    view! {
+        <table class="min-w-full divide-y divide-gray-200 text-sm">
+            <thead class="bg-gray-50">
+                <tr>
+                    <th class="px-4 py-2 text-left font-medium text-gray-500">"Name"</th>
+                    <th class="px-4 py-2 text-left font-medium text-gray-500">"Report"</th>
+                    <th class="px-4 py-2 text-left font-medium text-gray-500">"Updated"</th>
+                    <th class="px-4 py-2"></th>
+                </tr>
+            </thead>
+            <tbody class="divide-y divide-gray-100">
+                {reports.into_iter().map(|report| view! {
+                    <tr>
+                        <td class="px-4 py-2 text-gray-900">{report.name.clone()}</td>
+                        <td class="px-4 py-2 text-gray-700">{describe_spec(&report.spec)}</td>
+                        <td class="px-4 py-2 text-gray-500">{report.updated_at.format("%Y-%m-%d %H:%M").to_string()}</td>
+                        <td class="px-4 py-2 text-right">
+                            <div class="flex justify-end items-center gap-3">
+                                <a href=report_href(&report.spec, report.view) class="text-indigo-600 hover:text-indigo-800 text-xs font-medium">"Open"</a>
+                                <ActionForm action=delete_action>
+                                    <input type="hidden" name="report_id" value=report.id.to_string()/>
+                                    <button type="submit" class="text-red-600 hover:text-red-800 text-xs font-medium">"Delete"</button>
+                                </ActionForm>
+                            </div>
+                        </td>
+                    </tr>
+                }).collect_view()}
+            </tbody>
+        </table>
+    }
+    .into_any()
+}
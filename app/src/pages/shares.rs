@@ -1,6 +1,5 @@
-// PLACEHOLDER: Shares page simplified for frontend-only mode
-// CRUD operations should be implemented through API adapter pattern
-
+use crate::api::traits::shares::{ShareLedgerEntry, ShareOffer, ShareSubscription, ShareTransfer};
+use crate::components::ui::ProgressBar;
 use leptos::prelude::*;
 use serde::{Deserialize, Serialize};
 
@@ -9,17 +8,398 @@ pub struct ShareOfferResponse {
     pub id: uuid::Uuid,
     pub title: String,
     pub description: Option<String>,
-    pub total_quantity: rust_decimal::Decimal,
-    pub available_quantity: rust_decimal::Decimal,
-    pub price_per_share: rust_decimal::Decimal,
-    pub total_value: rust_decimal::Decimal,
+    pub total_quantity: u64,
+    pub available_quantity: u64,
+    pub price_per_share: Option<rust_decimal::Decimal>,
+    pub total_value: Option<rust_decimal::Decimal>,
     pub status: String,
     pub expires_at: String,
     pub progress: f64,
 }
 
+impl From<ShareOffer> for ShareOfferResponse {
+    fn from(offer: ShareOffer) -> Self {
+        Self {
+            id: offer.id,
+            title: offer.title.clone(),
+            description: offer.description.clone(),
+            total_quantity: offer.total_quantity,
+            available_quantity: offer.available_quantity(),
+            price_per_share: offer.price_per_share,
+            total_value: offer.total_value(),
+            status: offer.status.as_str().to_string(),
+            expires_at: offer.expires_at.format("%Y-%m-%d %H:%M UTC").to_string(),
+            progress: offer.progress(),
+        }
+    }
+}
+
+#[server(GetShareOffers, "/api", "GetJson")]
+pub async fn get_share_offers() -> Result<Vec<ShareOfferResponse>, ServerFnError> {
+    use crate::api::{
+        backends::shares_api, client::get_request_user, config::ApiConfig, types::PaginationQuery,
+    };
+
+    get_request_user().await?;
+    let api = shares_api(&ApiConfig::from_env()).map_err(|e| ServerFnError::new(e.to_string()))?;
+    let offers = api
+        .get_share_offers(PaginationQuery {
+            page: Some(1),
+            limit: Some(100),
+        })
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to load share offers: {}", e);
+            ServerFnError::new(e.to_string())
+        })?;
+
+    Ok(offers
+        .data
+        .into_iter()
+        .map(ShareOfferResponse::from)
+        .collect())
+}
+
+#[server(CreateShareOffer, "/api")]
+pub async fn create_share_offer(
+    title: String,
+    description: Option<String>,
+    total_quantity: u64,
+    price_per_share: String,
+    expires_on: String,
+) -> Result<ShareOfferResponse, ServerFnError> {
+    use crate::api::{
+        backends::shares_api, client::require_admin, config::ApiConfig,
+        traits::shares::CreateShareOfferRequest,
+    };
+
+    require_admin("create share offers").await?;
+    let price_per_share = price_per_share
+        .trim()
+        .parse::<rust_decimal::Decimal>()
+        .map_err(|_| ServerFnError::new("Price per share must be a number"))?;
+    let expires_at = chrono::NaiveDate::parse_from_str(expires_on.trim(), "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(23, 59, 59))
+        .map(|dt| dt.and_utc())
+        .ok_or_else(|| ServerFnError::new("Expiry date must be in YYYY-MM-DD format"))?;
+
+    let api = shares_api(&ApiConfig::from_env()).map_err(|e| ServerFnError::new(e.to_string()))?;
+    let offer = api
+        .create_share_offer(CreateShareOfferRequest {
+            title,
+            description,
+            total_quantity,
+            price_per_share,
+            available_from: None,
+            expires_at,
+        })
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    Ok(offer.into())
+}
+
+#[server(SubscribeShares, "/api")]
+pub async fn subscribe_shares(offer_id: uuid::Uuid, quantity: u64) -> Result<(), ServerFnError> {
+    use crate::api::{
+        backends::shares_api, client::get_request_user, config::ApiConfig,
+        traits::shares::SubscribeSharesRequest,
+    };
+
+    // Members subscribe for themselves
+    let user = get_request_user().await?;
+    let api = shares_api(&ApiConfig::from_env()).map_err(|e| ServerFnError::new(e.to_string()))?;
+    api.subscribe_shares(SubscribeSharesRequest {
+        offer_id,
+        user_id: user.id,
+        quantity,
+    })
+    .await
+    .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    Ok(())
+}
+
+#[server(PayForShares, "/api")]
+pub async fn pay_for_shares(
+    subscription_id: uuid::Uuid,
+    amount: String,
+    wallet_id: Option<String>,
+) -> Result<ShareSubscription, ServerFnError> {
+    use crate::api::{
+        backends::shares_api, client::get_request_user, config::ApiConfig,
+        traits::shares::PaySharesRequest,
+    };
+
+    // Members pay from their own wallets; admins may record cash received without one
+    let payer = get_request_user().await?;
+    let amount = amount
+        .trim()
+        .parse::<rust_decimal::Decimal>()
+        .map_err(|_| ServerFnError::new("Amount must be a number"))?;
+    let wallet_id = match wallet_id.as_deref().map(str::trim) {
+        None | Some("") => None,
+        Some(id) => Some(
+            id.parse::<uuid::Uuid>()
+                .map_err(|_| ServerFnError::new("Wallet ID is not valid"))?,
+        ),
+    };
+
+    let api = shares_api(&ApiConfig::from_env()).map_err(|e| ServerFnError::new(e.to_string()))?;
+    api.pay_for_shares(
+        PaySharesRequest {
+            subscription_id,
+            wallet_id,
+            amount,
+        },
+        &payer,
+    )
+    .await
+    .map_err(|e| ServerFnError::new(e.to_string()))
+}
+
+#[server(CloseShareOffer, "/api")]
+pub async fn close_share_offer(offer_id: uuid::Uuid) -> Result<(), ServerFnError> {
+    use crate::api::{backends::shares_api, client::require_admin, config::ApiConfig};
+
+    require_admin("close share offers").await?;
+    let api = shares_api(&ApiConfig::from_env()).map_err(|e| ServerFnError::new(e.to_string()))?;
+    api.close_share_offer(offer_id)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    Ok(())
+}
+
+/// A member's share holdings together with their ledger and transfer history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemberShareHistory {
+    pub user_id: uuid::Uuid,
+    pub holdings: u64,
+    /// Shares held under subscriptions that are not yet paid in full
+    pub unpaid: u64,
+    /// Subscriptions, newest first
+    pub subscriptions: Vec<ShareSubscription>,
+    /// Ledger entries, newest first
+    pub ledger: Vec<ShareLedgerEntry>,
+    pub transfers: Vec<ShareTransfer>,
+    /// Whether the requesting user may approve or reject transfers
+    pub can_review: bool,
+}
+
+#[server(GetMemberShareHistory, "/api", "GetJson")]
+pub async fn get_member_share_history(
+    user_id: uuid::Uuid,
+) -> Result<MemberShareHistory, ServerFnError> {
+    use crate::api::{
+        backends::shares_api, client::get_request_user, config::ApiConfig,
+        traits::shares::ShareTransferFilter, types::PaginationQuery,
+    };
+
+    // Members may only see their own share history
+    let user = get_request_user().await?;
+    if !user.is_admin() && user.id != user_id {
+        return Err(ServerFnError::new(
+            "You can only view your own share history",
+        ));
+    }
+
+    let api = shares_api(&ApiConfig::from_env()).map_err(|e| ServerFnError::new(e.to_string()))?;
+    let mut ledger = api
+        .get_member_share_ledger(user_id)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+    let holdings = ledger.last().map(|entry| entry.balance_after).unwrap_or(0);
+    ledger.reverse();
+
+    let subscriptions = api
+        .get_member_subscriptions(user_id)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+    let unpaid = subscriptions
+        .iter()
+        .filter(|sub| !sub.is_paid_up())
+        .map(|sub| sub.quantity)
+        .sum();
+
+    let transfers = api
+        .get_share_transfers(
+            ShareTransferFilter {
+                user_id: Some(user_id),
+                status: None,
+            },
+            PaginationQuery {
+                page: Some(1),
+                limit: Some(100),
+            },
+        )
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    Ok(MemberShareHistory {
+        user_id,
+        holdings,
+        unpaid,
+        subscriptions,
+        ledger,
+        transfers: transfers.data,
+        can_review: user.is_admin(),
+    })
+}
+
+#[server(GetPendingShareTransfers, "/api", "GetJson")]
+pub async fn get_pending_share_transfers() -> Result<Vec<ShareTransfer>, ServerFnError> {
+    use crate::api::{
+        backends::shares_api,
+        client::require_admin,
+        config::ApiConfig,
+        traits::shares::{ShareTransferFilter, ShareTransferStatus},
+        types::PaginationQuery,
+    };
+
+    require_admin("review share transfers").await?;
+    let api = shares_api(&ApiConfig::from_env()).map_err(|e| ServerFnError::new(e.to_string()))?;
+    let transfers = api
+        .get_share_transfers(
+            ShareTransferFilter {
+                user_id: None,
+                status: Some(ShareTransferStatus::Pending),
+            },
+            PaginationQuery {
+                page: Some(1),
+                limit: Some(100),
+            },
+        )
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+    Ok(transfers.data)
+}
+
+#[server(RequestShareTransfer, "/api")]
+pub async fn request_share_transfer(
+    from_user_id: uuid::Uuid,
+    to_user_id: uuid::Uuid,
+    quantity: u64,
+    price_per_share: Option<String>,
+    note: Option<String>,
+) -> Result<ShareTransfer, ServerFnError> {
+    use crate::api::{
+        backends::shares_api, client::get_request_user, config::ApiConfig,
+        traits::shares::TransferSharesRequest,
+    };
+
+    // Members may only offer their own shares; admins can record transfers for anyone
+    let requester = get_request_user().await?;
+    if requester.id != from_user_id && !requester.is_admin() {
+        return Err(ServerFnError::new("You can only transfer shares you own"));
+    }
+
+    let price_per_share = match price_per_share.as_deref().map(str::trim) {
+        None | Some("") => None,
+        Some(price) => Some(
+            price
+                .parse::<rust_decimal::Decimal>()
+                .map_err(|_| ServerFnError::new("Price per share must be a number"))?,
+        ),
+    };
+
+    let api = shares_api(&ApiConfig::from_env()).map_err(|e| ServerFnError::new(e.to_string()))?;
+    api.request_share_transfer(
+        TransferSharesRequest {
+            from_user_id,
+            to_user_id,
+            quantity,
+            price_per_share,
+            note,
+        },
+        &requester,
+    )
+    .await
+    .map_err(|e| ServerFnError::new(e.to_string()))
+}
+
+#[server(ApproveShareTransfer, "/api")]
+pub async fn approve_share_transfer(
+    transfer_id: uuid::Uuid,
+) -> Result<ShareTransfer, ServerFnError> {
+    use crate::api::{backends::shares_api, client::get_request_user, config::ApiConfig};
+
+    let reviewer = get_request_user().await?;
+    let api = shares_api(&ApiConfig::from_env()).map_err(|e| ServerFnError::new(e.to_string()))?;
+    api.approve_share_transfer(transfer_id, &reviewer)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))
+}
+
+#[server(RejectShareTransfer, "/api")]
+pub async fn reject_share_transfer(
+    transfer_id: uuid::Uuid,
+    reason: Option<String>,
+) -> Result<ShareTransfer, ServerFnError> {
+    use crate::api::{backends::shares_api, client::get_request_user, config::ApiConfig};
+
+    let reviewer = get_request_user().await?;
+    let api = shares_api(&ApiConfig::from_env()).map_err(|e| ServerFnError::new(e.to_string()))?;
+    api.reject_share_transfer(transfer_id, &reviewer, reason)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))
+}
+
 #[component]
 pub fn SharesPage() -> impl IntoView {
+    let create_action = ServerAction::<CreateShareOffer>::new();
+    let subscribe_action = ServerAction::<SubscribeShares>::new();
+    let close_action = ServerAction::<CloseShareOffer>::new();
+    let approve_action = ServerAction::<ApproveShareTransfer>::new();
+    let reject_action = ServerAction::<RejectShareTransfer>::new();
+    let (show_create_form, set_show_create_form) = signal(false);
+    let (refresh_tick, set_refresh_tick) = signal(0u32);
+
+    // Keep subscription progress current while the page is open
+    Effect::new(move |_| {
+        set_interval(
+            move || set_refresh_tick.update(|tick| *tick += 1),
+            std::time::Duration::from_secs(30),
+        );
+    });
+
+    let offers = Resource::new(
+        move || {
+            (
+                create_action.version().get(),
+                subscribe_action.version().get(),
+                close_action.version().get(),
+                refresh_tick.get(),
+            )
+        },
+        |_| get_share_offers(),
+    );
+    let pending_transfers = Resource::new(
+        move || {
+            (
+                approve_action.version().get(),
+                reject_action.version().get(),
+            )
+        },
+        |_| get_pending_share_transfers(),
+    );
+
+    let action_error = move || {
+        [
+            create_action.value().get().and_then(|r| r.err()),
+            subscribe_action.value().get().and_then(|r| r.err()),
+            close_action.value().get().and_then(|r| r.err()),
+            approve_action.value().get().and_then(|r| r.err()),
+            reject_action.value().get().and_then(|r| r.err()),
+        ]
+        .into_iter()
+        .flatten()
+        .next()
+        .map(|e| e.to_string())
+    };
+
     view! {
         <div class="space-y-6">
             <div class="flex justify-between items-center">
@@ -29,28 +409,312 @@ pub fn SharesPage() -> impl IntoView {
                 </div>
                 <button
                     class="bg-indigo-600 hover:bg-indigo-700 text-white font-medium py-2 px-4 rounded-lg"
-                    disabled
+                    on:click=move |_| set_show_create_form.update(|show| *show = !*show)
                 >
                     "Create Share Offer"
                 </button>
             </div>
 
-            <div class="bg-white shadow rounded-lg p-8">
-                <div class="text-center">
-                    <div class="mx-auto flex items-center justify-center h-12 w-12 rounded-full bg-yellow-100">
-                        <svg class="h-6 w-6 text-yellow-600" fill="none" viewBox="0 0 24 24" stroke="currentColor">
-                            <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M9 19v-6a2 2 0 00-2-2H5a2 2 0 00-2 2v6a2 2 0 002 2h2a2 2 0 002-2zm0 0V9a2 2 0 012-2h2a2 2 0 012 2v10m-6 0a2 2 0 002 2h2a2 2 0 002-2m0 0V5a2 2 0 012-2h2a2 2 0 012 2v14a2 2 0 01-2 2h-2a2 2 0 01-2-2z" />
-                        </svg>
-                    </div>
-                    <h3 class="mt-2 text-sm font-medium text-gray-900">"Shares functionality pending"</h3>
-                    <p class="mt-1 text-sm text-gray-500">
-                        "Share offers and trading operations will be available once the API adapter pattern is implemented."
-                    </p>
-                    <p class="mt-2 text-xs text-gray-400">
-                        "Configure API_BACKEND=nestjs to use the NestJS backend for shares management."
-                    </p>
+            {move || action_error().map(|message| view! {
+                <div class="bg-red-50 border border-red-200 rounded-lg p-4 text-sm text-red-700" role="alert">
+                    {message}
                 </div>
+            })}
+
+            <Show when=move || show_create_form.get()>
+                <CreateOfferForm action=create_action />
+            </Show>
+
+            <Suspense fallback=move || view! { <OffersSkeleton /> }>
+                {move || {
+                    offers.get().map(|result| match result {
+                        Ok(offers) if offers.is_empty() => view! { <EmptyOffers /> }.into_any(),
+                        Ok(offers) => view! {
+                            <div class="grid grid-cols-1 lg:grid-cols-2 gap-6">
+                                {offers.into_iter().map(|offer| view! {
+                                    <OfferCard
+                                        offer=offer
+                                        subscribe_action=subscribe_action
+                                        close_action=close_action
+                                    />
+                                }).collect_view()}
+                            </div>
+                        }.into_any(),
+                        Err(e) => view! {
+                            <div class="bg-red-50 border border-red-200 rounded-xl p-6 text-center" role="alert">
+                                <h3 class="text-sm font-semibold text-red-800">"Unable to load share offers"</h3>
+                                <p class="mt-1 text-sm text-red-600">{e.to_string()}</p>
+                            </div>
+                        }.into_any(),
+                    })
+                }}
+            </Suspense>
+
+            <Suspense fallback=|| ()>
+                {move || {
+                    pending_transfers.get().and_then(|result| result.ok()).filter(|t| !t.is_empty()).map(|transfers| view! {
+                        <div class="bg-white shadow rounded-lg p-6">
+                            <h3 class="text-lg font-medium text-gray-900 mb-4">"Pending Transfers"</h3>
+                            <ShareTransferTable
+                                transfers=transfers
+                                approve_action=approve_action
+                                reject_action=reject_action
+                                can_review=true
+                            />
+                        </div>
+                    })
+                }}
+            </Suspense>
+        </div>
+    }
+}
+
+/// Transfer history table with inline review controls for pending requests
+#[component]
+pub fn ShareTransferTable(
+    transfers: Vec<ShareTransfer>,
+    approve_action: ServerAction<ApproveShareTransfer>,
+    reject_action: ServerAction<RejectShareTransfer>,
+    can_review: bool,
+) -> impl IntoView {
+    use crate::api::traits::shares::ShareTransferStatus;
+
+    view! {
+        <div class="overflow-x-auto">
+            <table class="min-w-full divide-y divide-gray-200 text-sm">
+                <thead class="bg-gray-50">
+                    <tr>
+                        <th class="px-4 py-2 text-left font-medium text-gray-500">"Requested"</th>
+                        <th class="px-4 py-2 text-left font-medium text-gray-500">"From"</th>
+                        <th class="px-4 py-2 text-left font-medium text-gray-500">"To"</th>
+                        <th class="px-4 py-2 text-right font-medium text-gray-500">"Shares"</th>
+                        <th class="px-4 py-2 text-left font-medium text-gray-500">"Status"</th>
+                        <th class="px-4 py-2"></th>
+                    </tr>
+                </thead>
+                <tbody class="divide-y divide-gray-100">
+                    {transfers.into_iter().map(|transfer| {
+                        let badge_class = match transfer.status {
+                            ShareTransferStatus::Pending => "bg-yellow-100 text-yellow-800",
+                            ShareTransferStatus::Approved => "bg-green-100 text-green-800",
+                            ShareTransferStatus::Rejected => "bg-red-100 text-red-800",
+                        };
+                        let reviewable = can_review && transfer.status == ShareTransferStatus::Pending;
+                        let approve_id = transfer.id.to_string();
+                        let reject_id = transfer.id.to_string();
+
+                        view! {
+                            <tr>
+                                <td class="px-4 py-2 text-gray-700">{transfer.requested_at.format("%Y-%m-%d %H:%M").to_string()}</td>
+                                <td class="px-4 py-2">
+                                    <a href=format!("/members/{}", transfer.from_user_id) class="text-indigo-600 hover:underline font-mono text-xs">
+                                        {transfer.from_user_id.to_string()}
+                                    </a>
+                                </td>
+                                <td class="px-4 py-2">
+                                    <a href=format!("/members/{}", transfer.to_user_id) class="text-indigo-600 hover:underline font-mono text-xs">
+                                        {transfer.to_user_id.to_string()}
+                                    </a>
+                                </td>
+                                <td class="px-4 py-2 text-right font-semibold text-gray-900">{transfer.quantity}</td>
+                                <td class="px-4 py-2">
+                                    <span class=format!("px-2 py-1 rounded-full text-xs font-medium {}", badge_class)>
+                                        {transfer.status.as_str()}
+                                    </span>
+                                    {transfer.rejection_reason.clone().map(|reason| view! {
+                                        <p class="mt-1 text-xs text-gray-500">{reason}</p>
+                                    })}
+                                </td>
+                                <td class="px-4 py-2">
+                                    {reviewable.then(move || view! {
+                                        <div class="flex justify-end gap-2">
+                                            <ActionForm action=approve_action>
+                                                <input type="hidden" name="transfer_id" value=approve_id.clone()/>
+                                                <button type="submit" class="bg-green-600 hover:bg-green-700 text-white text-xs font-medium py-1 px-2 rounded-md">
+                                                    "Approve"
+                                                </button>
+                                            </ActionForm>
+                                            <ActionForm action=reject_action attr:class="flex gap-1">
+                                                <input type="hidden" name="transfer_id" value=reject_id.clone()/>
+                                                <input name="reason" type="text" placeholder="Reason" class="w-28 rounded-md border-gray-300 text-xs"/>
+                                                <button type="submit" class="bg-red-600 hover:bg-red-700 text-white text-xs font-medium py-1 px-2 rounded-md">
+                                                    "Reject"
+                                                </button>
+                                            </ActionForm>
+                                        </div>
+                                    })}
+                                </td>
+                            </tr>
+                        }
+                    }).collect_view()}
+                </tbody>
+            </table>
+        </div>
+    }
+}
+
+#[component]
+fn CreateOfferForm(action: ServerAction<CreateShareOffer>) -> impl IntoView {
+    let input_class = "block w-full rounded-md border-gray-300 shadow-sm focus:ring-indigo-500 focus:border-indigo-500 sm:text-sm";
+
+    view! {
+        <div class="bg-white shadow rounded-lg p-6">
+            <h3 class="text-lg font-medium text-gray-900 mb-4">"New Share Offer"</h3>
+            <ActionForm action=action attr:class="grid grid-cols-1 gap-4 sm:grid-cols-2">
+                <div class="sm:col-span-2">
+                    <label for="offer-title" class="block text-sm font-medium text-gray-700 mb-1">"Title"</label>
+                    <input id="offer-title" name="title" type="text" required=true class=input_class placeholder="e.g. 2026 Member Share Issue"/>
+                </div>
+                <div class="sm:col-span-2">
+                    <label for="offer-description" class="block text-sm font-medium text-gray-700 mb-1">"Description"</label>
+                    <input id="offer-description" name="description" type="text" class=input_class/>
+                </div>
+                <div>
+                    <label for="offer-quantity" class="block text-sm font-medium text-gray-700 mb-1">"Total shares"</label>
+                    <input id="offer-quantity" name="total_quantity" type="number" min="1" required=true class=input_class/>
+                </div>
+                <div>
+                    <label for="offer-price" class="block text-sm font-medium text-gray-700 mb-1">"Price per share (KES)"</label>
+                    <input id="offer-price" name="price_per_share" type="number" min="0.01" step="0.01" required=true class=input_class/>
+                </div>
+                <div>
+                    <label for="offer-expiry" class="block text-sm font-medium text-gray-700 mb-1">"Expires on"</label>
+                    <input id="offer-expiry" name="expires_on" type="date" required=true class=input_class/>
+                </div>
+                <div class="flex items-end justify-end">
+                    <button
+                        type="submit"
+                        class="bg-indigo-600 hover:bg-indigo-700 text-white font-medium py-2 px-4 rounded-lg disabled:opacity-50"
+                        disabled=move || action.pending().get()
+                    >
+                        {move || if action.pending().get() { "Creating..." } else { "Create Offer" }}
+                    </button>
+                </div>
+            </ActionForm>
+        </div>
+    }
+}
+
+#[component]
+fn OfferCard(
+    offer: ShareOfferResponse,
+    subscribe_action: ServerAction<SubscribeShares>,
+    close_action: ServerAction<CloseShareOffer>,
+) -> impl IntoView {
+    let (badge_class, badge_label) = match offer.status.as_str() {
+        "open" => ("bg-green-100 text-green-800", "Open"),
+        "fully_subscribed" => ("bg-blue-100 text-blue-800", "Fully subscribed"),
+        "closed" => ("bg-gray-100 text-gray-800", "Closed"),
+        "expired" => ("bg-yellow-100 text-yellow-800", "Expired"),
+        _ => ("bg-gray-100 text-gray-800", "Unknown"),
+    };
+    let is_open = offer.status == "open";
+    let subscribe_offer_id = offer.id.to_string();
+    let close_offer_id = offer.id.to_string();
+    let max_quantity = offer.available_quantity.to_string();
+    let subscribed = offer.total_quantity - offer.available_quantity;
+    let format_kes = |amount: Option<rust_decimal::Decimal>| {
+        amount
+            .map(|value| format!("{} KES", value.round_dp(2)))
+            .unwrap_or_else(|| "n/a".to_string())
+    };
+
+    view! {
+        <div class="bg-white shadow rounded-lg p-6 space-y-4">
+            <div class="flex items-start justify-between">
+                <div>
+                    <h3 class="text-lg font-medium text-gray-900">{offer.title.clone()}</h3>
+                    {offer.description.clone().map(|description| view! {
+                        <p class="mt-1 text-sm text-gray-500">{description}</p>
+                    })}
+                </div>
+                <span class=format!("px-2 py-1 rounded-full text-xs font-medium {}", badge_class)>
+                    {badge_label}
+                </span>
             </div>
+
+            <dl class="grid grid-cols-3 gap-4 text-sm">
+                <div>
+                    <dt class="text-gray-500">"Price / share"</dt>
+                    <dd class="font-semibold text-gray-900">{format_kes(offer.price_per_share)}</dd>
+                </div>
+                <div>
+                    <dt class="text-gray-500">"Offer value"</dt>
+                    <dd class="font-semibold text-gray-900">{format_kes(offer.total_value)}</dd>
+                </div>
+                <div>
+                    <dt class="text-gray-500">"Expires"</dt>
+                    <dd class="font-semibold text-gray-900">{offer.expires_at.clone()}</dd>
+                </div>
+            </dl>
+
+            <div class="space-y-1">
+                <div class="flex justify-between text-xs text-gray-500">
+                    <span>{format!("{} of {} shares subscribed", subscribed, offer.total_quantity)}</span>
+                    <span>{format!("{} available", offer.available_quantity)}</span>
+                </div>
+                <ProgressBar progress=offer.progress color="bg-indigo-600" />
+            </div>
+
+            {is_open.then(move || view! {
+                <div class="border-t border-gray-100 pt-4 flex flex-col sm:flex-row gap-3">
+                    <ActionForm action=subscribe_action attr:class="flex flex-1 gap-2">
+                        <input type="hidden" name="offer_id" value=subscribe_offer_id.clone()/>
+                        <input
+                            name="quantity"
+                            type="number"
+                            min="1"
+                            max=max_quantity.clone()
+                            required=true
+                            placeholder="Qty"
+                            class="w-24 rounded-md border-gray-300 shadow-sm sm:text-sm"
+                        />
+                        <button type="submit" class="bg-indigo-600 hover:bg-indigo-700 text-white text-sm font-medium py-2 px-3 rounded-md">
+                            "Subscribe"
+                        </button>
+                    </ActionForm>
+                    <ActionForm action=close_action>
+                        <input type="hidden" name="offer_id" value=close_offer_id.clone()/>
+                        <button type="submit" class="bg-gray-200 hover:bg-gray-300 text-gray-900 text-sm font-medium py-2 px-3 rounded-md">
+                            "Close Offer"
+                        </button>
+                    </ActionForm>
+                </div>
+            })}
+        </div>
+    }
+}
+
+#[component]
+fn EmptyOffers() -> impl IntoView {
+    view! {
+        <div class="bg-white shadow rounded-lg p-8">
+            <div class="text-center">
+                <div class="mx-auto flex items-center justify-center h-12 w-12 rounded-full bg-indigo-100">
+                    <svg class="h-6 w-6 text-indigo-600" fill="none" viewBox="0 0 24 24" stroke="currentColor">
+                        <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M9 19v-6a2 2 0 00-2-2H5a2 2 0 00-2 2v6a2 2 0 002 2h2a2 2 0 002-2zm0 0V9a2 2 0 012-2h2a2 2 0 012 2v10m-6 0a2 2 0 002 2h2a2 2 0 002-2m0 0V5a2 2 0 012-2h2a2 2 0 012 2v14a2 2 0 01-2 2h-2a2 2 0 01-2-2z" />
+                    </svg>
+                </div>
+                <h3 class="mt-2 text-sm font-medium text-gray-900">"No share offers yet"</h3>
+                <p class="mt-1 text-sm text-gray-500">
+                    "Create a share offer to let members subscribe to SACCO shares."
+                </p>
+            </div>
+        </div>
+    }
+}
+
+#[component]
+fn OffersSkeleton() -> impl IntoView {
+    view! {
+        <div class="grid grid-cols-1 lg:grid-cols-2 gap-6" role="status" aria-label="Loading share offers">
+            {(0..2).map(|_| view! {
+                <div class="bg-white shadow rounded-lg p-6 animate-pulse space-y-4">
+                    <div class="h-5 bg-gray-200 rounded w-1/2"></div>
+                    <div class="h-4 bg-gray-200 rounded w-3/4"></div>
+                    <div class="h-2 bg-gray-200 rounded"></div>
+                </div>
+            }).collect_view()}
         </div>
     }
 }
@@ -0,0 +1,535 @@
+use crate::api::traits::{
+    ledger::LedgerCurrency,
+    swaps::{Swap, SwapDirection, SwapStatus},
+    wallets::TransactionStatus,
+};
+use crate::components::ui::Spinner;
+use crate::{pages::fx::describe_rate, utils::format_sats};
+use leptos::prelude::*;
+use leptos_router::hooks::use_query_map;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SwapsOverview {
+    pub swaps: Vec<Swap>,
+    /// Whether M-Pesa requests can be completed by hand from this page
+    pub mock_gateway: bool,
+}
+
+#[server(GetSwaps, "/api", "GetJson")]
+pub async fn get_swaps(
+    direction: Option<String>,
+    status: Option<String>,
+) -> Result<SwapsOverview, ServerFnError> {
+    use crate::api::{
+        backends::swaps_api,
+        config::{ApiConfig, Backend},
+        traits::swaps::SwapFilter,
+    };
+
+    crate::api::client::require_admin("manage swaps").await?;
+    let direction = match direction.as_deref().unwrap_or_default() {
+        "onramp" => Some(SwapDirection::Onramp),
+        "offramp" => Some(SwapDirection::Offramp),
+        _ => None,
+    };
+    let status = match status.as_deref().unwrap_or_default() {
+        "pending" => Some(SwapStatus::Pending),
+        "completed" => Some(SwapStatus::Completed),
+        "failed" => Some(SwapStatus::Failed),
+        _ => None,
+    };
+
+    let config = ApiConfig::from_env();
+    let api = swaps_api(&config).map_err(|e| ServerFnError::new(e.to_string()))?;
+    let swaps = api
+        .list_swaps(SwapFilter {
+            direction,
+            status,
+            wallet_id: None,
+        })
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+    Ok(SwapsOverview {
+        swaps,
+        // The Rust backend only runs swaps against the mock gateway so far
+        mock_gateway: matches!(config.backend, Backend::Rust),
+    })
+}
+
+#[server(GetSwap, "/api", "GetJson")]
+pub async fn get_swap(swap_id: uuid::Uuid) -> Result<Swap, ServerFnError> {
+    use crate::api::{backends::swaps_api, config::ApiConfig};
+
+    crate::api::client::require_admin("manage swaps").await?;
+    let api = swaps_api(&ApiConfig::from_env()).map_err(|e| ServerFnError::new(e.to_string()))?;
+    api.get_swap(swap_id)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))
+}
+
+#[server(StartOnramp, "/api")]
+pub async fn start_onramp(
+    wallet_id: uuid::Uuid,
+    phone: String,
+    amount_kes: String,
+) -> Result<Swap, ServerFnError> {
+    use crate::api::{backends::swaps_api, config::ApiConfig, traits::swaps::OnrampRequest};
+
+    crate::api::client::require_admin("manage swaps").await?;
+    let amount_kes = amount_kes
+        .trim()
+        .replace(',', "")
+        .parse::<rust_decimal::Decimal>()
+        .map_err(|_| ServerFnError::new("The KES amount must be a number"))?;
+    let api = swaps_api(&ApiConfig::from_env()).map_err(|e| ServerFnError::new(e.to_string()))?;
+    api.create_onramp(OnrampRequest {
+        wallet_id,
+        phone,
+        amount_kes,
+    })
+    .await
+    .map_err(|e| ServerFnError::new(e.to_string()))
+}
+
+#[server(StartOfframp, "/api")]
+pub async fn start_offramp(
+    wallet_id: uuid::Uuid,
+    phone: String,
+    amount_sats: u64,
+) -> Result<Swap, ServerFnError> {
+    use crate::api::{backends::swaps_api, config::ApiConfig, traits::swaps::OfframpRequest};
+
+    let user = crate::api::client::require_admin("manage swaps").await?;
+    let api = swaps_api(&ApiConfig::from_env()).map_err(|e| ServerFnError::new(e.to_string()))?;
+    api.create_offramp(
+        OfframpRequest {
+            wallet_id,
+            phone,
+            amount_sats,
+        },
+        &user,
+    )
+    .await
+    .map_err(|e| ServerFnError::new(e.to_string()))
+}
+
+#[server(ReconcileSwap, "/api")]
+pub async fn reconcile_swap(swap_id: uuid::Uuid) -> Result<Swap, ServerFnError> {
+    use crate::api::{backends::swaps_api, config::ApiConfig};
+
+    crate::api::client::require_admin("manage swaps").await?;
+    let api = swaps_api(&ApiConfig::from_env()).map_err(|e| ServerFnError::new(e.to_string()))?;
+    api.reconcile_swap(swap_id)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))
+}
+
+#[server(RetrySwap, "/api")]
+pub async fn retry_swap(swap_id: uuid::Uuid) -> Result<Swap, ServerFnError> {
+    use crate::api::{backends::swaps_api, config::ApiConfig};
+
+    let user = crate::api::client::require_admin("manage swaps").await?;
+    let api = swaps_api(&ApiConfig::from_env()).map_err(|e| ServerFnError::new(e.to_string()))?;
+    api.retry_swap(swap_id, &user)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))
+}
+
+/// Answer a swap's pending M-Pesa request on the mock gateway, then reconcile it
+#[server(SimulateMpesa, "/api")]
+pub async fn simulate_mpesa(swap_id: uuid::Uuid, outcome: String) -> Result<Swap, ServerFnError> {
+    use crate::api::{backends::rust_backend, config::ApiConfig, traits::swaps::SwapsApi};
+
+    crate::api::client::require_admin("manage swaps").await?;
+    let backend =
+        rust_backend(&ApiConfig::from_env()).map_err(|e| ServerFnError::new(e.to_string()))?;
+    let swap = backend
+        .swaps
+        .get_swap(swap_id)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+    let answered = match outcome.as_str() {
+        "complete" => backend
+            .mpesa
+            .complete(&swap.mpesa_request_id)
+            .await
+            .map(|_| ()),
+        _ => {
+            backend
+                .mpesa
+                .fail(&swap.mpesa_request_id, "Request cancelled by user")
+                .await
+        }
+    };
+    answered.map_err(|e| ServerFnError::new(e.to_string()))?;
+    backend
+        .swaps
+        .reconcile_swap(swap_id)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))
+}
+
+fn swap_badge(swap: &Swap, now: chrono::DateTime<chrono::Utc>) -> (&'static str, &'static str) {
+    match swap.status {
+        SwapStatus::Pending if swap.is_stuck(now) => ("bg-orange-100 text-orange-800", "Stuck"),
+        SwapStatus::Pending => ("bg-yellow-100 text-yellow-800", "Pending"),
+        SwapStatus::Completed => ("bg-green-100 text-green-800", "Completed"),
+        SwapStatus::Failed => ("bg-red-100 text-red-800", "Failed"),
+    }
+}
+
+fn leg_label(status: &TransactionStatus) -> &'static str {
+    match status {
+        TransactionStatus::Pending => "Pending",
+        TransactionStatus::Confirmed => "Confirmed",
+        TransactionStatus::Failed => "Failed",
+        TransactionStatus::Cancelled => "Cancelled",
+    }
+}
+
+#[derive(Clone, Copy)]
+struct SwapActions {
+    reconcile: ServerAction<ReconcileSwap>,
+    retry: ServerAction<RetrySwap>,
+    simulate: ServerAction<SimulateMpesa>,
+}
+
+#[component]
+pub fn SwapsPage() -> impl IntoView {
+    let query = use_query_map();
+    let onramp_action = ServerAction::<StartOnramp>::new();
+    let offramp_action = ServerAction::<StartOfframp>::new();
+    let actions = SwapActions {
+        reconcile: ServerAction::<ReconcileSwap>::new(),
+        retry: ServerAction::<RetrySwap>::new(),
+        simulate: ServerAction::<SimulateMpesa>::new(),
+    };
+
+    let changes = move || {
+        (
+            onramp_action.version().get(),
+            offramp_action.version().get(),
+            actions.reconcile.version().get(),
+            actions.retry.version().get(),
+            actions.simulate.version().get(),
+        )
+    };
+    let filter = move || {
+        let query = query.read();
+        (query.get("direction"), query.get("status"))
+    };
+    let overview = Resource::new(
+        move || (filter(), changes()),
+        |((direction, status), _)| get_swaps(direction, status),
+    );
+    let selected = move || {
+        query
+            .read()
+            .get("swap")
+            .and_then(|id| uuid::Uuid::parse_str(&id).ok())
+    };
+    let detail = Resource::new(
+        move || (selected(), changes()),
+        |(swap_id, _)| async move {
+            match swap_id {
+                Some(id) => get_swap(id).await.map(Some),
+                None => Ok(None),
+            }
+        },
+    );
+
+    let action_error = move || {
+        [
+            onramp_action.value().get().and_then(|r| r.err()),
+            offramp_action.value().get().and_then(|r| r.err()),
+            actions.reconcile.value().get().and_then(|r| r.err()),
+            actions.retry.value().get().and_then(|r| r.err()),
+            actions.simulate.value().get().and_then(|r| r.err()),
+        ]
+        .into_iter()
+        .flatten()
+        .next()
+        .map(|e| e.to_string())
+    };
+    let field = move |key: &'static str| move || query.read().get(key).unwrap_or_default();
+    let input_class = "block w-full rounded-md border-gray-300 shadow-sm sm:text-sm";
+
+    view! {
+        <div class="space-y-6">
+            <div>
+                <h1 class="text-2xl font-semibold text-gray-900">"Swaps"</h1>
+                <p class="mt-1 text-sm text-gray-500">"M-Pesa onramps and offramps, with both legs of every swap"</p>
+            </div>
+
+            {move || action_error().map(|message| view! {
+                <div class="bg-red-50 border border-red-200 rounded-lg p-4 text-sm text-red-700" role="alert">
+                    {message}
+                </div>
+            })}
+
+            <div class="grid grid-cols-1 lg:grid-cols-2 gap-6">
+                <div class="bg-white shadow rounded-lg p-6 space-y-4">
+                    <h3 class="text-lg font-medium text-gray-900">"Start Onramp"</h3>
+                    <ActionForm action=onramp_action attr:class="grid grid-cols-1 sm:grid-cols-3 gap-3 items-end">
+                        <label class="text-xs text-gray-500 sm:col-span-3">
+                            "Wallet ID"
+                            <input name="wallet_id" type="text" required=true class=input_class/>
+                        </label>
+                        <label class="text-xs text-gray-500">
+                            "Phone"
+                            <input name="phone" type="tel" placeholder="0712 345 678" required=true class=input_class/>
+                        </label>
+                        <label class="text-xs text-gray-500">
+                            "Amount (KES)"
+                            <input name="amount_kes" type="number" min="1" step="1" required=true class=input_class/>
+                        </label>
+                        <button type="submit" class="bg-indigo-600 hover:bg-indigo-700 text-white text-sm font-medium py-2 px-4 rounded-md">
+                            "Send STK Push"
+                        </button>
+                    </ActionForm>
+                </div>
+                <div class="bg-white shadow rounded-lg p-6 space-y-4">
+                    <h3 class="text-lg font-medium text-gray-900">"Start Offramp"</h3>
+                    <ActionForm action=offramp_action attr:class="grid grid-cols-1 sm:grid-cols-3 gap-3 items-end">
+                        <label class="text-xs text-gray-500 sm:col-span-3">
+                            "Wallet ID"
+                            <input name="wallet_id" type="text" required=true class=input_class/>
+                        </label>
+                        <label class="text-xs text-gray-500">
+                            "Phone"
+                            <input name="phone" type="tel" placeholder="0712 345 678" required=true class=input_class/>
+                        </label>
+                        <label class="text-xs text-gray-500">
+                            "Amount (sats)"
+                            <input name="amount_sats" type="number" min="1" step="1" required=true class=input_class/>
+                        </label>
+                        <button type="submit" class="bg-indigo-600 hover:bg-indigo-700 text-white text-sm font-medium py-2 px-4 rounded-md">
+                            "Pay Out"
+                        </button>
+                    </ActionForm>
+                </div>
+            </div>
+
+            <Suspense fallback=|| ()>
+                {move || detail.get().map(|result| match result {
+                    Ok(Some(swap)) => view! { <SwapDetail swap=swap /> }.into_any(),
+                    Ok(None) => ().into_any(),
+                    Err(e) => view! {
+                        <div class="bg-red-50 border border-red-200 rounded-lg p-4 text-sm text-red-700" role="alert">
+                            {e.to_string()}
+                        </div>
+                    }.into_any(),
+                })}
+            </Suspense>
+
+            <div class="bg-white shadow rounded-lg p-6 space-y-4">
+                <div class="flex flex-col md:flex-row md:items-end md:justify-between gap-4">
+                    <h3 class="text-lg font-medium text-gray-900">"All Swaps"</h3>
+                    <form method="get" action="/swaps" class="flex flex-wrap items-end gap-3">
+                        <label class="text-xs text-gray-500">
+                            "Direction"
+                            <select name="direction" class=input_class>
+                                <option value="" selected=move || field("direction")().is_empty()>"All"</option>
+                                <option value="onramp" selected=move || field("direction")() == "onramp">"Onramp"</option>
+                                <option value="offramp" selected=move || field("direction")() == "offramp">"Offramp"</option>
+                            </select>
+                        </label>
+                        <label class="text-xs text-gray-500">
+                            "Status"
+                            <select name="status" class=input_class>
+                                <option value="" selected=move || field("status")().is_empty()>"All"</option>
+                                <option value="pending" selected=move || field("status")() == "pending">"Pending"</option>
+                                <option value="completed" selected=move || field("status")() == "completed">"Completed"</option>
+                                <option value="failed" selected=move || field("status")() == "failed">"Failed"</option>
+                            </select>
+                        </label>
+                        <button type="submit" class="border border-gray-300 text-gray-700 hover:bg-gray-50 text-sm font-medium py-2 px-4 rounded-md">
+                            "Filter"
+                        </button>
+                    </form>
+                </div>
+
+                <Suspense fallback=move || view! { <div class="flex justify-center py-6"><Spinner /></div> }>
+                    {move || overview.get().map(|result| match result {
+                        Ok(overview) if overview.swaps.is_empty() => view! {
+                            <p class="text-sm text-gray-500">"No swaps match."</p>
+                        }.into_any(),
+                        Ok(overview) => view! {
+                            <SwapTable swaps=overview.swaps mock_gateway=overview.mock_gateway actions=actions />
+                        }.into_any(),
+                        Err(e) => view! {
+                            <div class="bg-red-50 border border-red-200 rounded-lg p-4 text-sm text-red-700" role="alert">
+                                {e.to_string()}
+                            </div>
+                        }.into_any(),
+                    })}
+                </Suspense>
+            </div>
+        </div>
+    }
+}
+
+#[component]
+fn SwapTable(swaps: Vec<Swap>, mock_gateway: bool, actions: SwapActions) -> impl IntoView {
+    let now = chrono::Utc::now();
+
+    view! {
+        <div class="overflow-x-auto">
+            <table class="min-w-full divide-y divide-gray-200 text-sm">
+                <thead class="bg-gray-50">
+                    <tr>
+                        <th class="px-4 py-2 text-left font-medium text-gray-500">"Started"</th>
+                        <th class="px-4 py-2 text-left font-medium text-gray-500">"Direction"</th>
+                        <th class="px-4 py-2 text-left font-medium text-gray-500">"Phone"</th>
+                        <th class="px-4 py-2 text-right font-medium text-gray-500">"KES"</th>
+                        <th class="px-4 py-2 text-right font-medium text-gray-500">"Sats"</th>
+                        <th class="px-4 py-2 text-left font-medium text-gray-500">"M-Pesa"</th>
+                        <th class="px-4 py-2 text-left font-medium text-gray-500">"Lightning"</th>
+                        <th class="px-4 py-2 text-left font-medium text-gray-500">"Status"</th>
+                        <th class="px-4 py-2"></th>
+                    </tr>
+                </thead>
+                <tbody class="divide-y divide-gray-100">
+                    {swaps.into_iter().map(|swap| {
+                        let (badge_class, badge_label) = swap_badge(&swap, now);
+                        let is_pending = swap.status == SwapStatus::Pending;
+                        let is_failed = swap.status == SwapStatus::Failed;
+                        let can_simulate = mock_gateway
+                            && is_pending
+                            && swap.mpesa_status == TransactionStatus::Pending;
+                        let id = swap.id.to_string();
+                        let (reconcile_id, retry_id, complete_id, fail_id) =
+                            (id.clone(), id.clone(), id.clone(), id.clone());
+                        let mpesa = swap
+                            .mpesa_reference
+                            .clone()
+                            .unwrap_or_else(|| leg_label(&swap.mpesa_status).to_string());
+                        view! {
+                            <tr>
+                                <td class="px-4 py-2 text-gray-700">{swap.created_at.format("%Y-%m-%d %H:%M").to_string()}</td>
+                                <td class="px-4 py-2 text-gray-700">{swap.direction.label()}</td>
+                                <td class="px-4 py-2 text-gray-700">{swap.phone.clone()}</td>
+                                <td class="px-4 py-2 text-right text-gray-900">{LedgerCurrency::Kes.format(swap.amount_kes)}</td>
+                                <td class="px-4 py-2 text-right text-gray-900">{format_sats(swap.amount_sats)}</td>
+                                <td class="px-4 py-2 text-gray-700">{mpesa}</td>
+                                <td class="px-4 py-2 text-gray-700">{leg_label(&swap.lightning_status)}</td>
+                                <td class="px-4 py-2">
+                                    <span class=format!("px-2 py-1 rounded-full text-xs font-medium {}", badge_class)>{badge_label}</span>
+                                </td>
+                                <td class="px-4 py-2">
+                                    <div class="flex flex-wrap justify-end gap-2">
+                                        <a href=format!("/swaps?swap={}", id) class="text-indigo-600 hover:text-indigo-800 text-xs font-medium py-1.5">"View"</a>
+                                        {is_pending.then(move || view! {
+                                            <ActionForm action=actions.reconcile>
+                                                <input type="hidden" name="swap_id" value=reconcile_id/>
+                                                <button type="submit" class="border border-gray-300 text-gray-700 hover:bg-gray-50 text-xs font-medium py-1.5 px-3 rounded-md">
+                                                    "Reconcile"
+                                                </button>
+                                            </ActionForm>
+                                        })}
+                                        {is_failed.then(move || view! {
+                                            <ActionForm action=actions.retry>
+                                                <input type="hidden" name="swap_id" value=retry_id/>
+                                                <button type="submit" class="bg-indigo-600 hover:bg-indigo-700 text-white text-xs font-medium py-1.5 px-3 rounded-md">
+                                                    "Retry"
+                                                </button>
+                                            </ActionForm>
+                                        })}
+                                        {can_simulate.then(move || view! {
+                                            <ActionForm action=actions.simulate>
+                                                <input type="hidden" name="swap_id" value=complete_id/>
+                                                <input type="hidden" name="outcome" value="complete"/>
+                                                <button type="submit" class="bg-green-600 hover:bg-green-700 text-white text-xs font-medium py-1.5 px-3 rounded-md">
+                                                    "Simulate Paid"
+                                                </button>
+                                            </ActionForm>
+                                            <ActionForm action=actions.simulate>
+                                                <input type="hidden" name="swap_id" value=fail_id/>
+                                                <input type="hidden" name="outcome" value="fail"/>
+                                                <button type="submit" class="bg-red-600 hover:bg-red-700 text-white text-xs font-medium py-1.5 px-3 rounded-md">
+                                                    "Simulate Cancel"
+                                                </button>
+                                            </ActionForm>
+                                        })}
+                                    </div>
+                                </td>
+                            </tr>
+                        }
+                    }).collect_view()}
+                </tbody>
+            </table>
+        </div>
+        {mock_gateway.then(|| view! {
+            <p class="text-xs text-gray-500">"M-Pesa requests go to the mock gateway; answer them with the simulate buttons."</p>
+        })}
+    }
+}
+
+#[component]
+fn SwapDetail(swap: Swap) -> impl IntoView {
+    let (badge_class, badge_label) = swap_badge(&swap, chrono::Utc::now());
+    let (sats_leg, mpesa_leg) = match swap.direction {
+        SwapDirection::Onramp => ("Wallet credit", "STK push"),
+        SwapDirection::Offramp => ("Wallet hold", "B2C payout"),
+    };
+    let rows = vec![
+        ("Wallet", swap.wallet_id.to_string()),
+        ("Phone", swap.phone.clone()),
+        ("Amount", LedgerCurrency::Kes.format(swap.amount_kes)),
+        ("Sats", format_sats(swap.amount_sats)),
+        ("Rate used", describe_rate(&swap.rate)),
+        (mpesa_leg, leg_label(&swap.mpesa_status).to_string()),
+        (
+            "M-Pesa request",
+            if swap.mpesa_request_id.is_empty() {
+                "—".to_string()
+            } else {
+                swap.mpesa_request_id.clone()
+            },
+        ),
+        (
+            "M-Pesa receipt",
+            swap.mpesa_reference
+                .clone()
+                .unwrap_or_else(|| "—".to_string()),
+        ),
+        (sats_leg, leg_label(&swap.lightning_status).to_string()),
+        ("Wallet transaction", swap.wallet_transaction_id.to_string()),
+        ("Attempts", swap.attempts.to_string()),
+        (
+            "Started",
+            swap.created_at.format("%Y-%m-%d %H:%M:%S UTC").to_string(),
+        ),
+        (
+            "Last update",
+            swap.updated_at.format("%Y-%m-%d %H:%M:%S UTC").to_string(),
+        ),
+        (
+            "Completed",
+            swap.completed_at
+                .map(|at| at.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+                .unwrap_or_else(|| "—".to_string()),
+        ),
+    ];
+
+    view! {
+        <div class="bg-white shadow rounded-lg p-6 space-y-4">
+            <div class="flex justify-between items-start">
+                <div>
+                    <h3 class="text-lg font-medium text-gray-900">{format!("{} {}", swap.direction.label(), swap.id)}</h3>
+                    {swap.failure_reason.clone().map(|reason| view! { <p class="text-xs text-red-600">{reason}</p> })}
+                </div>
+                <span class=format!("px-2 py-1 rounded-full text-xs font-medium {}", badge_class)>{badge_label}</span>
+            </div>
+            <dl class="grid grid-cols-1 md:grid-cols-2 gap-x-6 gap-y-2 text-sm">
+                {rows.into_iter().map(|(label, value)| view! {
+                    <div class="flex justify-between gap-4 border-b border-gray-100 py-1">
+                        <dt class="text-gray-500">{label}</dt>
+                        <dd class="text-gray-900 text-right break-all">{value}</dd>
+                    </div>
+                }).collect_view()}
+            </dl>
+        </div>
+    }
+}
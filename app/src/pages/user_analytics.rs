@@ -0,0 +1,389 @@
+use crate::api::dashboard_client::{
+    CohortData, Demographics, FeatureAdoptionData, FeatureUsageData, UserAnalyticsResponse,
+};
+use crate::api::get_user_analytics;
+use crate::components::ui::{ChartKind, ChartPoint, Spinner, TrendChart};
+use crate::pages::dashboard::{chart_points, TrendPeriod};
+use crate::utils::{format_count, percent};
+use chrono::Utc;
+use leptos::prelude::*;
+use std::collections::HashMap;
+
+// Each section is its own component returning `AnyView`, which keeps the
+// rendered view types shallow; one monolithic view of this response is deep
+// enough to overflow the stack while rendering.
+
+fn format_duration(seconds: f64) -> String {
+    let seconds = seconds.max(0.0).round() as u64;
+    if seconds >= 60 {
+        format!("{}m {}s", seconds / 60, seconds % 60)
+    } else {
+        format!("{}s", seconds)
+    }
+}
+
+/// Heatmap cell shading for a retention percentage
+fn heat_style(value: f64) -> String {
+    let alpha = (value / 100.0).clamp(0.0, 1.0) * 0.85 + 0.05;
+    let text = if alpha > 0.5 { "#ffffff" } else { "#111827" };
+    format!(
+        "background-color: rgba(79, 70, 229, {:.2}); color: {}",
+        alpha, text
+    )
+}
+
+#[component]
+pub fn UserAnalyticsPage() -> impl IntoView {
+    let analytics = Resource::new(|| (), |_| get_user_analytics());
+
+    view! {
+        <div class="space-y-6">
+            <div>
+                <h1 class="text-2xl font-semibold text-gray-900">"User Analytics"</h1>
+                <p class="mt-1 text-sm text-gray-500">"Engagement, cohort retention and feature adoption across members"</p>
+            </div>
+
+            <Suspense fallback=move || view! { <div class="flex justify-center py-12"><Spinner /></div> }>
+                {move || analytics.get().map(|result| match result {
+                    Ok(response) => match response.data {
+                        Some(data) => view! { <AnalyticsContent data=data /> }.into_any(),
+                        None => view! {
+                            <div class="bg-red-50 border border-red-200 rounded-lg p-4 text-sm text-red-700" role="alert">
+                                {response.message.unwrap_or_else(|| "User analytics are unavailable".to_string())}
+                            </div>
+                        }.into_any(),
+                    },
+                    Err(e) => view! {
+                        <div class="bg-red-50 border border-red-200 rounded-lg p-4 text-sm text-red-700" role="alert">
+                            {e.to_string()}
+                        </div>
+                    }.into_any(),
+                })}
+            </Suspense>
+        </div>
+    }
+}
+
+#[component]
+fn AnalyticsContent(data: UserAnalyticsResponse) -> impl IntoView {
+    view! {
+        <EngagementSummary data=data.clone() />
+        <ActivityTrends data=data.clone() />
+        <CohortHeatmap
+            cohorts=data.retention.cohort_data.clone()
+            day_rates=vec![
+                ("Day 1", data.retention.day1),
+                ("Day 7", data.retention.day7),
+                ("Day 30", data.retention.day30),
+                ("Day 90", data.retention.day90),
+            ]
+        />
+        <FeatureUsageTable features=data.feature_usage.top_features.clone() />
+        <FeatureAdoptionTable adoption=data.feature_usage.adoption.clone() />
+        <DemographicsSection demographics=data.demographics />
+    }
+    .into_any()
+}
+
+#[component]
+fn EngagementSummary(data: UserAnalyticsResponse) -> impl IntoView {
+    let engagement = &data.engagement;
+    let registrations = &data.membership_activity.new_registrations;
+    let participation = &data.membership_activity.chama_participation;
+    let cards = vec![
+        (
+            "Daily active",
+            engagement.daily_active_users.to_string(),
+            format!(
+                "{} weekly · {} monthly",
+                engagement.weekly_active_users, engagement.monthly_active_users
+            ),
+        ),
+        (
+            "Stickiness (DAU/MAU)",
+            percent(engagement.dau_mau_ratio * 100.0),
+            "Share of monthly users active today".to_string(),
+        ),
+        (
+            "Sessions today",
+            engagement.session_metrics.sessions_today.to_string(),
+            format!(
+                "Avg. {} · peak {} concurrent",
+                format_duration(engagement.session_metrics.average_duration),
+                engagement.session_metrics.peak_concurrent_users
+            ),
+        ),
+        (
+            "New registrations",
+            registrations.today.to_string(),
+            format!(
+                "{} this week · {} this month",
+                registrations.this_week, registrations.this_month
+            ),
+        ),
+        (
+            "Members in chamas",
+            participation.active_members_in_chamas.to_string(),
+            format!(
+                "{:.1} chamas per member",
+                participation.average_chamas_per_member
+            ),
+        ),
+    ];
+
+    view! {
+        <div class="grid grid-cols-1 sm:grid-cols-2 lg:grid-cols-5 gap-4">
+            {cards.into_iter().map(|(label, value, detail)| view! {
+                <div class="bg-white shadow rounded-lg p-4">
+                    <dt class="text-xs text-gray-500">{label}</dt>
+                    <dd class="text-2xl font-semibold text-gray-900">{value}</dd>
+                    <p class="text-xs text-gray-500 mt-1">{detail}</p>
+                </div>
+            }).collect_view()}
+        </div>
+    }
+    .into_any()
+}
+
+#[component]
+fn ActivityTrends(data: UserAnalyticsResponse) -> impl IntoView {
+    let today = Utc::now().date_naive();
+    let engagement = &data.engagement;
+    let active = vec![
+        ChartPoint {
+            label: "Daily".to_string(),
+            value: engagement.daily_active_users as f64,
+            display: None,
+        },
+        ChartPoint {
+            label: "Weekly".to_string(),
+            value: engagement.weekly_active_users as f64,
+            display: None,
+        },
+        ChartPoint {
+            label: "Monthly".to_string(),
+            value: engagement.monthly_active_users as f64,
+            display: None,
+        },
+    ];
+    let registrations = chart_points(
+        &data.demographics.registration_trend,
+        TrendPeriod::All,
+        today,
+    );
+    let chama_members = chart_points(
+        &data
+            .membership_activity
+            .chama_participation
+            .chama_membership_trend,
+        TrendPeriod::All,
+        today,
+    );
+    let charts = vec![
+        ("Active Users", active, ChartKind::Bar, "#4f46e5"),
+        ("Registrations", registrations, ChartKind::Area, "#16a34a"),
+        (
+            "Chama Membership",
+            chama_members,
+            ChartKind::Line,
+            "#ca8a04",
+        ),
+    ];
+
+    view! {
+        <div class="grid grid-cols-1 lg:grid-cols-3 gap-6">
+            {charts.into_iter().map(|(title, points, kind, color)| view! {
+                <div class="bg-white shadow rounded-lg p-6">
+                    <h3 class="text-sm font-medium text-gray-900 mb-2">{title}</h3>
+                    <TrendChart points=points kind=kind title=title color=color format=format_count />
+                </div>
+            }).collect_view()}
+        </div>
+    }
+    .into_any()
+}
+
+#[component]
+fn CohortHeatmap(cohorts: Vec<CohortData>, day_rates: Vec<(&'static str, f64)>) -> impl IntoView {
+    let mut cohorts = cohorts;
+    cohorts.sort_by(|a, b| b.cohort_month.cmp(&a.cohort_month));
+
+    view! {
+        <div class="bg-white shadow rounded-lg p-6 space-y-4">
+            <div class="flex flex-wrap items-center justify-between gap-3">
+                <h3 class="text-lg font-medium text-gray-900">"Cohort Retention"</h3>
+                <div class="flex flex-wrap gap-3 text-xs text-gray-500">
+                    {day_rates.into_iter().map(|(label, rate)| view! {
+                        <span>{format!("{}: ", label)}<span class="font-medium text-gray-900">{percent(rate)}</span></span>
+                    }).collect_view()}
+                </div>
+            </div>
+            {if cohorts.is_empty() {
+                view! { <p class="text-sm text-gray-500">"No cohorts have been reported yet."</p> }.into_any()
+            } else {
+                view! {
+                    <div class="overflow-x-auto">
+                        <table class="min-w-full text-sm">
+                            <thead>
+                                <tr>
+                                    <th class="px-3 py-2 text-left font-medium text-gray-500">"Cohort"</th>
+                                    <th class="px-3 py-2 text-right font-medium text-gray-500">"New users"</th>
+                                    {["Month 1", "Month 2", "Month 3", "Month 6"].into_iter().map(|label| view! {
+                                        <th class="px-3 py-2 text-center font-medium text-gray-500">{label}</th>
+                                    }).collect_view()}
+                                </tr>
+                            </thead>
+                            <tbody>
+                                {cohorts.into_iter().map(|cohort| {
+                                    let retention = cohort.retention;
+                                    let cells = [retention.month1, retention.month2, retention.month3, retention.month6];
+                                    view! {
+                                        <tr>
+                                            <td class="px-3 py-1 text-gray-700">{cohort.cohort_month}</td>
+                                            <td class="px-3 py-1 text-right text-gray-700">{cohort.new_users}</td>
+                                            {cells.into_iter().map(|value| view! {
+                                                <td class="px-1 py-1">
+                                                    <div class="rounded text-center text-xs font-medium py-2" style=heat_style(value)>
+                                                        {percent(value)}
+                                                    </div>
+                                                </td>
+                                            }).collect_view()}
+                                        </tr>
+                                    }
+                                }).collect_view()}
+                            </tbody>
+                        </table>
+                    </div>
+                }.into_any()
+            }}
+        </div>
+    }
+    .into_any()
+}
+
+#[component]
+fn FeatureUsageTable(features: Vec<FeatureUsageData>) -> impl IntoView {
+    let mut features = features;
+    features.sort_by_key(|feature| std::cmp::Reverse(feature.usage_count));
+
+    view! {
+        <div class="bg-white shadow rounded-lg p-6 space-y-4">
+            <h3 class="text-lg font-medium text-gray-900">"Feature Usage"</h3>
+            {if features.is_empty() {
+                view! { <p class="text-sm text-gray-500">"No feature usage has been reported yet."</p> }.into_any()
+            } else {
+                view! {
+                    <div class="overflow-x-auto">
+                        <table class="min-w-full divide-y divide-gray-200 text-sm">
+                            <thead class="bg-gray-50">
+                                <tr>
+                                    <th class="px-4 py-2 text-left font-medium text-gray-500">"Feature"</th>
+                                    <th class="px-4 py-2 text-right font-medium text-gray-500">"Uses"</th>
+                                    <th class="px-4 py-2 text-right font-medium text-gray-500">"Unique users"</th>
+                                    <th class="px-4 py-2 text-right font-medium text-gray-500">"Avg. duration"</th>
+                                    <th class="px-4 py-2 text-right font-medium text-gray-500">"Success rate"</th>
+                                </tr>
+                            </thead>
+                            <tbody class="divide-y divide-gray-100">
+                                {features.into_iter().map(|feature| view! {
+                                    <tr>
+                                        <td class="px-4 py-2 text-gray-900">{feature.feature_name}</td>
+                                        <td class="px-4 py-2 text-right text-gray-700">{feature.usage_count}</td>
+                                        <td class="px-4 py-2 text-right text-gray-700">{feature.unique_users}</td>
+                                        <td class="px-4 py-2 text-right text-gray-700">{format_duration(feature.average_duration)}</td>
+                                        <td class="px-4 py-2 text-right text-gray-700">{percent(feature.success_rate)}</td>
+                                    </tr>
+                                }).collect_view()}
+                            </tbody>
+                        </table>
+                    </div>
+                }.into_any()
+            }}
+        </div>
+    }
+    .into_any()
+}
+
+#[component]
+fn FeatureAdoptionTable(adoption: Vec<FeatureAdoptionData>) -> impl IntoView {
+    let mut adoption = adoption;
+    adoption.sort_by(|a, b| b.adoption_rate.total_cmp(&a.adoption_rate));
+
+    view! {
+        <div class="bg-white shadow rounded-lg p-6 space-y-4">
+            <h3 class="text-lg font-medium text-gray-900">"Feature Adoption"</h3>
+            {if adoption.is_empty() {
+                view! { <p class="text-sm text-gray-500">"No adoption data has been reported yet."</p> }.into_any()
+            } else {
+                view! {
+                    <ul class="space-y-3 text-sm">
+                        {adoption.into_iter().map(|feature| view! {
+                            <li>
+                                <div class="flex justify-between">
+                                    <span class="text-gray-900">{feature.feature_name}</span>
+                                    <span class="text-gray-700">
+                                        {format!("{} · adopted after {:.1} days", percent(feature.adoption_rate), feature.time_to_adoption)}
+                                    </span>
+                                </div>
+                                <div class="w-full bg-gray-200 rounded-full h-2 mt-1">
+                                    <div class="bg-indigo-500 h-2 rounded-full"
+                                         style=format!("width: {:.1}%", feature.adoption_rate.clamp(0.0, 100.0))></div>
+                                </div>
+                            </li>
+                        }).collect_view()}
+                    </ul>
+                }.into_any()
+            }}
+        </div>
+    }
+    .into_any()
+}
+
+#[component]
+fn DemographicsSection(demographics: Demographics) -> impl IntoView {
+    let breakdowns = vec![
+        ("By Region", demographics.by_region),
+        ("By Device", demographics.by_device_type),
+        ("By App Version", demographics.by_app_version),
+    ];
+
+    view! {
+        <div class="grid grid-cols-1 lg:grid-cols-3 gap-6">
+            {breakdowns.into_iter().map(|(title, counts)| view! {
+                <Breakdown title=title counts=counts />
+            }).collect_view()}
+        </div>
+    }
+    .into_any()
+}
+
+#[component]
+fn Breakdown(title: &'static str, counts: HashMap<String, u64>) -> impl IntoView {
+    let total: u64 = counts.values().sum();
+    let mut counts: Vec<(String, u64)> = counts.into_iter().collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+    view! {
+        <div class="bg-white shadow rounded-lg p-6 space-y-3">
+            <h3 class="text-sm font-medium text-gray-900">{title}</h3>
+            {if counts.is_empty() {
+                view! { <p class="text-sm text-gray-500">"Nothing reported."</p> }.into_any()
+            } else {
+                view! {
+                    <ul class="divide-y divide-gray-100 text-sm">
+                        {counts.into_iter().map(|(name, count)| {
+                            let share = if total > 0 { count as f64 / total as f64 * 100.0 } else { 0.0 };
+                            view! {
+                                <li class="flex justify-between py-1.5">
+                                    <span class="text-gray-700">{name}</span>
+                                    <span class="text-gray-900">{format!("{} ({})", count, percent(share))}</span>
+                                </li>
+                            }
+                        }).collect_view()}
+                    </ul>
+                }.into_any()
+            }}
+        </div>
+    }
+    .into_any()
+}
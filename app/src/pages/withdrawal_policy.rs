@@ -0,0 +1,463 @@
+use crate::api::{
+    traits::withdrawals::{
+        ApprovalStatus, DecisionOutcome, GroupLimits, WithdrawalApproval, WithdrawalDecision,
+        WithdrawalLimits, WithdrawalPolicy,
+    },
+    types::common::Role,
+};
+use crate::components::ui::Spinner;
+use crate::utils::format_sats;
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Decisions shown in the log
+const DECISION_LOG_SIZE: usize = 50;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WithdrawalPolicyOverview {
+    pub policy: WithdrawalPolicy,
+    pub pending: Vec<WithdrawalApproval>,
+    pub decisions: Vec<WithdrawalDecision>,
+}
+
+/// A blank field means no limit
+#[cfg(feature = "ssr")]
+fn parse_limit(label: &str, value: &str) -> Result<Option<u64>, ServerFnError> {
+    let value = value.trim().replace(',', "");
+    if value.is_empty() {
+        return Ok(None);
+    }
+    value
+        .parse()
+        .map(Some)
+        .map_err(|_| ServerFnError::new(format!("{} must be a whole number of sats", label)))
+}
+
+#[cfg(feature = "ssr")]
+fn parse_limits(
+    daily_sats: &str,
+    weekly_sats: &str,
+    approval_above_sats: &str,
+    cooldown_hours: &str,
+) -> Result<WithdrawalLimits, ServerFnError> {
+    let cooldown_hours = match cooldown_hours.trim() {
+        "" => 0,
+        hours => hours
+            .parse()
+            .map_err(|_| ServerFnError::new("Cooldown must be a whole number of hours"))?,
+    };
+    Ok(WithdrawalLimits {
+        daily_sats: parse_limit("Daily limit", daily_sats)?,
+        weekly_sats: parse_limit("Weekly limit", weekly_sats)?,
+        approval_above_sats: parse_limit("Approval threshold", approval_above_sats)?,
+        cooldown_hours,
+    })
+}
+
+#[server(GetWithdrawalPolicy, "/api", "GetJson")]
+pub async fn get_withdrawal_policy() -> Result<WithdrawalPolicyOverview, ServerFnError> {
+    use crate::api::{backends::withdrawal_policy_api, config::ApiConfig};
+
+    crate::api::client::require_admin("manage withdrawal limits").await?;
+    let api = withdrawal_policy_api(&ApiConfig::from_env())
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+    let policy = api
+        .get_policy()
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+    let pending = api
+        .get_approvals(Some(ApprovalStatus::Pending))
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+    let decisions = api
+        .get_decisions(None, DECISION_LOG_SIZE)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+    Ok(WithdrawalPolicyOverview {
+        policy,
+        pending,
+        decisions,
+    })
+}
+
+#[server(SetRoleLimits, "/api")]
+pub async fn set_role_limits(
+    role: Role,
+    daily_sats: String,
+    weekly_sats: String,
+    approval_above_sats: String,
+    cooldown_hours: String,
+) -> Result<WithdrawalPolicy, ServerFnError> {
+    use crate::api::{backends::withdrawal_policy_api, config::ApiConfig};
+
+    crate::api::client::require_admin("manage withdrawal limits").await?;
+    let limits = parse_limits(
+        &daily_sats,
+        &weekly_sats,
+        &approval_above_sats,
+        &cooldown_hours,
+    )?;
+    let api = withdrawal_policy_api(&ApiConfig::from_env())
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+    api.set_role_limits(role, limits)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))
+}
+
+#[server(SetGroupLimits, "/api")]
+pub async fn set_group_limits(
+    group_id: Uuid,
+    daily_sats: String,
+    weekly_sats: String,
+    approval_above_sats: String,
+    cooldown_hours: String,
+    chama_daily_sats: String,
+) -> Result<WithdrawalPolicy, ServerFnError> {
+    use crate::api::{backends::withdrawal_policy_api, config::ApiConfig};
+
+    crate::api::client::require_admin("manage withdrawal limits").await?;
+    let limits = GroupLimits {
+        group_id,
+        member_limits: parse_limits(
+            &daily_sats,
+            &weekly_sats,
+            &approval_above_sats,
+            &cooldown_hours,
+        )?,
+        chama_daily_sats: parse_limit("Chama daily limit", &chama_daily_sats)?,
+    };
+    let api = withdrawal_policy_api(&ApiConfig::from_env())
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+    api.set_group_limits(limits)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))
+}
+
+#[server(RemoveGroupLimits, "/api")]
+pub async fn remove_group_limits(group_id: Uuid) -> Result<WithdrawalPolicy, ServerFnError> {
+    use crate::api::{backends::withdrawal_policy_api, config::ApiConfig};
+
+    crate::api::client::require_admin("manage withdrawal limits").await?;
+    let api = withdrawal_policy_api(&ApiConfig::from_env())
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+    api.remove_group_limits(group_id)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))
+}
+
+#[server(ReviewWithdrawal, "/api")]
+pub async fn review_withdrawal(
+    approval_id: Uuid,
+    approve: bool,
+    note: Option<String>,
+) -> Result<WithdrawalApproval, ServerFnError> {
+    use crate::api::{backends::withdrawal_policy_api, config::ApiConfig};
+
+    let user = crate::api::client::require_admin("manage withdrawal limits").await?;
+    let api = withdrawal_policy_api(&ApiConfig::from_env())
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+    api.review_approval(approval_id, &user, approve, note)
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))
+}
+
+fn outcome_badge(outcome: DecisionOutcome) -> (&'static str, &'static str) {
+    match outcome {
+        DecisionOutcome::Allowed => ("bg-green-100 text-green-800", "Allowed"),
+        DecisionOutcome::Denied => ("bg-red-100 text-red-800", "Denied"),
+        DecisionOutcome::NeedsApproval => ("bg-yellow-100 text-yellow-800", "Needs approval"),
+    }
+}
+
+/// The role's form value, as it deserializes
+fn role_value(role: Role) -> &'static str {
+    match role {
+        Role::Member => "member",
+        Role::Admin => "admin",
+        Role::SuperAdmin => "super_admin",
+    }
+}
+
+fn format_limit(limit: Option<u64>) -> String {
+    limit
+        .map(format_sats)
+        .unwrap_or_else(|| "No limit".to_string())
+}
+
+fn limit_field(limit: Option<u64>) -> String {
+    limit.map(|sats| sats.to_string()).unwrap_or_default()
+}
+
+#[derive(Clone, Copy)]
+struct PolicyActions {
+    set_role: ServerAction<SetRoleLimits>,
+    set_group: ServerAction<SetGroupLimits>,
+    remove_group: ServerAction<RemoveGroupLimits>,
+    review: ServerAction<ReviewWithdrawal>,
+}
+
+#[component]
+pub fn WithdrawalPolicyPage() -> impl IntoView {
+    let actions = PolicyActions {
+        set_role: ServerAction::<SetRoleLimits>::new(),
+        set_group: ServerAction::<SetGroupLimits>::new(),
+        remove_group: ServerAction::<RemoveGroupLimits>::new(),
+        review: ServerAction::<ReviewWithdrawal>::new(),
+    };
+
+    let overview = Resource::new(
+        move || {
+            (
+                actions.set_role.version().get(),
+                actions.set_group.version().get(),
+                actions.remove_group.version().get(),
+                actions.review.version().get(),
+            )
+        },
+        |_| get_withdrawal_policy(),
+    );
+    let action_error = move || {
+        let errors = [
+            actions.set_role.value().get().and_then(|r| r.err()),
+            actions.set_group.value().get().and_then(|r| r.err()),
+            actions.remove_group.value().get().and_then(|r| r.err()),
+            actions.review.value().get().and_then(|r| r.err()),
+        ];
+        errors.into_iter().flatten().next().map(|e| e.to_string())
+    };
+
+    view! {
+        <div class="space-y-6">
+            <div>
+                <h1 class="text-2xl font-semibold text-gray-900">"Withdrawal Limits"</h1>
+                <p class="mt-1 text-sm text-gray-500">"Velocity limits, new-account cooldowns and approval of large withdrawals and payments"</p>
+            </div>
+
+            {move || action_error().map(|message| view! {
+                <div class="bg-red-50 border border-red-200 rounded-lg p-4 text-sm text-red-700" role="alert">
+                    {message}
+                </div>
+            })}
+
+            <Suspense fallback=move || view! { <div class="flex justify-center py-6"><Spinner /></div> }>
+                {move || overview.get().map(|result| match result {
+                    Ok(overview) => view! {
+                        <PendingApprovals approvals=overview.pending actions=actions />
+                        <RoleLimitsSection policy=overview.policy.clone() actions=actions />
+                        <GroupLimitsSection groups=overview.policy.groups actions=actions />
+                        <DecisionLog decisions=overview.decisions />
+                    }.into_any(),
+                    Err(e) => view! {
+                        <div class="bg-red-50 border border-red-200 rounded-lg p-4 text-sm text-red-700" role="alert">
+                            {e.to_string()}
+                        </div>
+                    }.into_any(),
+                })}
+            </Suspense>
+        </div>
+    }
+}
+
+/// Inputs shared by the role and group forms, filled with the current limits
+#[component]
+fn LimitInputs(limits: WithdrawalLimits) -> impl IntoView {
+    let input_class = "block w-32 mt-1 border border-gray-300 rounded-md px-3 py-2 text-sm";
+    view! {
+        <label class="text-xs text-gray-500">
+            "Daily (sats)"
+            <input name="daily_sats" type="text" inputmode="numeric" value=limit_field(limits.daily_sats) class=input_class/>
+        </label>
+        <label class="text-xs text-gray-500">
+            "Weekly (sats)"
+            <input name="weekly_sats" type="text" inputmode="numeric" value=limit_field(limits.weekly_sats) class=input_class/>
+        </label>
+        <label class="text-xs text-gray-500">
+            "Approval above (sats)"
+            <input name="approval_above_sats" type="text" inputmode="numeric" value=limit_field(limits.approval_above_sats) class=input_class/>
+        </label>
+        <label class="text-xs text-gray-500">
+            "Cooldown (hours)"
+            <input name="cooldown_hours" type="text" inputmode="numeric" value=limits.cooldown_hours.to_string() class=input_class/>
+        </label>
+    }
+}
+
+#[component]
+fn RoleLimitsSection(policy: WithdrawalPolicy, actions: PolicyActions) -> impl IntoView {
+    view! {
+        <div class="bg-white shadow rounded-lg p-6 space-y-4">
+            <h3 class="text-lg font-medium text-gray-900">"Limits by Role"</h3>
+            <p class="text-sm text-gray-500">"Leave a limit blank for no limit. A member in a chama with its own limits is held to the stricter of the two."</p>
+            {[Role::Member, Role::Admin, Role::SuperAdmin].into_iter().map(|role| {
+                let limits = policy.role_limits(role);
+                view! {
+                    <ActionForm action=actions.set_role attr:class="flex flex-wrap items-end gap-3 py-2 border-t border-gray-100">
+                        <input type="hidden" name="role" value=role_value(role)/>
+                        <span class="w-28 text-sm font-medium text-gray-900 pb-2">{role.label()}</span>
+                        <LimitInputs limits=limits />
+                        <button type="submit" class="border border-gray-300 text-gray-700 hover:bg-gray-50 text-sm font-medium py-2 px-4 rounded-md">
+                            "Save"
+                        </button>
+                    </ActionForm>
+                }
+            }).collect_view()}
+        </div>
+    }
+}
+
+#[component]
+fn GroupLimitsSection(groups: Vec<GroupLimits>, actions: PolicyActions) -> impl IntoView {
+    let input_class = "block w-32 mt-1 border border-gray-300 rounded-md px-3 py-2 text-sm";
+    view! {
+        <div class="bg-white shadow rounded-lg p-6 space-y-4">
+            <h3 class="text-lg font-medium text-gray-900">"Limits by Chama"</h3>
+            {if groups.is_empty() {
+                view! { <p class="text-sm text-gray-500">"No chama has its own limits."</p> }.into_any()
+            } else {
+                view! {
+                    <div class="overflow-x-auto">
+                        <table class="min-w-full divide-y divide-gray-200 text-sm">
+                            <thead class="bg-gray-50">
+                                <tr>
+                                    <th class="px-4 py-2 text-left font-medium text-gray-500">"Chama"</th>
+                                    <th class="px-4 py-2 text-right font-medium text-gray-500">"Member daily"</th>
+                                    <th class="px-4 py-2 text-right font-medium text-gray-500">"Member weekly"</th>
+                                    <th class="px-4 py-2 text-right font-medium text-gray-500">"Approval above"</th>
+                                    <th class="px-4 py-2 text-right font-medium text-gray-500">"Cooldown"</th>
+                                    <th class="px-4 py-2 text-right font-medium text-gray-500">"Chama daily"</th>
+                                    <th class="px-4 py-2"></th>
+                                </tr>
+                            </thead>
+                            <tbody class="divide-y divide-gray-100">
+                                {groups.into_iter().map(|group| view! {
+                                    <tr>
+                                        <td class="px-4 py-2 text-gray-700 font-mono text-xs">{group.group_id.to_string()}</td>
+                                        <td class="px-4 py-2 text-right text-gray-900">{format_limit(group.member_limits.daily_sats)}</td>
+                                        <td class="px-4 py-2 text-right text-gray-900">{format_limit(group.member_limits.weekly_sats)}</td>
+                                        <td class="px-4 py-2 text-right text-gray-900">{format_limit(group.member_limits.approval_above_sats)}</td>
+                                        <td class="px-4 py-2 text-right text-gray-900">{format!("{}h", group.member_limits.cooldown_hours)}</td>
+                                        <td class="px-4 py-2 text-right text-gray-900">{format_limit(group.chama_daily_sats)}</td>
+                                        <td class="px-4 py-2 text-right">
+                                            <ActionForm action=actions.remove_group>
+                                                <input type="hidden" name="group_id" value=group.group_id.to_string()/>
+                                                <button type="submit" class="text-red-600 hover:text-red-800 text-xs font-medium">"Remove"</button>
+                                            </ActionForm>
+                                        </td>
+                                    </tr>
+                                }).collect_view()}
+                            </tbody>
+                        </table>
+                    </div>
+                }.into_any()
+            }}
+            <ActionForm action=actions.set_group attr:class="flex flex-wrap items-end gap-3 pt-2 border-t border-gray-100">
+                <label class="text-xs text-gray-500">
+                    "Chama ID"
+                    <input name="group_id" type="text" required=true class="block w-80 mt-1 border border-gray-300 rounded-md px-3 py-2 text-sm font-mono"/>
+                </label>
+                <LimitInputs limits=WithdrawalLimits::default() />
+                <label class="text-xs text-gray-500">
+                    "Chama daily (sats)"
+                    <input name="chama_daily_sats" type="text" inputmode="numeric" class=input_class/>
+                </label>
+                <button type="submit" class="bg-indigo-600 hover:bg-indigo-700 text-white text-sm font-medium py-2 px-4 rounded-md">
+                    "Set Chama Limits"
+                </button>
+            </ActionForm>
+        </div>
+    }
+}
+
+#[component]
+fn PendingApprovals(approvals: Vec<WithdrawalApproval>, actions: PolicyActions) -> impl IntoView {
+    view! {
+        <div class="bg-white shadow rounded-lg p-6 space-y-4">
+            <h3 class="text-lg font-medium text-gray-900">"Awaiting Approval"</h3>
+            {if approvals.is_empty() {
+                view! { <p class="text-sm text-gray-500">"No withdrawals are waiting for approval."</p> }.into_any()
+            } else {
+                view! {
+                    <ul class="divide-y divide-gray-100 text-sm">
+                        {approvals.into_iter().map(|approval| {
+                            let id = approval.id.to_string();
+                            view! {
+                                <li class="py-3 flex flex-col lg:flex-row lg:items-center lg:justify-between gap-3">
+                                    <div>
+                                        <div class="text-gray-900 font-medium">
+                                            {format!("{} · {:?}", format_sats(approval.amount_sats), approval.transaction_type)}
+                                        </div>
+                                        <div class="text-xs text-gray-500 font-mono">
+                                            {format!("Member {} · wallet {} · requested {}", approval.user_id, approval.wallet_id, approval.requested_at.format("%Y-%m-%d %H:%M"))}
+                                            {(approval.requested_by != approval.user_id).then(|| format!(" by {}", approval.requested_by))}
+                                        </div>
+                                    </div>
+                                    <ActionForm action=actions.review attr:class="flex items-center gap-2">
+                                        <input type="hidden" name="approval_id" value=id/>
+                                        <input name="note" type="text" placeholder="Note" class="border border-gray-300 rounded-md px-3 py-1 text-sm"/>
+                                        <button type="submit" name="approve" value="true" class="bg-green-600 hover:bg-green-700 text-white text-xs font-medium py-1.5 px-3 rounded-md">
+                                            "Approve"
+                                        </button>
+                                        <button type="submit" name="approve" value="false" class="bg-red-600 hover:bg-red-700 text-white text-xs font-medium py-1.5 px-3 rounded-md">
+                                            "Reject"
+                                        </button>
+                                    </ActionForm>
+                                </li>
+                            }
+                        }).collect_view()}
+                    </ul>
+                    <p class="text-xs text-gray-500">"Once approved, the member's next attempt at the same withdrawal goes through within 24 hours."</p>
+                }.into_any()
+            }}
+        </div>
+    }
+}
+
+#[component]
+fn DecisionLog(decisions: Vec<WithdrawalDecision>) -> impl IntoView {
+    view! {
+        <div class="bg-white shadow rounded-lg p-6 space-y-4">
+            <h3 class="text-lg font-medium text-gray-900">"Recent Decisions"</h3>
+            {if decisions.is_empty() {
+                view! { <p class="text-sm text-gray-500">"No withdrawals have been checked yet."</p> }.into_any()
+            } else {
+                view! {
+                    <div class="overflow-x-auto">
+                        <table class="min-w-full divide-y divide-gray-200 text-sm">
+                            <thead class="bg-gray-50">
+                                <tr>
+                                    <th class="px-4 py-2 text-left font-medium text-gray-500">"When"</th>
+                                    <th class="px-4 py-2 text-left font-medium text-gray-500">"Member"</th>
+                                    <th class="px-4 py-2 text-right font-medium text-gray-500">"Amount"</th>
+                                    <th class="px-4 py-2 text-left font-medium text-gray-500">"Outcome"</th>
+                                    <th class="px-4 py-2 text-left font-medium text-gray-500">"Reasons"</th>
+                                </tr>
+                            </thead>
+                            <tbody class="divide-y divide-gray-100">
+                                {decisions.into_iter().map(|decision| {
+                                    let (badge_class, badge_label) = outcome_badge(decision.outcome);
+                                    view! {
+                                        <tr>
+                                            <td class="px-4 py-2 text-gray-700">{decision.decided_at.format("%Y-%m-%d %H:%M").to_string()}</td>
+                                            <td class="px-4 py-2 text-gray-700 font-mono text-xs">
+                                                {decision.user_id.to_string()}
+                                                {(decision.acted_by != decision.user_id).then(|| view! {
+                                                    <span class="block text-gray-500">{format!("by {}", decision.acted_by)}</span>
+                                                })}
+                                            </td>
+                                            <td class="px-4 py-2 text-right text-gray-900">{format_sats(decision.amount_sats)}</td>
+                                            <td class="px-4 py-2">
+                                                <span class=format!("px-2 py-1 rounded-full text-xs font-medium {}", badge_class)>{badge_label}</span>
+                                            </td>
+                                            <td class="px-4 py-2 text-gray-700">{decision.reasons.join("; ")}</td>
+                                        </tr>
+                                    }
+                                }).collect_view()}
+                            </tbody>
+                        </table>
+                    </div>
+                }.into_any()
+            }}
+        </div>
+    }
+}
@@ -0,0 +1,1203 @@
+// Dashboard exports
+// Exports are rendered on this server from the same dashboard endpoints the pages
+// read, so they work whether or not the backend can export. Each export runs as a
+// background job; when it finishes, the file is kept in memory until it expires and
+// is served from a signed link, which works without a session (a new tab or a
+// download manager can fetch it).
+
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use axum::{
+    extract::{Path, Query},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+};
+use chrono::{DateTime, NaiveDate, Utc};
+use hmac::{Hmac, Mac};
+use rust_decimal::prelude::ToPrimitive;
+use serde::Deserialize;
+use sha2::Sha256;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use super::export_formats::{ExportCell, ExportDocument, ExportFormat, ExportTable, TREND_COLUMNS};
+use crate::api::client::get_dashboard_client;
+use crate::api::dashboard_client::{
+    DashboardOverviewResponse, DateRange, ExportRecord, ExportRequest, ExportResponse,
+    ExportStatus, FinancialAnalyticsResponse, NestJsApiResponse, OperationalMetricsResponse,
+    TrendDataPoint, UserAnalyticsResponse,
+};
+use crate::api::types::Money;
+use crate::api::ApiError;
+
+/// Filters an export request may carry, named after the table column each narrows
+const FILTER_COLUMNS: [&str; 4] = ["currency", "operation", "service", "severity"];
+
+/// How long a finished export can be downloaded when EXPORT_TTL_SECONDS is unset
+const DEFAULT_EXPORT_TTL: Duration = Duration::from_secs(3600);
+
+type HmacSha256 = Hmac<Sha256>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportDataset {
+    Overview,
+    Users,
+    Financial,
+    Operations,
+}
+
+impl ExportDataset {
+    pub const ALL: [ExportDataset; 4] = [
+        ExportDataset::Overview,
+        ExportDataset::Users,
+        ExportDataset::Financial,
+        ExportDataset::Operations,
+    ];
+
+    /// Parse the `dataType` of an `ExportRequest`; `all` exports every dataset
+    pub fn parse_many(value: &str) -> Result<Vec<Self>, String> {
+        match value.trim().to_lowercase().as_str() {
+            "overview" => Ok(vec![ExportDataset::Overview]),
+            "users" => Ok(vec![ExportDataset::Users]),
+            "financial" => Ok(vec![ExportDataset::Financial]),
+            "operations" => Ok(vec![ExportDataset::Operations]),
+            "all" => Ok(Self::ALL.to_vec()),
+            other => Err(format!(
+                "Unknown export data type '{}'; use overview, users, financial, operations or all",
+                other
+            )),
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ExportDataset::Overview => "Overview",
+            ExportDataset::Users => "Users",
+            ExportDataset::Financial => "Financial",
+            ExportDataset::Operations => "Operations",
+        }
+    }
+}
+
+/// Inclusive dates an export is filtered by; only the financial dataset takes a range
+pub fn parse_period(range: Option<&DateRange>) -> Result<Option<(NaiveDate, NaiveDate)>, String> {
+    let Some(range) = range else {
+        return Ok(None);
+    };
+    let parse = |value: &str, label: &str| {
+        NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d")
+            .map_err(|_| format!("{} must be a date in YYYY-MM-DD form", label))
+    };
+    let (start, end) = (
+        parse(&range.start, "Start date")?,
+        parse(&range.end, "End date")?,
+    );
+    if start > end {
+        return Err("The start date must not be after the end date".to_string());
+    }
+    Ok(Some((start, end)))
+}
+
+/// Read the `filters` of an `ExportRequest` as column and value pairs. Empty
+/// values are skipped, so a form can always send every filter.
+pub fn parse_filters(
+    filters: Option<&HashMap<String, serde_json::Value>>,
+) -> Result<Vec<(String, String)>, String> {
+    let mut parsed = Vec::new();
+    for (key, value) in filters.into_iter().flatten() {
+        let column = key.trim().to_lowercase();
+        if !FILTER_COLUMNS.contains(&column.as_str()) {
+            return Err(format!(
+                "Unknown export filter '{}'; use {}",
+                key,
+                FILTER_COLUMNS.join(", ")
+            ));
+        }
+        let value = match value {
+            serde_json::Value::Null => continue,
+            serde_json::Value::String(value) => value.trim().to_string(),
+            other => other.to_string(),
+        };
+        if !value.is_empty() {
+            parsed.push((column, value));
+        }
+    }
+    parsed.sort();
+    Ok(parsed)
+}
+
+/// A checked `ExportRequest`
+#[derive(Debug, Clone)]
+struct ExportPlan {
+    format: ExportFormat,
+    datasets: Vec<ExportDataset>,
+    period: Option<(NaiveDate, NaiveDate)>,
+    filters: Vec<(String, String)>,
+    charts: bool,
+}
+
+impl ExportPlan {
+    fn parse(request: &ExportRequest) -> Result<Self, String> {
+        Ok(Self {
+            format: ExportFormat::parse(&request.format)?,
+            datasets: ExportDataset::parse_many(&request.data_type)?,
+            period: parse_period(request.date_range.as_ref())?,
+            filters: parse_filters(request.filters.as_ref())?,
+            charts: request.include_charts.unwrap_or(false),
+        })
+    }
+}
+
+fn amount(money: &Money) -> f64 {
+    money.amount.to_f64().unwrap_or_default()
+}
+
+/// A two-column table of headline figures: `Metric, Value, Unit`
+struct Summary(ExportTable);
+
+impl Summary {
+    fn new(title: &str) -> Self {
+        Summary(ExportTable::new(title, &["Metric", "Value", "Unit"]))
+    }
+
+    fn value(&mut self, metric: &str, value: impl Into<ExportCell>, unit: &str) -> &mut Self {
+        self.0.row(vec![metric.into(), value.into(), unit.into()]);
+        self
+    }
+
+    fn money(&mut self, metric: &str, money: &Money) -> &mut Self {
+        self.value(metric, amount(money), &money.currency)
+    }
+
+    fn finish(self) -> ExportTable {
+        self.0
+    }
+}
+
+/// Append a trend to a `Series, Date, Value` table
+fn trend_rows(table: &mut ExportTable, series: &str, points: &[TrendDataPoint]) {
+    for point in points {
+        table.row(vec![
+            series.into(),
+            point.date.as_str().into(),
+            point.value.to_f64().unwrap_or_default().into(),
+        ]);
+    }
+}
+
+/// Map entries largest first, then by name, so exports are stable
+fn ranked<V: Copy + PartialOrd>(map: &HashMap<String, V>) -> Vec<(&str, V)> {
+    let mut entries: Vec<(&str, V)> = map.iter().map(|(k, v)| (k.as_str(), *v)).collect();
+    entries.sort_by(|a, b| {
+        b.1.partial_cmp(&a.1)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.0.cmp(b.0))
+    });
+    entries
+}
+
+pub fn overview_tables(overview: &DashboardOverviewResponse) -> Vec<ExportTable> {
+    let summary = &overview.summary;
+    let quick = &overview.quick_stats;
+    let alerts = &overview.alerts;
+
+    let mut figures = Summary::new("Overview");
+    figures
+        .value("Total members", summary.total_members, "")
+        .value("Active members today", summary.active_members_today, "")
+        .value("Active chamas", summary.active_chamas, "")
+        .value("Total chamas", summary.total_chamas, "")
+        .money("Total volume", &summary.total_volume.money)
+        .value("Transactions", summary.transaction_count.total, "")
+        .value("Successful", summary.transaction_count.successful, "")
+        .value("Failed", summary.transaction_count.failed, "")
+        .value("Pending", summary.transaction_count.pending, "")
+        .value("Transactions today", quick.today_transactions, "")
+        .money("Volume today", &quick.today_volume)
+        .value("Active sessions", quick.active_sessions_now, "")
+        .value("New members today", quick.new_members_today, "")
+        .value("System health", alerts.system_health.as_str(), "")
+        .value("Error rate", alerts.error_rate, "%")
+        .value(
+            "Average response time",
+            u64::from(alerts.avg_response_time),
+            "ms",
+        );
+
+    let mut trends = ExportTable::new("Overview trends", &TREND_COLUMNS);
+    trend_rows(&mut trends, "Members", &overview.trends.member_growth);
+    trend_rows(&mut trends, "Volume", &overview.trends.volume_trend);
+    trend_rows(
+        &mut trends,
+        "Transactions",
+        &overview.trends.transaction_trend,
+    );
+    trend_rows(&mut trends, "Chamas", &overview.trends.chama_growth);
+
+    let mut critical = ExportTable::new(
+        "Critical alerts",
+        &["Time", "Severity", "Service", "Message"],
+    );
+    for alert in &alerts.critical_alerts {
+        critical.row(vec![
+            alert.timestamp.as_str().into(),
+            alert.severity.as_str().into(),
+            alert.service.clone().unwrap_or_default().into(),
+            alert.message.as_str().into(),
+        ]);
+    }
+
+    vec![figures.finish(), trends, critical]
+}
+
+pub fn user_tables(users: &UserAnalyticsResponse) -> Vec<ExportTable> {
+    let engagement = &users.engagement;
+    let sessions = &engagement.session_metrics;
+    let retention = &users.retention;
+    let activity = &users.membership_activity;
+
+    let mut figures = Summary::new("Users");
+    figures
+        .value("Daily active users", engagement.daily_active_users, "")
+        .value("Weekly active users", engagement.weekly_active_users, "")
+        .value("Monthly active users", engagement.monthly_active_users, "")
+        .value("DAU/MAU", engagement.dau_mau_ratio * 100.0, "%")
+        .value("Average session", sessions.average_duration, "s")
+        .value("Sessions", sessions.total_sessions, "")
+        .value("Sessions today", sessions.sessions_today, "")
+        .value("Peak concurrent users", sessions.peak_concurrent_users, "")
+        .value("Day 1 retention", retention.day1, "%")
+        .value("Day 7 retention", retention.day7, "%")
+        .value("Day 30 retention", retention.day30, "%")
+        .value("Day 90 retention", retention.day90, "%")
+        .value("Registrations today", activity.new_registrations.today, "")
+        .value(
+            "Registrations this week",
+            activity.new_registrations.this_week,
+            "",
+        )
+        .value(
+            "Registrations this month",
+            activity.new_registrations.this_month,
+            "",
+        )
+        .value(
+            "Members in chamas",
+            activity.chama_participation.active_members_in_chamas,
+            "",
+        )
+        .value(
+            "Chamas per member",
+            activity.chama_participation.average_chamas_per_member,
+            "",
+        );
+
+    let mut cohorts = ExportTable::new(
+        "Retention cohorts",
+        &[
+            "Cohort",
+            "New users",
+            "Month 1 %",
+            "Month 2 %",
+            "Month 3 %",
+            "Month 6 %",
+        ],
+    );
+    for cohort in &retention.cohort_data {
+        cohorts.row(vec![
+            cohort.cohort_month.as_str().into(),
+            cohort.new_users.into(),
+            cohort.retention.month1.into(),
+            cohort.retention.month2.into(),
+            cohort.retention.month3.into(),
+            cohort.retention.month6.into(),
+        ]);
+    }
+
+    let mut demographics = ExportTable::new("Demographics", &["Breakdown", "Group", "Members"]);
+    let demographic = &users.demographics;
+    for (breakdown, groups) in [
+        ("Region", &demographic.by_region),
+        ("Device", &demographic.by_device_type),
+        ("App version", &demographic.by_app_version),
+    ] {
+        for (group, members) in ranked(groups) {
+            demographics.row(vec![breakdown.into(), group.into(), members.into()]);
+        }
+    }
+
+    let mut features = ExportTable::new(
+        "Feature usage",
+        &[
+            "Feature",
+            "Uses",
+            "Unique users",
+            "Success rate %",
+            "Adoption rate %",
+        ],
+    );
+    for feature in &users.feature_usage.top_features {
+        let adoption = users
+            .feature_usage
+            .adoption
+            .iter()
+            .find(|adoption| adoption.feature_id == feature.feature_id)
+            .map(|adoption| ExportCell::Number(adoption.adoption_rate))
+            .unwrap_or_else(|| "".into());
+        features.row(vec![
+            feature.feature_name.as_str().into(),
+            feature.usage_count.into(),
+            feature.unique_users.into(),
+            feature.success_rate.into(),
+            adoption,
+        ]);
+    }
+
+    let mut trends = ExportTable::new("User trends", &TREND_COLUMNS);
+    trend_rows(
+        &mut trends,
+        "Registrations",
+        &demographic.registration_trend,
+    );
+    trend_rows(
+        &mut trends,
+        "Chama members",
+        &activity.chama_participation.chama_membership_trend,
+    );
+
+    vec![figures.finish(), cohorts, demographics, features, trends]
+}
+
+pub fn financial_tables(financial: &FinancialAnalyticsResponse) -> Vec<ExportTable> {
+    let volume = &financial.transactions.volume;
+    let counts = &financial.transactions.counts;
+    let performance = &financial.transactions.performance;
+    let swaps = &financial.swaps;
+    let chamas = &financial.chamas.financial;
+    let shares = &financial.shares;
+
+    let mut figures = Summary::new("Financial");
+    figures
+        .money("Volume", &volume.total)
+        .money("Volume today", &volume.today)
+        .money("Volume this week", &volume.this_week)
+        .money("Volume this month", &volume.this_month)
+        .value("Transactions", counts.total, "")
+        .value("Successful", counts.successful, "")
+        .value("Failed", counts.failed, "")
+        .value("Pending", counts.pending, "")
+        .value("Transactions per day", counts.average_per_day, "")
+        .value("Average duration", performance.average_duration, "ms")
+        .value("Success rate", performance.success_rate, "%")
+        .value("On-ramp swaps", swaps.onramp.count, "")
+        .money("On-ramp volume", &swaps.onramp.total_kes)
+        .value("Off-ramp swaps", swaps.offramp.count, "")
+        .money("Off-ramp volume", &swaps.offramp.total_kes)
+        .money("Chama balances", &chamas.total_balance)
+        .money("Chama deposits", &chamas.total_deposits)
+        .money("Chama withdrawals", &chamas.total_withdrawals)
+        .money("Chama net flow", &chamas.net_flow)
+        .value("Shares issued", shares.ownership.total_shares, "")
+        .value(
+            "Shares distributed",
+            shares.ownership.distributed_shares,
+            "",
+        )
+        .value("Shares available", shares.ownership.available_shares, "")
+        .value("Share transfers", shares.trading.total_transfers, "");
+
+    let mut currencies = ExportTable::new("Volume by currency", &["Currency", "Total", "Today"]);
+    let mut by_currency: Vec<_> = volume.by_currency.iter().collect();
+    by_currency.sort_by(|a, b| a.0.cmp(b.0));
+    for (currency, volume) in by_currency {
+        currencies.row(vec![
+            currency.as_str().into(),
+            amount(&volume.total).into(),
+            amount(&volume.today).into(),
+        ]);
+    }
+
+    let mut operations = ExportTable::new(
+        "Volume by operation",
+        &["Operation", "Count", "Total", "Average", "Currency"],
+    );
+    let mut by_operation: Vec<_> = volume.by_operation.iter().collect();
+    by_operation.sort_by(|a, b| b.1.count.cmp(&a.1.count).then_with(|| a.0.cmp(b.0)));
+    for (operation, volume) in by_operation {
+        operations.row(vec![
+            operation.as_str().into(),
+            volume.count.into(),
+            amount(&volume.total).into(),
+            amount(&volume.average_amount).into(),
+            volume.total.currency.as_str().into(),
+        ]);
+    }
+
+    let mut trends = ExportTable::new("Financial trends", &TREND_COLUMNS);
+    trend_rows(&mut trends, "Volume", &volume.trend);
+    trend_rows(&mut trends, "Duration (ms)", &performance.duration_trend);
+    trend_rows(
+        &mut trends,
+        "Share transfers",
+        &shares.trading.transfer_trend,
+    );
+
+    vec![figures.finish(), currencies, operations, trends]
+}
+
+pub fn operations_tables(operations: &OperationalMetricsResponse) -> Vec<ExportTable> {
+    let health = &operations.system.health;
+    let performance = &operations.system.performance;
+    let server = &operations.resources.server;
+    let database = &operations.resources.database;
+    let cache = &operations.resources.cache;
+    let infrastructure = &operations.infrastructure;
+
+    let mut figures = Summary::new("Operations");
+    figures
+        .value("Status", health.status.as_str(), "")
+        .value("Version", health.version.as_str(), "")
+        .value("Uptime", health.uptime, "s")
+        .value("Last restart", health.last_restart.as_str(), "")
+        .value(
+            "Response time (average)",
+            performance.response_time.average,
+            "ms",
+        )
+        .value("Response time (p50)", performance.response_time.p50, "ms")
+        .value("Response time (p95)", performance.response_time.p95, "ms")
+        .value("Response time (p99)", performance.response_time.p99, "ms")
+        .value(
+            "Requests per second",
+            performance.throughput.requests_per_second,
+            "",
+        )
+        .value(
+            "Peak requests per second",
+            performance.throughput.peak_rps,
+            "",
+        )
+        .value("Error rate", performance.errors.error_rate, "%")
+        .value("Errors", performance.errors.total_errors, "")
+        .value("CPU", server.cpu_usage, "%")
+        .value("Memory", server.memory_usage, "%")
+        .value("Disk", server.disk_usage, "%")
+        .value(
+            "Active connections",
+            server.network_activity.connections_active,
+            "",
+        )
+        .value(
+            "Database connections",
+            u64::from(database.connection_pool.active),
+            "",
+        )
+        .value(
+            "Database connection limit",
+            u64::from(database.connection_pool.max_connections),
+            "",
+        )
+        .value("Query time", database.performance.query_time, "ms")
+        .value("Slow queries", database.performance.slow_queries, "")
+        .value("Deadlocks", database.performance.deadlocks, "")
+        .value("Cache hit rate", cache.hit_rate, "%")
+        .value("Cache keys", cache.key_count, "")
+        .value("Cache evictions", cache.evictions, "")
+        .value(
+            "Active servers",
+            u64::from(infrastructure.load_balancer.active_servers),
+            "",
+        )
+        .value(
+            "Total servers",
+            u64::from(infrastructure.load_balancer.total_servers),
+            "",
+        )
+        .value("Active alerts", infrastructure.monitoring.alerts_active, "")
+        .value(
+            "Monitoring coverage",
+            infrastructure.monitoring.monitoring_coverage,
+            "%",
+        );
+
+    let mut services = ExportTable::new(
+        "Services",
+        &[
+            "Service",
+            "Status",
+            "Response time (ms)",
+            "Error rate %",
+            "Last health check",
+        ],
+    );
+    let mut by_name: Vec<_> = operations.services.iter().collect();
+    by_name.sort_by(|a, b| a.0.cmp(b.0));
+    for (name, service) in by_name {
+        services.row(vec![
+            name.as_str().into(),
+            service.status.as_str().into(),
+            service.response_time.into(),
+            service.error_rate.into(),
+            service.last_health_check.as_str().into(),
+        ]);
+    }
+
+    let mut errors = ExportTable::new("Errors", &["Grouped by", "Name", "Count"]);
+    for (grouping, counts) in [
+        ("Type", &performance.errors.errors_by_type),
+        ("Endpoint", &performance.errors.errors_by_endpoint),
+    ] {
+        for (name, count) in ranked(counts) {
+            errors.row(vec![grouping.into(), name.into(), count.into()]);
+        }
+    }
+
+    vec![figures.finish(), services, errors]
+}
+
+/// Where an export's figures come from
+#[async_trait]
+pub trait ExportSource: Send + Sync {
+    async fn tables(
+        &self,
+        dataset: ExportDataset,
+        period: Option<(NaiveDate, NaiveDate)>,
+    ) -> Result<Vec<ExportTable>, String>;
+}
+
+/// Reads datasets from the dashboard endpoints on behalf of the admin who asked
+pub struct DashboardExportSource {
+    auth_token: Option<String>,
+}
+
+impl DashboardExportSource {
+    pub fn new(auth_token: Option<String>) -> Self {
+        Self { auth_token }
+    }
+}
+
+fn dataset_data<T>(
+    dataset: ExportDataset,
+    response: Result<NestJsApiResponse<T>, ApiError>,
+) -> Result<T, String> {
+    match response {
+        Ok(response) if response.success => Ok(response.data),
+        Ok(response) => Err(format!(
+            "{} data is unavailable: {}",
+            dataset.label(),
+            response
+                .message
+                .unwrap_or_else(|| "the backend reported a failure".to_string())
+        )),
+        Err(e) => Err(format!("{} data is unavailable: {}", dataset.label(), e)),
+    }
+}
+
+#[async_trait]
+impl ExportSource for DashboardExportSource {
+    async fn tables(
+        &self,
+        dataset: ExportDataset,
+        period: Option<(NaiveDate, NaiveDate)>,
+    ) -> Result<Vec<ExportTable>, String> {
+        let client = get_dashboard_client();
+        let token = self.auth_token.as_deref();
+        match dataset {
+            ExportDataset::Overview => {
+                let overview = dataset_data(dataset, client.get_overview_with_auth(token).await)?;
+                Ok(overview_tables(&overview))
+            }
+            ExportDataset::Users => {
+                let users =
+                    dataset_data(dataset, client.get_user_analytics_with_auth(token).await)?;
+                Ok(user_tables(&users))
+            }
+            ExportDataset::Financial => {
+                let (start, end) = match period {
+                    Some((start, end)) => (
+                        Some(start.format("%Y-%m-%d").to_string()),
+                        Some(end.format("%Y-%m-%d").to_string()),
+                    ),
+                    None => (None, None),
+                };
+                let financial = dataset_data(
+                    dataset,
+                    client
+                        .get_financial_analytics_for_range(token, start.as_deref(), end.as_deref())
+                        .await,
+                )?;
+                Ok(financial_tables(&financial))
+            }
+            ExportDataset::Operations => {
+                let operations = dataset_data(
+                    dataset,
+                    client.get_operational_metrics_with_auth(token).await,
+                )?;
+                Ok(operations_tables(&operations))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ExportFile {
+    pub file_name: String,
+    pub content_type: &'static str,
+    pub bytes: Vec<u8>,
+}
+
+struct ExportJob {
+    request: ExportRequest,
+    status: ExportStatus,
+    requested_by: Uuid,
+    file: Option<ExportFile>,
+    /// When a finished job and its file are dropped
+    expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DownloadError {
+    /// The link was not issued by this server, or was altered
+    BadSignature,
+    /// The link or the file behind it has expired
+    Expired,
+}
+
+/// Export jobs and their finished files
+pub struct ExportJobs {
+    jobs: RwLock<HashMap<String, ExportJob>>,
+    signing_key: Vec<u8>,
+    ttl: Duration,
+}
+
+static EXPORT_JOBS: OnceLock<Arc<ExportJobs>> = OnceLock::new();
+
+/// The server-wide export jobs. Files only live in this process, so links are
+/// signed with a key that lives as long as they do.
+pub fn export_jobs() -> Arc<ExportJobs> {
+    EXPORT_JOBS
+        .get_or_init(|| {
+            let signing_key = [Uuid::new_v4(), Uuid::new_v4()]
+                .iter()
+                .flat_map(|id| *id.as_bytes())
+                .collect();
+            Arc::new(ExportJobs::new(signing_key, ExportJobs::ttl_from_env()))
+        })
+        .clone()
+}
+
+impl ExportJobs {
+    pub fn new(signing_key: Vec<u8>, ttl: Duration) -> Self {
+        Self {
+            jobs: RwLock::default(),
+            signing_key,
+            ttl,
+        }
+    }
+
+    /// Download lifetime from EXPORT_TTL_SECONDS
+    pub fn ttl_from_env() -> Duration {
+        std::env::var("EXPORT_TTL_SECONDS")
+            .ok()
+            .and_then(|value| value.trim().parse().ok())
+            .filter(|seconds| *seconds > 0)
+            .map(Duration::from_secs)
+            .unwrap_or(DEFAULT_EXPORT_TTL)
+    }
+
+    /// Check the request and start rendering it in the background
+    pub async fn start(
+        self: &Arc<Self>,
+        request: &ExportRequest,
+        requested_by: Uuid,
+        source: Arc<dyn ExportSource>,
+    ) -> Result<ExportResponse, String> {
+        let plan = ExportPlan::parse(request)?;
+        let export_id = Uuid::new_v4().to_string();
+        let status = ExportStatus {
+            export_id: export_id.clone(),
+            status: "processing".to_string(),
+            progress: 0.0,
+            download_url: None,
+            error: None,
+            created_at: Utc::now().to_rfc3339(),
+            completed_at: None,
+        };
+        {
+            let mut jobs = self.jobs.write().await;
+            purge_expired(&mut jobs, Utc::now());
+            jobs.insert(
+                export_id.clone(),
+                ExportJob {
+                    request: request.clone(),
+                    status,
+                    requested_by,
+                    file: None,
+                    expires_at: None,
+                },
+            );
+        }
+
+        let jobs = self.clone();
+        let id = export_id.clone();
+        tokio::spawn(async move { jobs.run(&id, plan, source).await });
+
+        Ok(ExportResponse {
+            export_id,
+            status: "processing".to_string(),
+            estimated_completion: None,
+            download_url: None,
+        })
+    }
+
+    async fn run(&self, export_id: &str, plan: ExportPlan, source: Arc<dyn ExportSource>) {
+        let ExportPlan {
+            format,
+            datasets,
+            period,
+            filters,
+            charts,
+        } = plan;
+        let mut tables = Vec::new();
+        for (done, dataset) in datasets.iter().enumerate() {
+            match source.tables(*dataset, period).await {
+                Ok(found) => tables.extend(found),
+                Err(error) => return self.finish(export_id, Err(error)).await,
+            }
+            // Fetching is most of the work; rendering takes the last stretch
+            self.set_progress(export_id, 90.0 * (done + 1) as f64 / datasets.len() as f64)
+                .await;
+        }
+        for table in &mut tables {
+            for (column, value) in &filters {
+                table.filter(column, value);
+            }
+        }
+
+        let title = match datasets.as_slice() {
+            [dataset] => format!("Bitsacco {} export", dataset.label().to_lowercase()),
+            _ => "Bitsacco dashboard export".to_string(),
+        };
+        let document = ExportDocument {
+            title,
+            period: period.map(|(start, end)| {
+                format!("{} to {}", start.format("%Y-%m-%d"), end.format("%Y-%m-%d"))
+            }),
+            generated_at: Utc::now(),
+            tables,
+            charts,
+        };
+        let file_name = format!(
+            "bitsacco-{}-{}.{}",
+            match datasets.as_slice() {
+                [dataset] => dataset.label().to_lowercase(),
+                _ => "dashboard".to_string(),
+            },
+            document.generated_at.format("%Y%m%d-%H%M%S"),
+            format.extension()
+        );
+        // Rendering is CPU-bound; keep it off the async workers
+        let rendered = tokio::task::spawn_blocking(move || document.render(format))
+            .await
+            .unwrap_or_else(|e| Err(format!("Rendering failed: {}", e)));
+        let file = rendered.map(|bytes| ExportFile {
+            file_name,
+            content_type: format.content_type(),
+            bytes,
+        });
+        self.finish(export_id, file).await;
+    }
+
+    async fn set_progress(&self, export_id: &str, progress: f64) {
+        if let Some(job) = self.jobs.write().await.get_mut(export_id) {
+            job.status.progress = progress;
+        }
+    }
+
+    async fn finish(&self, export_id: &str, outcome: Result<ExportFile, String>) {
+        let now = Utc::now();
+        let expires_at = now + self.ttl;
+        let mut jobs = self.jobs.write().await;
+        let Some(job) = jobs.get_mut(export_id) else {
+            return;
+        };
+        job.status.completed_at = Some(now.to_rfc3339());
+        job.expires_at = Some(expires_at);
+        match outcome {
+            Ok(file) => {
+                job.status.status = "completed".to_string();
+                job.status.progress = 100.0;
+                job.status.download_url =
+                    Some(self.download_url(export_id, expires_at.timestamp()));
+                job.file = Some(file);
+            }
+            Err(error) => {
+                tracing::warn!("Export {} failed: {}", export_id, error);
+                job.status.status = "failed".to_string();
+                job.status.error = Some(error);
+            }
+        }
+    }
+
+    /// Progress of an export, for the admin who started it
+    pub async fn status(&self, export_id: &str, requested_by: Uuid) -> Option<ExportStatus> {
+        let mut jobs = self.jobs.write().await;
+        purge_expired(&mut jobs, Utc::now());
+        jobs.get(export_id)
+            .filter(|job| job.requested_by == requested_by)
+            .map(|job| job.status.clone())
+    }
+
+    /// The admin's exports that haven't expired, newest first
+    pub async fn list(&self, requested_by: Uuid) -> Vec<ExportRecord> {
+        let mut jobs = self.jobs.write().await;
+        purge_expired(&mut jobs, Utc::now());
+        let mut records: Vec<ExportRecord> = jobs
+            .values()
+            .filter(|job| job.requested_by == requested_by)
+            .map(|job| ExportRecord {
+                request: job.request.clone(),
+                status: job.status.clone(),
+            })
+            .collect();
+        records.sort_by(|a, b| b.status.created_at.cmp(&a.status.created_at));
+        records
+    }
+
+    /// Start a failed export again with the same request
+    pub async fn retry(
+        self: &Arc<Self>,
+        export_id: &str,
+        requested_by: Uuid,
+        source: Arc<dyn ExportSource>,
+    ) -> Result<ExportResponse, String> {
+        let request = {
+            let jobs = self.jobs.read().await;
+            let job = jobs
+                .get(export_id)
+                .filter(|job| job.requested_by == requested_by)
+                .ok_or_else(|| "Export not found; it may have expired".to_string())?;
+            if job.status.status != "failed" {
+                return Err("Only failed exports can be retried".to_string());
+            }
+            job.request.clone()
+        };
+        self.start(&request, requested_by, source).await
+    }
+
+    pub async fn download(
+        &self,
+        export_id: &str,
+        expires: i64,
+        signature: &str,
+    ) -> Result<ExportFile, DownloadError> {
+        self.download_at(export_id, expires, signature, Utc::now())
+            .await
+    }
+
+    async fn download_at(
+        &self,
+        export_id: &str,
+        expires: i64,
+        signature: &str,
+        now: DateTime<Utc>,
+    ) -> Result<ExportFile, DownloadError> {
+        if !self.verify(export_id, expires, signature) {
+            return Err(DownloadError::BadSignature);
+        }
+        if now.timestamp() >= expires {
+            return Err(DownloadError::Expired);
+        }
+        let mut jobs = self.jobs.write().await;
+        purge_expired(&mut jobs, now);
+        // A valid link to a file that's gone can only mean it expired
+        jobs.get(export_id)
+            .and_then(|job| job.file.clone())
+            .ok_or(DownloadError::Expired)
+    }
+
+    fn mac(&self, export_id: &str, expires: i64) -> HmacSha256 {
+        let mut mac =
+            HmacSha256::new_from_slice(&self.signing_key).expect("HMAC accepts keys of any size");
+        mac.update(format!("{}:{}", export_id, expires).as_bytes());
+        mac
+    }
+
+    fn download_url(&self, export_id: &str, expires: i64) -> String {
+        let signature: String = self
+            .mac(export_id, expires)
+            .finalize()
+            .into_bytes()
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect();
+        format!(
+            "/api/exports/{}/download?expires={}&signature={}",
+            export_id, expires, signature
+        )
+    }
+
+    fn verify(&self, export_id: &str, expires: i64, signature: &str) -> bool {
+        let Some(bytes) = decode_hex(signature) else {
+            return false;
+        };
+        // Compared in constant time
+        self.mac(export_id, expires).verify_slice(&bytes).is_ok()
+    }
+}
+
+fn purge_expired(jobs: &mut HashMap<String, ExportJob>, now: DateTime<Utc>) {
+    jobs.retain(|_, job| job.expires_at.is_none_or(|expires_at| expires_at > now));
+}
+
+fn decode_hex(value: &str) -> Option<Vec<u8>> {
+    if !value.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..value.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(value.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DownloadQuery {
+    pub expires: i64,
+    pub signature: String,
+}
+
+/// `GET /api/exports/{export_id}/download?expires=...&signature=...`. The signed
+/// link is the credential, so no session is needed.
+pub async fn export_download(
+    Path(export_id): Path<String>,
+    Query(query): Query<DownloadQuery>,
+) -> Response {
+    match export_jobs()
+        .download(&export_id, query.expires, &query.signature)
+        .await
+    {
+        Ok(file) => (
+            [
+                (header::CONTENT_TYPE, file.content_type.to_string()),
+                (
+                    header::CONTENT_DISPOSITION,
+                    format!("attachment; filename=\"{}\"", file.file_name),
+                ),
+            ],
+            file.bytes,
+        )
+            .into_response(),
+        Err(DownloadError::BadSignature) => {
+            (StatusCode::FORBIDDEN, "This download link is not valid").into_response()
+        }
+        Err(DownloadError::Expired) => (
+            StatusCode::GONE,
+            "This download link has expired; export the data again",
+        )
+            .into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FakeSource {
+        fail: Option<ExportDataset>,
+    }
+
+    #[async_trait]
+    impl ExportSource for FakeSource {
+        async fn tables(
+            &self,
+            dataset: ExportDataset,
+            period: Option<(NaiveDate, NaiveDate)>,
+        ) -> Result<Vec<ExportTable>, String> {
+            if self.fail == Some(dataset) {
+                return Err(format!("{} data is unavailable", dataset.label()));
+            }
+            let mut table = ExportTable::new(dataset.label(), &["Metric", "Value", "Unit"]);
+            table.row(vec![
+                "Filtered".into(),
+                u64::from(period.is_some()).into(),
+                "".into(),
+            ]);
+            Ok(vec![table])
+        }
+    }
+
+    fn request(format: &str, data_type: &str) -> ExportRequest {
+        ExportRequest {
+            format: format.to_string(),
+            data_type: data_type.to_string(),
+            date_range: Some(DateRange {
+                start: "2026-10-01".to_string(),
+                end: "2026-10-18".to_string(),
+            }),
+            filters: None,
+            include_charts: None,
+        }
+    }
+
+    async fn settled(jobs: &ExportJobs, export_id: &str, owner: Uuid) -> ExportStatus {
+        for _ in 0..100 {
+            let status = jobs.status(export_id, owner).await.unwrap();
+            if status.status != "processing" {
+                return status;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("export {} never finished", export_id);
+    }
+
+    fn link_parts(url: &str) -> (i64, String) {
+        let query = url.split_once('?').unwrap().1;
+        let mut expires = 0;
+        let mut signature = String::new();
+        for pair in query.split('&') {
+            match pair.split_once('=').unwrap() {
+                ("expires", value) => expires = value.parse().unwrap(),
+                ("signature", value) => signature = value.to_string(),
+                _ => {}
+            }
+        }
+        (expires, signature)
+    }
+
+    #[tokio::test]
+    async fn test_exports_run_in_the_background_and_download_from_signed_links() {
+        let jobs = Arc::new(ExportJobs::new(b"test key".to_vec(), DEFAULT_EXPORT_TTL));
+        let admin = Uuid::new_v4();
+        let source = Arc::new(FakeSource { fail: None });
+
+        let started = jobs
+            .start(&request("csv", "all"), admin, source.clone())
+            .await
+            .unwrap();
+        assert_eq!(started.status, "processing");
+        assert!(jobs
+            .status(&started.export_id, Uuid::new_v4())
+            .await
+            .is_none());
+
+        let status = settled(&jobs, &started.export_id, admin).await;
+        assert_eq!(status.status, "completed");
+        assert_eq!(status.progress, 100.0);
+        let url = status.download_url.unwrap();
+        let (expires, signature) = link_parts(&url);
+
+        let file = jobs
+            .download(&started.export_id, expires, &signature)
+            .await
+            .unwrap();
+        assert!(file.file_name.starts_with("bitsacco-dashboard-"));
+        assert!(file.file_name.ends_with(".csv"));
+        assert!(jobs
+            .retry(&started.export_id, admin, source.clone())
+            .await
+            .is_err());
+        let csv = String::from_utf8(file.bytes).unwrap();
+        for dataset in ExportDataset::ALL {
+            assert!(csv.contains(&format!(
+                "{}\nMetric,Value,Unit\nFiltered,1,\n",
+                dataset.label()
+            )));
+        }
+
+        assert_eq!(
+            jobs.download(&started.export_id, expires + 1, &signature)
+                .await
+                .unwrap_err(),
+            DownloadError::BadSignature
+        );
+        assert_eq!(
+            jobs.download(&started.export_id, expires, "zz")
+                .await
+                .unwrap_err(),
+            DownloadError::BadSignature
+        );
+        let later = DateTime::from_timestamp(expires, 0).unwrap();
+        assert_eq!(
+            jobs.download_at(&started.export_id, expires, &signature, later)
+                .await
+                .unwrap_err(),
+            DownloadError::Expired
+        );
+        // Expired jobs are dropped along with their files
+        jobs.jobs
+            .write()
+            .await
+            .get_mut(&started.export_id)
+            .unwrap()
+            .expires_at = Some(Utc::now());
+        assert!(jobs.status(&started.export_id, admin).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_failed_and_invalid_exports_are_reported() {
+        let jobs = Arc::new(ExportJobs::new(b"test key".to_vec(), DEFAULT_EXPORT_TTL));
+        let admin = Uuid::new_v4();
+        let source = Arc::new(FakeSource {
+            fail: Some(ExportDataset::Financial),
+        });
+
+        let started = jobs
+            .start(&request("xlsx", "all"), admin, source.clone())
+            .await
+            .unwrap();
+        let status = settled(&jobs, &started.export_id, admin).await;
+        assert_eq!(status.status, "failed");
+        assert_eq!(
+            status.error.as_deref(),
+            Some("Financial data is unavailable")
+        );
+        assert!(status.download_url.is_none());
+        assert!(status.completed_at.is_some());
+
+        // Retrying runs the same request again as a new export
+        assert!(jobs
+            .retry(&started.export_id, Uuid::new_v4(), source.clone())
+            .await
+            .is_err());
+        let retried = jobs
+            .retry(&started.export_id, admin, source.clone())
+            .await
+            .unwrap();
+        let history = jobs.list(admin).await;
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].status.export_id, retried.export_id);
+        assert_eq!(history[0].request.format, "xlsx");
+        assert!(jobs.list(Uuid::new_v4()).await.is_empty());
+        assert_eq!(
+            settled(&jobs, &retried.export_id, admin).await.status,
+            "failed"
+        );
+
+        assert!(jobs
+            .start(&request("docx", "all"), admin, source.clone())
+            .await
+            .is_err());
+        assert!(jobs
+            .start(&request("csv", "ledger"), admin, source.clone())
+            .await
+            .is_err());
+        let mut unknown_filter = request("csv", "financial");
+        unknown_filter.filters = Some(HashMap::from([(
+            "region".to_string(),
+            serde_json::json!("Nairobi"),
+        )]));
+        assert!(jobs
+            .start(&unknown_filter, admin, source.clone())
+            .await
+            .is_err());
+        let mut reversed = request("csv", "users");
+        reversed.date_range = Some(DateRange {
+            start: "2026-10-18".to_string(),
+            end: "2026-10-01".to_string(),
+        });
+        assert!(jobs.start(&reversed, admin, source).await.is_err());
+    }
+}
@@ -0,0 +1,513 @@
+// Rendering of dashboard exports
+// Every dataset is first flattened into titled tables of text and numbers, which
+// each format then lays out its own way: CSV sections, JSON objects, one XLSX
+// worksheet per table, or fixed-width tables in a PDF
+
+use chrono::{DateTime, Utc};
+use rust_xlsxwriter::{Chart, ChartType, Format, Workbook, Worksheet, XlsxError};
+use serde_json::{json, Map, Value};
+
+use super::pdf::{PdfDocument, PdfFont};
+
+/// Characters that fit across an A4 page in 8pt Courier
+const PDF_LINE_CHARS: usize = 100;
+
+/// Columns of a trend table; XLSX exports can chart these
+pub const TREND_COLUMNS: [&str; 3] = ["Series", "Date", "Value"];
+
+/// Worksheet rows each trend chart takes up, including a gap below it
+const CHART_ROWS: u32 = 16;
+
+/// Longest worksheet name Excel accepts
+const MAX_SHEET_NAME_CHARS: usize = 31;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Xlsx,
+    Pdf,
+    Json,
+}
+
+impl ExportFormat {
+    /// Parse the `format` of an `ExportRequest`
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value.trim().to_lowercase().as_str() {
+            "csv" => Ok(ExportFormat::Csv),
+            "xlsx" => Ok(ExportFormat::Xlsx),
+            "pdf" => Ok(ExportFormat::Pdf),
+            "json" => Ok(ExportFormat::Json),
+            other => Err(format!(
+                "Unknown export format '{}'; use csv, xlsx, pdf or json",
+                other
+            )),
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Xlsx => "xlsx",
+            ExportFormat::Pdf => "pdf",
+            ExportFormat::Json => "json",
+        }
+    }
+
+    pub fn content_type(self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "text/csv; charset=utf-8",
+            ExportFormat::Xlsx => {
+                "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"
+            }
+            ExportFormat::Pdf => "application/pdf",
+            ExportFormat::Json => "application/json",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExportCell {
+    Text(String),
+    Number(f64),
+}
+
+impl ExportCell {
+    fn display(&self) -> String {
+        match self {
+            ExportCell::Text(text) => text.clone(),
+            ExportCell::Number(number) => number.to_string(),
+        }
+    }
+
+    fn to_json(&self) -> Value {
+        match self {
+            ExportCell::Text(text) => Value::String(text.clone()),
+            ExportCell::Number(number) => json!(number),
+        }
+    }
+}
+
+impl From<u64> for ExportCell {
+    fn from(value: u64) -> Self {
+        ExportCell::Number(value as f64)
+    }
+}
+
+impl From<f64> for ExportCell {
+    fn from(value: f64) -> Self {
+        ExportCell::Number(value)
+    }
+}
+
+impl From<&str> for ExportCell {
+    fn from(value: &str) -> Self {
+        ExportCell::Text(value.to_string())
+    }
+}
+
+impl From<String> for ExportCell {
+    fn from(value: String) -> Self {
+        ExportCell::Text(value)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportTable {
+    pub title: String,
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<ExportCell>>,
+}
+
+impl ExportTable {
+    pub fn new(title: &str, columns: &[&str]) -> Self {
+        Self {
+            title: title.to_string(),
+            columns: columns.iter().map(|column| column.to_string()).collect(),
+            rows: Vec::new(),
+        }
+    }
+
+    pub fn row(&mut self, cells: Vec<ExportCell>) -> &mut Self {
+        debug_assert_eq!(cells.len(), self.columns.len());
+        self.rows.push(cells);
+        self
+    }
+
+    /// Keep only the rows whose `column` equals `value`, ignoring case. Tables
+    /// without that column are left alone.
+    pub fn filter(&mut self, column: &str, value: &str) {
+        if let Some(index) = self
+            .columns
+            .iter()
+            .position(|name| name.eq_ignore_ascii_case(column))
+        {
+            self.rows
+                .retain(|row| row[index].display().eq_ignore_ascii_case(value));
+        }
+    }
+}
+
+/// Everything one export contains, in the order it is laid out
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportDocument {
+    pub title: String,
+    /// Date range the figures were filtered by, when there was one
+    pub period: Option<String>,
+    pub generated_at: DateTime<Utc>,
+    pub tables: Vec<ExportTable>,
+    /// Chart trend tables where the format can (XLSX only)
+    pub charts: bool,
+}
+
+impl ExportDocument {
+    pub fn render(&self, format: ExportFormat) -> Result<Vec<u8>, String> {
+        match format {
+            ExportFormat::Csv => Ok(render_csv(self).into_bytes()),
+            ExportFormat::Json => serde_json::to_vec_pretty(&render_json(self))
+                .map_err(|e| format!("Could not write JSON: {}", e)),
+            ExportFormat::Xlsx => render_xlsx(self),
+            ExportFormat::Pdf => Ok(render_pdf(self)),
+        }
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// One section per table: its title, a header row, the rows, then a blank line
+fn render_csv(document: &ExportDocument) -> String {
+    let mut csv = String::new();
+    for table in &document.tables {
+        csv.push_str(&csv_field(&table.title));
+        csv.push('\n');
+        let header: Vec<String> = table.columns.iter().map(|c| csv_field(c)).collect();
+        csv.push_str(&header.join(","));
+        csv.push('\n');
+        for row in &table.rows {
+            let cells: Vec<String> = row.iter().map(|cell| csv_field(&cell.display())).collect();
+            csv.push_str(&cells.join(","));
+            csv.push('\n');
+        }
+        csv.push('\n');
+    }
+    csv
+}
+
+/// Tables become arrays of objects keyed by column name
+fn render_json(document: &ExportDocument) -> Value {
+    let tables: Vec<Value> = document
+        .tables
+        .iter()
+        .map(|table| {
+            let rows: Vec<Value> = table
+                .rows
+                .iter()
+                .map(|row| {
+                    let object: Map<String, Value> = table
+                        .columns
+                        .iter()
+                        .cloned()
+                        .zip(row.iter().map(ExportCell::to_json))
+                        .collect();
+                    Value::Object(object)
+                })
+                .collect();
+            json!({ "title": table.title, "rows": rows })
+        })
+        .collect();
+    json!({
+        "title": document.title,
+        "period": document.period,
+        "generatedAt": document.generated_at.to_rfc3339(),
+        "tables": tables,
+    })
+}
+
+/// A worksheet name Excel accepts and no earlier sheet has taken
+fn sheet_name(title: &str, taken: &[String]) -> String {
+    let base: String = title
+        .chars()
+        .map(|ch| match ch {
+            '[' | ']' | ':' | '*' | '?' | '/' | '\\' => '-',
+            ch => ch,
+        })
+        .collect::<String>()
+        .trim_matches(|ch: char| ch == '\'' || ch.is_whitespace())
+        .chars()
+        .take(MAX_SHEET_NAME_CHARS)
+        .collect();
+    let base = if base.is_empty() {
+        "Sheet".to_string()
+    } else {
+        base
+    };
+    let is_taken = |name: &str| {
+        name.eq_ignore_ascii_case("History")
+            || taken.iter().any(|other| other.eq_ignore_ascii_case(name))
+    };
+    if !is_taken(&base) {
+        return base;
+    }
+    (2..)
+        .map(|n| {
+            let suffix = format!(" ({})", n);
+            let kept: String = base
+                .chars()
+                .take(MAX_SHEET_NAME_CHARS - suffix.len())
+                .collect();
+            format!("{}{}", kept, suffix)
+        })
+        .find(|name| !is_taken(name))
+        .expect("some suffix is free")
+}
+
+fn render_xlsx(document: &ExportDocument) -> Result<Vec<u8>, String> {
+    let xlsx_error = |e: rust_xlsxwriter::XlsxError| format!("Could not write XLSX: {}", e);
+    let bold = Format::new().set_bold();
+    let mut workbook = Workbook::new();
+    let mut names = Vec::with_capacity(document.tables.len());
+
+    for table in &document.tables {
+        let name = sheet_name(&table.title, &names);
+        let sheet = workbook.add_worksheet();
+        sheet.set_name(name.as_str()).map_err(xlsx_error)?;
+
+        for (col, column) in table.columns.iter().enumerate() {
+            sheet
+                .write_string_with_format(0, col as u16, column, &bold)
+                .map_err(xlsx_error)?;
+        }
+        for (row, cells) in table.rows.iter().enumerate() {
+            let row = row as u32 + 1;
+            for (col, cell) in cells.iter().enumerate() {
+                match cell {
+                    ExportCell::Text(text) => sheet.write_string(row, col as u16, text),
+                    ExportCell::Number(number) => sheet.write_number(row, col as u16, *number),
+                }
+                .map_err(xlsx_error)?;
+            }
+        }
+        if document.charts && table.columns == TREND_COLUMNS {
+            insert_trend_charts(sheet, &name, table).map_err(xlsx_error)?;
+        }
+        sheet.set_freeze_panes(1, 0).map_err(xlsx_error)?;
+        sheet.autofit();
+        names.push(name);
+    }
+    if document.tables.is_empty() {
+        workbook.add_worksheet();
+    }
+    workbook.save_to_buffer().map_err(xlsx_error)
+}
+
+/// A line chart beside the data for each series of a trend table. Rows of a
+/// series are contiguous, so each chart reads one block of the sheet.
+fn insert_trend_charts(
+    sheet: &mut Worksheet,
+    name: &str,
+    table: &ExportTable,
+) -> Result<(), XlsxError> {
+    let mut start = 0;
+    let mut placed = 0;
+    while start < table.rows.len() {
+        let series = table.rows[start][0].display();
+        let end = table.rows[start..]
+            .iter()
+            .position(|row| row[0].display() != series)
+            .map_or(table.rows.len(), |len| start + len);
+        // Row 0 holds the headers
+        let (first, last) = (start as u32 + 1, end as u32);
+
+        let mut chart = Chart::new(ChartType::Line);
+        chart.title().set_name(&series);
+        chart.legend().set_hidden();
+        chart
+            .add_series()
+            .set_categories((name, first, 1, last, 1))
+            .set_values((name, first, 2, last, 2));
+        sheet.insert_chart(placed * CHART_ROWS, TREND_COLUMNS.len() as u16 + 1, &chart)?;
+
+        placed += 1;
+        start = end;
+    }
+    Ok(())
+}
+
+/// Widths for a fixed-width table, narrowing the widest column until it fits a line
+fn column_widths(table: &ExportTable) -> Vec<usize> {
+    let mut widths: Vec<usize> = table
+        .columns
+        .iter()
+        .map(|column| column.chars().count())
+        .collect();
+    for row in &table.rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.display().chars().count());
+        }
+    }
+    let gaps = 2 * widths.len().saturating_sub(1);
+    while widths.iter().sum::<usize>() + gaps > PDF_LINE_CHARS {
+        match widths.iter_mut().max() {
+            Some(widest) if *widest > 4 => *widest -= 1,
+            _ => break,
+        }
+    }
+    widths
+}
+
+fn fit(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        text.to_string()
+    } else {
+        let mut cut: String = text.chars().take(width.saturating_sub(3)).collect();
+        cut.push_str("...");
+        cut
+    }
+}
+
+fn render_pdf(document: &ExportDocument) -> Vec<u8> {
+    let mut doc = PdfDocument::new();
+    doc.line(&document.title, PdfFont::Bold, 16.0);
+    if let Some(period) = &document.period {
+        doc.line(&format!("Period: {}", period), PdfFont::Regular, 10.0);
+    }
+    doc.line(
+        &format!(
+            "Generated: {}",
+            document.generated_at.format("%Y-%m-%d %H:%M UTC")
+        ),
+        PdfFont::Regular,
+        10.0,
+    );
+
+    for table in &document.tables {
+        let widths = column_widths(table);
+        // Numbers line up on the right, text on the left
+        let line = |cells: Vec<(String, bool)>| {
+            cells
+                .into_iter()
+                .zip(&widths)
+                .map(|((text, numeric), width)| {
+                    let text = fit(&text, *width);
+                    if numeric {
+                        format!("{:>width$}", text, width = width)
+                    } else {
+                        format!("{:<width$}", text, width = width)
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join("  ")
+        };
+
+        doc.gap(14.0).line(&table.title, PdfFont::Bold, 12.0);
+        doc.line(
+            &line(table.columns.iter().map(|c| (c.clone(), false)).collect()),
+            PdfFont::Mono,
+            8.0,
+        );
+        if table.rows.is_empty() {
+            doc.line("Nothing to report.", PdfFont::Regular, 8.0);
+        }
+        for row in &table.rows {
+            let cells = row
+                .iter()
+                .map(|cell| (cell.display(), matches!(cell, ExportCell::Number(_))))
+                .collect();
+            doc.line(&line(cells), PdfFont::Mono, 8.0);
+        }
+    }
+    doc.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn document() -> ExportDocument {
+        let mut summary = ExportTable::new("Overview: summary", &["Metric", "Value", "Unit"]);
+        summary
+            .row(vec!["Total members".into(), 1200u64.into(), "".into()])
+            .row(vec!["Total volume".into(), 1250.5.into(), "KES".into()]);
+        let mut alerts = ExportTable::new("Overview: alerts", &["Severity", "Message"]);
+        alerts.row(vec!["critical".into(), "Swaps \"stuck\", retrying".into()]);
+        ExportDocument {
+            title: "Bitsacco dashboard export".to_string(),
+            period: Some("2026-10-01 to 2026-10-18".to_string()),
+            generated_at: Utc::now(),
+            tables: vec![summary, alerts],
+            charts: false,
+        }
+    }
+
+    #[test]
+    fn test_documents_render_in_every_format() {
+        let document = document();
+
+        let csv = String::from_utf8(document.render(ExportFormat::Csv).unwrap()).unwrap();
+        assert!(csv.starts_with("Overview: summary\nMetric,Value,Unit\nTotal members,1200,\n"));
+        assert!(csv.contains("critical,\"Swaps \"\"stuck\"\", retrying\"\n"));
+
+        let json: Value =
+            serde_json::from_slice(&document.render(ExportFormat::Json).unwrap()).unwrap();
+        assert_eq!(json["tables"][0]["rows"][1]["Value"], json!(1250.5));
+        assert_eq!(json["period"], "2026-10-01 to 2026-10-18");
+
+        let xlsx = document.render(ExportFormat::Xlsx).unwrap();
+        // An XLSX file is a zip archive
+        assert!(xlsx.starts_with(b"PK"));
+
+        let pdf = String::from_utf8(document.render(ExportFormat::Pdf).unwrap()).unwrap();
+        assert!(pdf.starts_with("%PDF-1.4"));
+        assert!(pdf.contains("Period: 2026-10-01 to 2026-10-18"));
+        assert!(pdf.contains("Total members    1200"));
+
+        assert_eq!(ExportFormat::parse(" XLSX").unwrap(), ExportFormat::Xlsx);
+        assert!(ExportFormat::parse("docx").is_err());
+    }
+
+    #[test]
+    fn test_tables_filter_by_column_and_trends_chart_in_xlsx() {
+        let mut trends = ExportTable::new("Overview trends", &TREND_COLUMNS);
+        for (series, value) in [("Members", 10u64), ("Members", 12), ("Volume", 900)] {
+            trends.row(vec![series.into(), "2026-10-18".into(), value.into()]);
+        }
+        let mut filtered = trends.clone();
+        filtered.filter("series", "members");
+        assert_eq!(filtered.rows.len(), 2);
+        filtered.filter("currency", "KES");
+        assert_eq!(filtered.rows.len(), 2);
+
+        let mut document = document();
+        document.tables.push(trends);
+        let without = document.render(ExportFormat::Xlsx).unwrap();
+        document.charts = true;
+        let with = document.render(ExportFormat::Xlsx).unwrap();
+        // Zip entry names are stored uncompressed
+        let has = |bytes: &[u8], name: &str| {
+            bytes
+                .windows(name.len())
+                .any(|window| window == name.as_bytes())
+        };
+        assert!(!has(&without, "xl/charts/chart1.xml"));
+        assert!(has(&with, "xl/charts/chart2.xml"));
+        assert!(!has(&with, "xl/charts/chart3.xml"));
+    }
+
+    #[test]
+    fn test_sheet_names_are_valid_and_unique() {
+        let taken = vec!["Overview- summary".to_string()];
+        assert_eq!(
+            sheet_name("Overview: summary", &taken),
+            "Overview- summary (2)"
+        );
+        assert_eq!(sheet_name("history", &[]), "history (2)");
+        let long = sheet_name(&"Volume by operation ".repeat(3), &[]);
+        assert_eq!(long.chars().count(), MAX_SHEET_NAME_CHARS);
+        assert_eq!(sheet_name("''", &[]), "Sheet");
+    }
+}
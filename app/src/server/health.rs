@@ -0,0 +1,187 @@
+// System health probes
+// The dashboard server checks its own view of the world (can it reach the
+// backend, is token validation configured, is the circuit breaker open) and
+// reports it alongside the backend's operational metrics
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use crate::api::circuit_breaker::{CircuitSnapshot, CircuitState};
+use crate::api::dashboard_client::OperationalMetricsResponse;
+
+/// Responses slower than this are reported as a warning
+const SLOW_RESPONSE: Duration = Duration::from_secs(2);
+
+/// The secret the NestJS client falls back to when `JWT_SECRET` is unset
+const DEFAULT_JWT_SECRET: &str = "default_secret";
+
+/// Shortest HS256 secret we accept without a warning
+const MIN_JWT_SECRET_BYTES: usize = 32;
+
+/// Ordered from best to worst, so the worst probe decides the overall status
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum ProbeStatus {
+    Ok,
+    Warning,
+    Failed,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Probe {
+    pub name: String,
+    pub status: ProbeStatus,
+    pub detail: String,
+}
+
+impl Probe {
+    fn new(name: &str, status: ProbeStatus, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            status,
+            detail: detail.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SystemHealthReport {
+    pub probes: Vec<Probe>,
+    /// Operational metrics from the backend, when it could be reached
+    pub metrics: Option<OperationalMetricsResponse>,
+    pub metrics_error: Option<String>,
+    pub checked_at: DateTime<Utc>,
+}
+
+impl SystemHealthReport {
+    pub fn overall(&self) -> ProbeStatus {
+        self.probes
+            .iter()
+            .map(|probe| probe.status)
+            .max()
+            .unwrap_or(ProbeStatus::Ok)
+    }
+}
+
+/// Whether the backend answered the metrics request, and how quickly
+pub fn backend_probe(base_url: &str, outcome: Result<Duration, String>) -> Probe {
+    match outcome {
+        Ok(elapsed) if elapsed > SLOW_RESPONSE => Probe::new(
+            "Backend",
+            ProbeStatus::Warning,
+            format!("{} answered slowly ({} ms)", base_url, elapsed.as_millis()),
+        ),
+        Ok(elapsed) => Probe::new(
+            "Backend",
+            ProbeStatus::Ok,
+            format!("{} answered in {} ms", base_url, elapsed.as_millis()),
+        ),
+        Err(error) => Probe::new(
+            "Backend",
+            ProbeStatus::Failed,
+            format!("{} is unreachable: {}", base_url, error),
+        ),
+    }
+}
+
+/// Whether tokens are validated against a real secret; never reveals the secret
+pub fn jwt_probe(secret: Option<&str>) -> Probe {
+    match secret.map(str::trim).filter(|secret| !secret.is_empty()) {
+        None | Some(DEFAULT_JWT_SECRET) => Probe::new(
+            "JWT",
+            ProbeStatus::Failed,
+            "JWT_SECRET is not set; tokens are checked against the built-in default secret",
+        ),
+        Some(secret) if secret.len() < MIN_JWT_SECRET_BYTES => Probe::new(
+            "JWT",
+            ProbeStatus::Warning,
+            format!("JWT_SECRET is shorter than {} bytes", MIN_JWT_SECRET_BYTES),
+        ),
+        Some(_) => Probe::new("JWT", ProbeStatus::Ok, "JWT_SECRET is configured"),
+    }
+}
+
+pub fn circuit_probe(circuit: &CircuitSnapshot) -> Probe {
+    match circuit.state {
+        CircuitState::Closed if circuit.consecutive_failures == 0 => Probe::new(
+            "Circuit breaker",
+            ProbeStatus::Ok,
+            "Closed; calls to the backend go through",
+        ),
+        CircuitState::Closed => Probe::new(
+            "Circuit breaker",
+            ProbeStatus::Warning,
+            format!(
+                "Closed with {} of {} failures before it opens",
+                circuit.consecutive_failures, circuit.failure_threshold
+            ),
+        ),
+        CircuitState::HalfOpen => Probe::new(
+            "Circuit breaker",
+            ProbeStatus::Warning,
+            "Half-open; the next backend call decides whether it closes",
+        ),
+        CircuitState::Open => Probe::new(
+            "Circuit breaker",
+            ProbeStatus::Failed,
+            format!(
+                "Open after {} consecutive failures; retrying in {}s{}",
+                circuit.consecutive_failures,
+                circuit.retry_in_secs.unwrap_or_default(),
+                circuit
+                    .last_failure
+                    .as_deref()
+                    .map(|failure| format!(" (last: {})", failure))
+                    .unwrap_or_default()
+            ),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_probes_report_configuration_problems() {
+        assert_eq!(jwt_probe(None).status, ProbeStatus::Failed);
+        assert_eq!(
+            jwt_probe(Some("default_secret")).status,
+            ProbeStatus::Failed
+        );
+        assert_eq!(jwt_probe(Some("short")).status, ProbeStatus::Warning);
+        let configured = jwt_probe(Some(&"s".repeat(MIN_JWT_SECRET_BYTES)));
+        assert_eq!(configured.status, ProbeStatus::Ok);
+        assert!(!configured.detail.contains("sss"));
+
+        let url = "http://localhost:4000/v1";
+        assert_eq!(
+            backend_probe(url, Ok(Duration::from_millis(120))).status,
+            ProbeStatus::Ok
+        );
+        assert_eq!(
+            backend_probe(url, Ok(Duration::from_secs(5))).status,
+            ProbeStatus::Warning
+        );
+        assert_eq!(
+            backend_probe(url, Err("connection refused".to_string())).status,
+            ProbeStatus::Failed
+        );
+
+        let open = CircuitSnapshot {
+            state: CircuitState::Open,
+            consecutive_failures: 5,
+            failure_threshold: 5,
+            retry_in_secs: Some(12),
+            last_failure: Some("Network error: connection refused".to_string()),
+        };
+        let report = SystemHealthReport {
+            probes: vec![jwt_probe(Some("short")), circuit_probe(&open)],
+            metrics: None,
+            metrics_error: None,
+            checked_at: Utc::now(),
+        };
+        assert_eq!(report.overall(), ProbeStatus::Failed);
+        assert!(report.probes[1].detail.contains("retrying in 12s"));
+    }
+}
@@ -0,0 +1,139 @@
+// Last good dashboard figures
+// When a backend call fails, a dashboard section shows the last figures it got,
+// marked stale with their age, rather than an empty page or invented numbers.
+// Figures live in this process only, so a restart forgets them.
+
+use std::sync::Mutex;
+
+use chrono::{DateTime, Utc};
+
+use crate::api::dashboard_client::ResponseMeta;
+use crate::pages::dashboard::SectionState;
+
+struct Kept<T> {
+    data: T,
+    meta: Option<ResponseMeta>,
+    fetched_at: DateTime<Utc>,
+}
+
+/// The most recent successful result of one dashboard fetch
+pub struct LastGood<T> {
+    kept: Mutex<Option<Kept<T>>>,
+}
+
+impl<T> Default for LastGood<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> LastGood<T> {
+    pub const fn new() -> Self {
+        Self {
+            kept: Mutex::new(None),
+        }
+    }
+}
+
+impl<T: Clone> LastGood<T> {
+    /// Remember a successful fetch, or fall back to the last one after a failure
+    pub fn settle(&self, fetched: Result<(T, Option<ResponseMeta>), String>) -> SectionState<T> {
+        self.settle_at(fetched, Utc::now())
+    }
+
+    fn settle_at(
+        &self,
+        fetched: Result<(T, Option<ResponseMeta>), String>,
+        now: DateTime<Utc>,
+    ) -> SectionState<T> {
+        let mut kept = self.kept.lock().unwrap_or_else(|e| e.into_inner());
+        match fetched {
+            Ok((data, meta)) => {
+                *kept = Some(Kept {
+                    data: data.clone(),
+                    meta: meta.clone(),
+                    fetched_at: now,
+                });
+                SectionState::Loaded { data, meta }
+            }
+            Err(reason) => match kept.as_ref() {
+                Some(last) => {
+                    // Cached figures were already old when they arrived
+                    let held = (now - last.fetched_at).num_seconds().max(0) as u64;
+                    let age_at_fetch = last.meta.as_ref().map_or(0, |meta| meta.cache_age);
+                    SectionState::Stale {
+                        data: last.data.clone(),
+                        meta: last.meta.clone(),
+                        age_secs: held + u64::from(age_at_fetch),
+                        reason,
+                    }
+                }
+                None => SectionState::Unavailable { reason },
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn meta(cache_age: u32) -> Option<ResponseMeta> {
+        Some(ResponseMeta {
+            cached: cache_age > 0,
+            cache_age,
+            data_source: "cached".to_string(),
+        })
+    }
+
+    #[test]
+    fn test_failures_fall_back_to_the_last_good_figures_with_their_age() {
+        let last_good = LastGood::new();
+        let start = Utc::now();
+
+        assert!(matches!(
+            last_good.settle_at(Err("timed out".to_string()), start),
+            SectionState::Unavailable { reason } if reason == "timed out"
+        ));
+
+        assert!(matches!(
+            last_good.settle_at(Ok((150u64, meta(30))), start),
+            SectionState::Loaded {
+                data: 150,
+                meta: Some(_)
+            }
+        ));
+
+        let later = start + chrono::Duration::minutes(5);
+        match last_good.settle_at(Err("502 Bad Gateway".to_string()), later) {
+            SectionState::Stale {
+                data,
+                meta,
+                age_secs,
+                reason,
+            } => {
+                assert_eq!(data, 150);
+                assert_eq!(meta.unwrap().data_source, "cached");
+                assert_eq!(age_secs, 5 * 60 + 30);
+                assert_eq!(reason, "502 Bad Gateway");
+            }
+            other => panic!("expected stale figures, got {:?}", other),
+        }
+
+        assert!(matches!(
+            last_good.settle_at(Ok((160, None)), later),
+            SectionState::Loaded {
+                data: 160,
+                meta: None
+            }
+        ));
+        assert!(matches!(
+            last_good.settle_at(Err("down".to_string()), later),
+            SectionState::Stale {
+                data: 160,
+                age_secs: 0,
+                ..
+            }
+        ));
+    }
+}
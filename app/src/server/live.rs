@@ -0,0 +1,383 @@
+// Live metrics stream
+// One aggregator per server reads live updates from the backend's own stream when
+// it has one, and polls the dashboard endpoints when it doesn't. Browsers subscribe
+// over SSE (`/api/live`) or WebSocket (`/api/live/ws`) and share its updates.
+
+use std::sync::{Arc, OnceLock, RwLock};
+use std::time::{Duration, Instant};
+
+use axum::{
+    extract::ws::{Message, WebSocket, WebSocketUpgrade},
+    http::{HeaderMap, StatusCode},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Response,
+    },
+};
+use futures_util::{stream, StreamExt};
+use tokio::sync::{
+    broadcast::{self, error::RecvError},
+    Notify,
+};
+
+use crate::api::client::get_dashboard_client;
+use crate::api::dashboard_client::{
+    DashboardApiClient, DashboardOverviewResponse, LiveMetrics, LiveMetricsUpdate,
+    OperationalMetricsResponse,
+};
+use crate::api::ApiError;
+
+/// How long to poll before trying the backend's stream again
+const UPSTREAM_RETRY: Duration = Duration::from_secs(300);
+
+/// Updates a slow subscriber may fall behind by before it skips ahead
+const CHANNEL_CAPACITY: usize = 64;
+
+/// Fans live updates out to every subscriber and remembers the latest one
+pub struct LiveHub {
+    sender: broadcast::Sender<LiveMetricsUpdate>,
+    latest: RwLock<Option<LiveMetricsUpdate>>,
+    /// Wakes an idle aggregator when the first subscriber arrives
+    subscribed: Notify,
+}
+
+impl Default for LiveHub {
+    fn default() -> Self {
+        Self {
+            sender: broadcast::channel(CHANNEL_CAPACITY).0,
+            latest: RwLock::new(None),
+            subscribed: Notify::new(),
+        }
+    }
+}
+
+static LIVE_HUB: OnceLock<Arc<LiveHub>> = OnceLock::new();
+
+/// The server-wide hub the live endpoints subscribe to
+pub fn live_hub() -> Arc<LiveHub> {
+    LIVE_HUB.get_or_init(Default::default).clone()
+}
+
+impl LiveHub {
+    pub fn publish(&self, update: LiveMetricsUpdate) {
+        *self
+            .latest
+            .write()
+            .unwrap_or_else(std::sync::PoisonError::into_inner) = Some(update.clone());
+        // Nobody listening is fine; the update is still kept as the latest
+        let _ = self.sender.send(update);
+    }
+
+    pub fn latest(&self) -> Option<LiveMetricsUpdate> {
+        self.latest
+            .read()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .clone()
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<LiveMetricsUpdate> {
+        let receiver = self.sender.subscribe();
+        self.subscribed.notify_one();
+        receiver
+    }
+
+    fn has_subscribers(&self) -> bool {
+        self.sender.receiver_count() > 0
+    }
+
+    /// Keep the hub fed from the backend. Polling only happens while someone is
+    /// subscribed; `LIVE_METRICS_TOKEN` authenticates the server to the backend.
+    pub fn spawn_aggregator(self: &Arc<Self>, poll_interval: Duration) {
+        let hub = self.clone();
+        tokio::spawn(async move {
+            let client = get_dashboard_client();
+            let token = std::env::var("LIVE_METRICS_TOKEN").ok();
+            loop {
+                match stream_from_backend(&hub, client, token.as_deref()).await {
+                    Ok(()) => {
+                        tracing::info!("Live: backend stream ended; polling until it returns")
+                    }
+                    Err(ApiError::NotFound) => tracing::info!(
+                        "Live: backend can't push updates; polling every {}s",
+                        poll_interval.as_secs()
+                    ),
+                    Err(e) => tracing::warn!("Live: backend stream unavailable ({}); polling", e),
+                }
+                hub.poll_for(client, token.as_deref(), poll_interval, UPSTREAM_RETRY)
+                    .await;
+            }
+        });
+    }
+
+    async fn poll_for(
+        &self,
+        client: &DashboardApiClient,
+        token: Option<&str>,
+        poll_interval: Duration,
+        period: Duration,
+    ) {
+        let until = Instant::now() + period;
+        while Instant::now() < until {
+            if self.has_subscribers() {
+                match poll_live_update(client, token).await {
+                    Ok(update) => self.publish(update),
+                    Err(e) => tracing::warn!("Live: failed to poll metrics: {}", e),
+                }
+                tokio::time::sleep(poll_interval).await;
+            } else {
+                // Idle until someone subscribes, so nothing is fetched for nobody
+                let _ = tokio::time::timeout(
+                    until.saturating_duration_since(Instant::now()),
+                    self.subscribed.notified(),
+                )
+                .await;
+            }
+        }
+    }
+}
+
+/// Relay the backend's own stream into the hub until it ends
+async fn stream_from_backend(
+    hub: &LiveHub,
+    client: &DashboardApiClient,
+    token: Option<&str>,
+) -> Result<(), ApiError> {
+    let mut response = client.open_live_stream(token).await?;
+    tracing::info!("Live: relaying the backend's live stream");
+    let mut parser = SseParser::default();
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| ApiError::NetworkError(e.to_string()))?
+    {
+        for data in parser.push(&chunk) {
+            match serde_json::from_str::<LiveMetricsUpdate>(&data) {
+                Ok(update) => hub.publish(update),
+                Err(e) => tracing::warn!("Live: skipping unreadable update: {}", e),
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Build one update from the dashboard endpoints, for backends that can't push
+pub async fn poll_live_update(
+    client: &DashboardApiClient,
+    token: Option<&str>,
+) -> Result<LiveMetricsUpdate, ApiError> {
+    let (overview, operations) = tokio::join!(
+        client.get_overview_with_auth(token),
+        client.get_operational_metrics_with_auth(token),
+    );
+    let overview = overview?;
+    if !overview.success {
+        return Err(ApiError::ServerError(
+            overview
+                .message
+                .unwrap_or_else(|| "Dashboard overview unavailable".to_string()),
+        ));
+    }
+    // Load is a nice-to-have; the rest of the update stands without it
+    let operations = operations.ok().filter(|response| response.success);
+    Ok(live_update_from(
+        &overview.data,
+        operations.as_ref().map(|response| &response.data),
+        chrono::Utc::now(),
+    ))
+}
+
+pub fn live_update_from(
+    overview: &DashboardOverviewResponse,
+    operations: Option<&OperationalMetricsResponse>,
+    now: chrono::DateTime<chrono::Utc>,
+) -> LiveMetricsUpdate {
+    let alerts = &overview.alerts.critical_alerts;
+    LiveMetricsUpdate {
+        timestamp: now.to_rfc3339(),
+        metrics: LiveMetrics {
+            active_users: overview.quick_stats.active_sessions_now,
+            transactions_in_progress: overview.summary.transaction_count.pending,
+            system_load: operations
+                .map(|operations| operations.resources.server.cpu_usage)
+                .unwrap_or_default(),
+            error_rate: overview.alerts.error_rate,
+            response_time: overview.alerts.avg_response_time as f64,
+        },
+        alerts: (!alerts.is_empty()).then(|| alerts.clone()),
+        update_type: "metrics-update".to_string(),
+    }
+}
+
+/// Splits a `text/event-stream` body into event data, across chunk boundaries
+#[derive(Debug, Default)]
+struct SseParser {
+    buffer: Vec<u8>,
+    data: Vec<String>,
+}
+
+impl SseParser {
+    fn push(&mut self, bytes: &[u8]) -> Vec<String> {
+        self.buffer.extend_from_slice(bytes);
+        let mut events = Vec::new();
+        while let Some(end) = self.buffer.iter().position(|byte| *byte == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=end).collect();
+            let line = String::from_utf8_lossy(&line);
+            let line = line.trim_end_matches(['\n', '\r']);
+            if line.is_empty() {
+                if !self.data.is_empty() {
+                    events.push(self.data.join("\n"));
+                    self.data.clear();
+                }
+            } else if let Some(value) = line.strip_prefix("data:") {
+                self.data
+                    .push(value.strip_prefix(' ').unwrap_or(value).to_string());
+            }
+            // Comments, `event:`, `id:` and `retry:` lines carry nothing we use
+        }
+        events
+    }
+}
+
+fn is_authenticated(headers: &HeaderMap) -> bool {
+    crate::api::client::auth_token_from_headers(headers)
+        .and_then(|token| crate::api::client::user_from_token(&token).ok())
+        .is_some()
+}
+
+/// Every update as it arrives, starting with the latest one
+fn updates(hub: &LiveHub) -> impl futures_util::Stream<Item = LiveMetricsUpdate> {
+    let receiver = hub.subscribe();
+    let latest = hub.latest();
+    let live = stream::unfold(receiver, |mut receiver| async move {
+        loop {
+            match receiver.recv().await {
+                Ok(update) => return Some((update, receiver)),
+                // A slow client skips what it missed rather than falling further behind
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    });
+    stream::iter(latest).chain(live)
+}
+
+/// `GET /api/live`: live updates as server-sent events, one JSON
+/// `LiveMetricsUpdate` per event
+pub async fn live_sse(headers: HeaderMap) -> Response {
+    if !is_authenticated(&headers) {
+        return (StatusCode::UNAUTHORIZED, "Authentication required").into_response();
+    }
+    let events = updates(&live_hub()).map(|update| Event::default().json_data(update));
+    Sse::new(events)
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
+
+/// `GET /api/live/ws`: the same updates over a WebSocket, as JSON text messages
+pub async fn live_ws(ws: WebSocketUpgrade, headers: HeaderMap) -> Response {
+    if !is_authenticated(&headers) {
+        return (StatusCode::UNAUTHORIZED, "Authentication required").into_response();
+    }
+    ws.on_upgrade(forward_updates)
+}
+
+async fn forward_updates(mut socket: WebSocket) {
+    let mut updates = Box::pin(updates(&live_hub()));
+    loop {
+        tokio::select! {
+            update = updates.next() => {
+                let Some(update) = update else { break };
+                let Ok(text) = serde_json::to_string(&update) else { continue };
+                if socket.send(Message::Text(text.into())).await.is_err() {
+                    break;
+                }
+            }
+            incoming = socket.recv() => match incoming {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                // Clients have nothing to say; pings are answered by axum
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sse_events_survive_chunk_boundaries() {
+        let mut parser = SseParser::default();
+        assert!(parser.push(b": keep-alive\n\nda").is_empty());
+        assert!(parser.push(b"ta: {\"a\":\n").is_empty());
+        assert_eq!(
+            parser.push(b"data: 1}\r\n\r\nevent: x\n"),
+            vec!["{\"a\":\n1}"]
+        );
+        assert_eq!(parser.push(b"data:2\n\n"), vec!["2"]);
+    }
+
+    #[test]
+    fn test_polled_update_is_built_from_overview_and_operations() {
+        let overview: DashboardOverviewResponse = serde_json::from_value(serde_json::json!({
+            "summary": {
+                "totalMembers": 10,
+                "activeMembersToday": 4,
+                "activeChamas": 2,
+                "totalChamas": 3,
+                "totalVolume": { "amount": 1000.0, "currency": "KES", "period": "30d" },
+                "transactionCount": { "total": 9, "successful": 6, "failed": 1, "pending": 2 }
+            },
+            "trends": { "memberGrowth": [], "volumeTrend": [], "transactionTrend": [], "chamaGrowth": [] },
+            "alerts": {
+                "systemHealth": "warning",
+                "errorRate": 1.5,
+                "avgResponseTime": 240,
+                "criticalAlerts": [
+                    { "id": "a1", "severity": "critical", "message": "fmcd down", "timestamp": "2026-10-18T10:00:00Z" }
+                ]
+            },
+            "quickStats": { "todayTransactions": 5, "todayVolume": 50.0, "activeSessionsNow": 7, "newMembersToday": 1 }
+        }))
+        .unwrap();
+
+        let update = live_update_from(&overview, None, chrono::Utc::now());
+        assert_eq!(update.update_type, "metrics-update");
+        assert_eq!(update.metrics.active_users, 7);
+        assert_eq!(update.metrics.transactions_in_progress, 2);
+        assert_eq!(update.metrics.response_time, 240.0);
+        assert_eq!(update.metrics.system_load, 0.0);
+        assert_eq!(update.alerts.map(|alerts| alerts.len()), Some(1));
+    }
+
+    #[tokio::test]
+    async fn test_hub_replays_latest_then_streams() {
+        let hub = LiveHub::default();
+        let update = |users| LiveMetricsUpdate {
+            timestamp: "2026-10-18T10:00:00Z".to_string(),
+            metrics: LiveMetrics {
+                active_users: users,
+                transactions_in_progress: 0,
+                system_load: 0.0,
+                error_rate: 0.0,
+                response_time: 0.0,
+            },
+            alerts: None,
+            update_type: "metrics-update".to_string(),
+        };
+        hub.publish(update(1));
+        assert!(!hub.has_subscribers());
+
+        let mut stream = Box::pin(updates(&hub));
+        assert!(hub.has_subscribers());
+        hub.publish(update(2));
+        let seen: Vec<u64> = stream
+            .by_ref()
+            .take(2)
+            .map(|update| update.metrics.active_users)
+            .collect()
+            .await;
+        assert_eq!(seen, vec![1, 2]);
+        assert_eq!(hub.latest().map(|u| u.metrics.active_users), Some(2));
+    }
+}
@@ -1,3 +1,16 @@
 pub mod config;
+#[cfg(feature = "ssr")]
+pub mod export;
+#[cfg(feature = "ssr")]
+pub mod export_formats;
+pub mod health;
+#[cfg(feature = "ssr")]
+pub mod last_good;
+#[cfg(feature = "ssr")]
+pub mod live;
+#[cfg(feature = "ssr")]
+pub mod pdf;
+#[cfg(feature = "ssr")]
+pub mod statements;
 
 pub use config::*;
@@ -0,0 +1,236 @@
+// Minimal PDF writer for server-rendered documents
+// Lays out lines of text on A4 pages using the standard Type 1 fonts, which every
+// PDF viewer provides, so no font files or external renderer are needed
+
+const PAGE_WIDTH: f32 = 595.0;
+const PAGE_HEIGHT: f32 = 842.0;
+const MARGIN: f32 = 48.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PdfFont {
+    Regular,
+    Bold,
+    /// Fixed width, for columns that need to line up
+    Mono,
+}
+
+impl PdfFont {
+    fn resource(&self) -> &'static str {
+        match self {
+            PdfFont::Regular => "F1",
+            PdfFont::Bold => "F2",
+            PdfFont::Mono => "F3",
+        }
+    }
+}
+
+/// A text document that flows onto new pages as it fills up
+pub struct PdfDocument {
+    pages: Vec<String>,
+    cursor: f32,
+}
+
+impl Default for PdfDocument {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PdfDocument {
+    pub fn new() -> Self {
+        Self {
+            pages: vec![String::new()],
+            cursor: PAGE_HEIGHT - MARGIN,
+        }
+    }
+
+    /// Add a line of text, starting a new page if it doesn't fit
+    pub fn line(&mut self, text: &str, font: PdfFont, size: f32) -> &mut Self {
+        let height = size * 1.4;
+        if self.cursor - height < MARGIN {
+            self.pages.push(String::new());
+            self.cursor = PAGE_HEIGHT - MARGIN;
+        }
+        self.cursor -= height;
+
+        let page = self.pages.last_mut().expect("document always has a page");
+        page.push_str(&format!(
+            "BT /{} {} Tf {} {} Td ({}) Tj ET\n",
+            font.resource(),
+            size,
+            MARGIN,
+            self.cursor,
+            escape(text)
+        ));
+        self
+    }
+
+    /// Add vertical space
+    pub fn gap(&mut self, points: f32) -> &mut Self {
+        self.cursor -= points;
+        self
+    }
+
+    pub fn page_count(&self) -> usize {
+        self.pages.len()
+    }
+
+    /// Serialize the document, numbering pages in the footer
+    pub fn finish(&self) -> Vec<u8> {
+        let fonts = ["Helvetica", "Helvetica-Bold", "Courier"];
+        // Objects: catalog, page tree, fonts, then a page and its content per page
+        let first_page = 3 + fonts.len();
+        let page_ids: Vec<usize> = (0..self.pages.len()).map(|i| first_page + 2 * i).collect();
+
+        let mut objects: Vec<String> = Vec::new();
+        objects.push("<< /Type /Catalog /Pages 2 0 R >>".to_string());
+        objects.push(format!(
+            "<< /Type /Pages /Kids [{}] /Count {} >>",
+            page_ids
+                .iter()
+                .map(|id| format!("{} 0 R", id))
+                .collect::<Vec<_>>()
+                .join(" "),
+            page_ids.len()
+        ));
+        for font in fonts {
+            objects.push(format!(
+                "<< /Type /Font /Subtype /Type1 /BaseFont /{} /Encoding /WinAnsiEncoding >>",
+                font
+            ));
+        }
+        let font_resources = (0..fonts.len())
+            .map(|i| format!("/F{} {} 0 R", i + 1, 3 + i))
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        for (index, (content, page_id)) in self.pages.iter().zip(&page_ids).enumerate() {
+            let footer = format!(
+                "BT /F1 8 Tf {} {} Td (Page {} of {}) Tj ET\n",
+                MARGIN,
+                MARGIN / 2.0,
+                index + 1,
+                self.pages.len()
+            );
+            let stream = format!("{}{}", content, footer);
+            objects.push(format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] \
+                 /Resources << /Font << {} >> >> /Contents {} 0 R >>",
+                PAGE_WIDTH,
+                PAGE_HEIGHT,
+                font_resources,
+                page_id + 1
+            ));
+            objects.push(format!(
+                "<< /Length {} >>\nstream\n{}endstream",
+                stream.len(),
+                stream
+            ));
+        }
+
+        let mut out = Vec::from(&b"%PDF-1.4\n"[..]);
+        let mut offsets = Vec::with_capacity(objects.len());
+        for (i, object) in objects.iter().enumerate() {
+            offsets.push(out.len());
+            out.extend_from_slice(format!("{} 0 obj\n{}\nendobj\n", i + 1, object).as_bytes());
+        }
+
+        let xref = out.len();
+        out.extend_from_slice(format!("xref\n0 {}\n", objects.len() + 1).as_bytes());
+        out.extend_from_slice(b"0000000000 65535 f \n");
+        for offset in offsets {
+            out.extend_from_slice(format!("{:010} 00000 n \n", offset).as_bytes());
+        }
+        out.extend_from_slice(
+            format!(
+                "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+                objects.len() + 1,
+                xref
+            )
+            .as_bytes(),
+        );
+        out
+    }
+}
+
+/// Characters WinAnsiEncoding places at 0x80..=0x9F, with NUL at unused codes
+const WIN_ANSI_HIGH: &str = "€\0‚ƒ„…†‡ˆ‰Š‹Œ\0Ž\0\0‘’“”•–—˜™š›œ\0žŸ";
+
+/// The WinAnsiEncoding byte for a character, if the standard fonts can show it
+fn win_ansi(ch: char) -> Option<u8> {
+    match ch {
+        ' '..='~' | '\u{a0}'..='\u{ff}' => u8::try_from(ch).ok(),
+        '\0' => None,
+        _ => WIN_ANSI_HIGH
+            .chars()
+            .position(|high| high == ch)
+            .map(|i| 0x80 + i as u8),
+    }
+}
+
+/// Escape PDF string delimiters and write characters outside printable ASCII as
+/// octal WinAnsi codes; characters the encoding lacks are replaced
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match win_ansi(ch) {
+            Some(b'\\' | b'(' | b')') => {
+                escaped.push('\\');
+                escaped.push(ch);
+            }
+            Some(code @ b' '..=b'~') => escaped.push(char::from(code)),
+            Some(code) => escaped.push_str(&format!("\\{:03o}", code)),
+            None => escaped.push('?'),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_document_structure_and_pagination() {
+        let mut doc = PdfDocument::new();
+        doc.line("Statement (draft)", PdfFont::Bold, 16.0);
+        for i in 0..120 {
+            doc.line(&format!("Row {}", i), PdfFont::Mono, 9.0);
+        }
+        assert!(doc.page_count() > 1);
+
+        let bytes = doc.finish();
+        let text = String::from_utf8(bytes.clone()).unwrap();
+        assert!(text.starts_with("%PDF-1.4\n"));
+        assert!(text.ends_with("%%EOF\n"));
+        assert!(text.contains("(Statement \\(draft\\)) Tj"));
+        assert!(text.contains(&format!("/Count {}", doc.page_count())));
+
+        // Every xref entry points at the start of its object
+        let xref_at: usize = text
+            .rsplit("startxref\n")
+            .next()
+            .and_then(|tail| tail.lines().next())
+            .and_then(|n| n.parse().ok())
+            .unwrap();
+        let entries: Vec<&str> = text[xref_at..].lines().skip(3).collect();
+        for (i, entry) in entries
+            .iter()
+            .take_while(|l| l.ends_with(" n "))
+            .enumerate()
+        {
+            let offset: usize = entry[..10].parse().unwrap();
+            assert!(text[offset..].starts_with(&format!("{} 0 obj", i + 1)));
+        }
+    }
+
+    #[test]
+    fn test_escape_uses_win_ansi_codes() {
+        assert_eq!(escape("a (b) \\"), "a \\(b\\) \\\\");
+        assert_eq!(escape("Jan · Feb"), "Jan \\267 Feb");
+        assert_eq!(escape("é €5 – ok"), "\\351 \\2005 \\226 ok");
+        assert_eq!(escape("✓ 日"), "? ?");
+        // C1 controls share the 0x80..=0x9F codes but are not WinAnsi characters
+        assert_eq!(escape("\u{80}\0"), "??");
+    }
+}
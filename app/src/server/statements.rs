@@ -0,0 +1,280 @@
+// Member statement downloads
+// CSV and PDF statements are served from a plain route so browsers can save them
+// directly; the on-screen statement is rendered by the member detail page
+
+use axum::{
+    extract::{Path, Query},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+};
+use chrono::{DateTime, Duration, NaiveDate, NaiveTime, Utc};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use super::pdf::{PdfDocument, PdfFont};
+use crate::api::{
+    backends::statements_api,
+    config::ApiConfig,
+    traits::statements::{MemberStatement, StatementSection},
+};
+
+#[derive(Debug, Deserialize)]
+pub struct StatementQuery {
+    pub from: String,
+    pub to: String,
+    /// `csv` or `pdf`
+    pub format: String,
+}
+
+/// Turn inclusive `YYYY-MM-DD` dates into the `[from, to)` range statements cover
+pub fn parse_statement_period(
+    from: &str,
+    to: &str,
+) -> Result<(DateTime<Utc>, DateTime<Utc>), String> {
+    let parse = |value: &str, label: &str| {
+        NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d")
+            .map(|date| date.and_time(NaiveTime::MIN).and_utc())
+            .map_err(|_| format!("{} must be a date", label))
+    };
+    Ok((
+        parse(from, "Start date")?,
+        parse(to, "End date")? + Duration::days(1),
+    ))
+}
+
+/// Render a statement as a paginated PDF
+pub fn render_statement_pdf(statement: &MemberStatement) -> Vec<u8> {
+    let mut doc = PdfDocument::new();
+    doc.line("Bitsacco Member Statement", PdfFont::Bold, 16.0)
+        .line(
+            &format!("Member: {}", statement.user_id),
+            PdfFont::Regular,
+            10.0,
+        )
+        .line(
+            &format!(
+                "Period: {} to {}",
+                statement.from.format("%Y-%m-%d"),
+                statement.last_day().format("%Y-%m-%d")
+            ),
+            PdfFont::Regular,
+            10.0,
+        )
+        .line(
+            &format!(
+                "Generated: {}",
+                statement.generated_at.format("%Y-%m-%d %H:%M UTC")
+            ),
+            PdfFont::Regular,
+            10.0,
+        );
+
+    if statement.sections.is_empty() {
+        doc.gap(12.0).line(
+            "No accounts on record for this member.",
+            PdfFont::Regular,
+            10.0,
+        );
+    }
+    for section in &statement.sections {
+        render_section(&mut doc, section);
+    }
+    doc.finish()
+}
+
+fn render_section(doc: &mut PdfDocument, section: &StatementSection) {
+    let currency = section.currency;
+    let row = |date: &str, description: &str, amount: &str, balance: &str| {
+        format!(
+            "{:<10}  {:<44}  {:>18}  {:>18}",
+            date,
+            truncate(description, 44),
+            amount,
+            balance
+        )
+    };
+
+    doc.gap(14.0)
+        .line(
+            &format!("{} ({})", section.title, currency.as_str()),
+            PdfFont::Bold,
+            12.0,
+        )
+        .line(
+            &row("Date", "Description", "Amount", "Balance"),
+            PdfFont::Mono,
+            8.0,
+        )
+        .line(
+            &row(
+                "",
+                "Opening balance",
+                "",
+                &currency.format(section.opening_balance),
+            ),
+            PdfFont::Mono,
+            8.0,
+        );
+    for line in &section.lines {
+        doc.line(
+            &row(
+                &line.date.format("%Y-%m-%d").to_string(),
+                &line.description,
+                &currency.format(line.amount),
+                &currency.format(line.balance),
+            ),
+            PdfFont::Mono,
+            8.0,
+        );
+    }
+    doc.line(
+        &row(
+            "",
+            "Closing balance",
+            "",
+            &currency.format(section.closing_balance),
+        ),
+        PdfFont::Mono,
+        8.0,
+    )
+    .line(
+        &format!(
+            "Money in {} / money out {}",
+            currency.format(section.total_in()),
+            currency.format(section.total_out())
+        ),
+        PdfFont::Regular,
+        8.0,
+    );
+}
+
+fn truncate(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        text.to_string()
+    } else {
+        let mut cut: String = text.chars().take(width - 3).collect();
+        cut.push_str("...");
+        cut
+    }
+}
+
+/// `GET /api/members/{member_id}/statement?from=YYYY-MM-DD&to=YYYY-MM-DD&format=csv|pdf`.
+/// Admins can download any member's statement; members only their own.
+pub async fn member_statement_download(
+    Path(member_id): Path<Uuid>,
+    Query(query): Query<StatementQuery>,
+    headers: HeaderMap,
+) -> Response {
+    let Some(user) = crate::api::client::auth_token_from_headers(&headers)
+        .and_then(|token| crate::api::client::user_from_token(&token).ok())
+    else {
+        return (StatusCode::UNAUTHORIZED, "Authentication required").into_response();
+    };
+    if !user.is_admin() && user.id != member_id {
+        return (
+            StatusCode::FORBIDDEN,
+            "You can only download your own statement",
+        )
+            .into_response();
+    }
+
+    let (from, to) = match parse_statement_period(&query.from, &query.to) {
+        Ok(period) => period,
+        Err(message) => return (StatusCode::BAD_REQUEST, message).into_response(),
+    };
+    let statement = match statements_api(&ApiConfig::from_env()) {
+        Ok(api) => api.get_member_statement(member_id, from, to).await,
+        Err(e) => Err(e),
+    };
+    let statement = match statement {
+        Ok(statement) => statement,
+        Err(e) => return (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    };
+
+    let (content_type, extension, body) = match query.format.as_str() {
+        "csv" => (
+            "text/csv; charset=utf-8",
+            "csv",
+            statement.to_csv().into_bytes(),
+        ),
+        "pdf" => ("application/pdf", "pdf", render_statement_pdf(&statement)),
+        other => {
+            return (
+                StatusCode::BAD_REQUEST,
+                format!("Unknown statement format '{}'", other),
+            )
+                .into_response()
+        }
+    };
+
+    (
+        [
+            (header::CONTENT_TYPE, content_type.to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!(
+                    "attachment; filename=\"{}.{}\"",
+                    statement.file_stem(),
+                    extension
+                ),
+            ),
+        ],
+        body,
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::traits::{ledger::LedgerCurrency, statements::StatementLine};
+    use rust_decimal::Decimal;
+
+    #[test]
+    fn test_period_and_amount_formatting() {
+        let (from, to) = parse_statement_period("2024-01-01", "2024-12-31").unwrap();
+        assert_eq!(from.to_rfc3339(), "2024-01-01T00:00:00+00:00");
+        assert_eq!(to.to_rfc3339(), "2025-01-01T00:00:00+00:00");
+        assert!(parse_statement_period("2024-13-01", "2024-12-31").is_err());
+
+        assert_eq!(
+            LedgerCurrency::Sats.format(Decimal::from(1_234_567)),
+            "1,234,567 sats"
+        );
+        assert_eq!(
+            LedgerCurrency::Kes.format(Decimal::new(-125_050, 2)),
+            "-KES 1,250.50"
+        );
+    }
+
+    #[test]
+    fn test_statement_pdf_contains_sections() {
+        let (from, to) = parse_statement_period("2024-01-01", "2024-01-31").unwrap();
+        let statement = MemberStatement {
+            user_id: Uuid::new_v4(),
+            from,
+            to,
+            sections: vec![StatementSection {
+                title: "Wallet: Savings".to_string(),
+                currency: LedgerCurrency::Sats,
+                opening_balance: Decimal::from(1_000),
+                lines: vec![StatementLine {
+                    date: from + Duration::days(3),
+                    description: "Wallet Savings deposit".to_string(),
+                    reference_id: None,
+                    amount: Decimal::from(500),
+                    balance: Decimal::from(1_500),
+                }],
+                closing_balance: Decimal::from(1_500),
+            }],
+            generated_at: Utc::now(),
+        };
+
+        let pdf = String::from_utf8(render_statement_pdf(&statement)).unwrap();
+        assert!(pdf.starts_with("%PDF-1.4"));
+        assert!(pdf.contains("Wallet: Savings \\(SATS\\)"));
+        assert!(pdf.contains("Opening balance"));
+        assert!(pdf.contains("1,500 sats"));
+        assert!(pdf.contains("Period: 2024-01-01 to 2024-01-31"));
+    }
+}
@@ -0,0 +1,43 @@
+// Formatting helpers shared by the admin pages
+
+/// Sats with thousands separators, e.g. "1,250,000 sats"
+pub fn format_sats(sats: u64) -> String {
+    let digits = sats.to_string();
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, ch) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(ch);
+    }
+    format!("{} sats", grouped)
+}
+
+/// Counts the analytics API reports as floats, shown as whole numbers
+pub fn format_count(value: f64) -> String {
+    format!("{:.0}", value)
+}
+
+pub fn format_ms(value: f64) -> String {
+    format!("{:.0} ms", value)
+}
+
+/// Rates from the analytics API are percentages
+pub fn percent(value: f64) -> String {
+    format!("{:.1}%", value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_formatting() {
+        assert_eq!(format_sats(0), "0 sats");
+        assert_eq!(format_sats(999), "999 sats");
+        assert_eq!(format_sats(1_250_000), "1,250,000 sats");
+        assert_eq!(format_count(41.6), "42");
+        assert_eq!(format_ms(12.4), "12 ms");
+        assert_eq!(percent(97.46), "97.5%");
+    }
+}